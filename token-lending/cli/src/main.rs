@@ -1,4 +1,6 @@
 #![allow(clippy::integer_arithmetic)]
+mod output;
+
 use {
     clap::{
         crate_description, crate_name, crate_version, value_t, App, AppSettings, Arg, ArgMatches,
@@ -7,11 +9,19 @@ use {
     solana_clap_utils::{
         fee_payer::fee_payer_arg,
         input_parsers::{keypair_of, pubkey_of, value_of},
-        input_validators::{is_amount, is_keypair, is_parsable, is_pubkey, is_url},
+        input_validators::{
+            is_amount, is_amount_or_all, is_keypair, is_parsable, is_pubkey, is_url,
+        },
         keypair::signer_from_path,
     },
+    solana_cli_output::OutputFormat,
     solana_client::rpc_client::RpcClient,
-    solana_program::{native_token::lamports_to_sol, program_pack::Pack, pubkey::Pubkey},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        native_token::lamports_to_sol,
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
     solana_sdk::{
         commitment_config::CommitmentConfig,
         message::Message,
@@ -26,22 +36,59 @@ use {
     },
     spl_token_lending::{
         self,
-        instruction::{init_lending_market, init_reserve},
+        instruction::{
+            borrow_obligation_liquidity, deposit_obligation_collateral, deposit_reserve_liquidity,
+            flash_loan, init_lending_market, init_obligation, init_reserve,
+            init_reserve_with_fallback, liquidate_obligation, modify_reserve_config,
+            modify_reserve_config_with_fallback, redeem_reserve_collateral, refresh_obligation,
+            refresh_obligation_with_lending_market, refresh_reserve, refresh_reserve_with_fallback,
+            repay_obligation_liquidity, set_lending_market_owner, withdraw_obligation_collateral,
+        },
         math::WAD,
-        state::{LendingMarket, Reserve, ReserveConfig, ReserveFees},
+        state::{
+            BorrowRateCurve, BorrowRateCurvePoint, LendingMarket, Obligation, Reserve,
+            ReserveConfig, ReserveFees,
+        },
     },
     std::{borrow::Borrow, process::exit, str::FromStr},
     system_instruction::create_account,
 };
 
+use crate::output::*;
+
 struct Config {
     rpc_client: RpcClient,
     fee_payer: Box<dyn Signer>,
     lending_program_id: Pubkey,
     verbose: bool,
+    output_format: OutputFormat,
     dry_run: bool,
 }
 
+/// Reserve config values to change, the others keep their current value
+struct ReserveConfigUpdate {
+    optimal_utilization_rate: Option<u8>,
+    loan_to_value_ratio: Option<u8>,
+    liquidation_bonus: Option<u8>,
+    liquidation_threshold: Option<u8>,
+    min_borrow_rate: Option<u8>,
+    optimal_borrow_rate: Option<u8>,
+    max_borrow_rate: Option<u8>,
+    borrow_rate_curve: Option<BorrowRateCurve>,
+    borrow_fee: Option<f64>,
+    flash_loan_fee: Option<f64>,
+    host_fee_percentage: Option<u8>,
+    ui_deposit_limit: Option<f64>,
+    ui_borrow_limit: Option<f64>,
+    reserve_factor: Option<u8>,
+    max_liquidation_bonus: Option<u8>,
+    elevation_group: Option<u8>,
+    max_price_confidence_bps: Option<u16>,
+    max_price_age_slots: Option<u64>,
+    fallback_oracle_pubkey: Option<Pubkey>,
+    fallback_oracle_product_pubkey: Option<Pubkey>,
+}
+
 type Error = Box<dyn std::error::Error>;
 type CommandResult = Result<(), Error>;
 
@@ -101,6 +148,15 @@ fn main() {
                 .global(true)
                 .help("Show additional information"),
         )
+        .arg(
+            Arg::with_name("output_format")
+                .long("output")
+                .value_name("FORMAT")
+                .global(true)
+                .takes_value(true)
+                .possible_values(&["json", "json-compact"])
+                .help("Return information in specified output format"),
+        )
         .arg(
             Arg::with_name("dry_run")
                 .long("dry-run")
@@ -239,6 +295,24 @@ fn main() {
                         .default_value("5")
                         .help("Bonus a liquidator gets when repaying part of an unhealthy obligation: [0, 100]"),
                 )
+                .arg(
+                    Arg::with_name("max_liquidation_bonus")
+                        .long("max-liquidation-bonus")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER_PERCENT")
+                        .takes_value(true)
+                        .required(true)
+                        .default_value("0")
+                        .help("Bonus a liquidator gets when an obligation's borrows reach its deposits, 0 for a fixed bonus: [liquidation bonus, 100]"),
+                )
+                .arg(
+                    Arg::with_name("elevation_group")
+                        .long("elevation-group")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER")
+                        .takes_value(true)
+                        .help("Elevation group of the lending market the reserve belongs to, 0 for none"),
+                )
                 .arg(
                     Arg::with_name("liquidation_threshold")
                         .long("liquidation-threshold")
@@ -279,6 +353,16 @@ fn main() {
                         .default_value("30")
                         .help("Max borrow APY: min <= optimal <= max"),
                 )
+                .arg(
+                    Arg::with_name("borrow_rate_curve")
+                        .long("borrow-rate-curve")
+                        .validator(is_borrow_rate_curve)
+                        .value_name("UTILIZATION_BPS:RATE_BPS,...")
+                        .takes_value(true)
+                        .help("Borrow APY curve over utilization, in basis points, e.g. \
+                               0:0,8000:400,10000:3000. \
+                               Overrides the min, optimal and max borrow rates"),
+                )
                 .arg(
                     Arg::with_name("borrow_fee")
                         .long("borrow-fee")
@@ -309,64 +393,874 @@ fn main() {
                         .default_value("20")
                         .help("Amount of fee going to host account: [0, 100]"),
                 )
+                .arg(
+                    Arg::with_name("reserve_factor")
+                        .long("reserve-factor")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER_PERCENT")
+                        .takes_value(true)
+                        .required(true)
+                        .default_value("0")
+                        .help("Share of accrued borrow interest kept as protocol fees: [0, 100]"),
+                )
+                .arg(
+                    Arg::with_name("deposit_limit")
+                        .long("deposit-limit")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("Maximum total liquidity supplied to the reserve, 0 for no limit"),
+                )
+                .arg(
+                    Arg::with_name("borrow_limit")
+                        .long("borrow-limit")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("Maximum total liquidity borrowed from the reserve, 0 for no limit"),
+                )
+                .arg(
+                    Arg::with_name("max_price_confidence")
+                        .long("max-price-confidence")
+                        .validator(is_parsable::<u16>)
+                        .value_name("INTEGER_BPS")
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("Max oracle confidence interval as a fraction of the price, in basis points: [0, 10000], 0 for no limit"),
+                )
+                .arg(
+                    Arg::with_name("max_price_age")
+                        .long("max-price-age")
+                        .validator(is_parsable::<u64>)
+                        .value_name("INTEGER_SLOTS")
+                        .takes_value(true)
+                        .default_value("5")
+                        .help("Number of slots after which an oracle price is stale"),
+                )
+                .arg(
+                    Arg::with_name("fallback_oracle")
+                        .long("fallback-oracle")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .requires("fallback_oracle_product")
+                        .help("Second Pyth price account used when the primary price is rejected"),
+                )
+                .arg(
+                    Arg::with_name("fallback_oracle_product")
+                        .long("fallback-oracle-product")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .requires("fallback_oracle")
+                        .help("Pyth product account of the fallback oracle"),
+                )
         )
-        .get_matches();
-
-    let mut wallet_manager = None;
-    let config = {
-        let cli_config = if let Some(config_file) = matches.value_of("config_file") {
-            solana_cli_config::Config::load(config_file).unwrap_or_default()
-        } else {
-            solana_cli_config::Config::default()
-        };
-        let json_rpc_url = value_t!(matches, "json_rpc_url", String)
-            .unwrap_or_else(|_| cli_config.json_rpc_url.clone());
-
-        let fee_payer = signer_from_path(
-            &matches,
-            matches
-                .value_of("fee_payer")
-                .unwrap_or(&cli_config.keypair_path),
-            "fee_payer",
-            &mut wallet_manager,
+        .subcommand(
+            SubCommand::with_name("update-reserve")
+                .about("Update the configuration of a reserve")
+                // @TODO: use is_valid_signer
+                .arg(
+                    Arg::with_name("lending_market_owner")
+                        .long("market-owner")
+                        .validator(is_keypair)
+                        .value_name("KEYPAIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Owner of the lending market"),
+                )
+                .arg(
+                    Arg::with_name("reserve")
+                        .long("reserve")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Reserve address"),
+                )
+                .arg(
+                    Arg::with_name("optimal_utilization_rate")
+                        .long("optimal-utilization-rate")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER_PERCENT")
+                        .takes_value(true)
+                        .help("Optimal utilization rate: [0, 100]"),
+                )
+                .arg(
+                    Arg::with_name("loan_to_value_ratio")
+                        .long("loan-to-value-ratio")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER_PERCENT")
+                        .takes_value(true)
+                        .help("Target ratio of the value of borrows to deposits: [0, 100)"),
+                )
+                .arg(
+                    Arg::with_name("liquidation_bonus")
+                        .long("liquidation-bonus")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER_PERCENT")
+                        .takes_value(true)
+                        .help("Bonus a liquidator gets when repaying part of an unhealthy obligation: [0, 100]"),
+                )
+                .arg(
+                    Arg::with_name("liquidation_threshold")
+                        .long("liquidation-threshold")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER_PERCENT")
+                        .takes_value(true)
+                        .help("Loan to value ratio at which an obligation can be liquidated: (LTV, 100]"),
+                )
+                .arg(
+                    Arg::with_name("min_borrow_rate")
+                        .long("min-borrow-rate")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER_PERCENT")
+                        .takes_value(true)
+                        .help("Min borrow APY: min <= optimal <= max"),
+                )
+                .arg(
+                    Arg::with_name("optimal_borrow_rate")
+                        .long("optimal-borrow-rate")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER_PERCENT")
+                        .takes_value(true)
+                        .help("Optimal (utilization) borrow APY: min <= optimal <= max"),
+                )
+                .arg(
+                    Arg::with_name("max_borrow_rate")
+                        .long("max-borrow-rate")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER_PERCENT")
+                        .takes_value(true)
+                        .help("Max borrow APY: min <= optimal <= max"),
+                )
+                .arg(
+                    Arg::with_name("borrow_rate_curve")
+                        .long("borrow-rate-curve")
+                        .validator(is_borrow_rate_curve)
+                        .value_name("UTILIZATION_BPS:RATE_BPS,...")
+                        .takes_value(true)
+                        .help("Borrow APY curve over utilization, in basis points, e.g. \
+                               0:0,8000:400,10000:3000. \
+                               Overrides the min, optimal and max borrow rates"),
+                )
+                .arg(
+                    Arg::with_name("borrow_fee")
+                        .long("borrow-fee")
+                        .validator(is_parsable::<f64>)
+                        .value_name("DECIMAL_PERCENT")
+                        .takes_value(true)
+                        .help("Fee assessed on borrow, expressed as a percentage: [0, 1)"),
+                )
+                .arg(
+                    Arg::with_name("flash_loan_fee")
+                        .long("flash-loan-fee")
+                        .validator(is_parsable::<f64>)
+                        .value_name("DECIMAL_PERCENT")
+                        .takes_value(true)
+                        .help("Fee assessed for flash loans, expressed as a percentage: [0, 1)"),
+                )
+                .arg(
+                    Arg::with_name("host_fee_percentage")
+                        .long("host-fee-percentage")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER_PERCENT")
+                        .takes_value(true)
+                        .help("Amount of fee going to host account: [0, 100]"),
+                )
+                .arg(
+                    Arg::with_name("deposit_limit")
+                        .long("deposit-limit")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .help("Maximum total liquidity supplied to the reserve, 0 for no limit"),
+                )
+                .arg(
+                    Arg::with_name("borrow_limit")
+                        .long("borrow-limit")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .help("Maximum total liquidity borrowed from the reserve, 0 for no limit"),
+                )
+                .arg(
+                    Arg::with_name("reserve_factor")
+                        .long("reserve-factor")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER_PERCENT")
+                        .takes_value(true)
+                        .help("Share of accrued borrow interest kept as protocol fees: [0, 100]"),
+                )
+                .arg(
+                    Arg::with_name("max_liquidation_bonus")
+                        .long("max-liquidation-bonus")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER_PERCENT")
+                        .takes_value(true)
+                        .help("Bonus a liquidator gets when an obligation's borrows reach its deposits, 0 for a fixed bonus: [liquidation bonus, 100]"),
+                )
+                .arg(
+                    Arg::with_name("elevation_group")
+                        .long("elevation-group")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER")
+                        .takes_value(true)
+                        .help("Elevation group of the lending market the reserve belongs to, 0 for none"),
+                )
+                .arg(
+                    Arg::with_name("max_price_confidence")
+                        .long("max-price-confidence")
+                        .validator(is_parsable::<u16>)
+                        .value_name("INTEGER_BPS")
+                        .takes_value(true)
+                        .help("Max oracle confidence interval as a fraction of the price, in basis points: [0, 10000], 0 for no limit"),
+                )
+                .arg(
+                    Arg::with_name("max_price_age")
+                        .long("max-price-age")
+                        .validator(is_parsable::<u64>)
+                        .value_name("INTEGER_SLOTS")
+                        .takes_value(true)
+                        .help("Number of slots after which an oracle price is stale"),
+                )
+                .arg(
+                    Arg::with_name("fallback_oracle")
+                        .long("fallback-oracle")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .help("Second Pyth price account used when the primary price is rejected, \
+                               11111111111111111111111111111111 to remove it"),
+                )
+                .arg(
+                    Arg::with_name("fallback_oracle_product")
+                        .long("fallback-oracle-product")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .requires("fallback_oracle")
+                        .help("Pyth product account of the fallback oracle, required to set it"),
+                )
         )
-        .unwrap_or_else(|e| {
-            eprintln!("error: {}", e);
-            exit(1);
-        });
-
-        let lending_program_id = pubkey_of(&matches, "lending_program_id").unwrap();
-        let verbose = matches.is_present("verbose");
-        let dry_run = matches.is_present("dry_run");
-
-        Config {
-            rpc_client: RpcClient::new_with_commitment(json_rpc_url, CommitmentConfig::confirmed()),
-            fee_payer,
-            lending_program_id,
-            verbose,
-            dry_run,
-        }
-    };
-
-    let _ = match matches.subcommand() {
-        ("create-market", Some(arg_matches)) => {
-            let lending_market_owner = pubkey_of(arg_matches, "lending_market_owner").unwrap();
-            let quote_currency = quote_currency_of(arg_matches, "quote_currency").unwrap();
-            let oracle_program_id = pubkey_of(arg_matches, "oracle_program_id").unwrap();
-            command_create_lending_market(
-                &config,
-                lending_market_owner,
-                quote_currency,
-                oracle_program_id,
-            )
-        }
-        ("add-reserve", Some(arg_matches)) => {
-            let lending_market_owner_keypair =
-                keypair_of(arg_matches, "lending_market_owner").unwrap();
-            let source_liquidity_owner_keypair =
-                keypair_of(arg_matches, "source_liquidity_owner").unwrap();
-            let lending_market_pubkey = pubkey_of(arg_matches, "lending_market").unwrap();
-            let source_liquidity_pubkey = pubkey_of(arg_matches, "source_liquidity").unwrap();
+        .subcommand(
+            SubCommand::with_name("set-market-owner")
+                .about("Transfer ownership of a lending market")
+                // @TODO: use is_valid_signer
+                .arg(
+                    Arg::with_name("lending_market_owner")
+                        .long("market-owner")
+                        .validator(is_keypair)
+                        .value_name("KEYPAIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Owner of the lending market"),
+                )
+                .arg(
+                    Arg::with_name("lending_market")
+                        .long("market")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Lending market address"),
+                )
+                .arg(
+                    Arg::with_name("new_owner")
+                        .long("new-owner")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("New owner of the lending market"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("show-market")
+                .about("Show the state of a lending market")
+                .arg(
+                    Arg::with_name("lending_market")
+                        .long("market")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Lending market address"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("show-reserve")
+                .about("Show the state and configuration of a reserve")
+                .arg(
+                    Arg::with_name("reserve")
+                        .long("reserve")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Reserve address"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("show-obligation")
+                .about("Show the deposits, borrows and health of an obligation")
+                .arg(
+                    Arg::with_name("obligation")
+                        .long("obligation")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Obligation address"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("deposit")
+                .about("Deposit liquidity into a reserve in exchange for collateral")
+                // @TODO: use is_valid_signer
+                .arg(
+                    Arg::with_name("source_liquidity_owner")
+                        .long("source-owner")
+                        .validator(is_keypair)
+                        .value_name("KEYPAIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Owner of the SPL Token account to deposit liquidity from"),
+                )
+                .arg(
+                    Arg::with_name("reserve")
+                        .long("reserve")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Reserve address"),
+                )
+                .arg(
+                    Arg::with_name("source_liquidity")
+                        .long("source")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("SPL Token account to deposit liquidity from"),
+                )
+                .arg(
+                    Arg::with_name("destination_collateral")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("SPL Token account to receive collateral in"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of liquidity to deposit"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("redeem")
+                .about("Redeem collateral from a reserve in exchange for liquidity")
+                // @TODO: use is_valid_signer
+                .arg(
+                    Arg::with_name("source_collateral_owner")
+                        .long("source-owner")
+                        .validator(is_keypair)
+                        .value_name("KEYPAIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Owner of the SPL Token account to redeem collateral from"),
+                )
+                .arg(
+                    Arg::with_name("reserve")
+                        .long("reserve")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Reserve address"),
+                )
+                .arg(
+                    Arg::with_name("source_collateral")
+                        .long("source")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("SPL Token account to redeem collateral from"),
+                )
+                .arg(
+                    Arg::with_name("destination_liquidity")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("SPL Token account to receive liquidity in"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of collateral to redeem"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("init-obligation")
+                .about("Create an obligation to deposit collateral and borrow with")
+                // @TODO: use is_valid_signer
+                .arg(
+                    Arg::with_name("obligation_owner")
+                        .long("obligation-owner")
+                        .validator(is_keypair)
+                        .value_name("KEYPAIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Owner of the new obligation"),
+                )
+                .arg(
+                    Arg::with_name("lending_market")
+                        .long("market")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Lending market address"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("deposit-collateral")
+                .about("Deposit reserve collateral into an obligation")
+                // @TODO: use is_valid_signer
+                .arg(
+                    Arg::with_name("obligation_owner")
+                        .long("obligation-owner")
+                        .validator(is_keypair)
+                        .value_name("KEYPAIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Owner of the obligation"),
+                )
+                // @TODO: use is_valid_signer
+                .arg(
+                    Arg::with_name("source_collateral_owner")
+                        .long("source-owner")
+                        .validator(is_keypair)
+                        .value_name("KEYPAIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Owner of the SPL Token account to deposit collateral from"),
+                )
+                .arg(
+                    Arg::with_name("obligation")
+                        .long("obligation")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Obligation address"),
+                )
+                .arg(
+                    Arg::with_name("reserve")
+                        .long("reserve")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Reserve of the collateral"),
+                )
+                .arg(
+                    Arg::with_name("source_collateral")
+                        .long("source")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("SPL Token account to deposit collateral from"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of collateral to deposit"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("withdraw-collateral")
+                .about("Withdraw reserve collateral from an obligation")
+                // @TODO: use is_valid_signer
+                .arg(
+                    Arg::with_name("obligation_owner")
+                        .long("obligation-owner")
+                        .validator(is_keypair)
+                        .value_name("KEYPAIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Owner of the obligation"),
+                )
+                .arg(
+                    Arg::with_name("obligation")
+                        .long("obligation")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Obligation address"),
+                )
+                .arg(
+                    Arg::with_name("reserve")
+                        .long("reserve")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Reserve of the collateral"),
+                )
+                .arg(
+                    Arg::with_name("destination_collateral")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("SPL Token account to receive collateral in"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .validator(is_amount_or_all)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of collateral to withdraw, or ALL for as much as the obligation allows"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("borrow")
+                .about("Borrow liquidity from a reserve against an obligation")
+                // @TODO: use is_valid_signer
+                .arg(
+                    Arg::with_name("obligation_owner")
+                        .long("obligation-owner")
+                        .validator(is_keypair)
+                        .value_name("KEYPAIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Owner of the obligation"),
+                )
+                .arg(
+                    Arg::with_name("obligation")
+                        .long("obligation")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Obligation address"),
+                )
+                .arg(
+                    Arg::with_name("reserve")
+                        .long("reserve")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Reserve to borrow from"),
+                )
+                .arg(
+                    Arg::with_name("destination_liquidity")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("SPL Token account to receive liquidity in"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .validator(is_amount_or_all)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of liquidity to borrow, or ALL for the remaining borrowing power"),
+                )
+                .arg(
+                    Arg::with_name("slippage_limit")
+                        .long("slippage-limit")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .help("Minimum amount of liquidity to receive when borrowing ALL"),
+                )
+                .arg(
+                    Arg::with_name("host_fee_receiver")
+                        .long("host-fee-receiver")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .help("SPL Token account to receive the host share of the borrow fee"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("repay")
+                .about("Repay liquidity borrowed by an obligation")
+                // @TODO: use is_valid_signer
+                .arg(
+                    Arg::with_name("source_liquidity_owner")
+                        .long("source-owner")
+                        .validator(is_keypair)
+                        .value_name("KEYPAIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Owner of the SPL Token account to repay from"),
+                )
+                .arg(
+                    Arg::with_name("obligation")
+                        .long("obligation")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Obligation address"),
+                )
+                .arg(
+                    Arg::with_name("reserve")
+                        .long("reserve")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Reserve the liquidity was borrowed from"),
+                )
+                .arg(
+                    Arg::with_name("source_liquidity")
+                        .long("source")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("SPL Token account to repay from"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .validator(is_amount_or_all)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of liquidity to repay, or ALL for the whole borrow"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("liquidate")
+                .about("Repay part of an unhealthy obligation to receive its collateral at a discount")
+                // @TODO: use is_valid_signer
+                .arg(
+                    Arg::with_name("source_liquidity_owner")
+                        .long("source-owner")
+                        .validator(is_keypair)
+                        .value_name("KEYPAIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Owner of the SPL Token account to repay from"),
+                )
+                .arg(
+                    Arg::with_name("obligation")
+                        .long("obligation")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Obligation address"),
+                )
+                .arg(
+                    Arg::with_name("repay_reserve")
+                        .long("repay-reserve")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Reserve the liquidity was borrowed from"),
+                )
+                .arg(
+                    Arg::with_name("withdraw_reserve")
+                        .long("withdraw-reserve")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Reserve of the collateral to receive"),
+                )
+                .arg(
+                    Arg::with_name("source_liquidity")
+                        .long("source")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("SPL Token account to repay from"),
+                )
+                .arg(
+                    Arg::with_name("destination_collateral")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("SPL Token account to receive collateral in"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .validator(is_amount_or_all)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of liquidity to repay, or ALL for as much as the close factor allows"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("flash-loan")
+                .about("Simulate a flash loan through a flash loan receiver program")
+                // @TODO: use is_valid_signer
+                .arg(
+                    Arg::with_name("destination_liquidity_owner")
+                        .long("destination-owner")
+                        .validator(is_keypair)
+                        .value_name("KEYPAIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Owner of the destination SPL Token account, signs to return the loan"),
+                )
+                .arg(
+                    Arg::with_name("reserve")
+                        .long("reserve")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Reserve to borrow from"),
+                )
+                .arg(
+                    Arg::with_name("destination_liquidity")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("SPL Token account to receive the loan in, holding enough to pay the fee"),
+                )
+                .arg(
+                    Arg::with_name("flash_loan_receiver_program_id")
+                        .long("receiver-program")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Flash loan receiver program ID, e.g. the flash_loan_receiver example"),
+                )
+                .arg(
+                    Arg::with_name("host_fee_receiver")
+                        .long("host-fee-receiver")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .help("SPL Token account to receive the host share of the fee, defaults to the destination"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .validator(is_amount_or_all)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of liquidity to borrow, or ALL for the available liquidity"),
+                )
+        )
+        .get_matches();
+
+    let mut wallet_manager = None;
+    let config = {
+        let cli_config = if let Some(config_file) = matches.value_of("config_file") {
+            solana_cli_config::Config::load(config_file).unwrap_or_default()
+        } else {
+            solana_cli_config::Config::default()
+        };
+        let json_rpc_url = value_t!(matches, "json_rpc_url", String)
+            .unwrap_or_else(|_| cli_config.json_rpc_url.clone());
+
+        let fee_payer = signer_from_path(
+            &matches,
+            matches
+                .value_of("fee_payer")
+                .unwrap_or(&cli_config.keypair_path),
+            "fee_payer",
+            &mut wallet_manager,
+        )
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            exit(1);
+        });
+
+        let lending_program_id = pubkey_of(&matches, "lending_program_id").unwrap();
+        let verbose = matches.is_present("verbose");
+        let output_format = matches
+            .value_of("output_format")
+            .map(|value| match value {
+                "json" => OutputFormat::Json,
+                "json-compact" => OutputFormat::JsonCompact,
+                _ => unreachable!(),
+            })
+            .unwrap_or(if verbose {
+                OutputFormat::DisplayVerbose
+            } else {
+                OutputFormat::Display
+            });
+        let dry_run = matches.is_present("dry_run");
+
+        Config {
+            rpc_client: RpcClient::new_with_commitment(json_rpc_url, CommitmentConfig::confirmed()),
+            fee_payer,
+            lending_program_id,
+            verbose,
+            output_format,
+            dry_run,
+        }
+    };
+
+    let _ = match matches.subcommand() {
+        ("create-market", Some(arg_matches)) => {
+            let lending_market_owner = pubkey_of(arg_matches, "lending_market_owner").unwrap();
+            let quote_currency = quote_currency_of(arg_matches, "quote_currency").unwrap();
+            let oracle_program_id = pubkey_of(arg_matches, "oracle_program_id").unwrap();
+            command_create_lending_market(
+                &config,
+                lending_market_owner,
+                quote_currency,
+                oracle_program_id,
+            )
+        }
+        ("add-reserve", Some(arg_matches)) => {
+            let lending_market_owner_keypair =
+                keypair_of(arg_matches, "lending_market_owner").unwrap();
+            let source_liquidity_owner_keypair =
+                keypair_of(arg_matches, "source_liquidity_owner").unwrap();
+            let lending_market_pubkey = pubkey_of(arg_matches, "lending_market").unwrap();
+            let source_liquidity_pubkey = pubkey_of(arg_matches, "source_liquidity").unwrap();
             let ui_amount = value_of(arg_matches, "liquidity_amount").unwrap();
             let pyth_product_pubkey = pubkey_of(arg_matches, "pyth_product").unwrap();
             let pyth_price_pubkey = pubkey_of(arg_matches, "pyth_price").unwrap();
@@ -374,13 +1268,25 @@ fn main() {
                 value_of(arg_matches, "optimal_utilization_rate").unwrap();
             let loan_to_value_ratio = value_of(arg_matches, "loan_to_value_ratio").unwrap();
             let liquidation_bonus = value_of(arg_matches, "liquidation_bonus").unwrap();
+            let max_liquidation_bonus = value_of(arg_matches, "max_liquidation_bonus").unwrap();
+            let elevation_group = value_of(arg_matches, "elevation_group").unwrap_or_default();
             let liquidation_threshold = value_of(arg_matches, "liquidation_threshold").unwrap();
             let min_borrow_rate = value_of(arg_matches, "min_borrow_rate").unwrap();
             let optimal_borrow_rate = value_of(arg_matches, "optimal_borrow_rate").unwrap();
             let max_borrow_rate = value_of(arg_matches, "max_borrow_rate").unwrap();
+            let borrow_rate_curve = borrow_rate_curve_of(arg_matches, "borrow_rate_curve")
+                .unwrap_or(BorrowRateCurve::LEGACY);
             let borrow_fee = value_of::<f64>(arg_matches, "borrow_fee").unwrap();
             let flash_loan_fee = value_of::<f64>(arg_matches, "flash_loan_fee").unwrap();
             let host_fee_percentage = value_of(arg_matches, "host_fee_percentage").unwrap();
+            let reserve_factor = value_of(arg_matches, "reserve_factor").unwrap();
+            let ui_deposit_limit = value_of(arg_matches, "deposit_limit").unwrap();
+            let ui_borrow_limit = value_of(arg_matches, "borrow_limit").unwrap();
+            let max_price_confidence_bps = value_of(arg_matches, "max_price_confidence").unwrap();
+            let max_price_age_slots = value_of(arg_matches, "max_price_age").unwrap();
+            let fallback_oracle_pubkey =
+                pubkey_of(arg_matches, "fallback_oracle").unwrap_or_default();
+            let fallback_oracle_product_pubkey = pubkey_of(arg_matches, "fallback_oracle_product");
 
             let borrow_fee_wad = (borrow_fee * WAD as f64) as u64;
             let flash_loan_fee_wad = (flash_loan_fee * WAD as f64) as u64;
@@ -388,26 +1294,248 @@ fn main() {
             command_add_reserve(
                 &config,
                 ui_amount,
-                ReserveConfig {
-                    optimal_utilization_rate,
-                    loan_to_value_ratio,
-                    liquidation_bonus,
-                    liquidation_threshold,
-                    min_borrow_rate,
-                    optimal_borrow_rate,
-                    max_borrow_rate,
-                    fees: ReserveFees {
-                        borrow_fee_wad,
-                        flash_loan_fee_wad,
-                        host_fee_percentage,
-                    },
-                },
+                ReserveConfig {
+                    optimal_utilization_rate,
+                    loan_to_value_ratio,
+                    liquidation_bonus,
+                    liquidation_threshold,
+                    min_borrow_rate,
+                    optimal_borrow_rate,
+                    max_borrow_rate,
+                    fees: ReserveFees {
+                        borrow_fee_wad,
+                        flash_loan_fee_wad,
+                        host_fee_percentage,
+                    },
+                    borrow_rate_curve,
+                    deposit_limit: 0,
+                    borrow_limit: 0,
+                    max_price_confidence_bps,
+                    max_price_age_slots,
+                    fallback_oracle_pubkey,
+                    reserve_factor,
+                    max_liquidation_bonus,
+                    elevation_group,
+                },
+                ui_deposit_limit,
+                ui_borrow_limit,
+                source_liquidity_pubkey,
+                source_liquidity_owner_keypair,
+                lending_market_pubkey,
+                lending_market_owner_keypair,
+                pyth_product_pubkey,
+                pyth_price_pubkey,
+                fallback_oracle_product_pubkey,
+            )
+        }
+        ("update-reserve", Some(arg_matches)) => {
+            let lending_market_owner_keypair =
+                keypair_of(arg_matches, "lending_market_owner").unwrap();
+            let reserve_pubkey = pubkey_of(arg_matches, "reserve").unwrap();
+
+            command_update_reserve(
+                &config,
+                reserve_pubkey,
+                lending_market_owner_keypair,
+                ReserveConfigUpdate {
+                    optimal_utilization_rate: value_of(arg_matches, "optimal_utilization_rate"),
+                    loan_to_value_ratio: value_of(arg_matches, "loan_to_value_ratio"),
+                    liquidation_bonus: value_of(arg_matches, "liquidation_bonus"),
+                    liquidation_threshold: value_of(arg_matches, "liquidation_threshold"),
+                    min_borrow_rate: value_of(arg_matches, "min_borrow_rate"),
+                    optimal_borrow_rate: value_of(arg_matches, "optimal_borrow_rate"),
+                    max_borrow_rate: value_of(arg_matches, "max_borrow_rate"),
+                    borrow_rate_curve: borrow_rate_curve_of(arg_matches, "borrow_rate_curve"),
+                    borrow_fee: value_of(arg_matches, "borrow_fee"),
+                    flash_loan_fee: value_of(arg_matches, "flash_loan_fee"),
+                    host_fee_percentage: value_of(arg_matches, "host_fee_percentage"),
+                    ui_deposit_limit: value_of(arg_matches, "deposit_limit"),
+                    ui_borrow_limit: value_of(arg_matches, "borrow_limit"),
+                    reserve_factor: value_of(arg_matches, "reserve_factor"),
+                    max_liquidation_bonus: value_of(arg_matches, "max_liquidation_bonus"),
+                    elevation_group: value_of(arg_matches, "elevation_group"),
+                    max_price_confidence_bps: value_of(arg_matches, "max_price_confidence"),
+                    max_price_age_slots: value_of(arg_matches, "max_price_age"),
+                    fallback_oracle_pubkey: pubkey_of(arg_matches, "fallback_oracle"),
+                    fallback_oracle_product_pubkey: pubkey_of(
+                        arg_matches,
+                        "fallback_oracle_product",
+                    ),
+                },
+            )
+        }
+        ("set-market-owner", Some(arg_matches)) => {
+            let lending_market_owner_keypair =
+                keypair_of(arg_matches, "lending_market_owner").unwrap();
+            let lending_market_pubkey = pubkey_of(arg_matches, "lending_market").unwrap();
+            let new_owner = pubkey_of(arg_matches, "new_owner").unwrap();
+            command_set_lending_market_owner(
+                &config,
+                lending_market_pubkey,
+                lending_market_owner_keypair,
+                new_owner,
+            )
+        }
+        ("show-market", Some(arg_matches)) => {
+            let lending_market_pubkey = pubkey_of(arg_matches, "lending_market").unwrap();
+            command_show_lending_market(&config, lending_market_pubkey)
+        }
+        ("show-reserve", Some(arg_matches)) => {
+            let reserve_pubkey = pubkey_of(arg_matches, "reserve").unwrap();
+            command_show_reserve(&config, reserve_pubkey)
+        }
+        ("show-obligation", Some(arg_matches)) => {
+            let obligation_pubkey = pubkey_of(arg_matches, "obligation").unwrap();
+            command_show_obligation(&config, obligation_pubkey)
+        }
+        ("deposit", Some(arg_matches)) => {
+            let source_liquidity_owner_keypair =
+                keypair_of(arg_matches, "source_liquidity_owner").unwrap();
+            let reserve_pubkey = pubkey_of(arg_matches, "reserve").unwrap();
+            let source_liquidity_pubkey = pubkey_of(arg_matches, "source_liquidity").unwrap();
+            let destination_collateral_pubkey =
+                pubkey_of(arg_matches, "destination_collateral").unwrap();
+            let ui_amount = value_of(arg_matches, "amount").unwrap();
+            command_deposit_reserve_liquidity(
+                &config,
+                reserve_pubkey,
+                ui_amount,
+                source_liquidity_pubkey,
+                source_liquidity_owner_keypair,
+                destination_collateral_pubkey,
+            )
+        }
+        ("redeem", Some(arg_matches)) => {
+            let source_collateral_owner_keypair =
+                keypair_of(arg_matches, "source_collateral_owner").unwrap();
+            let reserve_pubkey = pubkey_of(arg_matches, "reserve").unwrap();
+            let source_collateral_pubkey = pubkey_of(arg_matches, "source_collateral").unwrap();
+            let destination_liquidity_pubkey =
+                pubkey_of(arg_matches, "destination_liquidity").unwrap();
+            let ui_amount = value_of(arg_matches, "amount").unwrap();
+            command_redeem_reserve_collateral(
+                &config,
+                reserve_pubkey,
+                ui_amount,
+                source_collateral_pubkey,
+                source_collateral_owner_keypair,
+                destination_liquidity_pubkey,
+            )
+        }
+        ("init-obligation", Some(arg_matches)) => {
+            let obligation_owner_keypair = keypair_of(arg_matches, "obligation_owner").unwrap();
+            let lending_market_pubkey = pubkey_of(arg_matches, "lending_market").unwrap();
+            command_init_obligation(&config, lending_market_pubkey, obligation_owner_keypair)
+        }
+        ("deposit-collateral", Some(arg_matches)) => {
+            let obligation_owner_keypair = keypair_of(arg_matches, "obligation_owner").unwrap();
+            let source_collateral_owner_keypair =
+                keypair_of(arg_matches, "source_collateral_owner").unwrap();
+            let obligation_pubkey = pubkey_of(arg_matches, "obligation").unwrap();
+            let reserve_pubkey = pubkey_of(arg_matches, "reserve").unwrap();
+            let source_collateral_pubkey = pubkey_of(arg_matches, "source_collateral").unwrap();
+            let ui_amount = value_of(arg_matches, "amount").unwrap();
+            command_deposit_obligation_collateral(
+                &config,
+                obligation_pubkey,
+                obligation_owner_keypair,
+                reserve_pubkey,
+                ui_amount,
+                source_collateral_pubkey,
+                source_collateral_owner_keypair,
+            )
+        }
+        ("withdraw-collateral", Some(arg_matches)) => {
+            let obligation_owner_keypair = keypair_of(arg_matches, "obligation_owner").unwrap();
+            let obligation_pubkey = pubkey_of(arg_matches, "obligation").unwrap();
+            let reserve_pubkey = pubkey_of(arg_matches, "reserve").unwrap();
+            let destination_collateral_pubkey =
+                pubkey_of(arg_matches, "destination_collateral").unwrap();
+            let ui_amount = amount_or_all_of(arg_matches, "amount");
+            command_withdraw_obligation_collateral(
+                &config,
+                obligation_pubkey,
+                obligation_owner_keypair,
+                reserve_pubkey,
+                ui_amount,
+                destination_collateral_pubkey,
+            )
+        }
+        ("borrow", Some(arg_matches)) => {
+            let obligation_owner_keypair = keypair_of(arg_matches, "obligation_owner").unwrap();
+            let obligation_pubkey = pubkey_of(arg_matches, "obligation").unwrap();
+            let reserve_pubkey = pubkey_of(arg_matches, "reserve").unwrap();
+            let destination_liquidity_pubkey =
+                pubkey_of(arg_matches, "destination_liquidity").unwrap();
+            let ui_amount = amount_or_all_of(arg_matches, "amount");
+            let ui_slippage_limit = value_of(arg_matches, "slippage_limit");
+            let host_fee_receiver_pubkey = pubkey_of(arg_matches, "host_fee_receiver");
+            command_borrow_obligation_liquidity(
+                &config,
+                obligation_pubkey,
+                obligation_owner_keypair,
+                reserve_pubkey,
+                ui_amount,
+                ui_slippage_limit,
+                destination_liquidity_pubkey,
+                host_fee_receiver_pubkey,
+            )
+        }
+        ("repay", Some(arg_matches)) => {
+            let source_liquidity_owner_keypair =
+                keypair_of(arg_matches, "source_liquidity_owner").unwrap();
+            let obligation_pubkey = pubkey_of(arg_matches, "obligation").unwrap();
+            let reserve_pubkey = pubkey_of(arg_matches, "reserve").unwrap();
+            let source_liquidity_pubkey = pubkey_of(arg_matches, "source_liquidity").unwrap();
+            let ui_amount = amount_or_all_of(arg_matches, "amount");
+            command_repay_obligation_liquidity(
+                &config,
+                obligation_pubkey,
+                reserve_pubkey,
+                ui_amount,
                 source_liquidity_pubkey,
                 source_liquidity_owner_keypair,
-                lending_market_pubkey,
-                lending_market_owner_keypair,
-                pyth_product_pubkey,
-                pyth_price_pubkey,
+            )
+        }
+        ("liquidate", Some(arg_matches)) => {
+            let source_liquidity_owner_keypair =
+                keypair_of(arg_matches, "source_liquidity_owner").unwrap();
+            let obligation_pubkey = pubkey_of(arg_matches, "obligation").unwrap();
+            let repay_reserve_pubkey = pubkey_of(arg_matches, "repay_reserve").unwrap();
+            let withdraw_reserve_pubkey = pubkey_of(arg_matches, "withdraw_reserve").unwrap();
+            let source_liquidity_pubkey = pubkey_of(arg_matches, "source_liquidity").unwrap();
+            let destination_collateral_pubkey =
+                pubkey_of(arg_matches, "destination_collateral").unwrap();
+            let ui_amount = amount_or_all_of(arg_matches, "amount");
+            command_liquidate_obligation(
+                &config,
+                obligation_pubkey,
+                repay_reserve_pubkey,
+                withdraw_reserve_pubkey,
+                ui_amount,
+                source_liquidity_pubkey,
+                source_liquidity_owner_keypair,
+                destination_collateral_pubkey,
+            )
+        }
+        ("flash-loan", Some(arg_matches)) => {
+            let destination_liquidity_owner_keypair =
+                keypair_of(arg_matches, "destination_liquidity_owner").unwrap();
+            let reserve_pubkey = pubkey_of(arg_matches, "reserve").unwrap();
+            let destination_liquidity_pubkey =
+                pubkey_of(arg_matches, "destination_liquidity").unwrap();
+            let flash_loan_receiver_program_id =
+                pubkey_of(arg_matches, "flash_loan_receiver_program_id").unwrap();
+            let host_fee_receiver_pubkey = pubkey_of(arg_matches, "host_fee_receiver");
+            let ui_amount = amount_or_all_of(arg_matches, "amount");
+            command_flash_loan(
+                &config,
+                reserve_pubkey,
+                ui_amount,
+                destination_liquidity_pubkey,
+                destination_liquidity_owner_keypair,
+                flash_loan_receiver_program_id,
+                host_fee_receiver_pubkey,
             )
         }
         _ => unreachable!(),
@@ -427,9 +1555,12 @@ fn command_create_lending_market(
     oracle_program_id: Pubkey,
 ) -> CommandResult {
     let lending_market_keypair = Keypair::new();
-    println!(
-        "Creating lending market {}",
-        lending_market_keypair.pubkey()
+    println_display(
+        config,
+        format!(
+            "Creating lending market {}",
+            lending_market_keypair.pubkey()
+        ),
     );
 
     let lending_market_balance = config
@@ -477,13 +1608,16 @@ fn command_create_lending_market(
 fn command_add_reserve(
     config: &Config,
     ui_amount: f64,
-    reserve_config: ReserveConfig,
+    mut reserve_config: ReserveConfig,
+    ui_deposit_limit: f64,
+    ui_borrow_limit: f64,
     source_liquidity_pubkey: Pubkey,
     source_liquidity_owner_keypair: Keypair,
     lending_market_pubkey: Pubkey,
     lending_market_owner_keypair: Keypair,
     pyth_product_pubkey: Pubkey,
     pyth_price_pubkey: Pubkey,
+    fallback_oracle_product_pubkey: Option<Pubkey>,
 ) -> CommandResult {
     let source_liquidity_account = config.rpc_client.get_account(&source_liquidity_pubkey)?;
     let source_liquidity = Token::unpack_from_slice(source_liquidity_account.data.borrow())?;
@@ -492,6 +1626,10 @@ fn command_add_reserve(
     let source_liquidity_mint =
         Mint::unpack_from_slice(source_liquidity_mint_account.data.borrow())?;
     let liquidity_amount = ui_amount_to_amount(ui_amount, source_liquidity_mint.decimals);
+    reserve_config.deposit_limit =
+        ui_amount_to_amount(ui_deposit_limit, source_liquidity_mint.decimals);
+    reserve_config.borrow_limit =
+        ui_amount_to_amount(ui_borrow_limit, source_liquidity_mint.decimals);
 
     let reserve_keypair = Keypair::new();
     let collateral_mint_keypair = Keypair::new();
@@ -501,31 +1639,52 @@ fn command_add_reserve(
     let user_collateral_keypair = Keypair::new();
     let user_transfer_authority_keypair = Keypair::new();
 
-    println!("Adding reserve {}", reserve_keypair.pubkey());
+    println_display(
+        config,
+        format!("Adding reserve {}", reserve_keypair.pubkey()),
+    );
     if config.verbose {
-        println!(
-            "Adding collateral mint {}",
-            collateral_mint_keypair.pubkey()
+        println_display(
+            config,
+            format!(
+                "Adding collateral mint {}",
+                collateral_mint_keypair.pubkey()
+            ),
         );
-        println!(
-            "Adding collateral supply {}",
-            collateral_supply_keypair.pubkey()
+        println_display(
+            config,
+            format!(
+                "Adding collateral supply {}",
+                collateral_supply_keypair.pubkey()
+            ),
         );
-        println!(
-            "Adding liquidity supply {}",
-            liquidity_supply_keypair.pubkey()
+        println_display(
+            config,
+            format!(
+                "Adding liquidity supply {}",
+                liquidity_supply_keypair.pubkey()
+            ),
         );
-        println!(
-            "Adding liquidity fee receiver {}",
-            liquidity_fee_receiver_keypair.pubkey()
+        println_display(
+            config,
+            format!(
+                "Adding liquidity fee receiver {}",
+                liquidity_fee_receiver_keypair.pubkey()
+            ),
         );
-        println!(
-            "Adding user collateral {}",
-            user_collateral_keypair.pubkey()
+        println_display(
+            config,
+            format!(
+                "Adding user collateral {}",
+                user_collateral_keypair.pubkey()
+            ),
         );
-        println!(
-            "Adding user transfer authority {}",
-            user_transfer_authority_keypair.pubkey()
+        println_display(
+            config,
+            format!(
+                "Adding user transfer authority {}",
+                user_transfer_authority_keypair.pubkey()
+            ),
         );
     }
 
@@ -550,144 +1709,766 @@ fn command_add_reserve(
         + liquidity_supply_balance
         + liquidity_fee_receiver_balance;
 
-    let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
-    let message_1 = Message::new_with_blockhash(
+    let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
+    let message_1 = Message::new_with_blockhash(
+        &[
+            create_account(
+                &config.fee_payer.pubkey(),
+                &reserve_keypair.pubkey(),
+                reserve_balance,
+                Reserve::LEN as u64,
+                &config.lending_program_id,
+            ),
+            create_account(
+                &config.fee_payer.pubkey(),
+                &collateral_mint_keypair.pubkey(),
+                collateral_mint_balance,
+                Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            create_account(
+                &config.fee_payer.pubkey(),
+                &collateral_supply_keypair.pubkey(),
+                collateral_supply_balance,
+                Token::LEN as u64,
+                &spl_token::id(),
+            ),
+            create_account(
+                &config.fee_payer.pubkey(),
+                &user_collateral_keypair.pubkey(),
+                user_collateral_balance,
+                Token::LEN as u64,
+                &spl_token::id(),
+            ),
+        ],
+        Some(&config.fee_payer.pubkey()),
+        &recent_blockhash,
+    );
+
+    let message_2 = Message::new_with_blockhash(
+        &[
+            create_account(
+                &config.fee_payer.pubkey(),
+                &liquidity_supply_keypair.pubkey(),
+                liquidity_supply_balance,
+                Token::LEN as u64,
+                &spl_token::id(),
+            ),
+            create_account(
+                &config.fee_payer.pubkey(),
+                &liquidity_fee_receiver_keypair.pubkey(),
+                liquidity_fee_receiver_balance,
+                Token::LEN as u64,
+                &spl_token::id(),
+            ),
+        ],
+        Some(&config.fee_payer.pubkey()),
+        &recent_blockhash,
+    );
+
+    let init_reserve_instruction = match fallback_oracle_product_pubkey {
+        Some(fallback_oracle_product_pubkey) => init_reserve_with_fallback(
+            config.lending_program_id,
+            liquidity_amount,
+            reserve_config,
+            source_liquidity_pubkey,
+            user_collateral_keypair.pubkey(),
+            reserve_keypair.pubkey(),
+            source_liquidity.mint,
+            liquidity_supply_keypair.pubkey(),
+            liquidity_fee_receiver_keypair.pubkey(),
+            collateral_mint_keypair.pubkey(),
+            collateral_supply_keypair.pubkey(),
+            pyth_product_pubkey,
+            pyth_price_pubkey,
+            lending_market_pubkey,
+            lending_market_owner_keypair.pubkey(),
+            user_transfer_authority_keypair.pubkey(),
+            fallback_oracle_product_pubkey,
+        ),
+        None => init_reserve(
+            config.lending_program_id,
+            liquidity_amount,
+            reserve_config,
+            source_liquidity_pubkey,
+            user_collateral_keypair.pubkey(),
+            reserve_keypair.pubkey(),
+            source_liquidity.mint,
+            liquidity_supply_keypair.pubkey(),
+            liquidity_fee_receiver_keypair.pubkey(),
+            collateral_mint_keypair.pubkey(),
+            collateral_supply_keypair.pubkey(),
+            pyth_product_pubkey,
+            pyth_price_pubkey,
+            lending_market_pubkey,
+            lending_market_owner_keypair.pubkey(),
+            user_transfer_authority_keypair.pubkey(),
+        ),
+    };
+
+    let message_3 = Message::new_with_blockhash(
+        &[
+            approve(
+                &spl_token::id(),
+                &source_liquidity_pubkey,
+                &user_transfer_authority_keypair.pubkey(),
+                &source_liquidity_owner_keypair.pubkey(),
+                &[],
+                liquidity_amount,
+            )
+            .unwrap(),
+            init_reserve_instruction,
+            revoke(
+                &spl_token::id(),
+                &source_liquidity_pubkey,
+                &source_liquidity_owner_keypair.pubkey(),
+                &[],
+            )
+            .unwrap(),
+        ],
+        Some(&config.fee_payer.pubkey()),
+        &recent_blockhash,
+    );
+    check_fee_payer_balance(
+        config,
+        total_balance
+            + config.rpc_client.get_fee_for_message(&message_1)?
+            + config.rpc_client.get_fee_for_message(&message_2)?
+            + config.rpc_client.get_fee_for_message(&message_3)?,
+    )?;
+
+    let transaction_1 = Transaction::new(
+        &vec![
+            config.fee_payer.as_ref(),
+            &reserve_keypair,
+            &collateral_mint_keypair,
+            &collateral_supply_keypair,
+            &user_collateral_keypair,
+        ],
+        message_1,
+        recent_blockhash,
+    );
+    send_transaction(config, transaction_1)?;
+    let transaction_2 = Transaction::new(
+        &vec![
+            config.fee_payer.as_ref(),
+            &liquidity_supply_keypair,
+            &liquidity_fee_receiver_keypair,
+        ],
+        message_2,
+        recent_blockhash,
+    );
+    send_transaction(config, transaction_2)?;
+    let transaction_3 = Transaction::new(
+        &vec![
+            config.fee_payer.as_ref(),
+            &source_liquidity_owner_keypair,
+            &lending_market_owner_keypair,
+            &user_transfer_authority_keypair,
+        ],
+        message_3,
+        recent_blockhash,
+    );
+    send_transaction(config, transaction_3)?;
+    Ok(())
+}
+
+fn command_update_reserve(
+    config: &Config,
+    reserve_pubkey: Pubkey,
+    lending_market_owner_keypair: Keypair,
+    update: ReserveConfigUpdate,
+) -> CommandResult {
+    let reserve = get_reserve(config, &reserve_pubkey)?;
+    let decimals = reserve.liquidity.mint_decimals;
+
+    let mut reserve_config = reserve.config;
+    if let Some(optimal_utilization_rate) = update.optimal_utilization_rate {
+        reserve_config.optimal_utilization_rate = optimal_utilization_rate;
+    }
+    if let Some(loan_to_value_ratio) = update.loan_to_value_ratio {
+        reserve_config.loan_to_value_ratio = loan_to_value_ratio;
+    }
+    if let Some(liquidation_bonus) = update.liquidation_bonus {
+        reserve_config.liquidation_bonus = liquidation_bonus;
+    }
+    if let Some(liquidation_threshold) = update.liquidation_threshold {
+        reserve_config.liquidation_threshold = liquidation_threshold;
+    }
+    if let Some(min_borrow_rate) = update.min_borrow_rate {
+        reserve_config.min_borrow_rate = min_borrow_rate;
+    }
+    if let Some(optimal_borrow_rate) = update.optimal_borrow_rate {
+        reserve_config.optimal_borrow_rate = optimal_borrow_rate;
+    }
+    if let Some(max_borrow_rate) = update.max_borrow_rate {
+        reserve_config.max_borrow_rate = max_borrow_rate;
+    }
+    if let Some(borrow_rate_curve) = update.borrow_rate_curve {
+        reserve_config.borrow_rate_curve = borrow_rate_curve;
+    }
+    if let Some(borrow_fee) = update.borrow_fee {
+        reserve_config.fees.borrow_fee_wad = (borrow_fee * WAD as f64) as u64;
+    }
+    if let Some(flash_loan_fee) = update.flash_loan_fee {
+        reserve_config.fees.flash_loan_fee_wad = (flash_loan_fee * WAD as f64) as u64;
+    }
+    if let Some(host_fee_percentage) = update.host_fee_percentage {
+        reserve_config.fees.host_fee_percentage = host_fee_percentage;
+    }
+    if let Some(ui_deposit_limit) = update.ui_deposit_limit {
+        reserve_config.deposit_limit = ui_amount_to_amount(ui_deposit_limit, decimals);
+    }
+    if let Some(ui_borrow_limit) = update.ui_borrow_limit {
+        reserve_config.borrow_limit = ui_amount_to_amount(ui_borrow_limit, decimals);
+    }
+    if let Some(reserve_factor) = update.reserve_factor {
+        reserve_config.reserve_factor = reserve_factor;
+    }
+    if let Some(max_liquidation_bonus) = update.max_liquidation_bonus {
+        reserve_config.max_liquidation_bonus = max_liquidation_bonus;
+    }
+    if let Some(elevation_group) = update.elevation_group {
+        reserve_config.elevation_group = elevation_group;
+    }
+    if let Some(max_price_confidence_bps) = update.max_price_confidence_bps {
+        reserve_config.max_price_confidence_bps = max_price_confidence_bps;
+    }
+    if let Some(max_price_age_slots) = update.max_price_age_slots {
+        reserve_config.max_price_age_slots = max_price_age_slots;
+    }
+    if let Some(fallback_oracle_pubkey) = update.fallback_oracle_pubkey {
+        reserve_config.fallback_oracle_pubkey = fallback_oracle_pubkey;
+    }
+
+    let modify_reserve_config_instruction = match reserve_config.fallback_oracle() {
+        Some(fallback_oracle_pubkey)
+            if reserve.config.fallback_oracle() != Some(fallback_oracle_pubkey) =>
+        {
+            modify_reserve_config_with_fallback(
+                config.lending_program_id,
+                reserve_config,
+                reserve_pubkey,
+                reserve.lending_market,
+                lending_market_owner_keypair.pubkey(),
+                update
+                    .fallback_oracle_product_pubkey
+                    .ok_or("--fallback-oracle-product is required to set the fallback oracle")?,
+            )
+        }
+        _ => modify_reserve_config(
+            config.lending_program_id,
+            reserve_config,
+            reserve_pubkey,
+            reserve.lending_market,
+            lending_market_owner_keypair.pubkey(),
+        ),
+    };
+
+    println_display(config, format!("Updating reserve {}", reserve_pubkey));
+
+    let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
+    let message = Message::new_with_blockhash(
+        &[modify_reserve_config_instruction],
+        Some(&config.fee_payer.pubkey()),
+        &recent_blockhash,
+    );
+
+    check_fee_payer_balance(config, config.rpc_client.get_fee_for_message(&message)?)?;
+    let transaction = Transaction::new(
+        &vec![config.fee_payer.as_ref(), &lending_market_owner_keypair],
+        message,
+        recent_blockhash,
+    );
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn command_set_lending_market_owner(
+    config: &Config,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_keypair: Keypair,
+    new_owner: Pubkey,
+) -> CommandResult {
+    println_display(
+        config,
+        format!(
+            "Setting owner of lending market {} to {}",
+            lending_market_pubkey, new_owner
+        ),
+    );
+
+    send_instructions(
+        config,
+        &[set_lending_market_owner(
+            config.lending_program_id,
+            lending_market_pubkey,
+            lending_market_owner_keypair.pubkey(),
+            new_owner,
+        )],
+        vec![&lending_market_owner_keypair],
+    )
+}
+
+fn command_show_lending_market(config: &Config, lending_market_pubkey: Pubkey) -> CommandResult {
+    let lending_market_account = config.rpc_client.get_account(&lending_market_pubkey)?;
+    let lending_market = LendingMarket::unpack(lending_market_account.data.borrow())?;
+
+    let cli_lending_market = CliLendingMarket::new(&lending_market_pubkey, &lending_market);
+    println!(
+        "{}",
+        config.output_format.formatted_string(&cli_lending_market)
+    );
+    Ok(())
+}
+
+fn command_show_reserve(config: &Config, reserve_pubkey: Pubkey) -> CommandResult {
+    let reserve = get_reserve(config, &reserve_pubkey)?;
+
+    let cli_reserve = CliReserve::new(&reserve_pubkey, &reserve)?;
+    println!("{}", config.output_format.formatted_string(&cli_reserve));
+    Ok(())
+}
+
+fn command_show_obligation(config: &Config, obligation_pubkey: Pubkey) -> CommandResult {
+    let obligation = get_obligation(config, &obligation_pubkey)?;
+
+    let reserve_pubkeys = obligation
+        .deposits
+        .iter()
+        .map(|collateral| collateral.deposit_reserve)
+        .chain(
+            obligation
+                .borrows
+                .iter()
+                .map(|liquidity| liquidity.borrow_reserve),
+        )
+        .collect::<Vec<_>>();
+    let decimals = config
+        .rpc_client
+        .get_multiple_accounts(&reserve_pubkeys)?
+        .into_iter()
+        .zip(reserve_pubkeys.iter())
+        .map(|(reserve_account, reserve_pubkey)| {
+            let reserve_account = reserve_account
+                .ok_or_else(|| format!("Reserve {} does not exist", reserve_pubkey))?;
+            let reserve = Reserve::unpack(reserve_account.data.borrow())?;
+            Ok(reserve.liquidity.mint_decimals)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let cli_obligation = CliObligation::new(&obligation_pubkey, &obligation, &decimals)?;
+    println!("{}", config.output_format.formatted_string(&cli_obligation));
+    Ok(())
+}
+
+fn command_deposit_reserve_liquidity(
+    config: &Config,
+    reserve_pubkey: Pubkey,
+    ui_amount: f64,
+    source_liquidity_pubkey: Pubkey,
+    source_liquidity_owner_keypair: Keypair,
+    destination_collateral_pubkey: Pubkey,
+) -> CommandResult {
+    let reserve = get_reserve(config, &reserve_pubkey)?;
+    let liquidity_amount = ui_amount_to_amount(ui_amount, reserve.liquidity.mint_decimals);
+
+    println_display(
+        config,
+        format!("Depositing {} into reserve {}", ui_amount, reserve_pubkey),
+    );
+
+    send_instructions(
+        config,
         &[
-            create_account(
-                &config.fee_payer.pubkey(),
-                &reserve_keypair.pubkey(),
-                reserve_balance,
-                Reserve::LEN as u64,
-                &config.lending_program_id,
-            ),
-            create_account(
-                &config.fee_payer.pubkey(),
-                &collateral_mint_keypair.pubkey(),
-                collateral_mint_balance,
-                Mint::LEN as u64,
-                &spl_token::id(),
-            ),
-            create_account(
-                &config.fee_payer.pubkey(),
-                &collateral_supply_keypair.pubkey(),
-                collateral_supply_balance,
-                Token::LEN as u64,
-                &spl_token::id(),
-            ),
-            create_account(
-                &config.fee_payer.pubkey(),
-                &user_collateral_keypair.pubkey(),
-                user_collateral_balance,
-                Token::LEN as u64,
-                &spl_token::id(),
+            refresh_reserve_instruction(config, &reserve_pubkey, &reserve),
+            deposit_reserve_liquidity(
+                config.lending_program_id,
+                liquidity_amount,
+                source_liquidity_pubkey,
+                destination_collateral_pubkey,
+                reserve_pubkey,
+                reserve.liquidity.supply_pubkey,
+                reserve.collateral.mint_pubkey,
+                reserve.lending_market,
+                source_liquidity_owner_keypair.pubkey(),
             ),
         ],
-        Some(&config.fee_payer.pubkey()),
-        &recent_blockhash,
+        vec![&source_liquidity_owner_keypair],
+    )
+}
+
+fn command_redeem_reserve_collateral(
+    config: &Config,
+    reserve_pubkey: Pubkey,
+    ui_amount: f64,
+    source_collateral_pubkey: Pubkey,
+    source_collateral_owner_keypair: Keypair,
+    destination_liquidity_pubkey: Pubkey,
+) -> CommandResult {
+    let reserve = get_reserve(config, &reserve_pubkey)?;
+    let collateral_amount = ui_amount_to_amount(ui_amount, reserve.liquidity.mint_decimals);
+
+    println_display(
+        config,
+        format!(
+            "Redeeming {} collateral from reserve {}",
+            ui_amount, reserve_pubkey
+        ),
     );
 
-    let message_2 = Message::new_with_blockhash(
+    send_instructions(
+        config,
         &[
-            create_account(
-                &config.fee_payer.pubkey(),
-                &liquidity_supply_keypair.pubkey(),
-                liquidity_supply_balance,
-                Token::LEN as u64,
-                &spl_token::id(),
-            ),
-            create_account(
-                &config.fee_payer.pubkey(),
-                &liquidity_fee_receiver_keypair.pubkey(),
-                liquidity_fee_receiver_balance,
-                Token::LEN as u64,
-                &spl_token::id(),
+            refresh_reserve_instruction(config, &reserve_pubkey, &reserve),
+            redeem_reserve_collateral(
+                config.lending_program_id,
+                collateral_amount,
+                source_collateral_pubkey,
+                destination_liquidity_pubkey,
+                reserve_pubkey,
+                reserve.collateral.mint_pubkey,
+                reserve.liquidity.supply_pubkey,
+                reserve.lending_market,
+                source_collateral_owner_keypair.pubkey(),
             ),
         ],
-        Some(&config.fee_payer.pubkey()),
-        &recent_blockhash,
+        vec![&source_collateral_owner_keypair],
+    )
+}
+
+fn command_init_obligation(
+    config: &Config,
+    lending_market_pubkey: Pubkey,
+    obligation_owner_keypair: Keypair,
+) -> CommandResult {
+    let obligation_keypair = Keypair::new();
+    println_display(
+        config,
+        format!("Creating obligation {}", obligation_keypair.pubkey()),
     );
 
-    let message_3 = Message::new_with_blockhash(
+    let obligation_balance = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(Obligation::LEN)?;
+
+    let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
+    let message = Message::new_with_blockhash(
         &[
-            approve(
-                &spl_token::id(),
-                &source_liquidity_pubkey,
-                &user_transfer_authority_keypair.pubkey(),
-                &source_liquidity_owner_keypair.pubkey(),
-                &[],
-                liquidity_amount,
-            )
-            .unwrap(),
-            init_reserve(
+            // Account for the obligation
+            create_account(
+                &config.fee_payer.pubkey(),
+                &obligation_keypair.pubkey(),
+                obligation_balance,
+                Obligation::LEN as u64,
+                &config.lending_program_id,
+            ),
+            // Initialize obligation account
+            init_obligation(
                 config.lending_program_id,
-                liquidity_amount,
-                reserve_config,
-                source_liquidity_pubkey,
-                user_collateral_keypair.pubkey(),
-                reserve_keypair.pubkey(),
-                source_liquidity.mint,
-                liquidity_supply_keypair.pubkey(),
-                liquidity_fee_receiver_keypair.pubkey(),
-                collateral_mint_keypair.pubkey(),
-                collateral_supply_keypair.pubkey(),
-                pyth_product_pubkey,
-                pyth_price_pubkey,
+                obligation_keypair.pubkey(),
                 lending_market_pubkey,
-                lending_market_owner_keypair.pubkey(),
-                user_transfer_authority_keypair.pubkey(),
+                obligation_owner_keypair.pubkey(),
             ),
-            revoke(
-                &spl_token::id(),
-                &source_liquidity_pubkey,
-                &source_liquidity_owner_keypair.pubkey(),
-                &[],
-            )
-            .unwrap(),
         ],
         Some(&config.fee_payer.pubkey()),
         &recent_blockhash,
     );
+
     check_fee_payer_balance(
         config,
-        total_balance
-            + config.rpc_client.get_fee_for_message(&message_1)?
-            + config.rpc_client.get_fee_for_message(&message_2)?
-            + config.rpc_client.get_fee_for_message(&message_3)?,
+        obligation_balance + config.rpc_client.get_fee_for_message(&message)?,
     )?;
-
-    let transaction_1 = Transaction::new(
+    let transaction = Transaction::new(
         &vec![
             config.fee_payer.as_ref(),
-            &reserve_keypair,
-            &collateral_mint_keypair,
-            &collateral_supply_keypair,
-            &user_collateral_keypair,
+            &obligation_keypair,
+            &obligation_owner_keypair,
         ],
-        message_1,
+        message,
         recent_blockhash,
     );
-    send_transaction(config, transaction_1)?;
-    let transaction_2 = Transaction::new(
-        &vec![
-            config.fee_payer.as_ref(),
-            &liquidity_supply_keypair,
-            &liquidity_fee_receiver_keypair,
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn command_deposit_obligation_collateral(
+    config: &Config,
+    obligation_pubkey: Pubkey,
+    obligation_owner_keypair: Keypair,
+    reserve_pubkey: Pubkey,
+    ui_amount: f64,
+    source_collateral_pubkey: Pubkey,
+    source_collateral_owner_keypair: Keypair,
+) -> CommandResult {
+    let reserve = get_reserve(config, &reserve_pubkey)?;
+    let collateral_amount = ui_amount_to_amount(ui_amount, reserve.liquidity.mint_decimals);
+
+    println_display(
+        config,
+        format!(
+            "Depositing {} collateral of reserve {} into obligation {}",
+            ui_amount, reserve_pubkey, obligation_pubkey
+        ),
+    );
+
+    send_instructions(
+        config,
+        &[
+            refresh_reserve_instruction(config, &reserve_pubkey, &reserve),
+            deposit_obligation_collateral(
+                config.lending_program_id,
+                collateral_amount,
+                source_collateral_pubkey,
+                reserve.collateral.supply_pubkey,
+                reserve_pubkey,
+                obligation_pubkey,
+                reserve.lending_market,
+                obligation_owner_keypair.pubkey(),
+                source_collateral_owner_keypair.pubkey(),
+            ),
         ],
-        message_2,
-        recent_blockhash,
+        vec![&obligation_owner_keypair, &source_collateral_owner_keypair],
+    )
+}
+
+fn command_withdraw_obligation_collateral(
+    config: &Config,
+    obligation_pubkey: Pubkey,
+    obligation_owner_keypair: Keypair,
+    reserve_pubkey: Pubkey,
+    ui_amount: Option<f64>,
+    destination_collateral_pubkey: Pubkey,
+) -> CommandResult {
+    let obligation = get_obligation(config, &obligation_pubkey)?;
+    let reserve = get_reserve(config, &reserve_pubkey)?;
+    let collateral_amount = amount_or_all(ui_amount, reserve.liquidity.mint_decimals);
+
+    println_display(
+        config,
+        format!(
+            "Withdrawing {} collateral of reserve {} from obligation {}",
+            ui_amount_or_all(ui_amount),
+            reserve_pubkey,
+            obligation_pubkey
+        ),
     );
-    send_transaction(config, transaction_2)?;
-    let transaction_3 = Transaction::new(
+
+    let mut instructions = refresh_obligation_instructions(
+        config,
+        &obligation_pubkey,
+        &obligation,
+        &[reserve_pubkey],
+    )?;
+    instructions.push(withdraw_obligation_collateral(
+        config.lending_program_id,
+        collateral_amount,
+        reserve.collateral.supply_pubkey,
+        destination_collateral_pubkey,
+        reserve_pubkey,
+        obligation_pubkey,
+        reserve.lending_market,
+        obligation_owner_keypair.pubkey(),
+    ));
+    send_instructions(config, &instructions, vec![&obligation_owner_keypair])
+}
+
+#[allow(clippy::too_many_arguments)]
+fn command_borrow_obligation_liquidity(
+    config: &Config,
+    obligation_pubkey: Pubkey,
+    obligation_owner_keypair: Keypair,
+    reserve_pubkey: Pubkey,
+    ui_amount: Option<f64>,
+    ui_slippage_limit: Option<f64>,
+    destination_liquidity_pubkey: Pubkey,
+    host_fee_receiver_pubkey: Option<Pubkey>,
+) -> CommandResult {
+    let obligation = get_obligation(config, &obligation_pubkey)?;
+    let reserve = get_reserve(config, &reserve_pubkey)?;
+    let decimals = reserve.liquidity.mint_decimals;
+    let liquidity_amount = amount_or_all(ui_amount, decimals);
+    let slippage_limit =
+        ui_slippage_limit.map(|ui_slippage_limit| ui_amount_to_amount(ui_slippage_limit, decimals));
+
+    println_display(
+        config,
+        format!(
+            "Borrowing {} from reserve {} against obligation {}",
+            ui_amount_or_all(ui_amount),
+            reserve_pubkey,
+            obligation_pubkey
+        ),
+    );
+
+    let mut instructions = refresh_obligation_instructions(
+        config,
+        &obligation_pubkey,
+        &obligation,
+        &[reserve_pubkey],
+    )?;
+    instructions.push(borrow_obligation_liquidity(
+        config.lending_program_id,
+        liquidity_amount,
+        slippage_limit,
+        reserve.liquidity.supply_pubkey,
+        destination_liquidity_pubkey,
+        reserve_pubkey,
+        reserve.liquidity.fee_receiver,
+        obligation_pubkey,
+        reserve.lending_market,
+        obligation_owner_keypair.pubkey(),
+        host_fee_receiver_pubkey,
+    ));
+    send_instructions(config, &instructions, vec![&obligation_owner_keypair])
+}
+
+fn command_repay_obligation_liquidity(
+    config: &Config,
+    obligation_pubkey: Pubkey,
+    reserve_pubkey: Pubkey,
+    ui_amount: Option<f64>,
+    source_liquidity_pubkey: Pubkey,
+    source_liquidity_owner_keypair: Keypair,
+) -> CommandResult {
+    let obligation = get_obligation(config, &obligation_pubkey)?;
+    let reserve = get_reserve(config, &reserve_pubkey)?;
+    let liquidity_amount = amount_or_all(ui_amount, reserve.liquidity.mint_decimals);
+
+    println_display(
+        config,
+        format!(
+            "Repaying {} to reserve {} for obligation {}",
+            ui_amount_or_all(ui_amount),
+            reserve_pubkey,
+            obligation_pubkey
+        ),
+    );
+
+    let mut instructions = refresh_obligation_instructions(
+        config,
+        &obligation_pubkey,
+        &obligation,
+        &[reserve_pubkey],
+    )?;
+    instructions.push(repay_obligation_liquidity(
+        config.lending_program_id,
+        liquidity_amount,
+        source_liquidity_pubkey,
+        reserve.liquidity.supply_pubkey,
+        reserve_pubkey,
+        obligation_pubkey,
+        reserve.lending_market,
+        source_liquidity_owner_keypair.pubkey(),
+    ));
+    send_instructions(config, &instructions, vec![&source_liquidity_owner_keypair])
+}
+
+#[allow(clippy::too_many_arguments)]
+fn command_liquidate_obligation(
+    config: &Config,
+    obligation_pubkey: Pubkey,
+    repay_reserve_pubkey: Pubkey,
+    withdraw_reserve_pubkey: Pubkey,
+    ui_amount: Option<f64>,
+    source_liquidity_pubkey: Pubkey,
+    source_liquidity_owner_keypair: Keypair,
+    destination_collateral_pubkey: Pubkey,
+) -> CommandResult {
+    let obligation = get_obligation(config, &obligation_pubkey)?;
+    let repay_reserve = get_reserve(config, &repay_reserve_pubkey)?;
+    let withdraw_reserve = get_reserve(config, &withdraw_reserve_pubkey)?;
+    let liquidity_amount = amount_or_all(ui_amount, repay_reserve.liquidity.mint_decimals);
+
+    println_display(
+        config,
+        format!(
+            "Liquidating obligation {}: repaying {} to reserve {} for collateral of reserve {}",
+            obligation_pubkey,
+            ui_amount_or_all(ui_amount),
+            repay_reserve_pubkey,
+            withdraw_reserve_pubkey
+        ),
+    );
+
+    let mut instructions = refresh_obligation_instructions(
+        config,
+        &obligation_pubkey,
+        &obligation,
+        &[repay_reserve_pubkey, withdraw_reserve_pubkey],
+    )?;
+    instructions.push(liquidate_obligation(
+        config.lending_program_id,
+        liquidity_amount,
+        source_liquidity_pubkey,
+        destination_collateral_pubkey,
+        repay_reserve_pubkey,
+        repay_reserve.liquidity.supply_pubkey,
+        withdraw_reserve_pubkey,
+        withdraw_reserve.collateral.supply_pubkey,
+        obligation_pubkey,
+        obligation.lending_market,
+        source_liquidity_owner_keypair.pubkey(),
+    ));
+    send_instructions(config, &instructions, vec![&source_liquidity_owner_keypair])
+}
+
+fn command_flash_loan(
+    config: &Config,
+    reserve_pubkey: Pubkey,
+    ui_amount: Option<f64>,
+    destination_liquidity_pubkey: Pubkey,
+    destination_liquidity_owner_keypair: Keypair,
+    flash_loan_receiver_program_id: Pubkey,
+    host_fee_receiver_pubkey: Option<Pubkey>,
+) -> CommandResult {
+    let reserve = get_reserve(config, &reserve_pubkey)?;
+    let liquidity_amount = amount_or_all(ui_amount, reserve.liquidity.mint_decimals);
+
+    println_display(
+        config,
+        format!(
+            "Simulating flash loan of {} from reserve {}",
+            ui_amount_or_all(ui_amount),
+            reserve_pubkey
+        ),
+    );
+
+    let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
+    let message = Message::new_with_blockhash(
+        &[flash_loan(
+            config.lending_program_id,
+            liquidity_amount,
+            reserve.liquidity.supply_pubkey,
+            destination_liquidity_pubkey,
+            reserve_pubkey,
+            reserve.liquidity.fee_receiver,
+            host_fee_receiver_pubkey.unwrap_or(destination_liquidity_pubkey),
+            reserve.lending_market,
+            flash_loan_receiver_program_id,
+            // The receiver repays the loan out of the destination account
+            vec![AccountMeta::new_readonly(
+                destination_liquidity_owner_keypair.pubkey(),
+                true,
+            )],
+        )],
+        Some(&config.fee_payer.pubkey()),
+        &recent_blockhash,
+    );
+    let transaction = Transaction::new(
         &vec![
             config.fee_payer.as_ref(),
-            &source_liquidity_owner_keypair,
-            &lending_market_owner_keypair,
-            &user_transfer_authority_keypair,
+            &destination_liquidity_owner_keypair,
         ],
-        message_3,
+        message,
         recent_blockhash,
     );
-    send_transaction(config, transaction_3)?;
+
+    // Flash loans are only ever simulated, the receiver program is expected to be swapped out
+    let result = config.rpc_client.simulate_transaction(&transaction)?;
+    let cli_simulation = CliSimulation::from(result.value);
+    println!("{}", config.output_format.formatted_string(&cli_simulation));
     Ok(())
 }
 
@@ -708,18 +2489,137 @@ fn check_fee_payer_balance(config: &Config, required_balance: u64) -> Result<(),
     }
 }
 
+fn get_reserve(config: &Config, reserve_pubkey: &Pubkey) -> Result<Reserve, Error> {
+    let reserve_account = config.rpc_client.get_account(reserve_pubkey)?;
+    Ok(Reserve::unpack(reserve_account.data.borrow())?)
+}
+
+fn get_obligation(config: &Config, obligation_pubkey: &Pubkey) -> Result<Obligation, Error> {
+    let obligation_account = config.rpc_client.get_account(obligation_pubkey)?;
+    Ok(Obligation::unpack_account(
+        obligation_account.data.borrow(),
+    )?)
+}
+
+fn refresh_reserve_instruction(
+    config: &Config,
+    reserve_pubkey: &Pubkey,
+    reserve: &Reserve,
+) -> Instruction {
+    match reserve.config.fallback_oracle() {
+        Some(fallback_oracle_pubkey) => refresh_reserve_with_fallback(
+            config.lending_program_id,
+            *reserve_pubkey,
+            reserve.liquidity.oracle_pubkey,
+            fallback_oracle_pubkey,
+        ),
+        None => refresh_reserve(
+            config.lending_program_id,
+            *reserve_pubkey,
+            reserve.liquidity.oracle_pubkey,
+        ),
+    }
+}
+
+/// Refreshes every reserve the obligation touches plus `extra_reserve_pubkeys`, then the
+/// obligation itself
+fn refresh_obligation_instructions(
+    config: &Config,
+    obligation_pubkey: &Pubkey,
+    obligation: &Obligation,
+    extra_reserve_pubkeys: &[Pubkey],
+) -> Result<Vec<Instruction>, Error> {
+    let obligation_reserve_pubkeys = obligation
+        .deposits
+        .iter()
+        .map(|collateral| collateral.deposit_reserve)
+        .chain(
+            obligation
+                .borrows
+                .iter()
+                .map(|liquidity| liquidity.borrow_reserve),
+        )
+        .collect::<Vec<_>>();
+
+    let mut reserve_pubkeys = Vec::<Pubkey>::new();
+    for reserve_pubkey in obligation_reserve_pubkeys
+        .iter()
+        .chain(extra_reserve_pubkeys.iter())
+    {
+        if !reserve_pubkeys.contains(reserve_pubkey) {
+            reserve_pubkeys.push(*reserve_pubkey);
+        }
+    }
+
+    let mut instructions = vec![];
+    for reserve_pubkey in &reserve_pubkeys {
+        let reserve = get_reserve(config, reserve_pubkey)?;
+        instructions.push(refresh_reserve_instruction(
+            config,
+            reserve_pubkey,
+            &reserve,
+        ));
+    }
+    instructions.push(if obligation.elevation_group != 0 {
+        refresh_obligation_with_lending_market(
+            config.lending_program_id,
+            *obligation_pubkey,
+            obligation_reserve_pubkeys,
+            obligation.lending_market,
+        )
+    } else {
+        refresh_obligation(
+            config.lending_program_id,
+            *obligation_pubkey,
+            obligation_reserve_pubkeys,
+        )
+    });
+    Ok(instructions)
+}
+
+fn send_instructions(
+    config: &Config,
+    instructions: &[Instruction],
+    signers: Vec<&dyn Signer>,
+) -> CommandResult {
+    let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
+    let message = Message::new_with_blockhash(
+        instructions,
+        Some(&config.fee_payer.pubkey()),
+        &recent_blockhash,
+    );
+
+    check_fee_payer_balance(config, config.rpc_client.get_fee_for_message(&message)?)?;
+    let mut all_signers = vec![config.fee_payer.as_ref()];
+    for signer in signers {
+        if !all_signers
+            .iter()
+            .any(|existing| existing.pubkey() == signer.pubkey())
+        {
+            all_signers.push(signer);
+        }
+    }
+    let transaction = Transaction::new(&all_signers, message, recent_blockhash);
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
 fn send_transaction(
     config: &Config,
     transaction: Transaction,
 ) -> solana_client::client_error::Result<()> {
     if config.dry_run {
         let result = config.rpc_client.simulate_transaction(&transaction)?;
-        println!("Simulate result: {:?}", result);
+        let cli_simulation = CliSimulation::from(result.value);
+        println!("{}", config.output_format.formatted_string(&cli_simulation));
     } else {
         let signature = config
             .rpc_client
             .send_and_confirm_transaction_with_spinner(&transaction)?;
-        println!("Signature: {}", signature);
+        let cli_signature = CliSignature {
+            signature: signature.to_string(),
+        };
+        println!("{}", config.output_format.formatted_string(&cli_signature));
     }
     Ok(())
 }
@@ -739,3 +2639,54 @@ fn quote_currency_of(matches: &ArgMatches<'_>, name: &str) -> Option<[u8; 32]> {
         None
     }
 }
+
+fn parse_borrow_rate_curve(value: &str) -> Result<BorrowRateCurve, String> {
+    let points = value
+        .split(',')
+        .map(|point| {
+            let (utilization_rate_bps, borrow_rate_bps) = point
+                .split_once(':')
+                .ok_or_else(|| format!("Expected UTILIZATION_BPS:RATE_BPS, got {}", point))?;
+            Ok(BorrowRateCurvePoint {
+                utilization_rate_bps: utilization_rate_bps
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("Invalid utilization rate {}: {}", point, e))?,
+                borrow_rate_bps: borrow_rate_bps
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("Invalid borrow rate {}: {}", point, e))?,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let curve = BorrowRateCurve::new(&points).map_err(|e| e.to_string())?;
+    curve.validate().map_err(|e| e.to_string())?;
+    Ok(curve)
+}
+
+fn is_borrow_rate_curve(value: String) -> Result<(), String> {
+    parse_borrow_rate_curve(&value).map(|_| ())
+}
+
+fn borrow_rate_curve_of(matches: &ArgMatches<'_>, name: &str) -> Option<BorrowRateCurve> {
+    matches
+        .value_of(name)
+        .map(|value| parse_borrow_rate_curve(value).unwrap())
+}
+
+fn amount_or_all_of(matches: &ArgMatches<'_>, name: &str) -> Option<f64> {
+    match matches.value_of(name) {
+        Some("ALL") => None,
+        _ => value_of(matches, name),
+    }
+}
+
+fn amount_or_all(ui_amount: Option<f64>, decimals: u8) -> u64 {
+    ui_amount.map_or(u64::MAX, |ui_amount| {
+        ui_amount_to_amount(ui_amount, decimals)
+    })
+}
+
+fn ui_amount_or_all(ui_amount: Option<f64>) -> String {
+    ui_amount.map_or_else(|| "ALL".to_string(), |ui_amount| ui_amount.to_string())
+}
@@ -0,0 +1,538 @@
+use {
+    crate::Config,
+    serde::{Deserialize, Serialize},
+    solana_cli_output::{OutputFormat, QuietDisplay, VerboseDisplay},
+    solana_client::rpc_response::RpcSimulateTransactionResult,
+    solana_program::{program_error::ProgramError, pubkey::Pubkey},
+    spl_token::amount_to_ui_amount,
+    spl_token_lending::{
+        math::{Decimal, Rate, TryDiv, WAD},
+        state::{LendingMarket, Obligation, Reserve},
+    },
+    std::fmt::{Display, Formatter, Result},
+};
+
+pub(crate) fn println_display(config: &Config, message: String) {
+    match config.output_format {
+        OutputFormat::Display | OutputFormat::DisplayVerbose => {
+            println!("{}", message);
+        }
+        _ => {}
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliSignature {
+    pub signature: String,
+}
+
+impl Display for CliSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Signature: {}", self.signature)
+    }
+}
+
+impl QuietDisplay for CliSignature {}
+impl VerboseDisplay for CliSignature {}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliSimulation {
+    pub err: Option<String>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+impl From<RpcSimulateTransactionResult> for CliSimulation {
+    fn from(result: RpcSimulateTransactionResult) -> Self {
+        Self {
+            err: result.err.map(|err| err.to_string()),
+            logs: result.logs.unwrap_or_default(),
+            units_consumed: result.units_consumed,
+        }
+    }
+}
+
+impl Display for CliSimulation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match &self.err {
+            None => writeln!(f, "Simulation succeeded")?,
+            Some(err) => writeln!(f, "Simulation failed: {}", err)?,
+        }
+        if let Some(units_consumed) = self.units_consumed {
+            writeln!(f, "Compute units consumed: {}", units_consumed)?;
+        }
+        for log in &self.logs {
+            writeln!(f, "  {}", log)?;
+        }
+        Ok(())
+    }
+}
+
+impl QuietDisplay for CliSimulation {}
+impl VerboseDisplay for CliSimulation {}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliElevationGroup {
+    pub id: u8,
+    pub loan_to_value_ratio: u8,
+    pub liquidation_threshold: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliLendingMarket {
+    pub address: String,
+    pub owner: String,
+    pub quote_currency: String,
+    pub token_program_id: String,
+    pub oracle_program_id: String,
+    pub liquidation_close_factor: f64,
+    pub liquidation_dust_value: f64,
+    pub elevation_groups: Vec<CliElevationGroup>,
+}
+
+impl CliLendingMarket {
+    pub fn new(address: &Pubkey, lending_market: &LendingMarket) -> Self {
+        Self {
+            address: address.to_string(),
+            owner: lending_market.owner.to_string(),
+            quote_currency: quote_currency_to_string(&lending_market.quote_currency),
+            token_program_id: lending_market.token_program_id.to_string(),
+            oracle_program_id: lending_market.oracle_program_id.to_string(),
+            liquidation_close_factor: percent(lending_market.effective_liquidation_close_factor()),
+            liquidation_dust_value: to_f64(lending_market.liquidation_dust_value),
+            elevation_groups: lending_market
+                .elevation_groups
+                .iter()
+                .filter(|elevation_group| elevation_group.id != 0)
+                .map(|elevation_group| CliElevationGroup {
+                    id: elevation_group.id,
+                    loan_to_value_ratio: elevation_group.loan_to_value_ratio,
+                    liquidation_threshold: elevation_group.liquidation_threshold,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Display for CliLendingMarket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "Lending Market: {}", self.address)?;
+        writeln!(f, "Owner: {}", self.owner)?;
+        writeln!(f, "Quote Currency: {}", self.quote_currency)?;
+        writeln!(f, "Oracle Program: {}", self.oracle_program_id)?;
+        writeln!(
+            f,
+            "Liquidation Close Factor: {}%",
+            self.liquidation_close_factor
+        )?;
+        writeln!(
+            f,
+            "Liquidation Dust Value: {} {}",
+            self.liquidation_dust_value, self.quote_currency
+        )?;
+        for elevation_group in &self.elevation_groups {
+            writeln!(
+                f,
+                "Elevation Group {}: LTV {}%, Liquidation Threshold {}%",
+                elevation_group.id,
+                elevation_group.loan_to_value_ratio,
+                elevation_group.liquidation_threshold
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl QuietDisplay for CliLendingMarket {}
+impl VerboseDisplay for CliLendingMarket {
+    fn write_str(&self, w: &mut dyn std::fmt::Write) -> Result {
+        write!(w, "{}", self)?;
+        writeln!(w, "Token Program: {}", self.token_program_id)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliReserveConfig {
+    pub optimal_utilization_rate: u8,
+    pub loan_to_value_ratio: u8,
+    pub liquidation_bonus: u8,
+    pub max_liquidation_bonus: u8,
+    pub liquidation_threshold: u8,
+    pub min_borrow_rate: u8,
+    pub optimal_borrow_rate: u8,
+    pub max_borrow_rate: u8,
+    pub borrow_fee: f64,
+    pub flash_loan_fee: f64,
+    pub host_fee_percentage: u8,
+    pub reserve_factor: u8,
+    pub deposit_limit: f64,
+    pub borrow_limit: f64,
+    pub max_price_confidence_bps: u16,
+    pub max_price_age_slots: u64,
+    pub fallback_oracle: Option<String>,
+    pub elevation_group: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliReserve {
+    pub address: String,
+    pub lending_market: String,
+    pub last_update_slot: u64,
+    pub stale: bool,
+    pub liquidity_mint: String,
+    pub liquidity_mint_decimals: u8,
+    pub liquidity_supply: String,
+    pub liquidity_fee_receiver: String,
+    pub oracle: String,
+    pub market_price: f64,
+    pub available_amount: f64,
+    pub borrowed_amount: f64,
+    pub total_supply: f64,
+    pub accumulated_protocol_fees: f64,
+    pub utilization_rate: f64,
+    pub borrow_rate: f64,
+    pub collateral_mint: String,
+    pub collateral_supply: String,
+    pub collateral_mint_total_supply: f64,
+    pub collateral_exchange_rate: f64,
+    pub config: CliReserveConfig,
+}
+
+impl CliReserve {
+    pub fn new(address: &Pubkey, reserve: &Reserve) -> std::result::Result<Self, ProgramError> {
+        let decimals = reserve.liquidity.mint_decimals;
+        let config = &reserve.config;
+        Ok(Self {
+            address: address.to_string(),
+            lending_market: reserve.lending_market.to_string(),
+            last_update_slot: reserve.last_update.slot,
+            stale: reserve.last_update.stale,
+            liquidity_mint: reserve.liquidity.mint_pubkey.to_string(),
+            liquidity_mint_decimals: decimals,
+            liquidity_supply: reserve.liquidity.supply_pubkey.to_string(),
+            liquidity_fee_receiver: reserve.liquidity.fee_receiver.to_string(),
+            oracle: reserve.liquidity.oracle_pubkey.to_string(),
+            market_price: to_f64(reserve.liquidity.market_price),
+            available_amount: amount_to_ui_amount(reserve.liquidity.available_amount, decimals),
+            borrowed_amount: decimal_to_ui_amount(reserve.liquidity.borrowed_amount_wads, decimals),
+            total_supply: decimal_to_ui_amount(reserve.liquidity.total_supply()?, decimals),
+            accumulated_protocol_fees: decimal_to_ui_amount(
+                reserve.liquidity.accumulated_protocol_fees_wads,
+                decimals,
+            ),
+            utilization_rate: percent(reserve.liquidity.utilization_rate()?),
+            borrow_rate: percent(reserve.current_borrow_rate()?),
+            collateral_mint: reserve.collateral.mint_pubkey.to_string(),
+            collateral_supply: reserve.collateral.supply_pubkey.to_string(),
+            collateral_mint_total_supply: amount_to_ui_amount(
+                reserve.collateral.mint_total_supply,
+                decimals,
+            ),
+            collateral_exchange_rate: to_f64(Rate::from(reserve.collateral_exchange_rate()?)),
+            config: CliReserveConfig {
+                optimal_utilization_rate: config.optimal_utilization_rate,
+                loan_to_value_ratio: config.loan_to_value_ratio,
+                liquidation_bonus: config.liquidation_bonus,
+                max_liquidation_bonus: config.effective_max_liquidation_bonus(),
+                liquidation_threshold: config.liquidation_threshold,
+                min_borrow_rate: config.min_borrow_rate,
+                optimal_borrow_rate: config.optimal_borrow_rate,
+                max_borrow_rate: config.max_borrow_rate,
+                borrow_fee: wad_to_f64(config.fees.borrow_fee_wad),
+                flash_loan_fee: wad_to_f64(config.fees.flash_loan_fee_wad),
+                host_fee_percentage: config.fees.host_fee_percentage,
+                reserve_factor: config.reserve_factor,
+                deposit_limit: amount_to_ui_amount(config.deposit_limit, decimals),
+                borrow_limit: amount_to_ui_amount(config.borrow_limit, decimals),
+                max_price_confidence_bps: config.max_price_confidence_bps,
+                max_price_age_slots: config.effective_max_price_age_slots(),
+                fallback_oracle: if config.fallback_oracle_pubkey == Pubkey::default() {
+                    None
+                } else {
+                    Some(config.fallback_oracle_pubkey.to_string())
+                },
+                elevation_group: config.elevation_group,
+            },
+        })
+    }
+}
+
+impl Display for CliReserve {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "Reserve: {}", self.address)?;
+        writeln!(f, "Lending Market: {}", self.lending_market)?;
+        writeln!(
+            f,
+            "Last Update: slot {}{}",
+            self.last_update_slot,
+            if self.stale { " (stale)" } else { "" }
+        )?;
+        writeln!(f, "Liquidity Mint: {}", self.liquidity_mint)?;
+        writeln!(f, "Market Price: {}", self.market_price)?;
+        writeln!(f, "Total Supply: {}", self.total_supply)?;
+        writeln!(f, "Available: {}", self.available_amount)?;
+        writeln!(f, "Borrowed: {}", self.borrowed_amount)?;
+        writeln!(f, "Protocol Fees: {}", self.accumulated_protocol_fees)?;
+        writeln!(f, "Utilization Rate: {}%", self.utilization_rate)?;
+        writeln!(f, "Borrow Rate: {}%", self.borrow_rate)?;
+        writeln!(f, "Collateral Mint: {}", self.collateral_mint)?;
+        writeln!(
+            f,
+            "Collateral Exchange Rate: {} collateral per token",
+            self.collateral_exchange_rate
+        )?;
+        writeln!(
+            f,
+            "Loan to Value Ratio: {}%",
+            self.config.loan_to_value_ratio
+        )?;
+        writeln!(
+            f,
+            "Liquidation Threshold: {}%",
+            self.config.liquidation_threshold
+        )?;
+        writeln!(
+            f,
+            "Liquidation Bonus: {}% - {}%",
+            self.config.liquidation_bonus, self.config.max_liquidation_bonus
+        )?;
+        if self.config.elevation_group != 0 {
+            writeln!(f, "Elevation Group: {}", self.config.elevation_group)?;
+        }
+        Ok(())
+    }
+}
+
+impl QuietDisplay for CliReserve {}
+impl VerboseDisplay for CliReserve {
+    fn write_str(&self, w: &mut dyn std::fmt::Write) -> Result {
+        write!(w, "{}", self)?;
+        writeln!(w, "Liquidity Supply: {}", self.liquidity_supply)?;
+        writeln!(w, "Liquidity Fee Receiver: {}", self.liquidity_fee_receiver)?;
+        writeln!(w, "Oracle: {}", self.oracle)?;
+        if let Some(fallback_oracle) = &self.config.fallback_oracle {
+            writeln!(w, "Fallback Oracle: {}", fallback_oracle)?;
+        }
+        writeln!(w, "Collateral Supply: {}", self.collateral_supply)?;
+        writeln!(
+            w,
+            "Collateral Mint Supply: {}",
+            self.collateral_mint_total_supply
+        )?;
+        writeln!(
+            w,
+            "Optimal Utilization Rate: {}%",
+            self.config.optimal_utilization_rate
+        )?;
+        writeln!(
+            w,
+            "Borrow Rates: {}% / {}% / {}%",
+            self.config.min_borrow_rate,
+            self.config.optimal_borrow_rate,
+            self.config.max_borrow_rate
+        )?;
+        writeln!(w, "Borrow Fee: {}", self.config.borrow_fee)?;
+        writeln!(w, "Flash Loan Fee: {}", self.config.flash_loan_fee)?;
+        writeln!(w, "Host Fee: {}%", self.config.host_fee_percentage)?;
+        writeln!(w, "Reserve Factor: {}%", self.config.reserve_factor)?;
+        writeln!(w, "Deposit Limit: {}", self.config.deposit_limit)?;
+        writeln!(w, "Borrow Limit: {}", self.config.borrow_limit)?;
+        writeln!(
+            w,
+            "Max Price Confidence: {} bps",
+            self.config.max_price_confidence_bps
+        )?;
+        writeln!(
+            w,
+            "Max Price Age: {} slots",
+            self.config.max_price_age_slots
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliObligationCollateral {
+    pub deposit_reserve: String,
+    pub deposited_amount: f64,
+    pub market_value: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliObligationLiquidity {
+    pub borrow_reserve: String,
+    pub borrowed_amount: f64,
+    pub market_value: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliObligation {
+    pub address: String,
+    pub lending_market: String,
+    pub owner: String,
+    pub last_update_slot: u64,
+    pub stale: bool,
+    pub elevation_group: u8,
+    pub deposits: Vec<CliObligationCollateral>,
+    pub borrows: Vec<CliObligationLiquidity>,
+    pub deposited_value: f64,
+    pub borrowed_value: f64,
+    pub allowed_borrow_value: f64,
+    pub unhealthy_borrow_value: f64,
+    pub loan_to_value: Option<f64>,
+    pub health_factor: Option<f64>,
+    pub liquidatable: bool,
+}
+
+impl CliObligation {
+    /// `decimals` holds the liquidity mint decimals of each deposit reserve followed by each
+    /// borrow reserve, in obligation order
+    pub fn new(
+        address: &Pubkey,
+        obligation: &Obligation,
+        decimals: &[u8],
+    ) -> std::result::Result<Self, ProgramError> {
+        let (deposit_decimals, borrow_decimals) = decimals.split_at(obligation.deposits.len());
+        Ok(Self {
+            address: address.to_string(),
+            lending_market: obligation.lending_market.to_string(),
+            owner: obligation.owner.to_string(),
+            last_update_slot: obligation.last_update.slot,
+            stale: obligation.last_update.stale,
+            elevation_group: obligation.elevation_group,
+            deposits: obligation
+                .deposits
+                .iter()
+                .zip(deposit_decimals)
+                .map(|(collateral, decimals)| CliObligationCollateral {
+                    deposit_reserve: collateral.deposit_reserve.to_string(),
+                    deposited_amount: amount_to_ui_amount(collateral.deposited_amount, *decimals),
+                    market_value: to_f64(collateral.market_value),
+                })
+                .collect(),
+            borrows: obligation
+                .borrows
+                .iter()
+                .zip(borrow_decimals)
+                .map(|(liquidity, decimals)| CliObligationLiquidity {
+                    borrow_reserve: liquidity.borrow_reserve.to_string(),
+                    borrowed_amount: decimal_to_ui_amount(
+                        liquidity.borrowed_amount_wads,
+                        *decimals,
+                    ),
+                    market_value: to_f64(liquidity.market_value),
+                })
+                .collect(),
+            deposited_value: to_f64(obligation.deposited_value),
+            borrowed_value: to_f64(obligation.borrowed_value),
+            allowed_borrow_value: to_f64(obligation.allowed_borrow_value),
+            unhealthy_borrow_value: to_f64(obligation.unhealthy_borrow_value),
+            loan_to_value: if obligation.deposited_value == Decimal::zero() {
+                None
+            } else {
+                Some(to_f64(obligation.loan_to_value()?) * 100.0)
+            },
+            health_factor: if obligation.borrowed_value == Decimal::zero() {
+                None
+            } else {
+                Some(to_f64(
+                    obligation
+                        .unhealthy_borrow_value
+                        .try_div(obligation.borrowed_value)?,
+                ))
+            },
+            liquidatable: obligation.borrowed_value > Decimal::zero()
+                && obligation.borrowed_value >= obligation.unhealthy_borrow_value,
+        })
+    }
+}
+
+impl Display for CliObligation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "Obligation: {}", self.address)?;
+        writeln!(f, "Lending Market: {}", self.lending_market)?;
+        writeln!(f, "Owner: {}", self.owner)?;
+        writeln!(
+            f,
+            "Last Update: slot {}{}",
+            self.last_update_slot,
+            if self.stale { " (stale)" } else { "" }
+        )?;
+        if self.elevation_group != 0 {
+            writeln!(f, "Elevation Group: {}", self.elevation_group)?;
+        }
+        for deposit in &self.deposits {
+            writeln!(
+                f,
+                "Deposit: {} collateral of reserve {}, worth {}",
+                deposit.deposited_amount, deposit.deposit_reserve, deposit.market_value
+            )?;
+        }
+        for borrow in &self.borrows {
+            writeln!(
+                f,
+                "Borrow: {} liquidity of reserve {}, worth {}",
+                borrow.borrowed_amount, borrow.borrow_reserve, borrow.market_value
+            )?;
+        }
+        writeln!(f, "Deposited Value: {}", self.deposited_value)?;
+        writeln!(f, "Borrowed Value: {}", self.borrowed_value)?;
+        writeln!(f, "Allowed Borrow Value: {}", self.allowed_borrow_value)?;
+        writeln!(f, "Unhealthy Borrow Value: {}", self.unhealthy_borrow_value)?;
+        if let Some(loan_to_value) = self.loan_to_value {
+            writeln!(f, "Loan to Value: {}%", loan_to_value)?;
+        }
+        match self.health_factor {
+            Some(health_factor) => writeln!(
+                f,
+                "Health Factor: {}{}",
+                health_factor,
+                if self.liquidatable {
+                    " (liquidatable)"
+                } else {
+                    ""
+                }
+            ),
+            None => writeln!(f, "Health Factor: no borrows"),
+        }
+    }
+}
+
+impl QuietDisplay for CliObligation {}
+impl VerboseDisplay for CliObligation {}
+
+fn quote_currency_to_string(quote_currency: &[u8; 32]) -> String {
+    let end = quote_currency
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(quote_currency.len());
+    match std::str::from_utf8(&quote_currency[..end]) {
+        Ok(currency) if end < quote_currency.len() => currency.to_string(),
+        _ => Pubkey::new_from_array(*quote_currency).to_string(),
+    }
+}
+
+// Decimal and Rate only format to exact decimal strings
+fn to_f64<T: ToString>(value: T) -> f64 {
+    value.to_string().parse().unwrap_or_default()
+}
+
+fn percent<T: ToString>(rate: T) -> f64 {
+    to_f64(rate) * 100.0
+}
+
+fn wad_to_f64(wad: u64) -> f64 {
+    wad as f64 / WAD as f64
+}
+
+fn decimal_to_ui_amount(amount: Decimal, decimals: u8) -> f64 {
+    to_f64(amount) / 10_f64.powi(decimals as i32)
+}
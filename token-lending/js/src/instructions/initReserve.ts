@@ -30,7 +30,8 @@ export const initReserveInstruction = (
     lendingMarket: PublicKey,
     lendingMarketAuthority: PublicKey,
     lendingMarketOwner: PublicKey,
-    transferAuthority: PublicKey
+    transferAuthority: PublicKey,
+    fallbackPythProduct?: PublicKey
 ): TransactionInstruction => {
     const data = Buffer.alloc(DataLayout.span);
     DataLayout.encode(
@@ -61,6 +62,9 @@ export const initReserveInstruction = (
         { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
         { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
     ];
+    if (fallbackPythProduct) {
+        keys.push({ pubkey: fallbackPythProduct, isSigner: false, isWritable: false });
+    }
 
     return new TransactionInstruction({
         keys,
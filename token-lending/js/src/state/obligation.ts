@@ -16,6 +16,8 @@ export interface Obligation {
     borrowedValue: BigNumber;
     allowedBorrowValue: BigNumber;
     unhealthyBorrowValue: BigNumber;
+    /** Lending market elevation group the obligation is in, 0 if none */
+    elevationGroup: number;
 }
 
 export interface ObligationCollateral {
@@ -44,6 +46,8 @@ export interface ObligationDataFlat {
     depositsLen: number;
     borrowsLen: number;
     dataFlat: Uint8Array;
+    elevationGroup: number;
+    padding: Uint8Array;
 }
 
 /** @internal */
@@ -77,20 +81,26 @@ export const ObligationLayout = struct<ObligationDataFlat>(
         u8('depositsLen'),
         u8('borrowsLen'),
         blob(ObligationCollateralLayout.span + 9 * ObligationLiquidityLayout.span, 'dataFlat'),
+        u8('elevationGroup'),
+        blob(63, 'padding'),
     ],
     'obligation'
 );
 
 export const OBLIGATION_SIZE = ObligationLayout.span;
 
+/** Size of obligation accounts created before elevation groups were added, read with no elevation group */
+export const LEGACY_OBLIGATION_SIZE = 916;
+
 export const isObligation = (info: AccountInfo<Buffer>): boolean => {
-    return info.data.length === OBLIGATION_SIZE;
+    return info.data.length === OBLIGATION_SIZE || info.data.length === LEGACY_OBLIGATION_SIZE;
 };
 
 export const parseObligation: Parser<Obligation> = (pubkey: PublicKey, info: AccountInfo<Buffer>) => {
     if (!isObligation(info)) return;
 
-    const buffer = Buffer.from(info.data);
+    const buffer = Buffer.alloc(OBLIGATION_SIZE);
+    buffer.set(info.data);
     const {
         version,
         lastUpdate,
@@ -103,6 +113,7 @@ export const parseObligation: Parser<Obligation> = (pubkey: PublicKey, info: Acc
         depositsLen,
         borrowsLen,
         dataFlat,
+        elevationGroup,
     } = ObligationLayout.decode(buffer);
 
     if (!version) return;
@@ -127,6 +138,7 @@ export const parseObligation: Parser<Obligation> = (pubkey: PublicKey, info: Acc
         unhealthyBorrowValue,
         deposits,
         borrows,
+        elevationGroup,
     };
 
     return {
@@ -1,6 +1,6 @@
 import { AccountInfo, PublicKey } from '@solana/web3.js';
 import BigNumber from 'bignumber.js';
-import { blob, struct, u8 } from '@solana/buffer-layout';
+import { blob, seq, struct, u16, u8 } from '@solana/buffer-layout';
 import { decimal, publicKey, u64 } from '@solana/buffer-layout-utils';
 import { LastUpdate, LastUpdateLayout } from './lastUpdate';
 import { Parser } from '../util';
@@ -25,6 +25,8 @@ export interface ReserveLiquidity {
     borrowedAmountWads: BigNumber;
     cumulativeBorrowRateWads: BigNumber;
     marketPrice: BigNumber;
+    /** Share of accrued borrow interest kept as protocol fees, not yet redeemed */
+    accumulatedProtocolFeesWads: BigNumber;
 }
 
 export interface ReserveCollateral {
@@ -42,6 +44,23 @@ export interface ReserveConfig {
     optimalBorrowRate: number;
     maxBorrowRate: number;
     fees: ReserveFees;
+    borrowRateCurve: BorrowRateCurve;
+    /** Maximum total liquidity supplied to the reserve, 0 if deposits are not limited */
+    depositLimit: bigint;
+    /** Maximum total liquidity borrowed from the reserve, 0 if borrows are not limited */
+    borrowLimit: bigint;
+    /** Maximum oracle confidence interval in basis points of the price, 0 if not checked */
+    maxPriceConfidenceBps: number;
+    /** Number of slots after which an oracle price is stale, 0 for the default of 5 */
+    maxPriceAgeSlots: bigint;
+    /** Second Pyth price account used when the primary price is rejected, default pubkey if none */
+    fallbackOraclePubkey: PublicKey;
+    /** Share of accrued borrow interest kept as protocol fees, as a percentage */
+    reserveFactor: number;
+    /** Bonus paid when an obligation's borrowed value reaches its deposited value, 0 if the bonus is fixed */
+    maxLiquidationBonus: number;
+    /** Elevation group of the lending market the reserve belongs to, 0 if none */
+    elevationGroup: number;
 }
 
 export interface ReserveFees {
@@ -50,8 +69,36 @@ export interface ReserveFees {
     hostFeePercentage: number;
 }
 
+export interface BorrowRateCurve {
+    /** Number of points in use, 0 to use the min, optimal and max borrow rates */
+    len: number;
+    points: BorrowRateCurvePoint[];
+}
+
+export interface BorrowRateCurvePoint {
+    utilizationRateBps: number;
+    borrowRateBps: number;
+}
+
+export const MAX_BORROW_RATE_CURVE_POINTS = 8;
+
+/** @internal */
+export interface ReserveDataFlat {
+    version: number;
+    lastUpdate: LastUpdate;
+    lendingMarket: PublicKey;
+    liquidity: Omit<ReserveLiquidity, 'accumulatedProtocolFeesWads'>;
+    collateral: ReserveCollateral;
+    config: Omit<ReserveConfig, 'reserveFactor' | 'maxLiquidationBonus' | 'elevationGroup'>;
+    accumulatedProtocolFeesWads: BigNumber;
+    reserveFactor: number;
+    maxLiquidationBonus: number;
+    elevationGroup: number;
+    padding: Uint8Array;
+}
+
 /** @internal */
-export const ReserveLiquidityLayout = struct<ReserveLiquidity>(
+export const ReserveLiquidityLayout = struct<ReserveDataFlat['liquidity']>(
     [
         publicKey('mintPubkey'),
         u8('mintDecimals'),
@@ -78,30 +125,55 @@ export const ReserveFeesLayout = struct<ReserveFees>(
     'fees'
 );
 
+/** @internal */
+export const BorrowRateCurvePointLayout = struct<BorrowRateCurvePoint>([
+    u16('utilizationRateBps'),
+    u16('borrowRateBps'),
+]);
+
+/** @internal */
+export const BorrowRateCurveLayout = struct<BorrowRateCurve>(
+    [u8('len'), seq(BorrowRateCurvePointLayout, MAX_BORROW_RATE_CURVE_POINTS, 'points')],
+    'borrowRateCurve'
+);
+
+const ReserveConfigFields = [
+    u8('optimalUtilizationRate'),
+    u8('loanToValueRatio'),
+    u8('liquidationBonus'),
+    u8('liquidationThreshold'),
+    u8('minBorrowRate'),
+    u8('optimalBorrowRate'),
+    u8('maxBorrowRate'),
+    ReserveFeesLayout,
+    BorrowRateCurveLayout,
+    u64('depositLimit'),
+    u64('borrowLimit'),
+    u16('maxPriceConfidenceBps'),
+    u64('maxPriceAgeSlots'),
+    publicKey('fallbackOraclePubkey'),
+];
+
+/** Config fields stored after `accumulatedProtocolFeesWads` in reserve accounts */
+const ReserveConfigTailFields = [u8('reserveFactor'), u8('maxLiquidationBonus'), u8('elevationGroup')];
+
 /** @internal */
 export const ReserveConfigLayout = struct<ReserveConfig>(
-    [
-        u8('optimalUtilizationRate'),
-        u8('loanToValueRatio'),
-        u8('liquidationBonus'),
-        u8('liquidationThreshold'),
-        u8('minBorrowRate'),
-        u8('optimalBorrowRate'),
-        u8('maxBorrowRate'),
-        ReserveFeesLayout,
-    ],
+    [...ReserveConfigFields, ...ReserveConfigTailFields],
     'config'
 );
 
 /** @internal */
-export const ReserveLayout = struct<Reserve>([
+export const ReserveLayout = struct<ReserveDataFlat>([
     u8('version'),
     LastUpdateLayout,
     publicKey('lendingMarket'),
     ReserveLiquidityLayout,
     ReserveCollateralLayout,
-    ReserveConfigLayout,
-    blob(248, 'padding'),
+    struct<ReserveDataFlat['config']>(ReserveConfigFields, 'config'),
+    decimal('accumulatedProtocolFeesWads'),
+    ...ReserveConfigTailFields,
+    blob(138, 'padding'),
 ]);
 
 export const RESERVE_SIZE = ReserveLayout.span;
@@ -113,8 +185,22 @@ export const isReserve = (info: AccountInfo<Uint8Array>): boolean => {
 export const parseReserve: Parser<Reserve> = (pubkey: PublicKey, info: AccountInfo<Uint8Array>) => {
     if (!isReserve(info)) return;
 
-    const reserve = ReserveLayout.decode(info.data);
-    if (!reserve.version) return;
+    const {
+        liquidity,
+        config,
+        accumulatedProtocolFeesWads,
+        reserveFactor,
+        maxLiquidationBonus,
+        elevationGroup,
+        ...rest
+    } = ReserveLayout.decode(info.data);
+    if (!rest.version) return;
+
+    const reserve: Reserve = {
+        ...rest,
+        liquidity: { ...liquidity, accumulatedProtocolFeesWads },
+        config: { ...config, reserveFactor, maxLiquidationBonus, elevationGroup },
+    };
 
     return {
         pubkey,
@@ -0,0 +1,30 @@
+//! Error types
+
+use {
+    solana_program::{program_error::ProgramError, pubkey::Pubkey},
+    spl_token_client::client::ProgramClientError,
+    thiserror::Error,
+};
+
+/// Errors that may be returned by the liquidator
+#[derive(Error, Debug)]
+pub enum LiquidatorError {
+    /// The client failed to fetch an account or send a transaction
+    #[error("client error: {0}")]
+    Client(ProgramClientError),
+    /// Account data could not be unpacked, or lending program math failed
+    #[error("program error: {0}")]
+    Program(#[from] ProgramError),
+    /// The account does not exist
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    /// The obligation uses a reserve missing from the market snapshot
+    #[error("reserve {0} not found in the lending market")]
+    ReserveNotFound(Pubkey),
+    /// Refreshing the reserves of the obligation and liquidating it exceeds the transaction size
+    #[error("liquidation of obligation {0} does not fit in one transaction")]
+    TransactionTooLarge(Pubkey),
+}
+
+/// Result of a liquidator operation
+pub type LiquidatorResult<T> = Result<T, LiquidatorError>;
@@ -0,0 +1,290 @@
+//! Liquidation executor
+
+use {
+    crate::{
+        error::{LiquidatorError, LiquidatorResult},
+        health::{self, LiquidationPlan},
+        scanner::MarketSnapshot,
+    },
+    solana_program::{instruction::Instruction, program_pack::Pack, pubkey::Pubkey},
+    solana_sdk::{
+        message::Message, packet::PACKET_DATA_SIZE, signer::Signer, transaction::Transaction,
+    },
+    spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        instruction::create_associated_token_account_idempotent,
+    },
+    spl_token_client::client::{ProgramClient, SendTransaction},
+    spl_token_lending::{
+        instruction::{
+            liquidate_obligation, refresh_obligation, refresh_obligation_with_lending_market,
+            refresh_reserve, refresh_reserve_with_fallback,
+        },
+        math::Decimal,
+        state::{Obligation, Reserve},
+    },
+    std::sync::Arc,
+};
+
+/// Liquidates unhealthy obligations of a lending market, repaying their borrows from the
+/// payer's associated token accounts
+pub struct Liquidator<T> {
+    client: Arc<dyn ProgramClient<T>>,
+    program_id: Pubkey,
+    payer: Arc<dyn Signer>,
+    min_profit: Decimal,
+}
+
+impl<T> Liquidator<T>
+where
+    T: SendTransaction,
+{
+    /// Create a liquidator that signs and pays for its transactions with `payer`
+    pub fn new(
+        client: Arc<dyn ProgramClient<T>>,
+        program_id: Pubkey,
+        payer: Arc<dyn Signer>,
+    ) -> Self {
+        Self {
+            client,
+            program_id,
+            payer,
+            min_profit: Decimal::zero(),
+        }
+    }
+
+    /// Skip liquidations expected to gain less than `min_profit` in the lending market quote
+    /// currency
+    pub fn with_min_profit(mut self, min_profit: Decimal) -> Self {
+        self.min_profit = min_profit;
+        self
+    }
+
+    /// Liquidate every obligation of the snapshot that is unhealthy at the prices of its
+    /// reserves, returning the liquidations that were sent. Obligations that fail to
+    /// liquidate are logged and skipped.
+    pub async fn liquidate_unhealthy(
+        &self,
+        snapshot: &MarketSnapshot,
+    ) -> LiquidatorResult<Vec<(Pubkey, LiquidationPlan)>> {
+        let mut liquidations = vec![];
+        for (obligation_pubkey, obligation) in snapshot.borrowing_obligations() {
+            let obligation = match health::refresh_obligation(
+                obligation,
+                &snapshot.reserves,
+                &snapshot.lending_market,
+            ) {
+                Ok(obligation) => obligation,
+                Err(err) => {
+                    log::warn!(
+                        "Failed to refresh obligation {}: {}",
+                        obligation_pubkey,
+                        err
+                    );
+                    continue;
+                }
+            };
+            if !health::is_liquidatable(&obligation) {
+                continue;
+            }
+
+            match self
+                .liquidate(*obligation_pubkey, &obligation, snapshot)
+                .await
+            {
+                Ok(Some(plan)) => {
+                    log::info!(
+                        "Liquidated obligation {}: repaid {} to reserve {}, received {} from reserve {}",
+                        obligation_pubkey,
+                        plan.repay_amount,
+                        plan.repay_reserve,
+                        plan.withdraw_amount,
+                        plan.withdraw_reserve,
+                    );
+                    liquidations.push((*obligation_pubkey, plan));
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    log::warn!(
+                        "Failed to liquidate obligation {}: {}",
+                        obligation_pubkey,
+                        err
+                    )
+                }
+            }
+        }
+        Ok(liquidations)
+    }
+
+    /// Liquidate a refreshed obligation, repaying its largest borrow with as much of the
+    /// payer's balance as the close factor allows. Returns `None` if the payer has nothing to
+    /// repay with or the liquidation is not profitable enough.
+    pub async fn liquidate(
+        &self,
+        obligation_pubkey: Pubkey,
+        obligation: &Obligation,
+        snapshot: &MarketSnapshot,
+    ) -> LiquidatorResult<Option<LiquidationPlan>> {
+        let (repay_reserve_pubkey, withdraw_reserve_pubkey) =
+            match health::select_liquidation(obligation) {
+                Some(reserves) => reserves,
+                None => return Ok(None),
+            };
+        let find_reserve = |reserve_pubkey: &Pubkey| {
+            snapshot
+                .reserves
+                .get(reserve_pubkey)
+                .ok_or(LiquidatorError::ReserveNotFound(*reserve_pubkey))
+        };
+        let repay_reserve = find_reserve(&repay_reserve_pubkey)?;
+        let withdraw_reserve = find_reserve(&withdraw_reserve_pubkey)?;
+
+        let payer_pubkey = self.payer.pubkey();
+        let token_program_id = snapshot.lending_market.token_program_id;
+        let source_liquidity_pubkey = get_associated_token_address_with_program_id(
+            &payer_pubkey,
+            &repay_reserve.liquidity.mint_pubkey,
+            &token_program_id,
+        );
+        let destination_collateral_pubkey = get_associated_token_address_with_program_id(
+            &payer_pubkey,
+            &withdraw_reserve.collateral.mint_pubkey,
+            &token_program_id,
+        );
+
+        let balance = match self
+            .client
+            .get_account(source_liquidity_pubkey)
+            .await
+            .map_err(LiquidatorError::Client)?
+        {
+            Some(account) => spl_token::state::Account::unpack(&account.data)?.amount,
+            None => 0,
+        };
+        if balance == 0 {
+            log::warn!(
+                "Skipping obligation {}: no {} to repay with",
+                obligation_pubkey,
+                repay_reserve.liquidity.mint_pubkey
+            );
+            return Ok(None);
+        }
+
+        let plan = health::plan_liquidation(
+            obligation,
+            repay_reserve_pubkey,
+            withdraw_reserve_pubkey,
+            &snapshot.reserves,
+            &snapshot.lending_market,
+            balance,
+        )?;
+        if plan.repay_amount == 0 || plan.profit()? < self.min_profit {
+            return Ok(None);
+        }
+
+        let reserve_pubkeys = obligation
+            .deposits
+            .iter()
+            .map(|collateral| collateral.deposit_reserve)
+            .chain(
+                obligation
+                    .borrows
+                    .iter()
+                    .map(|liquidity| liquidity.borrow_reserve),
+            )
+            .collect::<Vec<_>>();
+        let mut instructions = vec![create_associated_token_account_idempotent(
+            &payer_pubkey,
+            &payer_pubkey,
+            &withdraw_reserve.collateral.mint_pubkey,
+            &token_program_id,
+        )];
+        let mut refreshed_reserve_pubkeys = vec![];
+        for reserve_pubkey in &reserve_pubkeys {
+            if refreshed_reserve_pubkeys.contains(reserve_pubkey) {
+                continue;
+            }
+            instructions.push(
+                self.refresh_reserve_instruction(reserve_pubkey, find_reserve(reserve_pubkey)?),
+            );
+            refreshed_reserve_pubkeys.push(*reserve_pubkey);
+        }
+        instructions.push(if obligation.elevation_group != 0 {
+            refresh_obligation_with_lending_market(
+                self.program_id,
+                obligation_pubkey,
+                reserve_pubkeys,
+                snapshot.lending_market_pubkey,
+            )
+        } else {
+            refresh_obligation(self.program_id, obligation_pubkey, reserve_pubkeys)
+        });
+        instructions.push(liquidate_obligation(
+            self.program_id,
+            plan.liquidity_amount,
+            source_liquidity_pubkey,
+            destination_collateral_pubkey,
+            repay_reserve_pubkey,
+            repay_reserve.liquidity.supply_pubkey,
+            withdraw_reserve_pubkey,
+            withdraw_reserve.collateral.supply_pubkey,
+            obligation_pubkey,
+            snapshot.lending_market_pubkey,
+            payer_pubkey,
+        ));
+
+        // Reserves must be refreshed in the slot of the liquidation, so obligations with too
+        // many reserves to refresh in the liquidation transaction cannot be liquidated
+        if !self.fits_in_transaction(&instructions) {
+            return Err(LiquidatorError::TransactionTooLarge(obligation_pubkey));
+        }
+        self.send_instructions(&instructions).await?;
+
+        Ok(Some(plan))
+    }
+
+    fn refresh_reserve_instruction(
+        &self,
+        reserve_pubkey: &Pubkey,
+        reserve: &Reserve,
+    ) -> Instruction {
+        match reserve.config.fallback_oracle() {
+            Some(fallback_oracle_pubkey) => refresh_reserve_with_fallback(
+                self.program_id,
+                *reserve_pubkey,
+                reserve.liquidity.oracle_pubkey,
+                fallback_oracle_pubkey,
+            ),
+            None => refresh_reserve(
+                self.program_id,
+                *reserve_pubkey,
+                reserve.liquidity.oracle_pubkey,
+            ),
+        }
+    }
+
+    fn fits_in_transaction(&self, instructions: &[Instruction]) -> bool {
+        let message = Message::new(instructions, Some(&self.payer.pubkey()));
+        let signatures_size = 1 + message.header.num_required_signatures as usize * 64;
+        signatures_size + message.serialize().len() <= PACKET_DATA_SIZE
+    }
+
+    async fn send_instructions(&self, instructions: &[Instruction]) -> LiquidatorResult<()> {
+        let recent_blockhash = self
+            .client
+            .get_latest_blockhash()
+            .await
+            .map_err(LiquidatorError::Client)?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &vec![self.payer.clone()],
+            recent_blockhash,
+        );
+        self.client
+            .send_transaction(&transaction)
+            .await
+            .map_err(LiquidatorError::Client)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+//! Obligation health calculator
+
+use {
+    crate::error::{LiquidatorError, LiquidatorResult},
+    solana_program::{program_error::ProgramError, pubkey::Pubkey},
+    spl_token_lending::{
+        error::LendingError,
+        math::{Decimal, TryDiv, TryMul, TrySub},
+        state::{CalculateLiquidationResult, LendingMarket, Obligation, Reserve},
+    },
+    std::collections::HashMap,
+};
+
+/// Revalue an obligation at the market prices of its reserves, the way `RefreshObligation`
+/// does on chain. The reserves should have been refreshed first, on or off chain.
+pub fn refresh_obligation(
+    obligation: &Obligation,
+    reserves: &HashMap<Pubkey, Reserve>,
+    lending_market: &LendingMarket,
+) -> LiquidatorResult<Obligation> {
+    let find_reserve = |reserve_pubkey: &Pubkey| {
+        reserves
+            .get(reserve_pubkey)
+            .cloned()
+            .ok_or(LiquidatorError::ReserveNotFound(*reserve_pubkey))
+    };
+    let deposit_reserves = obligation
+        .deposits
+        .iter()
+        .map(|collateral| find_reserve(&collateral.deposit_reserve))
+        .collect::<LiquidatorResult<Vec<_>>>()?;
+    let borrow_reserves = obligation
+        .borrows
+        .iter()
+        .map(|liquidity| find_reserve(&liquidity.borrow_reserve))
+        .collect::<LiquidatorResult<Vec<_>>>()?;
+
+    let mut obligation = obligation.clone();
+    obligation.refresh_values(&deposit_reserves, &borrow_reserves, Some(lending_market))?;
+    Ok(obligation)
+}
+
+/// Ratio of the unhealthy borrow value to the borrowed value of a refreshed obligation, which
+/// can be liquidated once it falls to 1. `None` if the obligation has no borrowed value.
+pub fn health_factor(obligation: &Obligation) -> LiquidatorResult<Option<Decimal>> {
+    if obligation.borrowed_value == Decimal::zero() {
+        return Ok(None);
+    }
+    Ok(Some(
+        obligation
+            .unhealthy_borrow_value
+            .try_div(obligation.borrowed_value)?,
+    ))
+}
+
+/// Whether `LiquidateObligation` accepts a refreshed obligation
+pub fn is_liquidatable(obligation: &Obligation) -> bool {
+    obligation.deposited_value > Decimal::zero()
+        && obligation.borrowed_value > Decimal::zero()
+        && obligation.borrowed_value >= obligation.unhealthy_borrow_value
+}
+
+/// Pick the borrow to repay and the collateral to receive for a refreshed obligation: the
+/// borrow and the deposit with the highest market values
+pub fn select_liquidation(obligation: &Obligation) -> Option<(Pubkey, Pubkey)> {
+    let liquidity = obligation
+        .borrows
+        .iter()
+        .max_by(|a, b| a.market_value.cmp(&b.market_value))?;
+    let collateral = obligation
+        .deposits
+        .iter()
+        .max_by(|a, b| a.market_value.cmp(&b.market_value))?;
+    Some((liquidity.borrow_reserve, collateral.deposit_reserve))
+}
+
+/// Expected outcome of liquidating an obligation
+#[derive(Clone, Debug, PartialEq)]
+pub struct LiquidationPlan {
+    /// Reserve the liquidity is repaid to
+    pub repay_reserve: Pubkey,
+    /// Reserve the collateral is withdrawn from
+    pub withdraw_reserve: Pubkey,
+    /// Amount of liquidity to pass to `LiquidateObligation`
+    pub liquidity_amount: u64,
+    /// Amount of liquidity transferred from the liquidator
+    pub repay_amount: u64,
+    /// Amount of collateral transferred to the liquidator
+    pub withdraw_amount: u64,
+    /// Market value of the repaid liquidity in the lending market quote currency
+    pub repay_value: Decimal,
+    /// Market value of the withdrawn collateral in the lending market quote currency
+    pub withdraw_value: Decimal,
+}
+
+impl LiquidationPlan {
+    /// Value gained by the liquidator, before transaction fees
+    pub fn profit(&self) -> LiquidatorResult<Decimal> {
+        if self.withdraw_value <= self.repay_value {
+            return Ok(Decimal::zero());
+        }
+        Ok(self.withdraw_value.try_sub(self.repay_value)?)
+    }
+}
+
+/// Calculate the liquidation of a refreshed obligation with the same math as
+/// `LiquidateObligation`, repaying at most `liquidity_amount`
+pub fn plan_liquidation(
+    obligation: &Obligation,
+    repay_reserve_pubkey: Pubkey,
+    withdraw_reserve_pubkey: Pubkey,
+    reserves: &HashMap<Pubkey, Reserve>,
+    lending_market: &LendingMarket,
+    liquidity_amount: u64,
+) -> LiquidatorResult<LiquidationPlan> {
+    let repay_reserve = reserves
+        .get(&repay_reserve_pubkey)
+        .ok_or(LiquidatorError::ReserveNotFound(repay_reserve_pubkey))?;
+    let withdraw_reserve = reserves
+        .get(&withdraw_reserve_pubkey)
+        .ok_or(LiquidatorError::ReserveNotFound(withdraw_reserve_pubkey))?;
+    let (liquidity, _) = obligation.find_liquidity_in_borrows(repay_reserve_pubkey)?;
+    let (collateral, _) = obligation.find_collateral_in_deposits(withdraw_reserve_pubkey)?;
+
+    let CalculateLiquidationResult {
+        repay_amount,
+        withdraw_amount,
+        ..
+    } = withdraw_reserve.calculate_liquidation(
+        liquidity_amount,
+        obligation,
+        liquidity,
+        collateral,
+        lending_market,
+    )?;
+
+    // @TODO: add lookup table https://git.io/JOCYq
+    let decimals = 10u64
+        .checked_pow(repay_reserve.liquidity.mint_decimals as u32)
+        .ok_or_else(|| ProgramError::from(LendingError::MathOverflow))?;
+    let repay_value = repay_reserve
+        .liquidity
+        .market_price
+        .try_mul(repay_amount)?
+        .try_div(decimals)?;
+    let withdraw_value = if collateral.deposited_amount == 0 {
+        Decimal::zero()
+    } else {
+        collateral
+            .market_value
+            .try_mul(withdraw_amount)?
+            .try_div(collateral.deposited_amount)?
+    };
+
+    Ok(LiquidationPlan {
+        repay_reserve: repay_reserve_pubkey,
+        withdraw_reserve: withdraw_reserve_pubkey,
+        liquidity_amount,
+        repay_amount,
+        withdraw_amount,
+        repay_value,
+        withdraw_value,
+    })
+}
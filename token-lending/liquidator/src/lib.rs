@@ -0,0 +1,12 @@
+#![allow(clippy::integer_arithmetic)]
+#![deny(missing_docs)]
+
+//! An off-chain liquidator for the SPL token-lending program.
+
+pub mod error;
+pub mod executor;
+pub mod health;
+pub mod scanner;
+
+// Export the lending program for downstream users building with a different program version
+pub use spl_token_lending;
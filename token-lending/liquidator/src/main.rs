@@ -0,0 +1,175 @@
+#![allow(clippy::integer_arithmetic)]
+
+use {
+    clap::{crate_description, crate_name, crate_version, value_t, App, Arg},
+    solana_clap_utils::{
+        fee_payer::fee_payer_arg,
+        input_parsers::pubkey_of,
+        input_validators::{is_amount, is_parsable, is_pubkey, is_url},
+        keypair::signer_from_path,
+    },
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::commitment_config::CommitmentConfig,
+    spl_token_client::client::{ProgramClient, ProgramRpcClient, ProgramRpcClientSendTransaction},
+    spl_token_lending::math::{Decimal, WAD},
+    spl_token_lending_liquidator::{
+        executor::Liquidator,
+        scanner::{refresh_reserves, scan_market},
+    },
+    std::{process::exit, sync::Arc, time::Duration},
+};
+
+#[tokio::main]
+async fn main() {
+    solana_logger::setup_with_default("solana=info,spl_token_lending_liquidator=info");
+
+    let default_lending_program_id: &str = &spl_token_lending::id().to_string();
+
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .arg({
+            let arg = Arg::with_name("config_file")
+                .short("C")
+                .long("config")
+                .value_name("PATH")
+                .takes_value(true)
+                .help("Configuration file to use");
+            if let Some(ref config_file) = *solana_cli_config::CONFIG_FILE {
+                arg.default_value(config_file)
+            } else {
+                arg
+            }
+        })
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .long("url")
+                .value_name("URL")
+                .takes_value(true)
+                .validator(is_url)
+                .help("JSON RPC URL for the cluster.  Default from the configuration file."),
+        )
+        .arg(fee_payer_arg().short("p"))
+        .arg(
+            Arg::with_name("lending_program_id")
+                .long("program")
+                .validator(is_pubkey)
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .required(true)
+                .default_value(default_lending_program_id)
+                .help("Lending program ID"),
+        )
+        .arg(
+            Arg::with_name("lending_market")
+                .long("market")
+                .validator(is_pubkey)
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .required(true)
+                .help("Lending market to liquidate obligations of"),
+        )
+        .arg(
+            Arg::with_name("interval")
+                .long("interval")
+                .validator(is_parsable::<u64>)
+                .value_name("SECONDS")
+                .takes_value(true)
+                .default_value("10")
+                .help("Seconds to wait between scans of the lending market"),
+        )
+        .arg(
+            Arg::with_name("min_profit")
+                .long("min-profit")
+                .validator(is_amount)
+                .value_name("DECIMAL_AMOUNT")
+                .takes_value(true)
+                .default_value("0")
+                .help(
+                    "Skip liquidations gaining less than this value in the market quote currency",
+                ),
+        )
+        .arg(
+            Arg::with_name("once")
+                .long("once")
+                .takes_value(false)
+                .help("Scan the lending market once and exit"),
+        )
+        .get_matches();
+
+    let mut wallet_manager = None;
+    let cli_config = if let Some(config_file) = matches.value_of("config_file") {
+        solana_cli_config::Config::load(config_file).unwrap_or_default()
+    } else {
+        solana_cli_config::Config::default()
+    };
+    let json_rpc_url = value_t!(matches, "json_rpc_url", String)
+        .unwrap_or_else(|_| cli_config.json_rpc_url.clone());
+    let fee_payer = signer_from_path(
+        &matches,
+        matches
+            .value_of("fee_payer")
+            .unwrap_or(&cli_config.keypair_path),
+        "fee_payer",
+        &mut wallet_manager,
+    )
+    .unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        exit(1);
+    });
+    let lending_program_id = pubkey_of(&matches, "lending_program_id").unwrap();
+    let lending_market_pubkey = pubkey_of(&matches, "lending_market").unwrap();
+    let interval = Duration::from_secs(value_t!(matches, "interval", u64).unwrap());
+    let min_profit = value_t!(matches, "min_profit", f64).unwrap();
+    let once = matches.is_present("once");
+
+    let rpc_client = Arc::new(RpcClient::new_with_commitment(
+        json_rpc_url,
+        CommitmentConfig::confirmed(),
+    ));
+    let program_client: Arc<dyn ProgramClient<ProgramRpcClientSendTransaction>> = Arc::new(
+        ProgramRpcClient::new(rpc_client.clone(), ProgramRpcClientSendTransaction),
+    );
+    let liquidator = Liquidator::new(
+        program_client.clone(),
+        lending_program_id,
+        Arc::from(fee_payer),
+    )
+    .with_min_profit(Decimal::from_scaled_val((min_profit * WAD as f64) as u128));
+
+    loop {
+        match scan_market(&rpc_client, &lending_program_id, lending_market_pubkey).await {
+            Ok(mut snapshot) => {
+                println!(
+                    "Scanned {} reserves and {} obligations",
+                    snapshot.reserves.len(),
+                    snapshot.obligations.len()
+                );
+                if let Err(err) = refresh_reserves(program_client.as_ref(), &mut snapshot).await {
+                    eprintln!("error: failed to refresh reserves: {}", err);
+                } else {
+                    match liquidator.liquidate_unhealthy(&snapshot).await {
+                        Ok(liquidations) => {
+                            for (obligation_pubkey, plan) in liquidations {
+                                println!(
+                                    "Liquidated {}: repaid {} for {} collateral, profit {}",
+                                    obligation_pubkey,
+                                    plan.repay_amount,
+                                    plan.withdraw_amount,
+                                    plan.profit().unwrap_or_else(|_| Decimal::zero()),
+                                );
+                            }
+                        }
+                        Err(err) => eprintln!("error: failed to liquidate: {}", err),
+                    }
+                }
+            }
+            Err(err) => eprintln!("error: failed to scan lending market: {}", err),
+        }
+
+        if once {
+            break;
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
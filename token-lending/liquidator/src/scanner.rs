@@ -0,0 +1,232 @@
+//! Obligation scanner
+
+use {
+    crate::error::{LiquidatorError, LiquidatorResult},
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::{
+        nonblocking::rpc_client::RpcClient,
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+    },
+    solana_program::{
+        clock::Clock, program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, sysvar,
+    },
+    solana_sdk::account::{from_account, Account},
+    spl_token_client::client::{ProgramClient, SendTransaction},
+    spl_token_lending::state::{
+        LendingMarket, Obligation, OracleAccount, Reserve, LEGACY_OBLIGATION_LEN,
+    },
+    std::collections::HashMap,
+};
+
+/// Byte offset of the lending market in packed reserves and obligations, after the version and
+/// last update
+const LENDING_MARKET_OFFSET: usize = 10;
+
+/// A lending market with its reserves and obligations
+#[derive(Clone, Debug)]
+pub struct MarketSnapshot {
+    /// Lending market address
+    pub lending_market_pubkey: Pubkey,
+    /// Lending market state
+    pub lending_market: LendingMarket,
+    /// Reserves of the lending market, by address
+    pub reserves: HashMap<Pubkey, Reserve>,
+    /// Obligations of the lending market
+    pub obligations: Vec<(Pubkey, Obligation)>,
+}
+
+impl MarketSnapshot {
+    /// Sort lending program accounts into the reserves and obligations of a lending market,
+    /// skipping accounts of other markets and accounts that fail to unpack
+    pub fn from_accounts(
+        program_id: &Pubkey,
+        lending_market_pubkey: Pubkey,
+        lending_market: LendingMarket,
+        accounts: impl IntoIterator<Item = (Pubkey, Account)>,
+    ) -> Self {
+        let mut reserves = HashMap::new();
+        let mut obligations = vec![];
+        for (pubkey, account) in accounts {
+            if account.owner != *program_id {
+                continue;
+            }
+            if account.data.len() == Reserve::LEN {
+                match Reserve::unpack(&account.data) {
+                    Ok(reserve) if reserve.lending_market == lending_market_pubkey => {
+                        reserves.insert(pubkey, reserve);
+                    }
+                    Ok(_) => {}
+                    Err(err) => log::warn!("Invalid reserve {}: {}", pubkey, err),
+                }
+            } else if account.data.len() == Obligation::LEN
+                || account.data.len() == LEGACY_OBLIGATION_LEN
+            {
+                match Obligation::unpack_account(&account.data) {
+                    Ok(obligation) if obligation.lending_market == lending_market_pubkey => {
+                        obligations.push((pubkey, obligation));
+                    }
+                    Ok(_) => {}
+                    Err(err) => log::warn!("Invalid obligation {}: {}", pubkey, err),
+                }
+            }
+        }
+
+        Self {
+            lending_market_pubkey,
+            lending_market,
+            reserves,
+            obligations,
+        }
+    }
+
+    /// Obligations with borrows, the only ones that can become unhealthy
+    pub fn borrowing_obligations(&self) -> impl Iterator<Item = &(Pubkey, Obligation)> {
+        self.obligations
+            .iter()
+            .filter(|(_, obligation)| !obligation.borrows.is_empty())
+    }
+}
+
+/// `getProgramAccounts` filters for the accounts of a lending market with the data size of
+/// of either a reserve or an obligation
+pub fn market_account_filters(
+    lending_market_pubkey: &Pubkey,
+    data_size: usize,
+) -> Vec<RpcFilterType> {
+    vec![
+        RpcFilterType::DataSize(data_size as u64),
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            LENDING_MARKET_OFFSET,
+            lending_market_pubkey.as_ref(),
+        )),
+    ]
+}
+
+/// Fetch a lending market and every one of its reserves and obligations
+pub async fn scan_market(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    lending_market_pubkey: Pubkey,
+) -> LiquidatorResult<MarketSnapshot> {
+    let lending_market_account = rpc_client
+        .get_account(&lending_market_pubkey)
+        .await
+        .map_err(|err| LiquidatorError::Client(err.into()))?;
+    let lending_market = LendingMarket::unpack(&lending_market_account.data)?;
+
+    let mut accounts = vec![];
+    for data_size in [Reserve::LEN, Obligation::LEN, LEGACY_OBLIGATION_LEN] {
+        accounts.extend(
+            rpc_client
+                .get_program_accounts_with_config(
+                    program_id,
+                    RpcProgramAccountsConfig {
+                        filters: Some(market_account_filters(&lending_market_pubkey, data_size)),
+                        account_config: RpcAccountInfoConfig {
+                            encoding: Some(UiAccountEncoding::Base64),
+                            commitment: Some(rpc_client.commitment()),
+                            ..RpcAccountInfoConfig::default()
+                        },
+                        ..RpcProgramAccountsConfig::default()
+                    },
+                )
+                .await
+                .map_err(|err| LiquidatorError::Client(err.into()))?,
+        );
+    }
+
+    Ok(MarketSnapshot::from_accounts(
+        program_id,
+        lending_market_pubkey,
+        lending_market,
+        accounts,
+    ))
+}
+
+/// Fetch a lending market and the given reserves and obligations, for clients that cannot
+/// search program accounts
+pub async fn load_market<T>(
+    client: &dyn ProgramClient<T>,
+    program_id: &Pubkey,
+    lending_market_pubkey: Pubkey,
+    account_pubkeys: &[Pubkey],
+) -> LiquidatorResult<MarketSnapshot>
+where
+    T: SendTransaction,
+{
+    let lending_market_account = get_account(client, lending_market_pubkey).await?;
+    let lending_market = LendingMarket::unpack(&lending_market_account.data)?;
+
+    let mut accounts = vec![];
+    for pubkey in account_pubkeys {
+        accounts.push((*pubkey, get_account(client, *pubkey).await?));
+    }
+
+    Ok(MarketSnapshot::from_accounts(
+        program_id,
+        lending_market_pubkey,
+        lending_market,
+        accounts,
+    ))
+}
+
+/// Refresh the reserves of a snapshot off chain at the current slot and oracle prices, so that
+/// obligations are valued as `RefreshObligation` would value them. Reserves whose oracle
+/// prices are rejected are logged and left as they were.
+pub async fn refresh_reserves<T>(
+    client: &dyn ProgramClient<T>,
+    snapshot: &mut MarketSnapshot,
+) -> LiquidatorResult<()>
+where
+    T: SendTransaction,
+{
+    let clock_account = get_account(client, sysvar::clock::id()).await?;
+    let clock = from_account::<Clock, _>(&clock_account)
+        .ok_or(LiquidatorError::Program(ProgramError::InvalidAccountData))?;
+
+    for (reserve_pubkey, reserve) in snapshot.reserves.iter_mut() {
+        let oracle_account = get_account(client, reserve.liquidity.oracle_pubkey).await?;
+        let fallback_oracle_account = match reserve.config.fallback_oracle() {
+            Some(fallback_oracle_pubkey) => Some((
+                fallback_oracle_pubkey,
+                get_account(client, fallback_oracle_pubkey).await?,
+            )),
+            None => None,
+        };
+        let mut refreshed_reserve = reserve.clone();
+        match refreshed_reserve.refresh(
+            OracleAccount {
+                pubkey: &reserve.liquidity.oracle_pubkey,
+                owner: &oracle_account.owner,
+                data: &oracle_account.data,
+            },
+            fallback_oracle_account
+                .as_ref()
+                .map(|(pubkey, account)| OracleAccount {
+                    pubkey,
+                    owner: &account.owner,
+                    data: &account.data,
+                }),
+            clock.slot,
+        ) {
+            Ok(()) => *reserve = refreshed_reserve,
+            Err(err) => log::warn!("Failed to refresh reserve {}: {}", reserve_pubkey, err),
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn get_account<T>(
+    client: &dyn ProgramClient<T>,
+    pubkey: Pubkey,
+) -> LiquidatorResult<Account>
+where
+    T: SendTransaction,
+{
+    client
+        .get_account(pubkey)
+        .await
+        .map_err(LiquidatorError::Client)?
+        .ok_or(LiquidatorError::AccountNotFound(pubkey))
+}
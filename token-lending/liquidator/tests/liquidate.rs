@@ -0,0 +1,292 @@
+#![allow(clippy::integer_arithmetic)]
+#![cfg(feature = "test-sbf")]
+
+#[path = "../../program/tests/helpers/mod.rs"]
+mod helpers;
+
+use helpers::*;
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::{tokio::sync::Mutex, *};
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+    sysvar::clock::Clock,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::Account as Token;
+use spl_token_client::client::{
+    ProgramBanksClient, ProgramBanksClientProcessTransaction, ProgramClient, SendTransaction,
+};
+use spl_token_lending::{
+    math::Decimal,
+    processor::process_instruction,
+    pyth,
+    state::{InitLendingMarketParams, LendingMarket, ReserveConfig},
+};
+use spl_token_lending_liquidator::{
+    executor::Liquidator,
+    health,
+    scanner::{load_market, refresh_reserves},
+};
+use std::{mem::size_of, sync::Arc};
+
+const SOL_DEPOSIT_AMOUNT_LAMPORTS: u64 = 10 * LAMPORTS_TO_SOL;
+const USDC_BORROW_AMOUNT_FRACTIONAL: u64 = 100 * FRACTIONAL_TO_USDC;
+const USDC_RESERVE_LIQUIDITY_FRACTIONAL: u64 = 1_000 * FRACTIONAL_TO_USDC;
+const LIQUIDATOR_USDC_FRACTIONAL: u64 = 1_000 * FRACTIONAL_TO_USDC;
+
+/// Reserve config under which a single liquidation makes the test obligation healthy again
+const LIQUIDATOR_RESERVE_CONFIG: ReserveConfig = ReserveConfig {
+    liquidation_bonus: 10,
+    liquidation_threshold: 80,
+    ..TEST_RESERVE_CONFIG
+};
+
+/// Oracle whose price account is written by `set_price`
+fn test_oracle(price: u64) -> TestOracle {
+    TestOracle {
+        product_pubkey: Pubkey::new_unique(),
+        price_pubkey: Pubkey::new_unique(),
+        price: Decimal::from(price),
+    }
+}
+
+/// Write a Pyth price account quoting `price` as of the current slot
+async fn set_price(context: &Mutex<ProgramTestContext>, oracle_pubkey: Pubkey, price: i64) {
+    let mut context = context.lock().await;
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+
+    let mut data = vec![0; size_of::<pyth::Price>()];
+    let pyth_price = pyth::load_mut::<pyth::Price>(data.as_mut_slice()).unwrap();
+    pyth_price.magic = pyth::MAGIC;
+    pyth_price.ver = pyth::VERSION;
+    pyth_price.atype = pyth::AccountType::Price as u32;
+    pyth_price.ptype = pyth::PriceType::Price;
+    pyth_price.expo = 0;
+    pyth_price.valid_slot = clock.slot;
+    pyth_price.agg.price = price;
+    pyth_price.agg.status = pyth::PriceStatus::Trading;
+
+    context.set_account(
+        &oracle_pubkey,
+        &Account {
+            lamports: u32::MAX as u64,
+            data,
+            owner: Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+}
+
+async fn get_client_token_balance<T>(client: &dyn ProgramClient<T>, pubkey: Pubkey) -> u64
+where
+    T: SendTransaction,
+{
+    client
+        .get_account(pubkey)
+        .await
+        .unwrap()
+        .map(|account| Token::unpack(&account.data).unwrap().amount)
+        .unwrap_or(0)
+}
+
+#[tokio::test]
+async fn test_liquidate_unhealthy_obligation() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    // The lending market owner fixture is only found from the program directory
+    let lending_market_pubkey = Pubkey::new_unique();
+    let (lending_market_authority, bump_seed) =
+        Pubkey::find_program_address(&[lending_market_pubkey.as_ref()], &spl_token_lending::id());
+    let lending_market = TestLendingMarket {
+        pubkey: lending_market_pubkey,
+        owner: Keypair::new(),
+        authority: lending_market_authority,
+        quote_currency: QUOTE_CURRENCY,
+        oracle_program_id: Pubkey::new_unique(),
+    };
+    test.add_packable_account(
+        lending_market_pubkey,
+        u32::MAX as u64,
+        &LendingMarket::new(InitLendingMarketParams {
+            bump_seed,
+            owner: lending_market.owner.pubkey(),
+            quote_currency: lending_market.quote_currency,
+            token_program_id: spl_token::id(),
+            oracle_program_id: lending_market.oracle_program_id,
+        }),
+        &spl_token_lending::id(),
+    );
+
+    let user_accounts_owner = Keypair::new();
+    let sol_oracle = test_oracle(20);
+    let sol_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &sol_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            config: LIQUIDATOR_RESERVE_CONFIG,
+            liquidity_amount: SOL_DEPOSIT_AMOUNT_LAMPORTS,
+            liquidity_mint_pubkey: spl_token::native_mint::id(),
+            liquidity_mint_decimals: 9,
+            // All of the collateral is deposited in obligations
+            collateral_amount: SOL_DEPOSIT_AMOUNT_LAMPORTS,
+            ..AddReserveArgs::default()
+        },
+    );
+    let usdc_mint = add_usdc_mint(&mut test);
+    let usdc_oracle = test_oracle(1);
+    let usdc_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &usdc_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            config: LIQUIDATOR_RESERVE_CONFIG,
+            liquidity_amount: USDC_RESERVE_LIQUIDITY_FRACTIONAL,
+            liquidity_mint_pubkey: usdc_mint.pubkey,
+            liquidity_mint_decimals: usdc_mint.decimals,
+            borrow_amount: USDC_BORROW_AMOUNT_FRACTIONAL,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    // 10 SOL * $20 -> $200 deposited, $100 borrowed
+    let obligation = add_obligation(
+        &mut test,
+        &lending_market,
+        &user_accounts_owner,
+        AddObligationArgs {
+            deposits: &[(&sol_reserve, SOL_DEPOSIT_AMOUNT_LAMPORTS)],
+            borrows: &[(&usdc_reserve, USDC_BORROW_AMOUNT_FRACTIONAL)],
+            ..AddObligationArgs::default()
+        },
+    );
+    let obligation_pubkey = obligation.pubkey;
+
+    let liquidator_keypair = Arc::new(Keypair::new());
+    test.add_account(
+        liquidator_keypair.pubkey(),
+        Account::new(LAMPORTS_TO_SOL, 0, &solana_program::system_program::id()),
+    );
+    let liquidator_usdc_pubkey =
+        get_associated_token_address(&liquidator_keypair.pubkey(), &usdc_mint.pubkey);
+    add_token_account(
+        &mut test,
+        liquidator_usdc_pubkey,
+        &usdc_mint.pubkey,
+        &liquidator_keypair.pubkey(),
+        LIQUIDATOR_USDC_FRACTIONAL,
+    );
+    let liquidator_collateral_pubkey = get_associated_token_address(
+        &liquidator_keypair.pubkey(),
+        &sol_reserve.collateral_mint_pubkey,
+    );
+
+    let context = Arc::new(Mutex::new(test.start_with_context().await));
+    let client: Arc<dyn ProgramClient<ProgramBanksClientProcessTransaction>> = Arc::new(
+        ProgramBanksClient::new_from_context(context.clone(), ProgramBanksClientProcessTransaction),
+    );
+    let liquidator = Liquidator::new(
+        client.clone(),
+        spl_token_lending::id(),
+        liquidator_keypair.clone(),
+    );
+    let account_pubkeys = [sol_reserve.pubkey, usdc_reserve.pubkey, obligation_pubkey];
+
+    // SOL at $20: $160 unhealthy borrow value, nothing to liquidate
+    set_price(&context, sol_oracle.price_pubkey, 20).await;
+    set_price(&context, usdc_oracle.price_pubkey, 1).await;
+
+    let mut snapshot = load_market(
+        client.as_ref(),
+        &spl_token_lending::id(),
+        lending_market_pubkey,
+        &account_pubkeys,
+    )
+    .await
+    .unwrap();
+    assert_eq!(snapshot.reserves.len(), 2);
+    assert_eq!(snapshot.obligations.len(), 1);
+
+    refresh_reserves(client.as_ref(), &mut snapshot)
+        .await
+        .unwrap();
+    assert!(liquidator
+        .liquidate_unhealthy(&snapshot)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        get_client_token_balance(client.as_ref(), liquidator_usdc_pubkey).await,
+        LIQUIDATOR_USDC_FRACTIONAL
+    );
+
+    // SOL at $12: $96 unhealthy borrow value, below the $100 borrowed
+    {
+        let mut context = context.lock().await;
+        let slot = context.banks_client.get_root_slot().await.unwrap();
+        context.warp_to_slot(slot + 10).unwrap();
+        context.get_new_latest_blockhash().await.unwrap();
+    }
+    set_price(&context, sol_oracle.price_pubkey, 12).await;
+    set_price(&context, usdc_oracle.price_pubkey, 1).await;
+
+    let mut snapshot = load_market(
+        client.as_ref(),
+        &spl_token_lending::id(),
+        lending_market_pubkey,
+        &account_pubkeys,
+    )
+    .await
+    .unwrap();
+    refresh_reserves(client.as_ref(), &mut snapshot)
+        .await
+        .unwrap();
+    let liquidations = liquidator.liquidate_unhealthy(&snapshot).await.unwrap();
+    assert_eq!(liquidations.len(), 1);
+    let (liquidated_pubkey, plan) = &liquidations[0];
+    assert_eq!(*liquidated_pubkey, obligation_pubkey);
+    assert_eq!(plan.repay_reserve, usdc_reserve.pubkey);
+    assert_eq!(plan.withdraw_reserve, sol_reserve.pubkey);
+    assert!(plan.profit().unwrap() > Decimal::zero());
+
+    // At most half of the borrow is repaid
+    let usdc_spent = LIQUIDATOR_USDC_FRACTIONAL
+        - get_client_token_balance(client.as_ref(), liquidator_usdc_pubkey).await;
+    assert_eq!(usdc_spent, plan.repay_amount);
+    assert!(usdc_spent > 0);
+    assert!(usdc_spent <= USDC_BORROW_AMOUNT_FRACTIONAL / 2);
+    assert_eq!(
+        get_client_token_balance(client.as_ref(), liquidator_collateral_pubkey).await,
+        plan.withdraw_amount
+    );
+    assert!(plan.withdraw_amount > 0);
+
+    // The liquidated obligation is healthy again
+    let snapshot = load_market(
+        client.as_ref(),
+        &spl_token_lending::id(),
+        lending_market_pubkey,
+        &account_pubkeys,
+    )
+    .await
+    .unwrap();
+    let (_, obligation) = &snapshot.obligations[0];
+    let obligation =
+        health::refresh_obligation(obligation, &snapshot.reserves, &snapshot.lending_market)
+            .unwrap();
+    assert!(!health::is_liquidatable(&obligation));
+    assert!(liquidator
+        .liquidate_unhealthy(&snapshot)
+        .await
+        .unwrap()
+        .is_empty());
+}
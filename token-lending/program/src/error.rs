@@ -163,6 +163,40 @@ pub enum LendingError {
     /// Lending instruction exceeds desired slippage limit
     #[error("Amount smaller than desired slippage limit")]
     ExceededSlippage,
+    /// Deposit would exceed the reserve deposit limit
+    #[error("Deposit would exceed the reserve deposit limit")]
+    DepositLimitExceeded,
+    /// Borrow would exceed the reserve borrow limit
+    #[error("Borrow would exceed the reserve borrow limit")]
+    BorrowLimitExceeded,
+    /// Oracle price is not currently trading
+    #[error("Oracle price status is not trading")]
+    OraclePriceNotTrading,
+    /// Oracle price is older than the reserve staleness window
+    #[error("Oracle price is stale")]
+    OraclePriceStale,
+
+    // 50
+    /// Oracle price is zero or negative
+    #[error("Oracle price is not positive")]
+    OraclePriceNotPositive,
+    /// Oracle confidence interval is wider than the reserve allows
+    #[error("Oracle price confidence interval is too wide")]
+    OraclePriceConfidenceTooWide,
+    /// Expected a different fallback oracle account
+    #[error("Input fallback oracle account is not valid")]
+    InvalidFallbackOracle,
+    /// Elevation group does not exist or cannot be added
+    #[error("Elevation group is not valid")]
+    InvalidElevationGroup,
+    /// Reserve does not belong to the elevation group of the obligation
+    #[error("Reserve does not belong to the obligation elevation group")]
+    ElevationGroupMismatch,
+
+    // 55
+    /// Obligation still has collateral backing its borrows
+    #[error("Obligation deposits not empty")]
+    ObligationDepositsNotEmpty,
 }
 
 impl From<LendingError> for ProgramError {
@@ -2,17 +2,22 @@
 
 use crate::{
     error::LendingError,
-    state::{ReserveConfig, ReserveFees},
+    math::{Decimal, U192},
+    state::{BorrowRateCurve, ElevationGroup, ReserveConfig, ReserveFees},
 };
 use solana_program::{
     instruction::{AccountMeta, Instruction},
     msg,
     program_error::ProgramError,
+    program_pack::Pack,
     pubkey::{Pubkey, PUBKEY_BYTES},
-    sysvar,
+    system_program, sysvar,
 };
 use std::{convert::TryInto, mem::size_of};
 
+/// Decimals are packed into instruction data as their full 24 byte U192 value
+const DECIMAL_BYTES: usize = 24;
+
 /// Instructions supported by the lending program.
 #[derive(Clone, Debug, PartialEq)]
 pub enum LendingInstruction {
@@ -69,6 +74,8 @@ pub enum LendingInstruction {
     ///   14 `[]` Clock sysvar.
     ///   15 `[]` Rent sysvar.
     ///   16 `[]` Token program id.
+    ///   17 `[optional]` Fallback Pyth product account.
+    ///             Required when the config has a fallback oracle, which must be its price account.
     InitReserve {
         /// Initial amount of liquidity to deposit into the new reserve
         liquidity_amount: u64,
@@ -85,6 +92,9 @@ pub enum LendingInstruction {
     ///   1. `[]` Reserve liquidity oracle account.
     ///             Must be the Pyth price account specified at InitReserve.
     ///   2. `[]` Clock sysvar.
+    ///   3. `[optional]` Reserve fallback oracle account.
+    ///             Must be the Pyth price account in the reserve config, owned by the
+    ///             same oracle program, required when the primary price is rejected.
     RefreshReserve,
 
     // 4
@@ -154,6 +164,8 @@ pub enum LendingInstruction {
     ///   1. `[]` Clock sysvar.
     ///   .. `[]` Collateral deposit reserve accounts - refreshed, all, in order.
     ///   .. `[]` Liquidity borrow reserve accounts - refreshed, all, in order.
+    ///   .. `[optional]` Lending market account.
+    ///             Required when the obligation is in an elevation group.
     RefreshObligation,
 
     // 8
@@ -318,10 +330,107 @@ pub enum LendingInstruction {
     ///   0. `[writable]` Reserve account
     ///   1. `[]` Lending market account
     ///   2. `[signer]` Lending market owner
+    ///   3. `[optional]` Fallback Pyth product account
+    ///             Required when the fallback oracle changes, which must be its price account.
     ModifyReserveConfig {
         /// Reserve configuration updated values
         new_config: ReserveConfig,
     },
+
+    // 15
+    /// Transfer the protocol fees accrued on a reserve to its liquidity fee receiver.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Reserve account - refreshed.
+    ///   1. `[writable]` Reserve liquidity fee receiver account.
+    ///   2. `[writable]` Reserve liquidity supply SPL Token account.
+    ///   3. `[]` Lending market account.
+    ///   4. `[]` Derived lending market authority.
+    ///   5. `[]` Clock sysvar.
+    ///   6. `[]` Token program id.
+    RedeemFees,
+
+    // 16
+    /// Sets the liquidation parameters of a lending market.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Lending market account.
+    ///   1. `[signer]` Lending market owner.
+    SetLendingMarketConfig {
+        /// Percentage of an obligation's borrowed value that can be repaid during each
+        /// liquidation call - None for the default close factor
+        liquidation_close_factor: Option<u8>,
+        /// Borrowed value at or below which an obligation can be fully liquidated
+        liquidation_dust_value: Decimal,
+    },
+
+    // 17
+    /// Adds an elevation group to a lending market.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Lending market account.
+    ///   1. `[signer]` Lending market owner.
+    InitElevationGroup {
+        /// Elevation group id and borrowing parameters
+        elevation_group: ElevationGroup,
+    },
+
+    // 18
+    /// Modifies the borrowing parameters of an existing elevation group.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Lending market account.
+    ///   1. `[signer]` Lending market owner.
+    ModifyElevationGroup {
+        /// Elevation group id and updated borrowing parameters
+        elevation_group: ElevationGroup,
+    },
+
+    // 19
+    /// Moves an obligation into an elevation group, or out of it with id 0. Requires a refreshed
+    /// obligation and its reserves, which must all belong to the elevation group, as all
+    /// obligation collateral deposit reserves in order, followed by all liquidity borrow
+    /// reserves in order. The obligation must remain healthy with the new parameters.
+    /// Obligation accounts created before elevation groups were added are resized when moved
+    /// into one, with the obligation owner paying for the additional rent.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Obligation account - refreshed.
+    ///   1. `[]` Lending market account.
+    ///   2. `[writable, signer]` Obligation owner.
+    ///   3. `[]` Clock sysvar.
+    ///   4. `[]` Rent sysvar.
+    ///   5. `[]` System program.
+    ///   .. `[]` Collateral deposit reserve accounts - all, in order.
+    ///   .. `[]` Liquidity borrow reserve accounts - all, in order.
+    SetObligationElevationGroup {
+        /// Elevation group id - 0 to leave the current elevation group
+        elevation_group: u8,
+    },
+
+    // 20
+    /// Write off liquidity borrowed by an obligation that has no collateral left to back it. The
+    /// forgiven amount is removed from the reserve's borrowed liquidity, so the loss is shared by
+    /// all collateral token holders through the collateral exchange rate. Debt beyond the reserve's
+    /// total supply is taken out of its accumulated protocol fees. Requires a refreshed obligation
+    /// and reserve.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` Obligation account - refreshed.
+    ///   1. `[writable]` Borrow reserve account - refreshed.
+    ///   2. `[]` Lending market account.
+    ///   3. `[signer]` Lending market owner.
+    ///   4. `[]` Clock sysvar.
+    ForgiveDebt {
+        /// Amount of liquidity to forgive - u64::MAX for 100% of borrowed amount
+        liquidity_amount: u64,
+    },
 }
 
 impl LendingInstruction {
@@ -394,6 +503,40 @@ impl LendingInstruction {
                 let new_config = Self::unpack_reserve_config(rest)?;
                 Self::ModifyReserveConfig { new_config }
             }
+            15 => Self::RedeemFees,
+            16 => {
+                let (liquidation_close_factor_flag, rest) = Self::unpack_u8(rest)?;
+                let (liquidation_close_factor, rest) = Self::unpack_u8(rest)?;
+                let (liquidation_dust_value, _rest) = Self::unpack_decimal(rest)?;
+                let liquidation_close_factor = match liquidation_close_factor_flag {
+                    0 => None,
+                    1 => Some(liquidation_close_factor),
+                    _ => {
+                        msg!("Liquidation close factor flag cannot be unpacked");
+                        return Err(LendingError::InstructionUnpackError.into());
+                    }
+                };
+                Self::SetLendingMarketConfig {
+                    liquidation_close_factor,
+                    liquidation_dust_value,
+                }
+            }
+            17 => {
+                let (elevation_group, _rest) = Self::unpack_elevation_group(rest)?;
+                Self::InitElevationGroup { elevation_group }
+            }
+            18 => {
+                let (elevation_group, _rest) = Self::unpack_elevation_group(rest)?;
+                Self::ModifyElevationGroup { elevation_group }
+            }
+            19 => {
+                let (elevation_group, _rest) = Self::unpack_u8(rest)?;
+                Self::SetObligationElevationGroup { elevation_group }
+            }
+            20 => {
+                let (liquidity_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::ForgiveDebt { liquidity_amount }
+            }
             _ => {
                 msg!("Instruction cannot be unpacked");
                 return Err(LendingError::InstructionUnpackError.into());
@@ -401,6 +544,15 @@ impl LendingInstruction {
         })
     }
 
+    fn unpack_decimal(input: &[u8]) -> Result<(Decimal, &[u8]), ProgramError> {
+        if input.len() < DECIMAL_BYTES {
+            msg!("Decimal cannot be unpacked");
+            return Err(LendingError::InstructionUnpackError.into());
+        }
+        let (bytes, rest) = input.split_at(DECIMAL_BYTES);
+        Ok((Decimal(U192::from_little_endian(bytes)), rest))
+    }
+
     fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
         if input.len() < 8 {
             msg!("u64 cannot be unpacked");
@@ -415,6 +567,20 @@ impl LendingInstruction {
         Ok((value, rest))
     }
 
+    fn unpack_u16(input: &[u8]) -> Result<(u16, &[u8]), ProgramError> {
+        if input.len() < 2 {
+            msg!("u16 cannot be unpacked");
+            return Err(LendingError::InstructionUnpackError.into());
+        }
+        let (bytes, rest) = input.split_at(2);
+        let value = bytes
+            .get(..2)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u16::from_le_bytes)
+            .ok_or(LendingError::InstructionUnpackError)?;
+        Ok((value, rest))
+    }
+
     fn unpack_u8(input: &[u8]) -> Result<(u8, &[u8]), ProgramError> {
         if input.is_empty() {
             msg!("u8 cannot be unpacked");
@@ -463,7 +629,16 @@ impl LendingInstruction {
         let (max_borrow_rate, rest) = Self::unpack_u8(rest)?;
         let (borrow_fee_wad, rest) = Self::unpack_u64(rest)?;
         let (flash_loan_fee_wad, rest) = Self::unpack_u64(rest)?;
-        let (host_fee_percentage, _rest) = Self::unpack_u8(rest)?;
+        let (host_fee_percentage, rest) = Self::unpack_u8(rest)?;
+        let (borrow_rate_curve, rest) = Self::unpack_borrow_rate_curve(rest)?;
+        let (deposit_limit, rest) = Self::unpack_u64(rest)?;
+        let (borrow_limit, rest) = Self::unpack_u64(rest)?;
+        let (max_price_confidence_bps, rest) = Self::unpack_u16(rest)?;
+        let (max_price_age_slots, rest) = Self::unpack_u64(rest)?;
+        let (fallback_oracle_pubkey, rest) = Self::unpack_pubkey(rest)?;
+        let (reserve_factor, rest) = Self::unpack_u8(rest)?;
+        let (max_liquidation_bonus, rest) = Self::unpack_u8(rest)?;
+        let (elevation_group, _rest) = Self::unpack_u8(rest)?;
 
         Ok(ReserveConfig {
             optimal_utilization_rate,
@@ -478,9 +653,43 @@ impl LendingInstruction {
                 flash_loan_fee_wad,
                 host_fee_percentage,
             },
+            borrow_rate_curve,
+            deposit_limit,
+            borrow_limit,
+            max_price_confidence_bps,
+            max_price_age_slots,
+            fallback_oracle_pubkey,
+            reserve_factor,
+            max_liquidation_bonus,
+            elevation_group,
         })
     }
 
+    fn unpack_elevation_group(input: &[u8]) -> Result<(ElevationGroup, &[u8]), ProgramError> {
+        let (id, rest) = Self::unpack_u8(input)?;
+        let (loan_to_value_ratio, rest) = Self::unpack_u8(rest)?;
+        let (liquidation_threshold, rest) = Self::unpack_u8(rest)?;
+        Ok((
+            ElevationGroup {
+                id,
+                loan_to_value_ratio,
+                liquidation_threshold,
+            },
+            rest,
+        ))
+    }
+
+    fn unpack_borrow_rate_curve(input: &[u8]) -> Result<(BorrowRateCurve, &[u8]), ProgramError> {
+        if input.len() < BorrowRateCurve::LEN {
+            msg!("Borrow rate curve cannot be unpacked");
+            return Err(LendingError::InstructionUnpackError.into());
+        }
+        let (bytes, rest) = input.split_at(BorrowRateCurve::LEN);
+        let curve = BorrowRateCurve::unpack_from_slice(bytes)
+            .map_err(|_| LendingError::InstructionUnpackError)?;
+        Ok((curve, rest))
+    }
+
     /// Packs a [LendingInstruction](enum.LendingInstruction.html) into a byte buffer.
     pub fn pack(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(size_of::<Self>());
@@ -554,10 +763,55 @@ impl LendingInstruction {
                 buf.push(14);
                 Self::extend_buffer_from_reserve_config(&mut buf, &new_config);
             }
+            Self::RedeemFees => {
+                buf.push(15);
+            }
+            Self::SetLendingMarketConfig {
+                liquidation_close_factor,
+                liquidation_dust_value,
+            } => {
+                buf.push(16);
+                match liquidation_close_factor {
+                    Some(close_factor) => {
+                        buf.push(1);
+                        buf.push(close_factor);
+                    }
+                    None => {
+                        buf.push(0);
+                        buf.push(0);
+                    }
+                }
+                let mut dust_value = [0u8; DECIMAL_BYTES];
+                liquidation_dust_value.0.to_little_endian(&mut dust_value);
+                buf.extend_from_slice(&dust_value);
+            }
+            Self::InitElevationGroup { elevation_group } => {
+                buf.push(17);
+                Self::extend_buffer_from_elevation_group(&mut buf, &elevation_group);
+            }
+            Self::ModifyElevationGroup { elevation_group } => {
+                buf.push(18);
+                Self::extend_buffer_from_elevation_group(&mut buf, &elevation_group);
+            }
+            Self::SetObligationElevationGroup { elevation_group } => {
+                buf.push(19);
+                buf.extend_from_slice(&elevation_group.to_le_bytes());
+            }
+            Self::ForgiveDebt { liquidity_amount } => {
+                buf.push(20);
+                buf.extend_from_slice(&liquidity_amount.to_le_bytes());
+            }
         }
         buf
     }
 
+    // Helper function to pack an ElevationGroup into a Vec<u8> buffer
+    fn extend_buffer_from_elevation_group(buf: &mut Vec<u8>, elevation_group: &ElevationGroup) {
+        buf.extend_from_slice(&elevation_group.id.to_le_bytes());
+        buf.extend_from_slice(&elevation_group.loan_to_value_ratio.to_le_bytes());
+        buf.extend_from_slice(&elevation_group.liquidation_threshold.to_le_bytes());
+    }
+
     // Helper function to pack a ReserveConfig into a Vec<u8> buffer
     fn extend_buffer_from_reserve_config(buf: &mut Vec<u8>, config: &ReserveConfig) {
         buf.extend_from_slice(&config.optimal_utilization_rate.to_le_bytes());
@@ -570,6 +824,19 @@ impl LendingInstruction {
         buf.extend_from_slice(&config.fees.borrow_fee_wad.to_le_bytes());
         buf.extend_from_slice(&config.fees.flash_loan_fee_wad.to_le_bytes());
         buf.extend_from_slice(&config.fees.host_fee_percentage.to_le_bytes());
+        let mut borrow_rate_curve = [0u8; BorrowRateCurve::LEN];
+        config
+            .borrow_rate_curve
+            .pack_into_slice(&mut borrow_rate_curve);
+        buf.extend_from_slice(&borrow_rate_curve);
+        buf.extend_from_slice(&config.deposit_limit.to_le_bytes());
+        buf.extend_from_slice(&config.borrow_limit.to_le_bytes());
+        buf.extend_from_slice(&config.max_price_confidence_bps.to_le_bytes());
+        buf.extend_from_slice(&config.max_price_age_slots.to_le_bytes());
+        buf.extend_from_slice(config.fallback_oracle_pubkey.as_ref());
+        buf.extend_from_slice(&config.reserve_factor.to_le_bytes());
+        buf.extend_from_slice(&config.max_liquidation_bonus.to_le_bytes());
+        buf.extend_from_slice(&config.elevation_group.to_le_bytes());
     }
 }
 
@@ -668,24 +935,87 @@ pub fn init_reserve(
     }
 }
 
+/// Creates an 'InitReserve' instruction for a reserve config with a fallback oracle, validated
+/// against its Pyth product account
+#[allow(clippy::too_many_arguments)]
+pub fn init_reserve_with_fallback(
+    program_id: Pubkey,
+    liquidity_amount: u64,
+    config: ReserveConfig,
+    source_liquidity_pubkey: Pubkey,
+    destination_collateral_pubkey: Pubkey,
+    reserve_pubkey: Pubkey,
+    reserve_liquidity_mint_pubkey: Pubkey,
+    reserve_liquidity_supply_pubkey: Pubkey,
+    reserve_liquidity_fee_receiver_pubkey: Pubkey,
+    reserve_collateral_mint_pubkey: Pubkey,
+    reserve_collateral_supply_pubkey: Pubkey,
+    pyth_product_pubkey: Pubkey,
+    pyth_price_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_pubkey: Pubkey,
+    user_transfer_authority_pubkey: Pubkey,
+    fallback_pyth_product_pubkey: Pubkey,
+) -> Instruction {
+    let mut instruction = init_reserve(
+        program_id,
+        liquidity_amount,
+        config,
+        source_liquidity_pubkey,
+        destination_collateral_pubkey,
+        reserve_pubkey,
+        reserve_liquidity_mint_pubkey,
+        reserve_liquidity_supply_pubkey,
+        reserve_liquidity_fee_receiver_pubkey,
+        reserve_collateral_mint_pubkey,
+        reserve_collateral_supply_pubkey,
+        pyth_product_pubkey,
+        pyth_price_pubkey,
+        lending_market_pubkey,
+        lending_market_owner_pubkey,
+        user_transfer_authority_pubkey,
+    );
+    instruction.accounts.push(AccountMeta::new_readonly(
+        fallback_pyth_product_pubkey,
+        false,
+    ));
+    instruction
+}
+
 /// Creates a `RefreshReserve` instruction
 pub fn refresh_reserve(
     program_id: Pubkey,
     reserve_pubkey: Pubkey,
     reserve_liquidity_oracle_pubkey: Pubkey,
 ) -> Instruction {
-    let accounts = vec![
-        AccountMeta::new(reserve_pubkey, false),
-        AccountMeta::new_readonly(reserve_liquidity_oracle_pubkey, false),
-        AccountMeta::new_readonly(sysvar::clock::id(), false),
-    ];
     Instruction {
         program_id,
-        accounts,
+        accounts: vec![
+            AccountMeta::new(reserve_pubkey, false),
+            AccountMeta::new_readonly(reserve_liquidity_oracle_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
         data: LendingInstruction::RefreshReserve.pack(),
     }
 }
 
+/// Creates a `RefreshReserve` instruction for a reserve with a fallback oracle, used if the
+/// reserve liquidity oracle price is rejected
+pub fn refresh_reserve_with_fallback(
+    program_id: Pubkey,
+    reserve_pubkey: Pubkey,
+    reserve_liquidity_oracle_pubkey: Pubkey,
+    reserve_fallback_oracle_pubkey: Pubkey,
+) -> Instruction {
+    let mut instruction =
+        refresh_reserve(program_id, reserve_pubkey, reserve_liquidity_oracle_pubkey);
+    instruction.accounts.push(AccountMeta::new_readonly(
+        reserve_fallback_oracle_pubkey,
+        false,
+    ));
+    instruction
+}
+
 /// Creates a 'DepositReserveLiquidity' instruction.
 #[allow(clippy::too_many_arguments)]
 pub fn deposit_reserve_liquidity(
@@ -801,6 +1131,21 @@ pub fn refresh_obligation(
     }
 }
 
+/// Creates a 'RefreshObligation' instruction for an obligation in an elevation group, which
+/// reads the group parameters from its lending market
+pub fn refresh_obligation_with_lending_market(
+    program_id: Pubkey,
+    obligation_pubkey: Pubkey,
+    reserve_pubkeys: Vec<Pubkey>,
+    lending_market_pubkey: Pubkey,
+) -> Instruction {
+    let mut instruction = refresh_obligation(program_id, obligation_pubkey, reserve_pubkeys);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(lending_market_pubkey, false));
+    instruction
+}
+
 /// Creates a 'DepositObligationCollateral' instruction.
 #[allow(clippy::too_many_arguments)]
 pub fn deposit_obligation_collateral(
@@ -1035,9 +1380,169 @@ pub fn modify_reserve_config(
     }
 }
 
+/// Creates a `ModifyReserveConfig` instruction that changes the fallback oracle, validated
+/// against its Pyth product account
+pub fn modify_reserve_config_with_fallback(
+    program_id: Pubkey,
+    config: ReserveConfig,
+    reserve_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_pubkey: Pubkey,
+    fallback_pyth_product_pubkey: Pubkey,
+) -> Instruction {
+    let mut instruction = modify_reserve_config(
+        program_id,
+        config,
+        reserve_pubkey,
+        lending_market_pubkey,
+        lending_market_owner_pubkey,
+    );
+    instruction.accounts.push(AccountMeta::new_readonly(
+        fallback_pyth_product_pubkey,
+        false,
+    ));
+    instruction
+}
+
+/// Creates a `RedeemFees` instruction
+pub fn redeem_fees(
+    program_id: Pubkey,
+    reserve_pubkey: Pubkey,
+    reserve_liquidity_fee_receiver_pubkey: Pubkey,
+    reserve_liquidity_supply_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+) -> Instruction {
+    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
+        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
+        &program_id,
+    );
+    let accounts = vec![
+        AccountMeta::new(reserve_pubkey, false),
+        AccountMeta::new(reserve_liquidity_fee_receiver_pubkey, false),
+        AccountMeta::new(reserve_liquidity_supply_pubkey, false),
+        AccountMeta::new_readonly(lending_market_pubkey, false),
+        AccountMeta::new_readonly(lending_market_authority_pubkey, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    Instruction {
+        program_id,
+        accounts,
+        data: LendingInstruction::RedeemFees.pack(),
+    }
+}
+
+/// Creates an 'InitElevationGroup' instruction.
+pub fn init_elevation_group(
+    program_id: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner: Pubkey,
+    elevation_group: ElevationGroup,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_owner, true),
+        ],
+        data: LendingInstruction::InitElevationGroup { elevation_group }.pack(),
+    }
+}
+
+/// Creates a 'ModifyElevationGroup' instruction.
+pub fn modify_elevation_group(
+    program_id: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner: Pubkey,
+    elevation_group: ElevationGroup,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_owner, true),
+        ],
+        data: LendingInstruction::ModifyElevationGroup { elevation_group }.pack(),
+    }
+}
+
+/// Creates a 'SetObligationElevationGroup' instruction.
+pub fn set_obligation_elevation_group(
+    program_id: Pubkey,
+    elevation_group: u8,
+    obligation_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    obligation_owner_pubkey: Pubkey,
+    reserve_pubkeys: Vec<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(obligation_pubkey, false),
+        AccountMeta::new_readonly(lending_market_pubkey, false),
+        AccountMeta::new(obligation_owner_pubkey, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(
+        reserve_pubkeys
+            .into_iter()
+            .map(|pubkey| AccountMeta::new_readonly(pubkey, false)),
+    );
+    Instruction {
+        program_id,
+        accounts,
+        data: LendingInstruction::SetObligationElevationGroup { elevation_group }.pack(),
+    }
+}
+
+/// Creates a 'SetLendingMarketConfig' instruction.
+pub fn set_lending_market_config(
+    program_id: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner: Pubkey,
+    liquidation_close_factor: Option<u8>,
+    liquidation_dust_value: Decimal,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_owner, true),
+        ],
+        data: LendingInstruction::SetLendingMarketConfig {
+            liquidation_close_factor,
+            liquidation_dust_value,
+        }
+        .pack(),
+    }
+}
+
+/// Creates a 'ForgiveDebt' instruction.
+pub fn forgive_debt(
+    program_id: Pubkey,
+    liquidity_amount: u64,
+    obligation_pubkey: Pubkey,
+    reserve_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(obligation_pubkey, false),
+            AccountMeta::new(reserve_pubkey, false),
+            AccountMeta::new_readonly(lending_market_pubkey, false),
+            AccountMeta::new_readonly(lending_market_owner, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: LendingInstruction::ForgiveDebt { liquidity_amount }.pack(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::BorrowRateCurvePoint;
 
     #[test]
     fn test_init_lending_market() {
@@ -1104,6 +1609,15 @@ mod tests {
                 flash_loan_fee_wad: 3,
                 host_fee_percentage: 1,
             },
+            borrow_rate_curve: BorrowRateCurve::LEGACY,
+            deposit_limit: 0,
+            borrow_limit: 0,
+            max_price_confidence_bps: 0,
+            max_price_age_slots: 0,
+            fallback_oracle_pubkey: Pubkey::default(),
+            reserve_factor: 0,
+            max_liquidation_bonus: 0,
+            elevation_group: 0,
         };
         let source_liquidity_pubkey = Pubkey::new_unique();
         let destination_collateral_pubkey = Pubkey::new_unique();
@@ -1158,6 +1672,19 @@ mod tests {
         assert_eq!(instruction.program_id, program_id);
         assert_eq!(instruction.accounts.len(), 3);
         assert_eq!(instruction.data, LendingInstruction::RefreshReserve.pack());
+
+        let reserve_fallback_oracle_pubkey = Pubkey::new_unique();
+        let instruction = refresh_reserve_with_fallback(
+            program_id,
+            reserve_pubkey,
+            reserve_liquidity_oracle_pubkey,
+            reserve_fallback_oracle_pubkey,
+        );
+        assert_eq!(instruction.accounts.len(), 4);
+        assert_eq!(
+            instruction.accounts[3],
+            AccountMeta::new_readonly(reserve_fallback_oracle_pubkey, false)
+        );
     }
 
     #[test]
@@ -1242,13 +1769,24 @@ mod tests {
         let program_id = Pubkey::new_unique();
         let obligation_pubkey = Pubkey::new_unique();
         let reserve_pubkeys = vec![Pubkey::new_unique()];
-        let instruction = refresh_obligation(program_id, obligation_pubkey, reserve_pubkeys);
+        let instruction =
+            refresh_obligation(program_id, obligation_pubkey, reserve_pubkeys.clone());
         assert_eq!(instruction.program_id, program_id);
         assert_eq!(instruction.accounts.len(), 3);
         assert_eq!(
             instruction.data,
             LendingInstruction::RefreshObligation.pack()
         );
+
+        let lending_market_pubkey = Pubkey::new_unique();
+        let instruction = refresh_obligation_with_lending_market(
+            program_id,
+            obligation_pubkey,
+            reserve_pubkeys,
+            lending_market_pubkey,
+        );
+        assert_eq!(instruction.accounts.len(), 4);
+        assert_eq!(instruction.accounts[3].pubkey, lending_market_pubkey);
     }
 
     #[test]
@@ -1461,6 +1999,29 @@ mod tests {
                 flash_loan_fee_wad: 3,
                 host_fee_percentage: 1,
             },
+            borrow_rate_curve: BorrowRateCurve::new(&[
+                BorrowRateCurvePoint {
+                    utilization_rate_bps: 0,
+                    borrow_rate_bps: 200,
+                },
+                BorrowRateCurvePoint {
+                    utilization_rate_bps: 6_000,
+                    borrow_rate_bps: 400,
+                },
+                BorrowRateCurvePoint {
+                    utilization_rate_bps: 10_000,
+                    borrow_rate_bps: 1_000,
+                },
+            ])
+            .unwrap(),
+            deposit_limit: 1_000_000,
+            borrow_limit: 500_000,
+            max_price_confidence_bps: 200,
+            max_price_age_slots: 25,
+            fallback_oracle_pubkey: Pubkey::new_unique(),
+            reserve_factor: 10,
+            max_liquidation_bonus: 20,
+            elevation_group: 1,
         };
         let reserve_pubkey = Pubkey::new_unique();
         let lending_market_pubkey = Pubkey::new_unique();
@@ -1478,5 +2039,167 @@ mod tests {
             instruction.data,
             LendingInstruction::ModifyReserveConfig { new_config: config }.pack()
         );
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::ModifyReserveConfig { new_config: config }
+        );
+
+        let fallback_pyth_product_pubkey = Pubkey::new_unique();
+        let instruction = modify_reserve_config_with_fallback(
+            program_id,
+            config,
+            reserve_pubkey,
+            lending_market_pubkey,
+            lending_market_owner_pubkey,
+            fallback_pyth_product_pubkey,
+        );
+        assert_eq!(instruction.accounts.len(), 4);
+        assert_eq!(
+            instruction.accounts[3],
+            AccountMeta::new_readonly(fallback_pyth_product_pubkey, false)
+        );
+    }
+
+    #[test]
+    fn test_redeem_fees() {
+        let program_id = Pubkey::new_unique();
+        let reserve_pubkey = Pubkey::new_unique();
+        let reserve_liquidity_fee_receiver_pubkey = Pubkey::new_unique();
+        let reserve_liquidity_supply_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let instruction = redeem_fees(
+            program_id,
+            reserve_pubkey,
+            reserve_liquidity_fee_receiver_pubkey,
+            reserve_liquidity_supply_pubkey,
+            lending_market_pubkey,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 7);
+        assert_eq!(instruction.data, LendingInstruction::RedeemFees.pack());
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::RedeemFees
+        );
+    }
+
+    #[test]
+    fn test_set_lending_market_config() {
+        let program_id = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let lending_market_owner = Pubkey::new_unique();
+        let liquidation_close_factor = Some(0);
+        let liquidation_dust_value = Decimal::from_percent(50);
+        let instruction = set_lending_market_config(
+            program_id,
+            lending_market_pubkey,
+            lending_market_owner,
+            liquidation_close_factor,
+            liquidation_dust_value,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 2);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::SetLendingMarketConfig {
+                liquidation_close_factor,
+                liquidation_dust_value,
+            }
+        );
+    }
+
+    #[test]
+    fn test_init_elevation_group() {
+        let program_id = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let lending_market_owner = Pubkey::new_unique();
+        let elevation_group = ElevationGroup {
+            id: 1,
+            loan_to_value_ratio: 90,
+            liquidation_threshold: 95,
+        };
+        let instruction = init_elevation_group(
+            program_id,
+            lending_market_pubkey,
+            lending_market_owner,
+            elevation_group,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 2);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::InitElevationGroup { elevation_group }
+        );
+    }
+
+    #[test]
+    fn test_modify_elevation_group() {
+        let program_id = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let lending_market_owner = Pubkey::new_unique();
+        let elevation_group = ElevationGroup {
+            id: 3,
+            loan_to_value_ratio: 80,
+            liquidation_threshold: 85,
+        };
+        let instruction = modify_elevation_group(
+            program_id,
+            lending_market_pubkey,
+            lending_market_owner,
+            elevation_group,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 2);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::ModifyElevationGroup { elevation_group }
+        );
+    }
+
+    #[test]
+    fn test_set_obligation_elevation_group() {
+        let program_id = Pubkey::new_unique();
+        let obligation_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let obligation_owner_pubkey = Pubkey::new_unique();
+        let reserve_pubkeys = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let instruction = set_obligation_elevation_group(
+            program_id,
+            1,
+            obligation_pubkey,
+            lending_market_pubkey,
+            obligation_owner_pubkey,
+            reserve_pubkeys,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 8);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::SetObligationElevationGroup { elevation_group: 1 }
+        );
+    }
+
+    #[test]
+    fn test_forgive_debt() {
+        let program_id = Pubkey::new_unique();
+        let liquidity_amount = u64::MAX;
+        let obligation_pubkey = Pubkey::new_unique();
+        let reserve_pubkey = Pubkey::new_unique();
+        let lending_market_pubkey = Pubkey::new_unique();
+        let lending_market_owner = Pubkey::new_unique();
+        let instruction = forgive_debt(
+            program_id,
+            liquidity_amount,
+            obligation_pubkey,
+            reserve_pubkey,
+            lending_market_pubkey,
+            lending_market_owner,
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 5);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::ForgiveDebt { liquidity_amount }
+        );
     }
 }
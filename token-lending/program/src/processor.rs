@@ -6,9 +6,9 @@ use crate::{
     math::{Decimal, Rate, TryAdd, TryDiv, TryMul},
     pyth,
     state::{
-        CalculateBorrowResult, CalculateLiquidationResult, CalculateRepayResult,
+        CalculateBorrowResult, CalculateLiquidationResult, CalculateRepayResult, ElevationGroup,
         InitLendingMarketParams, InitObligationParams, InitReserveParams, LendingMarket,
-        NewReserveCollateralParams, NewReserveLiquidityParams, Obligation, Reserve,
+        NewReserveCollateralParams, NewReserveLiquidityParams, Obligation, OracleAccount, Reserve,
         ReserveCollateral, ReserveConfig, ReserveLiquidity,
     },
 };
@@ -23,11 +23,11 @@ use solana_program::{
     program_error::{PrintProgramError, ProgramError},
     program_pack::{IsInitialized, Pack},
     pubkey::Pubkey,
+    system_instruction, system_program,
     sysvar::{clock::Clock, rent::Rent, Sysvar},
 };
 use spl_token::solana_program::instruction::AccountMeta;
 use spl_token::state::{Account, Mint};
-use std::convert::TryInto;
 
 /// Processes an instruction
 pub fn process_instruction(
@@ -111,6 +111,38 @@ pub fn process_instruction(
             msg!("Instruction: Modify Reserve Config");
             process_modify_reserve_config(program_id, new_config, accounts)
         }
+        LendingInstruction::RedeemFees => {
+            msg!("Instruction: Redeem Fees");
+            process_redeem_fees(program_id, accounts)
+        }
+        LendingInstruction::SetLendingMarketConfig {
+            liquidation_close_factor,
+            liquidation_dust_value,
+        } => {
+            msg!("Instruction: Set Lending Market Config");
+            process_set_lending_market_config(
+                program_id,
+                liquidation_close_factor,
+                liquidation_dust_value,
+                accounts,
+            )
+        }
+        LendingInstruction::InitElevationGroup { elevation_group } => {
+            msg!("Instruction: Init Elevation Group");
+            process_init_elevation_group(program_id, elevation_group, accounts)
+        }
+        LendingInstruction::ModifyElevationGroup { elevation_group } => {
+            msg!("Instruction: Modify Elevation Group");
+            process_modify_elevation_group(program_id, elevation_group, accounts)
+        }
+        LendingInstruction::SetObligationElevationGroup { elevation_group } => {
+            msg!("Instruction: Set Obligation Elevation Group");
+            process_set_obligation_elevation_group(program_id, elevation_group, accounts)
+        }
+        LendingInstruction::ForgiveDebt { liquidity_amount } => {
+            msg!("Instruction: Forgive Debt");
+            process_forgive_debt(program_id, liquidity_amount, accounts)
+        }
     }
 }
 
@@ -175,6 +207,104 @@ fn process_set_lending_market_owner(
     Ok(())
 }
 
+fn process_set_lending_market_config(
+    program_id: &Pubkey,
+    liquidation_close_factor: Option<u8>,
+    liquidation_dust_value: Decimal,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    if liquidation_close_factor.map_or(false, |close_factor| close_factor > 100) {
+        msg!("Liquidation close factor must be in range [0, 100]");
+        return Err(LendingError::InvalidConfig.into());
+    }
+    if liquidation_dust_value.to_scaled_val().is_err() {
+        msg!("Liquidation dust value is too large to be stored in the lending market");
+        return Err(LendingError::InvalidConfig.into());
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_owner_info = next_account_info(account_info_iter)?;
+
+    let mut lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &lending_market.owner != lending_market_owner_info.key {
+        msg!("Lending market owner does not match the lending market owner provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_owner_info.is_signer {
+        msg!("Lending market owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    lending_market.liquidation_close_factor = liquidation_close_factor;
+    lending_market.liquidation_dust_value = liquidation_dust_value;
+    LendingMarket::pack(lending_market, &mut lending_market_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+fn process_init_elevation_group(
+    program_id: &Pubkey,
+    elevation_group: ElevationGroup,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_owner_info = next_account_info(account_info_iter)?;
+
+    let mut lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &lending_market.owner != lending_market_owner_info.key {
+        msg!("Lending market owner does not match the lending market owner provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_owner_info.is_signer {
+        msg!("Lending market owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    lending_market.add_elevation_group(elevation_group)?;
+    LendingMarket::pack(lending_market, &mut lending_market_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+fn process_modify_elevation_group(
+    program_id: &Pubkey,
+    elevation_group: ElevationGroup,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_owner_info = next_account_info(account_info_iter)?;
+
+    let mut lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &lending_market.owner != lending_market_owner_info.key {
+        msg!("Lending market owner does not match the lending market owner provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_owner_info.is_signer {
+        msg!("Lending market owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    lending_market.modify_elevation_group(elevation_group)?;
+    LendingMarket::pack(lending_market, &mut lending_market_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
 fn process_init_reserve(
     program_id: &Pubkey,
     liquidity_amount: u64,
@@ -238,48 +368,30 @@ fn process_init_reserve(
         return Err(LendingError::InvalidSigner.into());
     }
 
-    if &lending_market.oracle_program_id != pyth_product_info.owner {
-        msg!("Pyth product account provided is not owned by the lending market oracle program");
-        return Err(LendingError::InvalidOracleConfig.into());
+    if config.elevation_group != 0
+        && lending_market
+            .find_elevation_group(config.elevation_group)
+            .is_none()
+    {
+        msg!("Reserve elevation group does not exist in the lending market");
+        return Err(LendingError::InvalidElevationGroup.into());
     }
+
+    assert_pyth_product(&lending_market, pyth_product_info, pyth_price_info.key)?;
     if &lending_market.oracle_program_id != pyth_price_info.owner {
         msg!("Pyth price account provided is not owned by the lending market oracle program");
         return Err(LendingError::InvalidOracleConfig.into());
     }
-
-    let pyth_product_data = pyth_product_info.try_borrow_data()?;
-    let pyth_product = pyth::load::<pyth::Product>(&pyth_product_data)
-        .map_err(|_| ProgramError::InvalidAccountData)?;
-    if pyth_product.magic != pyth::MAGIC {
-        msg!("Pyth product account provided is not a valid Pyth account");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-    if pyth_product.ver != pyth::VERSION_2 {
-        msg!("Pyth product account provided has a different version than expected");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-    if pyth_product.atype != pyth::AccountType::Product as u32 {
-        msg!("Pyth product account provided is not a valid Pyth product account");
-        return Err(LendingError::InvalidOracleConfig.into());
+    if let Some(fallback_oracle_pubkey) = config.fallback_oracle() {
+        let fallback_pyth_product_info = next_account_info(account_info_iter)?;
+        assert_pyth_product(
+            &lending_market,
+            fallback_pyth_product_info,
+            &fallback_oracle_pubkey,
+        )?;
     }
 
-    let pyth_price_pubkey_bytes: &[u8; 32] = pyth_price_info
-        .key
-        .as_ref()
-        .try_into()
-        .map_err(|_| LendingError::InvalidAccountInput)?;
-    if &pyth_product.px_acc.val != pyth_price_pubkey_bytes {
-        msg!("Pyth product price account does not match the Pyth price provided");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-
-    let quote_currency = get_pyth_product_quote_currency(pyth_product)?;
-    if lending_market.quote_currency != quote_currency {
-        msg!("Lending market quote currency does not match the oracle quote currency");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-
-    let market_price = get_pyth_price(pyth_price_info, clock)?;
+    let market_price = pyth::get_price(&pyth_price_info.try_borrow_data()?, &config, clock.slot)?;
 
     let authority_signer_seeds = &[
         lending_market_info.key.as_ref(),
@@ -383,7 +495,7 @@ fn process_init_reserve(
 }
 
 fn process_refresh_reserve(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter().peekable();
+    let account_info_iter = &mut accounts.iter();
     let reserve_info = next_account_info(account_info_iter)?;
     let reserve_liquidity_oracle_info = next_account_info(account_info_iter)?;
     let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
@@ -393,15 +505,27 @@ fn process_refresh_reserve(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
         msg!("Reserve provided is not owned by the lending program");
         return Err(LendingError::InvalidAccountOwner.into());
     }
-    if &reserve.liquidity.oracle_pubkey != reserve_liquidity_oracle_info.key {
-        msg!("Reserve liquidity oracle does not match the reserve liquidity oracle provided");
-        return Err(LendingError::InvalidAccountInput.into());
-    }
 
-    reserve.liquidity.market_price = get_pyth_price(reserve_liquidity_oracle_info, clock)?;
-
-    reserve.accrue_interest(clock.slot)?;
-    reserve.last_update.update_slot(clock.slot);
+    let reserve_fallback_oracle_info = account_info_iter.next();
+    let oracle_data = reserve_liquidity_oracle_info.try_borrow_data()?;
+    let fallback_oracle_data = reserve_fallback_oracle_info
+        .map(|account_info| account_info.try_borrow_data())
+        .transpose()?;
+    reserve.refresh(
+        OracleAccount {
+            pubkey: reserve_liquidity_oracle_info.key,
+            owner: reserve_liquidity_oracle_info.owner,
+            data: &oracle_data,
+        },
+        reserve_fallback_oracle_info
+            .zip(fallback_oracle_data.as_ref())
+            .map(|(account_info, data)| OracleAccount {
+                pubkey: account_info.key,
+                owner: account_info.owner,
+                data,
+            }),
+        clock.slot,
+    )?;
     Reserve::pack(reserve, &mut reserve_info.data.borrow_mut())?;
 
     Ok(())
@@ -468,6 +592,10 @@ fn process_deposit_reserve_liquidity(
         msg!("Reserve is stale and must be refreshed in the current slot");
         return Err(LendingError::ReserveStale.into());
     }
+    if Decimal::from(liquidity_amount) > reserve.remaining_deposit_capacity()? {
+        msg!("Liquidity amount provided would exceed the reserve deposit limit");
+        return Err(LendingError::DepositLimitExceeded.into());
+    }
 
     let authority_signer_seeds = &[
         lending_market_info.key.as_ref(),
@@ -658,18 +786,14 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
     let obligation_info = next_account_info(account_info_iter)?;
     let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
 
-    let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
+    let mut obligation = Obligation::unpack_account(&obligation_info.data.borrow())?;
     if obligation_info.owner != program_id {
         msg!("Obligation provided is not owned by the lending program");
         return Err(LendingError::InvalidAccountOwner.into());
     }
 
-    let mut deposited_value = Decimal::zero();
-    let mut borrowed_value = Decimal::zero();
-    let mut allowed_borrow_value = Decimal::zero();
-    let mut unhealthy_borrow_value = Decimal::zero();
-
-    for (index, collateral) in obligation.deposits.iter_mut().enumerate() {
+    let mut deposit_reserves = Vec::with_capacity(obligation.deposits.len());
+    for (index, collateral) in obligation.deposits.iter().enumerate() {
         let deposit_reserve_info = next_account_info(account_info_iter)?;
         if deposit_reserve_info.owner != program_id {
             msg!(
@@ -694,31 +818,11 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
             );
             return Err(LendingError::ReserveStale.into());
         }
-
-        // @TODO: add lookup table https://git.io/JOCYq
-        let decimals = 10u64
-            .checked_pow(deposit_reserve.liquidity.mint_decimals as u32)
-            .ok_or(LendingError::MathOverflow)?;
-
-        let market_value = deposit_reserve
-            .collateral_exchange_rate()?
-            .decimal_collateral_to_liquidity(collateral.deposited_amount.into())?
-            .try_mul(deposit_reserve.liquidity.market_price)?
-            .try_div(decimals)?;
-        collateral.market_value = market_value;
-
-        let loan_to_value_rate = Rate::from_percent(deposit_reserve.config.loan_to_value_ratio);
-        let liquidation_threshold_rate =
-            Rate::from_percent(deposit_reserve.config.liquidation_threshold);
-
-        deposited_value = deposited_value.try_add(market_value)?;
-        allowed_borrow_value =
-            allowed_borrow_value.try_add(market_value.try_mul(loan_to_value_rate)?)?;
-        unhealthy_borrow_value =
-            unhealthy_borrow_value.try_add(market_value.try_mul(liquidation_threshold_rate)?)?;
+        deposit_reserves.push(deposit_reserve);
     }
 
-    for (index, liquidity) in obligation.borrows.iter_mut().enumerate() {
+    let mut borrow_reserves = Vec::with_capacity(obligation.borrows.len());
+    for (index, liquidity) in obligation.borrows.iter().enumerate() {
         let borrow_reserve_info = next_account_info(account_info_iter)?;
         if borrow_reserve_info.owner != program_id {
             msg!(
@@ -743,21 +847,170 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
             );
             return Err(LendingError::ReserveStale.into());
         }
+        borrow_reserves.push(borrow_reserve);
+    }
 
-        liquidity.accrue_interest(borrow_reserve.liquidity.cumulative_borrow_rate_wads)?;
+    let lending_market = if obligation.elevation_group != 0 {
+        let lending_market_info = next_account_info(account_info_iter)?;
+        if lending_market_info.owner != program_id {
+            msg!("Lending market provided is not owned by the lending program");
+            return Err(LendingError::InvalidAccountOwner.into());
+        }
+        if &obligation.lending_market != lending_market_info.key {
+            msg!("Obligation lending market does not match the lending market provided");
+            return Err(LendingError::InvalidAccountInput.into());
+        }
+        Some(LendingMarket::unpack(&lending_market_info.data.borrow())?)
+    } else {
+        None
+    };
 
-        // @TODO: add lookup table https://git.io/JOCYq
-        let decimals = 10u64
-            .checked_pow(borrow_reserve.liquidity.mint_decimals as u32)
-            .ok_or(LendingError::MathOverflow)?;
+    if account_info_iter.peek().is_some() {
+        msg!("Too many obligation deposit or borrow reserves provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+
+    obligation.refresh_values(&deposit_reserves, &borrow_reserves, lending_market.as_ref())?;
+
+    obligation.last_update.update_slot(clock.slot);
+    obligation.pack_account(&mut obligation_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+#[inline(never)] // avoid stack frame limit
+fn process_set_obligation_elevation_group(
+    program_id: &Pubkey,
+    elevation_group: u8,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().peekable();
+    let obligation_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let obligation_owner_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+    let rent = &Rent::from_account_info(next_account_info(account_info_iter)?)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+
+    let mut obligation = Obligation::unpack_account(&obligation_info.data.borrow())?;
+    if obligation_info.owner != program_id {
+        msg!("Obligation provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &obligation.lending_market != lending_market_info.key {
+        msg!("Obligation lending market does not match the lending market provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if &obligation.owner != obligation_owner_info.key {
+        msg!("Obligation owner does not match the obligation owner provided");
+        return Err(LendingError::InvalidObligationOwner.into());
+    }
+    if !obligation_owner_info.is_signer {
+        msg!("Obligation owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+    if obligation.last_update.is_stale(clock.slot)? {
+        msg!("Obligation is stale and must be refreshed in the current slot");
+        return Err(LendingError::ObligationStale.into());
+    }
 
-        let market_value = liquidity
-            .borrowed_amount_wads
-            .try_mul(borrow_reserve.liquidity.market_price)?
-            .try_div(decimals)?;
-        liquidity.market_value = market_value;
+    let group = if elevation_group == 0 {
+        None
+    } else {
+        let group = lending_market
+            .find_elevation_group(elevation_group)
+            .ok_or_else(|| {
+                msg!("Elevation group {} does not exist", elevation_group);
+                LendingError::InvalidElevationGroup
+            })?;
+        Some(*group)
+    };
+
+    let mut allowed_borrow_value = Decimal::zero();
+    let mut unhealthy_borrow_value = Decimal::zero();
+
+    for (index, collateral) in obligation.deposits.iter().enumerate() {
+        let deposit_reserve_info = next_account_info(account_info_iter)?;
+        if deposit_reserve_info.owner != program_id {
+            msg!(
+                "Deposit reserve provided for collateral {} is not owned by the lending program",
+                index
+            );
+            return Err(LendingError::InvalidAccountOwner.into());
+        }
+        if collateral.deposit_reserve != *deposit_reserve_info.key {
+            msg!(
+                "Deposit reserve of collateral {} does not match the deposit reserve provided",
+                index
+            );
+            return Err(LendingError::InvalidAccountInput.into());
+        }
+
+        let deposit_reserve = Reserve::unpack(&deposit_reserve_info.data.borrow())?;
+        let (loan_to_value_ratio, liquidation_threshold) = match group {
+            Some(group) => {
+                if deposit_reserve.config.elevation_group != group.id {
+                    msg!(
+                        "Deposit reserve provided for collateral {} does not belong to elevation group {}",
+                        index,
+                        group.id
+                    );
+                    return Err(LendingError::ElevationGroupMismatch.into());
+                }
+                (group.loan_to_value_ratio, group.liquidation_threshold)
+            }
+            None => (
+                deposit_reserve.config.loan_to_value_ratio,
+                deposit_reserve.config.liquidation_threshold,
+            ),
+        };
+
+        allowed_borrow_value = allowed_borrow_value.try_add(
+            collateral
+                .market_value
+                .try_mul(Rate::from_percent(loan_to_value_ratio))?,
+        )?;
+        unhealthy_borrow_value = unhealthy_borrow_value.try_add(
+            collateral
+                .market_value
+                .try_mul(Rate::from_percent(liquidation_threshold))?,
+        )?;
+    }
+
+    for (index, liquidity) in obligation.borrows.iter().enumerate() {
+        let borrow_reserve_info = next_account_info(account_info_iter)?;
+        if borrow_reserve_info.owner != program_id {
+            msg!(
+                "Borrow reserve provided for liquidity {} is not owned by the lending program",
+                index
+            );
+            return Err(LendingError::InvalidAccountOwner.into());
+        }
+        if liquidity.borrow_reserve != *borrow_reserve_info.key {
+            msg!(
+                "Borrow reserve of liquidity {} does not match the borrow reserve provided",
+                index
+            );
+            return Err(LendingError::InvalidAccountInput.into());
+        }
 
-        borrowed_value = borrowed_value.try_add(market_value)?;
+        if let Some(group) = group {
+            let borrow_reserve = Reserve::unpack(&borrow_reserve_info.data.borrow())?;
+            if borrow_reserve.config.elevation_group != group.id {
+                msg!(
+                    "Borrow reserve provided for liquidity {} does not belong to elevation group {}",
+                    index,
+                    group.id
+                );
+                return Err(LendingError::ElevationGroupMismatch.into());
+            }
+        }
     }
 
     if account_info_iter.peek().is_some() {
@@ -765,13 +1018,24 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
         return Err(LendingError::InvalidAccountInput.into());
     }
 
-    obligation.deposited_value = deposited_value;
-    obligation.borrowed_value = borrowed_value;
+    if obligation.borrowed_value > allowed_borrow_value {
+        msg!("Obligation borrowed value would exceed its allowed borrow value");
+        return Err(LendingError::BorrowTooLarge.into());
+    }
+
+    if elevation_group != 0 && obligation_info.data_len() < Obligation::LEN {
+        resize_legacy_obligation(
+            obligation_info,
+            obligation_owner_info,
+            system_program_info,
+            rent,
+        )?;
+    }
+
+    obligation.elevation_group = elevation_group;
     obligation.allowed_borrow_value = allowed_borrow_value;
     obligation.unhealthy_borrow_value = unhealthy_borrow_value;
-
-    obligation.last_update.update_slot(clock.slot);
-    Obligation::pack(obligation, &mut obligation_info.data.borrow_mut())?;
+    obligation.pack_account(&mut obligation_info.data.borrow_mut())?;
 
     Ok(())
 }
@@ -836,7 +1100,7 @@ fn process_deposit_obligation_collateral(
         return Err(LendingError::ReserveCollateralDisabled.into());
     }
 
-    let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
+    let mut obligation = Obligation::unpack_account(&obligation_info.data.borrow())?;
     if obligation_info.owner != program_id {
         msg!("Obligation provided is not owned by the lending program");
         return Err(LendingError::InvalidAccountOwner.into());
@@ -854,11 +1118,18 @@ fn process_deposit_obligation_collateral(
         return Err(LendingError::InvalidSigner.into());
     }
 
+    if obligation.elevation_group != 0
+        && deposit_reserve.config.elevation_group != obligation.elevation_group
+    {
+        msg!("Deposit reserve does not belong to the obligation elevation group");
+        return Err(LendingError::ElevationGroupMismatch.into());
+    }
+
     obligation
         .find_or_add_collateral_to_deposits(*deposit_reserve_info.key)?
         .deposit(collateral_amount)?;
     obligation.last_update.mark_stale();
-    Obligation::pack(obligation, &mut obligation_info.data.borrow_mut())?;
+    obligation.pack_account(&mut obligation_info.data.borrow_mut())?;
 
     spl_token_transfer(TokenTransferParams {
         source: source_collateral_info.clone(),
@@ -926,7 +1197,7 @@ fn process_withdraw_obligation_collateral(
         return Err(LendingError::ReserveStale.into());
     }
 
-    let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
+    let mut obligation = Obligation::unpack_account(&obligation_info.data.borrow())?;
     if obligation_info.owner != program_id {
         msg!("Obligation provided is not owned by the lending program");
         return Err(LendingError::InvalidAccountOwner.into());
@@ -978,9 +1249,15 @@ fn process_withdraw_obligation_collateral(
         msg!("Obligation deposited value is zero");
         return Err(LendingError::ObligationDepositsZero.into());
     } else {
-        let max_withdraw_value = obligation.max_withdraw_value(Rate::from_percent(
-            withdraw_reserve.config.loan_to_value_ratio,
-        ))?;
+        let withdraw_collateral_ltv =
+            match lending_market.find_elevation_group(obligation.elevation_group) {
+                Some(group) if withdraw_reserve.config.elevation_group == group.id => {
+                    group.loan_to_value_ratio
+                }
+                _ => withdraw_reserve.config.loan_to_value_ratio,
+            };
+        let max_withdraw_value =
+            obligation.max_withdraw_value(Rate::from_percent(withdraw_collateral_ltv))?;
         if max_withdraw_value == Decimal::zero() {
             msg!("Maximum withdraw value is zero");
             return Err(LendingError::WithdrawTooLarge.into());
@@ -1013,7 +1290,7 @@ fn process_withdraw_obligation_collateral(
 
     obligation.withdraw(withdraw_amount, collateral_index)?;
     obligation.last_update.mark_stale();
-    Obligation::pack(obligation, &mut obligation_info.data.borrow_mut())?;
+    obligation.pack_account(&mut obligation_info.data.borrow_mut())?;
 
     spl_token_transfer(TokenTransferParams {
         source: source_collateral_info.clone(),
@@ -1089,7 +1366,7 @@ fn process_borrow_obligation_liquidity(
         return Err(LendingError::ReserveStale.into());
     }
 
-    let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
+    let mut obligation = Obligation::unpack_account(&obligation_info.data.borrow())?;
     if obligation_info.owner != program_id {
         msg!("Obligation provided is not owned by the lending program");
         return Err(LendingError::InvalidAccountOwner.into());
@@ -1118,6 +1395,12 @@ fn process_borrow_obligation_liquidity(
         msg!("Obligation deposits have zero value");
         return Err(LendingError::ObligationDepositsZero.into());
     }
+    if obligation.elevation_group != 0
+        && borrow_reserve.config.elevation_group != obligation.elevation_group
+    {
+        msg!("Borrow reserve does not belong to the obligation elevation group");
+        return Err(LendingError::ElevationGroupMismatch.into());
+    }
 
     let authority_signer_seeds = &[
         lending_market_info.key.as_ref(),
@@ -1155,6 +1438,11 @@ fn process_borrow_obligation_liquidity(
         return Err(LendingError::ExceededSlippage.into());
     }
 
+    if borrow_amount > borrow_reserve.remaining_borrow_capacity()? {
+        msg!("Borrow amount would exceed the reserve borrow limit");
+        return Err(LendingError::BorrowLimitExceeded.into());
+    }
+
     borrow_reserve.liquidity.borrow(borrow_amount)?;
     borrow_reserve.last_update.mark_stale();
     Reserve::pack(borrow_reserve, &mut borrow_reserve_info.data.borrow_mut())?;
@@ -1163,7 +1451,7 @@ fn process_borrow_obligation_liquidity(
         .find_or_add_liquidity_to_borrows(*borrow_reserve_info.key)?
         .borrow(borrow_amount)?;
     obligation.last_update.mark_stale();
-    Obligation::pack(obligation, &mut obligation_info.data.borrow_mut())?;
+    obligation.pack_account(&mut obligation_info.data.borrow_mut())?;
 
     let mut owner_fee = borrow_fee;
     if let Ok(host_fee_receiver_info) = next_account_info(account_info_iter) {
@@ -1258,7 +1546,7 @@ fn process_repay_obligation_liquidity(
         return Err(LendingError::ReserveStale.into());
     }
 
-    let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
+    let mut obligation = Obligation::unpack_account(&obligation_info.data.borrow())?;
     if obligation_info.owner != program_id {
         msg!("Obligation provided is not owned by the lending program");
         return Err(LendingError::InvalidAccountOwner.into());
@@ -1295,7 +1583,7 @@ fn process_repay_obligation_liquidity(
 
     obligation.repay(settle_amount, liquidity_index)?;
     obligation.last_update.mark_stale();
-    Obligation::pack(obligation, &mut obligation_info.data.borrow_mut())?;
+    obligation.pack_account(&mut obligation_info.data.borrow_mut())?;
 
     spl_token_transfer(TokenTransferParams {
         source: source_liquidity_info.clone(),
@@ -1398,7 +1686,7 @@ fn process_liquidate_obligation(
         return Err(LendingError::ReserveStale.into());
     }
 
-    let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
+    let mut obligation = Obligation::unpack_account(&obligation_info.data.borrow())?;
     if obligation_info.owner != program_id {
         msg!("Obligation provided is not owned by the lending program");
         return Err(LendingError::InvalidAccountOwner.into());
@@ -1460,6 +1748,7 @@ fn process_liquidate_obligation(
         &obligation,
         liquidity,
         collateral,
+        &lending_market,
     )?;
 
     if repay_amount == 0 {
@@ -1478,7 +1767,7 @@ fn process_liquidate_obligation(
     obligation.repay(settle_amount, liquidity_index)?;
     obligation.withdraw(withdraw_amount, collateral_index)?;
     obligation.last_update.mark_stale();
-    Obligation::pack(obligation, &mut obligation_info.data.borrow_mut())?;
+    obligation.pack_account(&mut obligation_info.data.borrow_mut())?;
 
     spl_token_transfer(TokenTransferParams {
         source: source_liquidity_info.clone(),
@@ -1568,12 +1857,19 @@ fn process_flash_loan(
         return Err(LendingError::InvalidAccountInput.into());
     }
 
+    let remaining_borrow_capacity = reserve.remaining_borrow_capacity()?;
     // @FIXME: if u64::MAX is flash loaned, fees should be inclusive as with ordinary borrows
     let flash_loan_amount = if liquidity_amount == u64::MAX {
-        reserve.liquidity.available_amount
+        Decimal::from(reserve.liquidity.available_amount)
+            .min(remaining_borrow_capacity)
+            .try_floor_u64()?
     } else {
         liquidity_amount
     };
+    if Decimal::from(flash_loan_amount) > remaining_borrow_capacity {
+        msg!("Flash loan amount would exceed the reserve borrow limit");
+        return Err(LendingError::BorrowLimitExceeded.into());
+    }
 
     let flash_loan_amount_decimal = Decimal::from(flash_loan_amount);
     let (origination_fee, host_fee) = reserve
@@ -1711,6 +2007,15 @@ fn process_modify_reserve_config(
         return Err(LendingError::InvalidSigner.into());
     }
 
+    if new_config.elevation_group != 0
+        && lending_market
+            .find_elevation_group(new_config.elevation_group)
+            .is_none()
+    {
+        msg!("Reserve elevation group does not exist in the lending market");
+        return Err(LendingError::InvalidElevationGroup.into());
+    }
+
     let mut reserve = Reserve::unpack(&reserve_info.data.borrow_mut())?;
     // Validate that the reserve account corresponds to the correct lending market,
     // after validating above that the lending market and lending market owner correspond,
@@ -1720,6 +2025,17 @@ fn process_modify_reserve_config(
         return Err(LendingError::InvalidAccountInput.into());
     }
 
+    if let Some(fallback_oracle_pubkey) = new_config.fallback_oracle() {
+        if reserve.config.fallback_oracle() != Some(fallback_oracle_pubkey) {
+            let fallback_pyth_product_info = next_account_info(account_info_iter)?;
+            assert_pyth_product(
+                &lending_market,
+                fallback_pyth_product_info,
+                &fallback_oracle_pubkey,
+            )?;
+        }
+    }
+
     reserve.config = new_config;
 
     Reserve::pack(reserve, &mut reserve_info.data.borrow_mut())?;
@@ -1727,6 +2043,202 @@ fn process_modify_reserve_config(
     Ok(())
 }
 
+fn process_redeem_fees(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reserve_info = next_account_info(account_info_iter)?;
+    let reserve_liquidity_fee_receiver_info = next_account_info(account_info_iter)?;
+    let reserve_liquidity_supply_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_authority_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+    let token_program_id = next_account_info(account_info_iter)?;
+
+    let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &lending_market.token_program_id != token_program_id.key {
+        msg!("Lending market token program does not match the token program provided");
+        return Err(LendingError::InvalidTokenProgram.into());
+    }
+
+    let mut reserve = Reserve::unpack(&reserve_info.data.borrow())?;
+    if reserve_info.owner != program_id {
+        msg!("Reserve provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &reserve.lending_market != lending_market_info.key {
+        msg!("Reserve lending market does not match the lending market provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if &reserve.liquidity.fee_receiver != reserve_liquidity_fee_receiver_info.key {
+        msg!("Reserve liquidity fee receiver does not match the reserve liquidity fee receiver provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if &reserve.liquidity.supply_pubkey != reserve_liquidity_supply_info.key {
+        msg!("Reserve liquidity supply does not match the reserve liquidity supply provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if reserve.last_update.is_stale(clock.slot)? {
+        msg!("Reserve is stale and must be refreshed in the current slot");
+        return Err(LendingError::ReserveStale.into());
+    }
+
+    let authority_signer_seeds = &[
+        lending_market_info.key.as_ref(),
+        &[lending_market.bump_seed],
+    ];
+    let lending_market_authority_pubkey =
+        Pubkey::create_program_address(authority_signer_seeds, program_id)?;
+    if &lending_market_authority_pubkey != lending_market_authority_info.key {
+        msg!(
+            "Derived lending market authority does not match the lending market authority provided"
+        );
+        return Err(LendingError::InvalidMarketAuthority.into());
+    }
+
+    let fee_amount = reserve.liquidity.redeem_fees()?;
+    reserve.last_update.mark_stale();
+    Reserve::pack(reserve, &mut reserve_info.data.borrow_mut())?;
+
+    if fee_amount > 0 {
+        spl_token_transfer(TokenTransferParams {
+            source: reserve_liquidity_supply_info.clone(),
+            destination: reserve_liquidity_fee_receiver_info.clone(),
+            amount: fee_amount,
+            authority: lending_market_authority_info.clone(),
+            authority_signer_seeds,
+            token_program: token_program_id.clone(),
+        })?;
+    }
+
+    Ok(())
+}
+
+fn process_forgive_debt(
+    program_id: &Pubkey,
+    liquidity_amount: u64,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    if liquidity_amount == 0 {
+        msg!("Liquidity amount provided cannot be zero");
+        return Err(LendingError::InvalidAmount.into());
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let obligation_info = next_account_info(account_info_iter)?;
+    let reserve_info = next_account_info(account_info_iter)?;
+    let lending_market_info = next_account_info(account_info_iter)?;
+    let lending_market_owner_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+
+    let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
+    if lending_market_info.owner != program_id {
+        msg!("Lending market provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &lending_market.owner != lending_market_owner_info.key {
+        msg!("Lending market owner does not match the lending market owner provided");
+        return Err(LendingError::InvalidMarketOwner.into());
+    }
+    if !lending_market_owner_info.is_signer {
+        msg!("Lending market owner provided must be a signer");
+        return Err(LendingError::InvalidSigner.into());
+    }
+
+    let mut reserve = Reserve::unpack(&reserve_info.data.borrow())?;
+    if reserve_info.owner != program_id {
+        msg!("Reserve provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &reserve.lending_market != lending_market_info.key {
+        msg!("Reserve lending market does not match the lending market provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if reserve.last_update.is_stale(clock.slot)? {
+        msg!("Reserve is stale and must be refreshed in the current slot");
+        return Err(LendingError::ReserveStale.into());
+    }
+
+    let mut obligation = Obligation::unpack_account(&obligation_info.data.borrow())?;
+    if obligation_info.owner != program_id {
+        msg!("Obligation provided is not owned by the lending program");
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    if &obligation.lending_market != lending_market_info.key {
+        msg!("Obligation lending market does not match the lending market provided");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+    if obligation.last_update.is_stale(clock.slot)? {
+        msg!("Obligation is stale and must be refreshed in the current slot");
+        return Err(LendingError::ObligationStale.into());
+    }
+    if !obligation.deposits.is_empty() {
+        msg!("Obligation debt can only be forgiven once all of its collateral is liquidated");
+        return Err(LendingError::ObligationDepositsNotEmpty.into());
+    }
+
+    let (liquidity, liquidity_index) = obligation.find_liquidity_in_borrows(*reserve_info.key)?;
+    if liquidity.borrowed_amount_wads == Decimal::zero() {
+        msg!("Liquidity borrowed amount is zero");
+        return Err(LendingError::ObligationLiquidityEmpty.into());
+    }
+
+    let CalculateRepayResult { settle_amount, .. } =
+        reserve.calculate_repay(liquidity_amount, liquidity.borrowed_amount_wads)?;
+
+    let previous_exchange_rate = Rate::from(reserve.collateral_exchange_rate()?);
+    reserve.liquidity.forgive_debt(settle_amount)?;
+    let exchange_rate = Rate::from(reserve.collateral_exchange_rate()?);
+    reserve.last_update.mark_stale();
+    Reserve::pack(reserve, &mut reserve_info.data.borrow_mut())?;
+
+    obligation.repay(settle_amount, liquidity_index)?;
+    obligation.last_update.mark_stale();
+    obligation.pack_account(&mut obligation_info.data.borrow_mut())?;
+
+    msg!(
+        "Debt forgiven: obligation {}, reserve {}, liquidity amount {}, collateral exchange rate {} -> {}",
+        obligation_info.key,
+        reserve_info.key,
+        settle_amount,
+        previous_exchange_rate,
+        exchange_rate
+    );
+
+    Ok(())
+}
+
+/// Grow an obligation account created with the legacy length to the current length, with the
+/// obligation owner paying for the additional rent
+fn resize_legacy_obligation<'a>(
+    obligation_info: &AccountInfo<'a>,
+    obligation_owner_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    rent: &Rent,
+) -> ProgramResult {
+    if system_program_info.key != &system_program::id() {
+        msg!("System program provided does not match the system program id");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+
+    let lamports = rent
+        .minimum_balance(Obligation::LEN)
+        .saturating_sub(obligation_info.lamports());
+    if lamports > 0 {
+        invoke(
+            &system_instruction::transfer(obligation_owner_info.key, obligation_info.key, lamports),
+            &[
+                obligation_owner_info.clone(),
+                obligation_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    }
+    obligation_info.realloc(Obligation::LEN, true)
+}
+
 fn assert_rent_exempt(rent: &Rent, account_info: &AccountInfo) -> ProgramResult {
     if !rent.is_exempt(account_info.lamports(), account_info.data_len()) {
         msg!(&rent.minimum_balance(account_info.data_len()).to_string());
@@ -1752,6 +2264,45 @@ fn unpack_mint(data: &[u8]) -> Result<Mint, LendingError> {
     Mint::unpack(data).map_err(|_| LendingError::InvalidTokenMint)
 }
 
+fn assert_pyth_product(
+    lending_market: &LendingMarket,
+    pyth_product_info: &AccountInfo,
+    pyth_price_pubkey: &Pubkey,
+) -> ProgramResult {
+    if &lending_market.oracle_program_id != pyth_product_info.owner {
+        msg!("Pyth product account provided is not owned by the lending market oracle program");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+
+    let pyth_product_data = pyth_product_info.try_borrow_data()?;
+    let pyth_product = pyth::load::<pyth::Product>(&pyth_product_data)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if pyth_product.magic != pyth::MAGIC {
+        msg!("Pyth product account provided is not a valid Pyth account");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+    if pyth_product.ver != pyth::VERSION_2 {
+        msg!("Pyth product account provided has a different version than expected");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+    if pyth_product.atype != pyth::AccountType::Product as u32 {
+        msg!("Pyth product account provided is not a valid Pyth product account");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+    if pyth_product.px_acc.val != pyth_price_pubkey.to_bytes() {
+        msg!("Pyth product price account does not match the Pyth price provided");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+
+    let quote_currency = get_pyth_product_quote_currency(pyth_product)?;
+    if lending_market.quote_currency != quote_currency {
+        msg!("Lending market quote currency does not match the oracle quote currency");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+
+    Ok(())
+}
+
 fn get_pyth_product_quote_currency(pyth_product: &pyth::Product) -> Result<[u8; 32], ProgramError> {
     const LEN: usize = 14;
     const KEY: &[u8; LEN] = b"quote_currency";
@@ -1794,62 +2345,6 @@ fn get_pyth_product_quote_currency(pyth_product: &pyth::Product) -> Result<[u8;
     Err(LendingError::InvalidOracleConfig.into())
 }
 
-fn get_pyth_price(pyth_price_info: &AccountInfo, clock: &Clock) -> Result<Decimal, ProgramError> {
-    const STALE_AFTER_SLOTS_ELAPSED: u64 = 5;
-
-    let pyth_price_data = pyth_price_info.try_borrow_data()?;
-    let pyth_price = pyth::load::<pyth::Price>(&pyth_price_data)
-        .map_err(|_| ProgramError::InvalidAccountData)?;
-
-    if pyth_price.ptype != pyth::PriceType::Price {
-        msg!("Oracle price type is invalid");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-
-    if pyth_price.agg.status != pyth::PriceStatus::Trading {
-        msg!("Oracle price status is invalid");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-
-    let slots_elapsed = clock
-        .slot
-        .checked_sub(pyth_price.valid_slot)
-        .ok_or(LendingError::MathOverflow)?;
-    if slots_elapsed >= STALE_AFTER_SLOTS_ELAPSED {
-        msg!("Oracle price is stale");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-
-    let price: u64 = pyth_price.agg.price.try_into().map_err(|_| {
-        msg!("Oracle price cannot be negative");
-        LendingError::InvalidOracleConfig
-    })?;
-
-    let market_price = if pyth_price.expo >= 0 {
-        let exponent = pyth_price
-            .expo
-            .try_into()
-            .map_err(|_| LendingError::MathOverflow)?;
-        let zeros = 10u64
-            .checked_pow(exponent)
-            .ok_or(LendingError::MathOverflow)?;
-        Decimal::from(price).try_mul(zeros)?
-    } else {
-        let exponent = pyth_price
-            .expo
-            .checked_abs()
-            .ok_or(LendingError::MathOverflow)?
-            .try_into()
-            .map_err(|_| LendingError::MathOverflow)?;
-        let decimals = 10u64
-            .checked_pow(exponent)
-            .ok_or(LendingError::MathOverflow)?;
-        Decimal::from(price).try_div(decimals)?
-    };
-
-    Ok(market_price)
-}
-
 /// Issue a spl_token `InitializeAccount` instruction.
 #[inline(always)]
 fn spl_token_init_account(params: TokenInitializeAccountParams<'_>) -> ProgramResult {
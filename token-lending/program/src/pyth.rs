@@ -1,10 +1,16 @@
 #![allow(missing_docs)]
 /// Derived from https://github.com/project-serum/anchor/blob/9224e0fa99093943a6190e396bccbc3387e5b230/examples/pyth/programs/pyth/src/pc.rs
+use crate::{
+    error::LendingError,
+    math::{Decimal, TryDiv, TryMul},
+    state::ReserveConfig,
+};
 use bytemuck::{
     cast_slice, cast_slice_mut, from_bytes, from_bytes_mut, try_cast_slice, try_cast_slice_mut,
     Pod, PodCastError, Zeroable,
 };
-use std::mem::size_of;
+use solana_program::{clock::Slot, entrypoint::ProgramResult, msg, program_error::ProgramError};
+use std::{convert::TryInto, mem::size_of};
 
 pub const MAGIC: u32 = 0xa1b2c3d4;
 pub const VERSION_2: u32 = 2;
@@ -133,3 +139,88 @@ pub fn load_mut<T: Pod>(data: &mut [u8]) -> Result<&mut T, PodCastError> {
         try_cast_slice_mut(&mut data[0..size])?,
     )))
 }
+
+/// Market price of a Pyth price account in `slot`, rejecting prices that are not trading,
+/// not positive, stale or too uncertain for the reserve config
+pub fn get_price(data: &[u8], config: &ReserveConfig, slot: Slot) -> Result<Decimal, ProgramError> {
+    if data.len() < size_of::<Price>() {
+        msg!("Oracle price account is too small");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let pyth_price = load::<Price>(data).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    if pyth_price.ptype != PriceType::Price {
+        msg!("Oracle price type is invalid");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+
+    if pyth_price.agg.status != PriceStatus::Trading {
+        msg!("Oracle price status is invalid");
+        return Err(LendingError::OraclePriceNotTrading.into());
+    }
+
+    assert_price_fresh(pyth_price.valid_slot, config, slot)?;
+
+    let price: u64 = pyth_price.agg.price.try_into().map_err(|_| {
+        msg!("Oracle price cannot be negative");
+        LendingError::OraclePriceNotPositive
+    })?;
+    if price == 0 {
+        msg!("Oracle price cannot be zero");
+        return Err(LendingError::OraclePriceNotPositive.into());
+    }
+
+    assert_price_confident(price as u128, pyth_price.agg.conf as u128, config)?;
+
+    let market_price = if pyth_price.expo >= 0 {
+        let exponent = pyth_price
+            .expo
+            .try_into()
+            .map_err(|_| LendingError::MathOverflow)?;
+        let zeros = 10u64
+            .checked_pow(exponent)
+            .ok_or(LendingError::MathOverflow)?;
+        Decimal::from(price).try_mul(zeros)?
+    } else {
+        let exponent = pyth_price
+            .expo
+            .checked_abs()
+            .ok_or(LendingError::MathOverflow)?
+            .try_into()
+            .map_err(|_| LendingError::MathOverflow)?;
+        let decimals = 10u64
+            .checked_pow(exponent)
+            .ok_or(LendingError::MathOverflow)?;
+        Decimal::from(price).try_div(decimals)?
+    };
+
+    Ok(market_price)
+}
+
+fn assert_price_fresh(price_slot: Slot, config: &ReserveConfig, slot: Slot) -> ProgramResult {
+    let slots_elapsed = slot
+        .checked_sub(price_slot)
+        .ok_or(LendingError::MathOverflow)?;
+    if slots_elapsed >= config.effective_max_price_age_slots() {
+        msg!("Oracle price is stale");
+        return Err(LendingError::OraclePriceStale.into());
+    }
+    Ok(())
+}
+
+fn assert_price_confident(price: u128, confidence: u128, config: &ReserveConfig) -> ProgramResult {
+    if config.max_price_confidence_bps == 0 {
+        return Ok(());
+    }
+    let confidence_bps = confidence
+        .checked_mul(10_000)
+        .ok_or(LendingError::MathOverflow)?;
+    let max_confidence_bps = price
+        .checked_mul(config.max_price_confidence_bps as u128)
+        .ok_or(LendingError::MathOverflow)?;
+    if confidence_bps > max_confidence_bps {
+        msg!("Oracle price confidence interval is too wide");
+        return Err(LendingError::OraclePriceConfidenceTooWide.into());
+    }
+    Ok(())
+}
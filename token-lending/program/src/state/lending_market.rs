@@ -1,12 +1,20 @@
 use super::*;
+use crate::{
+    error::LendingError,
+    math::{Decimal, Rate},
+};
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use solana_program::{
+    entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
     pubkey::{Pubkey, PUBKEY_BYTES},
 };
 
+/// Maximum number of elevation groups in a lending market
+pub const MAX_ELEVATION_GROUPS: usize = 8;
+
 /// Lending market state
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct LendingMarket {
@@ -23,6 +31,14 @@ pub struct LendingMarket {
     pub token_program_id: Pubkey,
     /// Oracle (Pyth) program id
     pub oracle_program_id: Pubkey,
+    /// Percentage of an obligation's borrowed value that can be repaid during each
+    /// liquidation call. None to use the default close factor of 50%
+    pub liquidation_close_factor: Option<u8>,
+    /// Borrowed value at or below which an obligation can be fully repaid in a single
+    /// liquidation call, regardless of the close factor
+    pub liquidation_dust_value: Decimal,
+    /// Groups of correlated reserves with their own borrowing parameters, unique by id
+    pub elevation_groups: [ElevationGroup; MAX_ELEVATION_GROUPS],
 }
 
 impl LendingMarket {
@@ -42,6 +58,93 @@ impl LendingMarket {
         self.token_program_id = params.token_program_id;
         self.oracle_program_id = params.oracle_program_id;
     }
+
+    /// Close factor applied to liquidations of the market's obligations
+    pub fn effective_liquidation_close_factor(&self) -> Rate {
+        Rate::from_percent(
+            self.liquidation_close_factor
+                .unwrap_or(LIQUIDATION_CLOSE_FACTOR),
+        )
+    }
+
+    /// Find an elevation group by id
+    pub fn find_elevation_group(&self, id: u8) -> Option<&ElevationGroup> {
+        if id == 0 {
+            return None;
+        }
+        self.elevation_groups.iter().find(|group| group.id == id)
+    }
+
+    /// Add a new elevation group
+    pub fn add_elevation_group(&mut self, elevation_group: ElevationGroup) -> ProgramResult {
+        elevation_group.validate()?;
+        if self.find_elevation_group(elevation_group.id).is_some() {
+            msg!("Elevation group {} already exists", elevation_group.id);
+            return Err(LendingError::InvalidElevationGroup.into());
+        }
+        let unused_group = self
+            .elevation_groups
+            .iter_mut()
+            .find(|group| group.id == 0)
+            .ok_or_else(|| {
+                msg!(
+                    "Lending market cannot have more than {} elevation groups",
+                    MAX_ELEVATION_GROUPS
+                );
+                LendingError::InvalidElevationGroup
+            })?;
+        *unused_group = elevation_group;
+        Ok(())
+    }
+
+    /// Replace the parameters of an existing elevation group
+    pub fn modify_elevation_group(&mut self, elevation_group: ElevationGroup) -> ProgramResult {
+        elevation_group.validate()?;
+        let group = self
+            .elevation_groups
+            .iter_mut()
+            .find(|group| group.id == elevation_group.id)
+            .ok_or_else(|| {
+                msg!("Elevation group {} does not exist", elevation_group.id);
+                LendingError::InvalidElevationGroup
+            })?;
+        *group = elevation_group;
+        Ok(())
+    }
+}
+
+/// Group of correlated reserves, e.g. SOL and staked SOL. Obligations whose deposits and
+/// borrows all belong to the group can borrow with the group parameters instead of the
+/// reserve ones
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ElevationGroup {
+    /// Elevation group id, 0 if the group is unused
+    pub id: u8,
+    /// Target ratio of the value of borrows to deposits, as a percentage
+    pub loan_to_value_ratio: u8,
+    /// Loan to value ratio at which an obligation can be liquidated, as a percentage
+    pub liquidation_threshold: u8,
+}
+
+impl ElevationGroup {
+    /// Validate the elevation group, when adding or modifying it
+    pub fn validate(&self) -> ProgramResult {
+        if self.id == 0 {
+            msg!("Elevation group id must be in range [1, 255]");
+            return Err(LendingError::InvalidElevationGroup.into());
+        }
+        if self.loan_to_value_ratio >= 100 {
+            msg!("Loan to value ratio must be in range [0, 100)");
+            return Err(LendingError::InvalidConfig.into());
+        }
+        if self.liquidation_threshold <= self.loan_to_value_ratio
+            || self.liquidation_threshold > 100
+        {
+            msg!("Liquidation threshold must be in range (LTV, 100]");
+            return Err(LendingError::InvalidConfig.into());
+        }
+        Ok(())
+    }
 }
 
 /// Initialize a lending market
@@ -66,7 +169,8 @@ impl IsInitialized for LendingMarket {
     }
 }
 
-const LENDING_MARKET_LEN: usize = 258; // 1 + 1 + 32 + 32 + 32 + 32 + 128
+const ELEVATION_GROUP_LEN: usize = 3; // 1 + 1 + 1
+const LENDING_MARKET_LEN: usize = 258; // 1 + 1 + 32 + 32 + 32 + 32 + 1 + 1 + 16 + (3 * 8) + 86
 impl Pack for LendingMarket {
     const LEN: usize = LENDING_MARKET_LEN;
 
@@ -80,6 +184,10 @@ impl Pack for LendingMarket {
             quote_currency,
             token_program_id,
             oracle_program_id,
+            liquidation_close_factor_flag,
+            liquidation_close_factor,
+            liquidation_dust_value,
+            elevation_groups_flat,
             _padding,
         ) = mut_array_refs![
             output,
@@ -89,7 +197,11 @@ impl Pack for LendingMarket {
             32,
             PUBKEY_BYTES,
             PUBKEY_BYTES,
-            128
+            1,
+            1,
+            16,
+            ELEVATION_GROUP_LEN * MAX_ELEVATION_GROUPS,
+            86
         ];
 
         *version = self.version.to_le_bytes();
@@ -98,6 +210,31 @@ impl Pack for LendingMarket {
         quote_currency.copy_from_slice(self.quote_currency.as_ref());
         token_program_id.copy_from_slice(self.token_program_id.as_ref());
         oracle_program_id.copy_from_slice(self.oracle_program_id.as_ref());
+        match self.liquidation_close_factor {
+            Some(close_factor) => {
+                *liquidation_close_factor_flag = [1];
+                *liquidation_close_factor = close_factor.to_le_bytes();
+            }
+            None => {
+                *liquidation_close_factor_flag = [0];
+                *liquidation_close_factor = [0];
+            }
+        }
+        pack_decimal(self.liquidation_dust_value, liquidation_dust_value);
+
+        for (index, elevation_group) in self.elevation_groups.iter().enumerate() {
+            let elevation_group_flat = array_mut_ref![
+                elevation_groups_flat,
+                index * ELEVATION_GROUP_LEN,
+                ELEVATION_GROUP_LEN
+            ];
+            #[allow(clippy::ptr_offset_with_cast)]
+            let (id, loan_to_value_ratio, liquidation_threshold) =
+                mut_array_refs![elevation_group_flat, 1, 1, 1];
+            *id = elevation_group.id.to_le_bytes();
+            *loan_to_value_ratio = elevation_group.loan_to_value_ratio.to_le_bytes();
+            *liquidation_threshold = elevation_group.liquidation_threshold.to_le_bytes();
+        }
     }
 
     /// Unpacks a byte buffer into a [LendingMarketInfo](struct.LendingMarketInfo.html)
@@ -111,6 +248,10 @@ impl Pack for LendingMarket {
             quote_currency,
             token_program_id,
             oracle_program_id,
+            liquidation_close_factor_flag,
+            liquidation_close_factor,
+            liquidation_dust_value,
+            elevation_groups_flat,
             _padding,
         ) = array_refs![
             input,
@@ -120,7 +261,11 @@ impl Pack for LendingMarket {
             32,
             PUBKEY_BYTES,
             PUBKEY_BYTES,
-            128
+            1,
+            1,
+            16,
+            ELEVATION_GROUP_LEN * MAX_ELEVATION_GROUPS,
+            86
         ];
 
         let version = u8::from_le_bytes(*version);
@@ -129,6 +274,32 @@ impl Pack for LendingMarket {
             return Err(ProgramError::InvalidAccountData);
         }
 
+        let liquidation_close_factor = match liquidation_close_factor_flag {
+            [0] => None,
+            [1] => Some(u8::from_le_bytes(*liquidation_close_factor)),
+            _ => {
+                msg!("Lending market liquidation close factor flag is invalid");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        };
+
+        let mut elevation_groups = [ElevationGroup::default(); MAX_ELEVATION_GROUPS];
+        for (index, elevation_group) in elevation_groups.iter_mut().enumerate() {
+            let elevation_group_flat = array_ref![
+                elevation_groups_flat,
+                index * ELEVATION_GROUP_LEN,
+                ELEVATION_GROUP_LEN
+            ];
+            #[allow(clippy::ptr_offset_with_cast)]
+            let (id, loan_to_value_ratio, liquidation_threshold) =
+                array_refs![elevation_group_flat, 1, 1, 1];
+            *elevation_group = ElevationGroup {
+                id: u8::from_le_bytes(*id),
+                loan_to_value_ratio: u8::from_le_bytes(*loan_to_value_ratio),
+                liquidation_threshold: u8::from_le_bytes(*liquidation_threshold),
+            };
+        }
+
         Ok(Self {
             version,
             bump_seed: u8::from_le_bytes(*bump_seed),
@@ -136,6 +307,107 @@ impl Pack for LendingMarket {
             quote_currency: *quote_currency,
             token_program_id: Pubkey::new_from_array(*token_program_id),
             oracle_program_id: Pubkey::new_from_array(*oracle_program_id),
+            liquidation_close_factor,
+            liquidation_dust_value: unpack_decimal(liquidation_dust_value),
+            elevation_groups,
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn elevation_group(id: u8) -> ElevationGroup {
+        ElevationGroup {
+            id,
+            loan_to_value_ratio: 90,
+            liquidation_threshold: 95,
+        }
+    }
+
+    #[test]
+    fn pack_and_unpack_lending_market() {
+        let mut lending_market = LendingMarket {
+            version: PROGRAM_VERSION,
+            liquidation_close_factor: Some(20),
+            liquidation_dust_value: Decimal::from(5u64),
+            ..LendingMarket::default()
+        };
+        lending_market
+            .add_elevation_group(elevation_group(1))
+            .unwrap();
+        lending_market
+            .add_elevation_group(elevation_group(7))
+            .unwrap();
+
+        let mut data = [0u8; LENDING_MARKET_LEN];
+        lending_market.pack_into_slice(&mut data);
+        assert_eq!(
+            LendingMarket::unpack_from_slice(&data).unwrap(),
+            lending_market
+        );
+    }
+
+    #[test]
+    fn add_and_modify_elevation_groups() {
+        let mut lending_market = LendingMarket::default();
+        assert_eq!(lending_market.find_elevation_group(0), None);
+        assert_eq!(lending_market.find_elevation_group(1), None);
+
+        lending_market
+            .add_elevation_group(elevation_group(1))
+            .unwrap();
+        assert_eq!(
+            lending_market.find_elevation_group(1),
+            Some(&elevation_group(1))
+        );
+        assert_eq!(
+            lending_market.add_elevation_group(elevation_group(1)),
+            Err(LendingError::InvalidElevationGroup.into())
+        );
+
+        let modified_group = ElevationGroup {
+            loan_to_value_ratio: 80,
+            ..elevation_group(1)
+        };
+        lending_market
+            .modify_elevation_group(modified_group)
+            .unwrap();
+        assert_eq!(
+            lending_market.find_elevation_group(1),
+            Some(&modified_group)
+        );
+        assert_eq!(
+            lending_market.modify_elevation_group(elevation_group(2)),
+            Err(LendingError::InvalidElevationGroup.into())
+        );
+
+        for id in 2..=MAX_ELEVATION_GROUPS as u8 {
+            lending_market
+                .add_elevation_group(elevation_group(id))
+                .unwrap();
+        }
+        assert_eq!(
+            lending_market.add_elevation_group(elevation_group(u8::MAX)),
+            Err(LendingError::InvalidElevationGroup.into())
+        );
+    }
+
+    #[test]
+    fn validate_elevation_group() {
+        assert_eq!(
+            elevation_group(0).validate(),
+            Err(LendingError::InvalidElevationGroup.into())
+        );
+        assert_eq!(
+            ElevationGroup {
+                liquidation_threshold: 90,
+                ..elevation_group(1)
+            }
+            .validate(),
+            Err(LendingError::InvalidConfig.into())
+        );
+        assert_eq!(elevation_group(1).validate(), Ok(()));
+    }
+}
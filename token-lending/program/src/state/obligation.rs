@@ -43,6 +43,8 @@ pub struct Obligation {
     pub allowed_borrow_value: Decimal,
     /// The dangerous borrow value at the weighted average liquidation threshold
     pub unhealthy_borrow_value: Decimal,
+    /// Elevation group of the lending market the obligation borrows in, 0 if none
+    pub elevation_group: u8,
 }
 
 impl Obligation {
@@ -63,6 +65,85 @@ impl Obligation {
         self.borrows = params.borrows;
     }
 
+    /// Revalue deposits and borrows at their reserve market prices and recalculate the borrow
+    /// limits. `deposit_reserves` and `borrow_reserves` must line up with `deposits` and
+    /// `borrows`, and `lending_market` is required if the obligation is in an elevation group
+    pub fn refresh_values(
+        &mut self,
+        deposit_reserves: &[Reserve],
+        borrow_reserves: &[Reserve],
+        lending_market: Option<&LendingMarket>,
+    ) -> ProgramResult {
+        if deposit_reserves.len() != self.deposits.len()
+            || borrow_reserves.len() != self.borrows.len()
+        {
+            msg!("Obligation deposit and borrow reserves do not match the reserves provided");
+            return Err(LendingError::InvalidAccountInput.into());
+        }
+
+        let mut deposited_value = Decimal::zero();
+        let mut borrowed_value = Decimal::zero();
+        let mut allowed_borrow_value = Decimal::zero();
+        let mut unhealthy_borrow_value = Decimal::zero();
+        let mut all_reserves_in_elevation_group = self.elevation_group != 0;
+
+        for (collateral, deposit_reserve) in self.deposits.iter_mut().zip(deposit_reserves) {
+            collateral.update_market_value(deposit_reserve)?;
+            let market_value = collateral.market_value;
+
+            let loan_to_value_rate = Rate::from_percent(deposit_reserve.config.loan_to_value_ratio);
+            let liquidation_threshold_rate =
+                Rate::from_percent(deposit_reserve.config.liquidation_threshold);
+            all_reserves_in_elevation_group &=
+                deposit_reserve.config.elevation_group == self.elevation_group;
+
+            deposited_value = deposited_value.try_add(market_value)?;
+            allowed_borrow_value =
+                allowed_borrow_value.try_add(market_value.try_mul(loan_to_value_rate)?)?;
+            unhealthy_borrow_value = unhealthy_borrow_value
+                .try_add(market_value.try_mul(liquidation_threshold_rate)?)?;
+        }
+
+        for (liquidity, borrow_reserve) in self.borrows.iter_mut().zip(borrow_reserves) {
+            liquidity.update_market_value(borrow_reserve)?;
+
+            borrowed_value = borrowed_value.try_add(liquidity.market_value)?;
+            all_reserves_in_elevation_group &=
+                borrow_reserve.config.elevation_group == self.elevation_group;
+        }
+
+        if self.elevation_group != 0 {
+            let lending_market = lending_market.ok_or_else(|| {
+                msg!("Lending market is required for an obligation in an elevation group");
+                LendingError::InvalidAccountInput
+            })?;
+            let elevation_group = lending_market
+                .find_elevation_group(self.elevation_group)
+                .ok_or_else(|| {
+                    msg!(
+                        "Obligation elevation group {} does not exist",
+                        self.elevation_group
+                    );
+                    LendingError::InvalidElevationGroup
+                })?;
+
+            // Reserves moved out of the group since the obligation joined it fall back to the
+            // reserve parameters for the whole obligation
+            if all_reserves_in_elevation_group {
+                allowed_borrow_value = deposited_value
+                    .try_mul(Rate::from_percent(elevation_group.loan_to_value_ratio))?;
+                unhealthy_borrow_value = deposited_value
+                    .try_mul(Rate::from_percent(elevation_group.liquidation_threshold))?;
+            }
+        }
+
+        self.deposited_value = deposited_value;
+        self.borrowed_value = borrowed_value;
+        self.allowed_borrow_value = allowed_borrow_value;
+        self.unhealthy_borrow_value = unhealthy_borrow_value;
+        Ok(())
+    }
+
     /// Calculate the current ratio of borrowed value to deposited value
     pub fn loan_to_value(&self) -> Result<Decimal, ProgramError> {
         self.borrowed_value.try_div(self.deposited_value)
@@ -111,19 +192,47 @@ impl Obligation {
         self.allowed_borrow_value.try_sub(self.borrowed_value)
     }
 
-    /// Calculate the maximum liquidation amount for a given liquidity
+    /// Calculate the maximum liquidation amount for a given liquidity. Obligations with a
+    /// borrowed value at or below the dust value can be closed out in full
     pub fn max_liquidation_amount(
         &self,
         liquidity: &ObligationLiquidity,
+        close_factor: Rate,
+        dust_value: Decimal,
     ) -> Result<Decimal, ProgramError> {
+        if self.borrowed_value <= dust_value {
+            return Ok(liquidity.borrowed_amount_wads);
+        }
         let max_liquidation_value = self
             .borrowed_value
-            .try_mul(Rate::from_percent(LIQUIDATION_CLOSE_FACTOR))?
+            .try_mul(close_factor)?
             .min(liquidity.market_value);
         let max_liquidation_pct = max_liquidation_value.try_div(liquidity.market_value)?;
         liquidity.borrowed_amount_wads.try_mul(max_liquidation_pct)
     }
 
+    /// Calculate the liquidation bonus of an unhealthy obligation. The bonus scales linearly
+    /// from the min bonus when the borrowed value reaches the unhealthy borrow value, to the max
+    /// bonus when the borrowed value reaches the deposited value
+    pub fn liquidation_bonus(
+        &self,
+        min_bonus: Rate,
+        max_bonus: Rate,
+    ) -> Result<Rate, ProgramError> {
+        if max_bonus <= min_bonus || self.borrowed_value <= self.unhealthy_borrow_value {
+            return Ok(min_bonus);
+        }
+        if self.borrowed_value >= self.deposited_value {
+            return Ok(max_bonus);
+        }
+        let insolvency_pct = Rate::try_from(
+            self.borrowed_value
+                .try_sub(self.unhealthy_borrow_value)?
+                .try_div(self.deposited_value.try_sub(self.unhealthy_borrow_value)?)?,
+        )?;
+        min_bonus.try_add(max_bonus.try_sub(min_bonus)?.try_mul(insolvency_pct)?)
+    }
+
     /// Find collateral by deposit reserve
     pub fn find_collateral_in_deposits(
         &self,
@@ -266,6 +375,21 @@ impl ObligationCollateral {
             .ok_or(LendingError::MathOverflow)?;
         Ok(())
     }
+
+    /// Value the deposited collateral at the deposit reserve market price
+    pub fn update_market_value(&mut self, deposit_reserve: &Reserve) -> ProgramResult {
+        // @TODO: add lookup table https://git.io/JOCYq
+        let decimals = 10u64
+            .checked_pow(deposit_reserve.liquidity.mint_decimals as u32)
+            .ok_or(LendingError::MathOverflow)?;
+
+        self.market_value = deposit_reserve
+            .collateral_exchange_rate()?
+            .decimal_collateral_to_liquidity(self.deposited_amount.into())?
+            .try_mul(deposit_reserve.liquidity.market_price)?
+            .try_div(decimals)?;
+        Ok(())
+    }
 }
 
 /// Obligation liquidity state
@@ -304,6 +428,22 @@ impl ObligationLiquidity {
         Ok(())
     }
 
+    /// Accrue interest and value the borrowed liquidity at the borrow reserve market price
+    pub fn update_market_value(&mut self, borrow_reserve: &Reserve) -> ProgramResult {
+        self.accrue_interest(borrow_reserve.liquidity.cumulative_borrow_rate_wads)?;
+
+        // @TODO: add lookup table https://git.io/JOCYq
+        let decimals = 10u64
+            .checked_pow(borrow_reserve.liquidity.mint_decimals as u32)
+            .ok_or(LendingError::MathOverflow)?;
+
+        self.market_value = self
+            .borrowed_amount_wads
+            .try_mul(borrow_reserve.liquidity.market_price)?
+            .try_div(decimals)?;
+        Ok(())
+    }
+
     /// Accrue interest
     pub fn accrue_interest(&mut self, cumulative_borrow_rate_wads: Decimal) -> ProgramResult {
         match cumulative_borrow_rate_wads.cmp(&self.cumulative_borrow_rate_wads) {
@@ -330,7 +470,7 @@ impl ObligationLiquidity {
 
 const OBLIGATION_COLLATERAL_LEN: usize = 56; // 32 + 8 + 16
 const OBLIGATION_LIQUIDITY_LEN: usize = 80; // 32 + 16 + 16 + 16
-const OBLIGATION_LEN: usize = 916; // 1 + 8 + 1 + 32 + 32 + 16 + 16 + 16 + 16 + 1 + 1 + (56 * 1) + (80 * 9)
+const OBLIGATION_LEN: usize = 980; // 1 + 8 + 1 + 32 + 32 + 16 + 16 + 16 + 16 + 1 + 1 + (56 * 1) + (80 * 9) + 1 + 63
                                    // @TODO: break this up by obligation / collateral / liquidity https://git.io/JOCca
 impl Pack for Obligation {
     const LEN: usize = OBLIGATION_LEN;
@@ -351,6 +491,8 @@ impl Pack for Obligation {
             deposits_len,
             borrows_len,
             data_flat,
+            elevation_group,
+            _padding,
         ) = mut_array_refs![
             output,
             1,
@@ -364,7 +506,9 @@ impl Pack for Obligation {
             16,
             1,
             1,
-            OBLIGATION_COLLATERAL_LEN + (OBLIGATION_LIQUIDITY_LEN * (MAX_OBLIGATION_RESERVES - 1))
+            OBLIGATION_COLLATERAL_LEN + (OBLIGATION_LIQUIDITY_LEN * (MAX_OBLIGATION_RESERVES - 1)),
+            1,
+            63
         ];
 
         // obligation
@@ -379,6 +523,7 @@ impl Pack for Obligation {
         pack_decimal(self.unhealthy_borrow_value, unhealthy_borrow_value);
         *deposits_len = u8::try_from(self.deposits.len()).unwrap().to_le_bytes();
         *borrows_len = u8::try_from(self.borrows.len()).unwrap().to_le_bytes();
+        *elevation_group = self.elevation_group.to_le_bytes();
 
         let mut offset = 0;
 
@@ -428,6 +573,8 @@ impl Pack for Obligation {
             deposits_len,
             borrows_len,
             data_flat,
+            elevation_group,
+            _padding,
         ) = array_refs![
             input,
             1,
@@ -441,7 +588,9 @@ impl Pack for Obligation {
             16,
             1,
             1,
-            OBLIGATION_COLLATERAL_LEN + (OBLIGATION_LIQUIDITY_LEN * (MAX_OBLIGATION_RESERVES - 1))
+            OBLIGATION_COLLATERAL_LEN + (OBLIGATION_LIQUIDITY_LEN * (MAX_OBLIGATION_RESERVES - 1)),
+            1,
+            63
         ];
 
         let version = u8::from_le_bytes(*version);
@@ -496,10 +645,45 @@ impl Pack for Obligation {
             borrowed_value: unpack_decimal(borrowed_value),
             allowed_borrow_value: unpack_decimal(allowed_borrow_value),
             unhealthy_borrow_value: unpack_decimal(unhealthy_borrow_value),
+            elevation_group: u8::from_le_bytes(*elevation_group),
         })
     }
 }
 
+/// Length of obligation accounts created before elevation groups were added. Their layout is a
+/// prefix of the current one, read as an obligation outside of any elevation group
+pub const LEGACY_OBLIGATION_LEN: usize = 916;
+
+impl Obligation {
+    /// Unpack an obligation account, which may still have the legacy length
+    pub fn unpack_account(input: &[u8]) -> Result<Self, ProgramError> {
+        if input.len() == LEGACY_OBLIGATION_LEN {
+            let mut data = [0u8; OBLIGATION_LEN];
+            data[..LEGACY_OBLIGATION_LEN].copy_from_slice(input);
+            Self::unpack(&data)
+        } else {
+            Self::unpack(input)
+        }
+    }
+
+    /// Pack an obligation into its account, which may keep the legacy length as long as the
+    /// obligation is outside of any elevation group
+    pub fn pack_account(self, dst: &mut [u8]) -> ProgramResult {
+        if dst.len() == LEGACY_OBLIGATION_LEN {
+            if self.elevation_group != 0 {
+                msg!("Obligation account must be resized before joining an elevation group");
+                return Err(LendingError::InvalidAccountInput.into());
+            }
+            let mut data = [0u8; OBLIGATION_LEN];
+            Self::pack(self, &mut data)?;
+            dst.copy_from_slice(&data[..LEGACY_OBLIGATION_LEN]);
+            Ok(())
+        } else {
+            Self::pack(self, dst)
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -539,6 +723,128 @@ mod test {
         );
     }
 
+    #[test]
+    fn pack_and_unpack_legacy_obligation() {
+        let mut obligation = Obligation {
+            version: PROGRAM_VERSION,
+            deposits: vec![ObligationCollateral::new(Pubkey::new_unique())],
+            ..Obligation::default()
+        };
+
+        let mut data = [0u8; LEGACY_OBLIGATION_LEN];
+        obligation.clone().pack_account(&mut data).unwrap();
+        assert_eq!(Obligation::unpack_account(&data).unwrap(), obligation);
+
+        obligation.elevation_group = 1;
+        assert_eq!(
+            obligation.clone().pack_account(&mut data),
+            Err(LendingError::InvalidAccountInput.into())
+        );
+
+        let mut data = [0u8; OBLIGATION_LEN];
+        obligation.clone().pack_account(&mut data).unwrap();
+        assert_eq!(Obligation::unpack_account(&data).unwrap(), obligation);
+    }
+
+    #[test]
+    fn refresh_values_elevation_group() {
+        let reserve_config = ReserveConfig {
+            loan_to_value_ratio: 50,
+            liquidation_threshold: 60,
+            elevation_group: 1,
+            ..ReserveConfig::default()
+        };
+        let deposit_reserve = Reserve {
+            liquidity: ReserveLiquidity {
+                market_price: Decimal::from(2u64),
+                ..ReserveLiquidity::default()
+            },
+            config: reserve_config,
+            ..Reserve::default()
+        };
+        let borrow_reserve = Reserve {
+            liquidity: ReserveLiquidity {
+                market_price: Decimal::one(),
+                cumulative_borrow_rate_wads: Decimal::one(),
+                ..ReserveLiquidity::default()
+            },
+            config: reserve_config,
+            ..Reserve::default()
+        };
+        let mut lending_market = LendingMarket::default();
+        lending_market
+            .add_elevation_group(ElevationGroup {
+                id: 1,
+                loan_to_value_ratio: 80,
+                liquidation_threshold: 90,
+            })
+            .unwrap();
+
+        let mut obligation = Obligation {
+            deposits: vec![ObligationCollateral {
+                deposited_amount: 100,
+                ..ObligationCollateral::default()
+            }],
+            borrows: vec![ObligationLiquidity {
+                cumulative_borrow_rate_wads: Decimal::one(),
+                borrowed_amount_wads: Decimal::from(60u64),
+                ..ObligationLiquidity::default()
+            }],
+            ..Obligation::default()
+        };
+
+        // without an elevation group the reserve parameters apply
+        obligation
+            .refresh_values(
+                &[deposit_reserve.clone()],
+                &[borrow_reserve.clone()],
+                Some(&lending_market),
+            )
+            .unwrap();
+        assert_eq!(obligation.deposits[0].market_value, Decimal::from(200u64));
+        assert_eq!(obligation.borrows[0].market_value, Decimal::from(60u64));
+        assert_eq!(obligation.deposited_value, Decimal::from(200u64));
+        assert_eq!(obligation.borrowed_value, Decimal::from(60u64));
+        assert_eq!(obligation.allowed_borrow_value, Decimal::from(100u64));
+        assert_eq!(obligation.unhealthy_borrow_value, Decimal::from(120u64));
+
+        obligation.elevation_group = 1;
+        assert_eq!(
+            obligation.refresh_values(&[deposit_reserve.clone()], &[borrow_reserve.clone()], None),
+            Err(LendingError::InvalidAccountInput.into())
+        );
+        obligation
+            .refresh_values(
+                &[deposit_reserve.clone()],
+                &[borrow_reserve.clone()],
+                Some(&lending_market),
+            )
+            .unwrap();
+        assert_eq!(obligation.allowed_borrow_value, Decimal::from(160u64));
+        assert_eq!(obligation.unhealthy_borrow_value, Decimal::from(180u64));
+
+        // a reserve outside the group falls back to the reserve parameters
+        let mut borrow_reserve = borrow_reserve;
+        borrow_reserve.config.elevation_group = 0;
+        obligation
+            .refresh_values(&[deposit_reserve], &[borrow_reserve], Some(&lending_market))
+            .unwrap();
+        assert_eq!(obligation.allowed_borrow_value, Decimal::from(100u64));
+        assert_eq!(obligation.unhealthy_borrow_value, Decimal::from(120u64));
+    }
+
+    #[test]
+    fn refresh_values_reserves_mismatch() {
+        let mut obligation = Obligation {
+            deposits: vec![ObligationCollateral::default()],
+            ..Obligation::default()
+        };
+        assert_eq!(
+            obligation.refresh_values(&[], &[], None),
+            Err(LendingError::InvalidAccountInput.into())
+        );
+    }
+
     // Creates rates (r1, r2) where 0 < r1 <= r2 <= 100*r1
     prop_compose! {
         fn cumulative_rates()(rate in 1..=u128::MAX)(
@@ -570,7 +876,146 @@ mod test {
         }
     }
 
+    // Creates obligation values (deposited, unhealthy, borrowed) where unhealthy <= deposited
+    prop_compose! {
+        fn obligation_values()(deposited_value in 1..=u32::MAX as u64)(
+            deposited_value in Just(deposited_value),
+            unhealthy_borrow_value in 0..=deposited_value,
+            borrowed_value in 0..=deposited_value * 2,
+        ) -> (u64, u64, u64) {
+            (deposited_value, unhealthy_borrow_value, borrowed_value)
+        }
+    }
+
+    // Creates liquidation bonuses (min, max) where min <= max
+    prop_compose! {
+        fn liquidation_bonuses()(min_bonus in 0..=100u8)(
+            min_bonus in Just(min_bonus),
+            max_bonus in min_bonus..=100u8,
+        ) -> (u8, u8) {
+            (min_bonus, max_bonus)
+        }
+    }
+
+    // Creates obligation values (borrowed, liquidity market value) where market value <= borrowed
+    prop_compose! {
+        fn liquidation_values()(borrowed_value in 1..=u32::MAX as u64)(
+            borrowed_value in Just(borrowed_value),
+            market_value in 1..=borrowed_value,
+        ) -> (u64, u64) {
+            (borrowed_value, market_value)
+        }
+    }
+
     proptest! {
+        #[test]
+        fn liquidation_bonus_bounds(
+            (deposited_value, unhealthy_borrow_value, borrowed_value) in obligation_values(),
+            (min_bonus, max_bonus) in liquidation_bonuses(),
+        ) {
+            let obligation = Obligation {
+                deposited_value: Decimal::from(deposited_value),
+                unhealthy_borrow_value: Decimal::from(unhealthy_borrow_value),
+                borrowed_value: Decimal::from(borrowed_value),
+                ..Obligation::default()
+            };
+            let min_bonus = Rate::from_percent(min_bonus);
+            let max_bonus = Rate::from_percent(max_bonus);
+
+            let bonus = obligation.liquidation_bonus(min_bonus, max_bonus)?;
+            assert!(bonus >= min_bonus);
+            assert!(bonus <= max_bonus);
+
+            if borrowed_value <= unhealthy_borrow_value {
+                assert_eq!(bonus, min_bonus);
+            } else if borrowed_value >= deposited_value {
+                assert_eq!(bonus, max_bonus);
+            }
+        }
+
+        #[test]
+        fn liquidation_bonus_increases_with_borrowed_value(
+            (deposited_value, unhealthy_borrow_value, borrowed_value) in obligation_values(),
+            (min_bonus, max_bonus) in liquidation_bonuses(),
+            borrowed_value_increase in 0..=u32::MAX as u64,
+        ) {
+            let mut obligation = Obligation {
+                deposited_value: Decimal::from(deposited_value),
+                unhealthy_borrow_value: Decimal::from(unhealthy_borrow_value),
+                borrowed_value: Decimal::from(borrowed_value),
+                ..Obligation::default()
+            };
+            let min_bonus = Rate::from_percent(min_bonus);
+            let max_bonus = Rate::from_percent(max_bonus);
+
+            let bonus = obligation.liquidation_bonus(min_bonus, max_bonus)?;
+            obligation.borrowed_value = Decimal::from(borrowed_value + borrowed_value_increase);
+            let next_bonus = obligation.liquidation_bonus(min_bonus, max_bonus)?;
+            assert!(next_bonus >= bonus);
+        }
+
+        #[test]
+        fn max_liquidation_amount_close_factor(
+            (borrowed_value, market_value) in liquidation_values(),
+            borrowed_amount in 1..=u64::MAX,
+            close_factor in 1..=100u8,
+        ) {
+            let liquidity = ObligationLiquidity {
+                borrowed_amount_wads: Decimal::from(borrowed_amount),
+                market_value: Decimal::from(market_value),
+                ..ObligationLiquidity::default()
+            };
+            let obligation = Obligation {
+                borrowed_value: Decimal::from(borrowed_value),
+                borrows: vec![liquidity.clone()],
+                ..Obligation::default()
+            };
+
+            let max_liquidation_amount = obligation.max_liquidation_amount(
+                &liquidity,
+                Rate::from_percent(close_factor),
+                Decimal::zero(),
+            )?;
+            let max_liquidation_value = Decimal::from(borrowed_value)
+                .try_mul(Rate::from_percent(close_factor))?;
+
+            if max_liquidation_value >= liquidity.market_value {
+                assert_eq!(max_liquidation_amount, liquidity.borrowed_amount_wads);
+            } else {
+                assert!(max_liquidation_amount < liquidity.borrowed_amount_wads);
+            }
+        }
+
+        #[test]
+        fn max_liquidation_amount_dust(
+            (borrowed_value, market_value) in liquidation_values(),
+            borrowed_amount in 1..=u64::MAX,
+            close_factor in 1..=100u8,
+            dust_value in 0..=u32::MAX as u64,
+        ) {
+            let liquidity = ObligationLiquidity {
+                borrowed_amount_wads: Decimal::from(borrowed_amount),
+                market_value: Decimal::from(market_value),
+                ..ObligationLiquidity::default()
+            };
+            let obligation = Obligation {
+                borrowed_value: Decimal::from(borrowed_value),
+                borrows: vec![liquidity.clone()],
+                ..Obligation::default()
+            };
+
+            let max_liquidation_amount = obligation.max_liquidation_amount(
+                &liquidity,
+                Rate::from_percent(close_factor),
+                Decimal::from(dust_value),
+            )?;
+
+            assert!(max_liquidation_amount <= liquidity.borrowed_amount_wads);
+            if borrowed_value <= dust_value {
+                assert_eq!(max_liquidation_amount, liquidity.borrowed_amount_wads);
+            }
+        }
+
         #[test]
         fn repay_partial(
             (repay_amount, borrowed_amount) in repay_partial_amounts(),
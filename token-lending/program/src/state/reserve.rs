@@ -2,6 +2,7 @@ use super::*;
 use crate::{
     error::LendingError,
     math::{Decimal, Rate, TryAdd, TryDiv, TryMul, TrySub},
+    pyth,
 };
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use solana_program::{
@@ -17,12 +18,25 @@ use std::{
     convert::{TryFrom, TryInto},
 };
 
-/// Percentage of an obligation that can be repaid during each liquidation call
+/// Percentage of an obligation that can be repaid during each liquidation call, if the lending
+/// market does not configure one
 pub const LIQUIDATION_CLOSE_FACTOR: u8 = 50;
 
 /// Obligation borrow amount that is small enough to close out
 pub const LIQUIDATION_CLOSE_AMOUNT: u64 = 2;
 
+/// Maximum number of points on a reserve borrow rate curve
+pub const MAX_BORROW_RATE_CURVE_POINTS: usize = 8;
+
+/// Utilization rate of a fully borrowed reserve, in basis points
+const MAX_UTILIZATION_RATE_BPS: u16 = 10_000;
+
+/// Number of slots after which an oracle price is stale, if the reserve does not configure one
+pub const DEFAULT_MAX_PRICE_AGE_SLOTS: u64 = 5;
+
+/// Maximum oracle confidence interval as a fraction of the price, in basis points
+pub const MAX_PRICE_CONFIDENCE_BPS: u16 = 10_000;
+
 /// Lending market reserve state
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Reserve {
@@ -40,6 +54,17 @@ pub struct Reserve {
     pub config: ReserveConfig,
 }
 
+/// Pyth price account used to refresh a reserve
+#[derive(Clone, Copy, Debug)]
+pub struct OracleAccount<'a> {
+    /// Price account address
+    pub pubkey: &'a Pubkey,
+    /// Oracle program owning the price account
+    pub owner: &'a Pubkey,
+    /// Price account data
+    pub data: &'a [u8],
+}
+
 impl Reserve {
     /// Create a new reserve
     pub fn new(params: InitReserveParams) -> Self {
@@ -85,36 +110,35 @@ impl Reserve {
     /// Calculate the current borrow rate
     pub fn current_borrow_rate(&self) -> Result<Rate, ProgramError> {
         let utilization_rate = self.liquidity.utilization_rate()?;
-        let optimal_utilization_rate = Rate::from_percent(self.config.optimal_utilization_rate);
-        let low_utilization = utilization_rate < optimal_utilization_rate;
-        if low_utilization || self.config.optimal_utilization_rate == 100 {
-            let normalized_rate = utilization_rate.try_div(optimal_utilization_rate)?;
-            let min_rate = Rate::from_percent(self.config.min_borrow_rate);
-            let rate_range = Rate::from_percent(
-                self.config
-                    .optimal_borrow_rate
-                    .checked_sub(self.config.min_borrow_rate)
-                    .ok_or(LendingError::MathOverflow)?,
-            );
+        self.config
+            .effective_borrow_rate_curve()
+            .borrow_rate(utilization_rate)
+    }
 
-            Ok(normalized_rate.try_mul(rate_range)?.try_add(min_rate)?)
+    /// Amount of liquidity that can be deposited before reaching the deposit limit
+    pub fn remaining_deposit_capacity(&self) -> Result<Decimal, ProgramError> {
+        if self.config.deposit_limit == 0 {
+            return Ok(Decimal::from(u64::MAX));
+        }
+        let total_supply = self.liquidity.total_supply()?;
+        let deposit_limit = Decimal::from(self.config.deposit_limit);
+        if total_supply >= deposit_limit {
+            Ok(Decimal::zero())
         } else {
-            let normalized_rate = utilization_rate
-                .try_sub(optimal_utilization_rate)?
-                .try_div(Rate::from_percent(
-                    100u8
-                        .checked_sub(self.config.optimal_utilization_rate)
-                        .ok_or(LendingError::MathOverflow)?,
-                ))?;
-            let min_rate = Rate::from_percent(self.config.optimal_borrow_rate);
-            let rate_range = Rate::from_percent(
-                self.config
-                    .max_borrow_rate
-                    .checked_sub(self.config.optimal_borrow_rate)
-                    .ok_or(LendingError::MathOverflow)?,
-            );
+            deposit_limit.try_sub(total_supply)
+        }
+    }
 
-            Ok(normalized_rate.try_mul(rate_range)?.try_add(min_rate)?)
+    /// Amount of liquidity that can be borrowed before reaching the borrow limit
+    pub fn remaining_borrow_capacity(&self) -> Result<Decimal, ProgramError> {
+        if self.config.borrow_limit == 0 {
+            return Ok(Decimal::from(u64::MAX));
+        }
+        let borrow_limit = Decimal::from(self.config.borrow_limit);
+        if self.liquidity.borrowed_amount_wads >= borrow_limit {
+            Ok(Decimal::zero())
+        } else {
+            borrow_limit.try_sub(self.liquidity.borrowed_amount_wads)
         }
     }
 
@@ -124,13 +148,57 @@ impl Reserve {
         self.collateral.exchange_rate(total_liquidity)
     }
 
+    /// Update the market price from the reserve liquidity oracle, or from the fallback oracle if
+    /// the liquidity oracle price is rejected, then accrue interest and mark the reserve fresh
+    pub fn refresh(
+        &mut self,
+        oracle: OracleAccount,
+        fallback_oracle: Option<OracleAccount>,
+        current_slot: Slot,
+    ) -> ProgramResult {
+        if &self.liquidity.oracle_pubkey != oracle.pubkey {
+            msg!("Reserve liquidity oracle does not match the reserve liquidity oracle provided");
+            return Err(LendingError::InvalidAccountInput.into());
+        }
+
+        let market_price = pyth::get_price(oracle.data, &self.config, current_slot);
+        self.liquidity.market_price = match (market_price, self.config.fallback_oracle()) {
+            (Ok(market_price), _) => market_price,
+            (Err(err), None) => return Err(err),
+            (Err(_), Some(fallback_oracle_pubkey)) => {
+                msg!("Reserve liquidity oracle price rejected, using the fallback oracle");
+                let fallback_oracle = fallback_oracle.ok_or(ProgramError::NotEnoughAccountKeys)?;
+                if &fallback_oracle_pubkey != fallback_oracle.pubkey {
+                    msg!("Reserve fallback oracle does not match the fallback oracle provided");
+                    return Err(LendingError::InvalidFallbackOracle.into());
+                }
+                // The reserve liquidity oracle owner was checked against the lending market
+                // oracle program at InitReserve
+                if fallback_oracle.owner != oracle.owner {
+                    msg!(
+                        "Reserve fallback oracle is not owned by the reserve liquidity oracle program"
+                    );
+                    return Err(LendingError::InvalidFallbackOracle.into());
+                }
+                pyth::get_price(fallback_oracle.data, &self.config, current_slot)?
+            }
+        };
+
+        self.accrue_interest(current_slot)?;
+        self.last_update.update_slot(current_slot);
+        Ok(())
+    }
+
     /// Update borrow rate and accrue interest
     pub fn accrue_interest(&mut self, current_slot: Slot) -> ProgramResult {
         let slots_elapsed = self.last_update.slots_elapsed(current_slot)?;
         if slots_elapsed > 0 {
             let current_borrow_rate = self.current_borrow_rate()?;
-            self.liquidity
-                .compound_interest(current_borrow_rate, slots_elapsed)?;
+            self.liquidity.compound_interest(
+                current_borrow_rate,
+                slots_elapsed,
+                self.config.reserve_factor,
+            )?;
         }
         Ok(())
     }
@@ -149,7 +217,8 @@ impl Reserve {
             let borrow_amount = max_borrow_value
                 .try_mul(decimals)?
                 .try_div(self.liquidity.market_price)?
-                .min(self.liquidity.available_amount.into());
+                .min(self.liquidity.available_amount.into())
+                .min(self.remaining_borrow_capacity()?);
             let (borrow_fee, host_fee) = self
                 .config
                 .fees
@@ -217,8 +286,14 @@ impl Reserve {
         obligation: &Obligation,
         liquidity: &ObligationLiquidity,
         collateral: &ObligationCollateral,
+        lending_market: &LendingMarket,
     ) -> Result<CalculateLiquidationResult, ProgramError> {
-        let bonus_rate = Rate::from_percent(self.config.liquidation_bonus).try_add(Rate::one())?;
+        let bonus_rate = obligation
+            .liquidation_bonus(
+                Rate::from_percent(self.config.liquidation_bonus),
+                Rate::from_percent(self.config.effective_max_liquidation_bonus()),
+            )?
+            .try_add(Rate::one())?;
 
         let max_amount = if amount_to_liquidate == u64::MAX {
             liquidity.borrowed_amount_wads
@@ -257,7 +332,11 @@ impl Reserve {
         } else {
             // calculate settle_amount and withdraw_amount, repay_amount is settle_amount rounded
             let liquidation_amount = obligation
-                .max_liquidation_amount(liquidity)?
+                .max_liquidation_amount(
+                    liquidity,
+                    lending_market.effective_liquidation_close_factor(),
+                    lending_market.liquidation_dust_value,
+                )?
                 .min(max_amount);
             let liquidation_pct = liquidation_amount.try_div(liquidity.borrowed_amount_wads)?;
             let liquidation_value = liquidity
@@ -365,6 +444,8 @@ pub struct ReserveLiquidity {
     pub cumulative_borrow_rate_wads: Decimal,
     /// Reserve liquidity market price in quote currency
     pub market_price: Decimal,
+    /// Share of accrued borrow interest kept as protocol fees, not yet redeemed
+    pub accumulated_protocol_fees_wads: Decimal,
 }
 
 impl ReserveLiquidity {
@@ -380,12 +461,15 @@ impl ReserveLiquidity {
             borrowed_amount_wads: Decimal::zero(),
             cumulative_borrow_rate_wads: Decimal::one(),
             market_price: params.market_price,
+            accumulated_protocol_fees_wads: Decimal::zero(),
         }
     }
 
-    /// Calculate the total reserve supply including active loans
+    /// Calculate the total reserve supply including active loans, excluding protocol fees
     pub fn total_supply(&self) -> Result<Decimal, ProgramError> {
-        Decimal::from(self.available_amount).try_add(self.borrowed_amount_wads)
+        Decimal::from(self.available_amount)
+            .try_add(self.borrowed_amount_wads)?
+            .try_sub(self.accumulated_protocol_fees_wads)
     }
 
     /// Add liquidity to available amount
@@ -438,13 +522,54 @@ impl ReserveLiquidity {
         Ok(())
     }
 
+    /// Write off borrowed liquidity that will never be repaid, which lowers the total supply
+    /// shared by collateral holders. Debt beyond the total supply is taken out of the
+    /// accumulated protocol fees.
+    pub fn forgive_debt(&mut self, settle_amount: Decimal) -> ProgramResult {
+        let borrowed_amount_wads = self.borrowed_amount_wads.try_sub(settle_amount)?;
+        let total_liquidity = Decimal::from(self.available_amount).try_add(borrowed_amount_wads)?;
+        if total_liquidity < self.accumulated_protocol_fees_wads {
+            let forgiven_fees = self
+                .accumulated_protocol_fees_wads
+                .try_sub(total_liquidity)?;
+            msg!(
+                "Forgiven debt exceeds the reserve supply, writing off {} of protocol fees",
+                forgiven_fees
+            );
+            self.accumulated_protocol_fees_wads = total_liquidity;
+        }
+        self.borrowed_amount_wads = borrowed_amount_wads;
+
+        Ok(())
+    }
+
+    /// Remove the accumulated protocol fees that are available from the reserve
+    pub fn redeem_fees(&mut self) -> Result<u64, ProgramError> {
+        let fee_amount = self
+            .accumulated_protocol_fees_wads
+            .try_floor_u64()?
+            .min(self.available_amount);
+        self.available_amount = self
+            .available_amount
+            .checked_sub(fee_amount)
+            .ok_or(LendingError::MathOverflow)?;
+        self.accumulated_protocol_fees_wads = self
+            .accumulated_protocol_fees_wads
+            .try_sub(fee_amount.into())?;
+
+        Ok(fee_amount)
+    }
+
     /// Calculate the liquidity utilization rate of the reserve
     pub fn utilization_rate(&self) -> Result<Rate, ProgramError> {
-        let total_supply = self.total_supply()?;
-        if total_supply == Decimal::zero() {
+        let total_liquidity =
+            Decimal::from(self.available_amount).try_add(self.borrowed_amount_wads)?;
+        if total_liquidity == Decimal::zero() {
             return Ok(Rate::zero());
         }
-        self.borrowed_amount_wads.try_div(total_supply)?.try_into()
+        self.borrowed_amount_wads
+            .try_div(total_liquidity)?
+            .try_into()
     }
 
     /// Compound current borrow rate over elapsed slots
@@ -452,6 +577,7 @@ impl ReserveLiquidity {
         &mut self,
         current_borrow_rate: Rate,
         slots_elapsed: u64,
+        reserve_factor: u8,
     ) -> ProgramResult {
         let slot_interest_rate = current_borrow_rate.try_div(SLOTS_PER_YEAR)?;
         let compounded_interest_rate = Rate::one()
@@ -460,9 +586,17 @@ impl ReserveLiquidity {
         self.cumulative_borrow_rate_wads = self
             .cumulative_borrow_rate_wads
             .try_mul(compounded_interest_rate)?;
+        let previous_borrowed_amount_wads = self.borrowed_amount_wads;
         self.borrowed_amount_wads = self
             .borrowed_amount_wads
             .try_mul(compounded_interest_rate)?;
+
+        let protocol_fees = self
+            .borrowed_amount_wads
+            .try_sub(previous_borrowed_amount_wads)?
+            .try_mul(Rate::from_percent(reserve_factor))?;
+        self.accumulated_protocol_fees_wads =
+            self.accumulated_protocol_fees_wads.try_add(protocol_fees)?;
         Ok(())
     }
 }
@@ -594,7 +728,8 @@ pub struct ReserveConfig {
     /// Target ratio of the value of borrows to deposits, as a percentage
     /// 0 if use as collateral is disabled
     pub loan_to_value_ratio: u8,
-    /// Bonus a liquidator gets when repaying part of an unhealthy obligation, as a percentage
+    /// Bonus a liquidator gets when repaying part of an unhealthy obligation, as a percentage.
+    /// Applies to obligations that have just become unhealthy
     pub liquidation_bonus: u8,
     /// Loan to value ratio at which an obligation can be liquidated, as a percentage
     pub liquidation_threshold: u8,
@@ -606,6 +741,31 @@ pub struct ReserveConfig {
     pub max_borrow_rate: u8,
     /// Program owner fees assessed, separate from gains due to interest accrual
     pub fees: ReserveFees,
+    /// Borrow APY curve over utilization, in basis points. When empty, the min, optimal
+    /// and max borrow rates above define a two-segment curve instead.
+    pub borrow_rate_curve: BorrowRateCurve,
+    /// Maximum total liquidity supplied to the reserve, including borrows, in liquidity
+    /// token units. 0 if deposits are not limited
+    pub deposit_limit: u64,
+    /// Maximum total liquidity borrowed from the reserve, in liquidity token units.
+    /// 0 if borrows are not limited
+    pub borrow_limit: u64,
+    /// Maximum oracle confidence interval as a fraction of the price, in basis points.
+    /// 0 if the confidence interval is not checked
+    pub max_price_confidence_bps: u16,
+    /// Number of slots after which an oracle price is considered stale.
+    /// 0 to use the default of 5 slots
+    pub max_price_age_slots: u64,
+    /// Second Pyth price account used when the primary oracle price is rejected.
+    /// Default pubkey if the reserve has no fallback oracle
+    pub fallback_oracle_pubkey: Pubkey,
+    /// Share of accrued borrow interest kept as protocol fees, as a percentage
+    pub reserve_factor: u8,
+    /// Bonus a liquidator gets when repaying part of an obligation whose borrowed value has
+    /// reached its deposited value, as a percentage. 0 if the liquidation bonus is fixed
+    pub max_liquidation_bonus: u8,
+    /// Elevation group of the lending market the reserve belongs to, 0 if none
+    pub elevation_group: u8,
 }
 
 impl ReserveConfig {
@@ -623,6 +783,13 @@ impl ReserveConfig {
             msg!("Liquidation bonus must be in range [0, 100]");
             return Err(LendingError::InvalidConfig.into());
         }
+        if self.max_liquidation_bonus != 0
+            && (self.max_liquidation_bonus < self.liquidation_bonus
+                || self.max_liquidation_bonus > 100)
+        {
+            msg!("Max liquidation bonus must be 0 or in range [liquidation bonus, 100]");
+            return Err(LendingError::InvalidConfig.into());
+        }
         if self.liquidation_threshold <= self.loan_to_value_ratio
             || self.liquidation_threshold > 100
         {
@@ -649,9 +816,233 @@ impl ReserveConfig {
             msg!("Host fee percentage must be in range [0, 100]");
             return Err(LendingError::InvalidConfig.into());
         }
+        if self.reserve_factor > 100 {
+            msg!("Reserve factor must be in range [0, 100]");
+            return Err(LendingError::InvalidConfig.into());
+        }
+        if self.max_price_confidence_bps > MAX_PRICE_CONFIDENCE_BPS {
+            msg!("Max price confidence must be in range [0, 10_000]");
+            return Err(LendingError::InvalidConfig.into());
+        }
+        self.borrow_rate_curve.validate()?;
 
         Ok(())
     }
+
+    /// Number of slots after which an oracle price is considered stale
+    pub fn effective_max_price_age_slots(&self) -> u64 {
+        if self.max_price_age_slots == 0 {
+            DEFAULT_MAX_PRICE_AGE_SLOTS
+        } else {
+            self.max_price_age_slots
+        }
+    }
+
+    /// Liquidation bonus of an obligation whose borrowed value has reached its deposited value
+    pub fn effective_max_liquidation_bonus(&self) -> u8 {
+        if self.max_liquidation_bonus == 0 {
+            self.liquidation_bonus
+        } else {
+            self.max_liquidation_bonus
+        }
+    }
+
+    /// Fallback Pyth price account, if one is configured
+    pub fn fallback_oracle(&self) -> Option<Pubkey> {
+        if self.fallback_oracle_pubkey == Pubkey::default() {
+            None
+        } else {
+            Some(self.fallback_oracle_pubkey)
+        }
+    }
+
+    /// Borrow rate curve used to calculate the borrow rate, built from the min, optimal and
+    /// max borrow rates if no curve is configured
+    pub fn effective_borrow_rate_curve(&self) -> BorrowRateCurve {
+        if !self.borrow_rate_curve.is_empty() {
+            return self.borrow_rate_curve;
+        }
+
+        let mut curve = BorrowRateCurve::LEGACY;
+        curve.len = 3;
+        curve.points[0] = BorrowRateCurvePoint {
+            utilization_rate_bps: 0,
+            borrow_rate_bps: percent_to_bps(self.min_borrow_rate),
+        };
+        curve.points[1] = BorrowRateCurvePoint {
+            utilization_rate_bps: percent_to_bps(self.optimal_utilization_rate),
+            borrow_rate_bps: percent_to_bps(self.optimal_borrow_rate),
+        };
+        curve.points[2] = BorrowRateCurvePoint {
+            utilization_rate_bps: MAX_UTILIZATION_RATE_BPS,
+            borrow_rate_bps: percent_to_bps(self.max_borrow_rate),
+        };
+        curve
+    }
+}
+
+fn percent_to_bps(percent: u8) -> u16 {
+    percent as u16 * 100
+}
+
+/// Point on a borrow rate curve
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BorrowRateCurvePoint {
+    /// Utilization rate, in basis points
+    pub utilization_rate_bps: u16,
+    /// Borrow APY at this utilization rate, in basis points
+    pub borrow_rate_bps: u16,
+}
+
+/// Piecewise-linear borrow rate curve
+///
+/// The borrow rate is interpolated between the two points surrounding the current utilization
+/// rate. A configured curve must start at 0% utilization, end at 100% utilization, and have
+/// strictly increasing utilization rates with non-decreasing borrow rates.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BorrowRateCurve {
+    len: u8,
+    points: [BorrowRateCurvePoint; MAX_BORROW_RATE_CURVE_POINTS],
+}
+
+impl BorrowRateCurve {
+    /// Empty curve, which falls back to the reserve's min, optimal and max borrow rates
+    pub const LEGACY: Self = Self {
+        len: 0,
+        points: [BorrowRateCurvePoint {
+            utilization_rate_bps: 0,
+            borrow_rate_bps: 0,
+        }; MAX_BORROW_RATE_CURVE_POINTS],
+    };
+
+    /// Create a borrow rate curve from its points
+    pub fn new(points: &[BorrowRateCurvePoint]) -> Result<Self, ProgramError> {
+        if points.len() > MAX_BORROW_RATE_CURVE_POINTS {
+            msg!(
+                "Borrow rate curve cannot have more than {} points",
+                MAX_BORROW_RATE_CURVE_POINTS
+            );
+            return Err(LendingError::InvalidConfig.into());
+        }
+
+        let mut curve = Self::LEGACY;
+        curve.len = points.len() as u8;
+        curve.points[..points.len()].copy_from_slice(points);
+        Ok(curve)
+    }
+
+    /// Points on the curve
+    pub fn points(&self) -> &[BorrowRateCurvePoint] {
+        &self.points[..self.len as usize]
+    }
+
+    /// Check if the curve has no points
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Interpolate the borrow rate at a utilization rate
+    pub fn borrow_rate(&self, utilization_rate: Rate) -> Result<Rate, ProgramError> {
+        let (first, rest) = self
+            .points()
+            .split_first()
+            .ok_or(LendingError::InvalidConfig)?;
+
+        let mut start = first;
+        for end in rest {
+            let end_utilization_rate = Rate::from_bps(end.utilization_rate_bps);
+            if utilization_rate <= end_utilization_rate {
+                if end.utilization_rate_bps == start.utilization_rate_bps {
+                    return Ok(Rate::from_bps(end.borrow_rate_bps));
+                }
+
+                let start_utilization_rate = Rate::from_bps(start.utilization_rate_bps);
+                let normalized_rate = utilization_rate
+                    .try_sub(start_utilization_rate)?
+                    .try_div(end_utilization_rate.try_sub(start_utilization_rate)?)?;
+                let min_rate = Rate::from_bps(start.borrow_rate_bps);
+                let rate_range = Rate::from_bps(
+                    end.borrow_rate_bps
+                        .checked_sub(start.borrow_rate_bps)
+                        .ok_or(LendingError::MathOverflow)?,
+                );
+
+                return normalized_rate.try_mul(rate_range)?.try_add(min_rate);
+            }
+            start = end;
+        }
+
+        Ok(Rate::from_bps(start.borrow_rate_bps))
+    }
+
+    /// Check that the curve is empty, or spans all utilization rates with
+    /// strictly increasing utilization rates and non-decreasing borrow rates
+    pub fn validate(&self) -> ProgramResult {
+        let points = self.points();
+        if points.is_empty() {
+            return Ok(());
+        }
+        if points.len() < 2 {
+            msg!("Borrow rate curve must have at least 2 points");
+            return Err(LendingError::InvalidConfig.into());
+        }
+        if points[0].utilization_rate_bps != 0
+            || points[points.len() - 1].utilization_rate_bps != MAX_UTILIZATION_RATE_BPS
+        {
+            msg!("Borrow rate curve must span utilization rates from 0 to 10,000 bps");
+            return Err(LendingError::InvalidConfig.into());
+        }
+        for pair in points.windows(2) {
+            if pair[1].utilization_rate_bps <= pair[0].utilization_rate_bps {
+                msg!("Borrow rate curve utilization rates must be strictly increasing");
+                return Err(LendingError::InvalidConfig.into());
+            }
+            if pair[1].borrow_rate_bps < pair[0].borrow_rate_bps {
+                msg!("Borrow rate curve borrow rates must not decrease");
+                return Err(LendingError::InvalidConfig.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Sealed for BorrowRateCurve {}
+
+const BORROW_RATE_CURVE_LEN: usize = 33; // 1 + (2 + 2) * 8
+impl Pack for BorrowRateCurve {
+    const LEN: usize = BORROW_RATE_CURVE_LEN;
+
+    fn pack_into_slice(&self, output: &mut [u8]) {
+        let output = array_mut_ref![output, 0, BORROW_RATE_CURVE_LEN];
+        let (len, points) = mut_array_refs![output, 1, BORROW_RATE_CURVE_LEN - 1];
+
+        *len = self.len.to_le_bytes();
+        for (point, output) in self.points.iter().zip(points.chunks_exact_mut(4)) {
+            output[..2].copy_from_slice(&point.utilization_rate_bps.to_le_bytes());
+            output[2..].copy_from_slice(&point.borrow_rate_bps.to_le_bytes());
+        }
+    }
+
+    /// Unpacks a byte buffer into a [BorrowRateCurve](struct.BorrowRateCurve.html).
+    fn unpack_from_slice(input: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![input, 0, BORROW_RATE_CURVE_LEN];
+        let (len, points) = array_refs![input, 1, BORROW_RATE_CURVE_LEN - 1];
+
+        let len = u8::from_le_bytes(*len);
+        if len as usize > MAX_BORROW_RATE_CURVE_POINTS {
+            msg!("Borrow rate curve has too many points");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut curve = Self::LEGACY;
+        curve.len = len;
+        for (point, input) in curve.points.iter_mut().zip(points.chunks_exact(4)) {
+            point.utilization_rate_bps = u16::from_le_bytes([input[0], input[1]]);
+            point.borrow_rate_bps = u16::from_le_bytes([input[2], input[3]]);
+        }
+        Ok(curve)
+    }
 }
 
 /// Additional fee information on a reserve
@@ -762,7 +1153,7 @@ impl IsInitialized for Reserve {
     }
 }
 
-const RESERVE_LEN: usize = 571; // 1 + 8 + 1 + 32 + 32 + 1 + 32 + 32 + 32 + 8 + 16 + 16 + 16 + 32 + 8 + 32 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 8 + 8 + 1 + 248
+const RESERVE_LEN: usize = 571; // 1 + 8 + 1 + 32 + 32 + 1 + 32 + 32 + 32 + 8 + 16 + 16 + 16 + 32 + 8 + 32 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 8 + 8 + 1 + 33 + 8 + 8 + 2 + 8 + 32 + 16 + 1 + 1 + 1 + 138
 impl Pack for Reserve {
     const LEN: usize = RESERVE_LEN;
 
@@ -797,6 +1188,16 @@ impl Pack for Reserve {
             config_fees_borrow_fee_wad,
             config_fees_flash_loan_fee_wad,
             config_fees_host_fee_percentage,
+            config_borrow_rate_curve,
+            config_deposit_limit,
+            config_borrow_limit,
+            config_max_price_confidence_bps,
+            config_max_price_age_slots,
+            config_fallback_oracle_pubkey,
+            liquidity_accumulated_protocol_fees_wads,
+            config_reserve_factor,
+            config_max_liquidation_bonus,
+            config_elevation_group,
             _padding,
         ) = mut_array_refs![
             output,
@@ -826,7 +1227,17 @@ impl Pack for Reserve {
             8,
             8,
             1,
-            248
+            BORROW_RATE_CURVE_LEN,
+            8,
+            8,
+            2,
+            8,
+            PUBKEY_BYTES,
+            16,
+            1,
+            1,
+            1,
+            138
         ];
 
         // reserve
@@ -851,6 +1262,10 @@ impl Pack for Reserve {
             liquidity_cumulative_borrow_rate_wads,
         );
         pack_decimal(self.liquidity.market_price, liquidity_market_price);
+        pack_decimal(
+            self.liquidity.accumulated_protocol_fees_wads,
+            liquidity_accumulated_protocol_fees_wads,
+        );
 
         // collateral
         collateral_mint_pubkey.copy_from_slice(self.collateral.mint_pubkey.as_ref());
@@ -868,6 +1283,17 @@ impl Pack for Reserve {
         *config_fees_borrow_fee_wad = self.config.fees.borrow_fee_wad.to_le_bytes();
         *config_fees_flash_loan_fee_wad = self.config.fees.flash_loan_fee_wad.to_le_bytes();
         *config_fees_host_fee_percentage = self.config.fees.host_fee_percentage.to_le_bytes();
+        self.config
+            .borrow_rate_curve
+            .pack_into_slice(config_borrow_rate_curve);
+        *config_deposit_limit = self.config.deposit_limit.to_le_bytes();
+        *config_borrow_limit = self.config.borrow_limit.to_le_bytes();
+        *config_max_price_confidence_bps = self.config.max_price_confidence_bps.to_le_bytes();
+        *config_max_price_age_slots = self.config.max_price_age_slots.to_le_bytes();
+        config_fallback_oracle_pubkey.copy_from_slice(self.config.fallback_oracle_pubkey.as_ref());
+        *config_reserve_factor = self.config.reserve_factor.to_le_bytes();
+        *config_max_liquidation_bonus = self.config.max_liquidation_bonus.to_le_bytes();
+        *config_elevation_group = self.config.elevation_group.to_le_bytes();
     }
 
     /// Unpacks a byte buffer into a [ReserveInfo](struct.ReserveInfo.html).
@@ -901,6 +1327,16 @@ impl Pack for Reserve {
             config_fees_borrow_fee_wad,
             config_fees_flash_loan_fee_wad,
             config_fees_host_fee_percentage,
+            config_borrow_rate_curve,
+            config_deposit_limit,
+            config_borrow_limit,
+            config_max_price_confidence_bps,
+            config_max_price_age_slots,
+            config_fallback_oracle_pubkey,
+            liquidity_accumulated_protocol_fees_wads,
+            config_reserve_factor,
+            config_max_liquidation_bonus,
+            config_elevation_group,
             _padding,
         ) = array_refs![
             input,
@@ -930,7 +1366,17 @@ impl Pack for Reserve {
             8,
             8,
             1,
-            248
+            BORROW_RATE_CURVE_LEN,
+            8,
+            8,
+            2,
+            8,
+            PUBKEY_BYTES,
+            16,
+            1,
+            1,
+            1,
+            138
         ];
 
         let version = u8::from_le_bytes(*version);
@@ -956,6 +1402,9 @@ impl Pack for Reserve {
                 borrowed_amount_wads: unpack_decimal(liquidity_borrowed_amount_wads),
                 cumulative_borrow_rate_wads: unpack_decimal(liquidity_cumulative_borrow_rate_wads),
                 market_price: unpack_decimal(liquidity_market_price),
+                accumulated_protocol_fees_wads: unpack_decimal(
+                    liquidity_accumulated_protocol_fees_wads,
+                ),
             },
             collateral: ReserveCollateral {
                 mint_pubkey: Pubkey::new_from_array(*collateral_mint_pubkey),
@@ -975,6 +1424,15 @@ impl Pack for Reserve {
                     flash_loan_fee_wad: u64::from_le_bytes(*config_fees_flash_loan_fee_wad),
                     host_fee_percentage: u8::from_le_bytes(*config_fees_host_fee_percentage),
                 },
+                borrow_rate_curve: BorrowRateCurve::unpack_from_slice(config_borrow_rate_curve)?,
+                deposit_limit: u64::from_le_bytes(*config_deposit_limit),
+                borrow_limit: u64::from_le_bytes(*config_borrow_limit),
+                max_price_confidence_bps: u16::from_le_bytes(*config_max_price_confidence_bps),
+                max_price_age_slots: u64::from_le_bytes(*config_max_price_age_slots),
+                fallback_oracle_pubkey: Pubkey::new_from_array(*config_fallback_oracle_pubkey),
+                reserve_factor: u8::from_le_bytes(*config_reserve_factor),
+                max_liquidation_bonus: u8::from_le_bytes(*config_max_liquidation_bonus),
+                elevation_group: u8::from_le_bytes(*config_elevation_group),
             },
         })
     }
@@ -1000,6 +1458,23 @@ mod test {
         }
     }
 
+    // Creates a valid borrow rate curve with two kinks, rates in basis points
+    prop_compose! {
+        fn borrow_rate_curve()(first_kink in 1..=9_998u16)(
+            first_kink in Just(first_kink),
+            second_kink in (first_kink + 1)..=9_999u16,
+            mut rates in prop::array::uniform4(0..=u16::MAX),
+        ) -> BorrowRateCurve {
+            rates.sort_unstable();
+            BorrowRateCurve::new(&[
+                BorrowRateCurvePoint { utilization_rate_bps: 0, borrow_rate_bps: rates[0] },
+                BorrowRateCurvePoint { utilization_rate_bps: first_kink, borrow_rate_bps: rates[1] },
+                BorrowRateCurvePoint { utilization_rate_bps: second_kink, borrow_rate_bps: rates[2] },
+                BorrowRateCurvePoint { utilization_rate_bps: 10_000, borrow_rate_bps: rates[3] },
+            ]).unwrap()
+        }
+    }
+
     // Creates rates (threshold, ltv) where 2 <= threshold <= 100 and threshold <= ltv <= 1,000%
     prop_compose! {
         fn unhealthy_rates()(threshold in 2..=100u8)(
@@ -1076,6 +1551,39 @@ mod test {
             }
         }
 
+        #[test]
+        fn current_borrow_rate_curve(
+            total_liquidity in 0..=MAX_LIQUIDITY,
+            borrowed_percent in 0..=WAD,
+            borrow_rate_curve in borrow_rate_curve(),
+        ) {
+            let borrowed_amount_wads = Decimal::from(total_liquidity).try_mul(Rate::from_scaled_val(borrowed_percent))?;
+            let reserve = Reserve {
+                liquidity: ReserveLiquidity {
+                    borrowed_amount_wads,
+                    available_amount: total_liquidity - borrowed_amount_wads.try_round_u64()?,
+                    ..ReserveLiquidity::default()
+                },
+                config: ReserveConfig { borrow_rate_curve, ..ReserveConfig::default() },
+                ..Reserve::default()
+            };
+            reserve.config.borrow_rate_curve.validate()?;
+
+            let current_borrow_rate = reserve.current_borrow_rate()?;
+            let current_rate = reserve.liquidity.utilization_rate()?;
+            for pair in borrow_rate_curve.points().windows(2) {
+                let start_utilization_rate = Rate::from_bps(pair[0].utilization_rate_bps);
+                let end_utilization_rate = Rate::from_bps(pair[1].utilization_rate_bps);
+                if current_rate == end_utilization_rate {
+                    assert_eq!(current_borrow_rate, Rate::from_bps(pair[1].borrow_rate_bps));
+                }
+                if current_rate >= start_utilization_rate && current_rate <= end_utilization_rate {
+                    assert!(current_borrow_rate >= Rate::from_bps(pair[0].borrow_rate_bps));
+                    assert!(current_borrow_rate <= Rate::from_bps(pair[1].borrow_rate_bps));
+                }
+            }
+        }
+
         #[test]
         fn current_utilization_rate(
             total_liquidity in 0..=MAX_LIQUIDITY,
@@ -1147,7 +1655,7 @@ mod test {
             // Simulate running for max 1000 years, assuming that interest is
             // compounded at least once a year
             for _ in 0..1000 {
-                reserve.liquidity.compound_interest(borrow_rate, slots_elapsed)?;
+                reserve.liquidity.compound_interest(borrow_rate, slots_elapsed, 0)?;
                 reserve.liquidity.cumulative_borrow_rate_wads.to_scaled_val()?;
             }
         }
@@ -1180,6 +1688,96 @@ mod test {
             }
         }
 
+        #[test]
+        fn reserve_accrue_protocol_fees(
+            slots_elapsed in 0..=SLOTS_PER_YEAR,
+            available_liquidity in 0..=MAX_LIQUIDITY,
+            borrowed_liquidity in 0..=MAX_LIQUIDITY,
+            borrow_rate in 0..=u8::MAX,
+            reserve_factor in 0..=100u8,
+        ) {
+            let borrowed_amount_wads = Decimal::from(borrowed_liquidity);
+            let mut reserve = Reserve {
+                collateral: ReserveCollateral {
+                    mint_total_supply: available_liquidity.saturating_add(borrowed_liquidity),
+                    ..ReserveCollateral::default()
+                },
+                liquidity: ReserveLiquidity {
+                    available_amount: available_liquidity,
+                    borrowed_amount_wads,
+                    ..ReserveLiquidity::default()
+                },
+                config: ReserveConfig {
+                    max_borrow_rate: borrow_rate,
+                    reserve_factor,
+                    ..ReserveConfig::default()
+                },
+                ..Reserve::default()
+            };
+
+            reserve.accrue_interest(slots_elapsed)?;
+
+            // Protocol fees are the reserve factor share of the accrued interest
+            let interest = reserve.liquidity.borrowed_amount_wads.try_sub(borrowed_amount_wads)?;
+            assert_eq!(
+                reserve.liquidity.accumulated_protocol_fees_wads,
+                interest.try_mul(Rate::from_percent(reserve_factor))?
+            );
+
+            // Depositors get the remaining interest
+            let total_liquidity = Decimal::from(reserve.liquidity.available_amount)
+                .try_add(reserve.liquidity.borrowed_amount_wads)?;
+            assert_eq!(
+                reserve.liquidity.total_supply()?.try_add(reserve.liquidity.accumulated_protocol_fees_wads)?,
+                total_liquidity
+            );
+
+            // Redeeming fees does not change the value of collateral
+            let exchange_rate = reserve.collateral_exchange_rate()?;
+            let accumulated_protocol_fees_wads = reserve.liquidity.accumulated_protocol_fees_wads;
+            let fee_amount = reserve.liquidity.redeem_fees()?;
+            assert!(fee_amount <= available_liquidity);
+            assert_eq!(reserve.liquidity.available_amount, available_liquidity - fee_amount);
+            assert_eq!(
+                reserve.liquidity.accumulated_protocol_fees_wads,
+                accumulated_protocol_fees_wads.try_sub(fee_amount.into())?
+            );
+            assert_eq!(reserve.collateral_exchange_rate()?.0, exchange_rate.0);
+        }
+
+        #[test]
+        fn reserve_forgive_debt(
+            available_liquidity in 1..=MAX_LIQUIDITY,
+            borrowed_liquidity in 1..=MAX_LIQUIDITY,
+            forgiven_percent in 0..=WAD,
+        ) {
+            let borrowed_amount_wads = Decimal::from(borrowed_liquidity);
+            let mut reserve = Reserve {
+                collateral: ReserveCollateral {
+                    mint_total_supply: available_liquidity + borrowed_liquidity,
+                    ..ReserveCollateral::default()
+                },
+                liquidity: ReserveLiquidity {
+                    available_amount: available_liquidity,
+                    borrowed_amount_wads,
+                    ..ReserveLiquidity::default()
+                },
+                ..Reserve::default()
+            };
+
+            let total_supply = reserve.liquidity.total_supply()?;
+            let exchange_rate = reserve.collateral_exchange_rate()?;
+            let settle_amount = borrowed_amount_wads.try_mul(Rate::from_scaled_val(forgiven_percent))?;
+            reserve.liquidity.forgive_debt(settle_amount)?;
+
+            // Forgiven debt is removed from the total supply without touching available liquidity
+            assert_eq!(reserve.liquidity.available_amount, available_liquidity);
+            assert_eq!(reserve.liquidity.total_supply()?, total_supply.try_sub(settle_amount)?);
+
+            // Collateral can never be worth more after forgiving debt
+            assert!(reserve.collateral_exchange_rate()?.0 >= exchange_rate.0);
+        }
+
         #[test]
         fn borrow_fee_calculation(
             borrow_fee_wad in 0..WAD, // at WAD, fee == borrow amount, which fails
@@ -1267,6 +1865,252 @@ mod test {
         }
     }
 
+    fn reserve_with_utilization(utilization_percent: u8, config: ReserveConfig) -> Reserve {
+        Reserve {
+            liquidity: ReserveLiquidity {
+                borrowed_amount_wads: Decimal::from(utilization_percent as u64),
+                available_amount: 100 - utilization_percent as u64,
+                ..ReserveLiquidity::default()
+            },
+            config,
+            ..Reserve::default()
+        }
+    }
+
+    #[test]
+    fn forgive_debt_shared_pro_rata() {
+        let mut reserve = Reserve {
+            collateral: ReserveCollateral {
+                mint_total_supply: 1_000,
+                ..ReserveCollateral::default()
+            },
+            liquidity: ReserveLiquidity {
+                available_amount: 600,
+                borrowed_amount_wads: Decimal::from(400u64),
+                ..ReserveLiquidity::default()
+            },
+            ..Reserve::default()
+        };
+
+        let exchange_rate = reserve.collateral_exchange_rate().unwrap();
+        assert_eq!(exchange_rate.collateral_to_liquidity(250).unwrap(), 250);
+        assert_eq!(exchange_rate.collateral_to_liquidity(750).unwrap(), 750);
+
+        // A 20% loss of total supply is a 20% loss for every collateral holder
+        reserve
+            .liquidity
+            .forgive_debt(Decimal::from(200u64))
+            .unwrap();
+        let exchange_rate = reserve.collateral_exchange_rate().unwrap();
+        assert_eq!(exchange_rate.collateral_to_liquidity(250).unwrap(), 200);
+        assert_eq!(exchange_rate.collateral_to_liquidity(750).unwrap(), 600);
+    }
+
+    #[test]
+    fn forgive_debt_beyond_supply_takes_protocol_fees() {
+        let mut liquidity = ReserveLiquidity {
+            available_amount: 100,
+            borrowed_amount_wads: Decimal::from(400u64),
+            accumulated_protocol_fees_wads: Decimal::from(150u64),
+            ..ReserveLiquidity::default()
+        };
+        assert_eq!(liquidity.total_supply().unwrap(), Decimal::from(350u64));
+
+        // Up to the total supply, forgiven debt is shared by collateral holders only
+        liquidity.forgive_debt(Decimal::from(300u64)).unwrap();
+        assert_eq!(liquidity.total_supply().unwrap(), Decimal::from(50u64));
+        assert_eq!(
+            liquidity.accumulated_protocol_fees_wads,
+            Decimal::from(150u64)
+        );
+
+        // The 50 of debt beyond the remaining supply of 50 is taken out of protocol fees
+        liquidity.forgive_debt(Decimal::from(100u64)).unwrap();
+        assert_eq!(liquidity.borrowed_amount_wads, Decimal::zero());
+        assert_eq!(liquidity.available_amount, 100);
+        assert_eq!(
+            liquidity.accumulated_protocol_fees_wads,
+            Decimal::from(100u64)
+        );
+        assert_eq!(liquidity.total_supply().unwrap(), Decimal::zero());
+    }
+
+    #[test]
+    fn current_borrow_rate_multi_kink() {
+        let config = ReserveConfig {
+            borrow_rate_curve: BorrowRateCurve::new(&[
+                BorrowRateCurvePoint {
+                    utilization_rate_bps: 0,
+                    borrow_rate_bps: 100,
+                },
+                BorrowRateCurvePoint {
+                    utilization_rate_bps: 5_000,
+                    borrow_rate_bps: 500,
+                },
+                BorrowRateCurvePoint {
+                    utilization_rate_bps: 8_000,
+                    borrow_rate_bps: 1_000,
+                },
+                BorrowRateCurvePoint {
+                    utilization_rate_bps: 10_000,
+                    borrow_rate_bps: 30_000,
+                },
+            ])
+            .unwrap(),
+            ..ReserveConfig::default()
+        };
+
+        let expected = [
+            (0, Rate::from_bps(100)),
+            (25, Rate::from_bps(300)),
+            (50, Rate::from_bps(500)),
+            (65, Rate::from_bps(750)),
+            (80, Rate::from_bps(1_000)),
+            (90, Rate::from_bps(15_500)),
+            (100, Rate::from_bps(30_000)),
+        ];
+        for (utilization_percent, borrow_rate) in expected {
+            let reserve = reserve_with_utilization(utilization_percent, config);
+            assert_eq!(reserve.current_borrow_rate().unwrap(), borrow_rate);
+        }
+    }
+
+    #[test]
+    fn current_borrow_rate_legacy_config() {
+        let config = ReserveConfig {
+            optimal_utilization_rate: 80,
+            min_borrow_rate: 2,
+            optimal_borrow_rate: 10,
+            max_borrow_rate: 50,
+            ..ReserveConfig::default()
+        };
+        assert!(config.borrow_rate_curve.is_empty());
+        assert_eq!(
+            config.effective_borrow_rate_curve().points(),
+            &[
+                BorrowRateCurvePoint {
+                    utilization_rate_bps: 0,
+                    borrow_rate_bps: 200,
+                },
+                BorrowRateCurvePoint {
+                    utilization_rate_bps: 8_000,
+                    borrow_rate_bps: 1_000,
+                },
+                BorrowRateCurvePoint {
+                    utilization_rate_bps: 10_000,
+                    borrow_rate_bps: 5_000,
+                },
+            ]
+        );
+
+        let expected = [
+            (0, Rate::from_percent(2)),
+            (40, Rate::from_percent(6)),
+            (80, Rate::from_percent(10)),
+            (90, Rate::from_percent(30)),
+            (100, Rate::from_percent(50)),
+        ];
+        for (utilization_percent, borrow_rate) in expected {
+            let reserve = reserve_with_utilization(utilization_percent, config);
+            assert_eq!(reserve.current_borrow_rate().unwrap(), borrow_rate);
+        }
+    }
+
+    #[test]
+    fn borrow_rate_curve_migration() {
+        let reserve = Reserve {
+            version: PROGRAM_VERSION,
+            config: ReserveConfig {
+                optimal_utilization_rate: 80,
+                min_borrow_rate: 2,
+                optimal_borrow_rate: 10,
+                max_borrow_rate: 50,
+                ..ReserveConfig::default()
+            },
+            ..Reserve::default()
+        };
+
+        // reserves created before borrow rate curves have zeroed padding, which
+        // unpacks to an empty curve
+        let mut packed = [0u8; Reserve::LEN];
+        Reserve::pack(reserve.clone(), &mut packed).unwrap();
+        let unpacked = Reserve::unpack(&packed).unwrap();
+        assert_eq!(unpacked.config.borrow_rate_curve, BorrowRateCurve::LEGACY);
+        assert_eq!(
+            unpacked.current_borrow_rate().unwrap(),
+            reserve.current_borrow_rate().unwrap()
+        );
+
+        let mut reserve = reserve;
+        reserve.config.borrow_rate_curve = BorrowRateCurve::new(&[
+            BorrowRateCurvePoint {
+                utilization_rate_bps: 0,
+                borrow_rate_bps: 0,
+            },
+            BorrowRateCurvePoint {
+                utilization_rate_bps: 9_000,
+                borrow_rate_bps: 800,
+            },
+            BorrowRateCurvePoint {
+                utilization_rate_bps: 10_000,
+                borrow_rate_bps: 65_535,
+            },
+        ])
+        .unwrap();
+        Reserve::pack(reserve.clone(), &mut packed).unwrap();
+        assert_eq!(Reserve::unpack(&packed).unwrap(), reserve);
+    }
+
+    #[test]
+    fn borrow_rate_curve_validation() {
+        let point = |utilization_rate_bps, borrow_rate_bps| BorrowRateCurvePoint {
+            utilization_rate_bps,
+            borrow_rate_bps,
+        };
+        let validate = |points: &[BorrowRateCurvePoint]| {
+            ReserveConfig {
+                liquidation_threshold: 1,
+                borrow_rate_curve: BorrowRateCurve::new(points)?,
+                ..ReserveConfig::default()
+            }
+            .validate()
+        };
+        let invalid_config = Err(LendingError::InvalidConfig.into());
+
+        assert_eq!(validate(&[]), Ok(()));
+        assert_eq!(validate(&[point(0, 100), point(10_000, 100)]), Ok(()));
+        assert_eq!(
+            validate(&[point(0, 0), point(7_000, 400), point(10_000, 20_000)]),
+            Ok(())
+        );
+        assert_eq!(validate(&[point(0, 100)]), invalid_config);
+        assert_eq!(
+            validate(&[point(100, 100), point(10_000, 200)]),
+            invalid_config
+        );
+        assert_eq!(
+            validate(&[point(0, 100), point(9_000, 200)]),
+            invalid_config
+        );
+        assert_eq!(
+            validate(&[
+                point(0, 100),
+                point(5_000, 200),
+                point(5_000, 300),
+                point(10_000, 400)
+            ]),
+            invalid_config
+        );
+        assert_eq!(
+            validate(&[point(0, 100), point(5_000, 50), point(10_000, 400)]),
+            invalid_config
+        );
+        assert_eq!(
+            validate(&[point(0, 0); MAX_BORROW_RATE_CURVE_POINTS + 1]),
+            invalid_config
+        );
+    }
+
     #[test]
     fn borrow_fee_calculation_min_host() {
         let fees = ReserveFees {
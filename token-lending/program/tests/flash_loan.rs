@@ -223,3 +223,87 @@ async fn test_failure() {
         )
     );
 }
+
+#[tokio::test]
+async fn test_borrow_limit_exceeded() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    const FLASH_LOAN_AMOUNT: u64 = 1_000 * FRACTIONAL_TO_USDC;
+    const FEE_AMOUNT: u64 = 3_000_000;
+
+    let flash_loan_receiver_program_keypair = Keypair::new();
+    let flash_loan_receiver_program_id = flash_loan_receiver_program_keypair.pubkey();
+    test.prefer_bpf(false);
+    test.add_program(
+        "flash_loan_receiver",
+        flash_loan_receiver_program_id,
+        processor!(helpers::flash_loan_receiver::process_instruction),
+    );
+
+    let user_accounts_owner = Keypair::new();
+    let lending_market = add_lending_market(&mut test);
+
+    let mut reserve_config = TEST_RESERVE_CONFIG;
+    reserve_config.fees.flash_loan_fee_wad = 3_000_000_000_000_000;
+    reserve_config.borrow_limit = FLASH_LOAN_AMOUNT - 1;
+
+    let usdc_mint = add_usdc_mint(&mut test);
+    let usdc_oracle = add_usdc_oracle(&mut test);
+    let usdc_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &usdc_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            liquidity_amount: FLASH_LOAN_AMOUNT,
+            liquidity_mint_pubkey: usdc_mint.pubkey,
+            liquidity_mint_decimals: usdc_mint.decimals,
+            config: reserve_config,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let (receiver_authority_pubkey, _) =
+        Pubkey::find_program_address(&[b"flashloan"], &flash_loan_receiver_program_id);
+    let program_owned_token_account = add_account_for_program(
+        &mut test,
+        &receiver_authority_pubkey,
+        FEE_AMOUNT,
+        &usdc_mint.pubkey,
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[flash_loan(
+            spl_token_lending::id(),
+            FLASH_LOAN_AMOUNT,
+            usdc_test_reserve.liquidity_supply_pubkey,
+            program_owned_token_account,
+            usdc_test_reserve.pubkey,
+            usdc_test_reserve.liquidity_fee_receiver_pubkey,
+            usdc_test_reserve.liquidity_host_pubkey,
+            lending_market.pubkey,
+            flash_loan_receiver_program_id,
+            vec![AccountMeta::new_readonly(receiver_authority_pubkey, false)],
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(LendingError::BorrowLimitExceeded as u32)
+        )
+    );
+}
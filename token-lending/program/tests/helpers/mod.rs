@@ -4,10 +4,11 @@ pub mod flash_loan_receiver;
 pub mod genesis;
 
 use assert_matches::*;
-use solana_program::{program_option::COption, program_pack::Pack, pubkey::Pubkey};
+use solana_program::{program_option::COption, program_pack::Pack, pubkey::Pubkey, rent::Rent};
 use solana_program_test::*;
 use solana_sdk::{
     account::Account,
+    instruction::AccountMeta,
     signature::{read_keypair_file, Keypair, Signer},
     system_instruction::create_account,
     transaction::{Transaction, TransactionError},
@@ -20,14 +21,16 @@ use spl_token_lending::{
     instruction::{
         borrow_obligation_liquidity, deposit_reserve_liquidity, init_lending_market,
         init_obligation, init_reserve, liquidate_obligation, refresh_reserve,
+        refresh_reserve_with_fallback,
     },
     math::{Decimal, Rate, TryAdd, TryMul},
     pyth,
     state::{
-        InitLendingMarketParams, InitObligationParams, InitReserveParams, LendingMarket,
-        NewReserveCollateralParams, NewReserveLiquidityParams, Obligation, ObligationCollateral,
-        ObligationLiquidity, Reserve, ReserveCollateral, ReserveConfig, ReserveFees,
-        ReserveLiquidity, INITIAL_COLLATERAL_RATIO, PROGRAM_VERSION,
+        BorrowRateCurve, InitLendingMarketParams, InitObligationParams, InitReserveParams,
+        LendingMarket, NewReserveCollateralParams, NewReserveLiquidityParams, Obligation,
+        ObligationCollateral, ObligationLiquidity, Reserve, ReserveCollateral, ReserveConfig,
+        ReserveFees, ReserveLiquidity, INITIAL_COLLATERAL_RATIO, LEGACY_OBLIGATION_LEN,
+        PROGRAM_VERSION,
     },
 };
 use std::{convert::TryInto, str::FromStr};
@@ -53,6 +56,15 @@ pub const TEST_RESERVE_CONFIG: ReserveConfig = ReserveConfig {
         flash_loan_fee_wad: 3_000_000_000_000_000,
         host_fee_percentage: 20,
     },
+    borrow_rate_curve: BorrowRateCurve::LEGACY,
+    deposit_limit: 0,
+    borrow_limit: 0,
+    max_price_confidence_bps: 0,
+    max_price_age_slots: 0,
+    fallback_oracle_pubkey: Pubkey::new_from_array([0; 32]),
+    reserve_factor: 0,
+    max_liquidation_bonus: 0,
+    elevation_group: 0,
 };
 
 pub const SOL_PYTH_PRODUCT: &str = "3Mnn2fX6rQyUsyELYms1sBJyChWofzSNRoqYzvgMVz5E";
@@ -88,6 +100,14 @@ impl AddPacked for ProgramTest {
 }
 
 pub fn add_lending_market(test: &mut ProgramTest) -> TestLendingMarket {
+    add_lending_market_with_update(test, |_| {})
+}
+
+/// Add a lending market, then update its state, e.g. to add elevation groups
+pub fn add_lending_market_with_update(
+    test: &mut ProgramTest,
+    update: impl FnOnce(&mut LendingMarket),
+) -> TestLendingMarket {
     let lending_market_pubkey = Pubkey::new_unique();
     let (lending_market_authority, bump_seed) =
         Pubkey::find_program_address(&[lending_market_pubkey.as_ref()], &spl_token_lending::id());
@@ -98,16 +118,19 @@ pub fn add_lending_market(test: &mut ProgramTest) -> TestLendingMarket {
         .unwrap()
         .pubkey();
 
+    let mut lending_market = LendingMarket::new(InitLendingMarketParams {
+        bump_seed,
+        owner: lending_market_owner.pubkey(),
+        quote_currency: QUOTE_CURRENCY,
+        token_program_id: spl_token::id(),
+        oracle_program_id,
+    });
+    update(&mut lending_market);
+
     test.add_packable_account(
         lending_market_pubkey,
         u32::MAX as u64,
-        &LendingMarket::new(InitLendingMarketParams {
-            bump_seed,
-            owner: lending_market_owner.pubkey(),
-            quote_currency: QUOTE_CURRENCY,
-            token_program_id: spl_token::id(),
-            oracle_program_id,
-        }),
+        &lending_market,
         &spl_token_lending::id(),
     );
 
@@ -124,8 +147,11 @@ pub fn add_lending_market(test: &mut ProgramTest) -> TestLendingMarket {
 pub struct AddObligationArgs<'a> {
     pub deposits: &'a [(&'a TestReserve, u64)],
     pub borrows: &'a [(&'a TestReserve, u64)],
+    pub elevation_group: u8,
     pub mark_fresh: bool,
     pub slots_elapsed: u64,
+    /// Create the obligation account with the length used before elevation groups
+    pub legacy_len: bool,
 }
 
 pub fn add_obligation(
@@ -137,8 +163,10 @@ pub fn add_obligation(
     let AddObligationArgs {
         deposits,
         borrows,
+        elevation_group,
         mark_fresh,
         slots_elapsed,
+        legacy_len,
     } = args;
 
     let obligation_keypair = Keypair::new();
@@ -190,17 +218,29 @@ pub fn add_obligation(
         deposits: obligation_deposits,
         borrows: obligation_borrows,
     });
+    obligation.elevation_group = elevation_group;
 
     if mark_fresh {
         obligation.last_update.update_slot(current_slot);
     }
 
-    test.add_packable_account(
-        obligation_pubkey,
-        u32::MAX as u64,
-        &obligation,
-        &spl_token_lending::id(),
-    );
+    if legacy_len {
+        let mut account = Account::new(
+            Rent::default().minimum_balance(LEGACY_OBLIGATION_LEN),
+            Obligation::LEN,
+            &spl_token_lending::id(),
+        );
+        obligation.pack_into_slice(&mut account.data);
+        account.data.truncate(LEGACY_OBLIGATION_LEN);
+        test.add_account(obligation_pubkey, account);
+    } else {
+        test.add_packable_account(
+            obligation_pubkey,
+            u32::MAX as u64,
+            &obligation,
+            &spl_token_lending::id(),
+        );
+    }
 
     TestObligation {
         pubkey: obligation_pubkey,
@@ -221,6 +261,7 @@ pub struct AddReserveArgs {
     pub user_liquidity_amount: u64,
     pub borrow_amount: u64,
     pub initial_borrow_rate: u8,
+    pub accumulated_protocol_fees: u64,
     pub collateral_amount: u64,
     pub mark_fresh: bool,
     pub slots_elapsed: u64,
@@ -242,6 +283,7 @@ pub fn add_reserve(
         user_liquidity_amount,
         borrow_amount,
         initial_borrow_rate,
+        accumulated_protocol_fees,
         collateral_amount,
         mark_fresh,
         slots_elapsed,
@@ -358,6 +400,7 @@ pub fn add_reserve(
         .unwrap();
     reserve.liquidity.cumulative_borrow_rate_wads =
         Decimal::one().try_mul(borrow_rate_multiplier).unwrap();
+    reserve.liquidity.accumulated_protocol_fees_wads = Decimal::from(accumulated_protocol_fees);
 
     if mark_fresh {
         reserve.last_update.update_slot(current_slot);
@@ -446,6 +489,28 @@ pub fn add_account_for_program(
     program_owned_token_account.pubkey()
 }
 
+/// Add a token account at a given address, e.g. an associated token account
+pub fn add_token_account(
+    test: &mut ProgramTest,
+    pubkey: Pubkey,
+    mint_pubkey: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) {
+    test.add_packable_account(
+        pubkey,
+        u32::MAX as u64,
+        &Token {
+            mint: *mint_pubkey,
+            owner: *owner,
+            amount,
+            state: AccountState::Initialized,
+            ..Token::default()
+        },
+        &spl_token::id(),
+    );
+}
+
 pub struct TestLendingMarket {
     pub pubkey: Pubkey,
     pub owner: Keypair,
@@ -525,11 +590,19 @@ impl TestLendingMarket {
         reserve: &TestReserve,
     ) {
         let mut transaction = Transaction::new_with_payer(
-            &[refresh_reserve(
-                spl_token_lending::id(),
-                reserve.pubkey,
-                reserve.liquidity_oracle_pubkey,
-            )],
+            &[match reserve.config.fallback_oracle() {
+                Some(fallback_oracle_pubkey) => refresh_reserve_with_fallback(
+                    spl_token_lending::id(),
+                    reserve.pubkey,
+                    reserve.liquidity_oracle_pubkey,
+                    fallback_oracle_pubkey,
+                ),
+                None => refresh_reserve(
+                    spl_token_lending::id(),
+                    reserve.pubkey,
+                    reserve.liquidity_oracle_pubkey,
+                ),
+            }],
             Some(&payer.pubkey()),
         );
 
@@ -713,6 +786,7 @@ impl TestReserve {
         banks_client: &mut BanksClient,
         lending_market: &TestLendingMarket,
         oracle: &TestOracle,
+        fallback_oracle: Option<&TestOracle>,
         liquidity_amount: u64,
         config: ReserveConfig,
         liquidity_mint_pubkey: Pubkey,
@@ -737,6 +811,33 @@ impl TestReserve {
             .unwrap();
         let liquidity_mint = Mint::unpack(&liquidity_mint_account.data[..]).unwrap();
 
+        let mut init_reserve_instruction = init_reserve(
+            spl_token_lending::id(),
+            liquidity_amount,
+            config,
+            user_liquidity_pubkey,
+            user_collateral_token_keypair.pubkey(),
+            reserve_pubkey,
+            liquidity_mint_pubkey,
+            liquidity_supply_keypair.pubkey(),
+            liquidity_fee_receiver_keypair.pubkey(),
+            collateral_mint_keypair.pubkey(),
+            collateral_supply_keypair.pubkey(),
+            oracle.product_pubkey,
+            oracle.price_pubkey,
+            lending_market.pubkey,
+            lending_market.owner.pubkey(),
+            user_transfer_authority_keypair.pubkey(),
+        );
+        if let Some(fallback_oracle) = fallback_oracle {
+            init_reserve_instruction
+                .accounts
+                .push(AccountMeta::new_readonly(
+                    fallback_oracle.product_pubkey,
+                    false,
+                ));
+        }
+
         let rent = banks_client.get_rent().await.unwrap();
         let mut transaction = Transaction::new_with_payer(
             &[
@@ -798,24 +899,7 @@ impl TestReserve {
                     Reserve::LEN as u64,
                     &spl_token_lending::id(),
                 ),
-                init_reserve(
-                    spl_token_lending::id(),
-                    liquidity_amount,
-                    config,
-                    user_liquidity_pubkey,
-                    user_collateral_token_keypair.pubkey(),
-                    reserve_pubkey,
-                    liquidity_mint_pubkey,
-                    liquidity_supply_keypair.pubkey(),
-                    liquidity_fee_receiver_keypair.pubkey(),
-                    collateral_mint_keypair.pubkey(),
-                    collateral_supply_keypair.pubkey(),
-                    oracle.product_pubkey,
-                    oracle.price_pubkey,
-                    lending_market.pubkey,
-                    lending_market.owner.pubkey(),
-                    user_transfer_authority_keypair.pubkey(),
-                ),
+                init_reserve_instruction,
             ],
             Some(&payer.pubkey()),
         );
@@ -967,7 +1051,7 @@ impl TestObligation {
             .await
             .unwrap()
             .unwrap();
-        Obligation::unpack(&obligation_account.data[..]).unwrap()
+        Obligation::unpack_account(&obligation_account.data[..]).unwrap()
     }
 
     pub async fn validate_state(&self, banks_client: &mut BanksClient) {
@@ -992,7 +1076,7 @@ impl TestObligationCollateral {
             .await
             .unwrap()
             .unwrap();
-        Obligation::unpack(&obligation_account.data[..]).unwrap()
+        Obligation::unpack_account(&obligation_account.data[..]).unwrap()
     }
 
     pub async fn validate_state(&self, banks_client: &mut BanksClient) {
@@ -1020,7 +1104,7 @@ impl TestObligationLiquidity {
             .await
             .unwrap()
             .unwrap();
-        Obligation::unpack(&obligation_account.data[..]).unwrap()
+        Obligation::unpack_account(&obligation_account.data[..]).unwrap()
     }
 
     pub async fn validate_state(&self, banks_client: &mut BanksClient) {
@@ -1094,6 +1178,17 @@ pub fn add_oracle(
     product_pubkey: Pubkey,
     price_pubkey: Pubkey,
     price: Decimal,
+) -> TestOracle {
+    add_oracle_with_update(test, product_pubkey, price_pubkey, price, |_| {})
+}
+
+/// Add a Pyth oracle, then update the price account fixture, e.g. to set its confidence
+pub fn add_oracle_with_update(
+    test: &mut ProgramTest,
+    product_pubkey: Pubkey,
+    price_pubkey: Pubkey,
+    price: Decimal,
+    update: impl FnOnce(&mut pyth::Price),
 ) -> TestOracle {
     let oracle_program_id = read_keypair_file("tests/fixtures/oracle_program_id.json").unwrap();
 
@@ -1106,7 +1201,47 @@ pub fn add_oracle(
     );
 
     // Add Pyth price account after setting the price
-    let filename = &format!("{}.bin", price_pubkey);
+    test.add_account(
+        price_pubkey,
+        pyth_price_account(price_pubkey, price, oracle_program_id.pubkey(), update),
+    );
+
+    TestOracle {
+        product_pubkey,
+        price_pubkey,
+        price,
+    }
+}
+
+/// Add a copy of the SOL Pyth price account fixture at a new address, for use as a reserve
+/// fallback oracle, then update it like `add_oracle_with_update`
+pub fn add_fallback_oracle(
+    test: &mut ProgramTest,
+    price: Decimal,
+    owner: Option<Pubkey>,
+    update: impl FnOnce(&mut pyth::Price),
+) -> Pubkey {
+    let oracle_program_id = read_keypair_file("tests/fixtures/oracle_program_id.json").unwrap();
+    let pubkey = Pubkey::new_unique();
+    test.add_account(
+        pubkey,
+        pyth_price_account(
+            Pubkey::from_str(SOL_PYTH_PRICE).unwrap(),
+            price,
+            owner.unwrap_or_else(|| oracle_program_id.pubkey()),
+            update,
+        ),
+    );
+    pubkey
+}
+
+fn pyth_price_account(
+    fixture_pubkey: Pubkey,
+    price: Decimal,
+    owner: Pubkey,
+    update: impl FnOnce(&mut pyth::Price),
+) -> Account {
+    let filename = &format!("{}.bin", fixture_pubkey);
     let mut pyth_price_data = read_file(find_file(filename).unwrap_or_else(|| {
         panic!("Unable to locate {}", filename);
     }));
@@ -1119,28 +1254,20 @@ pub fn add_oracle(
 
     pyth_price.valid_slot = 0;
     pyth_price.agg.price = price
-        .try_round_u64()
+        .try_mul(decimals)
         .unwrap()
-        .checked_mul(decimals)
+        .try_round_u64()
         .unwrap()
         .try_into()
         .unwrap();
-
-    test.add_account(
-        price_pubkey,
-        Account {
-            lamports: u32::MAX as u64,
-            data: pyth_price_data,
-            owner: oracle_program_id.pubkey(),
-            executable: false,
-            rent_epoch: 0,
-        },
-    );
-
-    TestOracle {
-        product_pubkey,
-        price_pubkey,
-        price,
+    update(pyth_price);
+
+    Account {
+        lamports: u32::MAX as u64,
+        data: pyth_price_data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
     }
 }
 
@@ -51,6 +51,7 @@ async fn test_success() {
         &mut banks_client,
         &lending_market,
         &sol_oracle,
+        None,
         RESERVE_AMOUNT,
         TEST_RESERVE_CONFIG,
         spl_token::native_mint::id(),
@@ -186,6 +187,7 @@ async fn test_invalid_fees() {
                 &mut banks_client,
                 &lending_market,
                 &sol_oracle,
+                None,
                 RESERVE_AMOUNT,
                 config,
                 spl_token::native_mint::id(),
@@ -217,6 +219,7 @@ async fn test_invalid_fees() {
                 &mut banks_client,
                 &lending_market,
                 &sol_oracle,
+                None,
                 RESERVE_AMOUNT,
                 config,
                 spl_token::native_mint::id(),
@@ -233,3 +236,121 @@ async fn test_invalid_fees() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_invalid_fallback_oracle() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let user_accounts_owner = Keypair::new();
+    let lending_market = add_lending_market(&mut test);
+    let sol_oracle = add_sol_oracle(&mut test);
+    let usdc_oracle = add_usdc_oracle(&mut test);
+
+    let (mut banks_client, payer, _recent_blockhash) = test.start().await;
+
+    const RESERVE_AMOUNT: u64 = 42;
+
+    let sol_user_liquidity_account = create_and_mint_to_token_account(
+        &mut banks_client,
+        spl_token::native_mint::id(),
+        None,
+        &payer,
+        user_accounts_owner.pubkey(),
+        RESERVE_AMOUNT,
+    )
+    .await;
+
+    let mut config = TEST_RESERVE_CONFIG;
+    config.fallback_oracle_pubkey = usdc_oracle.price_pubkey;
+
+    // fallback oracle product account missing
+    assert_eq!(
+        TestReserve::init(
+            "sol".to_owned(),
+            &mut banks_client,
+            &lending_market,
+            &sol_oracle,
+            None,
+            RESERVE_AMOUNT,
+            config,
+            spl_token::native_mint::id(),
+            sol_user_liquidity_account,
+            &payer,
+            &user_accounts_owner,
+        )
+        .await
+        .unwrap_err(),
+        TransactionError::InstructionError(8, InstructionError::NotEnoughAccountKeys)
+    );
+
+    // fallback oracle product account for a different price account
+    assert_eq!(
+        TestReserve::init(
+            "sol".to_owned(),
+            &mut banks_client,
+            &lending_market,
+            &sol_oracle,
+            Some(&sol_oracle),
+            RESERVE_AMOUNT,
+            config,
+            spl_token::native_mint::id(),
+            sol_user_liquidity_account,
+            &payer,
+            &user_accounts_owner,
+        )
+        .await
+        .unwrap_err(),
+        TransactionError::InstructionError(
+            8,
+            InstructionError::Custom(LendingError::InvalidOracleConfig as u32)
+        )
+    );
+
+    // fallback oracle product account not owned by the oracle program
+    let fake_oracle = TestOracle {
+        product_pubkey: lending_market.pubkey,
+        ..usdc_oracle
+    };
+    assert_eq!(
+        TestReserve::init(
+            "sol".to_owned(),
+            &mut banks_client,
+            &lending_market,
+            &sol_oracle,
+            Some(&fake_oracle),
+            RESERVE_AMOUNT,
+            config,
+            spl_token::native_mint::id(),
+            sol_user_liquidity_account,
+            &payer,
+            &user_accounts_owner,
+        )
+        .await
+        .unwrap_err(),
+        TransactionError::InstructionError(
+            8,
+            InstructionError::Custom(LendingError::InvalidOracleConfig as u32)
+        )
+    );
+
+    let sol_reserve = TestReserve::init(
+        "sol".to_owned(),
+        &mut banks_client,
+        &lending_market,
+        &sol_oracle,
+        Some(&usdc_oracle),
+        RESERVE_AMOUNT,
+        config,
+        spl_token::native_mint::id(),
+        sol_user_liquidity_account,
+        &payer,
+        &user_accounts_owner,
+    )
+    .await
+    .unwrap();
+    sol_reserve.validate_state(&mut banks_client).await;
+}
@@ -0,0 +1,106 @@
+#![allow(clippy::integer_arithmetic)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use helpers::*;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use spl_token_lending::{
+    error::LendingError, instruction::modify_elevation_group, processor::process_instruction,
+    state::ElevationGroup,
+};
+
+const TEST_ELEVATION_GROUP: ElevationGroup = ElevationGroup {
+    id: 1,
+    loan_to_value_ratio: 90,
+    liquidation_threshold: 95,
+};
+
+#[tokio::test]
+async fn test_success() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    // limit to track compute unit increase
+    test.set_compute_max_units(4_000);
+
+    let lending_market = add_lending_market_with_update(&mut test, |lending_market| {
+        lending_market
+            .add_elevation_group(TEST_ELEVATION_GROUP)
+            .unwrap();
+    });
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let new_elevation_group = ElevationGroup {
+        loan_to_value_ratio: 85,
+        liquidation_threshold: 90,
+        ..TEST_ELEVATION_GROUP
+    };
+    let mut transaction = Transaction::new_with_payer(
+        &[modify_elevation_group(
+            spl_token_lending::id(),
+            lending_market.pubkey,
+            lending_market.owner.pubkey(),
+            new_elevation_group,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer, &lending_market.owner], recent_blockhash);
+
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())
+        .unwrap();
+
+    let lending_market_info = lending_market.get_state(&mut banks_client).await;
+    assert_eq!(
+        lending_market_info.find_elevation_group(TEST_ELEVATION_GROUP.id),
+        Some(&new_elevation_group)
+    );
+}
+
+#[tokio::test]
+async fn test_does_not_exist() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let lending_market = add_lending_market(&mut test);
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[modify_elevation_group(
+            spl_token_lending::id(),
+            lending_market.pubkey,
+            lending_market.owner.pubkey(),
+            TEST_ELEVATION_GROUP,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer, &lending_market.owner], recent_blockhash);
+
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(LendingError::InvalidElevationGroup as u32)
+        )
+    );
+}
@@ -13,11 +13,11 @@ use solana_sdk::{
 };
 use spl_token_lending::{
     error::LendingError,
-    instruction::modify_reserve_config,
+    instruction::{modify_reserve_config, modify_reserve_config_with_fallback},
     processor::process_instruction,
     state::{
-        InitLendingMarketParams, LendingMarket, ReserveConfig, ReserveFees,
-        INITIAL_COLLATERAL_RATIO,
+        BorrowRateCurve, BorrowRateCurvePoint, InitLendingMarketParams, LendingMarket,
+        ReserveConfig, ReserveFees, INITIAL_COLLATERAL_RATIO,
     },
 };
 
@@ -34,6 +34,7 @@ async fn modify_reserve_config_success() {
     let user_accounts_owner = Keypair::new();
     let lending_market = add_lending_market(&mut test);
     let sol_oracle = add_sol_oracle(&mut test);
+    let usdc_oracle = add_usdc_oracle(&mut test);
 
     const SOL_DEPOSIT_AMOUNT_LAMPORTS: u64 = 10 * LAMPORTS_TO_SOL * INITIAL_COLLATERAL_RATIO;
     const SOL_RESERVE_COLLATERAL_LAMPORTS: u64 = 2 * SOL_DEPOSIT_AMOUNT_LAMPORTS;
@@ -72,15 +73,43 @@ async fn modify_reserve_config_success() {
             flash_loan_fee_wad: 3_000_000_000_000_000,
             host_fee_percentage: 20,
         },
+        borrow_rate_curve: BorrowRateCurve::new(&[
+            BorrowRateCurvePoint {
+                utilization_rate_bps: 0,
+                borrow_rate_bps: 0,
+            },
+            BorrowRateCurvePoint {
+                utilization_rate_bps: 7_000,
+                borrow_rate_bps: 400,
+            },
+            BorrowRateCurvePoint {
+                utilization_rate_bps: 9_000,
+                borrow_rate_bps: 1_500,
+            },
+            BorrowRateCurvePoint {
+                utilization_rate_bps: 10_000,
+                borrow_rate_bps: 30_000,
+            },
+        ])
+        .unwrap(),
+        deposit_limit: 1_000 * LAMPORTS_TO_SOL,
+        borrow_limit: 500 * LAMPORTS_TO_SOL,
+        max_price_confidence_bps: 100,
+        max_price_age_slots: 10,
+        fallback_oracle_pubkey: usdc_oracle.price_pubkey,
+        reserve_factor: 20,
+        max_liquidation_bonus: 15,
+        elevation_group: 0,
     };
 
     let mut transaction = Transaction::new_with_payer(
-        &[modify_reserve_config(
+        &[modify_reserve_config_with_fallback(
             spl_token_lending::id(),
             new_config,
             sol_test_reserve.pubkey,
             lending_market.pubkey,
             lending_market.owner.pubkey(),
+            usdc_oracle.product_pubkey,
         )],
         Some(&payer.pubkey()),
     );
@@ -97,6 +126,94 @@ async fn modify_reserve_config_success() {
     assert_eq!(reserve_info.config, new_config);
 }
 
+#[tokio::test]
+async fn modify_reserve_config_invalid_fallback_oracle() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    test.set_compute_max_units(70_000);
+
+    let user_accounts_owner = Keypair::new();
+    let lending_market = add_lending_market(&mut test);
+    let sol_oracle = add_sol_oracle(&mut test);
+    let usdc_oracle = add_usdc_oracle(&mut test);
+
+    let sol_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &sol_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            liquidity_amount: LAMPORTS_TO_SOL,
+            liquidity_mint_decimals: 9,
+            liquidity_mint_pubkey: spl_token::native_mint::id(),
+            config: TEST_RESERVE_CONFIG,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let mut new_config = TEST_RESERVE_CONFIG;
+    new_config.fallback_oracle_pubkey = usdc_oracle.price_pubkey;
+
+    // fallback oracle product account missing, for a product account for a different price
+    // account, and for a product account not owned by the oracle program
+    for (instruction, error) in [
+        (
+            modify_reserve_config(
+                spl_token_lending::id(),
+                new_config,
+                sol_test_reserve.pubkey,
+                lending_market.pubkey,
+                lending_market.owner.pubkey(),
+            ),
+            InstructionError::NotEnoughAccountKeys,
+        ),
+        (
+            modify_reserve_config_with_fallback(
+                spl_token_lending::id(),
+                new_config,
+                sol_test_reserve.pubkey,
+                lending_market.pubkey,
+                lending_market.owner.pubkey(),
+                sol_oracle.product_pubkey,
+            ),
+            InstructionError::Custom(LendingError::InvalidOracleConfig as u32),
+        ),
+        (
+            modify_reserve_config_with_fallback(
+                spl_token_lending::id(),
+                new_config,
+                sol_test_reserve.pubkey,
+                lending_market.pubkey,
+                lending_market.owner.pubkey(),
+                lending_market.pubkey,
+            ),
+            InstructionError::Custom(LendingError::InvalidOracleConfig as u32),
+        ),
+    ] {
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        transaction.sign(&[&payer, &lending_market.owner], recent_blockhash);
+
+        assert_eq!(
+            banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap_err()
+                .unwrap(),
+            TransactionError::InstructionError(0, error)
+        );
+    }
+
+    let reserve = sol_test_reserve.get_state(&mut banks_client).await;
+    assert_eq!(reserve.config, TEST_RESERVE_CONFIG);
+}
+
 #[tokio::test]
 // Invalid Signer - Right owner, right market but owner is not a signer
 async fn wrong_signer_of_lending_market_cannot_change_reserve_config() {
@@ -153,6 +270,15 @@ async fn wrong_signer_of_lending_market_cannot_change_reserve_config() {
             flash_loan_fee_wad: 3_000_000_000_000_000,
             host_fee_percentage: 20,
         },
+        borrow_rate_curve: BorrowRateCurve::LEGACY,
+        deposit_limit: 0,
+        borrow_limit: 0,
+        max_price_confidence_bps: 0,
+        max_price_age_slots: 0,
+        fallback_oracle_pubkey: Pubkey::default(),
+        reserve_factor: 0,
+        max_liquidation_bonus: 0,
+        elevation_group: 0,
     };
 
     let mut instruction = modify_reserve_config(
@@ -267,6 +393,15 @@ async fn owner_of_different_lending_market_cannot_change_reserve_config() {
             flash_loan_fee_wad: 3_000_000_000_000_000,
             host_fee_percentage: 20,
         },
+        borrow_rate_curve: BorrowRateCurve::LEGACY,
+        deposit_limit: 0,
+        borrow_limit: 0,
+        max_price_confidence_bps: 0,
+        max_price_age_slots: 0,
+        fallback_oracle_pubkey: Pubkey::default(),
+        reserve_factor: 0,
+        max_liquidation_bonus: 0,
+        elevation_group: 0,
     };
 
     let mut transaction = Transaction::new_with_payer(
@@ -356,6 +491,15 @@ async fn correct_owner_providing_wrong_lending_market_fails() {
             flash_loan_fee_wad: 3_000_000_000_000_000,
             host_fee_percentage: 20,
         },
+        borrow_rate_curve: BorrowRateCurve::LEGACY,
+        deposit_limit: 0,
+        borrow_limit: 0,
+        max_price_confidence_bps: 0,
+        max_price_age_slots: 0,
+        fallback_oracle_pubkey: Pubkey::default(),
+        reserve_factor: 0,
+        max_liquidation_bonus: 0,
+        elevation_group: 0,
     };
 
     let mut transaction = Transaction::new_with_payer(
@@ -4,17 +4,22 @@
 mod helpers;
 
 use helpers::*;
+use solana_program::pubkey::Pubkey;
 use solana_program_test::*;
 use solana_sdk::{
+    instruction::InstructionError,
     signature::{Keypair, Signer},
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
 use spl_token_lending::{
-    instruction::refresh_reserve,
+    error::LendingError,
+    instruction::{refresh_reserve, refresh_reserve_with_fallback},
     math::{Decimal, Rate, TryAdd, TryDiv, TryMul},
     processor::process_instruction,
-    state::SLOTS_PER_YEAR,
+    pyth,
+    state::{ReserveConfig, SLOTS_PER_YEAR},
 };
+use std::str::FromStr;
 
 #[tokio::test]
 async fn test_success() {
@@ -138,3 +143,280 @@ async fn test_success() {
         usdc_test_reserve.market_price
     );
 }
+
+/// Refresh a SOL reserve whose Pyth price account was last updated at slot 0
+async fn refresh_sol_reserve(
+    reserve_config: ReserveConfig,
+    current_slot: u64,
+    update_price: impl FnOnce(&mut pyth::Price),
+    add_fallback_oracle: impl FnOnce(&mut ProgramTest) -> Option<Pubkey>,
+    provide_fallback_oracle: bool,
+) -> Result<Decimal, TransactionError> {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let user_accounts_owner = Keypair::new();
+    let lending_market = add_lending_market(&mut test);
+
+    let sol_oracle = add_oracle_with_update(
+        &mut test,
+        Pubkey::from_str(SOL_PYTH_PRODUCT).unwrap(),
+        Pubkey::from_str(SOL_PYTH_PRICE).unwrap(),
+        Decimal::from(20u64),
+        update_price,
+    );
+
+    let mut reserve_config = reserve_config;
+    let fallback_oracle_pubkey = add_fallback_oracle(&mut test);
+    if let Some(fallback_oracle_pubkey) = fallback_oracle_pubkey {
+        reserve_config.fallback_oracle_pubkey = fallback_oracle_pubkey;
+    }
+
+    let sol_test_reserve = add_reserve(
+        &mut test,
+        &lending_market,
+        &sol_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            liquidity_amount: 100 * LAMPORTS_TO_SOL,
+            liquidity_mint_decimals: 9,
+            liquidity_mint_pubkey: spl_token::native_mint::id(),
+            config: reserve_config,
+            slots_elapsed: 1, // elapsed from 1; clock.slot = 2
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let mut test_context = test.start_with_context().await;
+    test_context.warp_to_slot(current_slot).unwrap();
+
+    let ProgramTestContext {
+        mut banks_client,
+        payer,
+        last_blockhash: recent_blockhash,
+        ..
+    } = test_context;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[if provide_fallback_oracle {
+            refresh_reserve_with_fallback(
+                spl_token_lending::id(),
+                sol_test_reserve.pubkey,
+                sol_oracle.price_pubkey,
+                fallback_oracle_pubkey.unwrap_or_else(Pubkey::new_unique),
+            )
+        } else {
+            refresh_reserve(
+                spl_token_lending::id(),
+                sol_test_reserve.pubkey,
+                sol_oracle.price_pubkey,
+            )
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|err| err.unwrap())?;
+
+    let sol_reserve = sol_test_reserve.get_state(&mut banks_client).await;
+    Ok(sol_reserve.liquidity.market_price)
+}
+
+fn instruction_error(error: LendingError) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+}
+
+#[tokio::test]
+async fn test_oracle_price_stale() {
+    assert_eq!(
+        refresh_sol_reserve(TEST_RESERVE_CONFIG, 5, |_| {}, |_| None, false).await,
+        Err(instruction_error(LendingError::OraclePriceStale))
+    );
+
+    let mut reserve_config = TEST_RESERVE_CONFIG;
+    reserve_config.max_price_age_slots = 10;
+    assert_eq!(
+        refresh_sol_reserve(reserve_config, 5, |_| {}, |_| None, false).await,
+        Ok(Decimal::from(20u64))
+    );
+    assert_eq!(
+        refresh_sol_reserve(reserve_config, 10, |_| {}, |_| None, false).await,
+        Err(instruction_error(LendingError::OraclePriceStale))
+    );
+}
+
+#[tokio::test]
+async fn test_oracle_price_not_trading() {
+    assert_eq!(
+        refresh_sol_reserve(
+            TEST_RESERVE_CONFIG,
+            3,
+            |pyth_price| pyth_price.agg.status = pyth::PriceStatus::Halted,
+            |_| None,
+            false,
+        )
+        .await,
+        Err(instruction_error(LendingError::OraclePriceNotTrading))
+    );
+}
+
+#[tokio::test]
+async fn test_oracle_price_not_positive() {
+    assert_eq!(
+        refresh_sol_reserve(
+            TEST_RESERVE_CONFIG,
+            3,
+            |pyth_price| pyth_price.agg.price = 0,
+            |_| None,
+            false,
+        )
+        .await,
+        Err(instruction_error(LendingError::OraclePriceNotPositive))
+    );
+}
+
+#[tokio::test]
+async fn test_oracle_price_confidence_too_wide() {
+    let mut reserve_config = TEST_RESERVE_CONFIG;
+    reserve_config.max_price_confidence_bps = 100;
+
+    // Confidence interval of 1% of the price
+    let set_confidence = |pyth_price: &mut pyth::Price| {
+        pyth_price.agg.conf = pyth_price.agg.price as u64 / 100;
+    };
+    assert_eq!(
+        refresh_sol_reserve(reserve_config, 3, set_confidence, |_| None, false).await,
+        Ok(Decimal::from(20u64))
+    );
+
+    // Confidence interval of 2% of the price
+    let set_confidence = |pyth_price: &mut pyth::Price| {
+        pyth_price.agg.conf = pyth_price.agg.price as u64 / 50;
+    };
+    assert_eq!(
+        refresh_sol_reserve(reserve_config, 3, set_confidence, |_| None, false).await,
+        Err(instruction_error(
+            LendingError::OraclePriceConfidenceTooWide
+        ))
+    );
+}
+
+/// Add a $20.50 fallback oracle, last updated at `valid_slot`, with a confidence interval of
+/// `confidence_cents`
+fn fallback_oracle(
+    valid_slot: u64,
+    confidence_cents: u64,
+    owner: Option<Pubkey>,
+) -> impl FnOnce(&mut ProgramTest) -> Option<Pubkey> {
+    move |test| {
+        Some(add_fallback_oracle(
+            test,
+            Decimal::from(2_050u64).try_div(100).unwrap(),
+            owner,
+            |pyth_price| {
+                let decimals = 10u64.pow(pyth_price.expo.unsigned_abs());
+                pyth_price.valid_slot = valid_slot;
+                pyth_price.agg.conf = confidence_cents * decimals / 100;
+            },
+        ))
+    }
+}
+
+#[tokio::test]
+async fn test_fallback_oracle() {
+    let mut reserve_config = TEST_RESERVE_CONFIG;
+    reserve_config.max_price_confidence_bps = 100;
+
+    // Fresh primary price is used over the fallback oracle
+    assert_eq!(
+        refresh_sol_reserve(reserve_config, 3, |_| {}, fallback_oracle(8, 5, None), true).await,
+        Ok(Decimal::from(20u64))
+    );
+
+    // Stale primary price falls back to the fallback oracle
+    assert_eq!(
+        refresh_sol_reserve(
+            reserve_config,
+            10,
+            |_| {},
+            fallback_oracle(8, 5, None),
+            true
+        )
+        .await,
+        Ok(Decimal::from(2_050u64).try_div(100).unwrap())
+    );
+
+    // Fallback oracle must be provided when the primary price is rejected
+    assert_eq!(
+        refresh_sol_reserve(
+            reserve_config,
+            10,
+            |_| {},
+            fallback_oracle(8, 5, None),
+            false
+        )
+        .await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::NotEnoughAccountKeys
+        ))
+    );
+
+    // Fallback oracle is subject to the reserve staleness window
+    assert_eq!(
+        refresh_sol_reserve(
+            reserve_config,
+            10,
+            |_| {},
+            fallback_oracle(2, 5, None),
+            true
+        )
+        .await,
+        Err(instruction_error(LendingError::OraclePriceStale))
+    );
+
+    // Fallback oracle is subject to the reserve confidence bound
+    assert_eq!(
+        refresh_sol_reserve(
+            reserve_config,
+            10,
+            |_| {},
+            fallback_oracle(8, 50, None),
+            true
+        )
+        .await,
+        Err(instruction_error(
+            LendingError::OraclePriceConfidenceTooWide
+        ))
+    );
+
+    // Fallback oracle must be owned by the same oracle program as the primary oracle
+    assert_eq!(
+        refresh_sol_reserve(
+            reserve_config,
+            10,
+            |_| {},
+            fallback_oracle(8, 5, Some(Pubkey::new_unique())),
+            true
+        )
+        .await,
+        Err(instruction_error(LendingError::InvalidFallbackOracle))
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_fallback_oracle() {
+    let mut reserve_config = TEST_RESERVE_CONFIG;
+    reserve_config.fallback_oracle_pubkey = Pubkey::new_unique();
+
+    assert_eq!(
+        refresh_sol_reserve(reserve_config, 10, |_| {}, |_| None, true).await,
+        Err(instruction_error(LendingError::InvalidFallbackOracle))
+    );
+}
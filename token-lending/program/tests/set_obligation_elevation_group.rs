@@ -0,0 +1,348 @@
+#![allow(clippy::integer_arithmetic)]
+#![cfg(feature = "test-sbf")]
+
+mod helpers;
+
+use helpers::*;
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    instruction::InstructionError,
+    program_pack::Pack,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use spl_token_lending::{
+    error::LendingError,
+    instruction::{
+        refresh_obligation, refresh_obligation_with_lending_market, set_obligation_elevation_group,
+    },
+    math::{Rate, TryMul},
+    processor::process_instruction,
+    state::{ElevationGroup, Obligation, INITIAL_COLLATERAL_RATIO, LEGACY_OBLIGATION_LEN},
+};
+
+const TEST_ELEVATION_GROUP: ElevationGroup = ElevationGroup {
+    id: 1,
+    loan_to_value_ratio: 90,
+    liquidation_threshold: 95,
+};
+
+// 100 SOL collateral -> 2000 USDC deposited value
+const SOL_DEPOSIT_AMOUNT_LAMPORTS: u64 = 100 * LAMPORTS_TO_SOL * INITIAL_COLLATERAL_RATIO;
+// 75% of the deposited value, above the 50% reserve LTV and below the 90% group LTV
+const USDC_BORROW_AMOUNT_FRACTIONAL: u64 = 1_500 * FRACTIONAL_TO_USDC;
+
+/// Adds a market with an elevation group, and an obligation borrowing USDC against SOL
+fn add_test_obligation(
+    test: &mut ProgramTest,
+    usdc_reserve_elevation_group: u8,
+    obligation_elevation_group: u8,
+    legacy_len: bool,
+) -> (
+    TestLendingMarket,
+    TestReserve,
+    TestReserve,
+    TestObligation,
+    Keypair,
+) {
+    let user_accounts_owner = Keypair::new();
+    let lending_market = add_lending_market_with_update(test, |lending_market| {
+        lending_market
+            .add_elevation_group(TEST_ELEVATION_GROUP)
+            .unwrap();
+    });
+
+    let mut reserve_config = TEST_RESERVE_CONFIG;
+    reserve_config.elevation_group = TEST_ELEVATION_GROUP.id;
+
+    let sol_oracle = add_sol_oracle(test);
+    let sol_test_reserve = add_reserve(
+        test,
+        &lending_market,
+        &sol_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            collateral_amount: 2 * SOL_DEPOSIT_AMOUNT_LAMPORTS,
+            liquidity_mint_pubkey: spl_token::native_mint::id(),
+            liquidity_mint_decimals: 9,
+            config: reserve_config,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    reserve_config.elevation_group = usdc_reserve_elevation_group;
+
+    let usdc_mint = add_usdc_mint(test);
+    let usdc_oracle = add_usdc_oracle(test);
+    let usdc_test_reserve = add_reserve(
+        test,
+        &lending_market,
+        &usdc_oracle,
+        &user_accounts_owner,
+        AddReserveArgs {
+            borrow_amount: USDC_BORROW_AMOUNT_FRACTIONAL,
+            liquidity_amount: 2 * USDC_BORROW_AMOUNT_FRACTIONAL,
+            liquidity_mint_pubkey: usdc_mint.pubkey,
+            liquidity_mint_decimals: usdc_mint.decimals,
+            config: reserve_config,
+            mark_fresh: true,
+            ..AddReserveArgs::default()
+        },
+    );
+
+    let test_obligation = add_obligation(
+        test,
+        &lending_market,
+        &user_accounts_owner,
+        AddObligationArgs {
+            deposits: &[(&sol_test_reserve, SOL_DEPOSIT_AMOUNT_LAMPORTS)],
+            borrows: &[(&usdc_test_reserve, USDC_BORROW_AMOUNT_FRACTIONAL)],
+            elevation_group: obligation_elevation_group,
+            legacy_len,
+            ..AddObligationArgs::default()
+        },
+    );
+
+    (
+        lending_market,
+        sol_test_reserve,
+        usdc_test_reserve,
+        test_obligation,
+        user_accounts_owner,
+    )
+}
+
+#[tokio::test]
+async fn test_success() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let (lending_market, sol_test_reserve, usdc_test_reserve, test_obligation, user_accounts_owner) =
+        add_test_obligation(&mut test, TEST_ELEVATION_GROUP.id, 0, false);
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let reserve_pubkeys = vec![sol_test_reserve.pubkey, usdc_test_reserve.pubkey];
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            refresh_obligation(
+                spl_token_lending::id(),
+                test_obligation.pubkey,
+                reserve_pubkeys.clone(),
+            ),
+            set_obligation_elevation_group(
+                spl_token_lending::id(),
+                TEST_ELEVATION_GROUP.id,
+                test_obligation.pubkey,
+                lending_market.pubkey,
+                user_accounts_owner.pubkey(),
+                reserve_pubkeys.clone(),
+            ),
+            refresh_obligation_with_lending_market(
+                spl_token_lending::id(),
+                test_obligation.pubkey,
+                reserve_pubkeys,
+                lending_market.pubkey,
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer, &user_accounts_owner], recent_blockhash);
+    assert!(banks_client.process_transaction(transaction).await.is_ok());
+
+    let obligation = test_obligation.get_state(&mut banks_client).await;
+    assert_eq!(obligation.elevation_group, TEST_ELEVATION_GROUP.id);
+    assert_eq!(
+        obligation.allowed_borrow_value,
+        obligation
+            .deposited_value
+            .try_mul(Rate::from_percent(TEST_ELEVATION_GROUP.loan_to_value_ratio))
+            .unwrap()
+    );
+    assert_eq!(
+        obligation.unhealthy_borrow_value,
+        obligation
+            .deposited_value
+            .try_mul(Rate::from_percent(
+                TEST_ELEVATION_GROUP.liquidation_threshold
+            ))
+            .unwrap()
+    );
+    assert!(obligation.borrowed_value < obligation.allowed_borrow_value);
+}
+
+#[tokio::test]
+async fn test_resize_legacy_obligation() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let (lending_market, sol_test_reserve, usdc_test_reserve, test_obligation, user_accounts_owner) =
+        add_test_obligation(&mut test, TEST_ELEVATION_GROUP.id, 0, true);
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let obligation_account: Account = banks_client
+        .get_account(test_obligation.pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(obligation_account.data.len(), LEGACY_OBLIGATION_LEN);
+
+    let rent = Rent::default();
+    let resize_rent =
+        rent.minimum_balance(Obligation::LEN) - rent.minimum_balance(LEGACY_OBLIGATION_LEN);
+
+    let reserve_pubkeys = vec![sol_test_reserve.pubkey, usdc_test_reserve.pubkey];
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            system_instruction::transfer(
+                &payer.pubkey(),
+                &user_accounts_owner.pubkey(),
+                resize_rent + rent.minimum_balance(0),
+            ),
+            refresh_obligation(
+                spl_token_lending::id(),
+                test_obligation.pubkey,
+                reserve_pubkeys.clone(),
+            ),
+            set_obligation_elevation_group(
+                spl_token_lending::id(),
+                TEST_ELEVATION_GROUP.id,
+                test_obligation.pubkey,
+                lending_market.pubkey,
+                user_accounts_owner.pubkey(),
+                reserve_pubkeys,
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer, &user_accounts_owner], recent_blockhash);
+    assert!(banks_client.process_transaction(transaction).await.is_ok());
+
+    let obligation_account: Account = banks_client
+        .get_account(test_obligation.pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(obligation_account.data.len(), Obligation::LEN);
+    assert_eq!(
+        obligation_account.lamports,
+        rent.minimum_balance(Obligation::LEN)
+    );
+
+    let obligation = test_obligation.get_state(&mut banks_client).await;
+    assert_eq!(obligation.elevation_group, TEST_ELEVATION_GROUP.id);
+    assert_eq!(obligation.owner, user_accounts_owner.pubkey());
+}
+
+#[tokio::test]
+async fn test_reserve_not_in_elevation_group() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let (lending_market, sol_test_reserve, usdc_test_reserve, test_obligation, user_accounts_owner) =
+        add_test_obligation(&mut test, 0, 0, false);
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let reserve_pubkeys = vec![sol_test_reserve.pubkey, usdc_test_reserve.pubkey];
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            refresh_obligation(
+                spl_token_lending::id(),
+                test_obligation.pubkey,
+                reserve_pubkeys.clone(),
+            ),
+            set_obligation_elevation_group(
+                spl_token_lending::id(),
+                TEST_ELEVATION_GROUP.id,
+                test_obligation.pubkey,
+                lending_market.pubkey,
+                user_accounts_owner.pubkey(),
+                reserve_pubkeys,
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer, &user_accounts_owner], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(LendingError::ElevationGroupMismatch as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_leave_elevation_group_unhealthy() {
+    let mut test = ProgramTest::new(
+        "spl_token_lending",
+        spl_token_lending::id(),
+        processor!(process_instruction),
+    );
+
+    let (lending_market, sol_test_reserve, usdc_test_reserve, test_obligation, user_accounts_owner) =
+        add_test_obligation(
+            &mut test,
+            TEST_ELEVATION_GROUP.id,
+            TEST_ELEVATION_GROUP.id,
+            false,
+        );
+
+    let (mut banks_client, payer, recent_blockhash) = test.start().await;
+
+    let reserve_pubkeys = vec![sol_test_reserve.pubkey, usdc_test_reserve.pubkey];
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            refresh_obligation_with_lending_market(
+                spl_token_lending::id(),
+                test_obligation.pubkey,
+                reserve_pubkeys.clone(),
+                lending_market.pubkey,
+            ),
+            set_obligation_elevation_group(
+                spl_token_lending::id(),
+                0,
+                test_obligation.pubkey,
+                lending_market.pubkey,
+                user_accounts_owner.pubkey(),
+                reserve_pubkeys,
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer, &user_accounts_owner], recent_blockhash);
+    assert_eq!(
+        banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(LendingError::BorrowTooLarge as u32)
+        )
+    );
+}
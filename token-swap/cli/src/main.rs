@@ -0,0 +1,1840 @@
+#![allow(clippy::integer_arithmetic)]
+use {
+    clap::{
+        crate_description, crate_name, crate_version, value_t, App, AppSettings, Arg, ArgMatches,
+        SubCommand,
+    },
+    solana_clap_utils::{
+        fee_payer::fee_payer_arg,
+        input_parsers::{keypair_of, pubkey_of, value_of},
+        input_validators::{is_amount, is_keypair, is_parsable, is_pubkey, is_url},
+        keypair::signer_from_path,
+    },
+    solana_client::rpc_client::RpcClient,
+    solana_program::{
+        clock::Epoch, instruction::Instruction, native_token::lamports_to_sol, program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        message::Message,
+        signature::{Keypair, Signer},
+        system_instruction,
+        transaction::Transaction,
+    },
+    spl_math::precise_number::{PreciseNumber, ONE},
+    spl_token_2022::{
+        amount_to_ui_amount, amount_to_ui_amount_string_trimmed, check_spl_token_program_account,
+        extension::{transfer_fee::TransferFeeConfig, ExtensionType, StateWithExtensions},
+        instruction::{initialize_account, initialize_mint, transfer_checked},
+        state::{Account, Mint},
+        ui_amount_to_amount,
+    },
+    spl_token_swap::{
+        self,
+        constraints::SWAP_CONSTRAINTS,
+        curve::{
+            base::{CurveType, SwapCurve},
+            calculator::{RoundDirection, TradeDirection},
+            constant_price::ConstantPriceCurve,
+            constant_product::ConstantProductCurve,
+            fees::Fees,
+            offset::OffsetCurve,
+            stable::StableCurve,
+        },
+        instruction::{
+            deposit_all_token_types, deposit_single_token_type_exact_amount_in, initialize, swap,
+            withdraw_all_token_types, withdraw_single_token_type_exact_amount_out,
+            DepositAllTokenTypes, DepositSingleTokenTypeExactAmountIn, Swap, WithdrawAllTokenTypes,
+            WithdrawSingleTokenTypeExactAmountOut,
+        },
+        state::{SwapState, SwapVersion},
+    },
+    std::{convert::TryFrom, process::exit, sync::Arc},
+    system_instruction::create_account,
+};
+
+struct Config {
+    rpc_client: RpcClient,
+    fee_payer: Box<dyn Signer>,
+    swap_program_id: Pubkey,
+    verbose: bool,
+    dry_run: bool,
+}
+
+type Error = Box<dyn std::error::Error>;
+type CommandResult = Result<(), Error>;
+
+fn main() {
+    solana_logger::setup_with_default("solana=info");
+
+    let default_swap_program_id: &str = &spl_token_swap::id().to_string();
+    let default_pool_token_program_id: &str = &spl_token::id().to_string();
+
+    let pool_arg = Arg::with_name("pool")
+        .long("pool")
+        .validator(is_pubkey)
+        .value_name("PUBKEY")
+        .takes_value(true)
+        .required(true)
+        .help("Token swap pool address");
+    // @TODO: use is_valid_signer
+    let owner_arg = Arg::with_name("owner")
+        .long("owner")
+        .validator(is_keypair)
+        .value_name("KEYPAIR")
+        .takes_value(true)
+        .required(true)
+        .help("Owner of the token accounts to transfer from");
+    let slippage_arg = Arg::with_name("slippage")
+        .long("slippage")
+        .validator(is_parsable::<f64>)
+        .value_name("DECIMAL_PERCENT")
+        .takes_value(true)
+        .required(true)
+        .default_value("1")
+        .help(
+            "Slippage allowed from the current pool state, if no explicit limit is given: [0, 100]",
+        );
+
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg({
+            let arg = Arg::with_name("config_file")
+                .short("C")
+                .long("config")
+                .value_name("PATH")
+                .takes_value(true)
+                .global(true)
+                .help("Configuration file to use");
+            if let Some(ref config_file) = *solana_cli_config::CONFIG_FILE {
+                arg.default_value(config_file)
+            } else {
+                arg
+            }
+        })
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .long("url")
+                .value_name("URL")
+                .takes_value(true)
+                .validator(is_url)
+                .help("JSON RPC URL for the cluster.  Default from the configuration file."),
+        )
+        .arg(
+            fee_payer_arg()
+                .short("p")
+                .global(true)
+        )
+        .arg(
+            Arg::with_name("swap_program_id")
+                .long("program")
+                .validator(is_pubkey)
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .required(true)
+                .default_value(default_swap_program_id)
+                .help("Token swap program ID"),
+        )
+        .arg(
+            Arg::with_name("verbose")
+                .long("verbose")
+                .short("v")
+                .takes_value(false)
+                .global(true)
+                .help("Show additional information"),
+        )
+        .arg(
+            Arg::with_name("dry_run")
+                .long("dry-run")
+                .takes_value(false)
+                .global(true)
+                .help("Simulate transaction instead of executing"),
+        )
+        .subcommand(
+            SubCommand::with_name("create-pool")
+                .about("Create a new token swap pool")
+                // @TODO: use is_valid_signer
+                .arg(
+                    Arg::with_name("source_owner")
+                        .long("source-owner")
+                        .validator(is_keypair)
+                        .value_name("KEYPAIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Owner of the token accounts to deposit initial liquidity from, who receives the initial pool tokens"),
+                )
+                .arg(
+                    Arg::with_name("source_a")
+                        .long("source-a")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Token A account to deposit initial liquidity from"),
+                )
+                .arg(
+                    Arg::with_name("source_b")
+                        .long("source-b")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Token B account to deposit initial liquidity from"),
+                )
+                .arg(
+                    Arg::with_name("amount_a")
+                        .long("amount-a")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Initial amount of token A in the pool"),
+                )
+                .arg(
+                    Arg::with_name("amount_b")
+                        .long("amount-b")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Initial amount of token B in the pool"),
+                )
+                .arg(
+                    Arg::with_name("curve_type")
+                        .long("curve")
+                        .value_name("CURVE")
+                        .takes_value(true)
+                        .required(true)
+                        .possible_values(&["constant-product", "constant-price", "offset", "stable"])
+                        .default_value("constant-product")
+                        .help("Curve used to price swaps"),
+                )
+                .arg(
+                    Arg::with_name("curve_parameter")
+                        .long("curve-parameter")
+                        .validator(is_parsable::<u64>)
+                        .value_name("INTEGER")
+                        .takes_value(true)
+                        .required_ifs(&[
+                            ("curve_type", "constant-price"),
+                            ("curve_type", "offset"),
+                            ("curve_type", "stable"),
+                        ])
+                        .help("Price of token B in token A base units for the constant price curve, \
+                              offset of token B for the offset curve, or amplification coefficient \
+                              for the stable curve"),
+                )
+                .arg(
+                    Arg::with_name("trade_fee")
+                        .long("trade-fee")
+                        .validator(is_fraction)
+                        .value_name("NUMERATOR/DENOMINATOR")
+                        .takes_value(true)
+                        .required(true)
+                        .default_value("25/10000")
+                        .help("Fee on swaps going to liquidity providers"),
+                )
+                .arg(
+                    Arg::with_name("owner_trade_fee")
+                        .long("owner-trade-fee")
+                        .validator(is_fraction)
+                        .value_name("NUMERATOR/DENOMINATOR")
+                        .takes_value(true)
+                        .required(true)
+                        .default_value("5/10000")
+                        .help("Fee on swaps going to the pool fee account, in pool tokens"),
+                )
+                .arg(
+                    Arg::with_name("owner_withdraw_fee")
+                        .long("owner-withdraw-fee")
+                        .validator(is_fraction)
+                        .value_name("NUMERATOR/DENOMINATOR")
+                        .takes_value(true)
+                        .required(true)
+                        .default_value("0/0")
+                        .help("Fee on withdrawals going to the pool fee account, in pool tokens"),
+                )
+                .arg(
+                    Arg::with_name("host_fee")
+                        .long("host-fee")
+                        .validator(is_fraction)
+                        .value_name("NUMERATOR/DENOMINATOR")
+                        .takes_value(true)
+                        .required(true)
+                        .default_value("20/100")
+                        .help("Part of the owner trade fee going to the host fee account of a swap, if provided"),
+                )
+                .arg(
+                    Arg::with_name("admin")
+                        .long("admin")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .help("Admin authority allowed to update the fees and pause the pool.  \
+                              If not provided, the pool can never be updated."),
+                )
+                .arg(
+                    Arg::with_name("pool_fee_owner")
+                        .long("fee-owner")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .help("Owner of the pool fee account.  Defaults to the fee payer."),
+                )
+                .arg(
+                    Arg::with_name("pool_token_program_id")
+                        .long("pool-token-program")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .default_value(default_pool_token_program_id)
+                        .help("Token program of the pool token, spl-token or token-2022"),
+                )
+                .arg(
+                    Arg::with_name("pool_decimals")
+                        .long("pool-decimals")
+                        .validator(is_parsable::<u8>)
+                        .value_name("INTEGER")
+                        .takes_value(true)
+                        .required(true)
+                        .default_value("2")
+                        .help("Number of decimals of the pool token"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("deposit")
+                .about("Deposit both tokens into a pool, in exchange for pool tokens")
+                .arg(pool_arg.clone())
+                .arg(owner_arg.clone())
+                .arg(
+                    Arg::with_name("pool_token_amount")
+                        .long("pool-tokens")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of pool tokens to receive"),
+                )
+                .arg(
+                    Arg::with_name("source_a")
+                        .long("source-a")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Token A account to deposit from"),
+                )
+                .arg(
+                    Arg::with_name("source_b")
+                        .long("source-b")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Token B account to deposit from"),
+                )
+                .arg(
+                    Arg::with_name("destination")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Pool token account to receive pool tokens"),
+                )
+                .arg(
+                    Arg::with_name("maximum_token_a_amount")
+                        .long("maximum-a")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .help("Maximum amount of token A to deposit"),
+                )
+                .arg(
+                    Arg::with_name("maximum_token_b_amount")
+                        .long("maximum-b")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .help("Maximum amount of token B to deposit"),
+                )
+                .arg(slippage_arg.clone())
+        )
+        .subcommand(
+            SubCommand::with_name("withdraw")
+                .about("Withdraw both tokens from a pool, in exchange for pool tokens")
+                .arg(pool_arg.clone())
+                .arg(owner_arg.clone())
+                .arg(
+                    Arg::with_name("pool_token_amount")
+                        .long("pool-tokens")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of pool tokens to burn"),
+                )
+                .arg(
+                    Arg::with_name("source")
+                        .long("source")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Pool token account to burn pool tokens from"),
+                )
+                .arg(
+                    Arg::with_name("destination_a")
+                        .long("destination-a")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Token A account to receive tokens"),
+                )
+                .arg(
+                    Arg::with_name("destination_b")
+                        .long("destination-b")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Token B account to receive tokens"),
+                )
+                .arg(
+                    Arg::with_name("minimum_token_a_amount")
+                        .long("minimum-a")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .help("Minimum amount of token A to withdraw"),
+                )
+                .arg(
+                    Arg::with_name("minimum_token_b_amount")
+                        .long("minimum-b")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .help("Minimum amount of token B to withdraw"),
+                )
+                .arg(slippage_arg.clone())
+        )
+        .subcommand(
+            SubCommand::with_name("deposit-single")
+                .about("Deposit one token into a pool, in exchange for pool tokens")
+                .arg(pool_arg.clone())
+                .arg(owner_arg.clone())
+                .arg(
+                    Arg::with_name("source")
+                        .long("source")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Token A or B account to deposit from"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of token A or B to deposit"),
+                )
+                .arg(
+                    Arg::with_name("destination")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Pool token account to receive pool tokens"),
+                )
+                .arg(
+                    Arg::with_name("minimum_pool_token_amount")
+                        .long("minimum-pool-tokens")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .help("Minimum amount of pool tokens to receive"),
+                )
+                .arg(slippage_arg.clone())
+        )
+        .subcommand(
+            SubCommand::with_name("withdraw-single")
+                .about("Withdraw one token from a pool, in exchange for pool tokens")
+                .arg(pool_arg.clone())
+                .arg(owner_arg.clone())
+                .arg(
+                    Arg::with_name("source")
+                        .long("source")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Pool token account to burn pool tokens from"),
+                )
+                .arg(
+                    Arg::with_name("destination")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Token A or B account to receive tokens"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of token A or B to withdraw"),
+                )
+                .arg(
+                    Arg::with_name("maximum_pool_token_amount")
+                        .long("maximum-pool-tokens")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .help("Maximum amount of pool tokens to burn"),
+                )
+                .arg(slippage_arg.clone())
+        )
+        .subcommand(
+            SubCommand::with_name("swap")
+                .about("Swap one token of a pool for the other")
+                .arg(pool_arg.clone())
+                .arg(owner_arg)
+                .arg(
+                    Arg::with_name("source")
+                        .long("source")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Token A or B account to swap from"),
+                )
+                .arg(
+                    Arg::with_name("destination")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Token B or A account to receive tokens"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of token to swap"),
+                )
+                .arg(
+                    Arg::with_name("minimum_amount_out")
+                        .long("minimum-amount-out")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .help("Minimum amount of token to receive"),
+                )
+                .arg(
+                    Arg::with_name("host_fee_account")
+                        .long("host-fee-account")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .help("Pool token account to receive the host fee"),
+                )
+                .arg(slippage_arg)
+        )
+        .subcommand(
+            SubCommand::with_name("quote")
+                .about("Calculate the result of a swap from the current pool state, without sending a transaction")
+                .arg(pool_arg.clone())
+                .arg(
+                    Arg::with_name("source_mint")
+                        .long("source-mint")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Mint of the token to swap from"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .validator(is_amount)
+                        .value_name("DECIMAL_AMOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Amount of token to swap"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("show")
+                .about("Show the state of a pool, with its prices and the value of its pool tokens")
+                .arg(pool_arg)
+        )
+        .get_matches();
+
+    let mut wallet_manager = None;
+    let config = {
+        let cli_config = if let Some(config_file) = matches.value_of("config_file") {
+            solana_cli_config::Config::load(config_file).unwrap_or_default()
+        } else {
+            solana_cli_config::Config::default()
+        };
+        let json_rpc_url = value_t!(matches, "json_rpc_url", String)
+            .unwrap_or_else(|_| cli_config.json_rpc_url.clone());
+
+        let fee_payer = signer_from_path(
+            &matches,
+            matches
+                .value_of("fee_payer")
+                .unwrap_or(&cli_config.keypair_path),
+            "fee_payer",
+            &mut wallet_manager,
+        )
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            exit(1);
+        });
+
+        let swap_program_id = pubkey_of(&matches, "swap_program_id").unwrap();
+        let verbose = matches.is_present("verbose");
+        let dry_run = matches.is_present("dry_run");
+
+        Config {
+            rpc_client: RpcClient::new_with_commitment(json_rpc_url, CommitmentConfig::confirmed()),
+            fee_payer,
+            swap_program_id,
+            verbose,
+            dry_run,
+        }
+    };
+
+    let _ = match matches.subcommand() {
+        ("create-pool", Some(arg_matches)) => {
+            let source_owner_keypair = keypair_of(arg_matches, "source_owner").unwrap();
+            let source_a_pubkey = pubkey_of(arg_matches, "source_a").unwrap();
+            let source_b_pubkey = pubkey_of(arg_matches, "source_b").unwrap();
+            let ui_amount_a = value_of(arg_matches, "amount_a").unwrap();
+            let ui_amount_b = value_of(arg_matches, "amount_b").unwrap();
+            let swap_curve = swap_curve_of(arg_matches);
+            let (trade_fee_numerator, trade_fee_denominator) =
+                fraction_of(arg_matches, "trade_fee").unwrap();
+            let (owner_trade_fee_numerator, owner_trade_fee_denominator) =
+                fraction_of(arg_matches, "owner_trade_fee").unwrap();
+            let (owner_withdraw_fee_numerator, owner_withdraw_fee_denominator) =
+                fraction_of(arg_matches, "owner_withdraw_fee").unwrap();
+            let (host_fee_numerator, host_fee_denominator) =
+                fraction_of(arg_matches, "host_fee").unwrap();
+            let admin_pubkey = pubkey_of(arg_matches, "admin");
+            let pool_fee_owner_pubkey = pubkey_of(arg_matches, "pool_fee_owner")
+                .unwrap_or_else(|| config.fee_payer.pubkey());
+            let pool_token_program_id = pubkey_of(arg_matches, "pool_token_program_id").unwrap();
+            let pool_decimals = value_of(arg_matches, "pool_decimals").unwrap();
+
+            command_create_pool(
+                &config,
+                ui_amount_a,
+                ui_amount_b,
+                swap_curve,
+                Fees {
+                    trade_fee_numerator,
+                    trade_fee_denominator,
+                    owner_trade_fee_numerator,
+                    owner_trade_fee_denominator,
+                    owner_withdraw_fee_numerator,
+                    owner_withdraw_fee_denominator,
+                    host_fee_numerator,
+                    host_fee_denominator,
+                },
+                source_a_pubkey,
+                source_b_pubkey,
+                source_owner_keypair,
+                admin_pubkey,
+                pool_fee_owner_pubkey,
+                pool_token_program_id,
+                pool_decimals,
+            )
+        }
+        ("deposit", Some(arg_matches)) => {
+            let pool_pubkey = pubkey_of(arg_matches, "pool").unwrap();
+            let owner_keypair = keypair_of(arg_matches, "owner").unwrap();
+            let ui_pool_token_amount = value_of(arg_matches, "pool_token_amount").unwrap();
+            let source_a_pubkey = pubkey_of(arg_matches, "source_a").unwrap();
+            let source_b_pubkey = pubkey_of(arg_matches, "source_b").unwrap();
+            let destination_pubkey = pubkey_of(arg_matches, "destination").unwrap();
+            let ui_maximum_token_a_amount = value_of(arg_matches, "maximum_token_a_amount");
+            let ui_maximum_token_b_amount = value_of(arg_matches, "maximum_token_b_amount");
+            let slippage = value_of(arg_matches, "slippage").unwrap();
+            command_deposit_all_token_types(
+                &config,
+                pool_pubkey,
+                ui_pool_token_amount,
+                source_a_pubkey,
+                source_b_pubkey,
+                destination_pubkey,
+                owner_keypair,
+                ui_maximum_token_a_amount,
+                ui_maximum_token_b_amount,
+                slippage,
+            )
+        }
+        ("withdraw", Some(arg_matches)) => {
+            let pool_pubkey = pubkey_of(arg_matches, "pool").unwrap();
+            let owner_keypair = keypair_of(arg_matches, "owner").unwrap();
+            let ui_pool_token_amount = value_of(arg_matches, "pool_token_amount").unwrap();
+            let source_pubkey = pubkey_of(arg_matches, "source").unwrap();
+            let destination_a_pubkey = pubkey_of(arg_matches, "destination_a").unwrap();
+            let destination_b_pubkey = pubkey_of(arg_matches, "destination_b").unwrap();
+            let ui_minimum_token_a_amount = value_of(arg_matches, "minimum_token_a_amount");
+            let ui_minimum_token_b_amount = value_of(arg_matches, "minimum_token_b_amount");
+            let slippage = value_of(arg_matches, "slippage").unwrap();
+            command_withdraw_all_token_types(
+                &config,
+                pool_pubkey,
+                ui_pool_token_amount,
+                source_pubkey,
+                destination_a_pubkey,
+                destination_b_pubkey,
+                owner_keypair,
+                ui_minimum_token_a_amount,
+                ui_minimum_token_b_amount,
+                slippage,
+            )
+        }
+        ("deposit-single", Some(arg_matches)) => {
+            let pool_pubkey = pubkey_of(arg_matches, "pool").unwrap();
+            let owner_keypair = keypair_of(arg_matches, "owner").unwrap();
+            let source_pubkey = pubkey_of(arg_matches, "source").unwrap();
+            let ui_amount = value_of(arg_matches, "amount").unwrap();
+            let destination_pubkey = pubkey_of(arg_matches, "destination").unwrap();
+            let ui_minimum_pool_token_amount = value_of(arg_matches, "minimum_pool_token_amount");
+            let slippage = value_of(arg_matches, "slippage").unwrap();
+            command_deposit_single_token_type(
+                &config,
+                pool_pubkey,
+                ui_amount,
+                source_pubkey,
+                destination_pubkey,
+                owner_keypair,
+                ui_minimum_pool_token_amount,
+                slippage,
+            )
+        }
+        ("withdraw-single", Some(arg_matches)) => {
+            let pool_pubkey = pubkey_of(arg_matches, "pool").unwrap();
+            let owner_keypair = keypair_of(arg_matches, "owner").unwrap();
+            let source_pubkey = pubkey_of(arg_matches, "source").unwrap();
+            let destination_pubkey = pubkey_of(arg_matches, "destination").unwrap();
+            let ui_amount = value_of(arg_matches, "amount").unwrap();
+            let ui_maximum_pool_token_amount = value_of(arg_matches, "maximum_pool_token_amount");
+            let slippage = value_of(arg_matches, "slippage").unwrap();
+            command_withdraw_single_token_type(
+                &config,
+                pool_pubkey,
+                ui_amount,
+                source_pubkey,
+                destination_pubkey,
+                owner_keypair,
+                ui_maximum_pool_token_amount,
+                slippage,
+            )
+        }
+        ("swap", Some(arg_matches)) => {
+            let pool_pubkey = pubkey_of(arg_matches, "pool").unwrap();
+            let owner_keypair = keypair_of(arg_matches, "owner").unwrap();
+            let source_pubkey = pubkey_of(arg_matches, "source").unwrap();
+            let destination_pubkey = pubkey_of(arg_matches, "destination").unwrap();
+            let ui_amount = value_of(arg_matches, "amount").unwrap();
+            let ui_minimum_amount_out = value_of(arg_matches, "minimum_amount_out");
+            let host_fee_pubkey = pubkey_of(arg_matches, "host_fee_account");
+            let slippage = value_of(arg_matches, "slippage").unwrap();
+            command_swap(
+                &config,
+                pool_pubkey,
+                ui_amount,
+                source_pubkey,
+                destination_pubkey,
+                owner_keypair,
+                ui_minimum_amount_out,
+                host_fee_pubkey,
+                slippage,
+            )
+        }
+        ("quote", Some(arg_matches)) => {
+            let pool_pubkey = pubkey_of(arg_matches, "pool").unwrap();
+            let source_mint_pubkey = pubkey_of(arg_matches, "source_mint").unwrap();
+            let ui_amount = value_of(arg_matches, "amount").unwrap();
+            command_quote(&config, pool_pubkey, source_mint_pubkey, ui_amount)
+        }
+        ("show", Some(arg_matches)) => {
+            let pool_pubkey = pubkey_of(arg_matches, "pool").unwrap();
+            command_show(&config, pool_pubkey)
+        }
+        _ => unreachable!(),
+    }
+    .map_err(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+}
+
+// COMMANDS
+
+#[allow(clippy::too_many_arguments)]
+fn command_create_pool(
+    config: &Config,
+    ui_amount_a: f64,
+    ui_amount_b: f64,
+    swap_curve: SwapCurve,
+    fees: Fees,
+    source_a_pubkey: Pubkey,
+    source_b_pubkey: Pubkey,
+    source_owner_keypair: Keypair,
+    admin_pubkey: Option<Pubkey>,
+    pool_fee_owner_pubkey: Pubkey,
+    pool_token_program_id: Pubkey,
+    pool_decimals: u8,
+) -> CommandResult {
+    check_spl_token_program_account(&pool_token_program_id)?;
+    if let Some(swap_constraints) = SWAP_CONSTRAINTS {
+        swap_constraints.validate_pool(&pool_fee_owner_pubkey, &swap_curve, &fees)?;
+    }
+    fees.validate()?;
+    swap_curve.calculator.validate()?;
+
+    let source_a = get_token_account(config, &source_a_pubkey)?;
+    let source_b = get_token_account(config, &source_b_pubkey)?;
+    let mint_a = get_mint(config, &source_a.mint)?;
+    let mint_b = get_mint(config, &source_b.mint)?;
+    let amount_a = ui_amount_to_amount(ui_amount_a, mint_a.decimals);
+    let amount_b = ui_amount_to_amount(ui_amount_b, mint_b.decimals);
+
+    let swap_keypair = Keypair::new();
+    let (authority_pubkey, _bump_seed) = Pubkey::find_program_address(
+        &[&swap_keypair.pubkey().to_bytes()],
+        &config.swap_program_id,
+    );
+    let token_a_keypair = Keypair::new();
+    let token_b_keypair = Keypair::new();
+    let pool_mint_keypair = Keypair::new();
+    let pool_fee_keypair = Keypair::new();
+    let destination_keypair = Keypair::new();
+
+    println!("Creating pool {}", swap_keypair.pubkey());
+    println!("Adding pool token account {}", destination_keypair.pubkey());
+    if config.verbose {
+        println!("Adding swap authority {}", authority_pubkey);
+        println!("Adding token A account {}", token_a_keypair.pubkey());
+        println!("Adding token B account {}", token_b_keypair.pubkey());
+        println!("Adding pool mint {}", pool_mint_keypair.pubkey());
+        println!("Adding pool fee account {}", pool_fee_keypair.pubkey());
+    }
+
+    let token_a_len = ExtensionType::get_account_len::<Account>(
+        &ExtensionType::get_required_init_account_extensions(&mint_a.extension_types),
+    );
+    let token_b_len = ExtensionType::get_account_len::<Account>(
+        &ExtensionType::get_required_init_account_extensions(&mint_b.extension_types),
+    );
+    let swap_balance = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(SwapVersion::LATEST_LEN)?;
+    let token_a_balance = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(token_a_len)?;
+    let token_b_balance = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(token_b_len)?;
+    let pool_mint_balance = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(Mint::LEN)?;
+    let pool_token_account_balance = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(Account::LEN)?;
+
+    // Token accounts of the pool, funded together with the swap initialization
+    // so that no liquidity is left behind if pool creation fails
+    send_instructions(
+        config,
+        &[
+            create_account(
+                &config.fee_payer.pubkey(),
+                &token_a_keypair.pubkey(),
+                token_a_balance,
+                token_a_len as u64,
+                &mint_a.program_id,
+            ),
+            initialize_account(
+                &mint_a.program_id,
+                &token_a_keypair.pubkey(),
+                &source_a.mint,
+                &authority_pubkey,
+            )?,
+            create_account(
+                &config.fee_payer.pubkey(),
+                &token_b_keypair.pubkey(),
+                token_b_balance,
+                token_b_len as u64,
+                &mint_b.program_id,
+            ),
+            initialize_account(
+                &mint_b.program_id,
+                &token_b_keypair.pubkey(),
+                &source_b.mint,
+                &authority_pubkey,
+            )?,
+        ],
+        vec![&token_a_keypair, &token_b_keypair],
+        token_a_balance + token_b_balance,
+    )?;
+
+    // Pool mint, with the accounts receiving the initial pool tokens and fees
+    send_instructions(
+        config,
+        &[
+            create_account(
+                &config.fee_payer.pubkey(),
+                &pool_mint_keypair.pubkey(),
+                pool_mint_balance,
+                Mint::LEN as u64,
+                &pool_token_program_id,
+            ),
+            initialize_mint(
+                &pool_token_program_id,
+                &pool_mint_keypair.pubkey(),
+                &authority_pubkey,
+                None,
+                pool_decimals,
+            )?,
+            create_account(
+                &config.fee_payer.pubkey(),
+                &pool_fee_keypair.pubkey(),
+                pool_token_account_balance,
+                Account::LEN as u64,
+                &pool_token_program_id,
+            ),
+            initialize_account(
+                &pool_token_program_id,
+                &pool_fee_keypair.pubkey(),
+                &pool_mint_keypair.pubkey(),
+                &pool_fee_owner_pubkey,
+            )?,
+            create_account(
+                &config.fee_payer.pubkey(),
+                &destination_keypair.pubkey(),
+                pool_token_account_balance,
+                Account::LEN as u64,
+                &pool_token_program_id,
+            ),
+            initialize_account(
+                &pool_token_program_id,
+                &destination_keypair.pubkey(),
+                &pool_mint_keypair.pubkey(),
+                &source_owner_keypair.pubkey(),
+            )?,
+        ],
+        vec![&pool_mint_keypair, &pool_fee_keypair, &destination_keypair],
+        pool_mint_balance + 2 * pool_token_account_balance,
+    )?;
+
+    // Swap account, initialized in the same transaction that moves the
+    // initial liquidity into the pool token accounts
+    send_instructions(
+        config,
+        &[
+            create_account(
+                &config.fee_payer.pubkey(),
+                &swap_keypair.pubkey(),
+                swap_balance,
+                SwapVersion::LATEST_LEN as u64,
+                &config.swap_program_id,
+            ),
+            transfer_checked(
+                &mint_a.program_id,
+                &source_a_pubkey,
+                &source_a.mint,
+                &token_a_keypair.pubkey(),
+                &source_owner_keypair.pubkey(),
+                &[],
+                amount_a,
+                mint_a.decimals,
+            )?,
+            transfer_checked(
+                &mint_b.program_id,
+                &source_b_pubkey,
+                &source_b.mint,
+                &token_b_keypair.pubkey(),
+                &source_owner_keypair.pubkey(),
+                &[],
+                amount_b,
+                mint_b.decimals,
+            )?,
+            initialize(
+                &config.swap_program_id,
+                &pool_token_program_id,
+                &swap_keypair.pubkey(),
+                &authority_pubkey,
+                &token_a_keypair.pubkey(),
+                &token_b_keypair.pubkey(),
+                &pool_mint_keypair.pubkey(),
+                &pool_fee_keypair.pubkey(),
+                &destination_keypair.pubkey(),
+                admin_pubkey.as_ref(),
+                fees,
+                swap_curve,
+            )?,
+        ],
+        vec![&swap_keypair, &source_owner_keypair],
+        swap_balance,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn command_deposit_all_token_types(
+    config: &Config,
+    pool_pubkey: Pubkey,
+    ui_pool_token_amount: f64,
+    source_a_pubkey: Pubkey,
+    source_b_pubkey: Pubkey,
+    destination_pubkey: Pubkey,
+    owner_keypair: Keypair,
+    ui_maximum_token_a_amount: Option<f64>,
+    ui_maximum_token_b_amount: Option<f64>,
+    slippage: f64,
+) -> CommandResult {
+    let pool = load_pool(config, &pool_pubkey)?;
+    let pool_token_amount = ui_amount_to_amount(ui_pool_token_amount, pool.pool_mint.decimals);
+
+    let results = pool
+        .state
+        .swap_curve()
+        .calculator
+        .pool_tokens_to_trading_tokens(
+            u128::from(pool_token_amount),
+            u128::from(pool.pool_mint.supply),
+            u128::from(pool.token_a_amount),
+            u128::from(pool.token_b_amount),
+            RoundDirection::Ceiling,
+        )
+        .ok_or("Pool cannot deposit this amount of pool tokens")?;
+    let maximum_token_a_amount = match ui_maximum_token_a_amount {
+        Some(ui_amount) => ui_amount_to_amount(ui_amount, pool.token_a_mint.decimals),
+        None => add_slippage(u64::try_from(results.token_a_amount)?, slippage),
+    };
+    let maximum_token_b_amount = match ui_maximum_token_b_amount {
+        Some(ui_amount) => ui_amount_to_amount(ui_amount, pool.token_b_mint.decimals),
+        None => add_slippage(u64::try_from(results.token_b_amount)?, slippage),
+    };
+
+    println!(
+        "Depositing at most {} token A and {} token B for {} pool tokens",
+        amount_to_ui_amount_string_trimmed(maximum_token_a_amount, pool.token_a_mint.decimals),
+        amount_to_ui_amount_string_trimmed(maximum_token_b_amount, pool.token_b_mint.decimals),
+        amount_to_ui_amount_string_trimmed(pool_token_amount, pool.pool_mint.decimals),
+    );
+
+    send_instructions(
+        config,
+        &[deposit_all_token_types(
+            &config.swap_program_id,
+            &pool.token_a_mint.program_id,
+            &pool.token_b_mint.program_id,
+            &pool.pool_mint.program_id,
+            &pool.address,
+            &pool.authority,
+            &owner_keypair.pubkey(),
+            &source_a_pubkey,
+            &source_b_pubkey,
+            pool.state.token_a_account(),
+            pool.state.token_b_account(),
+            pool.state.pool_mint(),
+            &destination_pubkey,
+            pool.state.token_a_mint(),
+            pool.state.token_b_mint(),
+            DepositAllTokenTypes {
+                pool_token_amount,
+                maximum_token_a_amount,
+                maximum_token_b_amount,
+            },
+        )?],
+        vec![&owner_keypair],
+        0,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn command_withdraw_all_token_types(
+    config: &Config,
+    pool_pubkey: Pubkey,
+    ui_pool_token_amount: f64,
+    source_pubkey: Pubkey,
+    destination_a_pubkey: Pubkey,
+    destination_b_pubkey: Pubkey,
+    owner_keypair: Keypair,
+    ui_minimum_token_a_amount: Option<f64>,
+    ui_minimum_token_b_amount: Option<f64>,
+    slippage: f64,
+) -> CommandResult {
+    let pool = load_pool(config, &pool_pubkey)?;
+    let pool_token_amount = ui_amount_to_amount(ui_pool_token_amount, pool.pool_mint.decimals);
+
+    let withdraw_fee = pool.withdraw_fee(&source_pubkey, u128::from(pool_token_amount))?;
+    let results = pool
+        .state
+        .swap_curve()
+        .calculator
+        .pool_tokens_to_trading_tokens(
+            u128::from(pool_token_amount) - withdraw_fee,
+            u128::from(pool.pool_mint.supply),
+            u128::from(pool.token_a_amount),
+            u128::from(pool.token_b_amount),
+            RoundDirection::Floor,
+        )
+        .ok_or("Pool cannot withdraw this amount of pool tokens")?;
+    let minimum_token_a_amount = match ui_minimum_token_a_amount {
+        Some(ui_amount) => ui_amount_to_amount(ui_amount, pool.token_a_mint.decimals),
+        None => subtract_slippage(
+            std::cmp::min(u64::try_from(results.token_a_amount)?, pool.token_a_amount),
+            slippage,
+        ),
+    };
+    let minimum_token_b_amount = match ui_minimum_token_b_amount {
+        Some(ui_amount) => ui_amount_to_amount(ui_amount, pool.token_b_mint.decimals),
+        None => subtract_slippage(
+            std::cmp::min(u64::try_from(results.token_b_amount)?, pool.token_b_amount),
+            slippage,
+        ),
+    };
+
+    println!(
+        "Withdrawing at least {} token A and {} token B for {} pool tokens",
+        amount_to_ui_amount_string_trimmed(minimum_token_a_amount, pool.token_a_mint.decimals),
+        amount_to_ui_amount_string_trimmed(minimum_token_b_amount, pool.token_b_mint.decimals),
+        amount_to_ui_amount_string_trimmed(pool_token_amount, pool.pool_mint.decimals),
+    );
+
+    send_instructions(
+        config,
+        &[withdraw_all_token_types(
+            &config.swap_program_id,
+            &pool.pool_mint.program_id,
+            &pool.token_a_mint.program_id,
+            &pool.token_b_mint.program_id,
+            &pool.address,
+            &pool.authority,
+            &owner_keypair.pubkey(),
+            pool.state.pool_mint(),
+            pool.state.pool_fee_account(),
+            &source_pubkey,
+            pool.state.token_a_account(),
+            pool.state.token_b_account(),
+            &destination_a_pubkey,
+            &destination_b_pubkey,
+            pool.state.token_a_mint(),
+            pool.state.token_b_mint(),
+            WithdrawAllTokenTypes {
+                pool_token_amount,
+                minimum_token_a_amount,
+                minimum_token_b_amount,
+            },
+        )?],
+        vec![&owner_keypair],
+        0,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn command_deposit_single_token_type(
+    config: &Config,
+    pool_pubkey: Pubkey,
+    ui_amount: f64,
+    source_pubkey: Pubkey,
+    destination_pubkey: Pubkey,
+    owner_keypair: Keypair,
+    ui_minimum_pool_token_amount: Option<f64>,
+    slippage: f64,
+) -> CommandResult {
+    let pool = load_pool(config, &pool_pubkey)?;
+    let source = get_token_account(config, &source_pubkey)?;
+    let trade_direction = pool.trade_direction(&source.mint)?;
+    let (source_token, _) = pool.source_and_destination(trade_direction);
+    let source_token_amount = ui_amount_to_amount(ui_amount, source_token.mint_info.decimals);
+
+    let minimum_pool_token_amount = match ui_minimum_pool_token_amount {
+        Some(ui_amount) => ui_amount_to_amount(ui_amount, pool.pool_mint.decimals),
+        None => {
+            let pool_token_amount = pool
+                .state
+                .swap_curve()
+                .deposit_single_token_type(
+                    u128::from(source_token_amount),
+                    u128::from(pool.token_a_amount),
+                    u128::from(pool.token_b_amount),
+                    u128::from(pool.pool_mint.supply),
+                    trade_direction,
+                    pool.state.fees(),
+                )
+                .ok_or("Pool cannot deposit this amount of token")?;
+            subtract_slippage(u64::try_from(pool_token_amount)?, slippage)
+        }
+    };
+
+    println!(
+        "Depositing {} token for at least {} pool tokens",
+        amount_to_ui_amount_string_trimmed(source_token_amount, source_token.mint_info.decimals),
+        amount_to_ui_amount_string_trimmed(minimum_pool_token_amount, pool.pool_mint.decimals),
+    );
+
+    send_instructions(
+        config,
+        &[deposit_single_token_type_exact_amount_in(
+            &config.swap_program_id,
+            &source_token.mint_info.program_id,
+            &pool.pool_mint.program_id,
+            &pool.address,
+            &pool.authority,
+            &owner_keypair.pubkey(),
+            &source_pubkey,
+            pool.state.token_a_account(),
+            pool.state.token_b_account(),
+            pool.state.pool_mint(),
+            &destination_pubkey,
+            source_token.mint,
+            DepositSingleTokenTypeExactAmountIn {
+                source_token_amount,
+                minimum_pool_token_amount,
+            },
+        )?],
+        vec![&owner_keypair],
+        0,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn command_withdraw_single_token_type(
+    config: &Config,
+    pool_pubkey: Pubkey,
+    ui_amount: f64,
+    source_pubkey: Pubkey,
+    destination_pubkey: Pubkey,
+    owner_keypair: Keypair,
+    ui_maximum_pool_token_amount: Option<f64>,
+    slippage: f64,
+) -> CommandResult {
+    let pool = load_pool(config, &pool_pubkey)?;
+    let destination = get_token_account(config, &destination_pubkey)?;
+    let trade_direction = pool.trade_direction(&destination.mint)?;
+    let (destination_token, _) = pool.source_and_destination(trade_direction);
+    let destination_token_amount =
+        ui_amount_to_amount(ui_amount, destination_token.mint_info.decimals);
+
+    let maximum_pool_token_amount = match ui_maximum_pool_token_amount {
+        Some(ui_amount) => ui_amount_to_amount(ui_amount, pool.pool_mint.decimals),
+        None => {
+            let burn_pool_token_amount = pool
+                .state
+                .swap_curve()
+                .withdraw_single_token_type_exact_out(
+                    u128::from(destination_token_amount),
+                    u128::from(pool.token_a_amount),
+                    u128::from(pool.token_b_amount),
+                    u128::from(pool.pool_mint.supply),
+                    trade_direction,
+                    pool.state.fees(),
+                )
+                .ok_or("Pool cannot withdraw this amount of token")?;
+            let withdraw_fee = pool.withdraw_fee(&source_pubkey, burn_pool_token_amount)?;
+            add_slippage(
+                u64::try_from(burn_pool_token_amount + withdraw_fee)?,
+                slippage,
+            )
+        }
+    };
+
+    println!(
+        "Withdrawing {} token for at most {} pool tokens",
+        amount_to_ui_amount_string_trimmed(
+            destination_token_amount,
+            destination_token.mint_info.decimals
+        ),
+        amount_to_ui_amount_string_trimmed(maximum_pool_token_amount, pool.pool_mint.decimals),
+    );
+
+    send_instructions(
+        config,
+        &[withdraw_single_token_type_exact_amount_out(
+            &config.swap_program_id,
+            &pool.pool_mint.program_id,
+            &destination_token.mint_info.program_id,
+            &pool.address,
+            &pool.authority,
+            &owner_keypair.pubkey(),
+            pool.state.pool_mint(),
+            pool.state.pool_fee_account(),
+            &source_pubkey,
+            pool.state.token_a_account(),
+            pool.state.token_b_account(),
+            &destination_pubkey,
+            destination_token.mint,
+            WithdrawSingleTokenTypeExactAmountOut {
+                destination_token_amount,
+                maximum_pool_token_amount,
+            },
+        )?],
+        vec![&owner_keypair],
+        0,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn command_swap(
+    config: &Config,
+    pool_pubkey: Pubkey,
+    ui_amount: f64,
+    source_pubkey: Pubkey,
+    destination_pubkey: Pubkey,
+    owner_keypair: Keypair,
+    ui_minimum_amount_out: Option<f64>,
+    host_fee_pubkey: Option<Pubkey>,
+    slippage: f64,
+) -> CommandResult {
+    let pool = load_pool(config, &pool_pubkey)?;
+    let source = get_token_account(config, &source_pubkey)?;
+    let trade_direction = pool.trade_direction(&source.mint)?;
+    let (source_token, destination_token) = pool.source_and_destination(trade_direction);
+    let amount_in = ui_amount_to_amount(ui_amount, source_token.mint_info.decimals);
+
+    let minimum_amount_out = match ui_minimum_amount_out {
+        Some(ui_amount) => ui_amount_to_amount(ui_amount, destination_token.mint_info.decimals),
+        None => {
+            let epoch = config.rpc_client.get_epoch_info()?.epoch;
+            let quote = quote_swap(&pool, trade_direction, amount_in, epoch)?;
+            subtract_slippage(quote.amount_out, slippage)
+        }
+    };
+
+    println!(
+        "Swapping {} token for at least {} token",
+        amount_to_ui_amount_string_trimmed(amount_in, source_token.mint_info.decimals),
+        amount_to_ui_amount_string_trimmed(
+            minimum_amount_out,
+            destination_token.mint_info.decimals
+        ),
+    );
+
+    send_instructions(
+        config,
+        &[swap(
+            &config.swap_program_id,
+            &source_token.mint_info.program_id,
+            &destination_token.mint_info.program_id,
+            &pool.pool_mint.program_id,
+            &pool.address,
+            &pool.authority,
+            &owner_keypair.pubkey(),
+            &source_pubkey,
+            source_token.account,
+            destination_token.account,
+            &destination_pubkey,
+            pool.state.pool_mint(),
+            pool.state.pool_fee_account(),
+            source_token.mint,
+            destination_token.mint,
+            host_fee_pubkey.as_ref(),
+            Swap {
+                amount_in,
+                minimum_amount_out,
+            },
+        )?],
+        vec![&owner_keypair],
+        0,
+    )
+}
+
+fn command_quote(
+    config: &Config,
+    pool_pubkey: Pubkey,
+    source_mint_pubkey: Pubkey,
+    ui_amount: f64,
+) -> CommandResult {
+    let pool = load_pool(config, &pool_pubkey)?;
+    let trade_direction = pool.trade_direction(&source_mint_pubkey)?;
+    let (source_token, destination_token) = pool.source_and_destination(trade_direction);
+    let amount_in = ui_amount_to_amount(ui_amount, source_token.mint_info.decimals);
+    let epoch = config.rpc_client.get_epoch_info()?.epoch;
+    let quote = quote_swap(&pool, trade_direction, amount_in, epoch)?;
+
+    let source_decimals = source_token.mint_info.decimals;
+    let destination_decimals = destination_token.mint_info.decimals;
+    println!(
+        "Amount in: {} {}",
+        amount_to_ui_amount_string_trimmed(quote.amount_in, source_decimals),
+        source_token.mint,
+    );
+    println!(
+        "Amount out: {} {}",
+        amount_to_ui_amount_string_trimmed(quote.amount_out, destination_decimals),
+        destination_token.mint,
+    );
+    println!(
+        "Trade fee: {}",
+        amount_to_ui_amount_string_trimmed(quote.trade_fee, source_decimals)
+    );
+    println!(
+        "Owner fee: {}",
+        amount_to_ui_amount_string_trimmed(quote.owner_fee, source_decimals)
+    );
+    if quote.amount_in > 0 {
+        let price = amount_to_ui_amount(quote.amount_out, destination_decimals)
+            / amount_to_ui_amount(quote.amount_in, source_decimals);
+        println!("Price: {}", price);
+        if let Some(spot_price) = pool.ui_spot_price(trade_direction) {
+            println!("Spot price: {}", spot_price);
+            println!("Price impact: {:.4}%", (1.0 - price / spot_price) * 100.0);
+        }
+    }
+    Ok(())
+}
+
+fn command_show(config: &Config, pool_pubkey: Pubkey) -> CommandResult {
+    let pool = load_pool(config, &pool_pubkey)?;
+    let fees = pool.state.fees();
+    let token_a_decimals = pool.token_a_mint.decimals;
+    let token_b_decimals = pool.token_b_mint.decimals;
+    let pool_decimals = pool.pool_mint.decimals;
+
+    println!("Pool: {}", pool.address);
+    println!("Authority: {}", pool.authority);
+    println!(
+        "Curve: {:?} {:?}",
+        pool.state.swap_curve().curve_type,
+        pool.state.swap_curve().calculator
+    );
+    println!(
+        "Token A: {} {} in {}",
+        amount_to_ui_amount_string_trimmed(pool.token_a_amount, token_a_decimals),
+        pool.state.token_a_mint(),
+        pool.state.token_a_account(),
+    );
+    println!(
+        "Token B: {} {} in {}",
+        amount_to_ui_amount_string_trimmed(pool.token_b_amount, token_b_decimals),
+        pool.state.token_b_mint(),
+        pool.state.token_b_account(),
+    );
+    println!(
+        "Pool token supply: {} {}",
+        amount_to_ui_amount_string_trimmed(pool.pool_mint.supply, pool_decimals),
+        pool.state.pool_mint(),
+    );
+    println!("Pool fee account: {}", pool.state.pool_fee_account());
+    println!(
+        "Fees: trade {}/{}, owner trade {}/{}, owner withdraw {}/{}, host {}/{}",
+        fees.trade_fee_numerator,
+        fees.trade_fee_denominator,
+        fees.owner_trade_fee_numerator,
+        fees.owner_trade_fee_denominator,
+        fees.owner_withdraw_fee_numerator,
+        fees.owner_withdraw_fee_denominator,
+        fees.host_fee_numerator,
+        fees.host_fee_denominator,
+    );
+    match Option::<Pubkey>::from(pool.state.admin()) {
+        Some(admin) => println!("Admin: {}", admin),
+        None => println!("Admin: none"),
+    }
+    println!("Paused: {}", pool.state.is_paused());
+
+    let price_a = pool.ui_spot_price(TradeDirection::AtoB);
+    if let Some(price_a) = price_a {
+        println!("Price of token A: {} token B", price_a);
+    }
+    if let Some(price_b) = pool.ui_spot_price(TradeDirection::BtoA) {
+        println!("Price of token B: {} token A", price_b);
+    }
+
+    if pool.pool_mint.supply > 0 {
+        let one_pool_token = 10u64.pow(pool_decimals as u32);
+        if let Some(results) = pool
+            .state
+            .swap_curve()
+            .calculator
+            .pool_tokens_to_trading_tokens(
+                u128::from(one_pool_token),
+                u128::from(pool.pool_mint.supply),
+                u128::from(pool.token_a_amount),
+                u128::from(pool.token_b_amount),
+                RoundDirection::Floor,
+            )
+        {
+            let ui_token_a_amount =
+                amount_to_ui_amount(u64::try_from(results.token_a_amount)?, token_a_decimals);
+            let ui_token_b_amount =
+                amount_to_ui_amount(u64::try_from(results.token_b_amount)?, token_b_decimals);
+            println!(
+                "Value of 1 pool token: {} token A and {} token B",
+                ui_token_a_amount, ui_token_b_amount
+            );
+            if let Some(price_a) = price_a {
+                if price_a > 0.0 {
+                    println!(
+                        "Value of 1 pool token in token A: {}",
+                        ui_token_a_amount + ui_token_b_amount / price_a
+                    );
+                }
+            }
+        }
+    }
+
+    if let Some(price_oracle) = pool.state.price_oracle() {
+        println!(
+            "Cumulative price of token A: {}",
+            price_oracle.price_a_cumulative
+        );
+        println!(
+            "Cumulative price of token B: {}",
+            price_oracle.price_b_cumulative
+        );
+        println!(
+            "Cumulative prices last updated at: {}",
+            price_oracle.last_update_timestamp
+        );
+    }
+    Ok(())
+}
+
+// HELPERS
+
+/// Mint information needed to convert amounts and calculate transfer fees
+struct MintInfo {
+    program_id: Pubkey,
+    decimals: u8,
+    supply: u64,
+    extension_types: Vec<ExtensionType>,
+    transfer_fee_config: Option<TransferFeeConfig>,
+}
+
+impl MintInfo {
+    /// Amount received by the destination of a transfer, after the transfer fee
+    fn amount_after_transfer_fee(&self, epoch: Epoch, amount: u64) -> Result<u64, Error> {
+        match &self.transfer_fee_config {
+            Some(transfer_fee_config) => {
+                let fee = transfer_fee_config
+                    .calculate_epoch_fee(epoch, amount)
+                    .ok_or("Transfer fee calculation failed")?;
+                Ok(amount.saturating_sub(fee))
+            }
+            None => Ok(amount),
+        }
+    }
+}
+
+/// One of the two tokens traded by a pool
+struct PoolToken<'a> {
+    account: &'a Pubkey,
+    mint: &'a Pubkey,
+    mint_info: &'a MintInfo,
+    amount: u64,
+}
+
+/// Pool state, with the accounts and balances needed to build and quote its
+/// instructions
+struct Pool {
+    address: Pubkey,
+    state: Arc<dyn SwapState>,
+    authority: Pubkey,
+    token_a_amount: u64,
+    token_b_amount: u64,
+    token_a_mint: MintInfo,
+    token_b_mint: MintInfo,
+    pool_mint: MintInfo,
+}
+
+impl Pool {
+    fn trade_direction(&self, source_mint: &Pubkey) -> Result<TradeDirection, Error> {
+        if source_mint == self.state.token_a_mint() {
+            Ok(TradeDirection::AtoB)
+        } else if source_mint == self.state.token_b_mint() {
+            Ok(TradeDirection::BtoA)
+        } else {
+            Err(format!("Pool {} does not trade mint {}", self.address, source_mint).into())
+        }
+    }
+
+    fn source_and_destination(
+        &self,
+        trade_direction: TradeDirection,
+    ) -> (PoolToken<'_>, PoolToken<'_>) {
+        let token_a = PoolToken {
+            account: self.state.token_a_account(),
+            mint: self.state.token_a_mint(),
+            mint_info: &self.token_a_mint,
+            amount: self.token_a_amount,
+        };
+        let token_b = PoolToken {
+            account: self.state.token_b_account(),
+            mint: self.state.token_b_mint(),
+            mint_info: &self.token_b_mint,
+            amount: self.token_b_amount,
+        };
+        match trade_direction {
+            TradeDirection::AtoB => (token_a, token_b),
+            TradeDirection::BtoA => (token_b, token_a),
+        }
+    }
+
+    /// Owner withdraw fee, in pool tokens, unless withdrawing from the pool fee
+    /// account
+    fn withdraw_fee(&self, source_pubkey: &Pubkey, pool_token_amount: u128) -> Result<u128, Error> {
+        if source_pubkey == self.state.pool_fee_account() {
+            Ok(0)
+        } else {
+            Ok(self
+                .state
+                .fees()
+                .owner_withdraw_fee(pool_token_amount)
+                .ok_or("Withdraw fee calculation failed")?)
+        }
+    }
+
+    /// Spot price of a whole source token, in whole destination tokens
+    fn ui_spot_price(&self, trade_direction: TradeDirection) -> Option<f64> {
+        let (source, destination) = self.source_and_destination(trade_direction);
+        let price = self.state.swap_curve().calculator.spot_price(
+            u128::from(source.amount),
+            u128::from(destination.amount),
+            trade_direction,
+        )?;
+        let decimals =
+            i32::from(source.mint_info.decimals) - i32::from(destination.mint_info.decimals);
+        Some(precise_number_to_f64(&price) * 10f64.powi(decimals))
+    }
+}
+
+/// Result of a swap, including the transfer fees of both tokens
+struct SwapQuote {
+    /// Amount transferred from the user
+    amount_in: u64,
+    /// Amount received by the user
+    amount_out: u64,
+    /// Amount of source token going to pool holders
+    trade_fee: u64,
+    /// Amount of source token going to the pool fee account
+    owner_fee: u64,
+}
+
+fn quote_swap(
+    pool: &Pool,
+    trade_direction: TradeDirection,
+    amount_in: u64,
+    epoch: Epoch,
+) -> Result<SwapQuote, Error> {
+    let (source, destination) = pool.source_and_destination(trade_direction);
+    let actual_amount_in = source
+        .mint_info
+        .amount_after_transfer_fee(epoch, amount_in)?;
+    let result = pool
+        .state
+        .swap_curve()
+        .swap(
+            u128::from(actual_amount_in),
+            u128::from(source.amount),
+            u128::from(destination.amount),
+            trade_direction,
+            pool.state.fees(),
+        )
+        .ok_or("Pool cannot swap this amount of token")?;
+    let amount_out = destination
+        .mint_info
+        .amount_after_transfer_fee(epoch, u64::try_from(result.destination_amount_swapped)?)?;
+    Ok(SwapQuote {
+        amount_in,
+        amount_out,
+        trade_fee: u64::try_from(result.trade_fee)?,
+        owner_fee: u64::try_from(result.owner_fee)?,
+    })
+}
+
+fn load_pool(config: &Config, pool_pubkey: &Pubkey) -> Result<Pool, Error> {
+    let account = config.rpc_client.get_account(pool_pubkey)?;
+    if account.owner != config.swap_program_id {
+        return Err(format!(
+            "Account {} is not owned by the token swap program {}",
+            pool_pubkey, config.swap_program_id
+        )
+        .into());
+    }
+    let state = SwapVersion::unpack(&account.data)?;
+    let authority = Pubkey::create_program_address(
+        &[&pool_pubkey.to_bytes(), &[state.bump_seed()]],
+        &config.swap_program_id,
+    )?;
+    let token_a_amount = get_token_account(config, state.token_a_account())?.amount;
+    let token_b_amount = get_token_account(config, state.token_b_account())?.amount;
+    let token_a_mint = get_mint(config, state.token_a_mint())?;
+    let token_b_mint = get_mint(config, state.token_b_mint())?;
+    let pool_mint = get_mint(config, state.pool_mint())?;
+    Ok(Pool {
+        address: *pool_pubkey,
+        state,
+        authority,
+        token_a_amount,
+        token_b_amount,
+        token_a_mint,
+        token_b_mint,
+        pool_mint,
+    })
+}
+
+fn get_token_account(config: &Config, pubkey: &Pubkey) -> Result<Account, Error> {
+    let account = config.rpc_client.get_account(pubkey)?;
+    check_spl_token_program_account(&account.owner)?;
+    Ok(StateWithExtensions::<Account>::unpack(&account.data)?.base)
+}
+
+fn get_mint(config: &Config, pubkey: &Pubkey) -> Result<MintInfo, Error> {
+    let account = config.rpc_client.get_account(pubkey)?;
+    check_spl_token_program_account(&account.owner)?;
+    let mint = StateWithExtensions::<Mint>::unpack(&account.data)?;
+    Ok(MintInfo {
+        program_id: account.owner,
+        decimals: mint.base.decimals,
+        supply: mint.base.supply,
+        extension_types: mint.get_extension_types()?,
+        transfer_fee_config: mint.get_extension::<TransferFeeConfig>().ok().copied(),
+    })
+}
+
+fn precise_number_to_f64(number: &PreciseNumber) -> f64 {
+    number.value.low_u128() as f64 / ONE as f64
+}
+
+/// Increase an amount by the slippage percentage, for use as a maximum
+fn add_slippage(amount: u64, slippage: f64) -> u64 {
+    (amount as f64 * (1.0 + slippage / 100.0)).ceil() as u64
+}
+
+/// Decrease an amount by the slippage percentage, for use as a minimum
+fn subtract_slippage(amount: u64, slippage: f64) -> u64 {
+    (amount as f64 * (1.0 - slippage / 100.0)).max(0.0).floor() as u64
+}
+
+fn check_fee_payer_balance(config: &Config, required_balance: u64) -> Result<(), Error> {
+    let balance = config.rpc_client.get_balance(&config.fee_payer.pubkey())?;
+    if balance < required_balance {
+        Err(format!(
+            "Fee payer, {}, has insufficient balance: {} required, {} available",
+            config.fee_payer.pubkey(),
+            lamports_to_sol(required_balance),
+            lamports_to_sol(balance)
+        )
+        .into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Sign and send a transaction with the given instructions, paid by the fee
+/// payer, who must also hold `lamports` for the accounts created
+fn send_instructions(
+    config: &Config,
+    instructions: &[Instruction],
+    signers: Vec<&dyn Signer>,
+    lamports: u64,
+) -> CommandResult {
+    let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
+    let message = Message::new_with_blockhash(
+        instructions,
+        Some(&config.fee_payer.pubkey()),
+        &recent_blockhash,
+    );
+    check_fee_payer_balance(
+        config,
+        lamports + config.rpc_client.get_fee_for_message(&message)?,
+    )?;
+
+    let mut unique_signers = vec![config.fee_payer.as_ref()];
+    for signer in signers {
+        if !unique_signers.iter().any(|s| s.pubkey() == signer.pubkey()) {
+            unique_signers.push(signer);
+        }
+    }
+    let transaction = Transaction::new(&unique_signers, message, recent_blockhash);
+    send_transaction(config, transaction)?;
+    Ok(())
+}
+
+fn send_transaction(
+    config: &Config,
+    transaction: Transaction,
+) -> solana_client::client_error::Result<()> {
+    if config.dry_run {
+        let result = config.rpc_client.simulate_transaction(&transaction)?;
+        println!("Simulate result: {:?}", result);
+    } else {
+        let signature = config
+            .rpc_client
+            .send_and_confirm_transaction_with_spinner(&transaction)?;
+        println!("Signature: {}", signature);
+    }
+    Ok(())
+}
+
+fn is_fraction(string: String) -> Result<(), String> {
+    parse_fraction(&string)
+        .map(|_| ())
+        .ok_or_else(|| format!("Unable to parse {} as NUMERATOR/DENOMINATOR", string))
+}
+
+fn parse_fraction(string: &str) -> Option<(u64, u64)> {
+    let mut parts = string.splitn(2, '/');
+    let numerator = parts.next()?.trim().parse().ok()?;
+    let denominator = parts.next()?.trim().parse().ok()?;
+    Some((numerator, denominator))
+}
+
+fn fraction_of(matches: &ArgMatches<'_>, name: &str) -> Option<(u64, u64)> {
+    matches.value_of(name).and_then(parse_fraction)
+}
+
+fn swap_curve_of(matches: &ArgMatches<'_>) -> SwapCurve {
+    let curve_parameter = value_of(matches, "curve_parameter").unwrap_or(0);
+    match matches.value_of("curve_type").unwrap() {
+        "constant-price" => SwapCurve {
+            curve_type: CurveType::ConstantPrice,
+            calculator: Arc::new(ConstantPriceCurve {
+                token_b_price: curve_parameter,
+            }),
+        },
+        "offset" => SwapCurve {
+            curve_type: CurveType::Offset,
+            calculator: Arc::new(OffsetCurve {
+                token_b_offset: curve_parameter,
+            }),
+        },
+        "stable" => SwapCurve {
+            curve_type: CurveType::Stable,
+            calculator: Arc::new(StableCurve {
+                amp: curve_parameter,
+            }),
+        },
+        _ => SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve {}),
+        },
+    }
+}
@@ -62,7 +62,7 @@ export class Numberu64 extends BN {
   }
 }
 
-export const TokenSwapLayout = BufferLayout.struct([
+const TokenSwapV1Fields = [
   BufferLayout.u8('version'),
   BufferLayout.u8('isInitialized'),
   BufferLayout.u8('bumpSeed'),
@@ -83,12 +83,33 @@ export const TokenSwapLayout = BufferLayout.struct([
   Layout.uint64('hostFeeDenominator'),
   BufferLayout.u8('curveType'),
   BufferLayout.blob(32, 'curveParameters'),
+];
+
+// Pools created before the admin authority was introduced
+export const TokenSwapLayoutV1 = BufferLayout.struct(TokenSwapV1Fields);
+
+const TokenSwapV2Fields = [
+  ...TokenSwapV1Fields,
+  BufferLayout.u32('adminOption'),
+  Layout.publicKey('admin'),
+  BufferLayout.u8('isPaused'),
+];
+
+// Pools created before the price oracle was introduced
+export const TokenSwapLayoutV2 = BufferLayout.struct(TokenSwapV2Fields);
+
+export const TokenSwapLayout = BufferLayout.struct([
+  ...TokenSwapV2Fields,
+  Layout.uint128('priceACumulative'),
+  Layout.uint128('priceBCumulative'),
+  BufferLayout.ns64('lastUpdateTimestamp'),
 ]);
 
 export const CurveType = Object.freeze({
   ConstantProduct: 0, // Constant product curve, Uniswap-style
   ConstantPrice: 1, // Constant price curve, always X amount of A token for 1 B token, where X is defined at init
   Offset: 2, // Offset curve, like Uniswap, but with an additional offset on the token B side
+  Stable: 3, // Stable curve, like curve.fi, flatter around the balanced point depending on the amplification coefficient
 });
 
 /**
@@ -263,7 +284,12 @@ export class TokenSwap {
     payer: Account,
   ): Promise<TokenSwap> {
     const data = await loadAccount(connection, address, programId);
-    const tokenSwapData = TokenSwapLayout.decode(data);
+    const tokenSwapData =
+      data[0] === 1
+        ? TokenSwapLayoutV1.decode(data)
+        : data[0] === 2
+        ? TokenSwapLayoutV2.decode(data)
+        : TokenSwapLayout.decode(data);
     if (!tokenSwapData.isInitialized) {
       throw new Error(`Invalid token swap state`);
     }
@@ -545,7 +571,7 @@ export class TokenSwap {
     );
 
     const keys = [
-      {pubkey: tokenSwap, isSigner: false, isWritable: false},
+      {pubkey: tokenSwap, isSigner: false, isWritable: true},
       {pubkey: authority, isSigner: false, isWritable: false},
       {pubkey: userTransferAuthority, isSigner: true, isWritable: false},
       {pubkey: userSource, isSigner: false, isWritable: true},
@@ -662,7 +688,7 @@ export class TokenSwap {
     );
 
     const keys = [
-      {pubkey: tokenSwap, isSigner: false, isWritable: false},
+      {pubkey: tokenSwap, isSigner: false, isWritable: true},
       {pubkey: authority, isSigner: false, isWritable: false},
       {pubkey: userTransferAuthority, isSigner: true, isWritable: false},
       {pubkey: sourceA, isSigner: false, isWritable: true},
@@ -779,7 +805,7 @@ export class TokenSwap {
     );
 
     const keys = [
-      {pubkey: tokenSwap, isSigner: false, isWritable: false},
+      {pubkey: tokenSwap, isSigner: false, isWritable: true},
       {pubkey: authority, isSigner: false, isWritable: false},
       {pubkey: userTransferAuthority, isSigner: true, isWritable: false},
       {pubkey: poolMint, isSigner: false, isWritable: true},
@@ -882,7 +908,7 @@ export class TokenSwap {
     );
 
     const keys = [
-      {pubkey: tokenSwap, isSigner: false, isWritable: false},
+      {pubkey: tokenSwap, isSigner: false, isWritable: true},
       {pubkey: authority, isSigner: false, isWritable: false},
       {pubkey: userTransferAuthority, isSigner: true, isWritable: false},
       {pubkey: source, isSigner: false, isWritable: true},
@@ -986,7 +1012,7 @@ export class TokenSwap {
     );
 
     const keys = [
-      {pubkey: tokenSwap, isSigner: false, isWritable: false},
+      {pubkey: tokenSwap, isSigner: false, isWritable: true},
       {pubkey: authority, isSigner: false, isWritable: false},
       {pubkey: userTransferAuthority, isSigner: true, isWritable: false},
       {pubkey: poolMint, isSigner: false, isWritable: true},
@@ -14,6 +14,13 @@ export const uint64 = (property: string = 'uint64'): Object => {
   return BufferLayout.blob(8, property);
 };
 
+/**
+ * Layout for a 128bit unsigned value
+ */
+export const uint128 = (property: string = 'uint128'): Object => {
+  return BufferLayout.blob(16, property);
+};
+
 /**
  * Layout for a Rust String type
  */
@@ -90,6 +90,7 @@ impl NativeTokenSwap {
             &pool_mint_account.key,
             &pool_fee_account.key,
             &pool_token_account.key,
+            None,
             fees.clone(),
             swap_curve.clone(),
         )
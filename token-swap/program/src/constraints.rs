@@ -7,7 +7,7 @@ use crate::{
     },
     error::SwapError,
 };
-use solana_program::program_error::ProgramError;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 
 #[cfg(feature = "production")]
 use std::env;
@@ -28,6 +28,25 @@ pub struct SwapConstraints<'a> {
 }
 
 impl<'a> SwapConstraints<'a> {
+    /// Checks that a new pool with the given fee account owner, curve, and
+    /// fees is valid for the given constraints
+    pub fn validate_pool(
+        &self,
+        pool_fee_owner: &Pubkey,
+        swap_curve: &SwapCurve,
+        fees: &Fees,
+    ) -> Result<(), ProgramError> {
+        let owner_key = self
+            .owner_key
+            .parse::<Pubkey>()
+            .map_err(|_| SwapError::InvalidOwner)?;
+        if *pool_fee_owner != owner_key {
+            return Err(SwapError::InvalidOwner.into());
+        }
+        self.validate_curve(swap_curve)?;
+        self.validate_fees(fees)
+    }
+
     /// Checks that the provided curve is valid for the given constraints
     pub fn validate_curve(&self, swap_curve: &SwapCurve) -> Result<(), ProgramError> {
         if self
@@ -73,7 +92,11 @@ const FEES: &Fees = &Fees {
     host_fee_denominator: 100,
 };
 #[cfg(feature = "production")]
-const VALID_CURVE_TYPES: &[CurveType] = &[CurveType::ConstantPrice, CurveType::ConstantProduct];
+const VALID_CURVE_TYPES: &[CurveType] = &[
+    CurveType::ConstantPrice,
+    CurveType::ConstantProduct,
+    CurveType::Stable,
+];
 
 /// Fee structure defined by program creator in order to enforce certain
 /// fees when others use the program.  Adds checks on pool creation and
@@ -11,6 +11,7 @@ use crate::curve::{
     constant_product::ConstantProductCurve,
     fees::Fees,
     offset::OffsetCurve,
+    stable::StableCurve,
 };
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use std::convert::{TryFrom, TryInto};
@@ -31,6 +32,9 @@ pub enum CurveType {
     ConstantPrice,
     /// Offset curve, like Uniswap, but the token B side has a faked offset
     Offset,
+    /// Curve.fi-style stable swap curve, close to a constant sum around the
+    /// balanced point, flattened by an amplification coefficient set at init
+    Stable,
 }
 
 /// Encodes all results of swapping from a source token to a destination token
@@ -102,6 +106,42 @@ impl SwapCurve {
         })
     }
 
+    /// Subtract the destination amount from the swap, and calculate the
+    /// source amount, including fees, required to receive it exactly
+    pub fn swap_exact_amount_out(
+        &self,
+        destination_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+        fees: &Fees,
+    ) -> Option<SwapResult> {
+        let SwapWithoutFeesResult {
+            source_amount_swapped,
+            destination_amount_swapped,
+        } = self.calculator.swap_without_fees_exact_out(
+            destination_amount,
+            swap_source_amount,
+            swap_destination_amount,
+            trade_direction,
+        )?;
+
+        // credit the fee on top of the amount required by the curve
+        let source_amount_swapped = fees.pre_trading_fee_amount(source_amount_swapped)?;
+        let trade_fee = fees.trading_fee(source_amount_swapped)?;
+        let owner_fee = fees.owner_trading_fee(source_amount_swapped)?;
+
+        Some(SwapResult {
+            new_swap_source_amount: swap_source_amount.checked_add(source_amount_swapped)?,
+            new_swap_destination_amount: swap_destination_amount
+                .checked_sub(destination_amount_swapped)?,
+            source_amount_swapped,
+            destination_amount_swapped,
+            trade_fee,
+            owner_fee,
+        })
+    }
+
     /// Get the amount of pool tokens for the deposited amount of token A or B
     pub fn deposit_single_token_type(
         &self,
@@ -220,6 +260,7 @@ impl Pack for SwapCurve {
                     Arc::new(ConstantPriceCurve::unpack_from_slice(calculator)?)
                 }
                 CurveType::Offset => Arc::new(OffsetCurve::unpack_from_slice(calculator)?),
+                CurveType::Stable => Arc::new(StableCurve::unpack_from_slice(calculator)?),
             },
         })
     }
@@ -249,6 +290,7 @@ impl TryFrom<u8> for CurveType {
             0 => Ok(CurveType::ConstantProduct),
             1 => Ok(CurveType::ConstantPrice),
             2 => Ok(CurveType::Offset),
+            3 => Ok(CurveType::Stable),
             _ => Err(ProgramError::InvalidAccountData),
         }
     }
@@ -393,4 +435,60 @@ mod tests {
         assert_eq!(result.destination_amount_swapped, 4545);
         assert_eq!(result.new_swap_destination_amount, 45455);
     }
+
+    #[test]
+    fn constant_product_exact_amount_out() {
+        let swap_source_amount: u128 = 1_000;
+        let swap_destination_amount: u128 = 50_000;
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            ..Fees::default()
+        };
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve {}),
+        };
+        let result = swap_curve
+            .swap_exact_amount_out(
+                4_504,
+                swap_source_amount,
+                swap_destination_amount,
+                TradeDirection::AtoB,
+                &fees,
+            )
+            .unwrap();
+        // 99 tokens are required by the curve, plus one token for each fee
+        assert_eq!(result.source_amount_swapped, 101);
+        assert_eq!(result.new_swap_source_amount, 1_101);
+        assert_eq!(result.destination_amount_swapped, 4_504);
+        assert_eq!(result.new_swap_destination_amount, 45_496);
+        assert_eq!(result.trade_fee, 1);
+        assert_eq!(result.owner_fee, 1);
+
+        // the same source amount gives at least the destination amount
+        let forward = swap_curve
+            .swap(
+                result.source_amount_swapped,
+                swap_source_amount,
+                swap_destination_amount,
+                TradeDirection::AtoB,
+                &fees,
+            )
+            .unwrap();
+        assert!(forward.destination_amount_swapped >= result.destination_amount_swapped);
+
+        // the pool can't be emptied
+        assert!(swap_curve
+            .swap_exact_amount_out(
+                swap_destination_amount,
+                swap_source_amount,
+                swap_destination_amount,
+                TradeDirection::AtoB,
+                &fees,
+            )
+            .is_none());
+    }
 }
@@ -95,6 +95,18 @@ pub trait CurveCalculator: Debug + DynPack {
         trade_direction: TradeDirection,
     ) -> Option<SwapWithoutFeesResult>;
 
+    /// Calculate how much source token must be provided to receive exactly
+    /// `destination_amount` of destination token.
+    ///
+    /// The source amount is rounded up, so the pool never loses value.
+    fn swap_without_fees_exact_out(
+        &self,
+        destination_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Option<SwapWithoutFeesResult>;
+
     /// Get the supply for a new pool
     /// The default implementation is a Balancer-style fixed initial supply
     fn new_pool_supply(&self) -> u128 {
@@ -173,6 +185,21 @@ pub trait CurveCalculator: Debug + DynPack {
         true
     }
 
+    /// Get the spot price of the source token, in destination tokens, ie. the
+    /// amount of destination token received per source token on an
+    /// infinitely small trade, without fees.
+    /// The default implementation is the constant product price, which is the
+    /// ratio of the swap balances.
+    fn spot_price(
+        &self,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        _trade_direction: TradeDirection,
+    ) -> Option<PreciseNumber> {
+        PreciseNumber::new(swap_destination_amount)?
+            .checked_div(&PreciseNumber::new(swap_source_amount)?)
+    }
+
     /// Calculates the total normalized value of the curve given the liquidity
     /// parameters.
     ///
@@ -436,6 +463,97 @@ pub mod test {
         assert!(difference <= epsilon);
     }
 
+    /// Test function checking that an exact-out swap provides exactly the
+    /// requested destination amount and never reduces the overall value of
+    /// the pool.
+    pub fn check_curve_value_from_swap_exact_out(
+        curve: &dyn CurveCalculator,
+        destination_token_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) {
+        let results = curve
+            .swap_without_fees_exact_out(
+                destination_token_amount,
+                swap_source_amount,
+                swap_destination_amount,
+                trade_direction,
+            )
+            .unwrap();
+        assert_eq!(results.destination_amount_swapped, destination_token_amount);
+
+        // swapping the source amount gives at least the requested amount
+        let forward_results = curve
+            .swap_without_fees(
+                results.source_amount_swapped,
+                swap_source_amount,
+                swap_destination_amount,
+                trade_direction,
+            )
+            .unwrap();
+        assert!(forward_results.destination_amount_swapped >= destination_token_amount);
+
+        let (swap_token_a_amount, swap_token_b_amount) = match trade_direction {
+            TradeDirection::AtoB => (swap_source_amount, swap_destination_amount),
+            TradeDirection::BtoA => (swap_destination_amount, swap_source_amount),
+        };
+        let previous_value = curve
+            .normalized_value(swap_token_a_amount, swap_token_b_amount)
+            .unwrap();
+
+        let new_swap_source_amount = swap_source_amount
+            .checked_add(results.source_amount_swapped)
+            .unwrap();
+        let new_swap_destination_amount = swap_destination_amount
+            .checked_sub(results.destination_amount_swapped)
+            .unwrap();
+        let (swap_token_a_amount, swap_token_b_amount) = match trade_direction {
+            TradeDirection::AtoB => (new_swap_source_amount, new_swap_destination_amount),
+            TradeDirection::BtoA => (new_swap_destination_amount, new_swap_source_amount),
+        };
+
+        let new_value = curve
+            .normalized_value(swap_token_a_amount, swap_token_b_amount)
+            .unwrap();
+        assert!(new_value.greater_than_or_equal(&previous_value));
+    }
+
+    /// Test function checking that the average price of a swap is never
+    /// better than the spot price before the swap.
+    pub fn check_spot_price_from_swap(
+        curve: &dyn CurveCalculator,
+        source_token_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) {
+        let spot_price = curve
+            .spot_price(swap_source_amount, swap_destination_amount, trade_direction)
+            .unwrap();
+        let results = curve
+            .swap_without_fees(
+                source_token_amount,
+                swap_source_amount,
+                swap_destination_amount,
+                trade_direction,
+            )
+            .unwrap();
+
+        // allow for the spot price to be truncated by one precise unit
+        let source_amount_swapped = U256::from(results.source_amount_swapped);
+        let destination_value = U256::from(results.destination_amount_swapped)
+            .checked_mul(U256::from(spl_math::precise_number::ONE))
+            .unwrap();
+        let spot_value = spot_price
+            .value
+            .checked_add(U256::one())
+            .unwrap()
+            .checked_mul(source_amount_swapped)
+            .unwrap();
+        assert!(destination_value <= spot_value);
+    }
+
     /// Test function checking that a deposit never reduces the value of pool
     /// tokens.
     ///
@@ -98,6 +98,49 @@ impl CurveCalculator for ConstantPriceCurve {
         })
     }
 
+    /// The inverse constant price swap rounds up the amount of token B
+    /// required when buying token A
+    fn swap_without_fees_exact_out(
+        &self,
+        destination_amount: u128,
+        _swap_source_amount: u128,
+        _swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Option<SwapWithoutFeesResult> {
+        let token_b_price = self.token_b_price as u128;
+
+        let source_amount_swapped = match trade_direction {
+            TradeDirection::BtoA => {
+                let mut source_amount_swapped = destination_amount.checked_div(token_b_price)?;
+                if destination_amount.checked_rem(token_b_price)? > 0 {
+                    source_amount_swapped = source_amount_swapped.checked_add(1)?;
+                }
+                source_amount_swapped
+            }
+            TradeDirection::AtoB => destination_amount.checked_mul(token_b_price)?,
+        };
+        let source_amount_swapped = map_zero_to_none(source_amount_swapped)?;
+        let destination_amount_swapped = map_zero_to_none(destination_amount)?;
+        Some(SwapWithoutFeesResult {
+            source_amount_swapped,
+            destination_amount_swapped,
+        })
+    }
+
+    /// The spot price is always the constant price, whatever the balances
+    fn spot_price(
+        &self,
+        _swap_source_amount: u128,
+        _swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Option<PreciseNumber> {
+        let token_b_price = PreciseNumber::new(self.token_b_price as u128)?;
+        match trade_direction {
+            TradeDirection::AtoB => PreciseNumber::new(1)?.checked_div(&token_b_price),
+            TradeDirection::BtoA => Some(token_b_price),
+        }
+    }
+
     /// Get the amount of trading tokens for the given amount of pool tokens,
     /// provided the total trading tokens and supply of pool tokens.
     /// For the constant price curve, the total value of the pool is weighted
@@ -264,7 +307,8 @@ mod tests {
     use super::*;
     use crate::curve::calculator::{
         test::{
-            check_curve_value_from_swap, check_deposit_token_conversion,
+            check_curve_value_from_swap, check_curve_value_from_swap_exact_out,
+            check_deposit_token_conversion, check_spot_price_from_swap,
             check_withdraw_token_conversion, total_and_intermediate,
             CONVERSION_BASIS_POINTS_GUARANTEE,
         },
@@ -557,6 +601,64 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn curve_value_does_not_decrease_from_swap_exact_out(
+            destination_token_amount in 1..u64::MAX,
+            swap_source_amount in 1..u64::MAX,
+            swap_destination_amount in 1..u64::MAX,
+            token_b_price in 1..u64::MAX,
+        ) {
+            // Make sure there's enough tokens to give out
+            prop_assume!(destination_token_amount <= swap_destination_amount);
+            let curve = ConstantPriceCurve { token_b_price };
+            check_curve_value_from_swap_exact_out(
+                &curve,
+                destination_token_amount as u128,
+                swap_source_amount as u128,
+                swap_destination_amount as u128,
+                TradeDirection::AtoB
+            );
+            check_curve_value_from_swap_exact_out(
+                &curve,
+                destination_token_amount as u128,
+                swap_source_amount as u128,
+                swap_destination_amount as u128,
+                TradeDirection::BtoA
+            );
+        }
+    }
+
+    #[test]
+    fn spot_price() {
+        let curve = ConstantPriceCurve { token_b_price: 4 };
+        let price = curve.spot_price(1, 1_000, TradeDirection::BtoA).unwrap();
+        assert_eq!(price, PreciseNumber::new(4).unwrap());
+        let price = curve.spot_price(1_000, 1, TradeDirection::AtoB).unwrap();
+        assert_eq!(price.value, (spl_math::precise_number::ONE / 4).into());
+    }
+
+    proptest! {
+        #[test]
+        fn spot_price_is_not_beaten_by_swap(
+            source_token_amount in 1..u64::MAX,
+            swap_source_amount in 1..u64::MAX,
+            swap_destination_amount in 1..u64::MAX,
+            token_b_price in 1..u32::MAX,
+        ) {
+            // Make sure that the trade yields at least 1 token B
+            prop_assume!(source_token_amount / token_b_price as u64 >= 1);
+            let curve = ConstantPriceCurve { token_b_price: token_b_price as u64 };
+            check_spot_price_from_swap(
+                &curve,
+                source_token_amount as u128,
+                swap_source_amount as u128,
+                swap_destination_amount as u128,
+                TradeDirection::AtoB
+            );
+        }
+    }
+
     proptest! {
         #[test]
         fn curve_value_does_not_decrease_from_deposit(
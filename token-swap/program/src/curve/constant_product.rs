@@ -45,6 +45,36 @@ pub fn swap(
     })
 }
 
+/// The inverse constant product swap calculation, giving the source amount
+/// required to receive exactly `destination_amount`, rounded up.
+///
+/// This is guaranteed to work for all values such that:
+///  - 1 <= swap_source_amount * swap_destination_amount <= u128::MAX
+///  - 1 <= destination_amount < swap_destination_amount
+pub fn swap_exact_out(
+    destination_amount: u128,
+    swap_source_amount: u128,
+    swap_destination_amount: u128,
+) -> Option<SwapWithoutFeesResult> {
+    let invariant = swap_source_amount.checked_mul(swap_destination_amount)?;
+
+    let new_swap_destination_amount =
+        map_zero_to_none(swap_destination_amount.checked_sub(destination_amount)?)?;
+    let mut new_swap_source_amount = invariant.checked_div(new_swap_destination_amount)?;
+    if invariant.checked_rem(new_swap_destination_amount)? > 0 {
+        new_swap_source_amount = new_swap_source_amount.checked_add(1)?;
+    }
+
+    let source_amount_swapped =
+        map_zero_to_none(new_swap_source_amount.checked_sub(swap_source_amount)?)?;
+    let destination_amount_swapped = map_zero_to_none(destination_amount)?;
+
+    Some(SwapWithoutFeesResult {
+        source_amount_swapped,
+        destination_amount_swapped,
+    })
+}
+
 /// Get the amount of trading tokens for the given amount of pool tokens,
 /// provided the total trading tokens and supply of pool tokens.
 ///
@@ -182,6 +212,20 @@ impl CurveCalculator for ConstantProductCurve {
         swap(source_amount, swap_source_amount, swap_destination_amount)
     }
 
+    fn swap_without_fees_exact_out(
+        &self,
+        destination_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        _trade_direction: TradeDirection,
+    ) -> Option<SwapWithoutFeesResult> {
+        swap_exact_out(
+            destination_amount,
+            swap_source_amount,
+            swap_destination_amount,
+        )
+    }
+
     /// The constant product implementation is a simple ratio calculation for how many
     /// trading tokens correspond to a certain number of pool tokens
     fn pool_tokens_to_trading_tokens(
@@ -278,8 +322,9 @@ mod tests {
     use super::*;
     use crate::curve::calculator::{
         test::{
-            check_curve_value_from_swap, check_deposit_token_conversion,
-            check_pool_value_from_deposit, check_pool_value_from_withdraw,
+            check_curve_value_from_swap, check_curve_value_from_swap_exact_out,
+            check_deposit_token_conversion, check_pool_value_from_deposit,
+            check_pool_value_from_withdraw, check_spot_price_from_swap,
             check_withdraw_token_conversion, total_and_intermediate,
             CONVERSION_BASIS_POINTS_GUARANTEE,
         },
@@ -494,6 +539,58 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn curve_value_does_not_decrease_from_swap_exact_out(
+            destination_token_amount in 1..u64::MAX,
+            swap_source_amount in 1..u64::MAX,
+            swap_destination_amount in 1..u64::MAX,
+        ) {
+            // The pool can't give out its whole balance
+            prop_assume!(destination_token_amount < swap_destination_amount);
+            let curve = ConstantProductCurve {};
+            check_curve_value_from_swap_exact_out(
+                &curve,
+                destination_token_amount as u128,
+                swap_source_amount as u128,
+                swap_destination_amount as u128,
+                TradeDirection::AtoB
+            );
+        }
+    }
+
+    #[test]
+    fn spot_price() {
+        let curve = ConstantProductCurve {};
+        let price = curve
+            .spot_price(1_000, 4_000, TradeDirection::AtoB)
+            .unwrap();
+        assert_eq!(price, PreciseNumber::new(4).unwrap());
+        let price = curve
+            .spot_price(4_000, 1_000, TradeDirection::BtoA)
+            .unwrap();
+        assert_eq!(price.value, (spl_math::precise_number::ONE / 4).into());
+        assert!(curve.spot_price(0, 1_000, TradeDirection::AtoB).is_none());
+    }
+
+    proptest! {
+        #[test]
+        fn spot_price_is_not_beaten_by_swap(
+            source_token_amount in 1..u64::MAX,
+            swap_source_amount in 1..u64::MAX,
+            swap_destination_amount in 1..u64::MAX,
+        ) {
+            let curve = ConstantProductCurve {};
+            check_spot_price_from_swap(
+                &curve,
+                source_token_amount as u128,
+                swap_source_amount as u128,
+                swap_destination_amount as u128,
+                TradeDirection::AtoB
+            );
+        }
+    }
+
     proptest! {
         #[test]
         fn curve_value_does_not_decrease_from_deposit(
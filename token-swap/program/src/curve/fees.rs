@@ -61,6 +61,18 @@ pub fn calculate_fee(
     }
 }
 
+/// Helper function giving a fee as a fraction, where an unset fee is zero
+fn fee_fraction(numerator: u64, denominator: u64) -> Option<(u128, u128)> {
+    if numerator == 0 {
+        Some((0, 1))
+    } else {
+        Some((
+            u128::try_from(numerator).ok()?,
+            u128::try_from(denominator).ok()?,
+        ))
+    }
+}
+
 fn validate_fraction(numerator: u64, denominator: u64) -> Result<(), SwapError> {
     if denominator == 0 && numerator == 0 {
         Ok(())
@@ -99,6 +111,54 @@ impl Fees {
         )
     }
 
+    /// Calculate the amount of trading tokens which leaves at least
+    /// `post_fee_amount` once the trading fee and owner trading fee are
+    /// taken out, used for swaps with an exact output
+    pub fn pre_trading_fee_amount(&self, post_fee_amount: u128) -> Option<u128> {
+        let (trade_fee_numerator, trade_fee_denominator) =
+            fee_fraction(self.trade_fee_numerator, self.trade_fee_denominator)?;
+        let (owner_fee_numerator, owner_fee_denominator) = fee_fraction(
+            self.owner_trade_fee_numerator,
+            self.owner_trade_fee_denominator,
+        )?;
+        let denominator = trade_fee_denominator.checked_mul(owner_fee_denominator)?;
+        let numerator = trade_fee_numerator
+            .checked_mul(owner_fee_denominator)?
+            .checked_add(owner_fee_numerator.checked_mul(trade_fee_denominator)?)?;
+        let remaining = denominator.checked_sub(numerator)?;
+        if remaining == 0 {
+            return None;
+        }
+
+        // start from the exact inverse of both fractions, rounded up, then
+        // account for the minimum fee of one token, and for any fee rounded
+        // down which allows a slightly smaller amount
+        let amount_less_fees = |amount: u128| -> Option<u128> {
+            let fees = self
+                .trading_fee(amount)?
+                .checked_add(self.owner_trading_fee(amount)?)?;
+            Some(amount.saturating_sub(fees))
+        };
+        let scaled_amount = post_fee_amount.checked_mul(denominator)?;
+        let mut pre_fee_amount = scaled_amount.checked_div(remaining)?;
+        if scaled_amount.checked_rem(remaining)? > 0 {
+            pre_fee_amount = pre_fee_amount.checked_add(1)?;
+        }
+        loop {
+            let amount = amount_less_fees(pre_fee_amount)?;
+            if amount >= post_fee_amount {
+                break;
+            }
+            pre_fee_amount = pre_fee_amount.checked_add(post_fee_amount.checked_sub(amount)?)?;
+        }
+        while pre_fee_amount > post_fee_amount
+            && amount_less_fees(pre_fee_amount.checked_sub(1)?)? >= post_fee_amount
+        {
+            pre_fee_amount = pre_fee_amount.checked_sub(1)?;
+        }
+        Some(pre_fee_amount)
+    }
+
     /// Calculate the host fee based on the owner fee, only used in production
     /// situations where a program is hosted by multiple frontends
     pub fn host_fee(&self, owner_fee: u128) -> Option<u128> {
@@ -225,4 +285,41 @@ mod tests {
         let unpacked = Fees::unpack_from_slice(&packed).unwrap();
         assert_eq!(fees, unpacked);
     }
+
+    #[test]
+    fn pre_trading_fee_amount() {
+        let fees = Fees {
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            owner_trade_fee_numerator: 5,
+            owner_trade_fee_denominator: 10_000,
+            ..Fees::default()
+        };
+        for post_fee_amount in [1, 2, 10, 333, 1_000, 12_345, 1_000_000_000] {
+            let pre_fee_amount = fees.pre_trading_fee_amount(post_fee_amount).unwrap();
+            let total_fees = fees.trading_fee(pre_fee_amount).unwrap()
+                + fees.owner_trading_fee(pre_fee_amount).unwrap();
+            assert!(pre_fee_amount - total_fees >= post_fee_amount);
+            let smaller_amount = pre_fee_amount - 1;
+            let total_fees = fees.trading_fee(smaller_amount).unwrap()
+                + fees.owner_trading_fee(smaller_amount).unwrap();
+            assert!(smaller_amount.saturating_sub(total_fees) < post_fee_amount);
+            // at most the minimum fee of one token for each fee above the
+            // exact inverse
+            let exact = post_fee_amount * 10_000 / 9_970 + 1;
+            assert!(pre_fee_amount <= exact + 2);
+        }
+
+        let no_fees = Fees::default();
+        assert_eq!(no_fees.pre_trading_fee_amount(100), Some(100));
+
+        let all_fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 2,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 2,
+            ..Fees::default()
+        };
+        assert_eq!(all_fees.pre_trading_fee_amount(100), None);
+    }
 }
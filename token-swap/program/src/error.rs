@@ -109,6 +109,26 @@ pub enum SwapError {
     /// The pool fee account is invalid.
     #[error("The pool fee account is invalid")]
     InvalidFeeAccount,
+    /// Swaps and deposits are paused by the swap admin
+    #[error("Swaps and deposits are paused by the swap admin")]
+    SwapPaused,
+
+    // 30.
+    /// The provided admin does not match the swap's admin authority
+    #[error("The provided admin does not match the swap's admin authority")]
+    InvalidAdmin,
+    /// The swap has no admin authority, so it cannot be updated
+    #[error("The swap has no admin authority, so it cannot be updated")]
+    NoAdmin,
+    /// A pool of the route cannot swap all of the previous pool's output
+    #[error("A pool of the route cannot swap all of the previous pool's output")]
+    PartialRouteSwap,
+    /// The swap account must be writable to update its price oracle
+    #[error("The swap account must be writable to update its price oracle")]
+    ReadonlySwapAccount,
+    /// The route has more pools than a route swap allows
+    #[error("The route has more pools than a route swap allows")]
+    TooManyRouteHops,
 }
 impl From<SwapError> for ProgramError {
     fn from(e: SwapError) -> Self {
@@ -192,6 +212,24 @@ impl PrintProgramError for SwapError {
             SwapError::InvalidFeeAccount => {
                 msg!("Error: The pool fee account is invalid")
             }
+            SwapError::SwapPaused => {
+                msg!("Error: Swaps and deposits are paused by the swap admin")
+            }
+            SwapError::InvalidAdmin => {
+                msg!("Error: The provided admin does not match the swap's admin authority")
+            }
+            SwapError::NoAdmin => {
+                msg!("Error: The swap has no admin authority, so it cannot be updated")
+            }
+            SwapError::PartialRouteSwap => {
+                msg!("Error: A pool of the route cannot swap all of the previous pool's output")
+            }
+            SwapError::ReadonlySwapAccount => {
+                msg!("Error: The swap account must be writable to update its price oracle")
+            }
+            SwapError::TooManyRouteHops => {
+                msg!("Error: The route has more pools than a route swap allows")
+            }
         }
     }
 }
@@ -7,10 +7,11 @@ use crate::error::SwapError;
 use solana_program::{
     instruction::{AccountMeta, Instruction},
     program_error::ProgramError,
+    program_option::COption,
     program_pack::Pack,
     pubkey::Pubkey,
 };
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 use std::mem::size_of;
 
 #[cfg(feature = "fuzz")]
@@ -38,6 +39,17 @@ pub struct Swap {
     pub minimum_amount_out: u64,
 }
 
+/// SwapExactAmountOut instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapExactAmountOut {
+    /// DESTINATION amount to receive, input from SOURCE is based on the exchange rate
+    pub amount_out: u64,
+    /// Maximum amount of SOURCE token to input, prevents excessive slippage
+    pub maximum_amount_in: u64,
+}
+
 /// DepositAllTokenTypes instruction data
 #[cfg_attr(feature = "fuzz", derive(Arbitrary))]
 #[repr(C)]
@@ -90,6 +102,50 @@ pub struct WithdrawSingleTokenTypeExactAmountOut {
     pub maximum_pool_token_amount: u64,
 }
 
+/// SetFees instruction data
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetFees {
+    /// New fees for the swap, checked against the program owner's constraints
+    pub fees: Fees,
+}
+
+/// SetAdmin instruction data
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetAdmin {
+    /// New admin authority for the swap.  If not present, the swap can no
+    /// longer be updated.
+    pub new_admin: COption<Pubkey>,
+}
+
+/// One pool of a RouteSwap
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteHop {
+    /// Whether a host fee account follows the accounts of this pool
+    pub has_host_fee_account: bool,
+}
+
+/// Maximum number of pools in a RouteSwap
+pub const MAX_ROUTE_HOPS: usize = 8;
+
+/// RouteSwap instruction data
+#[cfg_attr(feature = "fuzz", derive(Arbitrary))]
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteSwap {
+    /// SOURCE amount to transfer into the first pool, output to DESTINATION
+    /// is based on the exchange rates of all pools in the route
+    pub amount_in: u64,
+    /// Minimum amount of DESTINATION token to output from the last pool,
+    /// prevents excessive slippage over the whole route
+    pub minimum_amount_out: u64,
+    /// Pools to swap through, in order, at most `MAX_ROUTE_HOPS`
+    pub hops: Vec<RouteHop>,
+}
+
 /// Instructions supported by the token swap program.
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -106,11 +162,13 @@ pub enum SwapInstruction {
     ///   6. `[writable]` Pool Token Account to deposit the initial pool token
     ///   supply.  Must be empty, not owned by swap authority.
     ///   7. `[]` Pool Token program id
+    ///   8. `[optional]` Admin authority, allowed to update the fees and pause
+    ///   the swap.  If not provided, the swap cannot be updated.
     Initialize(Initialize),
 
     ///   Swap the tokens in the pool.
     ///
-    ///   0. `[]` Token-swap
+    ///   0. `[writable]` Token-swap, may be read-only for swaps without a price oracle
     ///   1. `[]` swap authority
     ///   2. `[]` user transfer authority
     ///   3. `[writable]` token_(A|B) SOURCE Account, amount is transferable by user transfer authority,
@@ -131,7 +189,7 @@ pub enum SwapInstruction {
     ///   token representing ownership in the pool. Inputs are converted to
     ///   the current ratio.
     ///
-    ///   0. `[]` Token-swap
+    ///   0. `[writable]` Token-swap, may be read-only for swaps without a price oracle
     ///   1. `[]` swap authority
     ///   2. `[]` user transfer authority
     ///   3. `[writable]` token_a user transfer authority can transfer amount,
@@ -151,7 +209,7 @@ pub enum SwapInstruction {
     ///   pool tokens.  The pool tokens are burned in exchange for an equivalent
     ///   amount of token A and B.
     ///
-    ///   0. `[]` Token-swap
+    ///   0. `[writable]` Token-swap, may be read-only for swaps without a price oracle
     ///   1. `[]` swap authority
     ///   2. `[]` user transfer authority
     ///   3. `[writable]` Pool mint account, swap authority is the owner
@@ -172,7 +230,7 @@ pub enum SwapInstruction {
     ///   representing ownership into the pool. Input token is converted as if
     ///   a swap and deposit all token types were performed.
     ///
-    ///   0. `[]` Token-swap
+    ///   0. `[writable]` Token-swap, may be read-only for swaps without a price oracle
     ///   1. `[]` swap authority
     ///   2. `[]` user transfer authority
     ///   3. `[writable]` token_(A|B) SOURCE Account, amount is transferable by user transfer authority,
@@ -188,7 +246,7 @@ pub enum SwapInstruction {
     ///   Withdraw one token type from the pool at the current ratio given the
     ///   exact amount out expected.
     ///
-    ///   0. `[]` Token-swap
+    ///   0. `[writable]` Token-swap, may be read-only for swaps without a price oracle
     ///   1. `[]` swap authority
     ///   2. `[]` user transfer authority
     ///   3. `[writable]` Pool mint account, swap authority is the owner
@@ -201,6 +259,79 @@ pub enum SwapInstruction {
     ///   10. `[]` Pool Token program id
     ///   11. `[]` Token (A|B) DESTINATION program id
     WithdrawSingleTokenTypeExactAmountOut(WithdrawSingleTokenTypeExactAmountOut),
+
+    ///   Update the fees of the swap.
+    ///
+    ///   0. `[writable]` Token-swap
+    ///   1. `[signer]` Admin authority
+    SetFees(SetFees),
+
+    ///   Set or remove the admin authority of the swap.
+    ///
+    ///   0. `[writable]` Token-swap
+    ///   1. `[signer]` Current admin authority
+    SetAdmin(SetAdmin),
+
+    ///   Pause the swap, rejecting all swaps and deposits.  Withdrawals are
+    ///   still allowed.
+    ///
+    ///   0. `[writable]` Token-swap
+    ///   1. `[signer]` Admin authority
+    Pause,
+
+    ///   Resume a paused swap.
+    ///
+    ///   0. `[writable]` Token-swap
+    ///   1. `[signer]` Admin authority
+    Resume,
+
+    ///   Swap through a route of pools, using the output of each pool as the
+    ///   input of the next one.  Intermediate tokens go directly from one
+    ///   pool to the next, and the slippage limit applies to the output of
+    ///   the last pool.  Fails if a pool after the first cannot swap all of
+    ///   the previous pool's output, e.g. because its curve rounds the input.
+    ///
+    ///   0. `[]` user transfer authority
+    ///   1. `[writable]` SOURCE Account, amount is transferable by user transfer authority,
+    ///   2. `[writable]` DESTINATION Account assigned to USER as the owner.
+    ///   3. `[]` SOURCE mint
+    ///   4. `[]` SOURCE program id
+    ///
+    ///   Followed by, for each hop of the route, in order:
+    ///
+    ///   0. `[writable]` Token-swap
+    ///   1. `[]` swap authority
+    ///   2. `[writable]` token_(A|B) Base Account to swap INTO.  Must be the
+    ///   DESTINATION token of the previous hop, or the SOURCE token.
+    ///   3. `[writable]` token_(A|B) Base Account to swap FROM.
+    ///   4. `[writable]` Pool token mint, to generate trading fees
+    ///   5. `[writable]` Fee account, to receive trading fees
+    ///   6. `[]` Token (A|B) mint to swap FROM
+    ///   7. `[]` Token (A|B) program id to swap FROM
+    ///   8. `[]` Pool Token program id
+    ///   9. `[optional, writable]` Host fee account to receive additional
+    ///   trading fees, if the hop has one
+    RouteSwap(RouteSwap),
+
+    ///   Swap the tokens in the pool, receiving an exact amount of the
+    ///   DESTINATION token.
+    ///
+    ///   0. `[writable]` Token-swap, may be read-only for swaps without a price oracle
+    ///   1. `[]` swap authority
+    ///   2. `[]` user transfer authority
+    ///   3. `[writable]` token_(A|B) SOURCE Account, amount is transferable by user transfer authority,
+    ///   4. `[writable]` token_(A|B) Base Account to swap INTO.  Must be the SOURCE token.
+    ///   5. `[writable]` token_(A|B) Base Account to swap FROM.  Must be the DESTINATION token.
+    ///   6. `[writable]` token_(A|B) DESTINATION Account assigned to USER as the owner.
+    ///   7. `[writable]` Pool token mint, to generate trading fees
+    ///   8. `[writable]` Fee account, to receive trading fees
+    ///   9. `[]` Token (A|B) SOURCE mint
+    ///   10. `[]` Token (A|B) DESTINATION mint
+    ///   11. `[]` Token (A|B) SOURCE program id
+    ///   12. `[]` Token (A|B) DESTINATION program id
+    ///   13. `[]` Pool Token program id
+    ///   14. `[optional, writable]` Host fee account to receive additional trading fees
+    SwapExactAmountOut(SwapExactAmountOut),
 }
 
 impl SwapInstruction {
@@ -262,6 +393,58 @@ impl SwapInstruction {
                     maximum_pool_token_amount,
                 })
             }
+            6 => {
+                if rest.len() >= Fees::LEN {
+                    let fees = Fees::unpack_unchecked(&rest[..Fees::LEN])?;
+                    Self::SetFees(SetFees { fees })
+                } else {
+                    return Err(SwapError::InvalidInstruction.into());
+                }
+            }
+            7 => {
+                let (new_admin, _rest) = Self::unpack_pubkey_option(rest)?;
+                Self::SetAdmin(SetAdmin { new_admin })
+            }
+            8 => Self::Pause,
+            9 => Self::Resume,
+            10 => {
+                let (amount_in, rest) = Self::unpack_u64(rest)?;
+                let (minimum_amount_out, rest) = Self::unpack_u64(rest)?;
+                let (&num_hops, rest) = rest.split_first().ok_or(SwapError::InvalidInstruction)?;
+                if num_hops as usize > MAX_ROUTE_HOPS {
+                    return Err(SwapError::TooManyRouteHops.into());
+                }
+                let hops = rest
+                    .get(..num_hops as usize)
+                    .ok_or(SwapError::InvalidInstruction)?
+                    .iter()
+                    .map(|has_host_fee_account| match has_host_fee_account {
+                        0 => Ok(RouteHop {
+                            has_host_fee_account: false,
+                        }),
+                        1 => Ok(RouteHop {
+                            has_host_fee_account: true,
+                        }),
+                        _ => Err(SwapError::InvalidInstruction),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                if hops.is_empty() {
+                    return Err(SwapError::InvalidInstruction.into());
+                }
+                Self::RouteSwap(RouteSwap {
+                    amount_in,
+                    minimum_amount_out,
+                    hops,
+                })
+            }
+            11 => {
+                let (amount_out, rest) = Self::unpack_u64(rest)?;
+                let (maximum_amount_in, _rest) = Self::unpack_u64(rest)?;
+                Self::SwapExactAmountOut(SwapExactAmountOut {
+                    amount_out,
+                    maximum_amount_in,
+                })
+            }
             _ => return Err(SwapError::InvalidInstruction.into()),
         })
     }
@@ -280,6 +463,28 @@ impl SwapInstruction {
         }
     }
 
+    fn unpack_pubkey_option(input: &[u8]) -> Result<(COption<Pubkey>, &[u8]), ProgramError> {
+        match input.split_first() {
+            Some((&0, rest)) => Ok((COption::None, rest)),
+            Some((&1, rest)) if rest.len() >= 32 => {
+                let (key, rest) = rest.split_at(32);
+                let key = Pubkey::new(key);
+                Ok((COption::Some(key), rest))
+            }
+            _ => Err(SwapError::InvalidInstruction.into()),
+        }
+    }
+
+    fn pack_pubkey_option(value: &COption<Pubkey>, buf: &mut Vec<u8>) {
+        match *value {
+            COption::Some(ref key) => {
+                buf.push(1);
+                buf.extend_from_slice(&key.to_bytes());
+            }
+            COption::None => buf.push(0),
+        }
+    }
+
     /// Packs a [SwapInstruction](enum.SwapInstruction.html) into a byte buffer.
     pub fn pack(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(size_of::<Self>());
@@ -339,6 +544,40 @@ impl SwapInstruction {
                 buf.extend_from_slice(&destination_token_amount.to_le_bytes());
                 buf.extend_from_slice(&maximum_pool_token_amount.to_le_bytes());
             }
+            Self::SetFees(SetFees { fees }) => {
+                buf.push(6);
+                let mut fees_slice = [0u8; Fees::LEN];
+                Pack::pack_into_slice(fees, &mut fees_slice[..]);
+                buf.extend_from_slice(&fees_slice);
+            }
+            Self::SetAdmin(SetAdmin { new_admin }) => {
+                buf.push(7);
+                Self::pack_pubkey_option(new_admin, &mut buf);
+            }
+            Self::Pause => buf.push(8),
+            Self::Resume => buf.push(9),
+            Self::RouteSwap(RouteSwap {
+                amount_in,
+                minimum_amount_out,
+                hops,
+            }) => {
+                buf.push(10);
+                buf.extend_from_slice(&amount_in.to_le_bytes());
+                buf.extend_from_slice(&minimum_amount_out.to_le_bytes());
+                // Routes over `MAX_ROUTE_HOPS` are rejected on unpack
+                buf.push(u8::try_from(hops.len()).unwrap_or(u8::MAX));
+                for hop in hops {
+                    buf.push(hop.has_host_fee_account as u8);
+                }
+            }
+            Self::SwapExactAmountOut(SwapExactAmountOut {
+                amount_out,
+                maximum_amount_in,
+            }) => {
+                buf.push(11);
+                buf.extend_from_slice(&amount_out.to_le_bytes());
+                buf.extend_from_slice(&maximum_amount_in.to_le_bytes());
+            }
         }
         buf
     }
@@ -355,13 +594,14 @@ pub fn initialize(
     pool_pubkey: &Pubkey,
     fee_pubkey: &Pubkey,
     destination_pubkey: &Pubkey,
+    admin_pubkey: Option<&Pubkey>,
     fees: Fees,
     swap_curve: SwapCurve,
 ) -> Result<Instruction, ProgramError> {
     let init_data = SwapInstruction::Initialize(Initialize { fees, swap_curve });
     let data = init_data.pack();
 
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(*swap_pubkey, true),
         AccountMeta::new_readonly(*authority_pubkey, false),
         AccountMeta::new_readonly(*token_a_pubkey, false),
@@ -371,6 +611,9 @@ pub fn initialize(
         AccountMeta::new(*destination_pubkey, false),
         AccountMeta::new_readonly(*token_program_id, false),
     ];
+    if let Some(admin_pubkey) = admin_pubkey {
+        accounts.push(AccountMeta::new_readonly(*admin_pubkey, false));
+    }
 
     Ok(Instruction {
         program_id: *program_id,
@@ -401,7 +644,7 @@ pub fn deposit_all_token_types(
     let data = SwapInstruction::DepositAllTokenTypes(instruction).pack();
 
     let accounts = vec![
-        AccountMeta::new_readonly(*swap_pubkey, false),
+        AccountMeta::new(*swap_pubkey, false),
         AccountMeta::new_readonly(*authority_pubkey, false),
         AccountMeta::new_readonly(*user_transfer_authority_pubkey, true),
         AccountMeta::new(*deposit_token_a_pubkey, false),
@@ -447,7 +690,7 @@ pub fn withdraw_all_token_types(
     let data = SwapInstruction::WithdrawAllTokenTypes(instruction).pack();
 
     let accounts = vec![
-        AccountMeta::new_readonly(*swap_pubkey, false),
+        AccountMeta::new(*swap_pubkey, false),
         AccountMeta::new_readonly(*authority_pubkey, false),
         AccountMeta::new_readonly(*user_transfer_authority_pubkey, true),
         AccountMeta::new(*pool_mint_pubkey, false),
@@ -490,7 +733,7 @@ pub fn deposit_single_token_type_exact_amount_in(
     let data = SwapInstruction::DepositSingleTokenTypeExactAmountIn(instruction).pack();
 
     let accounts = vec![
-        AccountMeta::new_readonly(*swap_pubkey, false),
+        AccountMeta::new(*swap_pubkey, false),
         AccountMeta::new_readonly(*authority_pubkey, false),
         AccountMeta::new_readonly(*user_transfer_authority_pubkey, true),
         AccountMeta::new(*source_token_pubkey, false),
@@ -530,7 +773,7 @@ pub fn withdraw_single_token_type_exact_amount_out(
     let data = SwapInstruction::WithdrawSingleTokenTypeExactAmountOut(instruction).pack();
 
     let accounts = vec![
-        AccountMeta::new_readonly(*swap_pubkey, false),
+        AccountMeta::new(*swap_pubkey, false),
         AccountMeta::new_readonly(*authority_pubkey, false),
         AccountMeta::new_readonly(*user_transfer_authority_pubkey, true),
         AccountMeta::new(*pool_mint_pubkey, false),
@@ -574,7 +817,56 @@ pub fn swap(
     let data = SwapInstruction::Swap(instruction).pack();
 
     let mut accounts = vec![
-        AccountMeta::new_readonly(*swap_pubkey, false),
+        AccountMeta::new(*swap_pubkey, false),
+        AccountMeta::new_readonly(*authority_pubkey, false),
+        AccountMeta::new_readonly(*user_transfer_authority_pubkey, true),
+        AccountMeta::new(*source_pubkey, false),
+        AccountMeta::new(*swap_source_pubkey, false),
+        AccountMeta::new(*swap_destination_pubkey, false),
+        AccountMeta::new(*destination_pubkey, false),
+        AccountMeta::new(*pool_mint_pubkey, false),
+        AccountMeta::new(*pool_fee_pubkey, false),
+        AccountMeta::new_readonly(*source_mint_pubkey, false),
+        AccountMeta::new_readonly(*destination_mint_pubkey, false),
+        AccountMeta::new_readonly(*source_token_program_id, false),
+        AccountMeta::new_readonly(*destination_token_program_id, false),
+        AccountMeta::new_readonly(*pool_token_program_id, false),
+    ];
+    if let Some(host_fee_pubkey) = host_fee_pubkey {
+        accounts.push(AccountMeta::new(*host_fee_pubkey, false));
+    }
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'swap_exact_amount_out' instruction.
+pub fn swap_exact_amount_out(
+    program_id: &Pubkey,
+    source_token_program_id: &Pubkey,
+    destination_token_program_id: &Pubkey,
+    pool_token_program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    user_transfer_authority_pubkey: &Pubkey,
+    source_pubkey: &Pubkey,
+    swap_source_pubkey: &Pubkey,
+    swap_destination_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    pool_mint_pubkey: &Pubkey,
+    pool_fee_pubkey: &Pubkey,
+    source_mint_pubkey: &Pubkey,
+    destination_mint_pubkey: &Pubkey,
+    host_fee_pubkey: Option<&Pubkey>,
+    instruction: SwapExactAmountOut,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::SwapExactAmountOut(instruction).pack();
+
+    let mut accounts = vec![
+        AccountMeta::new(*swap_pubkey, false),
         AccountMeta::new_readonly(*authority_pubkey, false),
         AccountMeta::new_readonly(*user_transfer_authority_pubkey, true),
         AccountMeta::new(*source_pubkey, false),
@@ -600,6 +892,172 @@ pub fn swap(
     })
 }
 
+/// Accounts of one pool in a route, used to create a 'route_swap' instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteHopAccounts {
+    /// Token-swap
+    pub swap_pubkey: Pubkey,
+    /// Swap authority
+    pub authority_pubkey: Pubkey,
+    /// Swap token account to swap INTO
+    pub swap_source_pubkey: Pubkey,
+    /// Swap token account to swap FROM
+    pub swap_destination_pubkey: Pubkey,
+    /// Pool token mint
+    pub pool_mint_pubkey: Pubkey,
+    /// Pool fee account
+    pub pool_fee_pubkey: Pubkey,
+    /// Mint of the token to swap FROM
+    pub destination_mint_pubkey: Pubkey,
+    /// Program id of the token to swap FROM
+    pub destination_token_program_id: Pubkey,
+    /// Pool token program id
+    pub pool_token_program_id: Pubkey,
+    /// Host fee account to receive additional trading fees
+    pub host_fee_pubkey: Option<Pubkey>,
+}
+
+/// Creates a 'route_swap' instruction.
+pub fn route_swap(
+    program_id: &Pubkey,
+    source_token_program_id: &Pubkey,
+    user_transfer_authority_pubkey: &Pubkey,
+    source_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    source_mint_pubkey: &Pubkey,
+    hops: &[RouteHopAccounts],
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<Instruction, ProgramError> {
+    if hops.len() > MAX_ROUTE_HOPS {
+        return Err(SwapError::TooManyRouteHops.into());
+    }
+    let data = SwapInstruction::RouteSwap(RouteSwap {
+        amount_in,
+        minimum_amount_out,
+        hops: hops
+            .iter()
+            .map(|hop| RouteHop {
+                has_host_fee_account: hop.host_fee_pubkey.is_some(),
+            })
+            .collect(),
+    })
+    .pack();
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*user_transfer_authority_pubkey, true),
+        AccountMeta::new(*source_pubkey, false),
+        AccountMeta::new(*destination_pubkey, false),
+        AccountMeta::new_readonly(*source_mint_pubkey, false),
+        AccountMeta::new_readonly(*source_token_program_id, false),
+    ];
+    for hop in hops {
+        accounts.extend_from_slice(&[
+            AccountMeta::new(hop.swap_pubkey, false),
+            AccountMeta::new_readonly(hop.authority_pubkey, false),
+            AccountMeta::new(hop.swap_source_pubkey, false),
+            AccountMeta::new(hop.swap_destination_pubkey, false),
+            AccountMeta::new(hop.pool_mint_pubkey, false),
+            AccountMeta::new(hop.pool_fee_pubkey, false),
+            AccountMeta::new_readonly(hop.destination_mint_pubkey, false),
+            AccountMeta::new_readonly(hop.destination_token_program_id, false),
+            AccountMeta::new_readonly(hop.pool_token_program_id, false),
+        ]);
+        if let Some(host_fee_pubkey) = hop.host_fee_pubkey {
+            accounts.push(AccountMeta::new(host_fee_pubkey, false));
+        }
+    }
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'set_fees' instruction.
+pub fn set_fees(
+    program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    admin_pubkey: &Pubkey,
+    fees: Fees,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::SetFees(SetFees { fees }).pack();
+
+    let accounts = vec![
+        AccountMeta::new(*swap_pubkey, false),
+        AccountMeta::new_readonly(*admin_pubkey, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'set_admin' instruction.
+pub fn set_admin(
+    program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    admin_pubkey: &Pubkey,
+    new_admin_pubkey: Option<&Pubkey>,
+) -> Result<Instruction, ProgramError> {
+    let new_admin = new_admin_pubkey.cloned().into();
+    let data = SwapInstruction::SetAdmin(SetAdmin { new_admin }).pack();
+
+    let accounts = vec![
+        AccountMeta::new(*swap_pubkey, false),
+        AccountMeta::new_readonly(*admin_pubkey, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'pause' instruction.
+pub fn pause(
+    program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    admin_pubkey: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::Pause.pack();
+
+    let accounts = vec![
+        AccountMeta::new(*swap_pubkey, false),
+        AccountMeta::new_readonly(*admin_pubkey, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'resume' instruction.
+pub fn resume(
+    program_id: &Pubkey,
+    swap_pubkey: &Pubkey,
+    admin_pubkey: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = SwapInstruction::Resume.pack();
+
+    let accounts = vec![
+        AccountMeta::new(*swap_pubkey, false),
+        AccountMeta::new_readonly(*admin_pubkey, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
 /// Unpacks a reference from a bytes buffer.
 /// TODO actually pack / unpack instead of relying on normal memory layout.
 pub fn unpack<T>(input: &[u8]) -> Result<&T, ProgramError> {
@@ -680,6 +1138,23 @@ mod tests {
         assert_eq!(unpacked, check);
     }
 
+    #[test]
+    fn pack_swap_exact_amount_out() {
+        let amount_out: u64 = 10;
+        let maximum_amount_in: u64 = 2;
+        let check = SwapInstruction::SwapExactAmountOut(SwapExactAmountOut {
+            amount_out,
+            maximum_amount_in,
+        });
+        let packed = check.pack();
+        let mut expect = vec![11];
+        expect.extend_from_slice(&amount_out.to_le_bytes());
+        expect.extend_from_slice(&maximum_amount_in.to_le_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+    }
+
     #[test]
     fn pack_deposit() {
         let pool_token_amount: u64 = 5;
@@ -757,4 +1232,161 @@ mod tests {
         let unpacked = SwapInstruction::unpack(&expect).unwrap();
         assert_eq!(unpacked, check);
     }
+
+    #[test]
+    fn pack_set_fees() {
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 4,
+            owner_trade_fee_numerator: 2,
+            owner_trade_fee_denominator: 5,
+            owner_withdraw_fee_numerator: 1,
+            owner_withdraw_fee_denominator: 3,
+            host_fee_numerator: 5,
+            host_fee_denominator: 20,
+        };
+        let check = SwapInstruction::SetFees(SetFees { fees: fees.clone() });
+        let packed = check.pack();
+        let mut expect = vec![6];
+        expect.extend_from_slice(&fees.trade_fee_numerator.to_le_bytes());
+        expect.extend_from_slice(&fees.trade_fee_denominator.to_le_bytes());
+        expect.extend_from_slice(&fees.owner_trade_fee_numerator.to_le_bytes());
+        expect.extend_from_slice(&fees.owner_trade_fee_denominator.to_le_bytes());
+        expect.extend_from_slice(&fees.owner_withdraw_fee_numerator.to_le_bytes());
+        expect.extend_from_slice(&fees.owner_withdraw_fee_denominator.to_le_bytes());
+        expect.extend_from_slice(&fees.host_fee_numerator.to_le_bytes());
+        expect.extend_from_slice(&fees.host_fee_denominator.to_le_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+    }
+
+    #[test]
+    fn pack_set_admin() {
+        let new_admin = Pubkey::new_unique();
+        let check = SwapInstruction::SetAdmin(SetAdmin {
+            new_admin: COption::Some(new_admin),
+        });
+        let packed = check.pack();
+        let mut expect = vec![7, 1];
+        expect.extend_from_slice(&new_admin.to_bytes());
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        let check = SwapInstruction::SetAdmin(SetAdmin {
+            new_admin: COption::None,
+        });
+        let packed = check.pack();
+        let expect = vec![7, 0];
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+    }
+
+    #[test]
+    fn pack_pause_and_resume() {
+        let check = SwapInstruction::Pause;
+        let packed = check.pack();
+        let expect = vec![8];
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        let check = SwapInstruction::Resume;
+        let packed = check.pack();
+        let expect = vec![9];
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+    }
+
+    #[test]
+    fn pack_route_swap() {
+        let amount_in: u64 = 2;
+        let minimum_amount_out: u64 = 10;
+        let check = SwapInstruction::RouteSwap(RouteSwap {
+            amount_in,
+            minimum_amount_out,
+            hops: vec![
+                RouteHop {
+                    has_host_fee_account: true,
+                },
+                RouteHop {
+                    has_host_fee_account: false,
+                },
+            ],
+        });
+        let packed = check.pack();
+        let mut expect = vec![10];
+        expect.extend_from_slice(&amount_in.to_le_bytes());
+        expect.extend_from_slice(&minimum_amount_out.to_le_bytes());
+        expect.extend_from_slice(&[2, 1, 0]);
+        assert_eq!(packed, expect);
+        let unpacked = SwapInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
+
+        // a route needs at least one hop, and a flag for each hop
+        let mut expect = vec![10];
+        expect.extend_from_slice(&amount_in.to_le_bytes());
+        expect.extend_from_slice(&minimum_amount_out.to_le_bytes());
+        expect.push(0);
+        assert_eq!(
+            SwapInstruction::unpack(&expect),
+            Err(SwapError::InvalidInstruction.into())
+        );
+        expect[17] = 2;
+        expect.push(1);
+        assert_eq!(
+            SwapInstruction::unpack(&expect),
+            Err(SwapError::InvalidInstruction.into())
+        );
+
+        // routes are capped at MAX_ROUTE_HOPS pools, including ones too long
+        // for the hop count byte
+        for num_hops in [MAX_ROUTE_HOPS + 1, u8::MAX as usize + 2] {
+            let check = SwapInstruction::RouteSwap(RouteSwap {
+                amount_in,
+                minimum_amount_out,
+                hops: vec![
+                    RouteHop {
+                        has_host_fee_account: false,
+                    };
+                    num_hops
+                ],
+            });
+            assert_eq!(
+                SwapInstruction::unpack(&check.pack()),
+                Err(SwapError::TooManyRouteHops.into())
+            );
+        }
+        let hop = RouteHopAccounts {
+            swap_pubkey: Pubkey::new_unique(),
+            authority_pubkey: Pubkey::new_unique(),
+            swap_source_pubkey: Pubkey::new_unique(),
+            swap_destination_pubkey: Pubkey::new_unique(),
+            pool_mint_pubkey: Pubkey::new_unique(),
+            pool_fee_pubkey: Pubkey::new_unique(),
+            destination_mint_pubkey: Pubkey::new_unique(),
+            destination_token_program_id: Pubkey::new_unique(),
+            pool_token_program_id: Pubkey::new_unique(),
+            host_fee_pubkey: None,
+        };
+        assert_eq!(
+            route_swap(
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &(0..=MAX_ROUTE_HOPS)
+                    .map(|_| hop.clone())
+                    .collect::<Vec<_>>(),
+                amount_in,
+                minimum_amount_out,
+            ),
+            Err(SwapError::TooManyRouteHops.into())
+        );
+    }
 }
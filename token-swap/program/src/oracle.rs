@@ -0,0 +1,263 @@
+//! Price oracle, accumulating the prices of a pool over time
+
+use crate::curve::{base::SwapCurve, calculator::TradeDirection};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use solana_program::{
+    clock::UnixTimestamp,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+};
+use spl_math::{precise_number::PreciseNumber, uint::U256};
+
+/// Cumulative prices of a pool, updated before any swap, deposit, or
+/// withdrawal changes its balances.
+///
+/// Each cumulative price is the sum of the spot price multiplied by the number
+/// of seconds it was in effect, stored as the raw value of a `PreciseNumber`.
+/// The sums wrap around on overflow, so only the difference between two
+/// observations is meaningful, as long as they are not too far apart.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PriceOracle {
+    /// Cumulative price of token A, in token B
+    pub price_a_cumulative: u128,
+    /// Cumulative price of token B, in token A
+    pub price_b_cumulative: u128,
+    /// Unix timestamp of the last update to the cumulative prices
+    pub last_update_timestamp: UnixTimestamp,
+}
+
+/// Time-weighted average prices of a pool between two observations
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeWeightedAveragePrice {
+    /// Average price of token A, in token B
+    pub price_a: PreciseNumber,
+    /// Average price of token B, in token A
+    pub price_b: PreciseNumber,
+}
+
+impl PriceOracle {
+    /// Create a new oracle, accumulating prices from the given time
+    pub fn new(timestamp: UnixTimestamp) -> Self {
+        Self {
+            price_a_cumulative: 0,
+            price_b_cumulative: 0,
+            last_update_timestamp: timestamp,
+        }
+    }
+
+    /// Accumulate the spot prices of the pool with the given balances, from
+    /// the last update until `timestamp`.
+    ///
+    /// On-chain, this is done before the balances of the pool change.  To
+    /// observe the cumulative prices at the current time, without waiting for
+    /// the next update, call it with the current balances of the pool.
+    ///
+    /// Prices which can't be calculated, for example on an empty pool, are not
+    /// accumulated, so that updating the oracle never fails an instruction.
+    pub fn accumulate(
+        &self,
+        swap_curve: &SwapCurve,
+        swap_token_a_amount: u128,
+        swap_token_b_amount: u128,
+        timestamp: UnixTimestamp,
+    ) -> Self {
+        let elapsed = timestamp.saturating_sub(self.last_update_timestamp);
+        if elapsed <= 0 {
+            return self.clone();
+        }
+        let elapsed = U256::from(elapsed as u64);
+        let accumulate_price = |price_cumulative: u128, price: Option<PreciseNumber>| match price
+            .and_then(|price| price.value.checked_mul(elapsed))
+        {
+            // only the lowest bits are kept, differences still work on overflow
+            Some(value) => price_cumulative.wrapping_add(value.low_u128()),
+            None => price_cumulative,
+        };
+        let calculator = &swap_curve.calculator;
+        Self {
+            price_a_cumulative: accumulate_price(
+                self.price_a_cumulative,
+                calculator.spot_price(
+                    swap_token_a_amount,
+                    swap_token_b_amount,
+                    TradeDirection::AtoB,
+                ),
+            ),
+            price_b_cumulative: accumulate_price(
+                self.price_b_cumulative,
+                calculator.spot_price(
+                    swap_token_b_amount,
+                    swap_token_a_amount,
+                    TradeDirection::BtoA,
+                ),
+            ),
+            last_update_timestamp: timestamp,
+        }
+    }
+
+    /// Calculate the time-weighted average prices between an earlier
+    /// observation of the same pool and this one
+    pub fn time_weighted_average_price(&self, earlier: &Self) -> Option<TimeWeightedAveragePrice> {
+        let elapsed = self
+            .last_update_timestamp
+            .checked_sub(earlier.last_update_timestamp)?;
+        if elapsed <= 0 {
+            return None;
+        }
+        let elapsed = U256::from(elapsed as u64);
+        let average_price = |price_cumulative: u128, earlier_price_cumulative: u128| {
+            U256::from(price_cumulative.wrapping_sub(earlier_price_cumulative))
+                .checked_div(elapsed)
+                .map(|value| PreciseNumber { value })
+        };
+        Some(TimeWeightedAveragePrice {
+            price_a: average_price(self.price_a_cumulative, earlier.price_a_cumulative)?,
+            price_b: average_price(self.price_b_cumulative, earlier.price_b_cumulative)?,
+        })
+    }
+}
+
+/// IsInitialized is required to use `Pack::pack` and `Pack::unpack`
+impl IsInitialized for PriceOracle {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+}
+
+impl Sealed for PriceOracle {}
+impl Pack for PriceOracle {
+    const LEN: usize = 40;
+    fn pack_into_slice(&self, output: &mut [u8]) {
+        let output = array_mut_ref![output, 0, 40];
+        let (price_a_cumulative, price_b_cumulative, last_update_timestamp) =
+            mut_array_refs![output, 16, 16, 8];
+        *price_a_cumulative = self.price_a_cumulative.to_le_bytes();
+        *price_b_cumulative = self.price_b_cumulative.to_le_bytes();
+        *last_update_timestamp = self.last_update_timestamp.to_le_bytes();
+    }
+
+    fn unpack_from_slice(input: &[u8]) -> Result<PriceOracle, ProgramError> {
+        let input = array_ref![input, 0, 40];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (price_a_cumulative, price_b_cumulative, last_update_timestamp) =
+            array_refs![input, 16, 16, 8];
+        Ok(Self {
+            price_a_cumulative: u128::from_le_bytes(*price_a_cumulative),
+            price_b_cumulative: u128::from_le_bytes(*price_b_cumulative),
+            last_update_timestamp: UnixTimestamp::from_le_bytes(*last_update_timestamp),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::{
+        base::CurveType, constant_product::ConstantProductCurve, stable::StableCurve,
+    };
+    use std::sync::Arc;
+
+    fn constant_product_curve() -> SwapCurve {
+        SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve {}),
+        }
+    }
+
+    #[test]
+    fn pack_price_oracle() {
+        let price_a_cumulative = u128::MAX - 1;
+        let price_b_cumulative = 42;
+        let last_update_timestamp = 1_650_000_000;
+        let price_oracle = PriceOracle {
+            price_a_cumulative,
+            price_b_cumulative,
+            last_update_timestamp,
+        };
+
+        let mut packed = [0u8; PriceOracle::LEN];
+        Pack::pack_into_slice(&price_oracle, &mut packed[..]);
+        let unpacked = PriceOracle::unpack_from_slice(&packed).unwrap();
+        assert_eq!(price_oracle, unpacked);
+
+        let mut packed = vec![];
+        packed.extend_from_slice(&price_a_cumulative.to_le_bytes());
+        packed.extend_from_slice(&price_b_cumulative.to_le_bytes());
+        packed.extend_from_slice(&last_update_timestamp.to_le_bytes());
+        let unpacked = PriceOracle::unpack_from_slice(&packed).unwrap();
+        assert_eq!(price_oracle, unpacked);
+    }
+
+    #[test]
+    fn time_weighted_average_price() {
+        let swap_curve = constant_product_curve();
+        let start = PriceOracle::new(100);
+
+        // price of A is 4 B for 10 seconds, then 1 B for 30 seconds
+        let middle = start.accumulate(&swap_curve, 1_000, 4_000, 110);
+        let end = middle.accumulate(&swap_curve, 2_000, 2_000, 140);
+        assert_eq!(end.last_update_timestamp, 140);
+
+        let twap = middle.time_weighted_average_price(&start).unwrap();
+        assert_eq!(twap.price_a, PreciseNumber::new(4).unwrap());
+
+        let twap = end.time_weighted_average_price(&start).unwrap();
+        // (4 * 10 + 1 * 30) / 40 = 1.75
+        let expected_price_a = PreciseNumber::new(7)
+            .unwrap()
+            .checked_div(&PreciseNumber::new(4).unwrap())
+            .unwrap();
+        assert_eq!(twap.price_a, expected_price_a);
+        // (0.25 * 10 + 1 * 30) / 40 = 0.8125
+        let expected_price_b = PreciseNumber::new(13)
+            .unwrap()
+            .checked_div(&PreciseNumber::new(16).unwrap())
+            .unwrap();
+        assert_eq!(twap.price_b, expected_price_b);
+
+        // no time elapsed
+        assert_eq!(end.time_weighted_average_price(&end), None);
+        assert_eq!(start.time_weighted_average_price(&end), None);
+    }
+
+    #[test]
+    fn accumulate_wraps_around() {
+        let swap_curve = constant_product_curve();
+        let start = PriceOracle {
+            price_a_cumulative: u128::MAX - 10,
+            price_b_cumulative: u128::MAX,
+            last_update_timestamp: 0,
+        };
+        let end = start.accumulate(&swap_curve, 1_000, 3_000, 60);
+        assert!(end.price_a_cumulative < start.price_a_cumulative);
+        assert!(end.price_b_cumulative < start.price_b_cumulative);
+        let twap = end.time_weighted_average_price(&start).unwrap();
+        assert_eq!(twap.price_a, PreciseNumber::new(3).unwrap());
+        assert_eq!(
+            twap.price_b,
+            PreciseNumber::new(1)
+                .unwrap()
+                .checked_div(&PreciseNumber::new(3).unwrap())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn accumulate_without_price() {
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::Stable,
+            calculator: Arc::new(StableCurve { amp: 100 }),
+        };
+        let start = PriceOracle::new(10);
+
+        // an empty pool has no price, but the timestamp still moves forward
+        let end = start.accumulate(&swap_curve, 0, 0, 20);
+        assert_eq!(end.price_a_cumulative, 0);
+        assert_eq!(end.price_b_cumulative, 0);
+        assert_eq!(end.last_update_timestamp, 20);
+
+        // time going backwards doesn't change anything
+        assert_eq!(end.accumulate(&swap_curve, 1_000, 1_000, 15), end);
+    }
+}
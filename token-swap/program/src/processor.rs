@@ -9,10 +9,12 @@ use crate::{
     },
     error::SwapError,
     instruction::{
-        DepositAllTokenTypes, DepositSingleTokenTypeExactAmountIn, Initialize, Swap,
-        SwapInstruction, WithdrawAllTokenTypes, WithdrawSingleTokenTypeExactAmountOut,
+        DepositAllTokenTypes, DepositSingleTokenTypeExactAmountIn, Initialize, RouteHop, RouteSwap,
+        SetAdmin, SetFees, Swap, SwapExactAmountOut, SwapInstruction, WithdrawAllTokenTypes,
+        WithdrawSingleTokenTypeExactAmountOut,
     },
-    state::{SwapState, SwapV1, SwapVersion},
+    oracle::PriceOracle,
+    state::{SwapState, SwapV2, SwapV3, SwapVersion},
 };
 use num_traits::FromPrimitive;
 use solana_program::{
@@ -25,6 +27,7 @@ use solana_program::{
     program::invoke_signed,
     program_error::{PrintProgramError, ProgramError},
     program_option::COption,
+    program_pack::Pack,
     pubkey::Pubkey,
     sysvar::Sysvar,
 };
@@ -37,7 +40,44 @@ use spl_token_2022::{
     },
     state::{Account, Mint},
 };
-use std::{convert::TryInto, error::Error};
+use std::{convert::TryInto, error::Error, sync::Arc};
+
+/// Accounts of one pool in a routed swap
+struct RouteHopInfo<'a, 'b> {
+    swap_info: &'a AccountInfo<'b>,
+    authority_info: &'a AccountInfo<'b>,
+    swap_source_info: &'a AccountInfo<'b>,
+    swap_destination_info: &'a AccountInfo<'b>,
+    pool_mint_info: &'a AccountInfo<'b>,
+    pool_fee_account_info: &'a AccountInfo<'b>,
+    destination_token_mint_info: &'a AccountInfo<'b>,
+    destination_token_program_info: &'a AccountInfo<'b>,
+    pool_token_program_info: &'a AccountInfo<'b>,
+    host_fee_account_info: Option<&'a AccountInfo<'b>>,
+}
+
+/// Amounts calculated for one pool in a routed swap, before moving any tokens
+struct RouteHopResult {
+    token_swap: Arc<dyn SwapState>,
+    source_amount_swapped: u64,
+    pool_token_amount: u128,
+    amount_out: u64,
+    destination_mint_decimals: u8,
+}
+
+/// Amounts requested for a swap, fixing either the input or the output
+enum SwapAmounts {
+    /// Swap an exact amount of the source token
+    ExactIn {
+        amount_in: u64,
+        minimum_amount_out: u64,
+    },
+    /// Swap for an exact amount of the destination token
+    ExactOut {
+        amount_out: u64,
+        maximum_amount_in: u64,
+    },
+}
 
 /// Program state handler.
 pub struct Processor {}
@@ -258,6 +298,10 @@ impl Processor {
         let fee_account_info = next_account_info(account_info_iter)?;
         let destination_info = next_account_info(account_info_iter)?;
         let pool_token_program_info = next_account_info(account_info_iter)?;
+        let admin = next_account_info(account_info_iter)
+            .map(|admin_info| *admin_info.key)
+            .ok()
+            .into();
 
         let token_program_id = *pool_token_program_info.key;
         if SwapVersion::is_initialized(&swap_info.data.borrow()) {
@@ -334,15 +378,7 @@ impl Processor {
         }
 
         if let Some(swap_constraints) = swap_constraints {
-            let owner_key = swap_constraints
-                .owner_key
-                .parse::<Pubkey>()
-                .map_err(|_| SwapError::InvalidOwner)?;
-            if fee_account.owner != owner_key {
-                return Err(SwapError::InvalidOwner.into());
-            }
-            swap_constraints.validate_curve(&swap_curve)?;
-            swap_constraints.validate_fees(&fees)?;
+            swap_constraints.validate_pool(&fee_account.owner, &swap_curve, &fees)?;
         }
         fees.validate()?;
         swap_curve.calculator.validate()?;
@@ -359,7 +395,7 @@ impl Processor {
             to_u64(initial_amount)?,
         )?;
 
-        let obj = SwapVersion::SwapV1(SwapV1 {
+        let obj = SwapVersion::SwapV3(SwapV3 {
             is_initialized: true,
             bump_seed,
             token_program_id,
@@ -371,6 +407,9 @@ impl Processor {
             pool_fee_account: *fee_account_info.key,
             fees,
             swap_curve,
+            admin,
+            is_paused: false,
+            price_oracle: PriceOracle::new(Clock::get()?.unix_timestamp),
         });
         SwapVersion::pack(obj, &mut swap_info.data.borrow_mut())?;
         Ok(())
@@ -382,6 +421,38 @@ impl Processor {
         amount_in: u64,
         minimum_amount_out: u64,
         accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        Self::process_swap_amounts(
+            program_id,
+            SwapAmounts::ExactIn {
+                amount_in,
+                minimum_amount_out,
+            },
+            accounts,
+        )
+    }
+
+    /// Processes a [SwapExactAmountOut](enum.Instruction.html).
+    pub fn process_swap_exact_amount_out(
+        program_id: &Pubkey,
+        amount_out: u64,
+        maximum_amount_in: u64,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        Self::process_swap_amounts(
+            program_id,
+            SwapAmounts::ExactOut {
+                amount_out,
+                maximum_amount_in,
+            },
+            accounts,
+        )
+    }
+
+    fn process_swap_amounts(
+        program_id: &Pubkey,
+        amounts: SwapAmounts,
+        accounts: &[AccountInfo],
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let swap_info = next_account_info(account_info_iter)?;
@@ -403,6 +474,9 @@ impl Processor {
             return Err(ProgramError::IncorrectProgramId);
         }
         let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
+        if token_swap.is_paused() {
+            return Err(SwapError::SwapPaused.into());
+        }
 
         if *authority_info.key
             != Self::authority_id(program_id, swap_info.key, token_swap.bump_seed())?
@@ -444,42 +518,98 @@ impl Processor {
             Self::unpack_token_account(swap_destination_info, token_swap.token_program_id())?;
         let pool_mint = Self::unpack_mint(pool_mint_info, token_swap.token_program_id())?;
 
-        // Take transfer fees into account for actual amount transferred in
-        let actual_amount_in = {
-            let source_mint_data = source_token_mint_info.data.borrow();
-            let source_mint = Self::unpack_mint_with_extensions(
-                &source_mint_data,
-                source_token_mint_info.owner,
-                token_swap.token_program_id(),
-            )?;
-
-            if let Ok(transfer_fee_config) = source_mint.get_extension::<TransferFeeConfig>() {
-                amount_in.saturating_sub(
-                    transfer_fee_config
-                        .calculate_epoch_fee(Clock::get()?.epoch, amount_in)
-                        .ok_or(SwapError::FeeCalculationFailure)?,
-                )
-            } else {
-                amount_in
-            }
-        };
-
-        // Calculate the trade amounts
         let trade_direction = if *swap_source_info.key == *token_swap.token_a_account() {
             TradeDirection::AtoB
         } else {
             TradeDirection::BtoA
         };
-        let result = token_swap
-            .swap_curve()
-            .swap(
-                to_u128(actual_amount_in)?,
-                to_u128(source_account.amount)?,
-                to_u128(dest_account.amount)?,
-                trade_direction,
-                token_swap.fees(),
-            )
-            .ok_or(SwapError::ZeroTradingTokens)?;
+        match trade_direction {
+            TradeDirection::AtoB => Self::update_price_oracle(
+                swap_info,
+                token_swap.as_ref(),
+                source_account.amount,
+                dest_account.amount,
+            )?,
+            TradeDirection::BtoA => Self::update_price_oracle(
+                swap_info,
+                token_swap.as_ref(),
+                dest_account.amount,
+                source_account.amount,
+            )?,
+        }
+
+        // Calculate the trade amounts
+        let result = match amounts {
+            SwapAmounts::ExactIn { amount_in, .. } => {
+                // Take transfer fees into account for actual amount transferred in
+                let actual_amount_in = {
+                    let source_mint_data = source_token_mint_info.data.borrow();
+                    let source_mint = Self::unpack_mint_with_extensions(
+                        &source_mint_data,
+                        source_token_mint_info.owner,
+                        token_swap.token_program_id(),
+                    )?;
+
+                    if let Ok(transfer_fee_config) =
+                        source_mint.get_extension::<TransferFeeConfig>()
+                    {
+                        amount_in.saturating_sub(
+                            transfer_fee_config
+                                .calculate_epoch_fee(Clock::get()?.epoch, amount_in)
+                                .ok_or(SwapError::FeeCalculationFailure)?,
+                        )
+                    } else {
+                        amount_in
+                    }
+                };
+
+                token_swap
+                    .swap_curve()
+                    .swap(
+                        to_u128(actual_amount_in)?,
+                        to_u128(source_account.amount)?,
+                        to_u128(dest_account.amount)?,
+                        trade_direction,
+                        token_swap.fees(),
+                    )
+                    .ok_or(SwapError::ZeroTradingTokens)?
+            }
+            SwapAmounts::ExactOut { amount_out, .. } => {
+                // Take transfer fees into account for the amount to send out,
+                // so that the user receives exactly the requested amount
+                let actual_amount_out = {
+                    let destination_mint_data = destination_token_mint_info.data.borrow();
+                    let destination_mint = Self::unpack_mint_with_extensions(
+                        &destination_mint_data,
+                        destination_token_mint_info.owner,
+                        token_swap.token_program_id(),
+                    )?;
+
+                    if let Ok(transfer_fee_config) =
+                        destination_mint.get_extension::<TransferFeeConfig>()
+                    {
+                        amount_out.saturating_add(
+                            transfer_fee_config
+                                .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount_out)
+                                .ok_or(SwapError::FeeCalculationFailure)?,
+                        )
+                    } else {
+                        amount_out
+                    }
+                };
+
+                token_swap
+                    .swap_curve()
+                    .swap_exact_amount_out(
+                        to_u128(actual_amount_out)?,
+                        to_u128(source_account.amount)?,
+                        to_u128(dest_account.amount)?,
+                        trade_direction,
+                        token_swap.fees(),
+                    )
+                    .ok_or(SwapError::ZeroTradingTokens)?
+            }
+        };
 
         // Re-calculate the source amount swapped based on what the curve says
         let (source_transfer_amount, source_mint_decimals) = {
@@ -503,6 +633,14 @@ impl Processor {
                 };
             (amount, source_mint.base.decimals)
         };
+        if let SwapAmounts::ExactOut {
+            maximum_amount_in, ..
+        } = amounts
+        {
+            if source_transfer_amount > maximum_amount_in {
+                return Err(SwapError::ExceededSlippage.into());
+            }
+        }
 
         let (destination_transfer_amount, destination_mint_decimals) = {
             let destination_mint_data = destination_token_mint_info.data.borrow();
@@ -523,8 +661,13 @@ impl Processor {
             } else {
                 amount_out
             };
-            if amount_received < minimum_amount_out {
-                return Err(SwapError::ExceededSlippage.into());
+            if let SwapAmounts::ExactIn {
+                minimum_amount_out, ..
+            } = amounts
+            {
+                if amount_received < minimum_amount_out {
+                    return Err(SwapError::ExceededSlippage.into());
+                }
             }
             (amount_out, destination_mint.base.decimals)
         };
@@ -624,6 +767,327 @@ impl Processor {
         Ok(())
     }
 
+    /// Processes a [RouteSwap](enum.Instruction.html).
+    pub fn process_route_swap(
+        program_id: &Pubkey,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        hops: &[RouteHop],
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let user_transfer_authority_info = next_account_info(account_info_iter)?;
+        let source_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let source_token_mint_info = next_account_info(account_info_iter)?;
+        let source_token_program_info = next_account_info(account_info_iter)?;
+        let mut hop_infos = Vec::with_capacity(hops.len());
+        for hop in hops {
+            hop_infos.push(RouteHopInfo {
+                swap_info: next_account_info(account_info_iter)?,
+                authority_info: next_account_info(account_info_iter)?,
+                swap_source_info: next_account_info(account_info_iter)?,
+                swap_destination_info: next_account_info(account_info_iter)?,
+                pool_mint_info: next_account_info(account_info_iter)?,
+                pool_fee_account_info: next_account_info(account_info_iter)?,
+                destination_token_mint_info: next_account_info(account_info_iter)?,
+                destination_token_program_info: next_account_info(account_info_iter)?,
+                pool_token_program_info: next_account_info(account_info_iter)?,
+                host_fee_account_info: if hop.has_host_fee_account {
+                    Some(next_account_info(account_info_iter)?)
+                } else {
+                    None
+                },
+            });
+        }
+        let first_hop = hop_infos.first().ok_or(SwapError::InvalidInstruction)?;
+        let last_hop = hop_infos.last().ok_or(SwapError::InvalidInstruction)?;
+        if first_hop.swap_source_info.key == source_info.key {
+            return Err(SwapError::InvalidInput.into());
+        }
+        if last_hop.swap_destination_info.key == destination_info.key {
+            return Err(SwapError::InvalidInput.into());
+        }
+
+        // Take transfer fees into account for actual amount transferred in
+        let mut hop_amount_in = {
+            let source_mint_data = source_token_mint_info.data.borrow();
+            let source_mint = Self::unpack_mint_with_extensions(
+                &source_mint_data,
+                source_token_mint_info.owner,
+                source_token_program_info.key,
+            )?;
+
+            if let Ok(transfer_fee_config) = source_mint.get_extension::<TransferFeeConfig>() {
+                amount_in.saturating_sub(
+                    transfer_fee_config
+                        .calculate_epoch_fee(Clock::get()?.epoch, amount_in)
+                        .ok_or(SwapError::FeeCalculationFailure)?,
+                )
+            } else {
+                amount_in
+            }
+        };
+
+        // Calculate the trades of all pools before moving any tokens, so that
+        // every pool is priced against its balances before the route
+        let mut hop_results: Vec<RouteHopResult> = Vec::with_capacity(hop_infos.len());
+        for (i, hop_info) in hop_infos.iter().enumerate() {
+            if hop_info.swap_info.owner != program_id {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            if hop_infos[..i]
+                .iter()
+                .any(|previous| previous.swap_info.key == hop_info.swap_info.key)
+            {
+                return Err(SwapError::InvalidInput.into());
+            }
+            let token_swap = SwapVersion::unpack(&hop_info.swap_info.data.borrow())?;
+            if token_swap.is_paused() {
+                return Err(SwapError::SwapPaused.into());
+            }
+            if *hop_info.authority_info.key
+                != Self::authority_id(program_id, hop_info.swap_info.key, token_swap.bump_seed())?
+            {
+                return Err(SwapError::InvalidProgramAddress.into());
+            }
+            if !(*hop_info.swap_source_info.key == *token_swap.token_a_account()
+                || *hop_info.swap_source_info.key == *token_swap.token_b_account())
+            {
+                return Err(SwapError::IncorrectSwapAccount.into());
+            }
+            if !(*hop_info.swap_destination_info.key == *token_swap.token_a_account()
+                || *hop_info.swap_destination_info.key == *token_swap.token_b_account())
+            {
+                return Err(SwapError::IncorrectSwapAccount.into());
+            }
+            if *hop_info.swap_source_info.key == *hop_info.swap_destination_info.key {
+                return Err(SwapError::InvalidInput.into());
+            }
+            if *hop_info.pool_mint_info.key != *token_swap.pool_mint() {
+                return Err(SwapError::IncorrectPoolMint.into());
+            }
+            if *hop_info.pool_fee_account_info.key != *token_swap.pool_fee_account() {
+                return Err(SwapError::IncorrectFeeAccount.into());
+            }
+            if *hop_info.pool_token_program_info.key != *token_swap.token_program_id() {
+                return Err(SwapError::IncorrectTokenProgramId.into());
+            }
+
+            let source_account = Self::unpack_token_account(
+                hop_info.swap_source_info,
+                token_swap.token_program_id(),
+            )?;
+            let dest_account = Self::unpack_token_account(
+                hop_info.swap_destination_info,
+                token_swap.token_program_id(),
+            )?;
+            let pool_mint =
+                Self::unpack_mint(hop_info.pool_mint_info, token_swap.token_program_id())?;
+            if i > 0 && source_account.mint != *hop_infos[i - 1].destination_token_mint_info.key {
+                return Err(SwapError::IncorrectSwapAccount.into());
+            }
+
+            let trade_direction = if *hop_info.swap_source_info.key == *token_swap.token_a_account()
+            {
+                TradeDirection::AtoB
+            } else {
+                TradeDirection::BtoA
+            };
+            match trade_direction {
+                TradeDirection::AtoB => Self::update_price_oracle(
+                    hop_info.swap_info,
+                    token_swap.as_ref(),
+                    source_account.amount,
+                    dest_account.amount,
+                )?,
+                TradeDirection::BtoA => Self::update_price_oracle(
+                    hop_info.swap_info,
+                    token_swap.as_ref(),
+                    dest_account.amount,
+                    source_account.amount,
+                )?,
+            }
+            let result = token_swap
+                .swap_curve()
+                .swap(
+                    to_u128(hop_amount_in)?,
+                    to_u128(source_account.amount)?,
+                    to_u128(dest_account.amount)?,
+                    trade_direction,
+                    token_swap.fees(),
+                )
+                .ok_or(SwapError::ZeroTradingTokens)?;
+            // The previous pool sends all of its output, so any part the curve
+            // rounds away would be stranded in this pool
+            if i > 0 && result.source_amount_swapped != to_u128(hop_amount_in)? {
+                return Err(SwapError::PartialRouteSwap.into());
+            }
+
+            let (amount_out, amount_received, destination_mint_decimals) = {
+                let destination_mint_data = hop_info.destination_token_mint_info.data.borrow();
+                let destination_mint = Self::unpack_mint_with_extensions(
+                    &destination_mint_data,
+                    hop_info.destination_token_mint_info.owner,
+                    hop_info.destination_token_program_info.key,
+                )?;
+                let amount_out = to_u64(result.destination_amount_swapped)?;
+                let amount_received = if let Ok(transfer_fee_config) =
+                    destination_mint.get_extension::<TransferFeeConfig>()
+                {
+                    amount_out.saturating_sub(
+                        transfer_fee_config
+                            .calculate_epoch_fee(Clock::get()?.epoch, amount_out)
+                            .ok_or(SwapError::FeeCalculationFailure)?,
+                    )
+                } else {
+                    amount_out
+                };
+                (amount_out, amount_received, destination_mint.base.decimals)
+            };
+
+            let (swap_token_a_amount, swap_token_b_amount) = match trade_direction {
+                TradeDirection::AtoB => (
+                    result.new_swap_source_amount,
+                    result.new_swap_destination_amount,
+                ),
+                TradeDirection::BtoA => (
+                    result.new_swap_destination_amount,
+                    result.new_swap_source_amount,
+                ),
+            };
+            let pool_token_amount = token_swap
+                .swap_curve()
+                .withdraw_single_token_type_exact_out(
+                    result.owner_fee,
+                    swap_token_a_amount,
+                    swap_token_b_amount,
+                    to_u128(pool_mint.supply)?,
+                    trade_direction,
+                    token_swap.fees(),
+                )
+                .ok_or(SwapError::FeeCalculationFailure)?;
+
+            hop_amount_in = amount_received;
+            hop_results.push(RouteHopResult {
+                token_swap,
+                source_amount_swapped: to_u64(result.source_amount_swapped)?,
+                pool_token_amount,
+                amount_out,
+                destination_mint_decimals,
+            });
+        }
+        if hop_amount_in < minimum_amount_out {
+            return Err(SwapError::ExceededSlippage.into());
+        }
+
+        // Re-calculate the source amount swapped based on what the curve says.
+        // Only the first pool takes its input from the user, the others take
+        // all of the output of the previous pool.
+        let (source_transfer_amount, source_mint_decimals) = {
+            let source_amount_swapped = hop_results[0].source_amount_swapped;
+
+            let source_mint_data = source_token_mint_info.data.borrow();
+            let source_mint = Self::unpack_mint_with_extensions(
+                &source_mint_data,
+                source_token_mint_info.owner,
+                source_token_program_info.key,
+            )?;
+            let amount =
+                if let Ok(transfer_fee_config) = source_mint.get_extension::<TransferFeeConfig>() {
+                    source_amount_swapped.saturating_add(
+                        transfer_fee_config
+                            .calculate_inverse_epoch_fee(Clock::get()?.epoch, source_amount_swapped)
+                            .ok_or(SwapError::FeeCalculationFailure)?,
+                    )
+                } else {
+                    source_amount_swapped
+                };
+            (amount, source_mint.base.decimals)
+        };
+
+        let first_swap = &hop_results[0].token_swap;
+        Self::token_transfer(
+            first_hop.swap_info.key,
+            source_token_program_info.clone(),
+            source_info.clone(),
+            source_token_mint_info.clone(),
+            first_hop.swap_source_info.clone(),
+            user_transfer_authority_info.clone(),
+            first_swap.bump_seed(),
+            source_transfer_amount,
+            source_mint_decimals,
+        )?;
+
+        for (i, (hop_info, hop_result)) in hop_infos.iter().zip(hop_results.iter()).enumerate() {
+            let token_swap = &hop_result.token_swap;
+            let mut pool_token_amount = hop_result.pool_token_amount;
+            if pool_token_amount > 0 {
+                if let Some(host_fee_account_info) = hop_info.host_fee_account_info {
+                    let host_fee_account = Self::unpack_token_account(
+                        host_fee_account_info,
+                        token_swap.token_program_id(),
+                    )?;
+                    if *hop_info.pool_mint_info.key != host_fee_account.mint {
+                        return Err(SwapError::IncorrectPoolMint.into());
+                    }
+                    let host_fee = token_swap
+                        .fees()
+                        .host_fee(pool_token_amount)
+                        .ok_or(SwapError::FeeCalculationFailure)?;
+                    if host_fee > 0 {
+                        pool_token_amount = pool_token_amount
+                            .checked_sub(host_fee)
+                            .ok_or(SwapError::FeeCalculationFailure)?;
+                        Self::token_mint_to(
+                            hop_info.swap_info.key,
+                            hop_info.pool_token_program_info.clone(),
+                            hop_info.pool_mint_info.clone(),
+                            host_fee_account_info.clone(),
+                            hop_info.authority_info.clone(),
+                            token_swap.bump_seed(),
+                            to_u64(host_fee)?,
+                        )?;
+                    }
+                }
+                if token_swap
+                    .check_pool_fee_info(hop_info.pool_fee_account_info)
+                    .is_ok()
+                {
+                    Self::token_mint_to(
+                        hop_info.swap_info.key,
+                        hop_info.pool_token_program_info.clone(),
+                        hop_info.pool_mint_info.clone(),
+                        hop_info.pool_fee_account_info.clone(),
+                        hop_info.authority_info.clone(),
+                        token_swap.bump_seed(),
+                        to_u64(pool_token_amount)?,
+                    )?;
+                }
+            }
+
+            // The output goes straight into the next pool, or to the user at
+            // the end of the route
+            let destination_info = hop_infos
+                .get(i + 1)
+                .map(|next_hop_info| next_hop_info.swap_source_info)
+                .unwrap_or(destination_info);
+            Self::token_transfer(
+                hop_info.swap_info.key,
+                hop_info.destination_token_program_info.clone(),
+                hop_info.swap_destination_info.clone(),
+                hop_info.destination_token_mint_info.clone(),
+                destination_info.clone(),
+                hop_info.authority_info.clone(),
+                token_swap.bump_seed(),
+                hop_result.amount_out,
+                hop_result.destination_mint_decimals,
+            )?;
+        }
+
+        Ok(())
+    }
+
     /// Processes an [DepositAllTokenTypes](enum.Instruction.html).
     pub fn process_deposit_all_token_types(
         program_id: &Pubkey,
@@ -649,6 +1113,9 @@ impl Processor {
         let pool_token_program_info = next_account_info(account_info_iter)?;
 
         let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
+        if token_swap.is_paused() {
+            return Err(SwapError::SwapPaused.into());
+        }
         let calculator = &token_swap.swap_curve().calculator;
         if !calculator.allows_deposits() {
             return Err(SwapError::UnsupportedCurveOperation.into());
@@ -669,6 +1136,12 @@ impl Processor {
 
         let token_a = Self::unpack_token_account(token_a_info, token_swap.token_program_id())?;
         let token_b = Self::unpack_token_account(token_b_info, token_swap.token_program_id())?;
+        Self::update_price_oracle(
+            swap_info,
+            token_swap.as_ref(),
+            token_a.amount,
+            token_b.amount,
+        )?;
         let pool_mint = Self::unpack_mint(pool_mint_info, token_swap.token_program_id())?;
         let current_pool_mint_supply = to_u128(pool_mint.supply)?;
         let (pool_token_amount, pool_mint_supply) = if current_pool_mint_supply > 0 {
@@ -780,6 +1253,12 @@ impl Processor {
 
         let token_a = Self::unpack_token_account(token_a_info, token_swap.token_program_id())?;
         let token_b = Self::unpack_token_account(token_b_info, token_swap.token_program_id())?;
+        Self::update_price_oracle(
+            swap_info,
+            token_swap.as_ref(),
+            token_a.amount,
+            token_b.amount,
+        )?;
         let pool_mint = Self::unpack_mint(pool_mint_info, token_swap.token_program_id())?;
 
         let calculator = &token_swap.swap_curve().calculator;
@@ -901,6 +1380,9 @@ impl Processor {
         let pool_token_program_info = next_account_info(account_info_iter)?;
 
         let token_swap = SwapVersion::unpack(&swap_info.data.borrow())?;
+        if token_swap.is_paused() {
+            return Err(SwapError::SwapPaused.into());
+        }
         let calculator = &token_swap.swap_curve().calculator;
         if !calculator.allows_deposits() {
             return Err(SwapError::UnsupportedCurveOperation.into());
@@ -938,6 +1420,12 @@ impl Processor {
             source_b_info,
             None,
         )?;
+        Self::update_price_oracle(
+            swap_info,
+            token_swap.as_ref(),
+            swap_token_a.amount,
+            swap_token_b.amount,
+        )?;
 
         let pool_mint = Self::unpack_mint(pool_mint_info, token_swap.token_program_id())?;
         let pool_mint_supply = to_u128(pool_mint.supply)?;
@@ -1062,6 +1550,12 @@ impl Processor {
             destination_b_info,
             Some(pool_fee_account_info),
         )?;
+        Self::update_price_oracle(
+            swap_info,
+            token_swap.as_ref(),
+            swap_token_a.amount,
+            swap_token_b.amount,
+        )?;
 
         let pool_mint = Self::unpack_mint(pool_mint_info, token_swap.token_program_id())?;
         let pool_mint_supply = to_u128(pool_mint.supply)?;
@@ -1162,16 +1656,130 @@ impl Processor {
         Ok(())
     }
 
-    /// Processes an [Instruction](enum.Instruction.html).
-    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
-        Self::process_with_constraints(program_id, accounts, input, &SWAP_CONSTRAINTS)
+    /// Unpacks a swap to be updated by its admin authority, checking that the
+    /// admin signed the transaction
+    fn unpack_swap_for_admin(
+        program_id: &Pubkey,
+        swap_info: &AccountInfo,
+        admin_info: &AccountInfo,
+    ) -> Result<SwapV2, ProgramError> {
+        if swap_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let data = swap_info.data.borrow();
+        let token_swap = SwapVersion::unpack(&data)?;
+        // only SwapV2 and later versions have an admin, and all of their
+        // layouts start with a SwapV2
+        match token_swap.admin() {
+            COption::Some(admin) if admin == *admin_info.key => {}
+            COption::Some(_) => return Err(SwapError::InvalidAdmin.into()),
+            COption::None => return Err(SwapError::NoAdmin.into()),
+        }
+        if !admin_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        SwapV2::unpack(&data[1..1 + SwapV2::LEN])
     }
 
-    /// Processes an instruction given extra constraint
-    pub fn process_with_constraints(
-        program_id: &Pubkey,
-        accounts: &[AccountInfo],
-        input: &[u8],
+    /// Packs a swap updated by its admin authority, keeping any later fields
+    /// of its version
+    fn pack_swap_for_admin(token_swap: SwapV2, swap_info: &AccountInfo) -> ProgramResult {
+        SwapV2::pack(
+            token_swap,
+            &mut swap_info.data.borrow_mut()[1..1 + SwapV2::LEN],
+        )
+    }
+
+    /// Accumulates the prices of a swap with a price oracle until now, using
+    /// its balances before they change
+    fn update_price_oracle(
+        swap_info: &AccountInfo,
+        token_swap: &dyn SwapState,
+        swap_token_a_amount: u64,
+        swap_token_b_amount: u64,
+    ) -> ProgramResult {
+        if let Some(price_oracle) = token_swap.price_oracle() {
+            // Skipping the update would let the next one count the whole time
+            // since the last update at the price after this instruction
+            if !swap_info.is_writable {
+                return Err(SwapError::ReadonlySwapAccount.into());
+            }
+            let price_oracle = price_oracle.accumulate(
+                token_swap.swap_curve(),
+                to_u128(swap_token_a_amount)?,
+                to_u128(swap_token_b_amount)?,
+                Clock::get()?.unix_timestamp,
+            );
+            let mut data = swap_info.data.borrow_mut();
+            let mut token_swap = SwapV3::unpack(&data[1..])?;
+            token_swap.price_oracle = price_oracle;
+            SwapV3::pack(token_swap, &mut data[1..])?;
+        }
+        Ok(())
+    }
+
+    /// Processes a [SetFees](enum.Instruction.html).
+    pub fn process_set_fees(
+        program_id: &Pubkey,
+        fees: Fees,
+        accounts: &[AccountInfo],
+        swap_constraints: &Option<SwapConstraints>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let admin_info = next_account_info(account_info_iter)?;
+
+        let mut token_swap = Self::unpack_swap_for_admin(program_id, swap_info, admin_info)?;
+        if let Some(swap_constraints) = swap_constraints {
+            swap_constraints.validate_fees(&fees)?;
+        }
+        fees.validate()?;
+
+        token_swap.fees = fees;
+        Self::pack_swap_for_admin(token_swap, swap_info)
+    }
+
+    /// Processes a [SetAdmin](enum.Instruction.html).
+    pub fn process_set_admin(
+        program_id: &Pubkey,
+        new_admin: COption<Pubkey>,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let admin_info = next_account_info(account_info_iter)?;
+
+        let mut token_swap = Self::unpack_swap_for_admin(program_id, swap_info, admin_info)?;
+        token_swap.admin = new_admin;
+        Self::pack_swap_for_admin(token_swap, swap_info)
+    }
+
+    /// Processes a [Pause](enum.Instruction.html) or
+    /// [Resume](enum.Instruction.html).
+    pub fn process_set_paused(
+        program_id: &Pubkey,
+        is_paused: bool,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let swap_info = next_account_info(account_info_iter)?;
+        let admin_info = next_account_info(account_info_iter)?;
+
+        let mut token_swap = Self::unpack_swap_for_admin(program_id, swap_info, admin_info)?;
+        token_swap.is_paused = is_paused;
+        Self::pack_swap_for_admin(token_swap, swap_info)
+    }
+
+    /// Processes an [Instruction](enum.Instruction.html).
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+        Self::process_with_constraints(program_id, accounts, input, &SWAP_CONSTRAINTS)
+    }
+
+    /// Processes an instruction given extra constraint
+    pub fn process_with_constraints(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        input: &[u8],
         swap_constraints: &Option<SwapConstraints>,
     ) -> ProgramResult {
         let instruction = SwapInstruction::unpack(input)?;
@@ -1243,6 +1851,42 @@ impl Processor {
                     accounts,
                 )
             }
+            SwapInstruction::RouteSwap(RouteSwap {
+                amount_in,
+                minimum_amount_out,
+                hops,
+            }) => {
+                msg!("Instruction: RouteSwap");
+                Self::process_route_swap(program_id, amount_in, minimum_amount_out, &hops, accounts)
+            }
+            SwapInstruction::SwapExactAmountOut(SwapExactAmountOut {
+                amount_out,
+                maximum_amount_in,
+            }) => {
+                msg!("Instruction: SwapExactAmountOut");
+                Self::process_swap_exact_amount_out(
+                    program_id,
+                    amount_out,
+                    maximum_amount_in,
+                    accounts,
+                )
+            }
+            SwapInstruction::SetFees(SetFees { fees }) => {
+                msg!("Instruction: SetFees");
+                Self::process_set_fees(program_id, fees, accounts, swap_constraints)
+            }
+            SwapInstruction::SetAdmin(SetAdmin { new_admin }) => {
+                msg!("Instruction: SetAdmin");
+                Self::process_set_admin(program_id, new_admin, accounts)
+            }
+            SwapInstruction::Pause => {
+                msg!("Instruction: Pause");
+                Self::process_set_paused(program_id, true, accounts)
+            }
+            SwapInstruction::Resume => {
+                msg!("Instruction: Resume");
+                Self::process_set_paused(program_id, false, accounts)
+            }
         }
     }
 }
@@ -1276,20 +1920,28 @@ mod tests {
         curve::calculator::{CurveCalculator, INITIAL_SWAP_POOL_AMOUNT},
         curve::{
             base::CurveType, constant_price::ConstantPriceCurve,
-            constant_product::ConstantProductCurve, offset::OffsetCurve,
+            constant_product::ConstantProductCurve, offset::OffsetCurve, stable::StableCurve,
         },
         instruction::{
-            deposit_all_token_types, deposit_single_token_type_exact_amount_in, initialize, swap,
+            deposit_all_token_types, deposit_single_token_type_exact_amount_in, initialize, pause,
+            resume, route_swap, set_admin, set_fees, swap, swap_exact_amount_out,
             withdraw_all_token_types, withdraw_single_token_type_exact_amount_out,
+            RouteHopAccounts,
         },
+        state::SwapV1,
     };
     use solana_program::{
-        clock::Clock, entrypoint::SUCCESS, instruction::Instruction, program_pack::Pack,
-        program_stubs, rent::Rent,
+        clock::{Clock, UnixTimestamp},
+        entrypoint::SUCCESS,
+        instruction::Instruction,
+        program_pack::Pack,
+        program_stubs,
+        rent::Rent,
     };
     use solana_sdk::account::{
         create_account_for_test, create_is_signer_account_infos, Account as SolanaAccount,
     };
+    use spl_math::precise_number::PreciseNumber;
     use spl_token_2022::{
         error::TokenError,
         extension::{
@@ -1362,13 +2014,26 @@ mod tests {
         }
 
         fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            let clock = Clock {
+                unix_timestamp: TEST_UNIX_TIMESTAMP.with(|timestamp| timestamp.get()),
+                ..Clock::default()
+            };
             unsafe {
-                *(var_addr as *mut _ as *mut Clock) = Clock::default();
+                *(var_addr as *mut _ as *mut Clock) = clock;
             }
             SUCCESS
         }
     }
 
+    thread_local! {
+        // tests run in parallel, so each one keeps its own clock
+        static TEST_UNIX_TIMESTAMP: std::cell::Cell<UnixTimestamp> = std::cell::Cell::new(0);
+    }
+
+    fn set_test_unix_timestamp(unix_timestamp: UnixTimestamp) {
+        TEST_UNIX_TIMESTAMP.with(|timestamp| timestamp.set(unix_timestamp));
+    }
+
     fn test_syscall_stubs() {
         use std::sync::Once;
         static ONCE: Once = Once::new();
@@ -1393,6 +2058,7 @@ mod tests {
         swap_curve: SwapCurve,
         swap_key: Pubkey,
         swap_account: SolanaAccount,
+        admin_key: Pubkey,
         pool_mint_key: Pubkey,
         pool_mint_account: SolanaAccount,
         pool_fee_key: Pubkey,
@@ -1492,6 +2158,7 @@ mod tests {
                 swap_curve,
                 swap_key,
                 swap_account,
+                admin_key: Pubkey::new_unique(),
                 pool_mint_key,
                 pool_mint_account,
                 pool_fee_key,
@@ -1524,6 +2191,7 @@ mod tests {
                     &self.pool_mint_key,
                     &self.pool_fee_key,
                     &self.pool_token_key,
+                    Some(&self.admin_key),
                     self.fees.clone(),
                     self.swap_curve.clone(),
                 )
@@ -1537,6 +2205,7 @@ mod tests {
                     &mut self.pool_fee_account,
                     &mut self.pool_token_account,
                     &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
                 ],
             )
         }
@@ -1731,6 +2400,96 @@ mod tests {
             Ok(())
         }
 
+        #[allow(clippy::too_many_arguments)]
+        pub fn swap_exact_amount_out(
+            &mut self,
+            user_key: &Pubkey,
+            user_source_key: &Pubkey,
+            user_source_account: &mut SolanaAccount,
+            swap_source_key: &Pubkey,
+            swap_destination_key: &Pubkey,
+            user_destination_key: &Pubkey,
+            user_destination_account: &mut SolanaAccount,
+            amount_out: u64,
+            maximum_amount_in: u64,
+        ) -> ProgramResult {
+            let user_transfer_key = Pubkey::new_unique();
+            let source_token_program_id = self.get_token_program_id(swap_source_key);
+            let destination_token_program_id = self.get_token_program_id(swap_destination_key);
+            // approve moving at most the maximum from user source account
+            do_process_instruction(
+                approve(
+                    source_token_program_id,
+                    user_source_key,
+                    &user_transfer_key,
+                    user_key,
+                    &[],
+                    maximum_amount_in,
+                )
+                .unwrap(),
+                vec![
+                    user_source_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                ],
+            )
+            .unwrap();
+
+            let (source_mint_key, mut source_mint_account) = self.get_token_mint(swap_source_key);
+            let (destination_mint_key, mut destination_mint_account) =
+                self.get_token_mint(swap_destination_key);
+            let mut swap_source_account = self.get_token_account(swap_source_key).clone();
+            let mut swap_destination_account = self.get_token_account(swap_destination_key).clone();
+
+            // perform the swap
+            do_process_instruction(
+                swap_exact_amount_out(
+                    &SWAP_PROGRAM_ID,
+                    source_token_program_id,
+                    destination_token_program_id,
+                    &self.pool_token_program_id,
+                    &self.swap_key,
+                    &self.authority_key,
+                    &user_transfer_key,
+                    user_source_key,
+                    swap_source_key,
+                    swap_destination_key,
+                    user_destination_key,
+                    &self.pool_mint_key,
+                    &self.pool_fee_key,
+                    &source_mint_key,
+                    &destination_mint_key,
+                    None,
+                    SwapExactAmountOut {
+                        amount_out,
+                        maximum_amount_in,
+                    },
+                )
+                .unwrap(),
+                vec![
+                    &mut self.swap_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    user_source_account,
+                    &mut swap_source_account,
+                    &mut swap_destination_account,
+                    user_destination_account,
+                    &mut self.pool_mint_account,
+                    &mut self.pool_fee_account,
+                    &mut source_mint_account,
+                    &mut destination_mint_account,
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                    &mut SolanaAccount::default(),
+                ],
+            )?;
+
+            self.set_token_account(swap_source_key, swap_source_account);
+            self.set_token_account(swap_destination_key, swap_destination_account);
+
+            Ok(())
+        }
+
         #[allow(clippy::too_many_arguments)]
         pub fn deposit_all_token_types(
             &mut self,
@@ -2907,6 +3666,7 @@ mod tests {
                         &accounts.pool_mint_key,
                         &accounts.pool_fee_key,
                         &accounts.pool_token_key,
+                        None,
                         accounts.fees.clone(),
                         accounts.swap_curve.clone(),
                     )
@@ -3077,6 +3837,71 @@ mod tests {
             accounts.initialize_swap().unwrap();
         }
 
+        // create invalid stable swap
+        {
+            let amp = 0;
+            let fees = Fees {
+                trade_fee_numerator,
+                trade_fee_denominator,
+                owner_trade_fee_numerator,
+                owner_trade_fee_denominator,
+                owner_withdraw_fee_numerator,
+                owner_withdraw_fee_denominator,
+                host_fee_numerator,
+                host_fee_denominator,
+            };
+            let swap_curve = SwapCurve {
+                curve_type: CurveType::Stable,
+                calculator: Arc::new(StableCurve { amp }),
+            };
+            let mut accounts = SwapAccountInfo::new(
+                &user_key,
+                fees,
+                SwapTransferFees::default(),
+                swap_curve,
+                token_a_amount,
+                token_b_amount,
+                &pool_token_program_id,
+                &token_a_program_id,
+                &token_b_program_id,
+            );
+            assert_eq!(
+                Err(SwapError::InvalidCurve.into()),
+                accounts.initialize_swap()
+            );
+        }
+
+        // create valid stable swap
+        {
+            let amp = 100;
+            let fees = Fees {
+                trade_fee_numerator,
+                trade_fee_denominator,
+                owner_trade_fee_numerator,
+                owner_trade_fee_denominator,
+                owner_withdraw_fee_numerator,
+                owner_withdraw_fee_denominator,
+                host_fee_numerator,
+                host_fee_denominator,
+            };
+            let swap_curve = SwapCurve {
+                curve_type: CurveType::Stable,
+                calculator: Arc::new(StableCurve { amp }),
+            };
+            let mut accounts = SwapAccountInfo::new(
+                &user_key,
+                fees,
+                SwapTransferFees::default(),
+                swap_curve,
+                token_a_amount,
+                token_b_amount,
+                &pool_token_program_id,
+                &token_a_program_id,
+                &token_b_program_id,
+            );
+            accounts.initialize_swap().unwrap();
+        }
+
         // wrong owner key in constraint
         {
             let new_key = Pubkey::new_unique();
@@ -3132,6 +3957,7 @@ mod tests {
                         &accounts.pool_mint_key,
                         &accounts.pool_fee_key,
                         &accounts.pool_token_key,
+                        None,
                         accounts.fees.clone(),
                         accounts.swap_curve.clone(),
                     )
@@ -3207,6 +4033,7 @@ mod tests {
                         &accounts.pool_mint_key,
                         &accounts.pool_fee_key,
                         &accounts.pool_token_key,
+                        None,
                         accounts.fees.clone(),
                         accounts.swap_curve.clone(),
                     )
@@ -3278,6 +4105,7 @@ mod tests {
                     &accounts.pool_mint_key,
                     &accounts.pool_fee_key,
                     &accounts.pool_token_key,
+                    None,
                     accounts.fees,
                     accounts.swap_curve.clone(),
                 )
@@ -3317,6 +4145,9 @@ mod tests {
         assert_eq!(*swap_state.token_a_mint(), accounts.token_a_mint_key);
         assert_eq!(*swap_state.token_b_mint(), accounts.token_b_mint_key);
         assert_eq!(*swap_state.pool_fee_account(), accounts.pool_fee_key);
+        assert_eq!(swap_state.admin(), COption::Some(accounts.admin_key));
+        assert!(!swap_state.is_paused());
+        assert_eq!(swap_state.price_oracle(), Some(&PriceOracle::new(0)));
         let token_a =
             StateWithExtensions::<Account>::unpack(&accounts.token_a_account.data).unwrap();
         assert_eq!(token_a.base.amount, token_a_amount);
@@ -6343,7 +7174,7 @@ mod tests {
         );
         let token_b_offset = 10_000_000_000;
         check_valid_swap_curve(
-            fees,
+            fees.clone(),
             SwapTransferFees::default(),
             CurveType::Offset,
             Arc::new(OffsetCurve { token_b_offset }),
@@ -6353,6 +7184,18 @@ mod tests {
             &token_a_program_id,
             &token_b_program_id,
         );
+        let amp = 100;
+        check_valid_swap_curve(
+            fees,
+            SwapTransferFees::default(),
+            CurveType::Stable,
+            Arc::new(StableCurve { amp }),
+            token_a_amount,
+            token_b_amount,
+            &pool_token_program_id,
+            &token_a_program_id,
+            &token_b_program_id,
+        );
     }
 
     #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
@@ -6411,7 +7254,7 @@ mod tests {
         );
         let token_b_offset = 1;
         check_valid_swap_curve(
-            fees,
+            fees.clone(),
             SwapTransferFees::default(),
             CurveType::Offset,
             Arc::new(OffsetCurve { token_b_offset }),
@@ -6421,6 +7264,18 @@ mod tests {
             &token_a_program_id,
             &token_b_program_id,
         );
+        let amp = 1;
+        check_valid_swap_curve(
+            fees,
+            SwapTransferFees::default(),
+            CurveType::Stable,
+            Arc::new(StableCurve { amp }),
+            token_a_amount,
+            token_b_amount,
+            &pool_token_program_id,
+            &token_a_program_id,
+            &token_b_program_id,
+        );
     }
 
     #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
@@ -6494,6 +7349,7 @@ mod tests {
                 &accounts.pool_mint_key,
                 &accounts.pool_fee_key,
                 &accounts.pool_token_key,
+                None,
                 accounts.fees.clone(),
                 accounts.swap_curve.clone(),
             )
@@ -8199,6 +9055,7 @@ mod tests {
                 &accounts.pool_mint_key,
                 &accounts.pool_fee_key,
                 &accounts.pool_token_key,
+                None,
                 accounts.fees.clone(),
                 accounts.swap_curve.clone(),
             )
@@ -8355,4 +9212,1491 @@ mod tests {
             &token_b_program_id,
         );
     }
+
+    #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
+    #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
+    fn test_admin(
+        pool_token_program_id: Pubkey,
+        token_a_program_id: Pubkey,
+        token_b_program_id: Pubkey,
+    ) {
+        let user_key = Pubkey::new_unique();
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 1000,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: 0,
+            host_fee_numerator: 0,
+            host_fee_denominator: 0,
+        };
+        let token_a_amount = 1_000_000;
+        let token_b_amount = 1_000_000;
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve {}),
+        };
+        let mut accounts = SwapAccountInfo::new(
+            &user_key,
+            fees,
+            SwapTransferFees::default(),
+            swap_curve,
+            token_a_amount,
+            token_b_amount,
+            &pool_token_program_id,
+            &token_a_program_id,
+            &token_b_program_id,
+        );
+        accounts.initialize_swap().unwrap();
+        let admin_key = accounts.admin_key;
+
+        let new_fees = Fees {
+            trade_fee_numerator: 3,
+            trade_fee_denominator: 1000,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 2000,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: 0,
+            host_fee_numerator: 0,
+            host_fee_denominator: 0,
+        };
+
+        // wrong admin
+        {
+            let wrong_admin_key = Pubkey::new_unique();
+            assert_eq!(
+                Err(SwapError::InvalidAdmin.into()),
+                do_process_instruction(
+                    set_fees(
+                        &SWAP_PROGRAM_ID,
+                        &accounts.swap_key,
+                        &wrong_admin_key,
+                        new_fees.clone(),
+                    )
+                    .unwrap(),
+                    vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+                )
+            );
+        }
+
+        // admin did not sign
+        {
+            let mut instruction = pause(&SWAP_PROGRAM_ID, &accounts.swap_key, &admin_key).unwrap();
+            instruction.accounts[1].is_signer = false;
+            assert_eq!(
+                Err(ProgramError::MissingRequiredSignature),
+                do_process_instruction(
+                    instruction,
+                    vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+                )
+            );
+        }
+
+        // invalid fees
+        {
+            let invalid_fees = Fees {
+                trade_fee_numerator: 2,
+                trade_fee_denominator: 1,
+                ..new_fees.clone()
+            };
+            assert_eq!(
+                Err(SwapError::InvalidFee.into()),
+                do_process_instruction(
+                    set_fees(
+                        &SWAP_PROGRAM_ID,
+                        &accounts.swap_key,
+                        &admin_key,
+                        invalid_fees
+                    )
+                    .unwrap(),
+                    vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+                )
+            );
+        }
+
+        // fees not allowed by the program owner's constraints
+        {
+            let owner_key = &user_key.to_string();
+            let valid_curve_types = &[CurveType::ConstantProduct];
+            let swap_constraints = Some(SwapConstraints {
+                owner_key,
+                valid_curve_types,
+                fees: &accounts.fees,
+            });
+            assert_eq!(
+                Err(SwapError::InvalidFee.into()),
+                do_process_instruction_with_fee_constraints(
+                    set_fees(
+                        &SWAP_PROGRAM_ID,
+                        &accounts.swap_key,
+                        &admin_key,
+                        new_fees.clone(),
+                    )
+                    .unwrap(),
+                    vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+                    &swap_constraints,
+                )
+            );
+        }
+
+        // set fees
+        {
+            do_process_instruction(
+                set_fees(
+                    &SWAP_PROGRAM_ID,
+                    &accounts.swap_key,
+                    &admin_key,
+                    new_fees.clone(),
+                )
+                .unwrap(),
+                vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+            )
+            .unwrap();
+            let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+            assert_eq!(*swap_state.fees(), new_fees);
+        }
+
+        let initial_a = token_a_amount / 5;
+        let initial_b = token_b_amount / 5;
+        let (
+            token_a_key,
+            mut token_a_account,
+            token_b_key,
+            mut token_b_account,
+            pool_key,
+            mut pool_account,
+        ) = accounts.setup_token_accounts(&user_key, &user_key, initial_a, initial_b, 0);
+        let pool_amount = to_u64(INITIAL_SWAP_POOL_AMOUNT / 10).unwrap();
+        accounts
+            .deposit_all_token_types(
+                &user_key,
+                &token_a_key,
+                &mut token_a_account,
+                &token_b_key,
+                &mut token_b_account,
+                &pool_key,
+                &mut pool_account,
+                pool_amount,
+                initial_a,
+                initial_b,
+            )
+            .unwrap();
+
+        // pause
+        do_process_instruction(
+            pause(&SWAP_PROGRAM_ID, &accounts.swap_key, &admin_key).unwrap(),
+            vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+        )
+        .unwrap();
+        let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+        assert!(swap_state.is_paused());
+
+        // swaps and deposits fail while paused
+        {
+            let swap_token_a_key = accounts.token_a_key;
+            let swap_token_b_key = accounts.token_b_key;
+            assert_eq!(
+                Err(SwapError::SwapPaused.into()),
+                accounts.swap(
+                    &user_key,
+                    &token_a_key,
+                    &mut token_a_account,
+                    &swap_token_a_key,
+                    &swap_token_b_key,
+                    &token_b_key,
+                    &mut token_b_account,
+                    1_000,
+                    0,
+                )
+            );
+            assert_eq!(
+                Err(SwapError::SwapPaused.into()),
+                accounts.deposit_all_token_types(
+                    &user_key,
+                    &token_a_key,
+                    &mut token_a_account,
+                    &token_b_key,
+                    &mut token_b_account,
+                    &pool_key,
+                    &mut pool_account,
+                    pool_amount,
+                    initial_a,
+                    initial_b,
+                )
+            );
+            assert_eq!(
+                Err(SwapError::SwapPaused.into()),
+                accounts.deposit_single_token_type_exact_amount_in(
+                    &user_key,
+                    &token_a_key,
+                    &mut token_a_account,
+                    &pool_key,
+                    &mut pool_account,
+                    1_000,
+                    0,
+                )
+            );
+        }
+
+        // withdrawals still work while paused
+        accounts
+            .withdraw_all_token_types(
+                &user_key,
+                &pool_key,
+                &mut pool_account,
+                &token_a_key,
+                &mut token_a_account,
+                &token_b_key,
+                &mut token_b_account,
+                pool_amount / 2,
+                0,
+                0,
+            )
+            .unwrap();
+        accounts
+            .withdraw_single_token_type_exact_amount_out(
+                &user_key,
+                &pool_key,
+                &mut pool_account,
+                &token_a_key,
+                &mut token_a_account,
+                1_000,
+                pool_amount / 2,
+            )
+            .unwrap();
+
+        // resume, and swaps work again
+        {
+            do_process_instruction(
+                resume(&SWAP_PROGRAM_ID, &accounts.swap_key, &admin_key).unwrap(),
+                vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+            )
+            .unwrap();
+            let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+            assert!(!swap_state.is_paused());
+            let swap_token_a_key = accounts.token_a_key;
+            let swap_token_b_key = accounts.token_b_key;
+            accounts
+                .swap(
+                    &user_key,
+                    &token_a_key,
+                    &mut token_a_account,
+                    &swap_token_a_key,
+                    &swap_token_b_key,
+                    &token_b_key,
+                    &mut token_b_account,
+                    1_000,
+                    0,
+                )
+                .unwrap();
+        }
+
+        // set a new admin, and the old one no longer works
+        let new_admin_key = Pubkey::new_unique();
+        {
+            do_process_instruction(
+                set_admin(
+                    &SWAP_PROGRAM_ID,
+                    &accounts.swap_key,
+                    &admin_key,
+                    Some(&new_admin_key),
+                )
+                .unwrap(),
+                vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+            )
+            .unwrap();
+            let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+            assert_eq!(swap_state.admin(), COption::Some(new_admin_key));
+            assert_eq!(
+                Err(SwapError::InvalidAdmin.into()),
+                do_process_instruction(
+                    pause(&SWAP_PROGRAM_ID, &accounts.swap_key, &admin_key).unwrap(),
+                    vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+                )
+            );
+        }
+
+        // remove the admin, and the swap can no longer be updated
+        {
+            do_process_instruction(
+                set_admin(&SWAP_PROGRAM_ID, &accounts.swap_key, &new_admin_key, None).unwrap(),
+                vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+            )
+            .unwrap();
+            assert_eq!(
+                Err(SwapError::NoAdmin.into()),
+                do_process_instruction(
+                    pause(&SWAP_PROGRAM_ID, &accounts.swap_key, &new_admin_key).unwrap(),
+                    vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_admin_v1_swap() {
+        let user_key = Pubkey::new_unique();
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 1000,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: 0,
+            host_fee_numerator: 0,
+            host_fee_denominator: 0,
+        };
+        let token_a_amount = 1_000_000;
+        let token_b_amount = 1_000_000;
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve {}),
+        };
+        let mut accounts = SwapAccountInfo::new(
+            &user_key,
+            fees,
+            SwapTransferFees::default(),
+            swap_curve,
+            token_a_amount,
+            token_b_amount,
+            &spl_token::id(),
+            &spl_token::id(),
+            &spl_token::id(),
+        );
+        accounts.initialize_swap().unwrap();
+
+        // rewrite the swap as a V1 swap, as created by earlier versions
+        let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+        let swap_v1 = SwapVersion::SwapV1(SwapV1 {
+            is_initialized: true,
+            bump_seed: swap_state.bump_seed(),
+            token_program_id: *swap_state.token_program_id(),
+            token_a: *swap_state.token_a_account(),
+            token_b: *swap_state.token_b_account(),
+            pool_mint: *swap_state.pool_mint(),
+            token_a_mint: *swap_state.token_a_mint(),
+            token_b_mint: *swap_state.token_b_mint(),
+            pool_fee_account: *swap_state.pool_fee_account(),
+            fees: swap_state.fees().clone(),
+            swap_curve: swap_state.swap_curve().clone(),
+        });
+        accounts.swap_account.data = vec![0; 1 + SwapV1::LEN];
+        SwapVersion::pack(swap_v1, &mut accounts.swap_account.data).unwrap();
+        let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+        assert_eq!(swap_state.admin(), COption::None);
+        assert!(!swap_state.is_paused());
+
+        // V1 swaps have no admin
+        let admin_key = accounts.admin_key;
+        assert_eq!(
+            Err(SwapError::NoAdmin.into()),
+            do_process_instruction(
+                pause(&SWAP_PROGRAM_ID, &accounts.swap_key, &admin_key).unwrap(),
+                vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+            )
+        );
+
+        // but still work
+        let (token_a_key, mut token_a_account, token_b_key, mut token_b_account, _, _) =
+            accounts.setup_token_accounts(&user_key, &user_key, 10_000, 0, 0);
+        let swap_token_a_key = accounts.token_a_key;
+        let swap_token_b_key = accounts.token_b_key;
+        accounts
+            .swap(
+                &user_key,
+                &token_a_key,
+                &mut token_a_account,
+                &swap_token_a_key,
+                &swap_token_b_key,
+                &token_b_key,
+                &mut token_b_account,
+                1_000,
+                0,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_price_oracle() {
+        let user_key = Pubkey::new_unique();
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 1000,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: 0,
+            host_fee_numerator: 0,
+            host_fee_denominator: 0,
+        };
+        let token_a_amount = 1_000_000;
+        let token_b_amount = 4_000_000;
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve {}),
+        };
+        let mut accounts = SwapAccountInfo::new(
+            &user_key,
+            fees,
+            SwapTransferFees::default(),
+            swap_curve,
+            token_a_amount,
+            token_b_amount,
+            &spl_token::id(),
+            &spl_token::id(),
+            &spl_token::id(),
+        );
+        set_test_unix_timestamp(100);
+        accounts.initialize_swap().unwrap();
+        let start = PriceOracle::new(100);
+        let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+        assert_eq!(swap_state.price_oracle(), Some(&start));
+
+        let swap_balances = |accounts: &SwapAccountInfo| {
+            let token_a =
+                StateWithExtensions::<Account>::unpack(&accounts.token_a_account.data).unwrap();
+            let token_b =
+                StateWithExtensions::<Account>::unpack(&accounts.token_b_account.data).unwrap();
+            (token_a.base.amount as u128, token_b.base.amount as u128)
+        };
+        let price_oracle = |accounts: &SwapAccountInfo| {
+            let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+            swap_state.price_oracle().unwrap().clone()
+        };
+
+        let (
+            token_a_key,
+            mut token_a_account,
+            token_b_key,
+            mut token_b_account,
+            pool_key,
+            mut pool_account,
+        ) = accounts.setup_token_accounts(&user_key, &user_key, 100_000, 100_000, 0);
+        let swap_token_a_key = accounts.token_a_key;
+        let swap_token_b_key = accounts.token_b_key;
+
+        // swap accumulates the prices before the swap
+        set_test_unix_timestamp(110);
+        let (swap_token_a_amount, swap_token_b_amount) = swap_balances(&accounts);
+        let expected = start.accumulate(
+            &accounts.swap_curve,
+            swap_token_a_amount,
+            swap_token_b_amount,
+            110,
+        );
+        accounts
+            .swap(
+                &user_key,
+                &token_a_key,
+                &mut token_a_account,
+                &swap_token_a_key,
+                &swap_token_b_key,
+                &token_b_key,
+                &mut token_b_account,
+                10_000,
+                0,
+            )
+            .unwrap();
+        let after_swap = price_oracle(&accounts);
+        assert_eq!(after_swap, expected);
+        let twap = after_swap.time_weighted_average_price(&start).unwrap();
+        assert_eq!(twap.price_a, PreciseNumber::new(4).unwrap());
+
+        // second update in the same second doesn't accumulate anything
+        accounts
+            .swap(
+                &user_key,
+                &token_b_key,
+                &mut token_b_account,
+                &swap_token_b_key,
+                &swap_token_a_key,
+                &token_a_key,
+                &mut token_a_account,
+                10_000,
+                0,
+            )
+            .unwrap();
+        assert_eq!(price_oracle(&accounts), after_swap);
+
+        // deposit
+        set_test_unix_timestamp(140);
+        let (swap_token_a_amount, swap_token_b_amount) = swap_balances(&accounts);
+        let expected = after_swap.accumulate(
+            &accounts.swap_curve,
+            swap_token_a_amount,
+            swap_token_b_amount,
+            140,
+        );
+        accounts
+            .deposit_all_token_types(
+                &user_key,
+                &token_a_key,
+                &mut token_a_account,
+                &token_b_key,
+                &mut token_b_account,
+                &pool_key,
+                &mut pool_account,
+                10_000_000,
+                100_000,
+                100_000,
+            )
+            .unwrap();
+        let after_deposit = price_oracle(&accounts);
+        assert_eq!(after_deposit, expected);
+
+        // withdraw
+        set_test_unix_timestamp(200);
+        let (swap_token_a_amount, swap_token_b_amount) = swap_balances(&accounts);
+        let expected = after_deposit.accumulate(
+            &accounts.swap_curve,
+            swap_token_a_amount,
+            swap_token_b_amount,
+            200,
+        );
+        accounts
+            .withdraw_all_token_types(
+                &user_key,
+                &pool_key,
+                &mut pool_account,
+                &token_a_key,
+                &mut token_a_account,
+                &token_b_key,
+                &mut token_b_account,
+                10_000_000,
+                0,
+                0,
+            )
+            .unwrap();
+        let after_withdraw = price_oracle(&accounts);
+        assert_eq!(after_withdraw, expected);
+
+        // a read-only swap account is rejected
+        set_test_unix_timestamp(300);
+        {
+            let (swap_token_a_amount, swap_token_b_amount) = swap_balances(&accounts);
+            let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+            let mut lamports = accounts.swap_account.lamports;
+            let mut data = accounts.swap_account.data.clone();
+            let swap_info = AccountInfo::new(
+                &accounts.swap_key,
+                false,
+                false,
+                &mut lamports,
+                &mut data,
+                &SWAP_PROGRAM_ID,
+                false,
+                0,
+            );
+            assert_eq!(
+                Err(SwapError::ReadonlySwapAccount.into()),
+                Processor::update_price_oracle(
+                    &swap_info,
+                    swap_state.as_ref(),
+                    to_u64(swap_token_a_amount).unwrap(),
+                    to_u64(swap_token_b_amount).unwrap(),
+                )
+            );
+        }
+
+        // the price moved away from 4 B per A after the first swap
+        let twap = after_withdraw.time_weighted_average_price(&start).unwrap();
+        assert!(twap.price_a.less_than(&PreciseNumber::new(4).unwrap()));
+        let twap = after_withdraw
+            .time_weighted_average_price(&after_swap)
+            .unwrap();
+        assert!(twap.price_a.less_than(&PreciseNumber::new(4).unwrap()));
+
+        // admin updates keep the oracle
+        let admin_key = accounts.admin_key;
+        do_process_instruction(
+            pause(&SWAP_PROGRAM_ID, &accounts.swap_key, &admin_key).unwrap(),
+            vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+        )
+        .unwrap();
+        let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+        assert!(swap_state.is_paused());
+        assert_eq!(swap_state.price_oracle(), Some(&after_withdraw));
+    }
+
+    #[test]
+    fn test_price_oracle_v2_swap() {
+        let user_key = Pubkey::new_unique();
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 1000,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: 0,
+            host_fee_numerator: 0,
+            host_fee_denominator: 0,
+        };
+        let token_a_amount = 1_000_000;
+        let token_b_amount = 1_000_000;
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve {}),
+        };
+        let mut accounts = SwapAccountInfo::new(
+            &user_key,
+            fees,
+            SwapTransferFees::default(),
+            swap_curve,
+            token_a_amount,
+            token_b_amount,
+            &spl_token::id(),
+            &spl_token::id(),
+            &spl_token::id(),
+        );
+        accounts.initialize_swap().unwrap();
+
+        // rewrite the swap as a V2 swap, as created by earlier versions
+        let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+        let swap_v2 = SwapVersion::SwapV2(SwapV2 {
+            is_initialized: true,
+            bump_seed: swap_state.bump_seed(),
+            token_program_id: *swap_state.token_program_id(),
+            token_a: *swap_state.token_a_account(),
+            token_b: *swap_state.token_b_account(),
+            pool_mint: *swap_state.pool_mint(),
+            token_a_mint: *swap_state.token_a_mint(),
+            token_b_mint: *swap_state.token_b_mint(),
+            pool_fee_account: *swap_state.pool_fee_account(),
+            fees: swap_state.fees().clone(),
+            swap_curve: swap_state.swap_curve().clone(),
+            admin: swap_state.admin(),
+            is_paused: false,
+        });
+        accounts.swap_account.data = vec![0; 1 + SwapV2::LEN];
+        SwapVersion::pack(swap_v2, &mut accounts.swap_account.data).unwrap();
+        let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+        assert_eq!(swap_state.price_oracle(), None);
+
+        // V2 swaps have no oracle, but still work and can be updated
+        let (token_a_key, mut token_a_account, token_b_key, mut token_b_account, _, _) =
+            accounts.setup_token_accounts(&user_key, &user_key, 10_000, 0, 0);
+        let swap_token_a_key = accounts.token_a_key;
+        let swap_token_b_key = accounts.token_b_key;
+        accounts
+            .swap(
+                &user_key,
+                &token_a_key,
+                &mut token_a_account,
+                &swap_token_a_key,
+                &swap_token_b_key,
+                &token_b_key,
+                &mut token_b_account,
+                1_000,
+                0,
+            )
+            .unwrap();
+
+        // without a price oracle, the swap account may be read-only
+        {
+            let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+            let mut lamports = accounts.swap_account.lamports;
+            let mut data = accounts.swap_account.data.clone();
+            let swap_info = AccountInfo::new(
+                &accounts.swap_key,
+                false,
+                false,
+                &mut lamports,
+                &mut data,
+                &SWAP_PROGRAM_ID,
+                false,
+                0,
+            );
+            Processor::update_price_oracle(&swap_info, swap_state.as_ref(), 1_000, 1_000).unwrap();
+        }
+
+        let admin_key = accounts.admin_key;
+        do_process_instruction(
+            pause(&SWAP_PROGRAM_ID, &accounts.swap_key, &admin_key).unwrap(),
+            vec![&mut accounts.swap_account, &mut SolanaAccount::default()],
+        )
+        .unwrap();
+        let swap_state = SwapVersion::unpack(&accounts.swap_account.data).unwrap();
+        assert!(swap_state.is_paused());
+        assert_eq!(swap_state.price_oracle(), None);
+    }
+
+    /// Swap token A of the first pool into token B of the second pool, through
+    /// token B of the first pool, which is token A of the second pool
+    #[allow(clippy::too_many_arguments)]
+    fn route_swap_a_to_b(
+        first: &mut SwapAccountInfo,
+        second: &mut SwapAccountInfo,
+        user_key: &Pubkey,
+        user_source_key: &Pubkey,
+        user_source_account: &mut SolanaAccount,
+        user_destination_key: &Pubkey,
+        user_destination_account: &mut SolanaAccount,
+        host_fee_keys: (&Pubkey, &Pubkey),
+        host_fee_accounts: (&mut SolanaAccount, &mut SolanaAccount),
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> ProgramResult {
+        let user_transfer_key = Pubkey::new_unique();
+        do_process_instruction(
+            approve(
+                &first.token_a_program_id,
+                user_source_key,
+                &user_transfer_key,
+                user_key,
+                &[],
+                amount_in,
+            )
+            .unwrap(),
+            vec![
+                user_source_account,
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+            ],
+        )
+        .unwrap();
+
+        let hops = [&*first, &*second]
+            .iter()
+            .zip([host_fee_keys.0, host_fee_keys.1].iter())
+            .map(|(pool, host_fee_key)| RouteHopAccounts {
+                swap_pubkey: pool.swap_key,
+                authority_pubkey: pool.authority_key,
+                swap_source_pubkey: pool.token_a_key,
+                swap_destination_pubkey: pool.token_b_key,
+                pool_mint_pubkey: pool.pool_mint_key,
+                pool_fee_pubkey: pool.pool_fee_key,
+                destination_mint_pubkey: pool.token_b_mint_key,
+                destination_token_program_id: pool.token_b_program_id,
+                pool_token_program_id: pool.pool_token_program_id,
+                host_fee_pubkey: Some(**host_fee_key),
+            })
+            .collect::<Vec<_>>();
+        let mut source_mint_account = first.token_a_mint_account.clone();
+        do_process_instruction(
+            route_swap(
+                &SWAP_PROGRAM_ID,
+                &first.token_a_program_id,
+                &user_transfer_key,
+                user_source_key,
+                user_destination_key,
+                &first.token_a_mint_key,
+                &hops,
+                amount_in,
+                minimum_amount_out,
+            )
+            .unwrap(),
+            vec![
+                &mut SolanaAccount::default(),
+                user_source_account,
+                user_destination_account,
+                &mut source_mint_account,
+                &mut SolanaAccount::default(),
+                &mut first.swap_account,
+                &mut SolanaAccount::default(),
+                &mut first.token_a_account,
+                &mut first.token_b_account,
+                &mut first.pool_mint_account,
+                &mut first.pool_fee_account,
+                &mut first.token_b_mint_account,
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+                host_fee_accounts.0,
+                &mut second.swap_account,
+                &mut SolanaAccount::default(),
+                &mut second.token_a_account,
+                &mut second.token_b_account,
+                &mut second.pool_mint_account,
+                &mut second.pool_fee_account,
+                &mut second.token_b_mint_account,
+                &mut SolanaAccount::default(),
+                &mut SolanaAccount::default(),
+                host_fee_accounts.1,
+            ],
+        )
+    }
+
+    #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
+    #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
+    fn test_route_swap(
+        pool_token_program_id: Pubkey,
+        token_a_program_id: Pubkey,
+        token_b_program_id: Pubkey,
+    ) {
+        let user_key = Pubkey::new_unique();
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: 0,
+            host_fee_numerator: 20,
+            host_fee_denominator: 100,
+        };
+        let first_token_a_amount = 1_000_000;
+        let first_token_b_amount = 2_000_000;
+        let mut first = SwapAccountInfo::new(
+            &user_key,
+            fees.clone(),
+            SwapTransferFees::default(),
+            SwapCurve {
+                curve_type: CurveType::ConstantProduct,
+                calculator: Arc::new(ConstantProductCurve {}),
+            },
+            first_token_a_amount,
+            first_token_b_amount,
+            &pool_token_program_id,
+            &token_a_program_id,
+            &token_b_program_id,
+        );
+        first.initialize_swap().unwrap();
+
+        // the second pool trades the first pool's token B for a new token
+        let second_token_a_amount = 5_000_000;
+        let second_token_b_amount = 5_000_000;
+        let mut second = SwapAccountInfo::new(
+            &user_key,
+            fees,
+            SwapTransferFees::default(),
+            SwapCurve {
+                curve_type: CurveType::Stable,
+                calculator: Arc::new(StableCurve { amp: 100 }),
+            },
+            second_token_a_amount,
+            second_token_b_amount,
+            &pool_token_program_id,
+            &token_b_program_id,
+            &token_b_program_id,
+        );
+        let (token_a_key, token_a_account) = mint_token(
+            &token_b_program_id,
+            &first.token_b_mint_key,
+            &mut first.token_b_mint_account,
+            &user_key,
+            &second.authority_key,
+            second_token_a_amount,
+        );
+        second.token_a_key = token_a_key;
+        second.token_a_account = token_a_account;
+        second.token_a_mint_key = first.token_b_mint_key;
+        second.token_a_mint_account = first.token_b_mint_account.clone();
+        second.initialize_swap().unwrap();
+
+        let initial_a = 100_000;
+        let (user_source_key, mut user_source_account, _, _, _, _) =
+            first.setup_token_accounts(&user_key, &user_key, initial_a, 0, 0);
+        let (_, _, user_destination_key, mut user_destination_account, _, _) =
+            second.setup_token_accounts(&user_key, &user_key, 0, 0, 0);
+        let (first_host_fee_key, mut first_host_fee_account) = mint_token(
+            &pool_token_program_id,
+            &first.pool_mint_key,
+            &mut first.pool_mint_account,
+            &first.authority_key,
+            &user_key,
+            0,
+        );
+        let (second_host_fee_key, mut second_host_fee_account) = mint_token(
+            &pool_token_program_id,
+            &second.pool_mint_key,
+            &mut second.pool_mint_account,
+            &second.authority_key,
+            &user_key,
+            0,
+        );
+
+        let amount_in = 50_000;
+        let first_result = first
+            .swap_curve
+            .swap(
+                amount_in.into(),
+                first_token_a_amount.into(),
+                first_token_b_amount.into(),
+                TradeDirection::AtoB,
+                &first.fees,
+            )
+            .unwrap();
+        let second_result = second
+            .swap_curve
+            .swap(
+                first_result.destination_amount_swapped,
+                second_token_a_amount.into(),
+                second_token_b_amount.into(),
+                TradeDirection::AtoB,
+                &second.fees,
+            )
+            .unwrap();
+        let amount_out = to_u64(second_result.destination_amount_swapped).unwrap();
+
+        // slippage is checked over the whole route
+        assert_eq!(
+            Err(SwapError::ExceededSlippage.into()),
+            route_swap_a_to_b(
+                &mut first,
+                &mut second,
+                &user_key,
+                &user_source_key,
+                &mut user_source_account,
+                &user_destination_key,
+                &mut user_destination_account,
+                (&first_host_fee_key, &second_host_fee_key),
+                (&mut first_host_fee_account, &mut second_host_fee_account),
+                amount_in,
+                amount_out + 1,
+            )
+        );
+
+        // any paused pool stops the route
+        {
+            let admin_key = second.admin_key;
+            do_process_instruction(
+                pause(&SWAP_PROGRAM_ID, &second.swap_key, &admin_key).unwrap(),
+                vec![&mut second.swap_account, &mut SolanaAccount::default()],
+            )
+            .unwrap();
+            assert_eq!(
+                Err(SwapError::SwapPaused.into()),
+                route_swap_a_to_b(
+                    &mut first,
+                    &mut second,
+                    &user_key,
+                    &user_source_key,
+                    &mut user_source_account,
+                    &user_destination_key,
+                    &mut user_destination_account,
+                    (&first_host_fee_key, &second_host_fee_key),
+                    (&mut first_host_fee_account, &mut second_host_fee_account),
+                    amount_in,
+                    0,
+                )
+            );
+            do_process_instruction(
+                resume(&SWAP_PROGRAM_ID, &second.swap_key, &admin_key).unwrap(),
+                vec![&mut second.swap_account, &mut SolanaAccount::default()],
+            )
+            .unwrap();
+        }
+
+        route_swap_a_to_b(
+            &mut first,
+            &mut second,
+            &user_key,
+            &user_source_key,
+            &mut user_source_account,
+            &user_destination_key,
+            &mut user_destination_account,
+            (&first_host_fee_key, &second_host_fee_key),
+            (&mut first_host_fee_account, &mut second_host_fee_account),
+            amount_in,
+            amount_out,
+        )
+        .unwrap();
+
+        let user_source =
+            StateWithExtensions::<Account>::unpack(&user_source_account.data).unwrap();
+        assert_eq!(
+            user_source.base.amount,
+            initial_a - to_u64(first_result.source_amount_swapped).unwrap()
+        );
+        let user_destination =
+            StateWithExtensions::<Account>::unpack(&user_destination_account.data).unwrap();
+        assert_eq!(user_destination.base.amount, amount_out);
+
+        // the intermediate tokens went straight from the first pool to the second
+        let intermediate_amount = to_u64(first_result.destination_amount_swapped).unwrap();
+        let first_token_b =
+            StateWithExtensions::<Account>::unpack(&first.token_b_account.data).unwrap();
+        assert_eq!(
+            first_token_b.base.amount,
+            first_token_b_amount - intermediate_amount
+        );
+        let second_token_a =
+            StateWithExtensions::<Account>::unpack(&second.token_a_account.data).unwrap();
+        assert_eq!(
+            second_token_a.base.amount,
+            second_token_a_amount + intermediate_amount
+        );
+
+        // each pool paid its own host fee
+        for host_fee_account in [&first_host_fee_account, &second_host_fee_account] {
+            let host_fee = StateWithExtensions::<Account>::unpack(&host_fee_account.data).unwrap();
+            assert!(host_fee.base.amount > 0);
+        }
+    }
+
+    #[test]
+    fn test_route_swap_partial_hop() {
+        let user_key = Pubkey::new_unique();
+        let token_program_id = spl_token::id();
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 100,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: 0,
+            host_fee_numerator: 20,
+            host_fee_denominator: 100,
+        };
+        let first_token_a_amount = 1_000_000;
+        let first_token_b_amount = 2_000_000;
+        let mut first = SwapAccountInfo::new(
+            &user_key,
+            fees.clone(),
+            SwapTransferFees::default(),
+            SwapCurve {
+                curve_type: CurveType::ConstantProduct,
+                calculator: Arc::new(ConstantProductCurve {}),
+            },
+            first_token_a_amount,
+            first_token_b_amount,
+            &token_program_id,
+            &token_program_id,
+            &token_program_id,
+        );
+        first.initialize_swap().unwrap();
+
+        // the second pool only swaps multiples of its token B price, so it
+        // rounds down its input
+        let token_b_price = 1_000;
+        let second_token_a_amount = 5_000_000;
+        let second_token_b_amount = 1_000_000;
+        let mut second = SwapAccountInfo::new(
+            &user_key,
+            fees,
+            SwapTransferFees::default(),
+            SwapCurve {
+                curve_type: CurveType::ConstantPrice,
+                calculator: Arc::new(ConstantPriceCurve { token_b_price }),
+            },
+            second_token_a_amount,
+            second_token_b_amount,
+            &token_program_id,
+            &token_program_id,
+            &token_program_id,
+        );
+        let (token_a_key, token_a_account) = mint_token(
+            &token_program_id,
+            &first.token_b_mint_key,
+            &mut first.token_b_mint_account,
+            &user_key,
+            &second.authority_key,
+            second_token_a_amount,
+        );
+        second.token_a_key = token_a_key;
+        second.token_a_account = token_a_account;
+        second.token_a_mint_key = first.token_b_mint_key;
+        second.token_a_mint_account = first.token_b_mint_account.clone();
+        second.initialize_swap().unwrap();
+
+        let initial_a = 100_000;
+        let (user_source_key, mut user_source_account, _, _, _, _) =
+            first.setup_token_accounts(&user_key, &user_key, initial_a, 0, 0);
+        let (_, _, user_destination_key, mut user_destination_account, _, _) =
+            second.setup_token_accounts(&user_key, &user_key, 0, 0, 0);
+        let (first_host_fee_key, mut first_host_fee_account) = mint_token(
+            &token_program_id,
+            &first.pool_mint_key,
+            &mut first.pool_mint_account,
+            &first.authority_key,
+            &user_key,
+            0,
+        );
+        let (second_host_fee_key, mut second_host_fee_account) = mint_token(
+            &token_program_id,
+            &second.pool_mint_key,
+            &mut second.pool_mint_account,
+            &second.authority_key,
+            &user_key,
+            0,
+        );
+
+        let amount_in = 50_000;
+        let first_result = first
+            .swap_curve
+            .swap(
+                amount_in.into(),
+                first_token_a_amount.into(),
+                first_token_b_amount.into(),
+                TradeDirection::AtoB,
+                &first.fees,
+            )
+            .unwrap();
+        let second_result = second
+            .swap_curve
+            .swap(
+                first_result.destination_amount_swapped,
+                second_token_a_amount.into(),
+                second_token_b_amount.into(),
+                TradeDirection::AtoB,
+                &second.fees,
+            )
+            .unwrap();
+        assert!(second_result.source_amount_swapped < first_result.destination_amount_swapped);
+
+        assert_eq!(
+            Err(SwapError::PartialRouteSwap.into()),
+            route_swap_a_to_b(
+                &mut first,
+                &mut second,
+                &user_key,
+                &user_source_key,
+                &mut user_source_account,
+                &user_destination_key,
+                &mut user_destination_account,
+                (&first_host_fee_key, &second_host_fee_key),
+                (&mut first_host_fee_account, &mut second_host_fee_account),
+                amount_in,
+                0,
+            )
+        );
+    }
+
+    #[test_case(spl_token::id(), spl_token::id(), spl_token::id(); "all-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
+    #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
+    fn test_swap_exact_amount_out(
+        pool_token_program_id: Pubkey,
+        token_a_program_id: Pubkey,
+        token_b_program_id: Pubkey,
+    ) {
+        let user_key = Pubkey::new_unique();
+        let swapper_key = Pubkey::new_unique();
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 1000,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: 0,
+            host_fee_numerator: 0,
+            host_fee_denominator: 0,
+        };
+        let token_a_amount = 1_000_000;
+        let token_b_amount = 5_000_000;
+        let curves: Vec<(CurveType, Arc<dyn CurveCalculator + Send + Sync>)> = vec![
+            (
+                CurveType::ConstantProduct,
+                Arc::new(ConstantProductCurve {}),
+            ),
+            (CurveType::Stable, Arc::new(StableCurve { amp: 100 })),
+        ];
+        for (curve_type, calculator) in curves {
+            let swap_curve = SwapCurve {
+                curve_type,
+                calculator,
+            };
+            let mut accounts = SwapAccountInfo::new(
+                &user_key,
+                fees.clone(),
+                SwapTransferFees::default(),
+                swap_curve.clone(),
+                token_a_amount,
+                token_b_amount,
+                &pool_token_program_id,
+                &token_a_program_id,
+                &token_b_program_id,
+            );
+            accounts.initialize_swap().unwrap();
+            let swap_token_a_key = accounts.token_a_key;
+            let swap_token_b_key = accounts.token_b_key;
+            let initial_a = token_a_amount / 5;
+            let initial_b = token_b_amount / 5;
+            let (
+                token_a_key,
+                mut token_a_account,
+                token_b_key,
+                mut token_b_account,
+                _pool_key,
+                _pool_account,
+            ) = accounts.setup_token_accounts(&user_key, &swapper_key, initial_a, initial_b, 0);
+
+            let b_amount_out = 100_000;
+            let results = swap_curve
+                .swap_exact_amount_out(
+                    b_amount_out.into(),
+                    token_a_amount.into(),
+                    token_b_amount.into(),
+                    TradeDirection::AtoB,
+                    &fees,
+                )
+                .unwrap();
+            let a_amount_in = to_u64(results.source_amount_swapped).unwrap();
+
+            // maximum amount in too low
+            assert_eq!(
+                Err(SwapError::ExceededSlippage.into()),
+                accounts.swap_exact_amount_out(
+                    &swapper_key,
+                    &token_a_key,
+                    &mut token_a_account,
+                    &swap_token_a_key,
+                    &swap_token_b_key,
+                    &token_b_key,
+                    &mut token_b_account,
+                    b_amount_out,
+                    a_amount_in - 1,
+                )
+            );
+
+            // the whole pool can't be taken out
+            assert_eq!(
+                Err(SwapError::ZeroTradingTokens.into()),
+                accounts.swap_exact_amount_out(
+                    &swapper_key,
+                    &token_a_key,
+                    &mut token_a_account,
+                    &swap_token_a_key,
+                    &swap_token_b_key,
+                    &token_b_key,
+                    &mut token_b_account,
+                    token_b_amount,
+                    initial_a,
+                )
+            );
+
+            // receive exactly the amount out
+            let pool_mint =
+                StateWithExtensions::<Mint>::unpack(&accounts.pool_mint_account.data).unwrap();
+            let initial_supply = pool_mint.base.supply;
+            accounts
+                .swap_exact_amount_out(
+                    &swapper_key,
+                    &token_a_key,
+                    &mut token_a_account,
+                    &swap_token_a_key,
+                    &swap_token_b_key,
+                    &token_b_key,
+                    &mut token_b_account,
+                    b_amount_out,
+                    a_amount_in,
+                )
+                .unwrap();
+
+            let token_a = StateWithExtensions::<Account>::unpack(&token_a_account.data).unwrap();
+            assert_eq!(token_a.base.amount, initial_a - a_amount_in);
+            let token_b = StateWithExtensions::<Account>::unpack(&token_b_account.data).unwrap();
+            assert_eq!(token_b.base.amount, initial_b + b_amount_out);
+            let swap_token_a =
+                StateWithExtensions::<Account>::unpack(&accounts.token_a_account.data).unwrap();
+            assert_eq!(
+                swap_token_a.base.amount,
+                to_u64(results.new_swap_source_amount).unwrap()
+            );
+            let swap_token_b =
+                StateWithExtensions::<Account>::unpack(&accounts.token_b_account.data).unwrap();
+            assert_eq!(
+                swap_token_b.base.amount,
+                to_u64(results.new_swap_destination_amount).unwrap()
+            );
+
+            let owner_fee = swap_curve
+                .withdraw_single_token_type_exact_out(
+                    results.owner_fee,
+                    results.new_swap_source_amount,
+                    results.new_swap_destination_amount,
+                    initial_supply.into(),
+                    TradeDirection::AtoB,
+                    &fees,
+                )
+                .unwrap();
+            let fee_account =
+                StateWithExtensions::<Account>::unpack(&accounts.pool_fee_account.data).unwrap();
+            assert_eq!(fee_account.base.amount, to_u64(owner_fee).unwrap());
+
+            // and the other way
+            let a_amount_out = 10_000;
+            let results = swap_curve
+                .swap_exact_amount_out(
+                    a_amount_out.into(),
+                    swap_token_b.base.amount.into(),
+                    swap_token_a.base.amount.into(),
+                    TradeDirection::BtoA,
+                    &fees,
+                )
+                .unwrap();
+            let b_amount_in = to_u64(results.source_amount_swapped).unwrap();
+            accounts
+                .swap_exact_amount_out(
+                    &swapper_key,
+                    &token_b_key,
+                    &mut token_b_account,
+                    &swap_token_b_key,
+                    &swap_token_a_key,
+                    &token_a_key,
+                    &mut token_a_account,
+                    a_amount_out,
+                    b_amount_in,
+                )
+                .unwrap();
+
+            let token_a = StateWithExtensions::<Account>::unpack(&token_a_account.data).unwrap();
+            assert_eq!(token_a.base.amount, initial_a - a_amount_in + a_amount_out);
+            let token_b = StateWithExtensions::<Account>::unpack(&token_b_account.data).unwrap();
+            assert_eq!(token_b.base.amount, initial_b + b_amount_out - b_amount_in);
+        }
+    }
+
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token_2022::id(); "all-token-2022")]
+    #[test_case(spl_token::id(), spl_token_2022::id(), spl_token_2022::id(); "mixed-pool-token")]
+    #[test_case(spl_token_2022::id(), spl_token_2022::id(), spl_token::id(); "mixed-pool-token-2022")]
+    fn test_swap_exact_amount_out_with_transfer_fees(
+        pool_token_program_id: Pubkey,
+        token_a_program_id: Pubkey,
+        token_b_program_id: Pubkey,
+    ) {
+        let user_key = Pubkey::new_unique();
+        let swapper_key = Pubkey::new_unique();
+        let fees = Fees {
+            trade_fee_numerator: 1,
+            trade_fee_denominator: 100,
+            owner_trade_fee_numerator: 1,
+            owner_trade_fee_denominator: 1000,
+            owner_withdraw_fee_numerator: 0,
+            owner_withdraw_fee_denominator: 0,
+            host_fee_numerator: 0,
+            host_fee_denominator: 0,
+        };
+        let token_a_fee = TransferFee {
+            epoch: 0.into(),
+            transfer_fee_basis_points: 100.into(),
+            maximum_fee: 1_000_000_000.into(),
+        };
+        let token_a_amount = 1_000_000;
+        let token_b_amount = 5_000_000;
+        let swap_curve = SwapCurve {
+            curve_type: CurveType::ConstantProduct,
+            calculator: Arc::new(ConstantProductCurve {}),
+        };
+        let mut accounts = SwapAccountInfo::new(
+            &user_key,
+            fees.clone(),
+            SwapTransferFees {
+                pool_token: TransferFee::default(),
+                token_a: token_a_fee,
+                token_b: TransferFee::default(),
+            },
+            swap_curve.clone(),
+            token_a_amount,
+            token_b_amount,
+            &pool_token_program_id,
+            &token_a_program_id,
+            &token_b_program_id,
+        );
+        accounts.initialize_swap().unwrap();
+        let swap_token_a_key = accounts.token_a_key;
+        let swap_token_b_key = accounts.token_b_key;
+        let initial_a = token_a_amount / 5;
+        let initial_b = token_b_amount / 5;
+        let (
+            token_a_key,
+            mut token_a_account,
+            token_b_key,
+            mut token_b_account,
+            _pool_key,
+            _pool_account,
+        ) = accounts.setup_token_accounts(&user_key, &swapper_key, initial_a, initial_b, 0);
+        let swap_token_a =
+            StateWithExtensions::<Account>::unpack(&accounts.token_a_account.data).unwrap();
+        let swap_token_a_amount = swap_token_a.base.amount;
+
+        // the transfer fee on the source is paid on top of the swap
+        let b_amount_out = 100_000;
+        let results = swap_curve
+            .swap_exact_amount_out(
+                b_amount_out.into(),
+                swap_token_a_amount.into(),
+                token_b_amount.into(),
+                TradeDirection::AtoB,
+                &fees,
+            )
+            .unwrap();
+        let a_amount_swapped = to_u64(results.source_amount_swapped).unwrap();
+        let a_amount_in =
+            a_amount_swapped + token_a_fee.calculate_inverse_fee(a_amount_swapped).unwrap();
+        assert_eq!(
+            Err(SwapError::ExceededSlippage.into()),
+            accounts.swap_exact_amount_out(
+                &swapper_key,
+                &token_a_key,
+                &mut token_a_account,
+                &swap_token_a_key,
+                &swap_token_b_key,
+                &token_b_key,
+                &mut token_b_account,
+                b_amount_out,
+                a_amount_in - 1,
+            )
+        );
+        accounts
+            .swap_exact_amount_out(
+                &swapper_key,
+                &token_a_key,
+                &mut token_a_account,
+                &swap_token_a_key,
+                &swap_token_b_key,
+                &token_b_key,
+                &mut token_b_account,
+                b_amount_out,
+                a_amount_in,
+            )
+            .unwrap();
+        let token_a = StateWithExtensions::<Account>::unpack(&token_a_account.data).unwrap();
+        assert_eq!(token_a.base.amount, initial_a - a_amount_in);
+        let token_b = StateWithExtensions::<Account>::unpack(&token_b_account.data).unwrap();
+        assert_eq!(token_b.base.amount, initial_b + b_amount_out);
+        let swap_token_a =
+            StateWithExtensions::<Account>::unpack(&accounts.token_a_account.data).unwrap();
+        assert_eq!(
+            swap_token_a.base.amount,
+            swap_token_a_amount + a_amount_swapped
+        );
+        let swap_token_a_amount = swap_token_a.base.amount;
+        let swap_token_b =
+            StateWithExtensions::<Account>::unpack(&accounts.token_b_account.data).unwrap();
+        let swap_token_b_amount = swap_token_b.base.amount;
+
+        // the transfer fee on the destination is sent out on top of the
+        // amount received
+        let a_amount_out = 10_000;
+        let a_amount_sent = a_amount_out + token_a_fee.calculate_inverse_fee(a_amount_out).unwrap();
+        let results = swap_curve
+            .swap_exact_amount_out(
+                a_amount_sent.into(),
+                swap_token_b_amount.into(),
+                swap_token_a_amount.into(),
+                TradeDirection::BtoA,
+                &fees,
+            )
+            .unwrap();
+        let b_amount_in = to_u64(results.source_amount_swapped).unwrap();
+        accounts
+            .swap_exact_amount_out(
+                &swapper_key,
+                &token_b_key,
+                &mut token_b_account,
+                &swap_token_b_key,
+                &swap_token_a_key,
+                &token_a_key,
+                &mut token_a_account,
+                a_amount_out,
+                b_amount_in,
+            )
+            .unwrap();
+        let token_a = StateWithExtensions::<Account>::unpack(&token_a_account.data).unwrap();
+        assert_eq!(token_a.base.amount, initial_a - a_amount_in + a_amount_out);
+        let token_b = StateWithExtensions::<Account>::unpack(&token_b_account.data).unwrap();
+        assert_eq!(token_b.base.amount, initial_b + b_amount_out - b_amount_in);
+        let swap_token_a =
+            StateWithExtensions::<Account>::unpack(&accounts.token_a_account.data).unwrap();
+        assert_eq!(
+            swap_token_a.base.amount,
+            swap_token_a_amount - a_amount_sent
+        );
+    }
 }
@@ -3,6 +3,7 @@
 use crate::{
     curve::{base::SwapCurve, fees::Fees},
     error::SwapError,
+    oracle::PriceOracle,
 };
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use enum_dispatch::enum_dispatch;
@@ -10,6 +11,7 @@ use solana_program::{
     account_info::AccountInfo,
     msg,
     program_error::ProgramError,
+    program_option::COption,
     program_pack::{IsInitialized, Pack, Sealed},
     pubkey::Pubkey,
 };
@@ -50,13 +52,25 @@ pub trait SwapState {
     fn fees(&self) -> &Fees;
     /// Curve associated with swap
     fn swap_curve(&self) -> &SwapCurve;
+
+    /// Authority allowed to update fees and pause the swap, if any
+    fn admin(&self) -> COption<Pubkey>;
+    /// Are swaps and deposits currently rejected by the swap
+    fn is_paused(&self) -> bool;
+
+    /// Cumulative prices of the swap, if it tracks them
+    fn price_oracle(&self) -> Option<&PriceOracle>;
 }
 
 /// All versions of SwapState
 #[enum_dispatch(SwapState)]
 pub enum SwapVersion {
-    /// Latest version, used for all new swaps
+    /// Original version, without an admin authority
     SwapV1,
+    /// Version with an admin authority, without a price oracle
+    SwapV2,
+    /// Latest version, used for all new swaps
+    SwapV3,
 }
 
 /// SwapVersion does not implement program_pack::Pack because there are size
@@ -64,7 +78,7 @@ pub enum SwapVersion {
 /// special implementations are provided here
 impl SwapVersion {
     /// Size of the latest version of the SwapState
-    pub const LATEST_LEN: usize = 1 + SwapV1::LEN; // add one for the version enum
+    pub const LATEST_LEN: usize = 1 + SwapV3::LEN; // add one for the version enum
 
     /// Pack a swap into a byte array, based on its version
     pub fn pack(src: Self, dst: &mut [u8]) -> Result<(), ProgramError> {
@@ -73,6 +87,14 @@ impl SwapVersion {
                 dst[0] = 1;
                 SwapV1::pack(swap_info, &mut dst[1..])
             }
+            Self::SwapV2(swap_info) => {
+                dst[0] = 2;
+                SwapV2::pack(swap_info, &mut dst[1..])
+            }
+            Self::SwapV3(swap_info) => {
+                dst[0] = 3;
+                SwapV3::pack(swap_info, &mut dst[1..])
+            }
         }
     }
 
@@ -84,6 +106,8 @@ impl SwapVersion {
             .ok_or(ProgramError::InvalidAccountData)?;
         match version {
             1 => Ok(Arc::new(SwapV1::unpack(rest)?)),
+            2 => Ok(Arc::new(SwapV2::unpack(rest)?)),
+            3 => Ok(Arc::new(SwapV3::unpack(rest)?)),
             _ => Err(ProgramError::UninitializedAccount),
         }
     }
@@ -177,22 +201,7 @@ impl SwapState for SwapV1 {
     }
 
     fn check_pool_fee_info(&self, pool_fee_info: &AccountInfo) -> Result<(), ProgramError> {
-        let data = &pool_fee_info.data.borrow();
-        let token_account =
-            StateWithExtensions::<Account>::unpack(data).map_err(|err| match err {
-                ProgramError::InvalidAccountData | ProgramError::UninitializedAccount => {
-                    SwapError::InvalidFeeAccount.into()
-                }
-                _ => err,
-            })?;
-        if pool_fee_info.owner != &self.token_program_id
-            || token_account.base.state != AccountState::Initialized
-            || token_account.base.mint != self.pool_mint
-        {
-            msg!("Pool fee account is not owned by token program, is not initialized, or does not match stake pool's mint");
-            return Err(SwapError::InvalidFeeAccount.into());
-        }
-        Ok(())
+        check_pool_fee_info(&self.token_program_id, &self.pool_mint, pool_fee_info)
     }
 
     fn fees(&self) -> &Fees {
@@ -202,6 +211,18 @@ impl SwapState for SwapV1 {
     fn swap_curve(&self) -> &SwapCurve {
         &self.swap_curve
     }
+
+    fn admin(&self) -> COption<Pubkey> {
+        COption::None
+    }
+
+    fn is_paused(&self) -> bool {
+        false
+    }
+
+    fn price_oracle(&self) -> Option<&PriceOracle> {
+        None
+    }
 }
 
 impl Sealed for SwapV1 {}
@@ -216,30 +237,7 @@ impl Pack for SwapV1 {
 
     fn pack_into_slice(&self, output: &mut [u8]) {
         let output = array_mut_ref![output, 0, 323];
-        let (
-            is_initialized,
-            bump_seed,
-            token_program_id,
-            token_a,
-            token_b,
-            pool_mint,
-            token_a_mint,
-            token_b_mint,
-            pool_fee_account,
-            fees,
-            swap_curve,
-        ) = mut_array_refs![output, 1, 1, 32, 32, 32, 32, 32, 32, 32, 64, 33];
-        is_initialized[0] = self.is_initialized as u8;
-        bump_seed[0] = self.bump_seed;
-        token_program_id.copy_from_slice(self.token_program_id.as_ref());
-        token_a.copy_from_slice(self.token_a.as_ref());
-        token_b.copy_from_slice(self.token_b.as_ref());
-        pool_mint.copy_from_slice(self.pool_mint.as_ref());
-        token_a_mint.copy_from_slice(self.token_a_mint.as_ref());
-        token_b_mint.copy_from_slice(self.token_b_mint.as_ref());
-        pool_fee_account.copy_from_slice(self.pool_fee_account.as_ref());
-        self.fees.pack_into_slice(&mut fees[..]);
-        self.swap_curve.pack_into_slice(&mut swap_curve[..]);
+        pack_swap_v1_fields(self, output);
     }
 
     /// Unpacks a byte buffer into a [SwapV1](struct.SwapV1.html).
@@ -279,6 +277,399 @@ impl Pack for SwapV1 {
     }
 }
 
+/// Program state with an admin authority, allowed to update the fees and to
+/// pause swaps and deposits.  The layout starts with all of the fields of
+/// `SwapV1`.
+#[repr(C)]
+#[derive(Debug, Default, PartialEq)]
+pub struct SwapV2 {
+    /// Initialized state.
+    pub is_initialized: bool,
+    /// Bump seed used in program address.
+    /// The program address is created deterministically with the bump seed,
+    /// swap program id, and swap account pubkey.  This program address has
+    /// authority over the swap's token A account, token B account, and pool
+    /// token mint.
+    pub bump_seed: u8,
+
+    /// Program ID of the tokens being exchanged.
+    pub token_program_id: Pubkey,
+
+    /// Token A
+    pub token_a: Pubkey,
+    /// Token B
+    pub token_b: Pubkey,
+
+    /// Pool tokens are issued when A or B tokens are deposited.
+    /// Pool tokens can be withdrawn back to the original A or B token.
+    pub pool_mint: Pubkey,
+
+    /// Mint information for token A
+    pub token_a_mint: Pubkey,
+    /// Mint information for token B
+    pub token_b_mint: Pubkey,
+
+    /// Pool token account to receive trading and / or withdrawal fees
+    pub pool_fee_account: Pubkey,
+
+    /// All fee information
+    pub fees: Fees,
+
+    /// Swap curve parameters, to be unpacked and used by the SwapCurve, which
+    /// calculates swaps, deposits, and withdrawals
+    pub swap_curve: SwapCurve,
+
+    /// Authority allowed to update the fees and pause the swap.  If not
+    /// present, the swap can never be changed after initialization.
+    pub admin: COption<Pubkey>,
+    /// While paused, swaps and deposits are rejected, but withdrawals still
+    /// work
+    pub is_paused: bool,
+}
+
+impl SwapState for SwapV2 {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    fn bump_seed(&self) -> u8 {
+        self.bump_seed
+    }
+
+    fn token_program_id(&self) -> &Pubkey {
+        &self.token_program_id
+    }
+
+    fn token_a_account(&self) -> &Pubkey {
+        &self.token_a
+    }
+
+    fn token_b_account(&self) -> &Pubkey {
+        &self.token_b
+    }
+
+    fn pool_mint(&self) -> &Pubkey {
+        &self.pool_mint
+    }
+
+    fn token_a_mint(&self) -> &Pubkey {
+        &self.token_a_mint
+    }
+
+    fn token_b_mint(&self) -> &Pubkey {
+        &self.token_b_mint
+    }
+
+    fn pool_fee_account(&self) -> &Pubkey {
+        &self.pool_fee_account
+    }
+
+    fn check_pool_fee_info(&self, pool_fee_info: &AccountInfo) -> Result<(), ProgramError> {
+        check_pool_fee_info(&self.token_program_id, &self.pool_mint, pool_fee_info)
+    }
+
+    fn fees(&self) -> &Fees {
+        &self.fees
+    }
+
+    fn swap_curve(&self) -> &SwapCurve {
+        &self.swap_curve
+    }
+
+    fn admin(&self) -> COption<Pubkey> {
+        self.admin
+    }
+
+    fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    fn price_oracle(&self) -> Option<&PriceOracle> {
+        None
+    }
+}
+
+impl Sealed for SwapV2 {}
+impl IsInitialized for SwapV2 {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for SwapV2 {
+    const LEN: usize = 360;
+
+    fn pack_into_slice(&self, output: &mut [u8]) {
+        let output = array_mut_ref![output, 0, 360];
+        pack_swap_v2_fields(self, output);
+    }
+
+    /// Unpacks a byte buffer into a [SwapV2](struct.SwapV2.html).
+    fn unpack_from_slice(input: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![input, 0, 360];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (swap_v1, admin, is_paused) = array_refs![input, 323, 36, 1];
+        let swap_v1 = SwapV1::unpack_from_slice(swap_v1)?;
+        Ok(Self {
+            is_initialized: swap_v1.is_initialized,
+            bump_seed: swap_v1.bump_seed,
+            token_program_id: swap_v1.token_program_id,
+            token_a: swap_v1.token_a,
+            token_b: swap_v1.token_b,
+            pool_mint: swap_v1.pool_mint,
+            token_a_mint: swap_v1.token_a_mint,
+            token_b_mint: swap_v1.token_b_mint,
+            pool_fee_account: swap_v1.pool_fee_account,
+            fees: swap_v1.fees,
+            swap_curve: swap_v1.swap_curve,
+            admin: unpack_coption_key(admin)?,
+            is_paused: match is_paused {
+                [0] => false,
+                [1] => true,
+                _ => return Err(ProgramError::InvalidAccountData),
+            },
+        })
+    }
+}
+
+/// Program state with an admin authority and a price oracle, accumulating the
+/// prices of the pool over time.  The layout starts with all of the fields of
+/// `SwapV2`.
+#[repr(C)]
+#[derive(Debug, Default, PartialEq)]
+pub struct SwapV3 {
+    /// Initialized state.
+    pub is_initialized: bool,
+    /// Bump seed used in program address.
+    /// The program address is created deterministically with the bump seed,
+    /// swap program id, and swap account pubkey.  This program address has
+    /// authority over the swap's token A account, token B account, and pool
+    /// token mint.
+    pub bump_seed: u8,
+
+    /// Program ID of the tokens being exchanged.
+    pub token_program_id: Pubkey,
+
+    /// Token A
+    pub token_a: Pubkey,
+    /// Token B
+    pub token_b: Pubkey,
+
+    /// Pool tokens are issued when A or B tokens are deposited.
+    /// Pool tokens can be withdrawn back to the original A or B token.
+    pub pool_mint: Pubkey,
+
+    /// Mint information for token A
+    pub token_a_mint: Pubkey,
+    /// Mint information for token B
+    pub token_b_mint: Pubkey,
+
+    /// Pool token account to receive trading and / or withdrawal fees
+    pub pool_fee_account: Pubkey,
+
+    /// All fee information
+    pub fees: Fees,
+
+    /// Swap curve parameters, to be unpacked and used by the SwapCurve, which
+    /// calculates swaps, deposits, and withdrawals
+    pub swap_curve: SwapCurve,
+
+    /// Authority allowed to update the fees and pause the swap.  If not
+    /// present, the swap can never be changed after initialization.
+    pub admin: COption<Pubkey>,
+    /// While paused, swaps and deposits are rejected, but withdrawals still
+    /// work
+    pub is_paused: bool,
+
+    /// Cumulative prices, updated before every swap, deposit, and withdrawal
+    pub price_oracle: PriceOracle,
+}
+
+impl SwapState for SwapV3 {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    fn bump_seed(&self) -> u8 {
+        self.bump_seed
+    }
+
+    fn token_program_id(&self) -> &Pubkey {
+        &self.token_program_id
+    }
+
+    fn token_a_account(&self) -> &Pubkey {
+        &self.token_a
+    }
+
+    fn token_b_account(&self) -> &Pubkey {
+        &self.token_b
+    }
+
+    fn pool_mint(&self) -> &Pubkey {
+        &self.pool_mint
+    }
+
+    fn token_a_mint(&self) -> &Pubkey {
+        &self.token_a_mint
+    }
+
+    fn token_b_mint(&self) -> &Pubkey {
+        &self.token_b_mint
+    }
+
+    fn pool_fee_account(&self) -> &Pubkey {
+        &self.pool_fee_account
+    }
+
+    fn check_pool_fee_info(&self, pool_fee_info: &AccountInfo) -> Result<(), ProgramError> {
+        check_pool_fee_info(&self.token_program_id, &self.pool_mint, pool_fee_info)
+    }
+
+    fn fees(&self) -> &Fees {
+        &self.fees
+    }
+
+    fn swap_curve(&self) -> &SwapCurve {
+        &self.swap_curve
+    }
+
+    fn admin(&self) -> COption<Pubkey> {
+        self.admin
+    }
+
+    fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    fn price_oracle(&self) -> Option<&PriceOracle> {
+        Some(&self.price_oracle)
+    }
+}
+
+impl Sealed for SwapV3 {}
+impl IsInitialized for SwapV3 {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for SwapV3 {
+    const LEN: usize = 400;
+
+    fn pack_into_slice(&self, output: &mut [u8]) {
+        let output = array_mut_ref![output, 0, 400];
+        let (swap_v2, price_oracle) = mut_array_refs![output, 360, 40];
+        pack_swap_v2_fields(self, swap_v2);
+        self.price_oracle.pack_into_slice(&mut price_oracle[..]);
+    }
+
+    /// Unpacks a byte buffer into a [SwapV3](struct.SwapV3.html).
+    fn unpack_from_slice(input: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![input, 0, 400];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (swap_v2, price_oracle) = array_refs![input, 360, 40];
+        let swap_v2 = SwapV2::unpack_from_slice(swap_v2)?;
+        Ok(Self {
+            is_initialized: swap_v2.is_initialized,
+            bump_seed: swap_v2.bump_seed,
+            token_program_id: swap_v2.token_program_id,
+            token_a: swap_v2.token_a,
+            token_b: swap_v2.token_b,
+            pool_mint: swap_v2.pool_mint,
+            token_a_mint: swap_v2.token_a_mint,
+            token_b_mint: swap_v2.token_b_mint,
+            pool_fee_account: swap_v2.pool_fee_account,
+            fees: swap_v2.fees,
+            swap_curve: swap_v2.swap_curve,
+            admin: swap_v2.admin,
+            is_paused: swap_v2.is_paused,
+            price_oracle: PriceOracle::unpack_from_slice(price_oracle)?,
+        })
+    }
+}
+
+/// Packs the fields of `SwapV1`, which start the layout of all later versions
+fn pack_swap_v1_fields(swap: &dyn SwapState, output: &mut [u8; 323]) {
+    let (
+        is_initialized,
+        bump_seed,
+        token_program_id,
+        token_a,
+        token_b,
+        pool_mint,
+        token_a_mint,
+        token_b_mint,
+        pool_fee_account,
+        fees,
+        swap_curve,
+    ) = mut_array_refs![output, 1, 1, 32, 32, 32, 32, 32, 32, 32, 64, 33];
+    is_initialized[0] = swap.is_initialized() as u8;
+    bump_seed[0] = swap.bump_seed();
+    token_program_id.copy_from_slice(swap.token_program_id().as_ref());
+    token_a.copy_from_slice(swap.token_a_account().as_ref());
+    token_b.copy_from_slice(swap.token_b_account().as_ref());
+    pool_mint.copy_from_slice(swap.pool_mint().as_ref());
+    token_a_mint.copy_from_slice(swap.token_a_mint().as_ref());
+    token_b_mint.copy_from_slice(swap.token_b_mint().as_ref());
+    pool_fee_account.copy_from_slice(swap.pool_fee_account().as_ref());
+    swap.fees().pack_into_slice(&mut fees[..]);
+    swap.swap_curve().pack_into_slice(&mut swap_curve[..]);
+}
+
+/// Packs the fields of `SwapV2`, which start the layout of all later versions
+fn pack_swap_v2_fields(swap: &dyn SwapState, output: &mut [u8; 360]) {
+    let (swap_v1, admin, is_paused) = mut_array_refs![output, 323, 36, 1];
+    pack_swap_v1_fields(swap, swap_v1);
+    pack_coption_key(&swap.admin(), admin);
+    is_paused[0] = swap.is_paused() as u8;
+}
+
+fn check_pool_fee_info(
+    token_program_id: &Pubkey,
+    pool_mint: &Pubkey,
+    pool_fee_info: &AccountInfo,
+) -> Result<(), ProgramError> {
+    let data = &pool_fee_info.data.borrow();
+    let token_account = StateWithExtensions::<Account>::unpack(data).map_err(|err| match err {
+        ProgramError::InvalidAccountData | ProgramError::UninitializedAccount => {
+            SwapError::InvalidFeeAccount.into()
+        }
+        _ => err,
+    })?;
+    if pool_fee_info.owner != token_program_id
+        || token_account.base.state != AccountState::Initialized
+        || token_account.base.mint != *pool_mint
+    {
+        msg!("Pool fee account is not owned by token program, is not initialized, or does not match stake pool's mint");
+        return Err(SwapError::InvalidFeeAccount.into());
+    }
+    Ok(())
+}
+
+fn pack_coption_key(src: &COption<Pubkey>, dst: &mut [u8; 36]) {
+    let (tag, body) = mut_array_refs![dst, 4, 32];
+    match src {
+        COption::Some(key) => {
+            *tag = [1, 0, 0, 0];
+            body.copy_from_slice(key.as_ref());
+        }
+        COption::None => {
+            *tag = [0; 4];
+            *body = [0; 32];
+        }
+    }
+}
+
+fn unpack_coption_key(src: &[u8; 36]) -> Result<COption<Pubkey>, ProgramError> {
+    let (tag, body) = array_refs![src, 4, 32];
+    match *tag {
+        [0, 0, 0, 0] => Ok(COption::None),
+        [1, 0, 0, 0] => Ok(COption::Some(Pubkey::new_from_array(*body))),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,6 +696,12 @@ mod tests {
     const TEST_TOKEN_A_MINT: Pubkey = Pubkey::new_from_array([5u8; 32]);
     const TEST_TOKEN_B_MINT: Pubkey = Pubkey::new_from_array([6u8; 32]);
     const TEST_POOL_FEE_ACCOUNT: Pubkey = Pubkey::new_from_array([7u8; 32]);
+    const TEST_ADMIN: Pubkey = Pubkey::new_from_array([8u8; 32]);
+    const TEST_PRICE_ORACLE: PriceOracle = PriceOracle {
+        price_a_cumulative: 1_000_000_000_000_000,
+        price_b_cumulative: u128::MAX,
+        last_update_timestamp: 1_650_000_000,
+    };
 
     const TEST_CURVE_TYPE: u8 = 2;
     const TEST_TOKEN_B_OFFSET: u64 = 1_000_000_000;
@@ -320,7 +717,7 @@ mod tests {
             curve_type,
             calculator,
         };
-        let swap_info = SwapVersion::SwapV1(SwapV1 {
+        let swap_info = SwapVersion::SwapV3(SwapV3 {
             is_initialized: true,
             bump_seed: TEST_BUMP_SEED,
             token_program_id: TEST_TOKEN_PROGRAM_ID,
@@ -332,6 +729,9 @@ mod tests {
             pool_fee_account: TEST_POOL_FEE_ACCOUNT,
             fees: TEST_FEES,
             swap_curve: swap_curve.clone(),
+            admin: COption::Some(TEST_ADMIN),
+            is_paused: true,
+            price_oracle: TEST_PRICE_ORACLE,
         });
 
         let mut packed = [0u8; SwapVersion::LATEST_LEN];
@@ -349,6 +749,63 @@ mod tests {
         assert_eq!(*unpacked.pool_fee_account(), TEST_POOL_FEE_ACCOUNT);
         assert_eq!(*unpacked.fees(), TEST_FEES);
         assert_eq!(*unpacked.swap_curve(), swap_curve);
+        assert_eq!(unpacked.admin(), COption::Some(TEST_ADMIN));
+        assert!(unpacked.is_paused());
+        assert_eq!(unpacked.price_oracle(), Some(&TEST_PRICE_ORACLE));
+
+        // V2 swaps still unpack, without a price oracle
+        let swap_info = SwapVersion::SwapV2(SwapV2 {
+            is_initialized: true,
+            bump_seed: TEST_BUMP_SEED,
+            token_program_id: TEST_TOKEN_PROGRAM_ID,
+            token_a: TEST_TOKEN_A,
+            token_b: TEST_TOKEN_B,
+            pool_mint: TEST_POOL_MINT,
+            token_a_mint: TEST_TOKEN_A_MINT,
+            token_b_mint: TEST_TOKEN_B_MINT,
+            pool_fee_account: TEST_POOL_FEE_ACCOUNT,
+            fees: TEST_FEES,
+            swap_curve: swap_curve.clone(),
+            admin: COption::Some(TEST_ADMIN),
+            is_paused: true,
+        });
+
+        let mut packed = [0u8; 1 + SwapV2::LEN];
+        SwapVersion::pack(swap_info, &mut packed).unwrap();
+        let unpacked = SwapVersion::unpack(&packed).unwrap();
+
+        assert!(unpacked.is_initialized());
+        assert_eq!(*unpacked.pool_mint(), TEST_POOL_MINT);
+        assert_eq!(unpacked.admin(), COption::Some(TEST_ADMIN));
+        assert!(unpacked.is_paused());
+        assert_eq!(unpacked.price_oracle(), None);
+
+        // V1 swaps still unpack, without an admin
+        let swap_info = SwapVersion::SwapV1(SwapV1 {
+            is_initialized: true,
+            bump_seed: TEST_BUMP_SEED,
+            token_program_id: TEST_TOKEN_PROGRAM_ID,
+            token_a: TEST_TOKEN_A,
+            token_b: TEST_TOKEN_B,
+            pool_mint: TEST_POOL_MINT,
+            token_a_mint: TEST_TOKEN_A_MINT,
+            token_b_mint: TEST_TOKEN_B_MINT,
+            pool_fee_account: TEST_POOL_FEE_ACCOUNT,
+            fees: TEST_FEES,
+            swap_curve: swap_curve.clone(),
+        });
+
+        let mut packed = [0u8; 1 + SwapV1::LEN];
+        SwapVersion::pack(swap_info, &mut packed).unwrap();
+        let unpacked = SwapVersion::unpack(&packed).unwrap();
+
+        assert!(unpacked.is_initialized());
+        assert_eq!(*unpacked.pool_mint(), TEST_POOL_MINT);
+        assert_eq!(*unpacked.fees(), TEST_FEES);
+        assert_eq!(*unpacked.swap_curve(), swap_curve);
+        assert_eq!(unpacked.admin(), COption::None);
+        assert!(!unpacked.is_paused());
+        assert_eq!(unpacked.price_oracle(), None);
     }
 
     #[test]
@@ -407,4 +864,112 @@ mod tests {
         let err = SwapV1::unpack(&packed).unwrap_err();
         assert_eq!(err, ProgramError::UninitializedAccount);
     }
+
+    #[test]
+    fn swap_v2_pack() {
+        let curve_type = TEST_CURVE_TYPE.try_into().unwrap();
+        let calculator = Arc::new(TEST_CURVE);
+        let swap_curve = SwapCurve {
+            curve_type,
+            calculator,
+        };
+        let swap_info = SwapV2 {
+            is_initialized: true,
+            bump_seed: TEST_BUMP_SEED,
+            token_program_id: TEST_TOKEN_PROGRAM_ID,
+            token_a: TEST_TOKEN_A,
+            token_b: TEST_TOKEN_B,
+            pool_mint: TEST_POOL_MINT,
+            token_a_mint: TEST_TOKEN_A_MINT,
+            token_b_mint: TEST_TOKEN_B_MINT,
+            pool_fee_account: TEST_POOL_FEE_ACCOUNT,
+            fees: TEST_FEES,
+            swap_curve,
+            admin: COption::Some(TEST_ADMIN),
+            is_paused: true,
+        };
+
+        let mut packed = [0u8; SwapV2::LEN];
+        SwapV2::pack_into_slice(&swap_info, &mut packed);
+        let unpacked = SwapV2::unpack(&packed).unwrap();
+        assert_eq!(swap_info, unpacked);
+
+        // the layout extends SwapV1
+        let mut expect = [0u8; SwapV1::LEN];
+        SwapV1::unpack(&packed[..SwapV1::LEN])
+            .unwrap()
+            .pack_into_slice(&mut expect);
+        assert_eq!(packed[..SwapV1::LEN], expect);
+        let mut expect = vec![1u8, 0, 0, 0];
+        expect.extend_from_slice(&TEST_ADMIN.to_bytes());
+        expect.push(1);
+        assert_eq!(packed[SwapV1::LEN..], expect[..]);
+
+        let swap_info = SwapV2 {
+            admin: COption::None,
+            is_paused: false,
+            ..swap_info
+        };
+        SwapV2::pack_into_slice(&swap_info, &mut packed);
+        assert_eq!(packed[SwapV1::LEN..], [0u8; 37]);
+        let unpacked = SwapV2::unpack(&packed).unwrap();
+        assert_eq!(swap_info, unpacked);
+
+        let packed = [0u8; SwapV2::LEN];
+        let swap_info: SwapV2 = Default::default();
+        let unpack_unchecked = SwapV2::unpack_unchecked(&packed).unwrap();
+        assert_eq!(unpack_unchecked, swap_info);
+        let err = SwapV2::unpack(&packed).unwrap_err();
+        assert_eq!(err, ProgramError::UninitializedAccount);
+    }
+
+    #[test]
+    fn swap_v3_pack() {
+        let curve_type = TEST_CURVE_TYPE.try_into().unwrap();
+        let calculator = Arc::new(TEST_CURVE);
+        let swap_curve = SwapCurve {
+            curve_type,
+            calculator,
+        };
+        let swap_info = SwapV3 {
+            is_initialized: true,
+            bump_seed: TEST_BUMP_SEED,
+            token_program_id: TEST_TOKEN_PROGRAM_ID,
+            token_a: TEST_TOKEN_A,
+            token_b: TEST_TOKEN_B,
+            pool_mint: TEST_POOL_MINT,
+            token_a_mint: TEST_TOKEN_A_MINT,
+            token_b_mint: TEST_TOKEN_B_MINT,
+            pool_fee_account: TEST_POOL_FEE_ACCOUNT,
+            fees: TEST_FEES,
+            swap_curve,
+            admin: COption::Some(TEST_ADMIN),
+            is_paused: true,
+            price_oracle: TEST_PRICE_ORACLE,
+        };
+
+        let mut packed = [0u8; SwapV3::LEN];
+        SwapV3::pack_into_slice(&swap_info, &mut packed);
+        let unpacked = SwapV3::unpack(&packed).unwrap();
+        assert_eq!(swap_info, unpacked);
+
+        // the layout extends SwapV2
+        let mut expect = [0u8; SwapV2::LEN];
+        SwapV2::unpack(&packed[..SwapV2::LEN])
+            .unwrap()
+            .pack_into_slice(&mut expect);
+        assert_eq!(packed[..SwapV2::LEN], expect);
+        let mut expect = vec![];
+        expect.extend_from_slice(&TEST_PRICE_ORACLE.price_a_cumulative.to_le_bytes());
+        expect.extend_from_slice(&TEST_PRICE_ORACLE.price_b_cumulative.to_le_bytes());
+        expect.extend_from_slice(&TEST_PRICE_ORACLE.last_update_timestamp.to_le_bytes());
+        assert_eq!(packed[SwapV2::LEN..], expect[..]);
+
+        let packed = [0u8; SwapV3::LEN];
+        let swap_info: SwapV3 = Default::default();
+        let unpack_unchecked = SwapV3::unpack_unchecked(&packed).unwrap();
+        assert_eq!(unpack_unchecked, swap_info);
+        let err = SwapV3::unpack(&packed).unwrap_err();
+        assert_eq!(err, ProgramError::UninitializedAccount);
+    }
 }
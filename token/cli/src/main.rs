@@ -37,17 +37,35 @@ use solana_sdk::{
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 use spl_token_2022::{
     extension::{
-        interest_bearing_mint::InterestBearingConfig, memo_transfer::MemoTransfer,
-        mint_close_authority::MintCloseAuthority, ExtensionType, StateWithExtensionsOwned,
+        confidential_transfer::{self, ConfidentialTransferAccount, ConfidentialTransferMint},
+        cpi_guard::CpiGuard,
+        default_account_state::DefaultAccountState,
+        interest_bearing_mint::InterestBearingConfig,
+        memo_transfer::MemoTransfer,
+        mint_close_authority::MintCloseAuthority,
+        pausable::PausableConfig,
+        permanent_delegate::PermanentDelegate,
+        scaled_ui_amount::ScaledUiAmountConfig,
+        token_metadata::TokenMetadata,
+        transfer_fee::TransferFeeConfig,
+        transfer_hook::TransferHook,
+        ExtensionType, StateWithExtensionsOwned,
     },
     instruction::*,
-    state::{Account, Mint},
+    solana_zk_token_sdk::encryption::{
+        auth_encryption::{AeCiphertext, AeKey},
+        elgamal::{ElGamalCiphertext, ElGamalKeypair, ElGamalPubkey},
+    },
+    state::{Account, AccountState, Mint},
 };
 use spl_token_client::{
     client::{ProgramRpcClientSendTransaction, RpcClientResponse},
     token::{ExtensionInitializationParams, Token},
 };
-use std::{collections::HashMap, fmt, fmt::Display, process::exit, str::FromStr, sync::Arc};
+use std::{
+    collections::HashMap, convert::TryInto, fmt, fmt::Display, process::exit, str::FromStr,
+    sync::Arc,
+};
 use strum_macros::{EnumString, IntoStaticStr};
 
 mod config;
@@ -110,6 +128,9 @@ pub enum CommandName {
     CreateMultisig,
     Authorize,
     SetInterestRate,
+    SetUiAmountMultiplier,
+    Pause,
+    Resume,
     Transfer,
     Burn,
     Mint,
@@ -130,6 +151,22 @@ pub enum CommandName {
     SyncNative,
     EnableRequiredTransferMemos,
     DisableRequiredTransferMemos,
+    EnableCpiGuard,
+    DisableCpiGuard,
+    ConfigureConfidentialTransferAccount,
+    ApproveConfidentialTransferAccount,
+    DepositConfidentialTokens,
+    WithdrawConfidentialTokens,
+    ConfidentialTransfer,
+    ApplyPendingBalance,
+    EnableConfidentialCredits,
+    DisableConfidentialCredits,
+    ConfidentialBalance,
+    SetTransferFee,
+    WithdrawWithheldTokens,
+    Harvest,
+    UpdateDefaultAccountState,
+    Reallocate,
 }
 impl fmt::Display for CommandName {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -365,8 +402,16 @@ async fn command_create_token(
     authority: Pubkey,
     enable_freeze: bool,
     enable_close: bool,
+    enable_non_transferable: bool,
+    enable_permanent_delegate: bool,
+    enable_pausable: bool,
     memo: Option<String>,
     rate_bps: Option<i16>,
+    ui_amount_multiplier: Option<f64>,
+    default_account_state: Option<AccountState>,
+    transfer_fee: Option<(u16, u64)>,
+    transfer_hook_program_id: Option<Pubkey>,
+    confidential_transfer_auto_approve: Option<bool>,
     bulk_signers: Vec<Arc<dyn Signer>>,
 ) -> CommandResult {
     println_display(
@@ -396,6 +441,60 @@ async fn command_create_token(
         })
     }
 
+    if let Some(multiplier) = ui_amount_multiplier {
+        extensions.push(ExtensionInitializationParams::ScaledUiAmountConfig {
+            authority: Some(authority),
+            multiplier,
+        })
+    }
+
+    if enable_non_transferable {
+        extensions.push(ExtensionInitializationParams::NonTransferable);
+    }
+
+    if enable_permanent_delegate {
+        extensions.push(ExtensionInitializationParams::PermanentDelegate {
+            delegate: authority,
+        });
+    }
+
+    if enable_pausable {
+        extensions.push(ExtensionInitializationParams::PausableConfig { authority });
+    }
+
+    if let Some(state) = default_account_state {
+        if state == AccountState::Frozen && !enable_freeze {
+            return Err("Token requires a freeze authority to default to frozen accounts".into());
+        }
+        extensions.push(ExtensionInitializationParams::DefaultAccountState { state })
+    }
+
+    if let Some((transfer_fee_basis_points, maximum_fee)) = transfer_fee {
+        extensions.push(ExtensionInitializationParams::TransferFeeConfig {
+            transfer_fee_config_authority: Some(authority),
+            withdraw_withheld_authority: Some(authority),
+            transfer_fee_basis_points,
+            maximum_fee,
+        });
+    }
+
+    if let Some(program_id) = transfer_hook_program_id {
+        extensions.push(ExtensionInitializationParams::TransferHook {
+            authority: Some(authority),
+            program_id: Some(program_id),
+        });
+    }
+
+    if let Some(auto_approve) = confidential_transfer_auto_approve {
+        extensions.push(ExtensionInitializationParams::ConfidentialTransferMint {
+            ct_mint: ConfidentialTransferMint {
+                authority,
+                auto_approve_new_accounts: auto_approve.into(),
+                ..ConfidentialTransferMint::default()
+            },
+        });
+    }
+
     if let Some(text) = memo {
         token.with_memo(text, vec![config.default_signer()?.pubkey()]);
     }
@@ -484,6 +583,267 @@ async fn command_set_interest_rate(
     })
 }
 
+async fn command_set_ui_amount_multiplier(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    multiplier_authority: Pubkey,
+    multiplier: f64,
+    effective_timestamp: i64,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+
+    if !config.sign_only {
+        let mint_account = config.get_account_checked(&token_pubkey).await?;
+
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
+            .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+
+        if let Ok(scaled_ui_amount_config) = mint_state.get_extension::<ScaledUiAmountConfig>() {
+            let mint_multiplier_authority_pubkey =
+                Option::<Pubkey>::from(scaled_ui_amount_config.authority);
+
+            if mint_multiplier_authority_pubkey != Some(multiplier_authority) {
+                return Err(format!(
+                    "Mint {} has UI amount multiplier authority {}, but {} was provided",
+                    token_pubkey,
+                    mint_multiplier_authority_pubkey
+                        .map(|pubkey| pubkey.to_string())
+                        .unwrap_or_else(|| "disabled".to_string()),
+                    multiplier_authority
+                )
+                .into());
+            }
+        } else {
+            return Err(
+                format!("Mint {} does not have a UI amount multiplier", token_pubkey).into(),
+            );
+        }
+    }
+
+    println_display(
+        config,
+        format!(
+            "Setting UI amount multiplier for {} to {}, effective at timestamp {}",
+            token_pubkey, multiplier, effective_timestamp
+        ),
+    );
+
+    let res = token
+        .update_multiplier(
+            &multiplier_authority,
+            multiplier,
+            effective_timestamp,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_pause(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    pause_authority: Pubkey,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+    pause: bool,
+) -> CommandResult {
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+
+    if !config.sign_only {
+        let mint_account = config.get_account_checked(&token_pubkey).await?;
+
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
+            .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+
+        if let Ok(pausable_config) = mint_state.get_extension::<PausableConfig>() {
+            let mint_pause_authority_pubkey = Option::<Pubkey>::from(pausable_config.authority);
+
+            if mint_pause_authority_pubkey != Some(pause_authority) {
+                return Err(format!(
+                    "Mint {} has pause authority {}, but {} was provided",
+                    token_pubkey,
+                    mint_pause_authority_pubkey
+                        .map(|pubkey| pubkey.to_string())
+                        .unwrap_or_else(|| "disabled".to_string()),
+                    pause_authority
+                )
+                .into());
+            }
+        } else {
+            return Err(format!("Mint {} is not pausable", token_pubkey).into());
+        }
+    }
+
+    let res = if pause {
+        println_display(config, format!("Pausing token {}", token_pubkey));
+        token.pause(&pause_authority, &bulk_signers).await?
+    } else {
+        println_display(config, format!("Resuming token {}", token_pubkey));
+        token.resume(&pause_authority, &bulk_signers).await?
+    };
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_set_transfer_fee(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    transfer_fee_authority: Pubkey,
+    transfer_fee_basis_points: u16,
+    maximum_fee: f64,
+    mint_decimals: Option<u8>,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let mint_info = config.get_mint_info(&token_pubkey, mint_decimals).await?;
+    let token = token_client_from_config(config, &token_pubkey, Some(mint_info.decimals))?;
+
+    if !config.sign_only {
+        let mint_account = config.get_account_checked(&token_pubkey).await?;
+
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
+            .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+
+        if let Ok(transfer_fee_config) = mint_state.get_extension::<TransferFeeConfig>() {
+            let mint_transfer_fee_authority_pubkey =
+                Option::<Pubkey>::from(transfer_fee_config.transfer_fee_config_authority);
+
+            if mint_transfer_fee_authority_pubkey != Some(transfer_fee_authority) {
+                return Err(format!(
+                    "Mint {} has transfer fee authority {}, but {} was provided",
+                    token_pubkey,
+                    mint_transfer_fee_authority_pubkey
+                        .map(|pubkey| pubkey.to_string())
+                        .unwrap_or_else(|| "disabled".to_string()),
+                    transfer_fee_authority
+                )
+                .into());
+            }
+        } else {
+            return Err(format!("Mint {} does not have a transfer fee", token_pubkey).into());
+        }
+    }
+
+    println_display(
+        config,
+        format!(
+            "Setting transfer fee for {} to {} bps, {} maximum",
+            token_pubkey, transfer_fee_basis_points, maximum_fee
+        ),
+    );
+
+    let maximum_fee = spl_token::ui_amount_to_amount(maximum_fee, mint_info.decimals);
+    let res = token
+        .set_transfer_fee(
+            &transfer_fee_authority,
+            transfer_fee_basis_points,
+            maximum_fee,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_update_default_account_state(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    freeze_authority: Pubkey,
+    new_default_state: AccountState,
+    bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+
+    if !config.sign_only {
+        let mint_account = config.get_account_checked(&token_pubkey).await?;
+
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint_account.data)
+            .map_err(|_| format!("Could not deserialize token mint {}", token_pubkey))?;
+
+        match mint_state.base.freeze_authority {
+            COption::None => {
+                return Err(format!("Mint {} has no freeze authority.", token_pubkey).into())
+            }
+            COption::Some(mint_freeze_authority) => {
+                if mint_freeze_authority != freeze_authority {
+                    return Err(format!(
+                        "Mint {} has a freeze authority {}, {} provided",
+                        token_pubkey, mint_freeze_authority, freeze_authority
+                    )
+                    .into());
+                }
+            }
+        }
+
+        if let Ok(default_account_state) = mint_state.get_extension::<DefaultAccountState>() {
+            if default_account_state.state == u8::from(new_default_state) {
+                let state_string = match new_default_state {
+                    AccountState::Frozen => "frozen",
+                    AccountState::Initialized => "initialized",
+                    _ => unreachable!(),
+                };
+                return Err(format!(
+                    "Mint {} already has default account state {}",
+                    token_pubkey, state_string
+                )
+                .into());
+            }
+        } else {
+            return Err(format!(
+                "Mint {} does not support default account states",
+                token_pubkey
+            )
+            .into());
+        }
+    }
+
+    println_display(
+        config,
+        format!(
+            "Updating default account state for {} to {:?}",
+            token_pubkey, new_default_state
+        ),
+    );
+
+    let res = token
+        .set_default_account_state(&freeze_authority, &new_default_state, &bulk_signers)
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
 async fn command_create_account(
     config: &Config<'_>,
     token_pubkey: Pubkey,
@@ -614,6 +974,10 @@ async fn command_authorize(
         AuthorityType::TransferFeeConfig => "transfer fee authority",
         AuthorityType::WithheldWithdraw => "withdraw withheld authority",
         AuthorityType::InterestRate => "interest rate authority",
+        AuthorityType::PermanentDelegate => "permanent delegate",
+        AuthorityType::TransferHookProgramId => "transfer hook program id authority",
+        AuthorityType::ScaledUiAmount => "UI amount multiplier authority",
+        AuthorityType::Pause => "pause authority",
     };
 
     let (mint_pubkey, previous_authority) = if !config.sign_only {
@@ -651,6 +1015,45 @@ async fn command_authorize(
                         Err(format!("Mint `{}` is not interest-bearing", account))
                     }
                 }
+                AuthorityType::PermanentDelegate => {
+                    if let Ok(permanent_delegate) = mint.get_extension::<PermanentDelegate>() {
+                        Ok(COption::<Pubkey>::from(permanent_delegate.delegate))
+                    } else {
+                        Err(format!(
+                            "Mint `{}` does not support permanent delegate",
+                            account
+                        ))
+                    }
+                }
+                AuthorityType::TransferHookProgramId => {
+                    if let Ok(transfer_hook) = mint.get_extension::<TransferHook>() {
+                        Ok(COption::<Pubkey>::from(transfer_hook.authority))
+                    } else {
+                        Err(format!(
+                            "Mint `{}` does not support a transfer hook program",
+                            account
+                        ))
+                    }
+                }
+                AuthorityType::ScaledUiAmount => {
+                    if let Ok(scaled_ui_amount_config) =
+                        mint.get_extension::<ScaledUiAmountConfig>()
+                    {
+                        Ok(COption::<Pubkey>::from(scaled_ui_amount_config.authority))
+                    } else {
+                        Err(format!(
+                            "Mint `{}` does not have a UI amount multiplier",
+                            account
+                        ))
+                    }
+                }
+                AuthorityType::Pause => {
+                    if let Ok(pausable_config) = mint.get_extension::<PausableConfig>() {
+                        Ok(COption::<Pubkey>::from(pausable_config.authority))
+                    } else {
+                        Err(format!("Mint `{}` is not pausable", account))
+                    }
+                }
             }?;
 
             Ok((account, previous_authority))
@@ -683,7 +1086,11 @@ async fn command_authorize(
                 | AuthorityType::CloseMint
                 | AuthorityType::TransferFeeConfig
                 | AuthorityType::WithheldWithdraw
-                | AuthorityType::InterestRate => Err(format!(
+                | AuthorityType::InterestRate
+                | AuthorityType::PermanentDelegate
+                | AuthorityType::TransferHookProgramId
+                | AuthorityType::ScaledUiAmount
+                | AuthorityType::Pause => Err(format!(
                     "Authority type `{}` not supported for SPL Token accounts",
                     auth_str
                 )),
@@ -1578,11 +1985,16 @@ async fn command_display(config: &Config<'_>, address: Pubkey) -> CommandResult
         }
         Ok(TokenAccountType::Mint(mint)) => {
             let epoch_info = config.rpc_client.get_epoch_info().await?;
+            let token_metadata = StateWithExtensionsOwned::<Mint>::unpack(account_data.data)
+                .and_then(|mint| mint.get_variable_len_extension::<TokenMetadata>())
+                .ok()
+                .map(CliTokenMetadata::from);
             let cli_output = CliMint {
                 address: address.to_string(),
                 epoch: epoch_info.epoch,
                 program_id: config.program_id.to_string(),
                 mint,
+                token_metadata,
             };
 
             Ok(config.output_format.formatted_string(&cli_output))
@@ -1855,458 +2267,1920 @@ async fn command_required_transfer_memos(
     })
 }
 
-struct SignOnlyNeedsFullMintSpec {}
-impl offline::ArgsConfig for SignOnlyNeedsFullMintSpec {
-    fn sign_only_arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
-        arg.requires_all(&[MINT_ADDRESS_ARG.name, MINT_DECIMALS_ARG.name])
-    }
-}
-
-struct SignOnlyNeedsMintDecimals {}
-impl offline::ArgsConfig for SignOnlyNeedsMintDecimals {
-    fn sign_only_arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
-        arg.requires_all(&[MINT_DECIMALS_ARG.name])
+// Both enable_cpi_guard and disable_cpi_guard
+// Switches with enable_guard bool
+async fn command_cpi_guard(
+    config: &Config<'_>,
+    token_account_address: Pubkey,
+    owner: Pubkey,
+    bulk_signers: BulkSigners,
+    enable_guard: bool,
+) -> CommandResult {
+    if config.sign_only {
+        panic!("Config can not be sign only for enabling/disabling CPI Guard.");
     }
-}
 
-struct SignOnlyNeedsMintAddress {}
-impl offline::ArgsConfig for SignOnlyNeedsMintAddress {
-    fn sign_only_arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
-        arg.requires_all(&[MINT_ADDRESS_ARG.name])
-    }
-}
+    let account = config.get_account_checked(&token_account_address).await?;
+    let current_account_len = account.data.len();
 
-struct SignOnlyNeedsDelegateAddress {}
-impl offline::ArgsConfig for SignOnlyNeedsDelegateAddress {
-    fn sign_only_arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
-        arg.requires_all(&[DELEGATE_ADDRESS_ARG.name])
+    let state_with_extension = StateWithExtensionsOwned::<Account>::unpack(account.data)?;
+    let token = token_client_from_config(config, &state_with_extension.base.mint, None)?;
+
+    // Reallocation (if needed)
+    let mut existing_extensions: Vec<ExtensionType> = state_with_extension.get_extension_types()?;
+    if existing_extensions.contains(&ExtensionType::CpiGuard) {
+        let extension_data: bool = state_with_extension
+            .get_extension::<CpiGuard>()?
+            .lock_cpi
+            .into();
+        if extension_data == enable_guard {
+            return Ok(format!(
+                "CPI Guard was already {}",
+                if extension_data {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            ));
+        }
+    } else {
+        existing_extensions.push(ExtensionType::CpiGuard);
+        let needed_account_len = ExtensionType::get_account_len::<Account>(&existing_extensions);
+        if needed_account_len > current_account_len {
+            token
+                .reallocate(
+                    &token_account_address,
+                    &owner,
+                    &[ExtensionType::CpiGuard],
+                    &bulk_signers,
+                )
+                .await?;
+        }
     }
-}
 
-fn minimum_signers_help_string() -> String {
-    format!(
-        "The minimum number of signers required to allow the operation. [{} <= M <= N]",
-        MIN_SIGNERS
-    )
+    let res = if enable_guard {
+        token
+            .enable_cpi_guard(&token_account_address, &owner, &bulk_signers)
+            .await
+    } else {
+        token
+            .disable_cpi_guard(&token_account_address, &owner, &bulk_signers)
+            .await
+    }?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
 }
 
-fn multisig_member_help_string() -> String {
-    format!(
-        "The public keys for each of the N signing members of this account. [{} <= N <= {}]",
-        MIN_SIGNERS, MAX_SIGNERS
-    )
+async fn command_withdraw_withheld_tokens(
+    config: &Config<'_>,
+    destination_token_account: Pubkey,
+    source_token_accounts: Vec<Pubkey>,
+    withdraw_withheld_authority: Pubkey,
+    include_mint: bool,
+    mint_address: Option<Pubkey>,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    if source_token_accounts.is_empty() && !include_mint {
+        return Err("No source token accounts or mint to withdraw withheld tokens from".into());
+    }
+
+    let mint_address = config
+        .check_account(&destination_token_account, mint_address)
+        .await?;
+    let token = token_client_from_config(config, &mint_address, None)?;
+
+    let mut results = vec![];
+    if include_mint {
+        let res = token
+            .withdraw_withheld_tokens_from_mint(
+                &destination_token_account,
+                &withdraw_withheld_authority,
+                &bulk_signers,
+            )
+            .await?;
+        results.push(res);
+    }
+
+    let source_refs = source_token_accounts.iter().collect::<Vec<_>>();
+    // keep each transaction within the account and size limits
+    const MAX_WITHDRAWAL_ACCOUNTS: usize = 25;
+    for sources in source_refs.chunks(MAX_WITHDRAWAL_ACCOUNTS) {
+        let res = token
+            .withdraw_withheld_tokens_from_accounts(
+                &destination_token_account,
+                &withdraw_withheld_authority,
+                sources,
+                &bulk_signers,
+            )
+            .await?;
+        results.push(res);
+    }
+
+    let mut output = String::new();
+    for res in results {
+        let tx_return = finish_tx(config, &res, false).await?;
+        output.push_str(&match tx_return {
+            TransactionReturnData::CliSignature(signature) => {
+                config.output_format.formatted_string(&signature)
+            }
+            TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+                config.output_format.formatted_string(&sign_only_data)
+            }
+        });
+    }
+    Ok(output)
 }
 
-fn is_valid_token_program_id<T>(string: T) -> Result<(), String>
-where
-    T: AsRef<str> + Display,
-{
-    match is_pubkey(string.as_ref()) {
-        Ok(()) => {
-            let program_id = string.as_ref().parse::<Pubkey>().unwrap();
-            if program_id == spl_token_2022::id() || program_id == spl_token::id() {
-                Ok(())
-            } else {
-                Err(format!("Unrecognized token program id: {}", program_id))
+async fn command_harvest(
+    config: &Config<'_>,
+    token_pubkey: Pubkey,
+    source_token_accounts: Vec<Pubkey>,
+) -> CommandResult {
+    let token = token_client_from_config(config, &token_pubkey, None)?;
+
+    println_display(
+        config,
+        format!(
+            "Harvesting withheld tokens from {} accounts into mint {}",
+            source_token_accounts.len(),
+            token_pubkey
+        ),
+    );
+
+    let source_refs = source_token_accounts.iter().collect::<Vec<_>>();
+    // keep each transaction within the account and size limits
+    const MAX_HARVEST_ACCOUNTS: usize = 25;
+    let mut output = String::new();
+    for sources in source_refs.chunks(MAX_HARVEST_ACCOUNTS) {
+        let res = token.harvest_withheld_tokens_to_mint(sources).await?;
+        let tx_return = finish_tx(config, &res, false).await?;
+        output.push_str(&match tx_return {
+            TransactionReturnData::CliSignature(signature) => {
+                config.output_format.formatted_string(&signature)
             }
-        }
-        Err(e) => Err(e),
+            TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+                config.output_format.formatted_string(&sign_only_data)
+            }
+        });
     }
+    Ok(output)
 }
 
-fn app<'a, 'b>(
-    default_decimals: &'a str,
-    minimum_signers_help: &'b str,
-    multisig_member_help: &'b str,
-) -> App<'a, 'b> {
-    App::new(crate_name!())
-        .about(crate_description!())
-        .version(crate_version!())
-        .setting(AppSettings::SubcommandRequiredElseHelp)
-        .arg(
-            Arg::with_name("config_file")
-                .short("C")
-                .long("config")
-                .value_name("PATH")
-                .takes_value(true)
-                .global(true)
-                .help("Configuration file to use"),
-        )
-        .arg(
-            Arg::with_name("verbose")
-                .short("v")
-                .long("verbose")
-                .takes_value(false)
-                .global(true)
-                .help("Show additional information"),
-        )
-        .arg(
-            Arg::with_name("output_format")
-                .long("output")
-                .value_name("FORMAT")
-                .global(true)
-                .takes_value(true)
-                .possible_values(&["json", "json-compact"])
-                .help("Return information in specified output format"),
-        )
-        .arg(
-            Arg::with_name("program_id")
-                .short("p")
-                .long("program-id")
-                .value_name("ADDRESS")
-                .takes_value(true)
-                .global(true)
-                .validator(is_valid_token_program_id)
-                .help("SPL Token program id"),
+async fn command_reallocate(
+    config: &Config<'_>,
+    token_account_address: Pubkey,
+    owner: Pubkey,
+    extension_types: Vec<ExtensionType>,
+    mint_address: Option<Pubkey>,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let mint_address = config
+        .check_account(&token_account_address, mint_address)
+        .await?;
+    let token = token_client_from_config(config, &mint_address, None)?;
+
+    println_display(
+        config,
+        format!(
+            "Reallocating {} for extensions {:?}",
+            token_account_address, extension_types
+        ),
+    );
+
+    let res = token
+        .reallocate(
+            &token_account_address,
+            &owner,
+            &extension_types,
+            &bulk_signers,
         )
-        .arg(
-            Arg::with_name("json_rpc_url")
-                .short("u")
-                .long("url")
-                .value_name("URL_OR_MONIKER")
-                .takes_value(true)
-                .global(true)
-                .validator(is_url_or_moniker)
-                .help(
-                    "URL for Solana's JSON RPC or moniker (or their first letter): \
-                       [mainnet-beta, testnet, devnet, localhost] \
-                    Default from the configuration file."
-                ),
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+/// Derive the ElGamal keypair and authenticated encryption key of a confidential token account
+/// from its owner's signer
+fn confidential_transfer_keys(
+    owner_signer: &dyn Signer,
+    token_account: &Pubkey,
+) -> Result<(ElGamalKeypair, AeKey), Error> {
+    let elgamal_keypair = ElGamalKeypair::new(owner_signer, token_account).map_err(|e| {
+        format!(
+            "Could not derive ElGamal keypair for {} from the owner's signer: {}",
+            token_account, e
         )
-        .arg(fee_payer_arg().global(true))
-        .arg(
-            Arg::with_name("use_unchecked_instruction")
-                .long("use-unchecked-instruction")
-                .takes_value(false)
-                .global(true)
-                .hidden(true)
-                .help("Use unchecked instruction if appropriate. Supports transfer, burn, mint, and approve."),
+    })?;
+    let ae_key = AeKey::new(owner_signer, token_account).map_err(|e| {
+        format!(
+            "Could not derive encryption key for {} from the owner's signer: {}",
+            token_account, e
         )
-        .bench_subcommand()
-        .subcommand(SubCommand::with_name(CommandName::CreateToken.into()).about("Create a new token")
-                .arg(
-                    Arg::with_name("token_keypair")
-                        .value_name("TOKEN_KEYPAIR")
-                        .validator(is_valid_signer)
-                        .takes_value(true)
-                        .index(1)
-                        .help(
-                            "Specify the token keypair. \
-                             This may be a keypair file or the ASK keyword. \
-                             [default: randomly generated keypair]"
-                        ),
-                )
-                .arg(
-                    Arg::with_name("mint_authority")
-                        .long("mint-authority")
-                        .alias("owner")
-                        .value_name("ADDRESS")
-                        .validator(is_valid_pubkey)
-                        .takes_value(true)
-                        .help(
-                            "Specify the mint authority address. \
-                             Defaults to the client keypair address."
-                        ),
-                )
-                .arg(
-                    Arg::with_name("decimals")
-                        .long("decimals")
-                        .validator(is_mint_decimals)
-                        .value_name("DECIMALS")
-                        .takes_value(true)
-                        .default_value(default_decimals)
-                        .help("Number of base 10 digits to the right of the decimal place"),
+    })?;
+    Ok((elgamal_keypair, ae_key))
+}
+
+async fn command_configure_confidential_transfer_account(
+    config: &Config<'_>,
+    token_account_address: Pubkey,
+    owner_signer: Arc<dyn Signer>,
+    mint_address: Option<Pubkey>,
+    maximum_pending_balance_credit_counter: Option<u64>,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let owner = owner_signer.pubkey();
+    let (elgamal_keypair, ae_key) =
+        confidential_transfer_keys(&*owner_signer, &token_account_address)?;
+    let maximum_pending_balance_credit_counter = maximum_pending_balance_credit_counter
+        .unwrap_or(2 << confidential_transfer::MAXIMUM_DEPOSIT_TRANSFER_AMOUNT_BIT_LENGTH);
+
+    let mint_address = config
+        .check_account(&token_account_address, mint_address)
+        .await?;
+    let token = token_client_from_config(config, &mint_address, None)?;
+
+    // Reallocation (if needed), which requires looking up the account
+    if !config.sign_only {
+        let account = config.get_account_checked(&token_account_address).await?;
+        let current_account_len = account.data.len();
+        let state_with_extension = StateWithExtensionsOwned::<Account>::unpack(account.data)?;
+        let mut existing_extensions: Vec<ExtensionType> =
+            state_with_extension.get_extension_types()?;
+        if existing_extensions.contains(&ExtensionType::ConfidentialTransferAccount) {
+            return Ok(format!(
+                "Confidential transfers were already configured for {}",
+                token_account_address
+            ));
+        }
+        existing_extensions.push(ExtensionType::ConfidentialTransferAccount);
+        let needed_account_len = ExtensionType::get_account_len::<Account>(&existing_extensions);
+        if needed_account_len > current_account_len {
+            token
+                .reallocate(
+                    &token_account_address,
+                    &owner,
+                    &[ExtensionType::ConfidentialTransferAccount],
+                    &bulk_signers,
                 )
-                .arg(
-                    Arg::with_name("enable_freeze")
+                .await?;
+        }
+    }
+
+    let res = token
+        .confidential_transfer_configure_token_account_with_pending_counter_and_keypair(
+            &token_account_address,
+            &owner,
+            maximum_pending_balance_credit_counter,
+            elgamal_keypair.public,
+            ae_key.encrypt(0),
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_approve_confidential_transfer_account(
+    config: &Config<'_>,
+    token_account_address: Pubkey,
+    authority: Pubkey,
+    mint_address: Option<Pubkey>,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let mint_address = config
+        .check_account(&token_account_address, mint_address)
+        .await?;
+    let token = token_client_from_config(config, &mint_address, None)?;
+
+    let res = token
+        .confidential_transfer_approve_account(&token_account_address, &authority, &bulk_signers)
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_deposit_confidential_tokens(
+    config: &Config<'_>,
+    token_account_address: Pubkey,
+    owner: Pubkey,
+    ui_amount: f64,
+    mint_address: Option<Pubkey>,
+    mint_decimals: Option<u8>,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    println_display(
+        config,
+        format!(
+            "Deposit {} tokens\n  Account: {}",
+            ui_amount, token_account_address
+        ),
+    );
+
+    let mint_address = config
+        .check_account(&token_account_address, mint_address)
+        .await?;
+    let mint_info = config.get_mint_info(&mint_address, mint_decimals).await?;
+    let amount = spl_token::ui_amount_to_amount(ui_amount, mint_info.decimals);
+
+    let token = token_client_from_config(config, &mint_info.address, Some(mint_info.decimals))?;
+    let res = token
+        .confidential_transfer_deposit(
+            &token_account_address,
+            &owner,
+            amount,
+            mint_info.decimals,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+/// Fetch a token account along with its confidential transfer extension, which is needed to
+/// generate the proofs for withdrawing, transferring, and applying pending balances
+async fn get_confidential_transfer_account(
+    config: &Config<'_>,
+    token_account_address: &Pubkey,
+) -> Result<(Account, ConfidentialTransferAccount), Error> {
+    let account = config.get_account_checked(token_account_address).await?;
+    let state_with_extension = StateWithExtensionsOwned::<Account>::unpack(account.data)
+        .map_err(|_| format!("Could not find token account {}", token_account_address))?;
+    let extension = *state_with_extension
+        .get_extension::<ConfidentialTransferAccount>()
+        .map_err(|_| {
+            format!(
+                "Confidential transfers are not configured for {}",
+                token_account_address
+            )
+        })?;
+    Ok((state_with_extension.base, extension))
+}
+
+async fn command_withdraw_confidential_tokens(
+    config: &Config<'_>,
+    token_account_address: Pubkey,
+    owner_signer: Arc<dyn Signer>,
+    ui_amount: Option<f64>,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    if config.sign_only {
+        return Err(
+            "The available balance must be fetched to withdraw confidential tokens, \
+            which is not possible with --sign-only"
+                .into(),
+        );
+    }
+
+    let owner = owner_signer.pubkey();
+    let (elgamal_keypair, ae_key) =
+        confidential_transfer_keys(&*owner_signer, &token_account_address)?;
+    let (account, extension) =
+        get_confidential_transfer_account(config, &token_account_address).await?;
+    let mint_info = config.get_mint_info(&account.mint, None).await?;
+
+    let available_balance_ciphertext: ElGamalCiphertext = extension
+        .available_balance
+        .try_into()
+        .map_err(|_| "Could not decode the available balance".to_string())?;
+    let decryptable_available_balance: AeCiphertext = extension
+        .decryptable_available_balance
+        .try_into()
+        .map_err(|_| "Could not decode the decryptable available balance".to_string())?;
+    let available_balance = decryptable_available_balance
+        .decrypt(&ae_key)
+        .ok_or("Could not decrypt the available balance")?;
+
+    let amount = match ui_amount {
+        Some(ui_amount) => spl_token::ui_amount_to_amount(ui_amount, mint_info.decimals),
+        None => available_balance,
+    };
+    if amount > available_balance {
+        return Err(format!(
+            "Error: Insufficient funds, available balance is {}",
+            spl_token::amount_to_ui_amount_string_trimmed(available_balance, mint_info.decimals)
+        )
+        .into());
+    }
+    println_display(
+        config,
+        format!(
+            "Withdraw {} tokens\n  Account: {}",
+            spl_token::amount_to_ui_amount_string_trimmed(amount, mint_info.decimals),
+            token_account_address
+        ),
+    );
+
+    let token = token_client_from_config(config, &mint_info.address, Some(mint_info.decimals))?;
+    let res = token
+        .confidential_transfer_withdraw_with_key(
+            &token_account_address,
+            &owner,
+            amount,
+            mint_info.decimals,
+            available_balance,
+            &available_balance_ciphertext,
+            &elgamal_keypair,
+            &ae_key,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_confidential_transfer(
+    config: &Config<'_>,
+    source_account_address: Pubkey,
+    destination_account_address: Pubkey,
+    owner_signer: Arc<dyn Signer>,
+    ui_amount: f64,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    if config.sign_only {
+        return Err(
+            "The available balance must be fetched to transfer confidential tokens, \
+            which is not possible with --sign-only"
+                .into(),
+        );
+    }
+
+    let owner = owner_signer.pubkey();
+    let (elgamal_keypair, ae_key) =
+        confidential_transfer_keys(&*owner_signer, &source_account_address)?;
+    let (source_account, source_extension) =
+        get_confidential_transfer_account(config, &source_account_address).await?;
+    let (destination_account, destination_extension) =
+        get_confidential_transfer_account(config, &destination_account_address).await?;
+    if source_account.mint != destination_account.mint {
+        return Err(format!(
+            "Source {} and destination {} do not hold the same token",
+            source_account_address, destination_account_address
+        )
+        .into());
+    }
+
+    let mint = config.get_account_checked(&source_account.mint).await?;
+    let mint_state = StateWithExtensionsOwned::<Mint>::unpack(mint.data)
+        .map_err(|_| format!("Could not find mint account {}", source_account.mint))?;
+    let decimals = mint_state.base.decimals;
+    let ct_mint = mint_state
+        .get_extension::<ConfidentialTransferMint>()
+        .map_err(|_| {
+            format!(
+                "Confidential transfers are not enabled for mint {}",
+                source_account.mint
+            )
+        })?;
+    let amount = spl_token::ui_amount_to_amount(ui_amount, decimals);
+
+    let available_balance_ciphertext: ElGamalCiphertext = source_extension
+        .available_balance
+        .try_into()
+        .map_err(|_| "Could not decode the available balance".to_string())?;
+    let decryptable_available_balance: AeCiphertext = source_extension
+        .decryptable_available_balance
+        .try_into()
+        .map_err(|_| "Could not decode the decryptable available balance".to_string())?;
+    let available_balance = decryptable_available_balance
+        .decrypt(&ae_key)
+        .ok_or("Could not decrypt the available balance")?;
+    if amount > available_balance {
+        return Err(format!(
+            "Error: Insufficient funds, available balance is {}",
+            spl_token::amount_to_ui_amount_string_trimmed(available_balance, decimals)
+        )
+        .into());
+    }
+
+    let destination_elgamal_pubkey: ElGamalPubkey = destination_extension
+        .encryption_pubkey
+        .try_into()
+        .map_err(|_| "Could not decode the destination encryption key".to_string())?;
+    let auditor_elgamal_pubkey: ElGamalPubkey = ct_mint
+        .auditor_encryption_pubkey
+        .try_into()
+        .map_err(|_| "Could not decode the auditor encryption key".to_string())?;
+
+    println_display(
+        config,
+        format!(
+            "Transfer {} tokens confidentially\n  Sender: {}\n  Recipient: {}",
+            ui_amount, source_account_address, destination_account_address
+        ),
+    );
+
+    let token = token_client_from_config(config, &source_account.mint, Some(decimals))?;
+    let res = if mint_state.get_extension::<TransferFeeConfig>().is_ok() {
+        let withdraw_withheld_authority_elgamal_pubkey: ElGamalPubkey = ct_mint
+            .withdraw_withheld_authority_encryption_pubkey
+            .try_into()
+            .map_err(|_| {
+                "Could not decode the withdraw withheld authority encryption key".to_string()
+            })?;
+        let epoch_info = config.rpc_client.get_epoch_info().await?;
+        token
+            .confidential_transfer_transfer_with_fee_with_key(
+                &source_account_address,
+                &destination_account_address,
+                &owner,
+                amount,
+                available_balance,
+                &available_balance_ciphertext,
+                &destination_elgamal_pubkey,
+                &auditor_elgamal_pubkey,
+                &withdraw_withheld_authority_elgamal_pubkey,
+                &elgamal_keypair,
+                &ae_key,
+                &epoch_info,
+                &bulk_signers,
+            )
+            .await?
+    } else {
+        token
+            .confidential_transfer_transfer_with_key(
+                &source_account_address,
+                &destination_account_address,
+                &owner,
+                amount,
+                available_balance,
+                &available_balance_ciphertext,
+                &destination_elgamal_pubkey,
+                &auditor_elgamal_pubkey,
+                &elgamal_keypair,
+                &ae_key,
+                &bulk_signers,
+            )
+            .await?
+    };
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_apply_pending_balance(
+    config: &Config<'_>,
+    token_account_address: Pubkey,
+    owner_signer: Arc<dyn Signer>,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    if config.sign_only {
+        return Err("The pending balance must be fetched to apply it, \
+            which is not possible with --sign-only"
+            .into());
+    }
+
+    let owner = owner_signer.pubkey();
+    let (elgamal_keypair, ae_key) =
+        confidential_transfer_keys(&*owner_signer, &token_account_address)?;
+    let (account, extension) =
+        get_confidential_transfer_account(config, &token_account_address).await?;
+
+    let token = token_client_from_config(config, &account.mint, None)?;
+    let available_balance = token
+        .confidential_transfer_get_available_balance_with_key(&token_account_address, &ae_key)
+        .await?;
+    let pending_balance = token
+        .confidential_transfer_get_pending_balance_with_key(
+            &token_account_address,
+            &elgamal_keypair,
+        )
+        .await?;
+
+    let res = token
+        .confidential_transfer_apply_pending_balance_with_key(
+            &token_account_address,
+            &owner,
+            available_balance,
+            pending_balance,
+            extension.pending_balance_credit_counter.into(),
+            &ae_key,
+            &bulk_signers,
+        )
+        .await?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+// Both enable_confidential_credits and disable_confidential_credits
+// Switches with enable_credits bool
+async fn command_confidential_credits(
+    config: &Config<'_>,
+    token_account_address: Pubkey,
+    owner: Pubkey,
+    mint_address: Option<Pubkey>,
+    bulk_signers: BulkSigners,
+    enable_credits: bool,
+) -> CommandResult {
+    let mint_address = config
+        .check_account(&token_account_address, mint_address)
+        .await?;
+    let token = token_client_from_config(config, &mint_address, None)?;
+
+    let res = if enable_credits {
+        token
+            .confidential_transfer_enable_balance_credits(
+                &token_account_address,
+                &owner,
+                &bulk_signers,
+            )
+            .await
+    } else {
+        token
+            .confidential_transfer_disable_balance_credits(
+                &token_account_address,
+                &owner,
+                &bulk_signers,
+            )
+            .await
+    }?;
+
+    let tx_return = finish_tx(config, &res, false).await?;
+    Ok(match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    })
+}
+
+async fn command_confidential_balance(
+    config: &Config<'_>,
+    token_account_address: Pubkey,
+    owner_signer: Arc<dyn Signer>,
+) -> CommandResult {
+    let (elgamal_keypair, ae_key) =
+        confidential_transfer_keys(&*owner_signer, &token_account_address)?;
+    let (account, _) = get_confidential_transfer_account(config, &token_account_address).await?;
+    let mint_info = config.get_mint_info(&account.mint, None).await?;
+
+    let token = token_client_from_config(config, &account.mint, None)?;
+    let available_balance = token
+        .confidential_transfer_get_available_balance_with_key(&token_account_address, &ae_key)
+        .await?;
+    let pending_balance = token
+        .confidential_transfer_get_pending_balance_with_key(
+            &token_account_address,
+            &elgamal_keypair,
+        )
+        .await?;
+
+    let cli_confidential_balance = CliConfidentialBalance {
+        address: token_account_address.to_string(),
+        available_balance: spl_token::amount_to_ui_amount_string_trimmed(
+            available_balance,
+            mint_info.decimals,
+        ),
+        pending_balance: spl_token::amount_to_ui_amount_string_trimmed(
+            pending_balance,
+            mint_info.decimals,
+        ),
+        decimals: mint_info.decimals,
+    };
+    Ok(config
+        .output_format
+        .formatted_string(&cli_confidential_balance))
+}
+
+struct SignOnlyNeedsFullMintSpec {}
+impl offline::ArgsConfig for SignOnlyNeedsFullMintSpec {
+    fn sign_only_arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
+        arg.requires_all(&[MINT_ADDRESS_ARG.name, MINT_DECIMALS_ARG.name])
+    }
+}
+
+struct SignOnlyNeedsMintDecimals {}
+impl offline::ArgsConfig for SignOnlyNeedsMintDecimals {
+    fn sign_only_arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
+        arg.requires_all(&[MINT_DECIMALS_ARG.name])
+    }
+}
+
+struct SignOnlyNeedsMintAddress {}
+impl offline::ArgsConfig for SignOnlyNeedsMintAddress {
+    fn sign_only_arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
+        arg.requires_all(&[MINT_ADDRESS_ARG.name])
+    }
+}
+
+struct SignOnlyNeedsDelegateAddress {}
+impl offline::ArgsConfig for SignOnlyNeedsDelegateAddress {
+    fn sign_only_arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
+        arg.requires_all(&[DELEGATE_ADDRESS_ARG.name])
+    }
+}
+
+fn minimum_signers_help_string() -> String {
+    format!(
+        "The minimum number of signers required to allow the operation. [{} <= M <= N]",
+        MIN_SIGNERS
+    )
+}
+
+fn multisig_member_help_string() -> String {
+    format!(
+        "The public keys for each of the N signing members of this account. [{} <= N <= {}]",
+        MIN_SIGNERS, MAX_SIGNERS
+    )
+}
+
+fn is_valid_token_program_id<T>(string: T) -> Result<(), String>
+where
+    T: AsRef<str> + Display,
+{
+    match is_pubkey(string.as_ref()) {
+        Ok(()) => {
+            let program_id = string.as_ref().parse::<Pubkey>().unwrap();
+            if program_id == spl_token_2022::id() || program_id == spl_token::id() {
+                Ok(())
+            } else {
+                Err(format!("Unrecognized token program id: {}", program_id))
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn app<'a, 'b>(
+    default_decimals: &'a str,
+    minimum_signers_help: &'b str,
+    multisig_member_help: &'b str,
+) -> App<'a, 'b> {
+    App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("config_file")
+                .short("C")
+                .long("config")
+                .value_name("PATH")
+                .takes_value(true)
+                .global(true)
+                .help("Configuration file to use"),
+        )
+        .arg(
+            Arg::with_name("verbose")
+                .short("v")
+                .long("verbose")
+                .takes_value(false)
+                .global(true)
+                .help("Show additional information"),
+        )
+        .arg(
+            Arg::with_name("output_format")
+                .long("output")
+                .value_name("FORMAT")
+                .global(true)
+                .takes_value(true)
+                .possible_values(&["json", "json-compact"])
+                .help("Return information in specified output format"),
+        )
+        .arg(
+            Arg::with_name("program_id")
+                .short("p")
+                .long("program-id")
+                .value_name("ADDRESS")
+                .takes_value(true)
+                .global(true)
+                .validator(is_valid_token_program_id)
+                .help("SPL Token program id"),
+        )
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .short("u")
+                .long("url")
+                .value_name("URL_OR_MONIKER")
+                .takes_value(true)
+                .global(true)
+                .validator(is_url_or_moniker)
+                .help(
+                    "URL for Solana's JSON RPC or moniker (or their first letter): \
+                       [mainnet-beta, testnet, devnet, localhost] \
+                    Default from the configuration file."
+                ),
+        )
+        .arg(fee_payer_arg().global(true))
+        .arg(
+            Arg::with_name("use_unchecked_instruction")
+                .long("use-unchecked-instruction")
+                .takes_value(false)
+                .global(true)
+                .hidden(true)
+                .help("Use unchecked instruction if appropriate. Supports transfer, burn, mint, and approve."),
+        )
+        .bench_subcommand()
+        .subcommand(SubCommand::with_name(CommandName::CreateToken.into()).about("Create a new token")
+                .arg(
+                    Arg::with_name("token_keypair")
+                        .value_name("TOKEN_KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .index(1)
+                        .help(
+                            "Specify the token keypair. \
+                             This may be a keypair file or the ASK keyword. \
+                             [default: randomly generated keypair]"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("mint_authority")
+                        .long("mint-authority")
+                        .alias("owner")
+                        .value_name("ADDRESS")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help(
+                            "Specify the mint authority address. \
+                             Defaults to the client keypair address."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("decimals")
+                        .long("decimals")
+                        .validator(is_mint_decimals)
+                        .value_name("DECIMALS")
+                        .takes_value(true)
+                        .default_value(default_decimals)
+                        .help("Number of base 10 digits to the right of the decimal place"),
+                )
+                .arg(
+                    Arg::with_name("enable_freeze")
                         .long("enable-freeze")
                         .takes_value(false)
                         .help(
-                            "Enable the mint authority to freeze token accounts for this mint"
+                            "Enable the mint authority to freeze token accounts for this mint"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("enable_close")
+                        .long("enable-close")
+                        .takes_value(false)
+                        .help(
+                            "Enable the mint authority to close this mint"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("interest_rate")
+                        .long("interest-rate")
+                        .value_name("RATE_BPS")
+                        .takes_value(true)
+                        .help(
+                            "Specify the interest rate in basis points. \
+                            Rate authority defaults to the mint authority."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("ui_amount_multiplier")
+                        .long("ui-amount-multiplier")
+                        .value_name("MULTIPLIER")
+                        .takes_value(true)
+                        .conflicts_with("interest_rate")
+                        .help(
+                            "Specify a multiplier applied to raw amounts to get UI amounts. \
+                            Multiplier authority defaults to the mint authority."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("enable_non_transferable")
+                        .long("enable-non-transferable")
+                        .takes_value(false)
+                        .help(
+                            "Permanently prevent tokens of this mint from being transferred"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("enable_permanent_delegate")
+                        .long("enable-permanent-delegate")
+                        .takes_value(false)
+                        .help(
+                            "Enable the mint authority to act as a permanent delegate \
+                            over every account of this mint"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("enable_pausable")
+                        .long("enable-pausable")
+                        .takes_value(false)
+                        .help(
+                            "Enable the mint authority to pause minting, burning, \
+                            and transferring of this token"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("default_account_state")
+                        .long("default-account-state")
+                        .value_name("STATE")
+                        .takes_value(true)
+                        .possible_values(&["initialized", "frozen"])
+                        .requires("enable_freeze")
+                        .help(
+                            "Specify the state of newly created token accounts. \
+                            Requires --enable-freeze."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("transfer_fee")
+                        .long("transfer-fee")
+                        .value_names(&["FEE_IN_BASIS_POINTS", "MAXIMUM_FEE"])
+                        .takes_value(true)
+                        .number_of_values(2)
+                        .help(
+                            "Charge a fee on every transfer, in basis points of the amount \
+                            transferred, capped at a maximum fee in tokens. Transfer fee \
+                            and withheld withdraw authorities default to the mint authority."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("transfer_hook")
+                        .long("transfer-hook")
+                        .value_name("TRANSFER_HOOK_PROGRAM_ID")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help(
+                            "Specify the program to invoke on every transfer. \
+                            Transfer hook authority defaults to the mint authority."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("enable_confidential_transfers")
+                        .long("enable-confidential-transfers")
+                        .value_name("APPROVE_POLICY")
+                        .takes_value(true)
+                        .possible_values(&["auto", "manual"])
+                        .help(
+                            "Enable confidential transfers for this mint. With the `manual` \
+                            policy, accounts must be approved by the confidential transfer \
+                            authority, which defaults to the mint authority."
+                        ),
+                )
+                .nonce_args(true)
+                .arg(memo_arg())
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::SetInterestRate.into())
+                .about("Set the interest rate for an interest-bearing token")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The interest-bearing token address"),
+                )
+                .arg(
+                    Arg::with_name("rate")
+                        .value_name("RATE")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The new interest rate in basis points"),
+                )
+                .arg(
+                    Arg::with_name("rate_authority")
+                    .long("rate-authority")
+                    .validator(is_valid_signer)
+                    .value_name("SIGNER")
+                    .takes_value(true)
+                    .help(
+                        "Specify the rate authority keypair. \
+                        Defaults to the client keypair address."
+                    )
+                )
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::SetUiAmountMultiplier.into())
+                .about("Set the UI amount multiplier for a scaled UI amount token")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The scaled UI amount token address"),
+                )
+                .arg(
+                    Arg::with_name("multiplier")
+                        .value_name("MULTIPLIER")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The new UI amount multiplier"),
+                )
+                .arg(
+                    Arg::with_name("effective_timestamp")
+                        .long("effective-timestamp")
+                        .value_name("UNIX_TIMESTAMP")
+                        .takes_value(true)
+                        .help(
+                            "Unix timestamp at which the new multiplier takes effect. \
+                            Defaults to taking effect immediately."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("multiplier_authority")
+                    .long("multiplier-authority")
+                    .validator(is_valid_signer)
+                    .value_name("SIGNER")
+                    .takes_value(true)
+                    .help(
+                        "Specify the multiplier authority keypair. \
+                        Defaults to the client keypair address."
+                    )
+                )
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Pause.into())
+                .about("Pause minting, burning, and transferring for a pausable token")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The pausable token address"),
+                )
+                .arg(
+                    Arg::with_name("pause_authority")
+                        .long("pause-authority")
+                        .validator(is_valid_signer)
+                        .value_name("SIGNER")
+                        .takes_value(true)
+                        .help(
+                            "Specify the pause authority keypair. \
+                            Defaults to the client keypair address."
+                        )
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Resume.into())
+                .about("Resume minting, burning, and transferring for a pausable token")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The pausable token address"),
+                )
+                .arg(
+                    Arg::with_name("pause_authority")
+                        .long("pause-authority")
+                        .validator(is_valid_signer)
+                        .value_name("SIGNER")
+                        .takes_value(true)
+                        .help(
+                            "Specify the pause authority keypair. \
+                            Defaults to the client keypair address."
+                        )
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::CreateAccount.into())
+                .about("Create a new token account")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token that the account will hold"),
+                )
+                .arg(
+                    Arg::with_name("account_keypair")
+                        .value_name("ACCOUNT_KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .index(2)
+                        .help(
+                            "Specify the account keypair. \
+                             This may be a keypair file or the ASK keyword. \
+                             [default: associated token account for --owner]"
+                        ),
+                )
+                .arg(
+                    Arg::with_name("immutable")
+                        .long("immutable")
+                        .takes_value(false)
+                        .help(
+                            "Lock the owner of this token account from ever being changed"
+                        ),
+                )
+                .arg(owner_address_arg())
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::CreateMultisig.into())
+                .about("Create a new account describing an M:N multisignature")
+                .arg(
+                    Arg::with_name("minimum_signers")
+                        .value_name("MINIMUM_SIGNERS")
+                        .validator(is_multisig_minimum_signers)
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help(minimum_signers_help),
+                )
+                .arg(
+                    Arg::with_name("multisig_member")
+                        .value_name("MULTISIG_MEMBER_PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .min_values(MIN_SIGNERS as u64)
+                        .max_values(MAX_SIGNERS as u64)
+                        .help(multisig_member_help),
+                )
+                .arg(
+                    Arg::with_name("address_keypair")
+                        .long("address-keypair")
+                        .value_name("ADDRESS_KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the address keypair. \
+                             This may be a keypair file or the ASK keyword. \
+                             [default: randomly generated keypair]"
+                        ),
+                )
+                .nonce_args(true)
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Authorize.into())
+                .about("Authorize a new signing keypair to a token or token account")
+                .arg(
+                    Arg::with_name("address")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The address of the token mint or account"),
+                )
+                .arg(
+                    Arg::with_name("authority_type")
+                        .value_name("AUTHORITY_TYPE")
+                        .takes_value(true)
+                        .possible_values(&[
+                            "mint", "freeze", "owner", "close",
+                            "close-mint", "transfer-fee-config", "withheld-withdraw",
+                            "interest-rate", "permanent-delegate", "transfer-hook-program-id",
+                            "ui-amount-multiplier", "pause",
+                        ])
+                        .index(2)
+                        .required(true)
+                        .help("The new authority type. \
+                            Token mints support `mint` and `freeze` authorities;\
+                            Token accounts support `owner` and `close` authorities."),
+                )
+                .arg(
+                    Arg::with_name("new_authority")
+                        .validator(is_valid_pubkey)
+                        .value_name("AUTHORITY_ADDRESS")
+                        .takes_value(true)
+                        .index(3)
+                        .required_unless("disable")
+                        .help("The address of the new authority"),
+                )
+                .arg(
+                    Arg::with_name("authority")
+                        .long("authority")
+                        .alias("owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the current authority keypair. \
+                             Defaults to the client keypair."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("disable")
+                        .long("disable")
+                        .takes_value(false)
+                        .conflicts_with("new_authority")
+                        .help("Disable mint, freeze, or close functionality by setting authority to None.")
+                )
+                .arg(
+                    Arg::with_name("force")
+                        .long("force")
+                        .hidden(true)
+                        .help("Force re-authorize the wallet's associate token account. Don't use this flag"),
+                )
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Transfer.into())
+                .about("Transfer tokens between accounts")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("Token to transfer"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .validator(is_amount_or_all)
+                        .value_name("TOKEN_AMOUNT")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Amount to send, in tokens; accepts keyword ALL"),
+                )
+                .arg(
+                    Arg::with_name("recipient")
+                        .validator(is_valid_pubkey)
+                        .value_name("RECIPIENT_ADDRESS or RECIPIENT_TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(3)
+                        .required(true)
+                        .help("If a token account address is provided, use it as the recipient. \
+                               Otherwise assume the recipient address is a user wallet and transfer to \
+                               the associated token account")
+                )
+                .arg(
+                    Arg::with_name("from")
+                        .validator(is_valid_pubkey)
+                        .value_name("SENDER_TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .long("from")
+                        .help("Specify the sending token account \
+                            [default: owner's associated token account]")
+                )
+                .arg(owner_keypair_arg_with_value_name("SENDER_TOKEN_OWNER_KEYPAIR")
+                        .help(
+                            "Specify the owner of the sending token account. \
+                            This may be a keypair file or the ASK keyword. \
+                            Defaults to the client keypair.",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("allow_unfunded_recipient")
+                        .long("allow-unfunded-recipient")
+                        .takes_value(false)
+                        .help("Complete the transfer even if the recipient address is not funded")
+                )
+                .arg(
+                    Arg::with_name("allow_empty_recipient")
+                        .long("allow-empty-recipient")
+                        .takes_value(false)
+                        .hidden(true) // Deprecated, use --allow-unfunded-recipient instead
+                )
+                .arg(
+                    Arg::with_name("fund_recipient")
+                        .long("fund-recipient")
+                        .takes_value(false)
+                        .help("Create the associated token account for the recipient if doesn't already exist")
+                )
+                .arg(
+                    Arg::with_name("no_wait")
+                        .long("no-wait")
+                        .takes_value(false)
+                        .help("Return signature immediately after submitting the transaction, instead of waiting for confirmations"),
+                )
+                .arg(
+                    Arg::with_name("allow_non_system_account_recipient")
+                        .long("allow-non-system-account-recipient")
+                        .takes_value(false)
+                        .help("Send tokens to the recipient even if the recipient is not a wallet owned by System Program."),
+                )
+                .arg(
+                    Arg::with_name("recipient_is_ata_owner")
+                        .long("recipient-is-ata-owner")
+                        .takes_value(false)
+                        .requires("sign_only")
+                        .help("In sign-only mode, specifies that the recipient is the owner of the associated token account rather than an actual token account"),
+                )
+                .arg(multisig_signer_arg())
+                .arg(mint_decimals_arg())
+                .nonce_args(true)
+                .arg(memo_arg())
+                .offline_args_config(&SignOnlyNeedsMintDecimals{}),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Burn.into())
+                .about("Burn tokens from an account")
+                .arg(
+                    Arg::with_name("account")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token account address to burn from"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .validator(is_amount)
+                        .value_name("TOKEN_AMOUNT")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Amount to burn, in tokens"),
+                )
+                .arg(owner_keypair_arg_with_value_name("TOKEN_OWNER_KEYPAIR")
+                        .help(
+                            "Specify the burnt token owner account. \
+                            This may be a keypair file or the ASK keyword. \
+                            Defaults to the client keypair.",
                         ),
                 )
+                .arg(multisig_signer_arg())
+                .mint_args()
+                .nonce_args(true)
+                .arg(memo_arg())
+                .offline_args_config(&SignOnlyNeedsFullMintSpec{}),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Mint.into())
+                .about("Mint new tokens")
                 .arg(
-                    Arg::with_name("enable_close")
-                        .long("enable-close")
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The token to mint"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .validator(is_amount)
+                        .value_name("TOKEN_AMOUNT")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Amount to mint, in tokens"),
+                )
+                .arg(
+                    Arg::with_name("recipient")
+                        .validator(is_valid_pubkey)
+                        .value_name("RECIPIENT_TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(3)
+                        .help("The token account address of recipient [default: associated token account for --owner]"),
+                )
+                .arg(
+                    Arg::with_name("mint_authority")
+                        .long("mint-authority")
+                        .alias("owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the mint authority keypair. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
+                .arg(mint_decimals_arg())
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+                .arg(memo_arg())
+                .offline_args_config(&SignOnlyNeedsMintDecimals{}),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Freeze.into())
+                .about("Freeze a token account")
+                .arg(
+                    Arg::with_name("account")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The address of the token account to freeze"),
+                )
+                .arg(
+                    Arg::with_name("freeze_authority")
+                        .long("freeze-authority")
+                        .alias("owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the freeze authority keypair. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
+                .arg(mint_address_arg())
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+                .offline_args_config(&SignOnlyNeedsMintAddress{}),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Thaw.into())
+                .about("Thaw a token account")
+                .arg(
+                    Arg::with_name("account")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The address of the token account to thaw"),
+                )
+                .arg(
+                    Arg::with_name("freeze_authority")
+                        .long("freeze-authority")
+                        .alias("owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the freeze authority keypair. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
+                .arg(mint_address_arg())
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+                .offline_args_config(&SignOnlyNeedsMintAddress{}),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Wrap.into())
+                .about("Wrap native SOL in a SOL token account")
+                .arg(
+                    Arg::with_name("amount")
+                        .validator(is_amount)
+                        .value_name("AMOUNT")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("Amount of SOL to wrap"),
+                )
+                .arg(
+                    Arg::with_name("wallet_keypair")
+                        .alias("owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the keypair for the wallet which will have its native SOL wrapped. \
+                             This wallet will be assigned as the owner of the wrapped SOL token account. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
+                )
+                .arg(
+                    Arg::with_name("create_aux_account")
+                        .takes_value(false)
+                        .long("create-aux-account")
+                        .help("Wrap SOL in an auxiliary account instead of associated token account"),
+                )
+                .arg(
+                    Arg::with_name("immutable")
+                        .long("immutable")
                         .takes_value(false)
                         .help(
-                            "Enable the mint authority to close this mint"
+                            "Lock the owner of this token account from ever being changed"
                         ),
                 )
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Unwrap.into())
+                .about("Unwrap a SOL token account")
+                .arg(
+                    Arg::with_name("account")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .help("The address of the auxiliary token account to unwrap \
+                            [default: associated token account for --owner]"),
+                )
                 .arg(
-                    Arg::with_name("interest_rate")
-                        .long("interest-rate")
-                        .value_name("RATE_BPS")
+                    Arg::with_name("wallet_keypair")
+                        .alias("owner")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
                         .takes_value(true)
                         .help(
-                            "Specify the interest rate in basis points. \
-                            Rate authority defaults to the mint authority."
+                            "Specify the keypair for the wallet which owns the wrapped SOL. \
+                             This wallet will receive the unwrapped SOL. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
                         ),
                 )
+                .arg(multisig_signer_arg())
                 .nonce_args(true)
-                .arg(memo_arg())
+                .offline_args(),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::SetInterestRate.into())
-                .about("Set the interest rate for an interest-bearing token")
+            SubCommand::with_name(CommandName::Approve.into())
+                .about("Approve a delegate for a token account")
+                .arg(
+                    Arg::with_name("account")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("The address of the token account to delegate"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .validator(is_amount)
+                        .value_name("TOKEN_AMOUNT")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Amount to approve, in tokens"),
+                )
                 .arg(
-                    Arg::with_name("token")
+                    Arg::with_name("delegate")
                         .validator(is_valid_pubkey)
-                        .value_name("TOKEN_MINT_ADDRESS")
+                        .value_name("DELEGATE_TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
+                        .index(3)
                         .required(true)
-                        .help("The interest-bearing token address"),
+                        .help("The token account address of delegate"),
                 )
                 .arg(
-                    Arg::with_name("rate")
-                        .value_name("RATE")
+                    owner_keypair_arg()
+                )
+                .arg(multisig_signer_arg())
+                .mint_args()
+                .nonce_args(true)
+                .offline_args_config(&SignOnlyNeedsFullMintSpec{}),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Revoke.into())
+                .about("Revoke a delegate's authority")
+                .arg(
+                    Arg::with_name("account")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
+                        .index(1)
                         .required(true)
-                        .help("The new interest rate in basis points"),
+                        .help("The address of the token account"),
                 )
-                .arg(
-                    Arg::with_name("rate_authority")
-                    .long("rate-authority")
-                    .validator(is_valid_signer)
-                    .value_name("SIGNER")
-                    .takes_value(true)
-                    .help(
-                        "Specify the rate authority keypair. \
-                        Defaults to the client keypair address."
-                    )
+                .arg(owner_keypair_arg()
                 )
+                .arg(delegate_address_arg())
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+                .offline_args_config(&SignOnlyNeedsDelegateAddress{}),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::CreateAccount.into())
-                .about("Create a new token account")
+            SubCommand::with_name(CommandName::Close.into())
+                .about("Close a token account")
                 .arg(
                     Arg::with_name("token")
                         .validator(is_valid_pubkey)
                         .value_name("TOKEN_MINT_ADDRESS")
                         .takes_value(true)
                         .index(1)
-                        .required(true)
-                        .help("The token that the account will hold"),
+                        .required_unless("address")
+                        .help("Token of the associated account to close. \
+                              To close a specific account, use the `--address` parameter instead"),
                 )
+                .arg(owner_address_arg())
                 .arg(
-                    Arg::with_name("account_keypair")
-                        .value_name("ACCOUNT_KEYPAIR")
+                    Arg::with_name("recipient")
+                        .long("recipient")
+                        .validator(is_valid_pubkey)
+                        .value_name("REFUND_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .help("The address of the account to receive remaining SOL [default: --owner]"),
+                )
+                .arg(
+                    Arg::with_name("close_authority")
+                        .long("close-authority")
+                        .alias("owner")
+                        .value_name("KEYPAIR")
                         .validator(is_valid_signer)
                         .takes_value(true)
-                        .index(2)
                         .help(
-                            "Specify the account keypair. \
-                             This may be a keypair file or the ASK keyword. \
-                             [default: associated token account for --owner]"
+                            "Specify the token's close authority if it has one, \
+                            otherwise specify the token's owner keypair. \
+                            This may be a keypair file or the ASK keyword. \
+                            Defaults to the client keypair.",
                         ),
                 )
                 .arg(
-                    Arg::with_name("immutable")
-                        .long("immutable")
-                        .takes_value(false)
-                        .help(
-                            "Lock the owner of this token account from ever being changed"
-                        ),
+                    Arg::with_name("address")
+                        .long("address")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .conflicts_with("token")
+                        .help("Specify the token account to close \
+                            [default: owner's associated token account]"),
                 )
-                .arg(owner_address_arg())
+                .arg(multisig_signer_arg())
                 .nonce_args(true)
         )
         .subcommand(
-            SubCommand::with_name(CommandName::CreateMultisig.into())
-                .about("Create a new account describing an M:N multisignature")
+            SubCommand::with_name(CommandName::CloseMint.into())
+                .about("Close a token mint")
                 .arg(
-                    Arg::with_name("minimum_signers")
-                        .value_name("MINIMUM_SIGNERS")
-                        .validator(is_multisig_minimum_signers)
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
                         .takes_value(true)
                         .index(1)
                         .required(true)
-                        .help(minimum_signers_help),
+                        .help("Token to close"),
                 )
+                .arg(owner_address_arg())
                 .arg(
-                    Arg::with_name("multisig_member")
-                        .value_name("MULTISIG_MEMBER_PUBKEY")
+                    Arg::with_name("recipient")
+                        .long("recipient")
                         .validator(is_valid_pubkey)
+                        .value_name("REFUND_ACCOUNT_ADDRESS")
                         .takes_value(true)
-                        .index(2)
-                        .required(true)
-                        .min_values(MIN_SIGNERS as u64)
-                        .max_values(MAX_SIGNERS as u64)
-                        .help(multisig_member_help),
+                        .help("The address of the account to receive remaining SOL [default: --owner]"),
                 )
                 .arg(
-                    Arg::with_name("address_keypair")
-                        .long("address-keypair")
-                        .value_name("ADDRESS_KEYPAIR")
+                    Arg::with_name("close_authority")
+                        .long("close-authority")
+                        .value_name("KEYPAIR")
                         .validator(is_valid_signer)
                         .takes_value(true)
                         .help(
-                            "Specify the address keypair. \
-                             This may be a keypair file or the ASK keyword. \
-                             [default: randomly generated keypair]"
+                            "Specify the token's close authority. \
+                            This may be a keypair file or the ASK keyword. \
+                            Defaults to the client keypair.",
                         ),
                 )
+                .arg(multisig_signer_arg())
                 .nonce_args(true)
+                .offline_args(),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Authorize.into())
-                .about("Authorize a new signing keypair to a token or token account")
+            SubCommand::with_name(CommandName::Balance.into())
+                .about("Get token account balance")
                 .arg(
-                    Arg::with_name("address")
+                    Arg::with_name("token")
                         .validator(is_valid_pubkey)
-                        .value_name("TOKEN_ADDRESS")
+                        .value_name("TOKEN_MINT_ADDRESS")
                         .takes_value(true)
                         .index(1)
-                        .required(true)
-                        .help("The address of the token mint or account"),
+                        .required_unless("address")
+                        .help("Token of associated account. To query a specific account, use the `--address` parameter instead"),
                 )
+                .arg(owner_address_arg().conflicts_with("address"))
                 .arg(
-                    Arg::with_name("authority_type")
-                        .value_name("AUTHORITY_TYPE")
+                    Arg::with_name("address")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
-                        .possible_values(&[
-                            "mint", "freeze", "owner", "close",
-                            "close-mint", "transfer-fee-config", "withheld-withdraw",
-                            "interest-rate",
-                        ])
-                        .index(2)
+                        .long("address")
+                        .conflicts_with("token")
+                        .help("Specify the token account to query \
+                            [default: owner's associated token account]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Supply.into())
+                .about("Get token supply")
+                .arg(
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
+                        .index(1)
                         .required(true)
-                        .help("The new authority type. \
-                            Token mints support `mint` and `freeze` authorities;\
-                            Token accounts support `owner` and `close` authorities."),
-                )
+                        .help("The token address"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Accounts.into())
+                .about("List all token accounts by owner")
                 .arg(
-                    Arg::with_name("new_authority")
+                    Arg::with_name("token")
                         .validator(is_valid_pubkey)
-                        .value_name("AUTHORITY_ADDRESS")
+                        .value_name("TOKEN_MINT_ADDRESS")
                         .takes_value(true)
-                        .index(3)
-                        .required_unless("disable")
-                        .help("The address of the new authority"),
+                        .index(1)
+                        .help("Limit results to the given token. [Default: list accounts for all tokens]"),
                 )
                 .arg(
-                    Arg::with_name("authority")
-                        .long("authority")
-                        .alias("owner")
-                        .value_name("KEYPAIR")
-                        .validator(is_valid_signer)
-                        .takes_value(true)
+                    Arg::with_name("delegated")
+                        .long("delegated")
+                        .takes_value(false)
+                        .conflicts_with("externally_closeable")
                         .help(
-                            "Specify the current authority keypair. \
-                             Defaults to the client keypair."
+                            "Limit results to accounts with transfer delegations"
                         ),
                 )
                 .arg(
-                    Arg::with_name("disable")
-                        .long("disable")
+                    Arg::with_name("externally_closeable")
+                        .long("externally-closeable")
                         .takes_value(false)
-                        .conflicts_with("new_authority")
-                        .help("Disable mint, freeze, or close functionality by setting authority to None.")
+                        .conflicts_with("delegated")
+                        .help(
+                            "Limit results to accounts with external close authorities"
+                        ),
                 )
                 .arg(
-                    Arg::with_name("force")
-                        .long("force")
-                        .hidden(true)
-                        .help("Force re-authorize the wallet's associate token account. Don't use this flag"),
+                    Arg::with_name("addresses_only")
+                        .long("addresses-only")
+                        .takes_value(false)
+                        .conflicts_with("verbose")
+                        .conflicts_with("output_format")
+                        .help(
+                            "Print token account addresses only"
+                        ),
                 )
-                .arg(multisig_signer_arg())
-                .nonce_args(true)
-                .offline_args(),
+                .arg(owner_address_arg())
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Transfer.into())
-                .about("Transfer tokens between accounts")
+            SubCommand::with_name(CommandName::Address.into())
+                .about("Get wallet address")
                 .arg(
                     Arg::with_name("token")
                         .validator(is_valid_pubkey)
                         .value_name("TOKEN_MINT_ADDRESS")
                         .takes_value(true)
-                        .index(1)
-                        .required(true)
-                        .help("Token to transfer"),
-                )
-                .arg(
-                    Arg::with_name("amount")
-                        .validator(is_amount_or_all)
-                        .value_name("TOKEN_AMOUNT")
-                        .takes_value(true)
-                        .index(2)
-                        .required(true)
-                        .help("Amount to send, in tokens; accepts keyword ALL"),
+                        .long("token")
+                        .requires("verbose")
+                        .help("Return the associated token address for the given token. \
+                               [Default: return the client keypair address]")
                 )
                 .arg(
-                    Arg::with_name("recipient")
-                        .validator(is_valid_pubkey)
-                        .value_name("RECIPIENT_ADDRESS or RECIPIENT_TOKEN_ACCOUNT_ADDRESS")
-                        .takes_value(true)
-                        .index(3)
-                        .required(true)
-                        .help("If a token account address is provided, use it as the recipient. \
-                               Otherwise assume the recipient address is a user wallet and transfer to \
-                               the associated token account")
-                )
+                    owner_address_arg()
+                        .requires("token")
+                        .help("Return the associated token address for the given owner. \
+                               [Default: return the associated token address for the client keypair]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::AccountInfo.into())
+                .about("Query details of an SPL Token account by address (DEPRECATED: use `spl-token display`)")
+                .setting(AppSettings::Hidden)
                 .arg(
-                    Arg::with_name("from")
+                    Arg::with_name("token")
                         .validator(is_valid_pubkey)
-                        .value_name("SENDER_TOKEN_ACCOUNT_ADDRESS")
+                        .value_name("TOKEN_MINT_ADDRESS")
                         .takes_value(true)
-                        .long("from")
-                        .help("Specify the sending token account \
-                            [default: owner's associated token account]")
-                )
-                .arg(owner_keypair_arg_with_value_name("SENDER_TOKEN_OWNER_KEYPAIR")
-                        .help(
-                            "Specify the owner of the sending token account. \
-                            This may be a keypair file or the ASK keyword. \
-                            Defaults to the client keypair.",
-                        ),
+                        .index(1)
+                        .conflicts_with("address")
+                        .required_unless("address")
+                        .help("Token of associated account. \
+                               To query a specific account, use the `--address` parameter instead"),
                 )
                 .arg(
-                    Arg::with_name("allow_unfunded_recipient")
-                        .long("allow-unfunded-recipient")
-                        .takes_value(false)
-                        .help("Complete the transfer even if the recipient address is not funded")
+                    owner_address_arg()
+                        .index(2)
+                        .conflicts_with("address")
+                        .help("Owner of the associated account for the specified token. \
+                               To query a specific account, use the `--address` parameter instead. \
+                               Defaults to the client keypair."),
                 )
                 .arg(
-                    Arg::with_name("allow_empty_recipient")
-                        .long("allow-empty-recipient")
-                        .takes_value(false)
-                        .hidden(true) // Deprecated, use --allow-unfunded-recipient instead
-                )
+                    Arg::with_name("address")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .long("address")
+                        .conflicts_with("token")
+                        .help("Specify the token account to query"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::MultisigInfo.into())
+                .about("Query details of an SPL Token multisig account by address (DEPRECATED: use `spl-token display`)")
+                .setting(AppSettings::Hidden)
                 .arg(
-                    Arg::with_name("fund_recipient")
-                        .long("fund-recipient")
-                        .takes_value(false)
-                        .help("Create the associated token account for the recipient if doesn't already exist")
-                )
+                    Arg::with_name("address")
+                    .validator(is_valid_pubkey)
+                    .value_name("MULTISIG_ACCOUNT_ADDRESS")
+                    .takes_value(true)
+                    .index(1)
+                    .required(true)
+                    .help("The address of the SPL Token multisig account to query"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Display.into())
+                .about("Query details of an SPL Token mint, account, or multisig by address")
                 .arg(
-                    Arg::with_name("no_wait")
-                        .long("no-wait")
-                        .takes_value(false)
-                        .help("Return signature immediately after submitting the transaction, instead of waiting for confirmations"),
-                )
+                    Arg::with_name("address")
+                    .validator(is_valid_pubkey)
+                    .value_name("TOKEN_ADDRESS")
+                    .takes_value(true)
+                    .index(1)
+                    .required(true)
+                    .help("The address of the SPL Token mint, account, or multisig to query"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::Gc.into())
+                .about("Cleanup unnecessary token accounts")
+                .arg(owner_keypair_arg())
                 .arg(
-                    Arg::with_name("allow_non_system_account_recipient")
-                        .long("allow-non-system-account-recipient")
-                        .takes_value(false)
-                        .help("Send tokens to the recipient even if the recipient is not a wallet owned by System Program."),
+                    Arg::with_name("close_empty_associated_accounts")
+                    .long("close-empty-associated-accounts")
+                    .takes_value(false)
+                    .help("close all empty associated token accounts (to get SOL back)")
                 )
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::SyncNative.into())
+                .about("Sync a native SOL token account to its underlying lamports")
                 .arg(
-                    Arg::with_name("recipient_is_ata_owner")
-                        .long("recipient-is-ata-owner")
-                        .takes_value(false)
-                        .requires("sign_only")
-                        .help("In sign-only mode, specifies that the recipient is the owner of the associated token account rather than an actual token account"),
+                    owner_address_arg()
+                        .index(1)
+                        .conflicts_with("address")
+                        .help("Owner of the associated account for the native token. \
+                               To query a specific account, use the `--address` parameter instead. \
+                               Defaults to the client keypair."),
                 )
-                .arg(multisig_signer_arg())
-                .arg(mint_decimals_arg())
-                .nonce_args(true)
-                .arg(memo_arg())
-                .offline_args_config(&SignOnlyNeedsMintDecimals{}),
+                .arg(
+                    Arg::with_name("address")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .long("address")
+                        .conflicts_with("owner")
+                        .help("Specify the specific token account address to sync"),
+                ),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Burn.into())
-                .about("Burn tokens from an account")
+            SubCommand::with_name(CommandName::EnableRequiredTransferMemos.into())
+                .about("Enable required transfer memos for token account")
                 .arg(
                     Arg::with_name("account")
                         .validator(is_valid_pubkey)
@@ -2314,81 +4188,35 @@ fn app<'a, 'b>(
                         .takes_value(true)
                         .index(1)
                         .required(true)
-                        .help("The token account address to burn from"),
+                        .help("The address of the token account to enable required transfer memos")
                 )
                 .arg(
-                    Arg::with_name("amount")
-                        .validator(is_amount)
-                        .value_name("TOKEN_AMOUNT")
-                        .takes_value(true)
-                        .index(2)
-                        .required(true)
-                        .help("Amount to burn, in tokens"),
-                )
-                .arg(owner_keypair_arg_with_value_name("TOKEN_OWNER_KEYPAIR")
-                        .help(
-                            "Specify the burnt token owner account. \
-                            This may be a keypair file or the ASK keyword. \
-                            Defaults to the client keypair.",
-                        ),
+                    owner_address_arg()
                 )
                 .arg(multisig_signer_arg())
-                .mint_args()
                 .nonce_args(true)
-                .arg(memo_arg())
-                .offline_args_config(&SignOnlyNeedsFullMintSpec{}),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Mint.into())
-                .about("Mint new tokens")
+            SubCommand::with_name(CommandName::DisableRequiredTransferMemos.into())
+                .about("Disable required transfer memos for token account")
                 .arg(
-                    Arg::with_name("token")
+                    Arg::with_name("account")
                         .validator(is_valid_pubkey)
-                        .value_name("TOKEN_MINT_ADDRESS")
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
                         .index(1)
                         .required(true)
-                        .help("The token to mint"),
-                )
-                .arg(
-                    Arg::with_name("amount")
-                        .validator(is_amount)
-                        .value_name("TOKEN_AMOUNT")
-                        .takes_value(true)
-                        .index(2)
-                        .required(true)
-                        .help("Amount to mint, in tokens"),
-                )
-                .arg(
-                    Arg::with_name("recipient")
-                        .validator(is_valid_pubkey)
-                        .value_name("RECIPIENT_TOKEN_ACCOUNT_ADDRESS")
-                        .takes_value(true)
-                        .index(3)
-                        .help("The token account address of recipient [default: associated token account for --owner]"),
+                        .help("The address of the token account to disable required transfer memos"),
                 )
                 .arg(
-                    Arg::with_name("mint_authority")
-                        .long("mint-authority")
-                        .alias("owner")
-                        .value_name("KEYPAIR")
-                        .validator(is_valid_signer)
-                        .takes_value(true)
-                        .help(
-                            "Specify the mint authority keypair. \
-                             This may be a keypair file or the ASK keyword. \
-                             Defaults to the client keypair."
-                        ),
+                    owner_address_arg()
                 )
-                .arg(mint_decimals_arg())
                 .arg(multisig_signer_arg())
                 .nonce_args(true)
-                .arg(memo_arg())
-                .offline_args_config(&SignOnlyNeedsMintDecimals{}),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Freeze.into())
-                .about("Freeze a token account")
+            SubCommand::with_name(CommandName::EnableCpiGuard.into())
+                .about("Enable CPI Guard for token account")
                 .arg(
                     Arg::with_name("account")
                         .validator(is_valid_pubkey)
@@ -2396,29 +4224,17 @@ fn app<'a, 'b>(
                         .takes_value(true)
                         .index(1)
                         .required(true)
-                        .help("The address of the token account to freeze"),
+                        .help("The address of the token account to enable CPI Guard"),
                 )
                 .arg(
-                    Arg::with_name("freeze_authority")
-                        .long("freeze-authority")
-                        .alias("owner")
-                        .value_name("KEYPAIR")
-                        .validator(is_valid_signer)
-                        .takes_value(true)
-                        .help(
-                            "Specify the freeze authority keypair. \
-                             This may be a keypair file or the ASK keyword. \
-                             Defaults to the client keypair."
-                        ),
+                    owner_address_arg()
                 )
-                .arg(mint_address_arg())
                 .arg(multisig_signer_arg())
                 .nonce_args(true)
-                .offline_args_config(&SignOnlyNeedsMintAddress{}),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Thaw.into())
-                .about("Thaw a token account")
+            SubCommand::with_name(CommandName::DisableCpiGuard.into())
+                .about("Disable CPI Guard for token account")
                 .arg(
                     Arg::with_name("account")
                         .validator(is_valid_pubkey)
@@ -2426,100 +4242,80 @@ fn app<'a, 'b>(
                         .takes_value(true)
                         .index(1)
                         .required(true)
-                        .help("The address of the token account to thaw"),
+                        .help("The address of the token account to disable CPI Guard"),
                 )
                 .arg(
-                    Arg::with_name("freeze_authority")
-                        .long("freeze-authority")
-                        .alias("owner")
-                        .value_name("KEYPAIR")
-                        .validator(is_valid_signer)
-                        .takes_value(true)
-                        .help(
-                            "Specify the freeze authority keypair. \
-                             This may be a keypair file or the ASK keyword. \
-                             Defaults to the client keypair."
-                        ),
+                    owner_address_arg()
                 )
-                .arg(mint_address_arg())
                 .arg(multisig_signer_arg())
                 .nonce_args(true)
-                .offline_args_config(&SignOnlyNeedsMintAddress{}),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Wrap.into())
-                .about("Wrap native SOL in a SOL token account")
+            SubCommand::with_name(CommandName::ConfigureConfidentialTransferAccount.into())
+                .about("Configure confidential transfers for a token account")
                 .arg(
-                    Arg::with_name("amount")
-                        .validator(is_amount)
-                        .value_name("AMOUNT")
+                    Arg::with_name("account")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
                         .index(1)
                         .required(true)
-                        .help("Amount of SOL to wrap"),
+                        .help("The address of the token account to configure"),
                 )
                 .arg(
-                    Arg::with_name("wallet_keypair")
-                        .alias("owner")
-                        .value_name("KEYPAIR")
-                        .validator(is_valid_signer)
-                        .takes_value(true)
+                    owner_keypair_arg()
                         .help(
-                            "Specify the keypair for the wallet which will have its native SOL wrapped. \
-                             This wallet will be assigned as the owner of the wrapped SOL token account. \
-                             This may be a keypair file or the ASK keyword. \
-                             Defaults to the client keypair."
+                            "Keypair of the token account's owner, from which the account's \
+                            encryption keys are derived. Defaults to the client keypair."
                         ),
                 )
-                .arg(
-                    Arg::with_name("create_aux_account")
-                        .takes_value(false)
-                        .long("create-aux-account")
-                        .help("Wrap SOL in an auxiliary account instead of associated token account"),
-                )
-                .arg(
-                    Arg::with_name("immutable")
-                        .long("immutable")
-                        .takes_value(false)
+                .arg(
+                    Arg::with_name("maximum_pending_balance_credit_counter")
+                        .long("max-pending-balance-credit-counter")
+                        .value_name("MAXIMUM_CREDIT_COUNTER")
+                        .takes_value(true)
+                        .validator(is_parsable::<u64>)
                         .help(
-                            "Lock the owner of this token account from ever being changed"
+                            "The maximum number of incoming deposits and transfers to the \
+                            account before the pending balance must be applied"
                         ),
                 )
+                .arg(mint_address_arg())
                 .nonce_args(true)
-                .offline_args(),
+                .offline_args_config(&SignOnlyNeedsMintAddress{}),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Unwrap.into())
-                .about("Unwrap a SOL token account")
+            SubCommand::with_name(CommandName::ApproveConfidentialTransferAccount.into())
+                .about("Approve a token account for confidential transfers")
                 .arg(
                     Arg::with_name("account")
                         .validator(is_valid_pubkey)
                         .value_name("TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
                         .index(1)
-                        .help("The address of the auxiliary token account to unwrap \
-                            [default: associated token account for --owner]"),
+                        .required(true)
+                        .help("The address of the token account to approve"),
                 )
                 .arg(
-                    Arg::with_name("wallet_keypair")
-                        .alias("owner")
+                    Arg::with_name("confidential_transfer_authority")
+                        .long("confidential-transfer-authority")
                         .value_name("KEYPAIR")
                         .validator(is_valid_signer)
                         .takes_value(true)
                         .help(
-                            "Specify the keypair for the wallet which owns the wrapped SOL. \
-                             This wallet will receive the unwrapped SOL. \
-                             This may be a keypair file or the ASK keyword. \
-                             Defaults to the client keypair."
+                            "Specify the confidential transfer authority of the mint. \
+                            This may be a keypair file or the ASK keyword. \
+                            Defaults to the client keypair."
                         ),
                 )
                 .arg(multisig_signer_arg())
+                .arg(mint_address_arg())
                 .nonce_args(true)
-                .offline_args(),
+                .offline_args_config(&SignOnlyNeedsMintAddress{}),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Approve.into())
-                .about("Approve a delegate for a token account")
+            SubCommand::with_name(CommandName::DepositConfidentialTokens.into())
+                .about("Deposit tokens from the non-confidential balance into the pending confidential balance")
                 .arg(
                     Arg::with_name("account")
                         .validator(is_valid_pubkey)
@@ -2527,7 +4323,7 @@ fn app<'a, 'b>(
                         .takes_value(true)
                         .index(1)
                         .required(true)
-                        .help("The address of the token account to delegate"),
+                        .help("The address of the token account to deposit into"),
                 )
                 .arg(
                     Arg::with_name("amount")
@@ -2536,16 +4332,7 @@ fn app<'a, 'b>(
                         .takes_value(true)
                         .index(2)
                         .required(true)
-                        .help("Amount to approve, in tokens"),
-                )
-                .arg(
-                    Arg::with_name("delegate")
-                        .validator(is_valid_pubkey)
-                        .value_name("DELEGATE_TOKEN_ACCOUNT_ADDRESS")
-                        .takes_value(true)
-                        .index(3)
-                        .required(true)
-                        .help("The token account address of delegate"),
+                        .help("Amount to deposit, in tokens"),
                 )
                 .arg(
                     owner_keypair_arg()
@@ -2556,8 +4343,8 @@ fn app<'a, 'b>(
                 .offline_args_config(&SignOnlyNeedsFullMintSpec{}),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Revoke.into())
-                .about("Revoke a delegate's authority")
+            SubCommand::with_name(CommandName::WithdrawConfidentialTokens.into())
+                .about("Withdraw tokens from the available confidential balance into the non-confidential balance")
                 .arg(
                     Arg::with_name("account")
                         .validator(is_valid_pubkey)
@@ -2565,312 +4352,300 @@ fn app<'a, 'b>(
                         .takes_value(true)
                         .index(1)
                         .required(true)
-                        .help("The address of the token account"),
+                        .help("The address of the token account to withdraw from"),
                 )
-                .arg(owner_keypair_arg()
+                .arg(
+                    Arg::with_name("amount")
+                        .validator(is_amount_or_all)
+                        .value_name("TOKEN_AMOUNT")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Amount to withdraw, in tokens; accepts keyword ALL"),
+                )
+                .arg(
+                    owner_keypair_arg()
+                        .help(
+                            "Keypair of the token account's owner, from which the account's \
+                            encryption keys are derived. Defaults to the client keypair."
+                        ),
                 )
-                .arg(delegate_address_arg())
-                .arg(multisig_signer_arg())
                 .nonce_args(true)
-                .offline_args_config(&SignOnlyNeedsDelegateAddress{}),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Close.into())
-                .about("Close a token account")
+            SubCommand::with_name(CommandName::ConfidentialTransfer.into())
+                .about("Transfer tokens between the confidential balances of two token accounts")
                 .arg(
-                    Arg::with_name("token")
+                    Arg::with_name("account")
                         .validator(is_valid_pubkey)
-                        .value_name("TOKEN_MINT_ADDRESS")
+                        .value_name("SOURCE_TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
                         .index(1)
-                        .required_unless("address")
-                        .help("Token of the associated account to close. \
-                              To close a specific account, use the `--address` parameter instead"),
+                        .required(true)
+                        .help("The address of the token account to send from"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .validator(is_amount)
+                        .value_name("TOKEN_AMOUNT")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Amount to send, in tokens"),
                 )
-                .arg(owner_address_arg())
                 .arg(
                     Arg::with_name("recipient")
-                        .long("recipient")
                         .validator(is_valid_pubkey)
-                        .value_name("REFUND_ACCOUNT_ADDRESS")
+                        .value_name("RECIPIENT_TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
-                        .help("The address of the account to receive remaining SOL [default: --owner]"),
+                        .index(3)
+                        .required(true)
+                        .help("The address of the token account to send to"),
                 )
                 .arg(
-                    Arg::with_name("close_authority")
-                        .long("close-authority")
-                        .alias("owner")
-                        .value_name("KEYPAIR")
-                        .validator(is_valid_signer)
-                        .takes_value(true)
+                    owner_keypair_arg()
                         .help(
-                            "Specify the token's close authority if it has one, \
-                            otherwise specify the token's owner keypair. \
-                            This may be a keypair file or the ASK keyword. \
-                            Defaults to the client keypair.",
+                            "Keypair of the source account's owner, from which the account's \
+                            encryption keys are derived. Defaults to the client keypair."
                         ),
                 )
-                .arg(
-                    Arg::with_name("address")
-                        .long("address")
-                        .validator(is_valid_pubkey)
-                        .value_name("TOKEN_ACCOUNT_ADDRESS")
-                        .takes_value(true)
-                        .conflicts_with("token")
-                        .help("Specify the token account to close \
-                            [default: owner's associated token account]"),
-                )
-                .arg(multisig_signer_arg())
                 .nonce_args(true)
         )
         .subcommand(
-            SubCommand::with_name(CommandName::CloseMint.into())
-                .about("Close a token mint")
+            SubCommand::with_name(CommandName::ApplyPendingBalance.into())
+                .about("Apply the pending confidential balance of a token account to its available balance")
                 .arg(
-                    Arg::with_name("token")
+                    Arg::with_name("account")
                         .validator(is_valid_pubkey)
-                        .value_name("TOKEN_MINT_ADDRESS")
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
                         .index(1)
                         .required(true)
-                        .help("Token to close"),
-                )
-                .arg(owner_address_arg())
-                .arg(
-                    Arg::with_name("recipient")
-                        .long("recipient")
-                        .validator(is_valid_pubkey)
-                        .value_name("REFUND_ACCOUNT_ADDRESS")
-                        .takes_value(true)
-                        .help("The address of the account to receive remaining SOL [default: --owner]"),
+                        .help("The address of the token account"),
                 )
                 .arg(
-                    Arg::with_name("close_authority")
-                        .long("close-authority")
-                        .value_name("KEYPAIR")
-                        .validator(is_valid_signer)
-                        .takes_value(true)
+                    owner_keypair_arg()
                         .help(
-                            "Specify the token's close authority. \
-                            This may be a keypair file or the ASK keyword. \
-                            Defaults to the client keypair.",
+                            "Keypair of the token account's owner, from which the account's \
+                            encryption keys are derived. Defaults to the client keypair."
                         ),
                 )
-                .arg(multisig_signer_arg())
                 .nonce_args(true)
-                .offline_args(),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Balance.into())
-                .about("Get token account balance")
+            SubCommand::with_name(CommandName::EnableConfidentialCredits.into())
+                .about("Allow incoming confidential deposits and transfers to a token account")
                 .arg(
-                    Arg::with_name("token")
+                    Arg::with_name("account")
                         .validator(is_valid_pubkey)
-                        .value_name("TOKEN_MINT_ADDRESS")
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
                         .index(1)
-                        .required_unless("address")
-                        .help("Token of associated account. To query a specific account, use the `--address` parameter instead"),
+                        .required(true)
+                        .help("The address of the token account"),
                 )
-                .arg(owner_address_arg().conflicts_with("address"))
                 .arg(
-                    Arg::with_name("address")
-                        .validator(is_valid_pubkey)
-                        .value_name("TOKEN_ACCOUNT_ADDRESS")
-                        .takes_value(true)
-                        .long("address")
-                        .conflicts_with("token")
-                        .help("Specify the token account to query \
-                            [default: owner's associated token account]"),
-                ),
+                    owner_keypair_arg()
+                )
+                .arg(multisig_signer_arg())
+                .arg(mint_address_arg())
+                .nonce_args(true)
+                .offline_args_config(&SignOnlyNeedsMintAddress{}),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Supply.into())
-                .about("Get token supply")
+            SubCommand::with_name(CommandName::DisableConfidentialCredits.into())
+                .about("Reject incoming confidential deposits and transfers to a token account")
                 .arg(
-                    Arg::with_name("token")
+                    Arg::with_name("account")
                         .validator(is_valid_pubkey)
-                        .value_name("TOKEN_MINT_ADDRESS")
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
                         .index(1)
                         .required(true)
-                        .help("The token address"),
-                ),
+                        .help("The address of the token account"),
+                )
+                .arg(
+                    owner_keypair_arg()
+                )
+                .arg(multisig_signer_arg())
+                .arg(mint_address_arg())
+                .nonce_args(true)
+                .offline_args_config(&SignOnlyNeedsMintAddress{}),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Accounts.into())
-                .about("List all token accounts by owner")
+            SubCommand::with_name(CommandName::ConfidentialBalance.into())
+                .about("Get the decrypted confidential balances of a token account")
                 .arg(
-                    Arg::with_name("token")
+                    Arg::with_name("account")
                         .validator(is_valid_pubkey)
-                        .value_name("TOKEN_MINT_ADDRESS")
+                        .value_name("TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
                         .index(1)
-                        .help("Limit results to the given token. [Default: list accounts for all tokens]"),
-                )
-                .arg(
-                    Arg::with_name("delegated")
-                        .long("delegated")
-                        .takes_value(false)
-                        .conflicts_with("externally_closeable")
-                        .help(
-                            "Limit results to accounts with transfer delegations"
-                        ),
-                )
-                .arg(
-                    Arg::with_name("externally_closeable")
-                        .long("externally-closeable")
-                        .takes_value(false)
-                        .conflicts_with("delegated")
-                        .help(
-                            "Limit results to accounts with external close authorities"
-                        ),
+                        .required(true)
+                        .help("The address of the token account"),
                 )
                 .arg(
-                    Arg::with_name("addresses_only")
-                        .long("addresses-only")
-                        .takes_value(false)
-                        .conflicts_with("verbose")
-                        .conflicts_with("output_format")
+                    owner_keypair_arg()
                         .help(
-                            "Print token account addresses only"
+                            "Keypair of the token account's owner, from which the account's \
+                            decryption keys are derived. Defaults to the client keypair."
                         ),
-                )
-                .arg(owner_address_arg())
+                ),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::Address.into())
-                .about("Get wallet address")
+            SubCommand::with_name(CommandName::SetTransferFee.into())
+                .about("Set the transfer fee for a token with a configured transfer fee")
                 .arg(
                     Arg::with_name("token")
                         .validator(is_valid_pubkey)
                         .value_name("TOKEN_MINT_ADDRESS")
                         .takes_value(true)
-                        .long("token")
-                        .requires("verbose")
-                        .help("Return the associated token address for the given token. \
-                               [Default: return the client keypair address]")
+                        .index(1)
+                        .required(true)
+                        .help("The token address with an existing transfer fee"),
                 )
                 .arg(
-                    owner_address_arg()
-                        .requires("token")
-                        .help("Return the associated token address for the given owner. \
-                               [Default: return the associated token address for the client keypair]"),
-                ),
-        )
-        .subcommand(
-            SubCommand::with_name(CommandName::AccountInfo.into())
-                .about("Query details of an SPL Token account by address (DEPRECATED: use `spl-token display`)")
-                .setting(AppSettings::Hidden)
+                    Arg::with_name("transfer_fee_basis_points")
+                        .value_name("FEE_IN_BASIS_POINTS")
+                        .validator(is_parsable::<u16>)
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("The new transfer fee in basis points"),
+                )
                 .arg(
-                    Arg::with_name("token")
-                        .validator(is_valid_pubkey)
-                        .value_name("TOKEN_MINT_ADDRESS")
+                    Arg::with_name("maximum_fee")
+                        .value_name("TOKEN_AMOUNT")
+                        .validator(is_amount)
                         .takes_value(true)
-                        .index(1)
-                        .conflicts_with("address")
-                        .required_unless("address")
-                        .help("Token of associated account. \
-                               To query a specific account, use the `--address` parameter instead"),
+                        .index(3)
+                        .required(true)
+                        .help("The new maximum transfer fee in UI amount"),
                 )
                 .arg(
-                    owner_address_arg()
-                        .index(2)
-                        .conflicts_with("address")
-                        .help("Owner of the associated account for the specified token. \
-                               To query a specific account, use the `--address` parameter instead. \
-                               Defaults to the client keypair."),
+                    Arg::with_name("transfer_fee_authority")
+                    .long("transfer-fee-authority")
+                    .validator(is_valid_signer)
+                    .value_name("SIGNER")
+                    .takes_value(true)
+                    .help(
+                        "Specify the transfer fee authority keypair. \
+                        Defaults to the client keypair address."
+                    )
                 )
+                .arg(mint_decimals_arg())
+                .arg(multisig_signer_arg())
+                .nonce_args(true)
+                .offline_args_config(&SignOnlyNeedsMintDecimals{}),
+        )
+        .subcommand(
+            SubCommand::with_name(CommandName::WithdrawWithheldTokens.into())
+                .about("Withdraw withheld transfer fee tokens from mint and / or account(s)")
                 .arg(
-                    Arg::with_name("address")
+                    Arg::with_name("account")
                         .validator(is_valid_pubkey)
                         .value_name("TOKEN_ACCOUNT_ADDRESS")
                         .takes_value(true)
-                        .long("address")
-                        .conflicts_with("token")
-                        .help("Specify the token account to query"),
-                ),
-        )
-        .subcommand(
-            SubCommand::with_name(CommandName::MultisigInfo.into())
-                .about("Query details of an SPL Token multisig account by address (DEPRECATED: use `spl-token display`)")
-                .setting(AppSettings::Hidden)
+                        .index(1)
+                        .required(true)
+                        .help("The address of the token account to receive withdrawn tokens"),
+                )
                 .arg(
-                    Arg::with_name("address")
-                    .validator(is_valid_pubkey)
-                    .value_name("MULTISIG_ACCOUNT_ADDRESS")
-                    .takes_value(true)
-                    .index(1)
-                    .required(true)
-                    .help("The address of the SPL Token multisig account to query"),
-                ),
-        )
-        .subcommand(
-            SubCommand::with_name(CommandName::Display.into())
-                .about("Query details of an SPL Token mint, account, or multisig by address")
+                    Arg::with_name("source")
+                        .validator(is_valid_pubkey)
+                        .value_name("ACCOUNT_ADDRESS")
+                        .takes_value(true)
+                        .multiple(true)
+                        .help("The token accounts to withdraw from")
+                )
                 .arg(
-                    Arg::with_name("address")
-                    .validator(is_valid_pubkey)
-                    .value_name("TOKEN_ADDRESS")
-                    .takes_value(true)
-                    .index(1)
-                    .required(true)
-                    .help("The address of the SPL Token mint, account, or multisig to query"),
-                ),
-        )
-        .subcommand(
-            SubCommand::with_name(CommandName::Gc.into())
-                .about("Cleanup unnecessary token accounts")
-                .arg(owner_keypair_arg())
+                    Arg::with_name("include_mint")
+                        .long("include-mint")
+                        .takes_value(false)
+                        .help("Also withdraw withheld tokens from the mint"),
+                )
                 .arg(
-                    Arg::with_name("close_empty_associated_accounts")
-                    .long("close-empty-associated-accounts")
-                    .takes_value(false)
-                    .help("close all empty associated token accounts (to get SOL back)")
+                    Arg::with_name("withdraw_withheld_authority")
+                        .long("withdraw-withheld-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the withdraw withheld authority keypair. \
+                             This may be a keypair file or the ASK keyword. \
+                             Defaults to the client keypair."
+                        ),
                 )
+                .arg(multisig_signer_arg())
+                .arg(mint_address_arg())
+                .nonce_args(true)
+                .offline_args_config(&SignOnlyNeedsMintAddress{}),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::SyncNative.into())
-                .about("Sync a native SOL token account to its underlying lamports")
+            SubCommand::with_name(CommandName::Harvest.into())
+                .about("Permissionlessly harvest withheld tokens from accounts to the mint")
                 .arg(
-                    owner_address_arg()
+                    Arg::with_name("token")
+                        .validator(is_valid_pubkey)
+                        .value_name("TOKEN_MINT_ADDRESS")
+                        .takes_value(true)
                         .index(1)
-                        .conflicts_with("address")
-                        .help("Owner of the associated account for the native token. \
-                               To query a specific account, use the `--address` parameter instead. \
-                               Defaults to the client keypair."),
+                        .required(true)
+                        .help("The token address with withheld transfer fees"),
                 )
                 .arg(
-                    Arg::with_name("address")
+                    Arg::with_name("source")
                         .validator(is_valid_pubkey)
-                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .value_name("ACCOUNT_ADDRESS")
                         .takes_value(true)
-                        .long("address")
-                        .conflicts_with("owner")
-                        .help("Specify the specific token account address to sync"),
-                ),
+                        .index(2)
+                        .required(true)
+                        .multiple(true)
+                        .help("The token accounts to harvest from")
+                )
+                .nonce_args(true)
+                .offline_args(),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::EnableRequiredTransferMemos.into())
-                .about("Enable required transfer memos for token account")
+            SubCommand::with_name(CommandName::UpdateDefaultAccountState.into())
+                .about("Update the default account state of a mint")
                 .arg(
-                    Arg::with_name("account")
+                    Arg::with_name("token")
                         .validator(is_valid_pubkey)
-                        .value_name("TOKEN_ACCOUNT_ADDRESS")
+                        .value_name("TOKEN_MINT_ADDRESS")
                         .takes_value(true)
                         .index(1)
                         .required(true)
-                        .help("The address of the token account to enable required transfer memos")
+                        .help("The address of the token mint to update default account state"),
                 )
                 .arg(
-                    owner_address_arg()
+                    Arg::with_name("state")
+                        .value_name("STATE")
+                        .takes_value(true)
+                        .possible_values(&["initialized", "frozen"])
+                        .index(2)
+                        .required(true)
+                        .help("The new default account state."),
+                )
+                .arg(
+                    Arg::with_name("freeze_authority")
+                        .long("freeze-authority")
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help(
+                            "Specify the token's freeze authority. \
+                            This may be a keypair file or the ASK keyword. \
+                            Defaults to the client keypair.",
+                        ),
                 )
                 .arg(multisig_signer_arg())
                 .nonce_args(true)
+                .offline_args(),
         )
         .subcommand(
-            SubCommand::with_name(CommandName::DisableRequiredTransferMemos.into())
-                .about("Disable required transfer memos for token account")
+            SubCommand::with_name(CommandName::Reallocate.into())
+                .about("Reallocate a token account to make room for account extensions")
                 .arg(
                     Arg::with_name("account")
                         .validator(is_valid_pubkey)
@@ -2878,585 +4653,971 @@ fn app<'a, 'b>(
                         .takes_value(true)
                         .index(1)
                         .required(true)
-                        .help("The address of the token account to disable required transfer memos"),
+                        .help("The address of the token account to reallocate"),
+                )
+                .arg(
+                    Arg::with_name("extension")
+                        .value_name("EXTENSION_NAME")
+                        .takes_value(true)
+                        .possible_values(&["memo-transfer", "cpi-guard", "confidential-transfer"])
+                        .index(2)
+                        .required(true)
+                        .multiple(true)
+                        .help("The account extensions to make room for"),
                 )
                 .arg(
                     owner_address_arg()
                 )
                 .arg(multisig_signer_arg())
+                .arg(mint_address_arg())
                 .nonce_args(true)
+                .offline_args_config(&SignOnlyNeedsMintAddress{}),
         )
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Error> {
-    let default_decimals = format!("{}", spl_token_2022::native_mint::DECIMALS);
-    let minimum_signers_help = minimum_signers_help_string();
-    let multisig_member_help = multisig_member_help_string();
-    let app_matches = app(
-        &default_decimals,
-        &minimum_signers_help,
-        &multisig_member_help,
-    )
-    .get_matches();
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let default_decimals = format!("{}", spl_token_2022::native_mint::DECIMALS);
+    let minimum_signers_help = minimum_signers_help_string();
+    let multisig_member_help = multisig_member_help_string();
+    let app_matches = app(
+        &default_decimals,
+        &minimum_signers_help,
+        &multisig_member_help,
+    )
+    .get_matches();
+
+    let mut wallet_manager = None;
+    let mut bulk_signers: Vec<Arc<dyn Signer>> = Vec::new();
+
+    let (sub_command, sub_matches) = app_matches.subcommand();
+    let sub_command = CommandName::from_str(sub_command).unwrap();
+    let matches = sub_matches.unwrap();
+
+    let mut multisigner_ids = Vec::new();
+    let config = Config::new(
+        matches,
+        &mut wallet_manager,
+        &mut bulk_signers,
+        &mut multisigner_ids,
+    )
+    .await;
+
+    solana_logger::setup_with_default("solana=info");
+    let result =
+        process_command(&sub_command, matches, &config, wallet_manager, bulk_signers).await?;
+    println!("{}", result);
+    Ok(())
+}
+
+async fn process_command<'a>(
+    sub_command: &CommandName,
+    sub_matches: &ArgMatches<'_>,
+    config: &Config<'a>,
+    mut wallet_manager: Option<Arc<RemoteWalletManager>>,
+    mut bulk_signers: Vec<Arc<dyn Signer>>,
+) -> CommandResult {
+    match (sub_command, sub_matches) {
+        (CommandName::Bench, arg_matches) => {
+            bench_process_command(
+                arg_matches,
+                config,
+                std::mem::take(&mut bulk_signers),
+                &mut wallet_manager,
+            )
+            .await
+        }
+        (CommandName::CreateToken, arg_matches) => {
+            let decimals = value_t_or_exit!(arg_matches, "decimals", u8);
+            let mint_authority =
+                config.pubkey_or_default(arg_matches, "mint_authority", &mut wallet_manager)?;
+            let memo = value_t!(arg_matches, "memo", String).ok();
+            let rate_bps = value_t!(arg_matches, "interest_rate", i16).ok();
+            let ui_amount_multiplier = value_t!(arg_matches, "ui_amount_multiplier", f64).ok();
+            let default_account_state =
+                arg_matches
+                    .value_of("default_account_state")
+                    .map(|state| match state {
+                        "initialized" => AccountState::Initialized,
+                        "frozen" => AccountState::Frozen,
+                        _ => unreachable!(),
+                    });
+            let transfer_fee = arg_matches.values_of("transfer_fee").map(|mut values| {
+                let transfer_fee_basis_points =
+                    values.next().unwrap().parse::<u16>().unwrap_or_else(|e| {
+                        eprintln!("error: Invalid transfer fee basis points: {}", e);
+                        exit(1);
+                    });
+                let maximum_fee = values.next().unwrap().parse::<f64>().unwrap_or_else(|e| {
+                    eprintln!("error: Invalid maximum fee: {}", e);
+                    exit(1);
+                });
+                (
+                    transfer_fee_basis_points,
+                    spl_token::ui_amount_to_amount(maximum_fee, decimals),
+                )
+            });
+            let transfer_hook_program_id =
+                pubkey_of_signer(arg_matches, "transfer_hook", &mut wallet_manager).unwrap();
+            let confidential_transfer_auto_approve = arg_matches
+                .value_of("enable_confidential_transfers")
+                .map(|policy| policy == "auto");
+
+            let (token_signer, token) =
+                get_signer(arg_matches, "token_keypair", &mut wallet_manager)
+                    .unwrap_or_else(new_throwaway_signer);
+            if !bulk_signers.contains(&token_signer) {
+                bulk_signers.push(token_signer);
+            }
+
+            command_create_token(
+                config,
+                decimals,
+                token,
+                mint_authority,
+                arg_matches.is_present("enable_freeze"),
+                arg_matches.is_present("enable_close"),
+                arg_matches.is_present("enable_non_transferable"),
+                arg_matches.is_present("enable_permanent_delegate"),
+                arg_matches.is_present("enable_pausable"),
+                memo,
+                rate_bps,
+                ui_amount_multiplier,
+                default_account_state,
+                transfer_fee,
+                transfer_hook_program_id,
+                confidential_transfer_auto_approve,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::SetInterestRate, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let rate_bps = value_t_or_exit!(arg_matches, "rate", i16);
+            let (rate_authority_signer, rate_authority_pubkey) =
+                config.signer_or_default(arg_matches, "rate_authority", &mut wallet_manager);
+            let bulk_signers = vec![rate_authority_signer];
+
+            command_set_interest_rate(
+                config,
+                token_pubkey,
+                rate_authority_pubkey,
+                rate_bps,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::SetUiAmountMultiplier, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let multiplier = value_t_or_exit!(arg_matches, "multiplier", f64);
+            let effective_timestamp =
+                value_t!(arg_matches, "effective_timestamp", i64).unwrap_or_default();
+            let (multiplier_authority_signer, multiplier_authority_pubkey) =
+                config.signer_or_default(arg_matches, "multiplier_authority", &mut wallet_manager);
+            let bulk_signers = vec![multiplier_authority_signer];
+
+            command_set_ui_amount_multiplier(
+                config,
+                token_pubkey,
+                multiplier_authority_pubkey,
+                multiplier,
+                effective_timestamp,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::Pause, arg_matches) | (CommandName::Resume, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let (pause_authority_signer, pause_authority) =
+                config.signer_or_default(arg_matches, "pause_authority", &mut wallet_manager);
+            if !bulk_signers.contains(&pause_authority_signer) {
+                bulk_signers.push(pause_authority_signer);
+            }
+            let pause = matches!(sub_command, CommandName::Pause);
+
+            command_pause(config, token_pubkey, pause_authority, bulk_signers, pause).await
+        }
+        (CommandName::CreateAccount, arg_matches) => {
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+
+            // No need to add a signer when creating an associated token account
+            let account = get_signer(arg_matches, "account_keypair", &mut wallet_manager).map(
+                |(signer, account)| {
+                    if !bulk_signers.contains(&signer) {
+                        bulk_signers.push(signer);
+                    }
+                    account
+                },
+            );
+
+            let owner = config.pubkey_or_default(arg_matches, "owner", &mut wallet_manager)?;
+            command_create_account(
+                config,
+                token,
+                owner,
+                account,
+                arg_matches.is_present("immutable"),
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::CreateMultisig, arg_matches) => {
+            let minimum_signers = value_of::<u8>(arg_matches, "minimum_signers").unwrap();
+            let multisig_members =
+                pubkeys_of_multiple_signers(arg_matches, "multisig_member", &mut wallet_manager)
+                    .unwrap_or_else(|e| {
+                        eprintln!("error: {}", e);
+                        exit(1);
+                    })
+                    .unwrap();
+            if minimum_signers as usize > multisig_members.len() {
+                eprintln!(
+                    "error: MINIMUM_SIGNERS cannot be greater than the number \
+                          of MULTISIG_MEMBERs passed"
+                );
+                exit(1);
+            }
+
+            let (signer, _) = get_signer(arg_matches, "address_keypair", &mut wallet_manager)
+                .unwrap_or_else(new_throwaway_signer);
 
-    let mut wallet_manager = None;
-    let mut bulk_signers: Vec<Arc<dyn Signer>> = Vec::new();
+            command_create_multisig(config, signer, minimum_signers, multisig_members).await
+        }
+        (CommandName::Authorize, arg_matches) => {
+            let address = pubkey_of_signer(arg_matches, "address", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let authority_type = arg_matches.value_of("authority_type").unwrap();
+            let authority_type = match authority_type {
+                "mint" => AuthorityType::MintTokens,
+                "freeze" => AuthorityType::FreezeAccount,
+                "owner" => AuthorityType::AccountOwner,
+                "close" => AuthorityType::CloseAccount,
+                "close-mint" => AuthorityType::CloseMint,
+                "transfer-fee-config" => AuthorityType::TransferFeeConfig,
+                "withheld-withdraw" => AuthorityType::WithheldWithdraw,
+                "interest-rate" => AuthorityType::InterestRate,
+                "permanent-delegate" => AuthorityType::PermanentDelegate,
+                "transfer-hook-program-id" => AuthorityType::TransferHookProgramId,
+                "ui-amount-multiplier" => AuthorityType::ScaledUiAmount,
+                "pause" => AuthorityType::Pause,
+                _ => unreachable!(),
+            };
 
-    let (sub_command, sub_matches) = app_matches.subcommand();
-    let sub_command = CommandName::from_str(sub_command).unwrap();
-    let matches = sub_matches.unwrap();
+            let (authority_signer, authority) =
+                config.signer_or_default(arg_matches, "authority", &mut wallet_manager);
+            if !bulk_signers.contains(&authority_signer) {
+                bulk_signers.push(authority_signer);
+            }
 
-    let mut multisigner_ids = Vec::new();
-    let config = Config::new(
-        matches,
-        &mut wallet_manager,
-        &mut bulk_signers,
-        &mut multisigner_ids,
-    )
-    .await;
+            let new_authority =
+                pubkey_of_signer(arg_matches, "new_authority", &mut wallet_manager).unwrap();
+            let force_authorize = arg_matches.is_present("force");
+            command_authorize(
+                config,
+                address,
+                authority_type,
+                authority,
+                new_authority,
+                force_authorize,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::Transfer, arg_matches) => {
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let amount = match arg_matches.value_of("amount").unwrap() {
+                "ALL" => None,
+                amount => Some(amount.parse::<f64>().unwrap()),
+            };
+            let recipient = pubkey_of_signer(arg_matches, "recipient", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let sender = pubkey_of_signer(arg_matches, "from", &mut wallet_manager).unwrap();
 
-    solana_logger::setup_with_default("solana=info");
-    let result =
-        process_command(&sub_command, matches, &config, wallet_manager, bulk_signers).await?;
-    println!("{}", result);
-    Ok(())
-}
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if !bulk_signers.contains(&owner_signer) {
+                bulk_signers.push(owner_signer);
+            }
 
-async fn process_command<'a>(
-    sub_command: &CommandName,
-    sub_matches: &ArgMatches<'_>,
-    config: &Config<'a>,
-    mut wallet_manager: Option<Arc<RemoteWalletManager>>,
-    mut bulk_signers: Vec<Arc<dyn Signer>>,
-) -> CommandResult {
-    match (sub_command, sub_matches) {
-        (CommandName::Bench, arg_matches) => {
-            bench_process_command(
-                arg_matches,
+            let mint_decimals = value_of::<u8>(arg_matches, MINT_DECIMALS_ARG.name);
+            let fund_recipient = arg_matches.is_present("fund_recipient");
+            let allow_unfunded_recipient = arg_matches.is_present("allow_empty_recipient")
+                || arg_matches.is_present("allow_unfunded_recipient");
+
+            let recipient_is_ata_owner = arg_matches.is_present("recipient_is_ata_owner");
+            let use_unchecked_instruction = arg_matches.is_present("use_unchecked_instruction");
+            let memo = value_t!(arg_matches, "memo", String).ok();
+
+            command_transfer(
                 config,
-                std::mem::take(&mut bulk_signers),
-                &mut wallet_manager,
+                token,
+                amount,
+                recipient,
+                sender,
+                owner,
+                allow_unfunded_recipient,
+                fund_recipient,
+                mint_decimals,
+                recipient_is_ata_owner,
+                use_unchecked_instruction,
+                memo,
+                bulk_signers,
+                arg_matches.is_present("no_wait"),
+                arg_matches.is_present("allow_non_system_account_recipient"),
+            )
+            .await
+        }
+        (CommandName::Burn, arg_matches) => {
+            let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if !bulk_signers.contains(&owner_signer) {
+                bulk_signers.push(owner_signer);
+            }
+
+            let amount = value_t_or_exit!(arg_matches, "amount", f64);
+            let mint_address =
+                pubkey_of_signer(arg_matches, MINT_ADDRESS_ARG.name, &mut wallet_manager).unwrap();
+            let mint_decimals = value_of::<u8>(arg_matches, MINT_DECIMALS_ARG.name);
+            let use_unchecked_instruction = arg_matches.is_present("use_unchecked_instruction");
+            let memo = value_t!(arg_matches, "memo", String).ok();
+            command_burn(
+                config,
+                account,
+                owner,
+                amount,
+                mint_address,
+                mint_decimals,
+                use_unchecked_instruction,
+                memo,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::Mint, arg_matches) => {
+            let (mint_authority_signer, mint_authority) =
+                config.signer_or_default(arg_matches, "mint_authority", &mut wallet_manager);
+            if !bulk_signers.contains(&mint_authority_signer) {
+                bulk_signers.push(mint_authority_signer);
+            }
+
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let amount = value_t_or_exit!(arg_matches, "amount", f64);
+            let mint_decimals = value_of::<u8>(arg_matches, MINT_DECIMALS_ARG.name);
+            let mint_info = config.get_mint_info(&token, mint_decimals).await?;
+            let recipient = if let Some(address) =
+                pubkey_of_signer(arg_matches, "recipient", &mut wallet_manager).unwrap()
+            {
+                address
+            } else {
+                let owner = config.default_signer()?.pubkey();
+                config.associated_token_address_for_token_and_program(
+                    &mint_info.address,
+                    &owner,
+                    &mint_info.program_id,
+                )?
+            };
+            config.check_account(&recipient, Some(token)).await?;
+            let use_unchecked_instruction = arg_matches.is_present("use_unchecked_instruction");
+            let memo = value_t!(arg_matches, "memo", String).ok();
+            command_mint(
+                config,
+                token,
+                amount,
+                recipient,
+                mint_info,
+                mint_authority,
+                use_unchecked_instruction,
+                memo,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::Freeze, arg_matches) => {
+            let (freeze_authority_signer, freeze_authority) =
+                config.signer_or_default(arg_matches, "freeze_authority", &mut wallet_manager);
+            if !bulk_signers.contains(&freeze_authority_signer) {
+                bulk_signers.push(freeze_authority_signer);
+            }
+
+            let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let mint_address =
+                pubkey_of_signer(arg_matches, MINT_ADDRESS_ARG.name, &mut wallet_manager).unwrap();
+            command_freeze(
+                config,
+                account,
+                mint_address,
+                freeze_authority,
+                bulk_signers,
+            )
+            .await
+        }
+        (CommandName::Thaw, arg_matches) => {
+            let (freeze_authority_signer, freeze_authority) =
+                config.signer_or_default(arg_matches, "freeze_authority", &mut wallet_manager);
+            if !bulk_signers.contains(&freeze_authority_signer) {
+                bulk_signers.push(freeze_authority_signer);
+            }
+
+            let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let mint_address =
+                pubkey_of_signer(arg_matches, MINT_ADDRESS_ARG.name, &mut wallet_manager).unwrap();
+            command_thaw(
+                config,
+                account,
+                mint_address,
+                freeze_authority,
+                bulk_signers,
             )
             .await
         }
-        (CommandName::CreateToken, arg_matches) => {
-            let decimals = value_t_or_exit!(arg_matches, "decimals", u8);
-            let mint_authority =
-                config.pubkey_or_default(arg_matches, "mint_authority", &mut wallet_manager)?;
-            let memo = value_t!(arg_matches, "memo", String).ok();
-            let rate_bps = value_t!(arg_matches, "interest_rate", i16).ok();
+        (CommandName::Wrap, arg_matches) => {
+            let amount = value_t_or_exit!(arg_matches, "amount", f64);
+            let account = if arg_matches.is_present("create_aux_account") {
+                let (signer, account) = new_throwaway_signer();
+                if !bulk_signers.contains(&signer) {
+                    bulk_signers.push(signer);
+                }
+                Some(account)
+            } else {
+                // No need to add a signer when creating an associated token account
+                None
+            };
 
-            let (token_signer, token) =
-                get_signer(arg_matches, "token_keypair", &mut wallet_manager)
-                    .unwrap_or_else(new_throwaway_signer);
-            if !bulk_signers.contains(&token_signer) {
-                bulk_signers.push(token_signer);
+            let (wallet_signer, wallet_address) =
+                config.signer_or_default(arg_matches, "wallet_keypair", &mut wallet_manager);
+            if !bulk_signers.contains(&wallet_signer) {
+                bulk_signers.push(wallet_signer);
             }
 
-            command_create_token(
+            command_wrap(
                 config,
-                decimals,
-                token,
-                mint_authority,
-                arg_matches.is_present("enable_freeze"),
-                arg_matches.is_present("enable_close"),
-                memo,
-                rate_bps,
+                amount,
+                wallet_address,
+                account,
+                arg_matches.is_present("immutable"),
                 bulk_signers,
             )
             .await
         }
-        (CommandName::SetInterestRate, arg_matches) => {
-            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+        (CommandName::Unwrap, arg_matches) => {
+            let (wallet_signer, wallet_address) =
+                config.signer_or_default(arg_matches, "wallet_keypair", &mut wallet_manager);
+            if !bulk_signers.contains(&wallet_signer) {
+                bulk_signers.push(wallet_signer);
+            }
+
+            let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager).unwrap();
+            command_unwrap(config, wallet_address, account, bulk_signers).await
+        }
+        (CommandName::Approve, arg_matches) => {
+            let (owner_signer, owner_address) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if !bulk_signers.contains(&owner_signer) {
+                bulk_signers.push(owner_signer);
+            }
+
+            let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
                 .unwrap()
                 .unwrap();
-            let rate_bps = value_t_or_exit!(arg_matches, "rate", i16);
-            let (rate_authority_signer, rate_authority_pubkey) =
-                config.signer_or_default(arg_matches, "rate_authority", &mut wallet_manager);
-            let bulk_signers = vec![rate_authority_signer];
-
-            command_set_interest_rate(
+            let amount = value_t_or_exit!(arg_matches, "amount", f64);
+            let delegate = pubkey_of_signer(arg_matches, "delegate", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let mint_address =
+                pubkey_of_signer(arg_matches, MINT_ADDRESS_ARG.name, &mut wallet_manager).unwrap();
+            let mint_decimals = value_of::<u8>(arg_matches, MINT_DECIMALS_ARG.name);
+            let use_unchecked_instruction = arg_matches.is_present("use_unchecked_instruction");
+            command_approve(
                 config,
-                token_pubkey,
-                rate_authority_pubkey,
-                rate_bps,
+                account,
+                owner_address,
+                amount,
+                delegate,
+                mint_address,
+                mint_decimals,
+                use_unchecked_instruction,
                 bulk_signers,
             )
             .await
         }
-        (CommandName::CreateAccount, arg_matches) => {
-            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+        (CommandName::Revoke, arg_matches) => {
+            let (owner_signer, owner_address) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if !bulk_signers.contains(&owner_signer) {
+                bulk_signers.push(owner_signer);
+            }
+
+            let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
                 .unwrap()
                 .unwrap();
-
-            // No need to add a signer when creating an associated token account
-            let account = get_signer(arg_matches, "account_keypair", &mut wallet_manager).map(
-                |(signer, account)| {
-                    if !bulk_signers.contains(&signer) {
-                        bulk_signers.push(signer);
-                    }
-                    account
-                },
-            );
-
-            let owner = config.pubkey_or_default(arg_matches, "owner", &mut wallet_manager)?;
-            command_create_account(
+            let delegate_address =
+                pubkey_of_signer(arg_matches, DELEGATE_ADDRESS_ARG.name, &mut wallet_manager)
+                    .unwrap();
+            command_revoke(
                 config,
-                token,
-                owner,
                 account,
-                arg_matches.is_present("immutable"),
+                owner_address,
+                delegate_address,
                 bulk_signers,
             )
             .await
         }
-        (CommandName::CreateMultisig, arg_matches) => {
-            let minimum_signers = value_of::<u8>(arg_matches, "minimum_signers").unwrap();
-            let multisig_members =
-                pubkeys_of_multiple_signers(arg_matches, "multisig_member", &mut wallet_manager)
-                    .unwrap_or_else(|e| {
-                        eprintln!("error: {}", e);
-                        exit(1);
-                    })
-                    .unwrap();
-            if minimum_signers as usize > multisig_members.len() {
-                eprintln!(
-                    "error: MINIMUM_SIGNERS cannot be greater than the number \
-                          of MULTISIG_MEMBERs passed"
-                );
-                exit(1);
+        (CommandName::Close, arg_matches) => {
+            let (close_authority_signer, close_authority) =
+                config.signer_or_default(arg_matches, "close_authority", &mut wallet_manager);
+            if !bulk_signers.contains(&close_authority_signer) {
+                bulk_signers.push(close_authority_signer);
             }
 
-            let (signer, _) = get_signer(arg_matches, "address_keypair", &mut wallet_manager)
-                .unwrap_or_else(new_throwaway_signer);
+            let address = config
+                .associated_token_address_or_override(arg_matches, "address", &mut wallet_manager)
+                .await?;
+            let recipient =
+                config.pubkey_or_default(arg_matches, "recipient", &mut wallet_manager)?;
+            command_close(config, address, close_authority, recipient, bulk_signers).await
+        }
+        (CommandName::CloseMint, arg_matches) => {
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let (close_authority_signer, close_authority) =
+                config.signer_or_default(arg_matches, "close_authority", &mut wallet_manager);
+            if !bulk_signers.contains(&close_authority_signer) {
+                bulk_signers.push(close_authority_signer);
+            }
+            let recipient =
+                config.pubkey_or_default(arg_matches, "recipient", &mut wallet_manager)?;
 
-            command_create_multisig(config, signer, minimum_signers, multisig_members).await
+            command_close_mint(config, token, close_authority, recipient, bulk_signers).await
         }
-        (CommandName::Authorize, arg_matches) => {
-            let address = pubkey_of_signer(arg_matches, "address", &mut wallet_manager)
+        (CommandName::Balance, arg_matches) => {
+            let address = config
+                .associated_token_address_or_override(arg_matches, "address", &mut wallet_manager)
+                .await?;
+            command_balance(config, address).await
+        }
+        (CommandName::Supply, arg_matches) => {
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
                 .unwrap()
                 .unwrap();
-            let authority_type = arg_matches.value_of("authority_type").unwrap();
-            let authority_type = match authority_type {
-                "mint" => AuthorityType::MintTokens,
-                "freeze" => AuthorityType::FreezeAccount,
-                "owner" => AuthorityType::AccountOwner,
-                "close" => AuthorityType::CloseAccount,
-                "close-mint" => AuthorityType::CloseMint,
-                "transfer-fee-config" => AuthorityType::TransferFeeConfig,
-                "withheld-withdraw" => AuthorityType::WithheldWithdraw,
-                "interest-rate" => AuthorityType::InterestRate,
-                _ => unreachable!(),
+            command_supply(config, token).await
+        }
+        (CommandName::Accounts, arg_matches) => {
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager).unwrap();
+            let owner = config.pubkey_or_default(arg_matches, "owner", &mut wallet_manager)?;
+            let filter = if arg_matches.is_present("delegated") {
+                AccountFilter::Delegated
+            } else if arg_matches.is_present("externally_closeable") {
+                AccountFilter::ExternallyCloseable
+            } else {
+                AccountFilter::All
             };
 
-            let (authority_signer, authority) =
-                config.signer_or_default(arg_matches, "authority", &mut wallet_manager);
-            if !bulk_signers.contains(&authority_signer) {
-                bulk_signers.push(authority_signer);
-            }
-
-            let new_authority =
-                pubkey_of_signer(arg_matches, "new_authority", &mut wallet_manager).unwrap();
-            let force_authorize = arg_matches.is_present("force");
-            command_authorize(
+            command_accounts(
                 config,
-                address,
-                authority_type,
-                authority,
-                new_authority,
-                force_authorize,
-                bulk_signers,
+                token,
+                owner,
+                filter,
+                arg_matches.is_present("addresses_only"),
             )
             .await
         }
-        (CommandName::Transfer, arg_matches) => {
-            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+        (CommandName::Address, arg_matches) => {
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager).unwrap();
+            let owner = config.pubkey_or_default(arg_matches, "owner", &mut wallet_manager)?;
+            command_address(config, token, owner).await
+        }
+        (CommandName::AccountInfo, arg_matches) => {
+            let address = config
+                .associated_token_address_or_override(arg_matches, "address", &mut wallet_manager)
+                .await?;
+            command_display(config, address).await
+        }
+        (CommandName::MultisigInfo, arg_matches) => {
+            let address = pubkey_of_signer(arg_matches, "address", &mut wallet_manager)
                 .unwrap()
                 .unwrap();
-            let amount = match arg_matches.value_of("amount").unwrap() {
-                "ALL" => None,
-                amount => Some(amount.parse::<f64>().unwrap()),
-            };
-            let recipient = pubkey_of_signer(arg_matches, "recipient", &mut wallet_manager)
+            command_display(config, address).await
+        }
+        (CommandName::Display, arg_matches) => {
+            let address = pubkey_of_signer(arg_matches, "address", &mut wallet_manager)
                 .unwrap()
                 .unwrap();
-            let sender = pubkey_of_signer(arg_matches, "from", &mut wallet_manager).unwrap();
+            command_display(config, address).await
+        }
+        (CommandName::Gc, arg_matches) => {
+            match config.output_format {
+                OutputFormat::Json | OutputFormat::JsonCompact => {
+                    eprintln!(
+                        "`spl-token gc` does not support the `--ouput` parameter at this time"
+                    );
+                    exit(1);
+                }
+                _ => {}
+            }
 
-            let (owner_signer, owner) =
+            let close_empty_associated_accounts =
+                arg_matches.is_present("close_empty_associated_accounts");
+
+            let (owner_signer, owner_address) =
                 config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
             if !bulk_signers.contains(&owner_signer) {
                 bulk_signers.push(owner_signer);
             }
 
-            let mint_decimals = value_of::<u8>(arg_matches, MINT_DECIMALS_ARG.name);
-            let fund_recipient = arg_matches.is_present("fund_recipient");
-            let allow_unfunded_recipient = arg_matches.is_present("allow_empty_recipient")
-                || arg_matches.is_present("allow_unfunded_recipient");
-
-            let recipient_is_ata_owner = arg_matches.is_present("recipient_is_ata_owner");
-            let use_unchecked_instruction = arg_matches.is_present("use_unchecked_instruction");
-            let memo = value_t!(arg_matches, "memo", String).ok();
-
-            command_transfer(
+            command_gc(
                 config,
-                token,
-                amount,
-                recipient,
-                sender,
-                owner,
-                allow_unfunded_recipient,
-                fund_recipient,
-                mint_decimals,
-                recipient_is_ata_owner,
-                use_unchecked_instruction,
-                memo,
+                owner_address,
+                close_empty_associated_accounts,
                 bulk_signers,
-                arg_matches.is_present("no_wait"),
-                arg_matches.is_present("allow_non_system_account_recipient"),
             )
             .await
         }
-        (CommandName::Burn, arg_matches) => {
-            let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
-                .unwrap()
-                .unwrap();
-
+        (CommandName::SyncNative, arg_matches) => {
+            let native_mint = *native_token_client_from_config(config)?.get_address();
+            let address = config
+                .associated_token_address_for_token_or_override(
+                    arg_matches,
+                    "address",
+                    &mut wallet_manager,
+                    Some(native_mint),
+                )
+                .await;
+            command_sync_native(config, address?).await
+        }
+        (CommandName::EnableRequiredTransferMemos, arg_matches) => {
             let (owner_signer, owner) =
                 config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
             if !bulk_signers.contains(&owner_signer) {
                 bulk_signers.push(owner_signer);
             }
-
-            let amount = value_t_or_exit!(arg_matches, "amount", f64);
+            // Since account is required argument it will always be present
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            command_required_transfer_memos(config, token_account, owner, bulk_signers, true).await
+        }
+        (CommandName::DisableRequiredTransferMemos, arg_matches) => {
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if !bulk_signers.contains(&owner_signer) {
+                bulk_signers.push(owner_signer);
+            }
+            // Since account is required argument it will always be present
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            command_required_transfer_memos(config, token_account, owner, bulk_signers, false).await
+        }
+        (CommandName::EnableCpiGuard, arg_matches) => {
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if !bulk_signers.contains(&owner_signer) {
+                bulk_signers.push(owner_signer);
+            }
+            // Since account is required argument it will always be present
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            command_cpi_guard(config, token_account, owner, bulk_signers, true).await
+        }
+        (CommandName::DisableCpiGuard, arg_matches) => {
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if !bulk_signers.contains(&owner_signer) {
+                bulk_signers.push(owner_signer);
+            }
+            // Since account is required argument it will always be present
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            command_cpi_guard(config, token_account, owner, bulk_signers, false).await
+        }
+        (CommandName::ConfigureConfidentialTransferAccount, arg_matches) => {
+            let (owner_signer, _) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if !bulk_signers.contains(&owner_signer) {
+                bulk_signers.push(owner_signer.clone());
+            }
+            // Since account is required argument it will always be present
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
             let mint_address =
                 pubkey_of_signer(arg_matches, MINT_ADDRESS_ARG.name, &mut wallet_manager).unwrap();
-            let mint_decimals = value_of::<u8>(arg_matches, MINT_DECIMALS_ARG.name);
-            let use_unchecked_instruction = arg_matches.is_present("use_unchecked_instruction");
-            let memo = value_t!(arg_matches, "memo", String).ok();
-            command_burn(
+            let maximum_pending_balance_credit_counter =
+                value_of::<u64>(arg_matches, "maximum_pending_balance_credit_counter");
+            command_configure_confidential_transfer_account(
                 config,
-                account,
-                owner,
-                amount,
+                token_account,
+                owner_signer,
                 mint_address,
-                mint_decimals,
-                use_unchecked_instruction,
-                memo,
+                maximum_pending_balance_credit_counter,
                 bulk_signers,
             )
             .await
         }
-        (CommandName::Mint, arg_matches) => {
-            let (mint_authority_signer, mint_authority) =
-                config.signer_or_default(arg_matches, "mint_authority", &mut wallet_manager);
-            if !bulk_signers.contains(&mint_authority_signer) {
-                bulk_signers.push(mint_authority_signer);
+        (CommandName::ApproveConfidentialTransferAccount, arg_matches) => {
+            let (authority_signer, authority) = config.signer_or_default(
+                arg_matches,
+                "confidential_transfer_authority",
+                &mut wallet_manager,
+            );
+            if !bulk_signers.contains(&authority_signer) {
+                bulk_signers.push(authority_signer);
             }
-
-            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
-                .unwrap()
-                .unwrap();
-            let amount = value_t_or_exit!(arg_matches, "amount", f64);
-            let mint_decimals = value_of::<u8>(arg_matches, MINT_DECIMALS_ARG.name);
-            let mint_info = config.get_mint_info(&token, mint_decimals).await?;
-            let recipient = if let Some(address) =
-                pubkey_of_signer(arg_matches, "recipient", &mut wallet_manager).unwrap()
-            {
-                address
-            } else {
-                let owner = config.default_signer()?.pubkey();
-                config.associated_token_address_for_token_and_program(
-                    &mint_info.address,
-                    &owner,
-                    &mint_info.program_id,
-                )?
-            };
-            config.check_account(&recipient, Some(token)).await?;
-            let use_unchecked_instruction = arg_matches.is_present("use_unchecked_instruction");
-            let memo = value_t!(arg_matches, "memo", String).ok();
-            command_mint(
+            // Since account is required argument it will always be present
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            let mint_address =
+                pubkey_of_signer(arg_matches, MINT_ADDRESS_ARG.name, &mut wallet_manager).unwrap();
+            command_approve_confidential_transfer_account(
                 config,
-                token,
-                amount,
-                recipient,
-                mint_info,
-                mint_authority,
-                use_unchecked_instruction,
-                memo,
+                token_account,
+                authority,
+                mint_address,
                 bulk_signers,
             )
             .await
         }
-        (CommandName::Freeze, arg_matches) => {
-            let (freeze_authority_signer, freeze_authority) =
-                config.signer_or_default(arg_matches, "freeze_authority", &mut wallet_manager);
-            if !bulk_signers.contains(&freeze_authority_signer) {
-                bulk_signers.push(freeze_authority_signer);
+        (CommandName::DepositConfidentialTokens, arg_matches) => {
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if !bulk_signers.contains(&owner_signer) {
+                bulk_signers.push(owner_signer);
             }
-
-            let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
-                .unwrap()
-                .unwrap();
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            let amount = value_t_or_exit!(arg_matches, "amount", f64);
             let mint_address =
                 pubkey_of_signer(arg_matches, MINT_ADDRESS_ARG.name, &mut wallet_manager).unwrap();
-            command_freeze(
+            let mint_decimals = value_of::<u8>(arg_matches, MINT_DECIMALS_ARG.name);
+            command_deposit_confidential_tokens(
                 config,
-                account,
+                token_account,
+                owner,
+                amount,
                 mint_address,
-                freeze_authority,
+                mint_decimals,
                 bulk_signers,
             )
             .await
         }
-        (CommandName::Thaw, arg_matches) => {
-            let (freeze_authority_signer, freeze_authority) =
-                config.signer_or_default(arg_matches, "freeze_authority", &mut wallet_manager);
-            if !bulk_signers.contains(&freeze_authority_signer) {
-                bulk_signers.push(freeze_authority_signer);
+        (CommandName::WithdrawConfidentialTokens, arg_matches) => {
+            let (owner_signer, _) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if !bulk_signers.contains(&owner_signer) {
+                bulk_signers.push(owner_signer.clone());
             }
-
-            let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
-                .unwrap()
-                .unwrap();
-            let mint_address =
-                pubkey_of_signer(arg_matches, MINT_ADDRESS_ARG.name, &mut wallet_manager).unwrap();
-            command_thaw(
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            let amount = match arg_matches.value_of("amount").unwrap() {
+                "ALL" => None,
+                amount => Some(amount.parse::<f64>().unwrap()),
+            };
+            command_withdraw_confidential_tokens(
                 config,
-                account,
-                mint_address,
-                freeze_authority,
+                token_account,
+                owner_signer,
+                amount,
                 bulk_signers,
             )
             .await
         }
-        (CommandName::Wrap, arg_matches) => {
-            let amount = value_t_or_exit!(arg_matches, "amount", f64);
-            let account = if arg_matches.is_present("create_aux_account") {
-                let (signer, account) = new_throwaway_signer();
-                if !bulk_signers.contains(&signer) {
-                    bulk_signers.push(signer);
-                }
-                Some(account)
-            } else {
-                // No need to add a signer when creating an associated token account
-                None
-            };
-
-            let (wallet_signer, wallet_address) =
-                config.signer_or_default(arg_matches, "wallet_keypair", &mut wallet_manager);
-            if !bulk_signers.contains(&wallet_signer) {
-                bulk_signers.push(wallet_signer);
+        (CommandName::ConfidentialTransfer, arg_matches) => {
+            let (owner_signer, _) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if !bulk_signers.contains(&owner_signer) {
+                bulk_signers.push(owner_signer.clone());
             }
-
-            command_wrap(
+            let source_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            let amount = value_t_or_exit!(arg_matches, "amount", f64);
+            let recipient = pubkey_of_signer(arg_matches, "recipient", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            command_confidential_transfer(
                 config,
+                source_account,
+                recipient,
+                owner_signer,
                 amount,
-                wallet_address,
-                account,
-                arg_matches.is_present("immutable"),
                 bulk_signers,
             )
             .await
         }
-        (CommandName::Unwrap, arg_matches) => {
-            let (wallet_signer, wallet_address) =
-                config.signer_or_default(arg_matches, "wallet_keypair", &mut wallet_manager);
-            if !bulk_signers.contains(&wallet_signer) {
-                bulk_signers.push(wallet_signer);
+        (CommandName::ApplyPendingBalance, arg_matches) => {
+            let (owner_signer, _) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            if !bulk_signers.contains(&owner_signer) {
+                bulk_signers.push(owner_signer.clone());
             }
-
-            let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager).unwrap();
-            command_unwrap(config, wallet_address, account, bulk_signers).await
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            command_apply_pending_balance(config, token_account, owner_signer, bulk_signers).await
         }
-        (CommandName::Approve, arg_matches) => {
-            let (owner_signer, owner_address) =
+        (CommandName::EnableConfidentialCredits, arg_matches) => {
+            let (owner_signer, owner) =
                 config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
             if !bulk_signers.contains(&owner_signer) {
                 bulk_signers.push(owner_signer);
             }
-
-            let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
-                .unwrap()
-                .unwrap();
-            let amount = value_t_or_exit!(arg_matches, "amount", f64);
-            let delegate = pubkey_of_signer(arg_matches, "delegate", &mut wallet_manager)
-                .unwrap()
-                .unwrap();
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
             let mint_address =
                 pubkey_of_signer(arg_matches, MINT_ADDRESS_ARG.name, &mut wallet_manager).unwrap();
-            let mint_decimals = value_of::<u8>(arg_matches, MINT_DECIMALS_ARG.name);
-            let use_unchecked_instruction = arg_matches.is_present("use_unchecked_instruction");
-            command_approve(
+            command_confidential_credits(
                 config,
-                account,
-                owner_address,
-                amount,
-                delegate,
-                mint_address,
-                mint_decimals,
-                use_unchecked_instruction,
+                token_account,
+                owner,
+                mint_address,
                 bulk_signers,
+                true,
             )
             .await
         }
-        (CommandName::Revoke, arg_matches) => {
-            let (owner_signer, owner_address) =
+        (CommandName::DisableConfidentialCredits, arg_matches) => {
+            let (owner_signer, owner) =
                 config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
             if !bulk_signers.contains(&owner_signer) {
                 bulk_signers.push(owner_signer);
             }
-
-            let account = pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
-                .unwrap()
-                .unwrap();
-            let delegate_address =
-                pubkey_of_signer(arg_matches, DELEGATE_ADDRESS_ARG.name, &mut wallet_manager)
-                    .unwrap();
-            command_revoke(
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            let mint_address =
+                pubkey_of_signer(arg_matches, MINT_ADDRESS_ARG.name, &mut wallet_manager).unwrap();
+            command_confidential_credits(
                 config,
-                account,
-                owner_address,
-                delegate_address,
+                token_account,
+                owner,
+                mint_address,
                 bulk_signers,
+                false,
             )
             .await
         }
-        (CommandName::Close, arg_matches) => {
-            let (close_authority_signer, close_authority) =
-                config.signer_or_default(arg_matches, "close_authority", &mut wallet_manager);
-            if !bulk_signers.contains(&close_authority_signer) {
-                bulk_signers.push(close_authority_signer);
-            }
-
-            let address = config
-                .associated_token_address_or_override(arg_matches, "address", &mut wallet_manager)
-                .await?;
-            let recipient =
-                config.pubkey_or_default(arg_matches, "recipient", &mut wallet_manager)?;
-            command_close(config, address, close_authority, recipient, bulk_signers).await
+        (CommandName::ConfidentialBalance, arg_matches) => {
+            let (owner_signer, _) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            let token_account =
+                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
+            command_confidential_balance(config, token_account, owner_signer).await
         }
-        (CommandName::CloseMint, arg_matches) => {
-            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
+        (CommandName::SetTransferFee, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
                 .unwrap()
                 .unwrap();
-            let (close_authority_signer, close_authority) =
-                config.signer_or_default(arg_matches, "close_authority", &mut wallet_manager);
-            if !bulk_signers.contains(&close_authority_signer) {
-                bulk_signers.push(close_authority_signer);
+            let transfer_fee_basis_points =
+                value_t_or_exit!(arg_matches, "transfer_fee_basis_points", u16);
+            let maximum_fee = value_t_or_exit!(arg_matches, "maximum_fee", f64);
+            let (transfer_fee_authority_signer, transfer_fee_authority_pubkey) = config
+                .signer_or_default(arg_matches, "transfer_fee_authority", &mut wallet_manager);
+            let mint_decimals = value_of::<u8>(arg_matches, MINT_DECIMALS_ARG.name);
+            if !bulk_signers.contains(&transfer_fee_authority_signer) {
+                bulk_signers.push(transfer_fee_authority_signer);
             }
-            let recipient =
-                config.pubkey_or_default(arg_matches, "recipient", &mut wallet_manager)?;
-
-            command_close_mint(config, token, close_authority, recipient, bulk_signers).await
-        }
-        (CommandName::Balance, arg_matches) => {
-            let address = config
-                .associated_token_address_or_override(arg_matches, "address", &mut wallet_manager)
-                .await?;
-            command_balance(config, address).await
-        }
-        (CommandName::Supply, arg_matches) => {
-            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
-                .unwrap()
-                .unwrap();
-            command_supply(config, token).await
-        }
-        (CommandName::Accounts, arg_matches) => {
-            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager).unwrap();
-            let owner = config.pubkey_or_default(arg_matches, "owner", &mut wallet_manager)?;
-            let filter = if arg_matches.is_present("delegated") {
-                AccountFilter::Delegated
-            } else if arg_matches.is_present("externally_closeable") {
-                AccountFilter::ExternallyCloseable
-            } else {
-                AccountFilter::All
-            };
 
-            command_accounts(
+            command_set_transfer_fee(
                 config,
-                token,
-                owner,
-                filter,
-                arg_matches.is_present("addresses_only"),
+                token_pubkey,
+                transfer_fee_authority_pubkey,
+                transfer_fee_basis_points,
+                maximum_fee,
+                mint_decimals,
+                bulk_signers,
             )
             .await
         }
-        (CommandName::Address, arg_matches) => {
-            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager).unwrap();
-            let owner = config.pubkey_or_default(arg_matches, "owner", &mut wallet_manager)?;
-            command_address(config, token, owner).await
-        }
-        (CommandName::AccountInfo, arg_matches) => {
-            let address = config
-                .associated_token_address_or_override(arg_matches, "address", &mut wallet_manager)
-                .await?;
-            command_display(config, address).await
+        (CommandName::WithdrawWithheldTokens, arg_matches) => {
+            let (authority_signer, authority) = config.signer_or_default(
+                arg_matches,
+                "withdraw_withheld_authority",
+                &mut wallet_manager,
+            );
+            if !bulk_signers.contains(&authority_signer) {
+                bulk_signers.push(authority_signer);
+            }
+            // Since destination is required it will always be present
+            let destination_token_account =
+                pubkey_of_signer(arg_matches, "account", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let include_mint = arg_matches.is_present("include_mint");
+            let source_accounts =
+                pubkeys_of_multiple_signers(arg_matches, "source", &mut wallet_manager)
+                    .unwrap_or_else(|e| {
+                        eprintln!("error: {}", e);
+                        exit(1);
+                    })
+                    .unwrap_or_default();
+            let mint_address =
+                pubkey_of_signer(arg_matches, MINT_ADDRESS_ARG.name, &mut wallet_manager).unwrap();
+            command_withdraw_withheld_tokens(
+                config,
+                destination_token_account,
+                source_accounts,
+                authority,
+                include_mint,
+                mint_address,
+                bulk_signers,
+            )
+            .await
         }
-        (CommandName::MultisigInfo, arg_matches) => {
-            let address = pubkey_of_signer(arg_matches, "address", &mut wallet_manager)
+        (CommandName::Harvest, arg_matches) => {
+            let token_pubkey = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
                 .unwrap()
                 .unwrap();
-            command_display(config, address).await
+            let source_accounts =
+                pubkeys_of_multiple_signers(arg_matches, "source", &mut wallet_manager)
+                    .unwrap_or_else(|e| {
+                        eprintln!("error: {}", e);
+                        exit(1);
+                    })
+                    .unwrap_or_default();
+            command_harvest(config, token_pubkey, source_accounts).await
         }
-        (CommandName::Display, arg_matches) => {
-            let address = pubkey_of_signer(arg_matches, "address", &mut wallet_manager)
+        (CommandName::UpdateDefaultAccountState, arg_matches) => {
+            // Since account is required argument it will always be present
+            let token = pubkey_of_signer(arg_matches, "token", &mut wallet_manager)
                 .unwrap()
                 .unwrap();
-            command_display(config, address).await
-        }
-        (CommandName::Gc, arg_matches) => {
-            match config.output_format {
-                OutputFormat::Json | OutputFormat::JsonCompact => {
-                    eprintln!(
-                        "`spl-token gc` does not support the `--ouput` parameter at this time"
-                    );
-                    exit(1);
-                }
-                _ => {}
-            }
-
-            let close_empty_associated_accounts =
-                arg_matches.is_present("close_empty_associated_accounts");
-
-            let (owner_signer, owner_address) =
-                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
-            if !bulk_signers.contains(&owner_signer) {
-                bulk_signers.push(owner_signer);
+            let (freeze_authority_signer, freeze_authority) =
+                config.signer_or_default(arg_matches, "freeze_authority", &mut wallet_manager);
+            if !bulk_signers.contains(&freeze_authority_signer) {
+                bulk_signers.push(freeze_authority_signer);
             }
-
-            command_gc(
+            let new_default_state = match arg_matches.value_of("state").unwrap() {
+                "initialized" => AccountState::Initialized,
+                "frozen" => AccountState::Frozen,
+                _ => unreachable!(),
+            };
+            command_update_default_account_state(
                 config,
-                owner_address,
-                close_empty_associated_accounts,
+                token,
+                freeze_authority,
+                new_default_state,
                 bulk_signers,
             )
             .await
         }
-        (CommandName::SyncNative, arg_matches) => {
-            let native_mint = *native_token_client_from_config(config)?.get_address();
-            let address = config
-                .associated_token_address_for_token_or_override(
-                    arg_matches,
-                    "address",
-                    &mut wallet_manager,
-                    Some(native_mint),
-                )
-                .await;
-            command_sync_native(config, address?).await
-        }
-        (CommandName::EnableRequiredTransferMemos, arg_matches) => {
-            let (owner_signer, owner) =
-                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
-            if !bulk_signers.contains(&owner_signer) {
-                bulk_signers.push(owner_signer);
-            }
-            // Since account is required argument it will always be present
-            let token_account =
-                config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
-            command_required_transfer_memos(config, token_account, owner, bulk_signers, true).await
-        }
-        (CommandName::DisableRequiredTransferMemos, arg_matches) => {
+        (CommandName::Reallocate, arg_matches) => {
             let (owner_signer, owner) =
                 config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
             if !bulk_signers.contains(&owner_signer) {
@@ -3465,7 +5626,27 @@ async fn process_command<'a>(
             // Since account is required argument it will always be present
             let token_account =
                 config.pubkey_or_default(arg_matches, "account", &mut wallet_manager)?;
-            command_required_transfer_memos(config, token_account, owner, bulk_signers, false).await
+            let extension_types = arg_matches
+                .values_of("extension")
+                .unwrap()
+                .map(|extension| match extension {
+                    "memo-transfer" => ExtensionType::MemoTransfer,
+                    "cpi-guard" => ExtensionType::CpiGuard,
+                    "confidential-transfer" => ExtensionType::ConfidentialTransferAccount,
+                    _ => unreachable!(),
+                })
+                .collect();
+            let mint_address =
+                pubkey_of_signer(arg_matches, MINT_ADDRESS_ARG.name, &mut wallet_manager).unwrap();
+            command_reallocate(
+                config,
+                token_account,
+                owner,
+                extension_types,
+                mint_address,
+                bulk_signers,
+            )
+            .await
         }
     }
 }
@@ -3532,6 +5713,7 @@ mod tests {
             transaction::Transaction,
         },
         solana_test_validator::{ProgramInfo, TestValidator, TestValidatorGenesis},
+        spl_token_2022::extension::transfer_fee::TransferFeeAmount,
         spl_token_client::client::{
             ProgramClient, ProgramRpcClient, ProgramRpcClientSendTransaction,
         },
@@ -3653,6 +5835,14 @@ mod tests {
             payer.pubkey(),
             false,
             false,
+            false,
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
             None,
             None,
             bulk_signers,
@@ -3679,8 +5869,16 @@ mod tests {
             payer.pubkey(),
             false,
             false,
+            false,
+            false,
+            false,
             None,
             Some(rate_bps),
+            None,
+            None,
+            None,
+            None,
+            None,
             bulk_signers,
         )
         .await
@@ -3881,19 +6079,125 @@ mod tests {
             &payer,
             &[
                 "spl-token",
-                CommandName::SetInterestRate.into(),
-                &token.to_string(),
-                &new_rate.to_string(),
+                CommandName::SetInterestRate.into(),
+                &token.to_string(),
+                &new_rate.to_string(),
+            ],
+        )
+        .await;
+        let _value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        let account = config.rpc_client.get_account(&token).await.unwrap();
+        let mint_account = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let extension = mint_account
+            .get_extension::<InterestBearingConfig>()
+            .unwrap();
+        assert_eq!(i16::from(extension.current_rate), new_rate);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn set_ui_amount_multiplier() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let config =
+            test_config_with_default_signer(&test_validator, &payer, &spl_token_2022::id());
+        let token = Keypair::new();
+        let token_pubkey = token.pubkey();
+        let bulk_signers: Vec<Arc<dyn Signer>> =
+            vec![Arc::new(clone_keypair(&payer)), Arc::new(token)];
+        command_create_token(
+            &config,
+            TEST_DECIMALS,
+            token_pubkey,
+            payer.pubkey(),
+            false,
+            false,
+            false,
+            false,
+            false,
+            None,
+            None,
+            Some(2.0),
+            None,
+            None,
+            None,
+            None,
+            bulk_signers,
+        )
+        .await
+        .unwrap();
+        let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+        let mint_account = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let extension = mint_account
+            .get_extension::<ScaledUiAmountConfig>()
+            .unwrap();
+        assert_eq!(f64::from(extension.multiplier), 2.0);
+
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::SetUiAmountMultiplier.into(),
+                &token_pubkey.to_string(),
+                "3.5",
             ],
         )
         .await;
         let _value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
-        let account = config.rpc_client.get_account(&token).await.unwrap();
+        let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
         let mint_account = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
         let extension = mint_account
-            .get_extension::<InterestBearingConfig>()
+            .get_extension::<ScaledUiAmountConfig>()
             .unwrap();
-        assert_eq!(i16::from(extension.current_rate), new_rate);
+        assert_eq!(f64::from(extension.multiplier), 3.5);
+        assert_eq!(f64::from(extension.new_multiplier), 3.5);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn pause_resume() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let config =
+            test_config_with_default_signer(&test_validator, &payer, &spl_token_2022::id());
+        let token = Keypair::new();
+        let token_pubkey = token.pubkey();
+        let bulk_signers: Vec<Arc<dyn Signer>> =
+            vec![Arc::new(clone_keypair(&payer)), Arc::new(token)];
+        command_create_token(
+            &config,
+            TEST_DECIMALS,
+            token_pubkey,
+            payer.pubkey(),
+            false,
+            false,
+            false,
+            false,
+            true,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            bulk_signers,
+        )
+        .await
+        .unwrap();
+
+        for (command, paused) in [(CommandName::Pause, true), (CommandName::Resume, false)] {
+            let result = process_test_command(
+                &config,
+                &payer,
+                &["spl-token", command.into(), &token_pubkey.to_string()],
+            )
+            .await;
+            let _value: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+            let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+            let mint_account = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+            let extension = mint_account.get_extension::<PausableConfig>().unwrap();
+            assert_eq!(bool::from(&extension.paused), paused);
+        }
     }
 
     #[tokio::test]
@@ -4739,65 +7043,404 @@ mod tests {
             )
             .await
             .unwrap();
-
-            let ui_account = config
-                .rpc_client
-                .get_token_account(&source)
+
+            let ui_account = config
+                .rpc_client
+                .get_token_account(&source)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(ui_account.delegate.unwrap(), delegate.pubkey().to_string());
+            assert_eq!(ui_account.delegated_amount.unwrap().amount, "10");
+
+            let result = exec_test_cmd(
+                &config,
+                &[
+                    "spl-token",
+                    CommandName::Burn.into(),
+                    &source.to_string(),
+                    "10",
+                    "--owner",
+                    delegate_keypair_file.path().to_str().unwrap(),
+                    "--fee-payer",
+                    fee_payer_keypair_file.path().to_str().unwrap(),
+                    "--program-id",
+                    &program_id.to_string(),
+                ],
+            )
+            .await;
+            result.unwrap();
+
+            let ui_account = config
+                .rpc_client
+                .get_token_account(&source)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(ui_account.token_amount.amount, "90");
+            assert_eq!(ui_account.delegate, None);
+            assert_eq!(ui_account.delegated_amount, None);
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn close_mint() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let config =
+            test_config_with_default_signer(&test_validator, &payer, &spl_token_2022::id());
+
+        let token_keypair = Keypair::new();
+        let token_pubkey = token_keypair.pubkey();
+        let bulk_signers: Vec<Arc<dyn Signer>> =
+            vec![Arc::new(clone_keypair(&payer)), Arc::new(token_keypair)];
+
+        command_create_token(
+            &config,
+            TEST_DECIMALS,
+            token_pubkey,
+            payer.pubkey(),
+            false,
+            true,
+            false,
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            bulk_signers,
+        )
+        .await
+        .unwrap();
+
+        let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
+        let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data);
+        assert!(test_mint.is_ok());
+
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::CloseMint.into(),
+                &token_pubkey.to_string(),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let account = config.rpc_client.get_account(&token_pubkey).await;
+        assert!(account.is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn required_transfer_memos() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let program_id = spl_token_2022::id();
+        let config = test_config_with_default_signer(&test_validator, &payer, &program_id);
+        let token = create_token(&config, &payer).await;
+        let token_account = create_associated_account(&config, &payer, token).await;
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::EnableRequiredTransferMemos.into(),
+                &token_account.to_string(),
+            ],
+        )
+        .await;
+        result.unwrap();
+        let extensions = StateWithExtensionsOwned::<Account>::unpack(
+            config
+                .rpc_client
+                .get_account(&token_account)
+                .await
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        let memo_transfer = extensions.get_extension::<MemoTransfer>().unwrap();
+        let enabled: bool = memo_transfer.require_incoming_transfer_memos.into();
+        assert!(enabled);
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::DisableRequiredTransferMemos.into(),
+                &token_account.to_string(),
+            ],
+        )
+        .await;
+        result.unwrap();
+        let extensions = StateWithExtensionsOwned::<Account>::unpack(
+            config
+                .rpc_client
+                .get_account(&token_account)
+                .await
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        let memo_transfer = extensions.get_extension::<MemoTransfer>().unwrap();
+        let enabled: bool = memo_transfer.require_incoming_transfer_memos.into();
+        assert!(!enabled);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn cpi_guard() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let program_id = spl_token_2022::id();
+        let config = test_config_with_default_signer(&test_validator, &payer, &program_id);
+        let token = create_token(&config, &payer).await;
+        let token_account = create_associated_account(&config, &payer, token).await;
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::EnableCpiGuard.into(),
+                &token_account.to_string(),
+            ],
+        )
+        .await;
+        result.unwrap();
+        let extensions = StateWithExtensionsOwned::<Account>::unpack(
+            config
+                .rpc_client
+                .get_account(&token_account)
+                .await
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        let cpi_guard = extensions.get_extension::<CpiGuard>().unwrap();
+        let enabled: bool = cpi_guard.lock_cpi.into();
+        assert!(enabled);
+        let result = process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::DisableCpiGuard.into(),
+                &token_account.to_string(),
+            ],
+        )
+        .await;
+        result.unwrap();
+        let extensions = StateWithExtensionsOwned::<Account>::unpack(
+            config
+                .rpc_client
+                .get_account(&token_account)
+                .await
+                .unwrap()
+                .data,
+        )
+        .unwrap();
+        let cpi_guard = extensions.get_extension::<CpiGuard>().unwrap();
+        let enabled: bool = cpi_guard.lock_cpi.into();
+        assert!(!enabled);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn confidential_transfer() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let program_id = spl_token_2022::id();
+        let config = test_config_with_default_signer(&test_validator, &payer, &program_id);
+
+        let mint = Keypair::new();
+        let token = token_client_from_config(&config, &mint.pubkey(), Some(TEST_DECIMALS)).unwrap();
+        token
+            .create_mint(
+                &payer.pubkey(),
+                None,
+                vec![ExtensionInitializationParams::ConfidentialTransferMint {
+                    ct_mint: ConfidentialTransferMint {
+                        authority: payer.pubkey(),
+                        ..ConfidentialTransferMint::default()
+                    },
+                }],
+                &[&mint],
+            )
+            .await
+            .unwrap();
+        let source = create_associated_account(&config, &payer, mint.pubkey()).await;
+        let destination = create_auxiliary_account(&config, &payer, mint.pubkey()).await;
+        mint_tokens(&config, &payer, mint.pubkey(), 100.0, source).await;
+
+        for account in [source, destination] {
+            for command in [
+                CommandName::ConfigureConfidentialTransferAccount,
+                CommandName::ApproveConfidentialTransferAccount,
+            ] {
+                process_test_command(
+                    &config,
+                    &payer,
+                    &["spl-token", command.into(), &account.to_string()],
+                )
                 .await
-                .unwrap()
                 .unwrap();
-            assert_eq!(ui_account.delegate.unwrap(), delegate.pubkey().to_string());
-            assert_eq!(ui_account.delegated_amount.unwrap().amount, "10");
+            }
+            let state = token.get_account_info(&account).await.unwrap();
+            let extension = state
+                .get_extension::<ConfidentialTransferAccount>()
+                .unwrap();
+            assert!(bool::from(&extension.approved));
+        }
 
-            let result = exec_test_cmd(
-                &config,
-                &[
-                    "spl-token",
-                    CommandName::Burn.into(),
-                    &source.to_string(),
-                    "10",
-                    "--owner",
-                    delegate_keypair_file.path().to_str().unwrap(),
-                    "--fee-payer",
-                    fee_payer_keypair_file.path().to_str().unwrap(),
-                    "--program-id",
-                    &program_id.to_string(),
-                ],
-            )
-            .await;
-            result.unwrap();
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::DepositConfidentialTokens.into(),
+                &source.to_string(),
+                "60",
+            ],
+        )
+        .await
+        .unwrap();
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::ApplyPendingBalance.into(),
+                &source.to_string(),
+            ],
+        )
+        .await
+        .unwrap();
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::WithdrawConfidentialTokens.into(),
+                &source.to_string(),
+                "10",
+            ],
+        )
+        .await
+        .unwrap();
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::ConfidentialTransfer.into(),
+                &source.to_string(),
+                "20",
+                &destination.to_string(),
+            ],
+        )
+        .await
+        .unwrap();
 
-            let ui_account = config
-                .rpc_client
-                .get_token_account(&source)
+        let state = token.get_account_info(&source).await.unwrap();
+        assert_eq!(state.base.amount, 50);
+        let (source_elgamal_keypair, source_ae_key) =
+            confidential_transfer_keys(&payer, &source).unwrap();
+        assert_eq!(
+            token
+                .confidential_transfer_get_available_balance_with_key(&source, &source_ae_key)
                 .await
-                .unwrap()
+                .unwrap(),
+            30
+        );
+        assert_eq!(
+            token
+                .confidential_transfer_get_pending_balance_with_key(
+                    &source,
+                    &source_elgamal_keypair
+                )
+                .await
+                .unwrap(),
+            0
+        );
+        let (destination_elgamal_keypair, _) =
+            confidential_transfer_keys(&payer, &destination).unwrap();
+        assert_eq!(
+            token
+                .confidential_transfer_get_pending_balance_with_key(
+                    &destination,
+                    &destination_elgamal_keypair
+                )
+                .await
+                .unwrap(),
+            20
+        );
+
+        // withdraw everything that is left
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::WithdrawConfidentialTokens.into(),
+                &source.to_string(),
+                "ALL",
+            ],
+        )
+        .await
+        .unwrap();
+        let state = token.get_account_info(&source).await.unwrap();
+        assert_eq!(state.base.amount, 80);
+
+        // incoming credits can be disabled and re-enabled
+        for (command, enabled) in [
+            (CommandName::DisableConfidentialCredits, false),
+            (CommandName::EnableConfidentialCredits, true),
+        ] {
+            process_test_command(
+                &config,
+                &payer,
+                &["spl-token", command.into(), &destination.to_string()],
+            )
+            .await
+            .unwrap();
+            let state = token.get_account_info(&destination).await.unwrap();
+            let extension = state
+                .get_extension::<ConfidentialTransferAccount>()
                 .unwrap();
-            assert_eq!(ui_account.token_amount.amount, "90");
-            assert_eq!(ui_account.delegate, None);
-            assert_eq!(ui_account.delegated_amount, None);
+            assert_eq!(bool::from(&extension.allow_balance_credits), enabled);
         }
     }
 
     #[tokio::test]
     #[serial]
-    async fn close_mint() {
+    async fn transfer_fee() {
         let (test_validator, payer) = new_validator_for_test().await;
-        let config =
-            test_config_with_default_signer(&test_validator, &payer, &spl_token_2022::id());
+        let program_id = spl_token_2022::id();
+        let config = test_config_with_default_signer(&test_validator, &payer, &program_id);
 
         let token_keypair = Keypair::new();
-        let token_pubkey = token_keypair.pubkey();
+        let token = token_keypair.pubkey();
         let bulk_signers: Vec<Arc<dyn Signer>> =
             vec![Arc::new(clone_keypair(&payer)), Arc::new(token_keypair)];
-
         command_create_token(
             &config,
             TEST_DECIMALS,
-            token_pubkey,
+            token,
             payer.pubkey(),
             false,
-            true,
+            false,
+            false,
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            Some((100, 10)),
             None,
             None,
             bulk_signers,
@@ -4805,80 +7448,203 @@ mod tests {
         .await
         .unwrap();
 
-        let account = config.rpc_client.get_account(&token_pubkey).await.unwrap();
-        let test_mint = StateWithExtensionsOwned::<Mint>::unpack(account.data);
-        assert!(test_mint.is_ok());
+        let source = create_associated_account(&config, &payer, token).await;
+        let destination = create_auxiliary_account(&config, &payer, token).await;
+        mint_tokens(&config, &payer, token, 1000.0, source).await;
+
+        let get_withheld_amount = |address: Pubkey| {
+            let config = &config;
+            async move {
+                let account = config.rpc_client.get_account(&address).await.unwrap();
+                let token_account =
+                    StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+                u64::from(
+                    token_account
+                        .get_extension::<TransferFeeAmount>()
+                        .unwrap()
+                        .withheld_amount,
+                )
+            }
+        };
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::Transfer.into(),
+                &token.to_string(),
+                "100",
+                &destination.to_string(),
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(get_withheld_amount(destination).await, 1);
 
+        // newer fee only takes effect after two epochs
         process_test_command(
             &config,
             &payer,
             &[
                 "spl-token",
-                CommandName::CloseMint.into(),
-                &token_pubkey.to_string(),
+                CommandName::SetTransferFee.into(),
+                &token.to_string(),
+                "200",
+                "5",
+            ],
+        )
+        .await
+        .unwrap();
+        let account = config.rpc_client.get_account(&token).await.unwrap();
+        let mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let extension = mint.get_extension::<TransferFeeConfig>().unwrap();
+        assert_eq!(
+            u16::from(extension.newer_transfer_fee.transfer_fee_basis_points),
+            200
+        );
+        assert_eq!(u64::from(extension.newer_transfer_fee.maximum_fee), 5);
+
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::Harvest.into(),
+                &token.to_string(),
+                &destination.to_string(),
             ],
         )
         .await
         .unwrap();
+        assert_eq!(get_withheld_amount(destination).await, 0);
+        let account = config.rpc_client.get_account(&token).await.unwrap();
+        let mint = StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap();
+        let extension = mint.get_extension::<TransferFeeConfig>().unwrap();
+        assert_eq!(u64::from(extension.withheld_amount), 1);
 
-        let account = config.rpc_client.get_account(&token_pubkey).await;
-        assert!(account.is_err());
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::Transfer.into(),
+                &token.to_string(),
+                "100",
+                &destination.to_string(),
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(get_withheld_amount(destination).await, 1);
+
+        process_test_command(
+            &config,
+            &payer,
+            &[
+                "spl-token",
+                CommandName::WithdrawWithheldTokens.into(),
+                &source.to_string(),
+                &destination.to_string(),
+                "--include-mint",
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(get_withheld_amount(destination).await, 0);
+        let account = config.rpc_client.get_account(&source).await.unwrap();
+        let token_account = StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap();
+        assert_eq!(token_account.base.amount, 802);
     }
 
     #[tokio::test]
     #[serial]
-    async fn required_transfer_memos() {
+    async fn default_account_state() {
         let (test_validator, payer) = new_validator_for_test().await;
         let program_id = spl_token_2022::id();
         let config = test_config_with_default_signer(&test_validator, &payer, &program_id);
-        let token = create_token(&config, &payer).await;
-        let token_account = create_associated_account(&config, &payer, token).await;
-        let result = process_test_command(
+
+        let token_keypair = Keypair::new();
+        let token = token_keypair.pubkey();
+        let bulk_signers: Vec<Arc<dyn Signer>> =
+            vec![Arc::new(clone_keypair(&payer)), Arc::new(token_keypair)];
+        command_create_token(
+            &config,
+            TEST_DECIMALS,
+            token,
+            payer.pubkey(),
+            true,
+            false,
+            false,
+            false,
+            false,
+            None,
+            None,
+            None,
+            Some(AccountState::Frozen),
+            None,
+            None,
+            None,
+            bulk_signers,
+        )
+        .await
+        .unwrap();
+
+        let account = create_associated_account(&config, &payer, token).await;
+        let token_account = config.rpc_client.get_account(&account).await.unwrap();
+        let token_account =
+            StateWithExtensionsOwned::<Account>::unpack(token_account.data).unwrap();
+        assert_eq!(token_account.base.state, AccountState::Frozen);
+
+        process_test_command(
             &config,
             &payer,
             &[
                 "spl-token",
-                CommandName::EnableRequiredTransferMemos.into(),
-                &token_account.to_string(),
+                CommandName::UpdateDefaultAccountState.into(),
+                &token.to_string(),
+                "initialized",
             ],
         )
-        .await;
-        result.unwrap();
-        let extensions = StateWithExtensionsOwned::<Account>::unpack(
-            config
-                .rpc_client
-                .get_account(&token_account)
-                .await
-                .unwrap()
-                .data,
-        )
+        .await
         .unwrap();
-        let memo_transfer = extensions.get_extension::<MemoTransfer>().unwrap();
-        let enabled: bool = memo_transfer.require_incoming_transfer_memos.into();
-        assert!(enabled);
-        let result = process_test_command(
+        let account = create_auxiliary_account(&config, &payer, token).await;
+        let token_account = config.rpc_client.get_account(&account).await.unwrap();
+        let token_account =
+            StateWithExtensionsOwned::<Account>::unpack(token_account.data).unwrap();
+        assert_eq!(token_account.base.state, AccountState::Initialized);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn reallocate() {
+        let (test_validator, payer) = new_validator_for_test().await;
+        let program_id = spl_token_2022::id();
+        let config = test_config_with_default_signer(&test_validator, &payer, &program_id);
+        let token = create_token(&config, &payer).await;
+        let token_account = create_associated_account(&config, &payer, token).await;
+
+        process_test_command(
             &config,
             &payer,
             &[
                 "spl-token",
-                CommandName::DisableRequiredTransferMemos.into(),
+                CommandName::Reallocate.into(),
                 &token_account.to_string(),
+                "memo-transfer",
+                "cpi-guard",
             ],
         )
-        .await;
-        result.unwrap();
-        let extensions = StateWithExtensionsOwned::<Account>::unpack(
-            config
-                .rpc_client
-                .get_account(&token_account)
-                .await
-                .unwrap()
-                .data,
-        )
+        .await
         .unwrap();
-        let memo_transfer = extensions.get_extension::<MemoTransfer>().unwrap();
-        let enabled: bool = memo_transfer.require_incoming_transfer_memos.into();
-        assert!(!enabled);
+        let account = config.rpc_client.get_account(&token_account).await.unwrap();
+        assert_eq!(
+            account.data.len(),
+            ExtensionType::get_account_len::<Account>(&[
+                ExtensionType::ImmutableOwner,
+                ExtensionType::MemoTransfer,
+                ExtensionType::CpiGuard,
+            ])
+        );
     }
 
     #[tokio::test]
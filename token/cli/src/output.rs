@@ -9,6 +9,8 @@ use solana_account_decoder::{
     },
 };
 use solana_cli_output::{display::writeln_name_value, OutputFormat, QuietDisplay, VerboseDisplay};
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::token_metadata::TokenMetadata;
 use std::fmt::{self, Display};
 
 pub(crate) trait Output: Serialize + fmt::Display + QuietDisplay + VerboseDisplay {}
@@ -129,6 +131,28 @@ impl fmt::Display for CliTokenAmount {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliConfidentialBalance {
+    pub(crate) address: String,
+    pub(crate) available_balance: String,
+    pub(crate) pending_balance: String,
+    pub(crate) decimals: u8,
+}
+
+impl QuietDisplay for CliConfidentialBalance {}
+impl VerboseDisplay for CliConfidentialBalance {}
+
+impl fmt::Display for CliConfidentialBalance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        writeln_name_value(f, "Address:", &self.address)?;
+        writeln_name_value(f, "Available balance:", &self.available_balance)?;
+        writeln_name_value(f, "Pending balance:", &self.pending_balance)?;
+        writeln_name_value(f, "Decimals:", &self.decimals.to_string())
+    }
+}
+
 #[derive(Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct CliWalletAddress {
@@ -270,6 +294,8 @@ pub(crate) struct CliMint {
     pub(crate) epoch: u64,
     #[serde(flatten)]
     pub(crate) mint: UiMint,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub(crate) token_metadata: Option<CliTokenMetadata>,
 }
 
 impl QuietDisplay for CliMint {}
@@ -305,10 +331,58 @@ impl fmt::Display for CliMint {
             }
         }
 
+        if let Some(token_metadata) = &self.token_metadata {
+            writeln!(f, "{}", style("Metadata").bold())?;
+            writeln_name_value(f, "  Name:", &token_metadata.name)?;
+            writeln_name_value(f, "  Symbol:", &token_metadata.symbol)?;
+            writeln_name_value(f, "  URI:", &token_metadata.uri)?;
+            writeln_name_value(
+                f,
+                "  Update authority:",
+                token_metadata
+                    .update_authority
+                    .as_ref()
+                    .unwrap_or(&String::new()),
+            )?;
+            for (key, value) in &token_metadata.additional_metadata {
+                writeln_name_value(f, &format!("  {}:", key), value)?;
+            }
+        }
+
         Ok(())
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliTokenMetadata {
+    pub(crate) update_authority: Option<String>,
+    pub(crate) name: String,
+    pub(crate) symbol: String,
+    pub(crate) uri: String,
+    pub(crate) additional_metadata: Vec<(String, String)>,
+}
+
+impl From<TokenMetadata> for CliTokenMetadata {
+    fn from(token_metadata: TokenMetadata) -> Self {
+        let TokenMetadata {
+            update_authority,
+            name,
+            symbol,
+            uri,
+            additional_metadata,
+            ..
+        } = token_metadata;
+        Self {
+            update_authority: Option::<Pubkey>::from(update_authority).map(|key| key.to_string()),
+            name,
+            symbol,
+            uri,
+            additional_metadata,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct CliTokenAccounts {
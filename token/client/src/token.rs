@@ -5,7 +5,7 @@ use {
         account::Account as BaseAccount,
         epoch_info::EpochInfo,
         hash::Hash,
-        instruction::Instruction,
+        instruction::{AccountMeta, Instruction},
         message::Message,
         program_error::ProgramError,
         program_pack::Pack,
@@ -20,8 +20,14 @@ use {
     },
     spl_token_2022::{
         extension::{
-            confidential_transfer, default_account_state, interest_bearing_mint, memo_transfer,
-            transfer_fee, ExtensionType, StateWithExtensionsOwned,
+            confidential_transfer, cpi_guard, default_account_state,
+            get_account_len_with_fixed_len_extension, get_account_len_with_variable_len_extension,
+            interest_bearing_mint, memo_transfer, pausable, scaled_ui_amount,
+            token_group::{self, TokenGroup, TokenGroupMember},
+            token_metadata::{self, Field, TokenMetadata},
+            transfer_fee,
+            transfer_hook::{self, TransferHook},
+            Extension, ExtensionType, StateWithExtensionsOwned,
         },
         instruction,
         solana_zk_token_sdk::{
@@ -105,6 +111,7 @@ pub enum ExtensionInitializationParams {
     ConfidentialTransferMint {
         ct_mint: confidential_transfer::ConfidentialTransferMint,
     },
+    ConfidentialMintBurn,
     DefaultAccountState {
         state: AccountState,
     },
@@ -121,18 +128,37 @@ pub enum ExtensionInitializationParams {
         rate_authority: Option<Pubkey>,
         rate: i16,
     },
+    ScaledUiAmountConfig {
+        authority: Option<Pubkey>,
+        multiplier: f64,
+    },
     NonTransferable,
+    PermanentDelegate {
+        delegate: Pubkey,
+    },
+    TransferHook {
+        authority: Option<Pubkey>,
+        program_id: Option<Pubkey>,
+    },
+    PausableConfig {
+        authority: Pubkey,
+    },
 }
 impl ExtensionInitializationParams {
     /// Get the extension type associated with the init params
     pub fn extension(&self) -> ExtensionType {
         match self {
             Self::ConfidentialTransferMint { .. } => ExtensionType::ConfidentialTransferMint,
+            Self::ConfidentialMintBurn => ExtensionType::ConfidentialMintBurn,
             Self::DefaultAccountState { .. } => ExtensionType::DefaultAccountState,
             Self::MintCloseAuthority { .. } => ExtensionType::MintCloseAuthority,
             Self::TransferFeeConfig { .. } => ExtensionType::TransferFeeConfig,
             Self::InterestBearingConfig { .. } => ExtensionType::InterestBearingConfig,
+            Self::ScaledUiAmountConfig { .. } => ExtensionType::ScaledUiAmount,
             Self::NonTransferable => ExtensionType::NonTransferable,
+            Self::PermanentDelegate { .. } => ExtensionType::PermanentDelegate,
+            Self::TransferHook { .. } => ExtensionType::TransferHook,
+            Self::PausableConfig { .. } => ExtensionType::Pausable,
         }
     }
     /// Generate an appropriate initialization instruction for the given mint
@@ -149,6 +175,12 @@ impl ExtensionInitializationParams {
                     &ct_mint,
                 )
             }
+            Self::ConfidentialMintBurn => {
+                confidential_transfer::instruction::initialize_confidential_mint_burn(
+                    token_program_id,
+                    mint,
+                )
+            }
             Self::DefaultAccountState { state } => {
                 default_account_state::instruction::initialize_default_account_state(
                     token_program_id,
@@ -185,9 +217,33 @@ impl ExtensionInitializationParams {
                 rate_authority,
                 rate,
             ),
+            Self::ScaledUiAmountConfig {
+                authority,
+                multiplier,
+            } => scaled_ui_amount::instruction::initialize(
+                token_program_id,
+                mint,
+                authority,
+                multiplier,
+            ),
             Self::NonTransferable => {
                 instruction::initialize_non_transferable_mint(token_program_id, mint)
             }
+            Self::PermanentDelegate { delegate } => {
+                instruction::initialize_permanent_delegate(token_program_id, mint, &delegate)
+            }
+            Self::TransferHook {
+                authority,
+                program_id,
+            } => transfer_hook::instruction::initialize(
+                token_program_id,
+                mint,
+                authority,
+                program_id,
+            ),
+            Self::PausableConfig { authority } => {
+                pausable::instruction::initialize(token_program_id, mint, &authority)
+            }
         }
     }
 }
@@ -756,7 +812,7 @@ where
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
         let instructions = if let Some(decimals) = self.decimals {
-            [instruction::transfer_checked(
+            let mut instruction = instruction::transfer_checked(
                 &self.program_id,
                 source,
                 &self.pubkey,
@@ -765,7 +821,9 @@ where
                 &multisig_signers,
                 amount,
                 decimals,
-            )?]
+            )?;
+            self.add_transfer_hook_accounts(&mut instruction).await?;
+            [instruction]
         } else {
             #[allow(deprecated)]
             [instruction::transfer(
@@ -809,7 +867,7 @@ where
         ];
 
         if let Some(decimals) = self.decimals {
-            instructions.push(instruction::transfer_checked(
+            let mut instruction = instruction::transfer_checked(
                 &self.program_id,
                 source,
                 &self.pubkey,
@@ -818,7 +876,9 @@ where
                 &multisig_signers,
                 amount,
                 decimals,
-            )?);
+            )?;
+            self.add_transfer_hook_accounts(&mut instruction).await?;
+            instructions.push(instruction);
         } else {
             #[allow(deprecated)]
             instructions.push(instruction::transfer(
@@ -849,21 +909,57 @@ where
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
         let decimals = self.decimals.ok_or(TokenError::MissingDecimals)?;
 
-        self.process_ixs(
-            &[transfer_fee::instruction::transfer_checked_with_fee(
-                &self.program_id,
-                source,
-                &self.pubkey,
-                destination,
-                authority,
-                &multisig_signers,
-                amount,
-                decimals,
-                fee,
-            )?],
-            signing_keypairs,
-        )
-        .await
+        let mut instruction = transfer_fee::instruction::transfer_checked_with_fee(
+            &self.program_id,
+            source,
+            &self.pubkey,
+            destination,
+            authority,
+            &multisig_signers,
+            amount,
+            decimals,
+            fee,
+        )?;
+        self.add_transfer_hook_accounts(&mut instruction).await?;
+
+        self.process_ixs(&[instruction], signing_keypairs).await
+    }
+
+    /// Append the accounts required by the mint's transfer hook program, if
+    /// any, to a transfer instruction.  Nothing is added if the mint cannot be
+    /// fetched, for example when the client is offline.
+    async fn add_transfer_hook_accounts(&self, instruction: &mut Instruction) -> TokenResult<()> {
+        let mint = if let Ok(mint) = self.get_mint_info().await {
+            mint
+        } else {
+            return Ok(());
+        };
+        let program_id = if let Some(program_id) = mint
+            .get_extension::<TransferHook>()
+            .ok()
+            .and_then(|extension| Option::<Pubkey>::from(extension.program_id))
+        {
+            program_id
+        } else {
+            return Ok(());
+        };
+
+        let validate_state_pubkey =
+            transfer_hook::interface::get_extra_account_metas_address(&self.pubkey, &program_id);
+        let validate_state_account = self.get_account(&validate_state_pubkey).await?;
+        let extra_account_metas =
+            transfer_hook::interface::unpack_extra_account_metas(&validate_state_account.data)?;
+
+        instruction
+            .accounts
+            .push(AccountMeta::new_readonly(program_id, false));
+        instruction
+            .accounts
+            .push(AccountMeta::new_readonly(validate_state_pubkey, false));
+        instruction
+            .accounts
+            .extend(extra_account_metas.iter().map(AccountMeta::from));
+        Ok(())
     }
 
     /// Burn tokens from account
@@ -1388,206 +1484,660 @@ where
         .await
     }
 
-    /// Update interest rate
-    pub async fn update_interest_rate<S: Signers>(
+    /// Prevent unsafe usage of token account through CPI
+    pub async fn enable_cpi_guard<S: Signers>(
         &self,
+        account: &Pubkey,
         authority: &Pubkey,
-        new_rate: i16,
         signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         let signing_pubkeys = signing_keypairs.pubkeys();
         let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
         self.process_ixs(
-            &[interest_bearing_mint::instruction::update_rate(
+            &[cpi_guard::instruction::enable_cpi_guard(
                 &self.program_id,
-                self.get_address(),
+                account,
                 authority,
                 &multisig_signers,
-                new_rate,
             )?],
             signing_keypairs,
         )
         .await
     }
 
-    /// Update confidential transfer mint
-    pub async fn confidential_transfer_update_mint<S: Signer>(
+    /// Stop preventing unsafe usage of token account through CPI
+    pub async fn disable_cpi_guard<S: Signers>(
         &self,
-        authority: &S,
-        new_ct_mint: confidential_transfer::ConfidentialTransferMint,
-        new_authority: Option<&S>,
+        account: &Pubkey,
+        authority: &Pubkey,
+        signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
-        let mut signers = vec![authority];
-        if let Some(new_authority) = new_authority {
-            signers.push(new_authority);
-        }
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
         self.process_ixs(
-            &[confidential_transfer::instruction::update_mint(
+            &[cpi_guard::instruction::disable_cpi_guard(
                 &self.program_id,
-                &self.pubkey,
-                &new_ct_mint,
-                &authority.pubkey(),
+                account,
+                authority,
+                &multisig_signers,
             )?],
-            &signers,
+            signing_keypairs,
         )
         .await
     }
 
-    /// Configures confidential transfers for a token account
-    pub async fn confidential_transfer_configure_token_account<S: Signer>(
+    /// Update interest rate
+    pub async fn update_interest_rate<S: Signers>(
         &self,
-        token_account: &Pubkey,
-        authority: &S,
+        authority: &Pubkey,
+        new_rate: i16,
+        signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
-        let maximum_pending_balance_credit_counter =
-            2 << confidential_transfer::MAXIMUM_DEPOSIT_TRANSFER_AMOUNT_BIT_LENGTH;
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
-        self.confidential_transfer_configure_token_account_with_pending_counter(
-            token_account,
-            authority,
-            maximum_pending_balance_credit_counter,
+        self.process_ixs(
+            &[interest_bearing_mint::instruction::update_rate(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                new_rate,
+            )?],
+            signing_keypairs,
         )
         .await
     }
 
-    pub async fn confidential_transfer_configure_token_account_with_pending_counter<S: Signer>(
+    /// Update the UI amount multiplier, effective at the given timestamp
+    pub async fn update_multiplier<S: Signers>(
         &self,
-        token_account: &Pubkey,
-        authority: &S,
-        maximum_pending_balance_credit_counter: u64,
+        authority: &Pubkey,
+        new_multiplier: f64,
+        effective_timestamp: i64,
+        signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
-        let elgamal_pubkey = ElGamalKeypair::new(authority, token_account)
-            .map_err(TokenError::Key)?
-            .public;
-        let decryptable_zero_balance = AeKey::new(authority, token_account)
-            .map_err(TokenError::Key)?
-            .encrypt(0);
-
-        self.confidential_transfer_configure_token_account_with_pending_counter_and_keypair(
-            token_account,
-            authority,
-            maximum_pending_balance_credit_counter,
-            elgamal_pubkey,
-            decryptable_zero_balance,
-        )
-        .await
-    }
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
-    pub async fn confidential_transfer_configure_token_account_with_pending_counter_and_keypair<
-        S: Signer,
-    >(
-        &self,
-        token_account: &Pubkey,
-        authority: &S,
-        maximum_pending_balance_credit_counter: u64,
-        elgamal_pubkey: ElGamalPubkey,
-        decryptable_zero_balance: AeCiphertext,
-    ) -> TokenResult<T::Output> {
         self.process_ixs(
-            &[confidential_transfer::instruction::configure_account(
+            &[scaled_ui_amount::instruction::update_multiplier(
                 &self.program_id,
-                token_account,
-                &self.pubkey,
-                elgamal_pubkey.into(),
-                decryptable_zero_balance,
-                maximum_pending_balance_credit_counter,
-                &authority.pubkey(),
-                &[],
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                new_multiplier,
+                effective_timestamp,
             )?],
-            &[authority],
+            signing_keypairs,
         )
         .await
     }
 
-    /// Approves a token account for confidential transfers
-    pub async fn confidential_transfer_approve_account<S: Signer>(
+    /// Pause minting, burning, and transferring for the mint
+    pub async fn pause<S: Signers>(
         &self,
-        token_account: &Pubkey,
-        authority: &S,
+        authority: &Pubkey,
+        signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
         self.process_ixs(
-            &[confidential_transfer::instruction::approve_account(
+            &[pausable::instruction::pause(
                 &self.program_id,
-                token_account,
-                &self.pubkey,
-                &authority.pubkey(),
+                self.get_address(),
+                authority,
+                &multisig_signers,
             )?],
-            &[authority],
+            signing_keypairs,
         )
         .await
     }
 
-    /// Prepare a token account with the confidential transfer extension for closing
-    pub async fn confidential_transfer_empty_account<S: Signer>(
+    /// Resume minting, burning, and transferring for the mint
+    pub async fn resume<S: Signers>(
         &self,
-        token_account: &Pubkey,
-        authority: &S,
+        authority: &Pubkey,
+        signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
-        let elgamal_keypair =
-            ElGamalKeypair::new(authority, token_account).map_err(TokenError::Key)?;
-        self.confidential_transfer_empty_account_with_keypair(
-            token_account,
-            authority,
-            &elgamal_keypair,
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[pausable::instruction::resume(
+                &self.program_id,
+                self.get_address(),
+                authority,
+                &multisig_signers,
+            )?],
+            signing_keypairs,
         )
         .await
     }
 
-    pub async fn confidential_transfer_empty_account_with_keypair<S: Signer>(
+    /// Update transfer hook program id
+    pub async fn update_transfer_hook_program_id<S: Signers>(
         &self,
-        token_account: &Pubkey,
-        authority: &S,
-        elgamal_keypair: &ElGamalKeypair,
+        authority: &Pubkey,
+        new_program_id: Option<Pubkey>,
+        signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
-        let state = self.get_account_info(token_account).await.unwrap();
-        let extension =
-            state.get_extension::<confidential_transfer::ConfidentialTransferAccount>()?;
-
-        let proof_data = confidential_transfer::instruction::CloseAccountData::new(
-            elgamal_keypair,
-            &extension.available_balance.try_into().unwrap(),
-        )
-        .map_err(TokenError::Proof)?;
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
 
         self.process_ixs(
-            &confidential_transfer::instruction::empty_account(
+            &[transfer_hook::instruction::update(
                 &self.program_id,
-                token_account,
-                &authority.pubkey(),
-                &[],
-                &proof_data,
-            )?,
-            &[authority],
+                self.get_address(),
+                authority,
+                &multisig_signers,
+                new_program_id,
+            )?],
+            signing_keypairs,
         )
         .await
     }
 
-    /// Fetch and decrypt the available balance of a confidential token account using the uniquely
-    /// derived decryption key from a signer
-    pub async fn confidential_transfer_get_available_balance<S: Signer>(
+    /// Retrieve the token metadata stored in the mint
+    pub async fn get_token_metadata(&self) -> TokenResult<TokenMetadata> {
+        let mint = self.get_mint_info().await?;
+        mint.get_variable_len_extension::<TokenMetadata>()
+            .map_err(Into::into)
+    }
+
+    /// Get the lamports the mint needs on top of its current balance to stay
+    /// rent-exempt once it holds the given token metadata
+    async fn get_additional_rent_for_token_metadata(
         &self,
-        token_account: &Pubkey,
-        authority: &S,
+        token_metadata: &TokenMetadata,
     ) -> TokenResult<u64> {
-        let authenticated_encryption_key =
-            AeKey::new(authority, token_account).map_err(TokenError::Key)?;
+        let account = self.get_account(&self.pubkey).await?;
+        let new_account_len =
+            get_account_len_with_variable_len_extension::<Mint, _>(&account.data, token_metadata)?;
+        let new_rent_exempt_minimum = self
+            .client
+            .get_minimum_balance_for_rent_exemption(new_account_len)
+            .await
+            .map_err(TokenError::Client)?;
+        Ok(new_rent_exempt_minimum.saturating_sub(account.lamports))
+    }
 
-        self.confidential_transfer_get_available_balance_with_key(
-            token_account,
-            &authenticated_encryption_key,
-        )
-        .await
+    /// Fund the mint from the payer if it needs more lamports to hold the
+    /// given token metadata. Skipped if the mint cannot be fetched, e.g. when
+    /// signing offline.
+    async fn add_token_metadata_rent(
+        &self,
+        instructions: &mut Vec<Instruction>,
+        token_metadata: &TokenMetadata,
+    ) {
+        if let Ok(additional_lamports) = self
+            .get_additional_rent_for_token_metadata(token_metadata)
+            .await
+        {
+            if additional_lamports > 0 {
+                instructions.push(system_instruction::transfer(
+                    &self.payer.pubkey(),
+                    &self.pubkey,
+                    additional_lamports,
+                ));
+            }
+        }
     }
 
-    /// Fetch and decrypt the available balance of a confidential token account using a custom
-    /// decryption key
-    pub async fn confidential_transfer_get_available_balance_with_key(
+    /// Initialize token metadata in the mint, funding any additional rent
+    /// from the payer
+    #[allow(clippy::too_many_arguments)]
+    pub async fn token_metadata_initialize<S: Signers>(
         &self,
-        token_account: &Pubkey,
-        authenticated_encryption_key: &AeKey,
-    ) -> TokenResult<u64> {
-        let state = self.get_account_info(token_account).await.unwrap();
+        update_authority: &Pubkey,
+        mint_authority: &Pubkey,
+        name: String,
+        symbol: String,
+        uri: String,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(mint_authority, &signing_pubkeys);
+
+        let token_metadata = TokenMetadata {
+            update_authority: Some(*update_authority).try_into()?,
+            mint: self.pubkey,
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            additional_metadata: vec![],
+        };
+        let mut instructions = vec![];
+        self.add_token_metadata_rent(&mut instructions, &token_metadata)
+            .await;
+        instructions.push(token_metadata::instruction::initialize(
+            &self.program_id,
+            &self.pubkey,
+            update_authority,
+            mint_authority,
+            &multisig_signers,
+            name,
+            symbol,
+            uri,
+        )?);
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Update a token metadata field, funding any additional rent from the payer
+    pub async fn token_metadata_update_field<S: Signers>(
+        &self,
+        update_authority: &Pubkey,
+        field: Field,
+        value: String,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(update_authority, &signing_pubkeys);
+
+        let mut instructions = vec![];
+        if let Ok(mut token_metadata) = self.get_token_metadata().await {
+            token_metadata.update(field.clone(), value.clone());
+            self.add_token_metadata_rent(&mut instructions, &token_metadata)
+                .await;
+        }
+        instructions.push(token_metadata::instruction::update_field(
+            &self.program_id,
+            &self.pubkey,
+            update_authority,
+            &multisig_signers,
+            field,
+            value,
+        )?);
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Remove a key from the token metadata additional fields
+    pub async fn token_metadata_remove_key<S: Signers>(
+        &self,
+        update_authority: &Pubkey,
+        key: String,
+        idempotent: bool,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(update_authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[token_metadata::instruction::remove_key(
+                &self.program_id,
+                &self.pubkey,
+                update_authority,
+                &multisig_signers,
+                key,
+                idempotent,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Update the token metadata update authority
+    pub async fn token_metadata_update_authority<S: Signers>(
+        &self,
+        current_authority: &Pubkey,
+        new_authority: Option<Pubkey>,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(current_authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[token_metadata::instruction::update_authority(
+                &self.program_id,
+                &self.pubkey,
+                current_authority,
+                &multisig_signers,
+                new_authority,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Get the lamports the mint needs on top of its current balance to stay
+    /// rent-exempt once it holds the given fixed-length extension
+    async fn get_additional_rent_for_fixed_len_extension<V: Extension>(&self) -> TokenResult<u64> {
+        let account = self.get_account(&self.pubkey).await?;
+        let new_account_len = get_account_len_with_fixed_len_extension::<Mint, V>(&account.data)?;
+        let new_rent_exempt_minimum = self
+            .client
+            .get_minimum_balance_for_rent_exemption(new_account_len)
+            .await
+            .map_err(TokenError::Client)?;
+        Ok(new_rent_exempt_minimum.saturating_sub(account.lamports))
+    }
+
+    /// Fund the mint from the payer if it needs more lamports to hold the
+    /// given fixed-length extension. Skipped if the mint cannot be fetched,
+    /// e.g. when signing offline.
+    async fn add_fixed_len_extension_rent<V: Extension>(
+        &self,
+        instructions: &mut Vec<Instruction>,
+    ) {
+        if let Ok(additional_lamports) = self
+            .get_additional_rent_for_fixed_len_extension::<V>()
+            .await
+        {
+            if additional_lamports > 0 {
+                instructions.push(system_instruction::transfer(
+                    &self.payer.pubkey(),
+                    &self.pubkey,
+                    additional_lamports,
+                ));
+            }
+        }
+    }
+
+    /// Retrieve the token group stored in the mint
+    pub async fn get_token_group(&self) -> TokenResult<TokenGroup> {
+        let mint = self.get_mint_info().await?;
+        mint.get_extension::<TokenGroup>()
+            .map(|group| *group)
+            .map_err(Into::into)
+    }
+
+    /// Initialize the mint as a token group, funding any additional rent from
+    /// the payer
+    pub async fn token_group_initialize<S: Signers>(
+        &self,
+        mint_authority: &Pubkey,
+        update_authority: Option<Pubkey>,
+        max_size: u64,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(mint_authority, &signing_pubkeys);
+
+        let mut instructions = vec![];
+        self.add_fixed_len_extension_rent::<TokenGroup>(&mut instructions)
+            .await;
+        instructions.push(token_group::instruction::initialize_group(
+            &self.program_id,
+            &self.pubkey,
+            mint_authority,
+            &multisig_signers,
+            update_authority,
+            max_size,
+        )?);
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Update the max size of the token group
+    pub async fn token_group_update_max_size<S: Signers>(
+        &self,
+        update_authority: &Pubkey,
+        new_max_size: u64,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(update_authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[token_group::instruction::update_group_max_size(
+                &self.program_id,
+                &self.pubkey,
+                update_authority,
+                &multisig_signers,
+                new_max_size,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Update the token group update authority
+    pub async fn token_group_update_authority<S: Signers>(
+        &self,
+        current_authority: &Pubkey,
+        new_authority: Option<Pubkey>,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(current_authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[token_group::instruction::update_group_authority(
+                &self.program_id,
+                &self.pubkey,
+                current_authority,
+                &multisig_signers,
+                new_authority,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Add the mint as a member of the given group, funding any additional
+    /// rent from the payer
+    pub async fn token_group_initialize_member<S: Signers>(
+        &self,
+        mint_authority: &Pubkey,
+        group_mint: &Pubkey,
+        group_update_authority: &Pubkey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        // both authorities sign directly unless one of them is a multisig
+        let multisig_signers = if signing_pubkeys
+            .iter()
+            .all(|pubkey| pubkey == mint_authority || pubkey == group_update_authority)
+        {
+            vec![]
+        } else {
+            signing_pubkeys.iter().collect::<Vec<_>>()
+        };
+
+        let mut instructions = vec![];
+        self.add_fixed_len_extension_rent::<TokenGroupMember>(&mut instructions)
+            .await;
+        instructions.push(token_group::instruction::initialize_member(
+            &self.program_id,
+            &self.pubkey,
+            mint_authority,
+            group_mint,
+            group_update_authority,
+            &multisig_signers,
+        )?);
+        self.process_ixs(&instructions, signing_keypairs).await
+    }
+
+    /// Update confidential transfer mint
+    pub async fn confidential_transfer_update_mint<S: Signer>(
+        &self,
+        authority: &S,
+        new_ct_mint: confidential_transfer::ConfidentialTransferMint,
+        new_authority: Option<&S>,
+    ) -> TokenResult<T::Output> {
+        let mut signers = vec![authority];
+        if let Some(new_authority) = new_authority {
+            signers.push(new_authority);
+        }
+        self.process_ixs(
+            &[confidential_transfer::instruction::update_mint(
+                &self.program_id,
+                &self.pubkey,
+                &new_ct_mint,
+                &authority.pubkey(),
+            )?],
+            &signers,
+        )
+        .await
+    }
+
+    /// Configures confidential transfers for a token account
+    pub async fn confidential_transfer_configure_token_account<S: Signer>(
+        &self,
+        token_account: &Pubkey,
+        authority: &S,
+    ) -> TokenResult<T::Output> {
+        let maximum_pending_balance_credit_counter =
+            2 << confidential_transfer::MAXIMUM_DEPOSIT_TRANSFER_AMOUNT_BIT_LENGTH;
+
+        self.confidential_transfer_configure_token_account_with_pending_counter(
+            token_account,
+            authority,
+            maximum_pending_balance_credit_counter,
+        )
+        .await
+    }
+
+    pub async fn confidential_transfer_configure_token_account_with_pending_counter<S: Signer>(
+        &self,
+        token_account: &Pubkey,
+        authority: &S,
+        maximum_pending_balance_credit_counter: u64,
+    ) -> TokenResult<T::Output> {
+        let elgamal_pubkey = ElGamalKeypair::new(authority, token_account)
+            .map_err(TokenError::Key)?
+            .public;
+        let decryptable_zero_balance = AeKey::new(authority, token_account)
+            .map_err(TokenError::Key)?
+            .encrypt(0);
+
+        self.confidential_transfer_configure_token_account_with_pending_counter_and_keypair(
+            token_account,
+            &authority.pubkey(),
+            maximum_pending_balance_credit_counter,
+            elgamal_pubkey,
+            decryptable_zero_balance,
+            &[authority],
+        )
+        .await
+    }
+
+    pub async fn confidential_transfer_configure_token_account_with_pending_counter_and_keypair<
+        S: Signers,
+    >(
+        &self,
+        token_account: &Pubkey,
+        authority: &Pubkey,
+        maximum_pending_balance_credit_counter: u64,
+        elgamal_pubkey: ElGamalPubkey,
+        decryptable_zero_balance: AeCiphertext,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &[confidential_transfer::instruction::configure_account(
+                &self.program_id,
+                token_account,
+                &self.pubkey,
+                elgamal_pubkey.into(),
+                decryptable_zero_balance,
+                maximum_pending_balance_credit_counter,
+                authority,
+                &multisig_signers,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Approves a token account for confidential transfers
+    pub async fn confidential_transfer_approve_account<S: Signers>(
+        &self,
+        token_account: &Pubkey,
+        authority: &Pubkey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        self.process_ixs(
+            &[confidential_transfer::instruction::approve_account(
+                &self.program_id,
+                token_account,
+                &self.pubkey,
+                authority,
+            )?],
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Prepare a token account with the confidential transfer extension for closing
+    pub async fn confidential_transfer_empty_account<S: Signer>(
+        &self,
+        token_account: &Pubkey,
+        authority: &S,
+    ) -> TokenResult<T::Output> {
+        let elgamal_keypair =
+            ElGamalKeypair::new(authority, token_account).map_err(TokenError::Key)?;
+        self.confidential_transfer_empty_account_with_keypair(
+            token_account,
+            &authority.pubkey(),
+            &elgamal_keypair,
+            &[authority],
+        )
+        .await
+    }
+
+    pub async fn confidential_transfer_empty_account_with_keypair<S: Signers>(
+        &self,
+        token_account: &Pubkey,
+        authority: &Pubkey,
+        elgamal_keypair: &ElGamalKeypair,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
+        let state = self.get_account_info(token_account).await.unwrap();
+        let extension =
+            state.get_extension::<confidential_transfer::ConfidentialTransferAccount>()?;
+
+        let proof_data = confidential_transfer::instruction::CloseAccountData::new(
+            elgamal_keypair,
+            &extension.available_balance.try_into().unwrap(),
+        )
+        .map_err(TokenError::Proof)?;
+
+        self.process_ixs(
+            &confidential_transfer::instruction::empty_account(
+                &self.program_id,
+                token_account,
+                authority,
+                &multisig_signers,
+                &proof_data,
+            )?,
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Fetch and decrypt the available balance of a confidential token account using the uniquely
+    /// derived decryption key from a signer
+    pub async fn confidential_transfer_get_available_balance<S: Signer>(
+        &self,
+        token_account: &Pubkey,
+        authority: &S,
+    ) -> TokenResult<u64> {
+        let authenticated_encryption_key =
+            AeKey::new(authority, token_account).map_err(TokenError::Key)?;
+
+        self.confidential_transfer_get_available_balance_with_key(
+            token_account,
+            &authenticated_encryption_key,
+        )
+        .await
+    }
+
+    /// Fetch and decrypt the available balance of a confidential token account using a custom
+    /// decryption key
+    pub async fn confidential_transfer_get_available_balance_with_key(
+        &self,
+        token_account: &Pubkey,
+        authenticated_encryption_key: &AeKey,
+    ) -> TokenResult<u64> {
+        let state = self.get_account_info(token_account).await.unwrap();
         let extension =
             state.get_extension::<confidential_transfer::ConfidentialTransferAccount>()?;
 
@@ -1735,17 +2285,21 @@ where
     }
 
     /// Deposit SPL Tokens into the pending balance of a confidential token account
-    pub async fn confidential_transfer_deposit<S: Signer>(
+    pub async fn confidential_transfer_deposit<S: Signers>(
         &self,
         token_account: &Pubkey,
-        token_authority: &S,
+        token_authority: &Pubkey,
         amount: u64,
         decimals: u8,
+        signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         if amount >> confidential_transfer::MAXIMUM_DEPOSIT_TRANSFER_AMOUNT_BIT_LENGTH != 0 {
             return Err(TokenError::MaximumDepositTransferAmountExceeded);
         }
 
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(token_authority, &signing_pubkeys);
+
         self.process_ixs(
             &[confidential_transfer::instruction::deposit(
                 &self.program_id,
@@ -1753,10 +2307,10 @@ where
                 &self.pubkey,
                 amount,
                 decimals,
-                &token_authority.pubkey(),
-                &[],
+                token_authority,
+                &multisig_signers,
             )?],
-            &[token_authority],
+            signing_keypairs,
         )
         .await
     }
@@ -1780,13 +2334,14 @@ where
 
         self.confidential_transfer_withdraw_with_key(
             token_account,
-            token_authority,
+            &token_authority.pubkey(),
             amount,
             decimals,
             available_balance,
             available_balance_ciphertext,
             &elgamal_keypair,
             &authenticated_encryption_key,
+            &[token_authority],
         )
         .await
     }
@@ -1794,17 +2349,21 @@ where
     /// Withdraw SPL Tokens from the available balance of a confidential token account using custom
     /// keys
     #[allow(clippy::too_many_arguments)]
-    pub async fn confidential_transfer_withdraw_with_key<S: Signer>(
+    pub async fn confidential_transfer_withdraw_with_key<S: Signers>(
         &self,
         token_account: &Pubkey,
-        token_authority: &S,
+        token_authority: &Pubkey,
         amount: u64,
         decimals: u8,
         available_balance: u64,
         available_balance_ciphertext: &ElGamalCiphertext,
         elgamal_keypair: &ElGamalKeypair,
         authenticated_encryption_key: &AeKey,
+        signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(token_authority, &signing_pubkeys);
+
         let proof_data = confidential_transfer::instruction::WithdrawData::new(
             amount,
             elgamal_keypair,
@@ -1827,11 +2386,11 @@ where
                 amount,
                 decimals,
                 new_decryptable_available_balance,
-                &token_authority.pubkey(),
-                &[],
+                token_authority,
+                &multisig_signers,
                 &proof_data,
             )?,
-            &[token_authority],
+            signing_keypairs,
         )
         .await
     }
@@ -1858,7 +2417,7 @@ where
         self.confidential_transfer_transfer_with_key(
             source_token_account,
             destination_token_account,
-            source_token_authority,
+            &source_token_authority.pubkey(),
             amount,
             source_available_balance,
             source_available_balance_ciphertext,
@@ -1866,17 +2425,18 @@ where
             auditor_elgamal_pubkey,
             &source_elgamal_keypair,
             &source_authenticated_encryption_key,
+            &[source_token_authority],
         )
         .await
     }
 
     /// Transfer tokens confidentially using custom decryption keys
     #[allow(clippy::too_many_arguments)]
-    pub async fn confidential_transfer_transfer_with_key<S: Signer>(
+    pub async fn confidential_transfer_transfer_with_key<S: Signers>(
         &self,
         source_token_account: &Pubkey,
         destination_token_account: &Pubkey,
-        source_token_authority: &S,
+        source_token_authority: &Pubkey,
         amount: u64,
         source_available_balance: u64,
         source_available_balance_ciphertext: &ElGamalCiphertext,
@@ -1884,6 +2444,7 @@ where
         auditor_elgamal_pubkey: &ElGamalPubkey,
         source_elgamal_keypair: &ElGamalKeypair,
         source_authenticated_encryption_key: &AeKey,
+        signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         if amount >> confidential_transfer::MAXIMUM_DEPOSIT_TRANSFER_AMOUNT_BIT_LENGTH != 0 {
             return Err(TokenError::MaximumDepositTransferAmountExceeded);
@@ -1906,6 +2467,9 @@ where
         let new_source_available_balance =
             source_authenticated_encryption_key.encrypt(source_remaining_balance);
 
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(source_token_authority, &signing_pubkeys);
+
         self.process_ixs(
             &confidential_transfer::instruction::transfer(
                 &self.program_id,
@@ -1913,11 +2477,11 @@ where
                 destination_token_account,
                 &self.pubkey,
                 new_source_available_balance,
-                &source_token_authority.pubkey(),
-                &[],
+                source_token_authority,
+                &multisig_signers,
                 &proof_data,
             )?,
-            &[source_token_authority],
+            signing_keypairs,
         )
         .await
     }
@@ -1947,7 +2511,7 @@ where
         self.confidential_transfer_transfer_with_fee_with_key(
             source_token_account,
             destination_token_account,
-            source_token_authority,
+            &source_token_authority.pubkey(),
             amount,
             source_available_balance,
             source_available_balance_ciphertext,
@@ -1957,17 +2521,18 @@ where
             &source_elgamal_keypair,
             &source_authenticated_encryption_key,
             epoch_info,
+            &[source_token_authority],
         )
         .await
     }
 
     /// Transfer tokens confidential with fee using custom decryption keys
     #[allow(clippy::too_many_arguments)]
-    pub async fn confidential_transfer_transfer_with_fee_with_key<S: Signer>(
+    pub async fn confidential_transfer_transfer_with_fee_with_key<S: Signers>(
         &self,
         source_token_account: &Pubkey,
         destination_token_account: &Pubkey,
-        source_token_authority: &S,
+        source_token_authority: &Pubkey,
         amount: u64,
         source_available_balance: u64,
         source_available_balance_ciphertext: &ElGamalCiphertext,
@@ -1977,6 +2542,7 @@ where
         source_elgamal_keypair: &ElGamalKeypair,
         source_authenticated_encryption_key: &AeKey,
         epoch_info: &EpochInfo,
+        signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
         if amount >> confidential_transfer::MAXIMUM_DEPOSIT_TRANSFER_AMOUNT_BIT_LENGTH != 0 {
             return Err(TokenError::MaximumDepositTransferAmountExceeded);
@@ -2011,6 +2577,9 @@ where
         let new_source_decryptable_balance =
             source_authenticated_encryption_key.encrypt(source_remaining_balance);
 
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(source_token_authority, &signing_pubkeys);
+
         self.process_ixs(
             &confidential_transfer::instruction::transfer_with_fee(
                 &self.program_id,
@@ -2018,15 +2587,160 @@ where
                 destination_token_account,
                 &self.pubkey,
                 new_source_decryptable_balance,
-                &source_token_authority.pubkey(),
-                &[],
+                source_token_authority,
+                &multisig_signers,
+                &proof_data,
+            )?,
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Mint tokens confidentially into the pending balance of a token account
+    pub async fn confidential_transfer_mint<S: Signers>(
+        &self,
+        destination_token_account: &Pubkey,
+        mint_authority: &Pubkey,
+        amount: u64,
+        destination_elgamal_pubkey: &ElGamalPubkey,
+        auditor_elgamal_pubkey: &ElGamalPubkey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        if amount >> confidential_transfer::MAXIMUM_DEPOSIT_TRANSFER_AMOUNT_BIT_LENGTH != 0 {
+            return Err(TokenError::MaximumDepositTransferAmountExceeded);
+        }
+
+        // The amount is proven against an ephemeral source that holds exactly the minted amount
+        let source_elgamal_keypair = ElGamalKeypair::new_rand();
+        let source_ciphertext = source_elgamal_keypair.public.encrypt(amount);
+        let proof_data = confidential_transfer::instruction::TransferData::new(
+            amount,
+            (amount, &source_ciphertext),
+            &source_elgamal_keypair,
+            (destination_elgamal_pubkey, auditor_elgamal_pubkey),
+        )
+        .map_err(TokenError::Proof)?;
+
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(mint_authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &confidential_transfer::instruction::confidential_mint(
+                &self.program_id,
+                destination_token_account,
+                &self.pubkey,
+                mint_authority,
+                &multisig_signers,
                 &proof_data,
             )?,
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Burn tokens confidentially using the uniquely derived decryption keys from a signer
+    pub async fn confidential_transfer_burn<S: Signer>(
+        &self,
+        source_token_account: &Pubkey,
+        source_token_authority: &S,
+        amount: u64,
+        source_available_balance: u64,
+        source_available_balance_ciphertext: &ElGamalCiphertext,
+        auditor_elgamal_pubkey: &ElGamalPubkey,
+    ) -> TokenResult<T::Output> {
+        let source_elgamal_keypair =
+            ElGamalKeypair::new(source_token_authority, source_token_account)
+                .map_err(TokenError::Key)?;
+        let source_authenticated_encryption_key =
+            AeKey::new(source_token_authority, source_token_account).map_err(TokenError::Key)?;
+
+        self.confidential_transfer_burn_with_key(
+            source_token_account,
+            &source_token_authority.pubkey(),
+            amount,
+            source_available_balance,
+            source_available_balance_ciphertext,
+            auditor_elgamal_pubkey,
+            &source_elgamal_keypair,
+            &source_authenticated_encryption_key,
             &[source_token_authority],
         )
         .await
     }
 
+    /// Burn tokens confidentially using custom decryption keys
+    #[allow(clippy::too_many_arguments)]
+    pub async fn confidential_transfer_burn_with_key<S: Signers>(
+        &self,
+        source_token_account: &Pubkey,
+        source_token_authority: &Pubkey,
+        amount: u64,
+        source_available_balance: u64,
+        source_available_balance_ciphertext: &ElGamalCiphertext,
+        auditor_elgamal_pubkey: &ElGamalPubkey,
+        source_elgamal_keypair: &ElGamalKeypair,
+        source_authenticated_encryption_key: &AeKey,
+        signing_keypairs: &S,
+    ) -> TokenResult<T::Output> {
+        if amount >> confidential_transfer::MAXIMUM_DEPOSIT_TRANSFER_AMOUNT_BIT_LENGTH != 0 {
+            return Err(TokenError::MaximumDepositTransferAmountExceeded);
+        }
+
+        // There is no destination for a burn, so the amount is only encrypted for the auditor
+        let proof_data = confidential_transfer::instruction::TransferData::new(
+            amount,
+            (
+                source_available_balance,
+                source_available_balance_ciphertext,
+            ),
+            source_elgamal_keypair,
+            (auditor_elgamal_pubkey, auditor_elgamal_pubkey),
+        )
+        .map_err(TokenError::Proof)?;
+
+        let source_remaining_balance = source_available_balance
+            .checked_sub(amount)
+            .ok_or(TokenError::NotEnoughFunds)?;
+        let new_source_available_balance =
+            source_authenticated_encryption_key.encrypt(source_remaining_balance);
+
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(source_token_authority, &signing_pubkeys);
+
+        self.process_ixs(
+            &confidential_transfer::instruction::confidential_burn(
+                &self.program_id,
+                source_token_account,
+                &self.pubkey,
+                new_source_available_balance,
+                source_token_authority,
+                &multisig_signers,
+                &proof_data,
+            )?,
+            signing_keypairs,
+        )
+        .await
+    }
+
+    /// Fetch and decrypt the confidential supply of the mint using the auditor ElGamal keypair
+    pub async fn confidential_transfer_get_supply_with_key(
+        &self,
+        auditor_elgamal_keypair: &ElGamalKeypair,
+    ) -> TokenResult<u64> {
+        let state = self.get_mint_info().await?;
+        let extension = state.get_extension::<confidential_transfer::ConfidentialMintBurn>()?;
+
+        let supply_ciphertext: ElGamalCiphertext = extension
+            .confidential_supply
+            .try_into()
+            .map_err(TokenError::Proof)?;
+        let supply = supply_ciphertext
+            .decrypt_u32(&auditor_elgamal_keypair.secret)
+            .ok_or(TokenError::AccountDecryption)?;
+
+        Ok(supply)
+    }
+
     /// Applies the confidential transfer pending balance to the available balance using the
     /// uniquely derived decryption key
     pub async fn confidential_transfer_apply_pending_balance<S: Signer>(
@@ -2042,26 +2756,32 @@ where
 
         self.confidential_transfer_apply_pending_balance_with_key(
             token_account,
-            authority,
+            &authority.pubkey(),
             available_balance,
             pending_balance,
             expected_pending_balance_credit_counter,
             &authenticated_encryption_key,
+            &[authority],
         )
         .await
     }
 
     /// Applies the confidential transfer pending balance to the available balance using a custom
     /// decryption key
-    pub async fn confidential_transfer_apply_pending_balance_with_key<S: Signer>(
+    #[allow(clippy::too_many_arguments)]
+    pub async fn confidential_transfer_apply_pending_balance_with_key<S: Signers>(
         &self,
         token_account: &Pubkey,
-        authority: &S,
+        authority: &Pubkey,
         available_balance: u64,
         pending_balance: u64,
         expected_pending_balance_credit_counter: u64,
         authenticated_encryption_key: &AeKey,
+        signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
         let new_decryptable_balance = available_balance.checked_add(pending_balance).unwrap();
         let new_decryptable_balance_ciphertext =
             authenticated_encryption_key.encrypt(new_decryptable_balance);
@@ -2072,46 +2792,54 @@ where
                 token_account,
                 expected_pending_balance_credit_counter,
                 new_decryptable_balance_ciphertext,
-                &authority.pubkey(),
-                &[],
+                authority,
+                &multisig_signers,
             )?],
-            &[authority],
+            signing_keypairs,
         )
         .await
     }
 
     /// Enable confidential transfer `Deposit` and `Transfer` instructions for a token account
-    pub async fn confidential_transfer_enable_balance_credits<S: Signer>(
+    pub async fn confidential_transfer_enable_balance_credits<S: Signers>(
         &self,
         token_account: &Pubkey,
-        authority: &S,
+        authority: &Pubkey,
+        signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
         self.process_ixs(
             &[confidential_transfer::instruction::enable_balance_credits(
                 &self.program_id,
                 token_account,
-                &authority.pubkey(),
-                &[],
+                authority,
+                &multisig_signers,
             )?],
-            &[authority],
+            signing_keypairs,
         )
         .await
     }
 
     /// Disable confidential transfer `Deposit` and `Transfer` instructions for a token account
-    pub async fn confidential_transfer_disable_balance_credits<S: Signer>(
+    pub async fn confidential_transfer_disable_balance_credits<S: Signers>(
         &self,
         token_account: &Pubkey,
-        authority: &S,
+        authority: &Pubkey,
+        signing_keypairs: &S,
     ) -> TokenResult<T::Output> {
+        let signing_pubkeys = signing_keypairs.pubkeys();
+        let multisig_signers = self.get_multisig_signers(authority, &signing_pubkeys);
+
         self.process_ixs(
             &[confidential_transfer::instruction::disable_balance_credits(
                 &self.program_id,
                 token_account,
-                &authority.pubkey(),
-                &[],
+                authority,
+                &multisig_signers,
             )?],
-            &[authority],
+            signing_keypairs,
         )
         .await
     }
@@ -13,7 +13,8 @@ use {
         error::TokenError,
         extension::{
             confidential_transfer::{
-                ConfidentialTransferAccount, ConfidentialTransferMint, EncryptedWithheldAmount,
+                ConfidentialMintBurn, ConfidentialTransferAccount, ConfidentialTransferMint,
+                EncryptedBalance, EncryptedWithheldAmount,
             },
             ExtensionType,
         },
@@ -188,7 +189,13 @@ impl ConfidentialTokenAccountMeta {
             .unwrap();
 
         token
-            .confidential_transfer_deposit(&meta.token_account, owner, amount, decimals)
+            .confidential_transfer_deposit(
+                &meta.token_account,
+                &owner.pubkey(),
+                amount,
+                decimals,
+                &[owner],
+            )
             .await
             .unwrap();
 
@@ -371,7 +378,11 @@ async fn ct_configure_token_account() {
     );
 
     token
-        .confidential_transfer_approve_account(&alice_meta.token_account, &ct_mint_authority)
+        .confidential_transfer_approve_account(
+            &alice_meta.token_account,
+            &ct_mint_authority.pubkey(),
+            &[&ct_mint_authority],
+        )
         .await
         .unwrap();
 
@@ -421,7 +432,11 @@ async fn ct_enable_disable_balance_credits() {
     let alice_meta = ConfidentialTokenAccountMeta::new(&token, &alice).await;
 
     token
-        .confidential_transfer_disable_balance_credits(&alice_meta.token_account, &alice)
+        .confidential_transfer_disable_balance_credits(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            &[&alice],
+        )
         .await
         .unwrap();
     let state = token
@@ -434,7 +449,11 @@ async fn ct_enable_disable_balance_credits() {
     assert!(!bool::from(&extension.allow_balance_credits));
 
     token
-        .confidential_transfer_enable_balance_credits(&alice_meta.token_account, &alice)
+        .confidential_transfer_enable_balance_credits(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            &[&alice],
+        )
         .await
         .unwrap();
     let state = token
@@ -524,7 +543,13 @@ async fn ct_deposit() {
     );
 
     token
-        .confidential_transfer_deposit(&alice_meta.token_account, &alice, 65537, decimals)
+        .confidential_transfer_deposit(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            65537,
+            decimals,
+            &[&alice],
+        )
         .await
         .unwrap();
 
@@ -553,12 +578,24 @@ async fn ct_deposit() {
         .await;
 
     token
-        .confidential_transfer_deposit(&alice_meta.token_account, &alice, 0, decimals)
+        .confidential_transfer_deposit(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            0,
+            decimals,
+            &[&alice],
+        )
         .await
         .unwrap();
 
     let err = token
-        .confidential_transfer_deposit(&alice_meta.token_account, &alice, 0, decimals)
+        .confidential_transfer_deposit(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            0,
+            decimals,
+            &[&alice],
+        )
         .await
         .unwrap_err();
 
@@ -1613,3 +1650,261 @@ async fn ct_transfer_with_fee_memo() {
         )
         .await;
 }
+
+#[tokio::test]
+async fn ct_mint_burn_fixed_auditor() {
+    // a confidential supply cannot be initialized without an auditor
+    let ConfidentialTransferMintWithKeypairs {
+        mut ct_mint,
+        ct_mint_authority,
+        ..
+    } = ConfidentialTransferMintWithKeypairs::new();
+    let auditor_encryption_pubkey = ct_mint.auditor_encryption_pubkey;
+    ct_mint.auditor_encryption_pubkey = zk_token_elgamal::pod::ElGamalPubkey::zeroed();
+    let mut context = TestContext::new().await;
+    let err = context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::ConfidentialTransferMint { ct_mint },
+            ExtensionInitializationParams::ConfidentialMintBurn,
+        ])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                2,
+                InstructionError::Custom(TokenError::InvalidConfidentialSupplyAuditor as u32),
+            )
+        )))
+    );
+
+    // nor can the auditor of a confidential supply change
+    ct_mint.auditor_encryption_pubkey = auditor_encryption_pubkey;
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::ConfidentialTransferMint { ct_mint },
+            ExtensionInitializationParams::ConfidentialMintBurn,
+        ])
+        .await
+        .unwrap();
+    let TokenContext { token, .. } = context.token_context.unwrap();
+
+    let new_ct_mint = ConfidentialTransferMint {
+        auditor_encryption_pubkey: ElGamalKeypair::new_rand().public.into(),
+        ..ct_mint
+    };
+    let err = token
+        .confidential_transfer_update_mint(&ct_mint_authority, new_ct_mint, None)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::InvalidConfidentialSupplyAuditor as u32),
+            )
+        )))
+    );
+
+    // other fields can still be updated
+    let new_ct_mint = ConfidentialTransferMint {
+        auto_approve_new_accounts: false.into(),
+        ..ct_mint
+    };
+    token
+        .confidential_transfer_update_mint(&ct_mint_authority, new_ct_mint, None)
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<ConfidentialTransferMint>().unwrap();
+    assert_eq!(*extension, new_ct_mint);
+}
+
+#[tokio::test]
+async fn ct_mint_burn() {
+    let ConfidentialTransferMintWithKeypairs {
+        ct_mint,
+        ct_mint_transfer_auditor_encryption_keypair,
+        ..
+    } = ConfidentialTransferMintWithKeypairs::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![
+            ExtensionInitializationParams::ConfidentialTransferMint { ct_mint },
+            ExtensionInitializationParams::ConfidentialMintBurn,
+        ])
+        .await
+        .unwrap();
+
+    let TokenContext {
+        token,
+        alice,
+        mint_authority,
+        decimals,
+        ..
+    } = context.token_context.unwrap();
+
+    let alice_meta = ConfidentialTokenAccountMeta::new(&token, &alice).await;
+
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<ConfidentialMintBurn>().unwrap();
+    assert_eq!(extension.confidential_supply, EncryptedBalance::zeroed());
+
+    // public mint is disabled
+    let err = token
+        .mint_to(
+            &alice_meta.token_account,
+            &mint_authority.pubkey(),
+            42,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::PublicMintBurnDisabled as u32),
+            )
+        )))
+    );
+
+    // only the mint authority can mint
+    let err = token
+        .confidential_transfer_mint(
+            &alice_meta.token_account,
+            &alice.pubkey(),
+            42,
+            &alice_meta.elgamal_keypair.public,
+            &ct_mint_transfer_auditor_encryption_keypair.public,
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32),
+            )
+        )))
+    );
+
+    token
+        .confidential_transfer_mint(
+            &alice_meta.token_account,
+            &mint_authority.pubkey(),
+            42,
+            &alice_meta.elgamal_keypair.public,
+            &ct_mint_transfer_auditor_encryption_keypair.public,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    alice_meta
+        .check_balances(
+            &token,
+            ConfidentialTokenAccountBalances {
+                pending_balance_lo: 42,
+                pending_balance_hi: 0,
+                available_balance: 0,
+                decryptable_available_balance: 0,
+            },
+        )
+        .await;
+
+    let state = token.get_mint_info().await.unwrap();
+    assert_eq!(state.base.supply, 0);
+    assert_eq!(
+        token
+            .confidential_transfer_get_supply_with_key(&ct_mint_transfer_auditor_encryption_keypair)
+            .await
+            .unwrap(),
+        42
+    );
+
+    token
+        .confidential_transfer_apply_pending_balance(&alice_meta.token_account, &alice, 0, 42, 1)
+        .await
+        .unwrap();
+
+    let state = token
+        .get_account_info(&alice_meta.token_account)
+        .await
+        .unwrap();
+    let extension = state
+        .get_extension::<ConfidentialTransferAccount>()
+        .unwrap();
+
+    token
+        .confidential_transfer_burn(
+            &alice_meta.token_account,
+            &alice,
+            12,
+            42,
+            &extension.available_balance.try_into().unwrap(),
+            &ct_mint_transfer_auditor_encryption_keypair.public,
+        )
+        .await
+        .unwrap();
+
+    alice_meta
+        .check_balances(
+            &token,
+            ConfidentialTokenAccountBalances {
+                pending_balance_lo: 0,
+                pending_balance_hi: 0,
+                available_balance: 30,
+                decryptable_available_balance: 30,
+            },
+        )
+        .await;
+
+    assert_eq!(
+        token
+            .confidential_transfer_get_supply_with_key(&ct_mint_transfer_auditor_encryption_keypair)
+            .await
+            .unwrap(),
+        30
+    );
+
+    // public burn is disabled, even for tokens withdrawn from the confidential balance
+    let state = token
+        .get_account_info(&alice_meta.token_account)
+        .await
+        .unwrap();
+    let extension = state
+        .get_extension::<ConfidentialTransferAccount>()
+        .unwrap();
+    token
+        .confidential_transfer_withdraw(
+            &alice_meta.token_account,
+            &alice,
+            10,
+            30,
+            &extension.available_balance.try_into().unwrap(),
+            decimals,
+        )
+        .await
+        .unwrap();
+
+    let err = token
+        .burn(&alice_meta.token_account, &alice.pubkey(), 10, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::PublicMintBurnDisabled as u32),
+            )
+        )))
+    );
+}
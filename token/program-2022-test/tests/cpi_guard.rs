@@ -0,0 +1,395 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::{processor, tokio, tokio::sync::Mutex, ProgramTest},
+    solana_sdk::{
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program::invoke,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        signature::Signer,
+        signer::keypair::Keypair,
+        transaction::TransactionError,
+        transport::TransportError,
+    },
+    spl_token_2022::{
+        error::TokenError,
+        extension::{
+            cpi_guard::{self, CpiGuard},
+            ExtensionType,
+        },
+        instruction::{self, AuthorityType},
+        processor::Processor,
+    },
+    spl_token_client::token::TokenError as TokenClientError,
+    std::sync::Arc,
+};
+
+/// Program that invokes the program given as its first account with the
+/// remaining accounts and the unchanged instruction data, so that any token
+/// instruction can be run through CPI
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    let (program_info, account_infos) = accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let instruction = Instruction {
+        program_id: *program_info.key,
+        accounts: account_infos
+            .iter()
+            .map(|info| AccountMeta {
+                pubkey: *info.key,
+                is_signer: info.is_signer,
+                is_writable: info.is_writable,
+            })
+            .collect(),
+        data: input.to_vec(),
+    };
+    invoke(&instruction, accounts)
+}
+
+/// Wrap a token instruction so that it is run through the proxy program
+fn through_cpi(proxy_program_id: &Pubkey, instruction: Instruction) -> Instruction {
+    let mut accounts = vec![AccountMeta::new_readonly(instruction.program_id, false)];
+    accounts.extend(instruction.accounts);
+    Instruction {
+        program_id: *proxy_program_id,
+        accounts,
+        data: instruction.data,
+    }
+}
+
+fn client_error(error: TokenError) -> TokenClientError {
+    TokenClientError::Client(Box::new(TransportError::TransactionError(
+        TransactionError::InstructionError(0, InstructionError::Custom(error as u32)),
+    )))
+}
+
+/// Start a test context with the proxy program deployed, and a token account
+/// for alice with CPI Guard enabled
+async fn setup(proxy_program_id: &Pubkey, amount: u64) -> (TestContext, Pubkey) {
+    let mut program_test = ProgramTest::new(
+        "spl_token_2022",
+        spl_token_2022::id(),
+        processor!(Processor::process),
+    );
+    program_test.add_program(
+        "cpi_proxy",
+        *proxy_program_id,
+        processor!(process_instruction),
+    );
+    let context = program_test.start_with_context().await;
+    let mut context = TestContext {
+        context: Arc::new(Mutex::new(context)),
+        token_context: None,
+    };
+    context.init_token_with_mint(vec![]).await.unwrap();
+
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        ..
+    } = context.token_context.as_ref().unwrap();
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account_with_extension_space(
+            &alice_account,
+            &alice.pubkey(),
+            vec![ExtensionType::CpiGuard],
+        )
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[mint_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .enable_cpi_guard(&alice_account, &alice.pubkey(), &[alice])
+        .await
+        .unwrap();
+
+    (context, alice_account)
+}
+
+#[tokio::test]
+async fn toggle() {
+    let proxy_program_id = Pubkey::new_unique();
+    let (context, alice_account) = setup(&proxy_program_id, 0).await;
+    let TokenContext { token, alice, .. } = context.token_context.unwrap();
+
+    let state = token.get_account_info(&alice_account).await.unwrap();
+    let extension = state.get_extension::<CpiGuard>().unwrap();
+    assert!(bool::from(extension.lock_cpi));
+
+    // fail, cannot be toggled through CPI
+    for instruction in [
+        cpi_guard::instruction::disable_cpi_guard(
+            &spl_token_2022::id(),
+            &alice_account,
+            &alice.pubkey(),
+            &[],
+        )
+        .unwrap(),
+        cpi_guard::instruction::enable_cpi_guard(
+            &spl_token_2022::id(),
+            &alice_account,
+            &alice.pubkey(),
+            &[],
+        )
+        .unwrap(),
+    ] {
+        let err = token
+            .process_ixs(&[through_cpi(&proxy_program_id, instruction)], &[&alice])
+            .await
+            .unwrap_err();
+        assert_eq!(err, client_error(TokenError::CpiGuardSettingsLocked));
+    }
+
+    token
+        .disable_cpi_guard(&alice_account, &alice.pubkey(), &[&alice])
+        .await
+        .unwrap();
+    let state = token.get_account_info(&alice_account).await.unwrap();
+    let extension = state.get_extension::<CpiGuard>().unwrap();
+    assert!(!bool::from(extension.lock_cpi));
+}
+
+#[tokio::test]
+async fn transfer() {
+    let proxy_program_id = Pubkey::new_unique();
+    let amount = 100;
+    let (context, alice_account) = setup(&proxy_program_id, amount).await;
+    let TokenContext {
+        decimals,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.unwrap();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+    let transfer = |authority: &Pubkey| {
+        instruction::transfer_checked(
+            &spl_token_2022::id(),
+            &alice_account,
+            token.get_address(),
+            &bob_account,
+            authority,
+            &[],
+            1,
+            decimals,
+        )
+        .unwrap()
+    };
+
+    // fail, owner transfer through CPI
+    let err = token
+        .process_ixs(
+            &[through_cpi(&proxy_program_id, transfer(&alice.pubkey()))],
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::CpiGuardTransferBlocked));
+
+    // owner transfer at the top level is fine
+    token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap();
+
+    // delegate transfer through CPI is fine
+    let delegate = Keypair::new();
+    token
+        .approve(
+            &alice_account,
+            &delegate.pubkey(),
+            &alice.pubkey(),
+            1,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    token
+        .process_ixs(
+            &[through_cpi(&proxy_program_id, transfer(&delegate.pubkey()))],
+            &[&delegate],
+        )
+        .await
+        .unwrap();
+
+    // owner transfer through CPI works once disabled
+    token
+        .disable_cpi_guard(&alice_account, &alice.pubkey(), &[&alice])
+        .await
+        .unwrap();
+    token
+        .process_ixs(
+            &[through_cpi(&proxy_program_id, transfer(&alice.pubkey()))],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    let state = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(state.base.amount, 3);
+}
+
+#[tokio::test]
+async fn approve() {
+    let proxy_program_id = Pubkey::new_unique();
+    let (context, alice_account) = setup(&proxy_program_id, 100).await;
+    let TokenContext { token, alice, .. } = context.token_context.unwrap();
+    let delegate = Pubkey::new_unique();
+    let approve = instruction::approve(
+        &spl_token_2022::id(),
+        &alice_account,
+        &delegate,
+        &alice.pubkey(),
+        &[],
+        10,
+    )
+    .unwrap();
+
+    // fail, approve through CPI
+    let err = token
+        .process_ixs(
+            &[through_cpi(&proxy_program_id, approve.clone())],
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::CpiGuardApproveBlocked));
+
+    // top level is fine
+    token.process_ixs(&[approve], &[&alice]).await.unwrap();
+    let state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(state.base.delegate, Some(delegate).into());
+}
+
+#[tokio::test]
+async fn close_account() {
+    let proxy_program_id = Pubkey::new_unique();
+    let (context, alice_account) = setup(&proxy_program_id, 0).await;
+    let TokenContext { token, alice, .. } = context.token_context.unwrap();
+    let close = |destination: &Pubkey| {
+        instruction::close_account(
+            &spl_token_2022::id(),
+            &alice_account,
+            destination,
+            &alice.pubkey(),
+            &[],
+        )
+        .unwrap()
+    };
+
+    // fail, lamports not returned to the owner
+    let err = token
+        .process_ixs(
+            &[through_cpi(&proxy_program_id, close(&Pubkey::new_unique()))],
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::CpiGuardCloseAccountBlocked));
+
+    // returning lamports to the owner is fine
+    token
+        .process_ixs(
+            &[through_cpi(&proxy_program_id, close(&alice.pubkey()))],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let err = token.get_account_info(&alice_account).await.unwrap_err();
+    assert_eq!(err, TokenClientError::AccountNotFound);
+}
+
+#[tokio::test]
+async fn set_authority() {
+    let proxy_program_id = Pubkey::new_unique();
+    let (context, alice_account) = setup(&proxy_program_id, 0).await;
+    let TokenContext { token, alice, .. } = context.token_context.unwrap();
+    let new_authority = Pubkey::new_unique();
+    let set_authority = |authority_type: AuthorityType, new_authority: Option<&Pubkey>| {
+        instruction::set_authority(
+            &spl_token_2022::id(),
+            &alice_account,
+            new_authority,
+            authority_type,
+            &alice.pubkey(),
+            &[],
+        )
+        .unwrap()
+    };
+
+    // fail, change owner through CPI
+    let err = token
+        .process_ixs(
+            &[through_cpi(
+                &proxy_program_id,
+                set_authority(AuthorityType::AccountOwner, Some(&new_authority)),
+            )],
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::CpiGuardSetAuthorityBlocked));
+
+    // fail, add close authority through CPI
+    let err = token
+        .process_ixs(
+            &[through_cpi(
+                &proxy_program_id,
+                set_authority(AuthorityType::CloseAccount, Some(&new_authority)),
+            )],
+            &[&alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::CpiGuardSetAuthorityBlocked));
+
+    // removing the close authority through CPI is fine
+    token
+        .process_ixs(
+            &[through_cpi(
+                &proxy_program_id,
+                set_authority(AuthorityType::CloseAccount, None),
+            )],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+
+    // top level is fine
+    token
+        .set_authority(
+            &alice_account,
+            &alice.pubkey(),
+            Some(&new_authority),
+            AuthorityType::AccountOwner,
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(state.base.owner, new_authority);
+}
@@ -0,0 +1,240 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+        transaction::TransactionError, transport::TransportError,
+    },
+    spl_token_2022::{
+        error::TokenError,
+        extension::{pausable::PausableConfig, ExtensionType},
+        instruction::AuthorityType,
+    },
+    spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
+    std::convert::TryInto,
+};
+
+fn client_error(error: TokenError) -> TokenClientError {
+    TokenClientError::Client(Box::new(TransportError::TransactionError(
+        TransactionError::InstructionError(0, InstructionError::Custom(error as u32)),
+    )))
+}
+
+async fn setup(authority: &Pubkey) -> TestContext {
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::PausableConfig {
+            authority: *authority,
+        }])
+        .await
+        .unwrap();
+    context
+}
+
+async fn setup_accounts(token_context: &TokenContext, amount: u64) -> (Pubkey, Pubkey) {
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = token_context;
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[mint_authority],
+        )
+        .await
+        .unwrap();
+
+    (alice_account, bob_account)
+}
+
+#[tokio::test]
+async fn success_initialize() {
+    let authority = Pubkey::new_unique();
+    let context = setup(&authority).await;
+    let TokenContext { token, alice, .. } = context.token_context.unwrap();
+
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PausableConfig>().unwrap();
+    assert_eq!(extension.authority, Some(authority).try_into().unwrap());
+    assert!(!bool::from(&extension.paused));
+
+    // new accounts get the account extension
+    let account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&account, &alice.pubkey())
+        .await
+        .unwrap();
+    let state = token.get_account_info(&account.pubkey()).await.unwrap();
+    assert_eq!(
+        state.get_extension_types().unwrap(),
+        vec![ExtensionType::PausableAccount]
+    );
+}
+
+#[tokio::test]
+async fn pause_and_resume() {
+    let authority = Keypair::new();
+    let context = setup(&authority.pubkey()).await;
+    let token_context = context.token_context.unwrap();
+    let amount = 10;
+    let (alice_account, bob_account) = setup_accounts(&token_context, amount).await;
+    let TokenContext {
+        mint_authority,
+        token,
+        token_unchecked,
+        alice,
+        ..
+    } = token_context;
+
+    // wrong signer
+    let wrong_signer = Keypair::new();
+    let err = token
+        .pause(&wrong_signer.pubkey(), &[&wrong_signer])
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::OwnerMismatch));
+
+    token
+        .pause(&authority.pubkey(), &[&authority])
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PausableConfig>().unwrap();
+    assert!(bool::from(&extension.paused));
+
+    // transfer fails
+    let err = token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::MintPaused));
+
+    // transfer without the mint cannot check the pause, so it fails
+    let err = token_unchecked
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::MintRequiredForTransfer));
+
+    // mint fails
+    let err = token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            1,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::MintPaused));
+
+    // burn fails
+    let err = token
+        .burn(&alice_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::MintPaused));
+
+    // everything works again after resuming
+    token
+        .resume(&authority.pubkey(), &[&authority])
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PausableConfig>().unwrap();
+    assert!(!bool::from(&extension.paused));
+
+    token
+        .transfer(&alice_account, &bob_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap();
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            1,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .burn(&alice_account, &alice.pubkey(), 1, &[&alice])
+        .await
+        .unwrap();
+    let state = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(state.base.amount, amount - 1);
+}
+
+#[tokio::test]
+async fn set_authority() {
+    let authority = Keypair::new();
+    let context = setup(&authority.pubkey()).await;
+    let TokenContext { token, .. } = context.token_context.unwrap();
+
+    // success
+    let new_authority = Keypair::new();
+    token
+        .set_authority(
+            token.get_address(),
+            &authority.pubkey(),
+            Some(&new_authority.pubkey()),
+            AuthorityType::Pause,
+            &[&authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PausableConfig>().unwrap();
+    assert_eq!(
+        extension.authority,
+        Some(new_authority.pubkey()).try_into().unwrap(),
+    );
+    let err = token
+        .pause(&authority.pubkey(), &[&authority])
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::OwnerMismatch));
+    token
+        .pause(&new_authority.pubkey(), &[&new_authority])
+        .await
+        .unwrap();
+
+    // set to none, the mint stays paused forever
+    token
+        .set_authority(
+            token.get_address(),
+            &new_authority.pubkey(),
+            None,
+            AuthorityType::Pause,
+            &[&new_authority],
+        )
+        .await
+        .unwrap();
+    let err = token
+        .resume(&new_authority.pubkey(), &[&new_authority])
+        .await
+        .unwrap_err();
+    assert_eq!(err, client_error(TokenError::NoAuthorityExists));
+}
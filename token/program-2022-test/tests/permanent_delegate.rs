@@ -0,0 +1,293 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
+        transaction::TransactionError, transport::TransportError,
+    },
+    spl_token_2022::{
+        error::TokenError, extension::permanent_delegate::PermanentDelegate, instruction,
+    },
+    spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
+    std::convert::TryInto,
+};
+
+#[tokio::test]
+async fn success_init() {
+    let delegate = Pubkey::new_unique();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::PermanentDelegate {
+            delegate,
+        }])
+        .await
+        .unwrap();
+    let TokenContext { token, .. } = context.token_context.unwrap();
+
+    let state = token.get_mint_info().await.unwrap();
+    assert!(state.base.is_initialized);
+    let extension = state.get_extension::<PermanentDelegate>().unwrap();
+    assert_eq!(extension.delegate, Some(delegate).try_into().unwrap(),);
+}
+
+#[tokio::test]
+async fn set_authority() {
+    let delegate = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::PermanentDelegate {
+            delegate: delegate.pubkey(),
+        }])
+        .await
+        .unwrap();
+    let token = context.token_context.unwrap().token;
+    let new_delegate = Keypair::new();
+
+    // fail, wrong signature
+    let wrong = Keypair::new();
+    let err = token
+        .set_authority(
+            token.get_address(),
+            &wrong.pubkey(),
+            Some(&new_delegate.pubkey()),
+            instruction::AuthorityType::PermanentDelegate,
+            &[&wrong],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // success
+    token
+        .set_authority(
+            token.get_address(),
+            &delegate.pubkey(),
+            Some(&new_delegate.pubkey()),
+            instruction::AuthorityType::PermanentDelegate,
+            &[&delegate],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PermanentDelegate>().unwrap();
+    assert_eq!(
+        extension.delegate,
+        Some(new_delegate.pubkey()).try_into().unwrap(),
+    );
+
+    // set to none
+    token
+        .set_authority(
+            token.get_address(),
+            &new_delegate.pubkey(),
+            None,
+            instruction::AuthorityType::PermanentDelegate,
+            &[&new_delegate],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<PermanentDelegate>().unwrap();
+    assert_eq!(extension.delegate, None.try_into().unwrap(),);
+
+    // fail set again
+    let err = token
+        .set_authority(
+            token.get_address(),
+            &new_delegate.pubkey(),
+            Some(&delegate.pubkey()),
+            instruction::AuthorityType::PermanentDelegate,
+            &[&new_delegate],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::AuthorityTypeNotSupported as u32)
+            )
+        )))
+    );
+}
+
+#[tokio::test]
+async fn success_transfer() {
+    let delegate = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::PermanentDelegate {
+            delegate: delegate.pubkey(),
+        }])
+        .await
+        .unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = context.token_context.unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+
+    let amount = 10;
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // delegate moves tokens without the owner's signature
+    token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &delegate.pubkey(),
+            amount,
+            &[&delegate],
+        )
+        .await
+        .unwrap();
+
+    let destination = token.get_account_info(&bob_account).await.unwrap();
+    assert_eq!(destination.base.amount, amount);
+    let source = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(source.base.amount, 0);
+}
+
+#[tokio::test]
+async fn success_burn() {
+    let delegate = Keypair::new();
+    let mut context = TestContext::new().await;
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::PermanentDelegate {
+            delegate: delegate.pubkey(),
+        }])
+        .await
+        .unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        ..
+    } = context.token_context.unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+
+    let amount = 10;
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    token
+        .burn(&alice_account, &delegate.pubkey(), amount, &[&delegate])
+        .await
+        .unwrap();
+
+    let source = token.get_account_info(&alice_account).await.unwrap();
+    assert_eq!(source.base.amount, 0);
+    let mint = token.get_mint_info().await.unwrap();
+    assert_eq!(mint.base.supply, 0);
+}
+
+#[tokio::test]
+async fn fail_without_extension() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        ..
+    } = context.token_context.unwrap();
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            10,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    // a random signer is not accepted on a mint without the extension
+    let delegate = Keypair::new();
+    let err = token
+        .burn(&alice_account, &delegate.pubkey(), 10, &[&delegate])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // setting the authority is not supported either
+    let err = token
+        .set_authority(
+            token.get_address(),
+            &mint_authority.pubkey(),
+            Some(&delegate.pubkey()),
+            instruction::AuthorityType::PermanentDelegate,
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+        )))
+    );
+}
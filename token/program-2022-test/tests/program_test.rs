@@ -42,8 +42,12 @@ impl TestContext {
         &mut self,
         extension_init_params: Vec<ExtensionInitializationParams>,
     ) -> TokenResult<()> {
-        self._init_token_with_mint(extension_init_params, None)
-            .await
+        self.init_token_with_mint_keypair_and_freeze_authority(
+            Keypair::new(),
+            extension_init_params,
+            None,
+        )
+        .await
     }
 
     pub async fn init_token_with_freezing_mint(
@@ -51,12 +55,17 @@ impl TestContext {
         extension_init_params: Vec<ExtensionInitializationParams>,
     ) -> TokenResult<()> {
         let freeze_authority = Keypair::new();
-        self._init_token_with_mint(extension_init_params, Some(freeze_authority))
-            .await
+        self.init_token_with_mint_keypair_and_freeze_authority(
+            Keypair::new(),
+            extension_init_params,
+            Some(freeze_authority),
+        )
+        .await
     }
 
-    pub async fn _init_token_with_mint(
+    pub async fn init_token_with_mint_keypair_and_freeze_authority(
         &mut self,
+        mint_account: Keypair,
         extension_init_params: Vec<ExtensionInitializationParams>,
         freeze_authority: Option<Keypair>,
     ) -> TokenResult<()> {
@@ -69,7 +78,6 @@ impl TestContext {
 
         let decimals: u8 = 9;
 
-        let mint_account = Keypair::new();
         let mint_authority = Keypair::new();
         let mint_authority_pubkey = mint_authority.pubkey();
         let freeze_authority_pubkey = freeze_authority
@@ -0,0 +1,383 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        signature::Signer,
+        signer::keypair::Keypair,
+        system_instruction,
+        transaction::{Transaction, TransactionError},
+        transport::TransportError,
+    },
+    spl_token_2022::{
+        error::TokenError,
+        extension::token_group::{TokenGroup, TokenGroupMember},
+        instruction,
+        state::Multisig,
+    },
+    spl_token_client::token::TokenError as TokenClientError,
+    std::convert::TryInto,
+};
+
+fn instruction_error(index: u8, error: InstructionError) -> TokenClientError {
+    TokenClientError::Client(Box::new(TransportError::TransactionError(
+        TransactionError::InstructionError(index, error),
+    )))
+}
+
+/// Create a group mint with the given max size, returning the context holding
+/// the group token and the group update authority
+async fn setup_group(max_size: u64) -> (TestContext, TokenContext, Keypair) {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let group_context = context.token_context.take().unwrap();
+    let update_authority = Keypair::new();
+    group_context
+        .token
+        .token_group_initialize(
+            &group_context.mint_authority.pubkey(),
+            Some(update_authority.pubkey()),
+            max_size,
+            &[&group_context.mint_authority],
+        )
+        .await
+        .unwrap();
+    (context, group_context, update_authority)
+}
+
+/// Create a new mint in the context, to be used as a group member
+async fn new_member(context: &mut TestContext) -> TokenContext {
+    context.init_token_with_mint(vec![]).await.unwrap();
+    context.token_context.take().unwrap()
+}
+
+#[tokio::test]
+async fn success_initialize_group() {
+    let (_context, group_context, update_authority) = setup_group(10).await;
+    let group = group_context.token.get_token_group().await.unwrap();
+    assert_eq!(
+        group,
+        TokenGroup {
+            update_authority: Some(update_authority.pubkey()).try_into().unwrap(),
+            mint: *group_context.token.get_address(),
+            size: 0.into(),
+            max_size: 10.into(),
+        }
+    );
+
+    // cannot initialize twice
+    let err = group_context
+        .token
+        .token_group_initialize(
+            &group_context.mint_authority.pubkey(),
+            None,
+            5,
+            &[&group_context.mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(
+            0,
+            InstructionError::Custom(TokenError::ExtensionAlreadyInitialized as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_initialize_group_without_mint_authority() {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let TokenContext { token, .. } = context.token_context.take().unwrap();
+    let wrong_authority = Keypair::new();
+    let err = token
+        .token_group_initialize(&wrong_authority.pubkey(), None, 5, &[&wrong_authority])
+        .await
+        .unwrap_err();
+    // the first instruction funds the mint for its new size
+    assert_eq!(
+        err,
+        instruction_error(
+            1,
+            InstructionError::Custom(TokenError::OwnerMismatch as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn initialize_members() {
+    let (mut context, group_context, update_authority) = setup_group(2).await;
+    let group_mint = *group_context.token.get_address();
+
+    for expected_member_number in 1..=2 {
+        let member_context = new_member(&mut context).await;
+        member_context
+            .token
+            .token_group_initialize_member(
+                &member_context.mint_authority.pubkey(),
+                &group_mint,
+                &update_authority.pubkey(),
+                &[&member_context.mint_authority, &update_authority],
+            )
+            .await
+            .unwrap();
+        let mint = member_context.token.get_mint_info().await.unwrap();
+        let member = mint.get_extension::<TokenGroupMember>().unwrap();
+        assert_eq!(
+            *member,
+            TokenGroupMember {
+                mint: *member_context.token.get_address(),
+                group: group_mint,
+                member_number: expected_member_number.into(),
+            }
+        );
+        let group = group_context.token.get_token_group().await.unwrap();
+        assert_eq!(u64::from(group.size), expected_member_number);
+    }
+
+    // group is full
+    let member_context = new_member(&mut context).await;
+    let err = member_context
+        .token
+        .token_group_initialize_member(
+            &member_context.mint_authority.pubkey(),
+            &group_mint,
+            &update_authority.pubkey(),
+            &[&member_context.mint_authority, &update_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(
+            1,
+            InstructionError::Custom(TokenError::SizeExceedsMaxSize as u32)
+        )
+    );
+
+    // requires the group update authority
+    group_context
+        .token
+        .token_group_update_max_size(&update_authority.pubkey(), 3, &[&update_authority])
+        .await
+        .unwrap();
+    let wrong_authority = Keypair::new();
+    let err = member_context
+        .token
+        .token_group_initialize_member(
+            &member_context.mint_authority.pubkey(),
+            &group_mint,
+            &wrong_authority.pubkey(),
+            &[&member_context.mint_authority, &wrong_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(
+            1,
+            InstructionError::Custom(TokenError::OwnerMismatch as u32)
+        )
+    );
+
+    // a group cannot be its own member
+    let err = group_context
+        .token
+        .token_group_initialize_member(
+            &group_context.mint_authority.pubkey(),
+            &group_mint,
+            &update_authority.pubkey(),
+            &[&group_context.mint_authority, &update_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err, instruction_error(1, InstructionError::InvalidArgument));
+}
+
+/// Create an m-of-n multisig owned by the token program
+async fn create_multisig(context: &TestContext, m: u8, signers: &[&Pubkey]) -> Pubkey {
+    let mut ctx = context.context.lock().await;
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let multisig = Keypair::new();
+    let instructions = vec![
+        system_instruction::create_account(
+            &ctx.payer.pubkey(),
+            &multisig.pubkey(),
+            rent.minimum_balance(Multisig::LEN),
+            Multisig::LEN as u64,
+            &spl_token_2022::id(),
+        ),
+        instruction::initialize_multisig(&spl_token_2022::id(), &multisig.pubkey(), signers, m)
+            .unwrap(),
+    ];
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&ctx.payer.pubkey()),
+        &[&ctx.payer, &multisig],
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+    multisig.pubkey()
+}
+
+#[tokio::test]
+async fn initialize_member_with_multisig_update_authority() {
+    let (mut context, group_context, update_authority) = setup_group(1).await;
+    let group_mint = *group_context.token.get_address();
+    let signer1 = Keypair::new();
+    let signer2 = Keypair::new();
+    let multisig = create_multisig(&context, 2, &[&signer1.pubkey(), &signer2.pubkey()]).await;
+    group_context
+        .token
+        .token_group_update_authority(
+            &update_authority.pubkey(),
+            Some(multisig),
+            &[&update_authority],
+        )
+        .await
+        .unwrap();
+
+    // not enough multisig signers
+    let member_context = new_member(&mut context).await;
+    let err = member_context
+        .token
+        .token_group_initialize_member(
+            &member_context.mint_authority.pubkey(),
+            &group_mint,
+            &multisig,
+            &[&member_context.mint_authority, &signer1],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(1, InstructionError::MissingRequiredSignature)
+    );
+
+    member_context
+        .token
+        .token_group_initialize_member(
+            &member_context.mint_authority.pubkey(),
+            &group_mint,
+            &multisig,
+            &[&member_context.mint_authority, &signer1, &signer2],
+        )
+        .await
+        .unwrap();
+    let mint = member_context.token.get_mint_info().await.unwrap();
+    let member = mint.get_extension::<TokenGroupMember>().unwrap();
+    assert_eq!(member.group, group_mint);
+    assert_eq!(u64::from(member.member_number), 1);
+}
+
+#[tokio::test]
+async fn update_max_size() {
+    let (mut context, group_context, update_authority) = setup_group(1).await;
+    let member_context = new_member(&mut context).await;
+    member_context
+        .token
+        .token_group_initialize_member(
+            &member_context.mint_authority.pubkey(),
+            group_context.token.get_address(),
+            &update_authority.pubkey(),
+            &[&member_context.mint_authority, &update_authority],
+        )
+        .await
+        .unwrap();
+
+    // cannot go below the current size
+    let err = group_context
+        .token
+        .token_group_update_max_size(&update_authority.pubkey(), 0, &[&update_authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(
+            0,
+            InstructionError::Custom(TokenError::SizeExceedsNewMaxSize as u32)
+        )
+    );
+
+    // wrong authority
+    let wrong_authority = Keypair::new();
+    let err = group_context
+        .token
+        .token_group_update_max_size(&wrong_authority.pubkey(), 5, &[&wrong_authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(
+            0,
+            InstructionError::Custom(TokenError::OwnerMismatch as u32)
+        )
+    );
+
+    group_context
+        .token
+        .token_group_update_max_size(&update_authority.pubkey(), 5, &[&update_authority])
+        .await
+        .unwrap();
+    let group = group_context.token.get_token_group().await.unwrap();
+    assert_eq!(u64::from(group.max_size), 5);
+}
+
+#[tokio::test]
+async fn update_authority() {
+    let (_context, group_context, update_authority) = setup_group(1).await;
+    let new_authority = Keypair::new();
+    group_context
+        .token
+        .token_group_update_authority(
+            &update_authority.pubkey(),
+            Some(new_authority.pubkey()),
+            &[&update_authority],
+        )
+        .await
+        .unwrap();
+    let group = group_context.token.get_token_group().await.unwrap();
+    assert_eq!(
+        group.update_authority,
+        Some(new_authority.pubkey()).try_into().unwrap()
+    );
+
+    // old authority no longer works
+    let err = group_context
+        .token
+        .token_group_update_max_size(&update_authority.pubkey(), 5, &[&update_authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(
+            0,
+            InstructionError::Custom(TokenError::OwnerMismatch as u32)
+        )
+    );
+
+    // removing the authority freezes the group
+    group_context
+        .token
+        .token_group_update_authority(&new_authority.pubkey(), None, &[&new_authority])
+        .await
+        .unwrap();
+    let err = group_context
+        .token
+        .token_group_update_max_size(&new_authority.pubkey(), 5, &[&new_authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(
+            0,
+            InstructionError::Custom(TokenError::NoAuthorityExists as u32)
+        )
+    );
+}
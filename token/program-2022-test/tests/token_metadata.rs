@@ -0,0 +1,324 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, signature::Signer, signer::keypair::Keypair,
+        transaction::TransactionError, transport::TransportError,
+    },
+    spl_token_2022::{
+        error::TokenError,
+        extension::{
+            get_account_len_with_variable_len_extension,
+            token_metadata::{Field, TokenMetadata},
+        },
+        state::Mint,
+    },
+    spl_token_client::token::TokenError as TokenClientError,
+    std::convert::TryInto,
+};
+
+async fn setup() -> (TestContext, Keypair) {
+    let mut context = TestContext::new().await;
+    context.init_token_with_mint(vec![]).await.unwrap();
+    let update_authority = Keypair::new();
+    (context, update_authority)
+}
+
+fn instruction_error(index: u8, error: InstructionError) -> TokenClientError {
+    TokenClientError::Client(Box::new(TransportError::TransactionError(
+        TransactionError::InstructionError(index, error),
+    )))
+}
+
+#[tokio::test]
+async fn success_initialize() {
+    let (context, update_authority) = setup().await;
+    let TokenContext {
+        mint_authority,
+        token,
+        ..
+    } = context.token_context.unwrap();
+
+    token
+        .token_metadata_initialize(
+            &update_authority.pubkey(),
+            &mint_authority.pubkey(),
+            "Token".to_string(),
+            "TKN".to_string(),
+            "https://example.com/token.json".to_string(),
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    let expected = TokenMetadata {
+        update_authority: Some(update_authority.pubkey()).try_into().unwrap(),
+        mint: *token.get_address(),
+        name: "Token".to_string(),
+        symbol: "TKN".to_string(),
+        uri: "https://example.com/token.json".to_string(),
+        additional_metadata: vec![],
+    };
+    assert_eq!(token.get_token_metadata().await.unwrap(), expected);
+    let account = token.get_account(token.get_address()).await.unwrap();
+    assert_eq!(
+        account.data.len(),
+        get_account_len_with_variable_len_extension::<Mint, _>(&account.data, &expected).unwrap()
+    );
+
+    // fail, already initialized
+    let err = token
+        .token_metadata_initialize(
+            &update_authority.pubkey(),
+            &mint_authority.pubkey(),
+            "Token".to_string(),
+            "TKN".to_string(),
+            "https://example.com/token.json".to_string(),
+            &[&mint_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(
+            0,
+            InstructionError::Custom(TokenError::ExtensionAlreadyInitialized as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_initialize_without_mint_authority() {
+    let (context, update_authority) = setup().await;
+    let token = context.token_context.unwrap().token;
+
+    // the first instruction funds the mint for the new metadata
+    let wrong = Keypair::new();
+    let err = token
+        .token_metadata_initialize(
+            &update_authority.pubkey(),
+            &wrong.pubkey(),
+            "Token".to_string(),
+            "TKN".to_string(),
+            "https://example.com/token.json".to_string(),
+            &[&wrong],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(
+            1,
+            InstructionError::Custom(TokenError::OwnerMismatch as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_update_and_remove_fields() {
+    let (context, update_authority) = setup().await;
+    let TokenContext {
+        mint_authority,
+        token,
+        ..
+    } = context.token_context.unwrap();
+    token
+        .token_metadata_initialize(
+            &update_authority.pubkey(),
+            &mint_authority.pubkey(),
+            "Token".to_string(),
+            "TKN".to_string(),
+            "https://example.com/token.json".to_string(),
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+    let initial_len = token
+        .get_account(token.get_address())
+        .await
+        .unwrap()
+        .data
+        .len();
+
+    // growing a field reallocates the mint
+    token
+        .token_metadata_update_field(
+            &update_authority.pubkey(),
+            Field::Name,
+            "A Much Longer Token Name".to_string(),
+            &[&update_authority],
+        )
+        .await
+        .unwrap();
+    token
+        .token_metadata_update_field(
+            &update_authority.pubkey(),
+            Field::Key("color".to_string()),
+            "blue".to_string(),
+            &[&update_authority],
+        )
+        .await
+        .unwrap();
+    let token_metadata = token.get_token_metadata().await.unwrap();
+    assert_eq!(token_metadata.name, "A Much Longer Token Name");
+    assert_eq!(
+        token_metadata.additional_metadata,
+        vec![("color".to_string(), "blue".to_string())]
+    );
+    let grown_len = token
+        .get_account(token.get_address())
+        .await
+        .unwrap()
+        .data
+        .len();
+    assert_eq!(
+        grown_len,
+        initial_len + "A Much Longer Token Name".len() - "Token".len() + 4 + 5 + 4 + 4
+    );
+
+    // removing a key shrinks it again
+    token
+        .token_metadata_remove_key(
+            &update_authority.pubkey(),
+            "color".to_string(),
+            false,
+            &[&update_authority],
+        )
+        .await
+        .unwrap();
+    let token_metadata = token.get_token_metadata().await.unwrap();
+    assert!(token_metadata.additional_metadata.is_empty());
+    let shrunk_len = token
+        .get_account(token.get_address())
+        .await
+        .unwrap()
+        .data
+        .len();
+    assert_eq!(shrunk_len, grown_len - 4 - 5 - 4 - 4);
+
+    // fail, key is gone
+    let err = token
+        .token_metadata_remove_key(
+            &update_authority.pubkey(),
+            "color".to_string(),
+            false,
+            &[&update_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(
+            0,
+            InstructionError::Custom(TokenError::TokenMetadataKeyNotFound as u32)
+        )
+    );
+
+    // idempotent removal succeeds
+    token
+        .token_metadata_remove_key(
+            &update_authority.pubkey(),
+            "color".to_string(),
+            true,
+            &[&update_authority],
+        )
+        .await
+        .unwrap();
+
+    // fail, wrong update authority
+    let wrong = Keypair::new();
+    let err = token
+        .token_metadata_update_field(&wrong.pubkey(), Field::Symbol, "BAD".to_string(), &[&wrong])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(
+            0,
+            InstructionError::Custom(TokenError::OwnerMismatch as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_update_authority() {
+    let (context, update_authority) = setup().await;
+    let TokenContext {
+        mint_authority,
+        token,
+        ..
+    } = context.token_context.unwrap();
+    token
+        .token_metadata_initialize(
+            &update_authority.pubkey(),
+            &mint_authority.pubkey(),
+            "Token".to_string(),
+            "TKN".to_string(),
+            "https://example.com/token.json".to_string(),
+            &[&mint_authority],
+        )
+        .await
+        .unwrap();
+
+    let new_authority = Keypair::new();
+    token
+        .token_metadata_update_authority(
+            &update_authority.pubkey(),
+            Some(new_authority.pubkey()),
+            &[&update_authority],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        token.get_token_metadata().await.unwrap().update_authority,
+        Some(new_authority.pubkey()).try_into().unwrap(),
+    );
+
+    // fail, old authority
+    let err = token
+        .token_metadata_update_authority(
+            &update_authority.pubkey(),
+            Some(update_authority.pubkey()),
+            &[&update_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(
+            0,
+            InstructionError::Custom(TokenError::OwnerMismatch as u32)
+        )
+    );
+
+    // set to none
+    token
+        .token_metadata_update_authority(&new_authority.pubkey(), None, &[&new_authority])
+        .await
+        .unwrap();
+    assert_eq!(
+        token.get_token_metadata().await.unwrap().update_authority,
+        None.try_into().unwrap(),
+    );
+
+    // fail, metadata is now immutable
+    let err = token
+        .token_metadata_update_field(
+            &new_authority.pubkey(),
+            Field::Uri,
+            "https://example.com".to_string(),
+            &[&new_authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        instruction_error(
+            0,
+            InstructionError::Custom(TokenError::NoAuthorityExists as u32)
+        )
+    );
+}
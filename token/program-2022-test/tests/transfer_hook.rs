@@ -0,0 +1,700 @@
+#![cfg(feature = "test-sbf")]
+
+mod program_test;
+use {
+    program_test::{TestContext, TokenContext},
+    solana_program_test::{processor, tokio, tokio::sync::Mutex, ProgramTest},
+    solana_sdk::{
+        account::Account as SolanaAccount,
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, InstructionError},
+        program::invoke,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        signature::Signer,
+        signer::keypair::Keypair,
+        system_instruction, system_program,
+        transaction::TransactionError,
+        transport::TransportError,
+    },
+    spl_token_2022::{
+        error::TokenError,
+        extension::{
+            transfer_hook::{interface, TransferHook, TransferHookAccount},
+            StateWithExtensions,
+        },
+        instruction,
+        processor::Processor,
+        state::Account,
+    },
+    spl_token_client::token::{ExtensionInitializationParams, TokenError as TokenClientError},
+    std::{convert::TryInto, sync::Arc},
+};
+
+/// Custom error returned by the rejecting transfer hook program
+const HOOK_REJECTED: u32 = 42;
+
+/// Transfer hook program that checks that it is called during a transfer with
+/// all of its extra accounts
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    interface::unpack_execute(input)?;
+
+    let account_info_iter = &mut accounts.iter();
+    let source_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let destination_account_info = next_account_info(account_info_iter)?;
+    let _authority_info = next_account_info(account_info_iter)?;
+    let validate_state_info = next_account_info(account_info_iter)?;
+
+    let expected_validate_state_pubkey =
+        interface::get_extra_account_metas_address(mint_info.key, program_id);
+    if *validate_state_info.key != expected_validate_state_pubkey {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let validate_state_data = validate_state_info.try_borrow_data()?;
+    for extra_meta in interface::unpack_extra_account_metas(&validate_state_data)? {
+        let extra_account_info = next_account_info(account_info_iter)?;
+        if *extra_account_info.key != extra_meta.pubkey {
+            return Err(ProgramError::InvalidArgument);
+        }
+    }
+
+    for account_info in [source_account_info, destination_account_info] {
+        let account_data = account_info.try_borrow_data()?;
+        let account = StateWithExtensions::<Account>::unpack(&account_data)?;
+        let extension = account.get_extension::<TransferHookAccount>()?;
+        if !bool::from(extension.transferring) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+    }
+
+    Ok(())
+}
+
+/// Transfer hook program that rejects every transfer
+fn process_instruction_reject(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _input: &[u8],
+) -> ProgramResult {
+    Err(ProgramError::Custom(HOOK_REJECTED))
+}
+
+/// Lamports the stealing transfer hook program tries to take from the transfer
+/// authority
+const STOLEN_LAMPORTS: u64 = 1_000_000;
+
+/// Transfer hook program that tries to move lamports out of the transfer
+/// authority, listed again among its extra accounts as a writable signer
+fn process_instruction_steal(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _input: &[u8],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().skip(5);
+    let authority_info = next_account_info(account_info_iter)?;
+    let thief_info = next_account_info(account_info_iter)?;
+    invoke(
+        &system_instruction::transfer(authority_info.key, thief_info.key, STOLEN_LAMPORTS),
+        &[authority_info.clone(), thief_info.clone()],
+    )
+}
+
+fn validate_state_account(extra_account_metas: &[AccountMeta], owner: &Pubkey) -> SolanaAccount {
+    let mut data = vec![0; interface::get_extra_account_metas_len(extra_account_metas.len())];
+    interface::init_extra_account_metas(&mut data, extra_account_metas).unwrap();
+    SolanaAccount {
+        lamports: 1_000_000_000,
+        data,
+        owner: *owner,
+        ..SolanaAccount::default()
+    }
+}
+
+/// Start a test context with both hook programs deployed, and their validation
+/// accounts created for the given mint
+async fn setup(
+    mint: &Keypair,
+    program_id: &Pubkey,
+    reject_program_id: &Pubkey,
+    extra_account_metas: &[AccountMeta],
+) -> TestContext {
+    let mut program_test = ProgramTest::new(
+        "spl_token_2022",
+        spl_token_2022::id(),
+        processor!(Processor::process),
+    );
+    program_test.add_program(
+        "my_transfer_hook",
+        *program_id,
+        processor!(process_instruction),
+    );
+    program_test.add_program(
+        "my_rejecting_transfer_hook",
+        *reject_program_id,
+        processor!(process_instruction_reject),
+    );
+    for extra_account_meta in extra_account_metas {
+        program_test.add_account(
+            extra_account_meta.pubkey,
+            SolanaAccount {
+                lamports: 1_000_000_000,
+                ..SolanaAccount::default()
+            },
+        );
+    }
+    for id in [program_id, reject_program_id] {
+        program_test.add_account(
+            interface::get_extra_account_metas_address(&mint.pubkey(), id),
+            validate_state_account(extra_account_metas, id),
+        );
+    }
+
+    let context = program_test.start_with_context().await;
+    TestContext {
+        context: Arc::new(Mutex::new(context)),
+        token_context: None,
+    }
+}
+
+async fn setup_with_hook(
+    authority: Pubkey,
+    program_id: Pubkey,
+    reject_program_id: Pubkey,
+    extra_account_metas: &[AccountMeta],
+) -> TestContext {
+    let mint = Keypair::new();
+    let mut context = setup(&mint, &program_id, &reject_program_id, extra_account_metas).await;
+    context
+        .init_token_with_mint_keypair_and_freeze_authority(
+            mint,
+            vec![ExtensionInitializationParams::TransferHook {
+                authority: Some(authority),
+                program_id: Some(program_id),
+            }],
+            None,
+        )
+        .await
+        .unwrap();
+    context
+}
+
+async fn setup_accounts(token_context: &TokenContext, amount: u64) -> (Pubkey, Pubkey) {
+    let TokenContext {
+        mint_authority,
+        token,
+        alice,
+        bob,
+        ..
+    } = token_context;
+
+    let alice_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&alice_account, &alice.pubkey())
+        .await
+        .unwrap();
+    let alice_account = alice_account.pubkey();
+    let bob_account = Keypair::new();
+    token
+        .create_auxiliary_token_account(&bob_account, &bob.pubkey())
+        .await
+        .unwrap();
+    let bob_account = bob_account.pubkey();
+
+    token
+        .mint_to(
+            &alice_account,
+            &mint_authority.pubkey(),
+            amount,
+            &[mint_authority],
+        )
+        .await
+        .unwrap();
+
+    (alice_account, bob_account)
+}
+
+#[tokio::test]
+async fn success_init() {
+    let authority = Pubkey::new_unique();
+    let program_id = Pubkey::new_unique();
+    let context = setup_with_hook(authority, program_id, Pubkey::new_unique(), &[]).await;
+    let token_context = context.token_context.unwrap();
+
+    let state = token_context.token.get_mint_info().await.unwrap();
+    assert!(state.base.is_initialized);
+    let extension = state.get_extension::<TransferHook>().unwrap();
+    assert_eq!(extension.authority, Some(authority).try_into().unwrap());
+    assert_eq!(extension.program_id, Some(program_id).try_into().unwrap());
+
+    // new accounts get the account extension
+    let (alice_account, _) = setup_accounts(&token_context, 0).await;
+    let state = token_context
+        .token
+        .get_account_info(&alice_account)
+        .await
+        .unwrap();
+    let extension = state.get_extension::<TransferHookAccount>().unwrap();
+    assert!(!bool::from(extension.transferring));
+}
+
+#[tokio::test]
+async fn fail_init_with_token_program_as_hook() {
+    let mut context = TestContext::new().await;
+    let err = context
+        .init_token_with_mint(vec![ExtensionInitializationParams::TransferHook {
+            authority: Some(Pubkey::new_unique()),
+            program_id: Some(spl_token_2022::id()),
+        }])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(1, InstructionError::IncorrectProgramId)
+        )))
+    );
+}
+
+#[tokio::test]
+async fn set_authority() {
+    let authority = Keypair::new();
+    let context = setup_with_hook(
+        authority.pubkey(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        &[],
+    )
+    .await;
+    let token = context.token_context.unwrap().token;
+    let new_authority = Keypair::new();
+
+    // fail, wrong signature
+    let wrong = Keypair::new();
+    let err = token
+        .set_authority(
+            token.get_address(),
+            &wrong.pubkey(),
+            Some(&new_authority.pubkey()),
+            instruction::AuthorityType::TransferHookProgramId,
+            &[&wrong],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // success
+    token
+        .set_authority(
+            token.get_address(),
+            &authority.pubkey(),
+            Some(&new_authority.pubkey()),
+            instruction::AuthorityType::TransferHookProgramId,
+            &[&authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<TransferHook>().unwrap();
+    assert_eq!(
+        extension.authority,
+        Some(new_authority.pubkey()).try_into().unwrap(),
+    );
+
+    // set to none
+    token
+        .set_authority(
+            token.get_address(),
+            &new_authority.pubkey(),
+            None,
+            instruction::AuthorityType::TransferHookProgramId,
+            &[&new_authority],
+        )
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<TransferHook>().unwrap();
+    assert_eq!(extension.authority, None.try_into().unwrap(),);
+
+    // fail update program id without an authority
+    let err = token
+        .update_transfer_hook_program_id(&new_authority.pubkey(), None, &[&new_authority])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::NoAuthorityExists as u32)
+            )
+        )))
+    );
+}
+
+#[tokio::test]
+async fn update_program_id() {
+    let authority = Keypair::new();
+    let program_id = Pubkey::new_unique();
+    let new_program_id = Pubkey::new_unique();
+    let context = setup_with_hook(authority.pubkey(), program_id, new_program_id, &[]).await;
+    let token = context.token_context.unwrap().token;
+
+    // fail, wrong signature
+    let wrong = Keypair::new();
+    let err = token
+        .update_transfer_hook_program_id(&wrong.pubkey(), Some(new_program_id), &[&wrong])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::OwnerMismatch as u32)
+            )
+        )))
+    );
+
+    // fail, token program cannot be its own hook
+    let err = token
+        .update_transfer_hook_program_id(
+            &authority.pubkey(),
+            Some(spl_token_2022::id()),
+            &[&authority],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::IncorrectProgramId)
+        )))
+    );
+
+    // success
+    token
+        .update_transfer_hook_program_id(&authority.pubkey(), Some(new_program_id), &[&authority])
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<TransferHook>().unwrap();
+    assert_eq!(
+        extension.program_id,
+        Some(new_program_id).try_into().unwrap(),
+    );
+
+    // set to none
+    token
+        .update_transfer_hook_program_id(&authority.pubkey(), None, &[&authority])
+        .await
+        .unwrap();
+    let state = token.get_mint_info().await.unwrap();
+    let extension = state.get_extension::<TransferHook>().unwrap();
+    assert_eq!(extension.program_id, None.try_into().unwrap(),);
+}
+
+#[tokio::test]
+async fn success_transfer() {
+    let program_id = Pubkey::new_unique();
+    let extra_account_metas = [
+        AccountMeta::new_readonly(Pubkey::new_unique(), false),
+        AccountMeta::new_readonly(Pubkey::new_unique(), false),
+    ];
+    let context = setup_with_hook(
+        Pubkey::new_unique(),
+        program_id,
+        Pubkey::new_unique(),
+        &extra_account_metas,
+    )
+    .await;
+    let token_context = context.token_context.unwrap();
+    let amount = 10;
+    let (alice_account, bob_account) = setup_accounts(&token_context, amount).await;
+
+    // the client adds the hook program, validation account, and extra accounts
+    token_context
+        .token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &token_context.alice.pubkey(),
+            amount,
+            &[&token_context.alice],
+        )
+        .await
+        .unwrap();
+
+    let destination = token_context
+        .token
+        .get_account_info(&bob_account)
+        .await
+        .unwrap();
+    assert_eq!(destination.base.amount, amount);
+    let extension = destination.get_extension::<TransferHookAccount>().unwrap();
+    assert!(!bool::from(extension.transferring));
+    let source = token_context
+        .token
+        .get_account_info(&alice_account)
+        .await
+        .unwrap();
+    assert_eq!(source.base.amount, 0);
+    let extension = source.get_extension::<TransferHookAccount>().unwrap();
+    assert!(!bool::from(extension.transferring));
+}
+
+#[tokio::test]
+async fn fail_transfer_hook_rejects() {
+    let authority = Keypair::new();
+    let reject_program_id = Pubkey::new_unique();
+    let context = setup_with_hook(
+        authority.pubkey(),
+        Pubkey::new_unique(),
+        reject_program_id,
+        &[],
+    )
+    .await;
+    let token_context = context.token_context.unwrap();
+    let amount = 10;
+    let (alice_account, bob_account) = setup_accounts(&token_context, amount).await;
+
+    token_context
+        .token
+        .update_transfer_hook_program_id(
+            &authority.pubkey(),
+            Some(reject_program_id),
+            &[&authority],
+        )
+        .await
+        .unwrap();
+
+    let err = token_context
+        .token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &token_context.alice.pubkey(),
+            amount,
+            &[&token_context.alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(HOOK_REJECTED))
+        )))
+    );
+
+    // removing the program id lets transfers through again
+    token_context
+        .token
+        .update_transfer_hook_program_id(&authority.pubkey(), None, &[&authority])
+        .await
+        .unwrap();
+    token_context
+        .token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &token_context.alice.pubkey(),
+            amount,
+            &[&token_context.alice],
+        )
+        .await
+        .unwrap();
+    let destination = token_context
+        .token
+        .get_account_info(&bob_account)
+        .await
+        .unwrap();
+    assert_eq!(destination.base.amount, amount);
+}
+
+#[tokio::test]
+async fn fail_transfer_missing_extra_account() {
+    let program_id = Pubkey::new_unique();
+    let extra_account_metas = [AccountMeta::new_readonly(Pubkey::new_unique(), false)];
+    let context = setup_with_hook(
+        Pubkey::new_unique(),
+        program_id,
+        Pubkey::new_unique(),
+        &extra_account_metas,
+    )
+    .await;
+    let token_context = context.token_context.unwrap();
+    let amount = 10;
+    let (alice_account, bob_account) = setup_accounts(&token_context, amount).await;
+
+    // build the instruction by hand, leaving out the extra account
+    let mut instruction = instruction::transfer_checked(
+        &spl_token_2022::id(),
+        &alice_account,
+        token_context.token.get_address(),
+        &bob_account,
+        &token_context.alice.pubkey(),
+        &[],
+        amount,
+        token_context.decimals,
+    )
+    .unwrap();
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(program_id, false));
+    instruction.accounts.push(AccountMeta::new_readonly(
+        interface::get_extra_account_metas_address(token_context.token.get_address(), &program_id),
+        false,
+    ));
+    let err = token_context
+        .token
+        .process_ixs(&[instruction], &[&token_context.alice])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+        )))
+    );
+}
+
+#[tokio::test]
+async fn fail_unchecked_transfer() {
+    let context = setup_with_hook(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        &[],
+    )
+    .await;
+    let token_context = context.token_context.unwrap();
+    let amount = 10;
+    let (alice_account, bob_account) = setup_accounts(&token_context, amount).await;
+
+    let err = token_context
+        .token_unchecked
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &token_context.alice.pubkey(),
+            amount,
+            &[&token_context.alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::MintRequiredForTransfer as u32)
+            )
+        )))
+    );
+}
+
+#[tokio::test]
+async fn fail_transfer_hook_escalates_authority_privileges() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new(
+        "spl_token_2022",
+        spl_token_2022::id(),
+        processor!(Processor::process),
+    );
+    program_test.add_program(
+        "my_stealing_transfer_hook",
+        program_id,
+        processor!(process_instruction_steal),
+    );
+    let mut context = TestContext {
+        context: Arc::new(Mutex::new(program_test.start_with_context().await)),
+        token_context: None,
+    };
+    context
+        .init_token_with_mint(vec![ExtensionInitializationParams::TransferHook {
+            authority: Some(Pubkey::new_unique()),
+            program_id: Some(program_id),
+        }])
+        .await
+        .unwrap();
+    let token_context = context.token_context.unwrap();
+    let amount = 10;
+    let (alice_account, bob_account) = setup_accounts(&token_context, amount).await;
+
+    // the hook asks for the transfer authority as a writable signer
+    let alice = token_context.alice.pubkey();
+    let thief = Pubkey::new_unique();
+    let extra_account_metas = [
+        AccountMeta::new(alice, true),
+        AccountMeta::new(thief, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    let alice_lamports = 1_000_000_000;
+    {
+        let mut context = context.context.lock().await;
+        context.set_account(
+            &interface::get_extra_account_metas_address(
+                token_context.token.get_address(),
+                &program_id,
+            ),
+            &validate_state_account(&extra_account_metas, &program_id).into(),
+        );
+        context.set_account(
+            &alice,
+            &SolanaAccount {
+                lamports: alice_lamports,
+                owner: system_program::id(),
+                ..SolanaAccount::default()
+            }
+            .into(),
+        );
+    }
+
+    let err = token_context
+        .token
+        .transfer(
+            &alice_account,
+            &bob_account,
+            &alice,
+            amount,
+            &[&token_context.alice],
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        TokenClientError::Client(Box::new(TransportError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::PrivilegeEscalation)
+        )))
+    );
+
+    let alice_account_info = context
+        .context
+        .lock()
+        .await
+        .banks_client
+        .get_account(alice)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(alice_account_info.lamports, alice_lamports);
+    let destination = token_context
+        .token
+        .get_account_info(&bob_account)
+        .await
+        .unwrap();
+    assert_eq!(destination.base.amount, 0);
+}
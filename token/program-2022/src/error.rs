@@ -159,6 +159,47 @@ pub enum TokenError {
     /// The deposit amount for the confidential extension exceeds the maximum limit
     #[error("Deposit amount exceeds maximum limit")]
     MaximumDepositAmountExceeded,
+    /// The key was not found in the token metadata
+    #[error("Key not found in token metadata")]
+    TokenMetadataKeyNotFound,
+    /// CPI Guard cannot be enabled or disabled in CPI
+    #[error("CPI Guard cannot be enabled or disabled in CPI")]
+    CpiGuardSettingsLocked,
+    /// CPI Guard is enabled, and a program attempted to transfer user funds without using a delegate
+    #[error("CPI Guard is enabled, and a program attempted to transfer user funds via CPI without using a delegate")]
+    CpiGuardTransferBlocked,
+    /// CPI Guard is enabled, and a program attempted to approve a delegate
+    #[error("CPI Guard is enabled, and a program attempted to approve a delegate")]
+    CpiGuardApproveBlocked,
+    /// CPI Guard is enabled, and a program attempted to close an account without returning lamports to owner
+    #[error("CPI Guard is enabled, and a program attempted to close an account via CPI without returning lamports to owner")]
+    CpiGuardCloseAccountBlocked,
+    /// CPI Guard is enabled, and a program attempted to change the owner or close authority
+    #[error("CPI Guard is enabled, and a program attempted to change the owner or close authority via CPI")]
+    CpiGuardSetAuthorityBlocked,
+    /// Mint supply is confidential, so tokens cannot be minted or burned publicly
+    #[error("Mint supply is confidential, so tokens cannot be minted or burned publicly")]
+    PublicMintBurnDisabled,
+    /// UI amount multiplier must be positive and finite
+    #[error("UI amount multiplier must be positive and finite")]
+    InvalidUiAmountMultiplier,
+    /// Mint cannot combine interest-bearing and scaled UI amount extensions
+    #[error("Mint cannot combine interest-bearing and scaled UI amount extensions")]
+    InvalidExtensionCombination,
+    /// Mint is paused, so tokens cannot be minted, burned, or transferred
+    #[error("Mint is paused, so tokens cannot be minted, burned, or transferred")]
+    MintPaused,
+    /// Group size would exceed the new max size
+    #[error("Group size would exceed the new max size")]
+    SizeExceedsNewMaxSize,
+    /// Group is already at its max size
+    #[error("Group is already at its max size")]
+    SizeExceedsMaxSize,
+    /// Confidential supply requires a nonzero auditor ElGamal public key that never changes
+    #[error(
+        "Confidential supply requires a nonzero auditor ElGamal public key that never changes"
+    )]
+    InvalidConfidentialSupplyAuditor,
 }
 impl From<TokenError> for ProgramError {
     fn from(e: TokenError) -> Self {
@@ -274,6 +315,45 @@ impl PrintProgramError for TokenError {
             TokenError::MaximumDepositAmountExceeded => {
                 msg!("Deposit amount exceeds maximum limit")
             }
+            TokenError::TokenMetadataKeyNotFound => {
+                msg!("Key not found in token metadata")
+            }
+            TokenError::CpiGuardSettingsLocked => {
+                msg!("CPI Guard cannot be enabled or disabled in CPI")
+            }
+            TokenError::CpiGuardTransferBlocked => {
+                msg!("CPI Guard is enabled, and a program attempted to transfer user funds via CPI without using a delegate")
+            }
+            TokenError::CpiGuardApproveBlocked => {
+                msg!("CPI Guard is enabled, and a program attempted to approve a delegate")
+            }
+            TokenError::CpiGuardCloseAccountBlocked => {
+                msg!("CPI Guard is enabled, and a program attempted to close an account via CPI without returning lamports to owner")
+            }
+            TokenError::CpiGuardSetAuthorityBlocked => {
+                msg!("CPI Guard is enabled, and a program attempted to change the owner or close authority via CPI")
+            }
+            TokenError::PublicMintBurnDisabled => {
+                msg!("Mint supply is confidential, so tokens cannot be minted or burned publicly")
+            }
+            TokenError::InvalidUiAmountMultiplier => {
+                msg!("UI amount multiplier must be positive and finite")
+            }
+            TokenError::InvalidExtensionCombination => {
+                msg!("Mint cannot combine interest-bearing and scaled UI amount extensions")
+            }
+            TokenError::MintPaused => {
+                msg!("Mint is paused, so tokens cannot be minted, burned, or transferred")
+            }
+            TokenError::SizeExceedsNewMaxSize => {
+                msg!("Group size would exceed the new max size")
+            }
+            TokenError::SizeExceedsMaxSize => {
+                msg!("Group is already at its max size")
+            }
+            TokenError::InvalidConfidentialSupplyAuditor => {
+                msg!("Confidential supply requires a nonzero auditor ElGamal public key that never changes")
+            }
         }
     }
 }
@@ -43,6 +43,9 @@ pub enum ConfidentialTransferInstruction {
 
     /// Updates the confidential transfer mint configuration for a mint.
     ///
+    /// The auditor ElGamal public key cannot be changed if the mint has a confidential
+    /// supply (see `InitializeConfidentialMintBurn`).
+    ///
     /// Accounts expected by this instruction:
     ///
     ///   0. `[writable]` The SPL Token mint.
@@ -369,6 +372,87 @@ pub enum ConfidentialTransferInstruction {
     ///   None
     ///
     HarvestWithheldTokensToMint,
+
+    /// Initializes an encrypted supply for a mint, enabling `ConfidentialMint` and
+    /// `ConfidentialBurn`.
+    ///
+    /// Once initialized, the mint's supply is only tracked as a ciphertext under the mint's
+    /// auditor ElGamal public key, and the public `MintTo` and `Burn` instructions are
+    /// disabled for the mint.
+    ///
+    /// The `ConfidentialTransferInstruction::InitializeConfidentialMintBurn` instruction
+    /// requires no signers and MUST be included within the same Transaction as
+    /// `TokenInstruction::InitializeMint`. Otherwise another party can initialize the
+    /// configuration.
+    ///
+    /// The instruction fails if the `TokenInstruction::InitializeMint` instruction has already
+    /// executed for the mint, or if the mint's auditor ElGamal public key is zero. Once
+    /// initialized, `UpdateMint` can no longer change the auditor ElGamal public key.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The SPL Token mint.
+    ///
+    /// Data expected by this instruction:
+    ///   None
+    ///
+    InitializeConfidentialMintBurn,
+
+    /// Mint tokens directly into the pending balance of a token account.
+    ///
+    /// The amount is encrypted in a `ProofInstruction::VerifyTransfer` proof generated by the
+    /// mint authority from an ephemeral source keypair. The destination ciphertexts are credited
+    /// to the account's pending balance, and the auditor ciphertexts are added to the mint's
+    /// encrypted supply.
+    ///
+    /// Fails if the destination account has `allow_balance_credits` disabled or if the pending
+    /// balance credit counter would exceed its maximum.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The destination SPL Token account.
+    ///   1. `[writable]` The SPL Token mint. Must include the `ConfidentialMintBurn` extension.
+    ///   2. `[]` Instructions sysvar.
+    ///   3. `[signer]` The mint authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The destination SPL Token account.
+    ///   1. `[writable]` The SPL Token mint. Must include the `ConfidentialMintBurn` extension.
+    ///   2. `[]` Instructions sysvar.
+    ///   3. `[]` The multisig mint authority.
+    ///   4.. `[signer]` Required M signer accounts for the SPL Token Multisig account.
+    ///
+    /// Data expected by this instruction:
+    ///   `ConfidentialMintInstructionData`
+    ///
+    ConfidentialMint,
+
+    /// Burn tokens from the available balance of a token account.
+    ///
+    /// The amount is encrypted in a `ProofInstruction::VerifyTransfer` proof generated by the
+    /// account owner. The source ciphertexts are subtracted from the account's available
+    /// balance, and the auditor ciphertexts are subtracted from the mint's encrypted supply.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner
+    ///   0. `[writable]` The source SPL Token account.
+    ///   1. `[writable]` The SPL Token mint. Must include the `ConfidentialMintBurn` extension.
+    ///   2. `[]` Instructions sysvar.
+    ///   3. `[signer]` The single source account owner.
+    ///
+    ///   * Multisignature owner
+    ///   0. `[writable]` The source SPL Token account.
+    ///   1. `[writable]` The SPL Token mint. Must include the `ConfidentialMintBurn` extension.
+    ///   2. `[]` Instructions sysvar.
+    ///   3. `[]` The multisig source account owner.
+    ///   4.. `[signer]` Required M signer accounts for the SPL Token Multisig account.
+    ///
+    /// Data expected by this instruction:
+    ///   `ConfidentialBurnInstructionData`
+    ///
+    ConfidentialBurn,
 }
 
 /// Data expected by `ConfidentialTransferInstruction::ConfigureAccount`
@@ -461,6 +545,26 @@ pub struct WithdrawWithheldTokensFromAccountsData {
     pub proof_instruction_offset: i8,
 }
 
+/// Data expected by `ConfidentialTransferInstruction::ConfidentialMint`
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct ConfidentialMintInstructionData {
+    /// Relative location of the `ProofInstruction::VerifyTransfer` instruction to the
+    /// `ConfidentialMint` instruction in the transaction
+    pub proof_instruction_offset: i8,
+}
+
+/// Data expected by `ConfidentialTransferInstruction::ConfidentialBurn`
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct ConfidentialBurnInstructionData {
+    /// The new source decryptable balance if the burn succeeds
+    pub new_source_decryptable_available_balance: DecryptableBalance,
+    /// Relative location of the `ProofInstruction::VerifyTransfer` instruction to the
+    /// `ConfidentialBurn` instruction in the transaction
+    pub proof_instruction_offset: i8,
+}
+
 /// Create a `InitializeMint` instruction
 pub fn initialize_mint(
     token_program_id: &Pubkey,
@@ -1107,3 +1211,137 @@ pub fn harvest_withheld_tokens_to_mint(
         &(),
     ))
 }
+
+/// Create a `InitializeConfidentialMintBurn` instruction
+pub fn initialize_confidential_mint_burn(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let accounts = vec![AccountMeta::new(*mint, false)];
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ConfidentialTransferExtension,
+        ConfidentialTransferInstruction::InitializeConfidentialMintBurn,
+        &(),
+    ))
+}
+
+/// Create a inner `ConfidentialMint` instruction
+///
+/// This instruction is suitable for use with a cross-program `invoke`
+pub fn inner_confidential_mint(
+    token_program_id: &Pubkey,
+    destination_token_account: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    proof_instruction_offset: i8,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*destination_token_account, false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(*authority, multisig_signers.is_empty()),
+    ];
+
+    for multisig_signer in multisig_signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**multisig_signer, true));
+    }
+
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ConfidentialTransferExtension,
+        ConfidentialTransferInstruction::ConfidentialMint,
+        &ConfidentialMintInstructionData {
+            proof_instruction_offset,
+        },
+    ))
+}
+
+/// Create a `ConfidentialMint` instruction
+#[cfg(not(target_os = "solana"))]
+pub fn confidential_mint(
+    token_program_id: &Pubkey,
+    destination_token_account: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    proof_data: &TransferData,
+) -> Result<Vec<Instruction>, ProgramError> {
+    Ok(vec![
+        inner_confidential_mint(
+            token_program_id,
+            destination_token_account,
+            mint,
+            authority,
+            multisig_signers,
+            1,
+        )?, // calls check_program_account
+        verify_transfer(proof_data),
+    ])
+}
+
+/// Create a inner `ConfidentialBurn` instruction
+///
+/// This instruction is suitable for use with a cross-program `invoke`
+pub fn inner_confidential_burn(
+    token_program_id: &Pubkey,
+    source_token_account: &Pubkey,
+    mint: &Pubkey,
+    new_source_decryptable_available_balance: DecryptableBalance,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    proof_instruction_offset: i8,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*source_token_account, false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(*authority, multisig_signers.is_empty()),
+    ];
+
+    for multisig_signer in multisig_signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**multisig_signer, true));
+    }
+
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::ConfidentialTransferExtension,
+        ConfidentialTransferInstruction::ConfidentialBurn,
+        &ConfidentialBurnInstructionData {
+            new_source_decryptable_available_balance,
+            proof_instruction_offset,
+        },
+    ))
+}
+
+/// Create a `ConfidentialBurn` instruction
+#[cfg(not(target_os = "solana"))]
+pub fn confidential_burn(
+    token_program_id: &Pubkey,
+    source_token_account: &Pubkey,
+    mint: &Pubkey,
+    new_source_decryptable_available_balance: AeCiphertext,
+    authority: &Pubkey,
+    multisig_signers: &[&Pubkey],
+    proof_data: &TransferData,
+) -> Result<Vec<Instruction>, ProgramError> {
+    Ok(vec![
+        inner_confidential_burn(
+            token_program_id,
+            source_token_account,
+            mint,
+            new_source_decryptable_available_balance.into(),
+            authority,
+            multisig_signers,
+            1,
+        )?, // calls check_program_account
+        verify_transfer(proof_data),
+    ])
+}
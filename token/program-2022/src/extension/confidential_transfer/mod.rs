@@ -78,6 +78,21 @@ impl Extension for ConfidentialTransferMint {
     const TYPE: ExtensionType = ExtensionType::ConfidentialTransferMint;
 }
 
+/// Encrypted supply of a mint that only mints and burns confidentially. `MintTo` and `Burn`
+/// are disabled, so the base mint `supply` stays zero.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ConfidentialMintBurn {
+    /// Amount minted by `ConfidentialMint` less amount burned by `ConfidentialBurn`, encrypted
+    /// under the `ConfidentialTransferMint` auditor ElGamal public key, which can no longer
+    /// change
+    pub confidential_supply: EncryptedBalance,
+}
+
+impl Extension for ConfidentialMintBurn {
+    const TYPE: ExtensionType = ExtensionType::ConfidentialMintBurn;
+}
+
 /// Confidential account state
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
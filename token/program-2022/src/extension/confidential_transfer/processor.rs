@@ -6,6 +6,7 @@ use {
             confidential_transfer::{instruction::*, *},
             memo_transfer::{check_previous_sibling_instruction_is_memo, memo_required},
             non_transferable::NonTransferable,
+            pausable::PausableConfig,
             StateWithExtensions, StateWithExtensionsMut,
         },
         instruction::{decode_instruction_data, decode_instruction_type},
@@ -27,7 +28,7 @@ use {
 #[cfg(feature = "zk-ops")]
 use {
     crate::extension::transfer_fee::TransferFeeConfig,
-    solana_program::{clock::Clock, sysvar::Sysvar},
+    solana_program::{clock::Clock, program_option::COption, sysvar::Sysvar},
     solana_zk_token_sdk::zk_token_elgamal::ops,
 };
 
@@ -45,6 +46,18 @@ fn decode_proof_instruction<T: Pod>(
     ProofInstruction::decode_data(&instruction.data).ok_or(ProgramError::InvalidInstructionData)
 }
 
+/// Checks that a mint's confidential supply can be encrypted under its auditor ElGamal
+/// public key
+fn check_confidential_supply_auditor(
+    confidential_transfer_mint: &ConfidentialTransferMint,
+) -> ProgramResult {
+    if confidential_transfer_mint.auditor_encryption_pubkey == EncryptionPubkey::zeroed() {
+        msg!("Confidential mint and burn requires an auditor ElGamal public key");
+        return Err(TokenError::InvalidConfidentialSupplyAuditor.into());
+    }
+    Ok(())
+}
+
 /// Processes an [InitializeMint] instruction.
 fn process_initialize_mint(
     accounts: &[AccountInfo],
@@ -56,6 +69,9 @@ fn process_initialize_mint(
     check_program_account(mint_info.owner)?;
     let mint_data = &mut mint_info.data.borrow_mut();
     let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(mint_data)?;
+    if mint.get_extension::<ConfidentialMintBurn>().is_ok() {
+        check_confidential_supply_auditor(confidential_transfer_mint)?;
+    }
     *mint.init_extension::<ConfidentialTransferMint>(true)? = *confidential_transfer_mint;
 
     Ok(())
@@ -74,8 +90,17 @@ fn process_update_mint(
     check_program_account(mint_info.owner)?;
     let mint_data = &mut mint_info.data.borrow_mut();
     let mut mint = StateWithExtensionsMut::<Mint>::unpack(mint_data)?;
+    let has_confidential_supply = mint.get_extension::<ConfidentialMintBurn>().is_ok();
     let confidential_transfer_mint = mint.get_extension_mut::<ConfidentialTransferMint>()?;
 
+    // The confidential supply is encrypted under the auditor key, so it must not change
+    if has_confidential_supply
+        && new_confidential_transfer_mint.auditor_encryption_pubkey
+            != confidential_transfer_mint.auditor_encryption_pubkey
+    {
+        return Err(TokenError::InvalidConfidentialSupplyAuditor.into());
+    }
+
     if authority_info.is_signer
         && confidential_transfer_mint.authority == *authority_info.key
         && (new_authority_info.is_signer || *new_authority_info.key == Pubkey::default())
@@ -88,6 +113,23 @@ fn process_update_mint(
     }
 }
 
+/// Processes an [InitializeConfidentialMintBurn] instruction.
+fn process_initialize_confidential_mint_burn(accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+
+    check_program_account(mint_info.owner)?;
+    let mint_data = &mut mint_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(mint_data)?;
+    if let Ok(confidential_transfer_mint) = mint.get_extension::<ConfidentialTransferMint>() {
+        check_confidential_supply_auditor(confidential_transfer_mint)?;
+    }
+    let confidential_mint_burn = mint.init_extension::<ConfidentialMintBurn>(true)?;
+    confidential_mint_burn.confidential_supply = EncryptedBalance::zeroed();
+
+    Ok(())
+}
+
 /// Processes a [ConfigureAccount] instruction.
 fn process_configure_account(
     program_id: &Pubkey,
@@ -464,6 +506,10 @@ fn process_transfer(
         return Err(TokenError::NonTransferable.into());
     }
 
+    if let Ok(pausable_config) = mint.get_extension::<PausableConfig>() {
+        pausable_config.check_not_paused()?;
+    }
+
     let confidential_transfer_mint = mint.get_extension::<ConfidentialTransferMint>()?;
     let previous_instruction =
         get_instruction_relative(proof_instruction_offset, instructions_sysvar_info)?;
@@ -1122,6 +1168,170 @@ fn process_harvest_withheld_tokens_to_mint(accounts: &[AccountInfo]) -> ProgramR
     Ok(())
 }
 
+/// Processes a [ConfidentialMint] instruction.
+#[cfg(feature = "zk-ops")]
+fn process_confidential_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proof_instruction_offset: i64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let destination_token_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let instructions_sysvar_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let authority_info_data_len = authority_info.data_len();
+
+    check_program_account(mint_info.owner)?;
+    let mint_data = &mut mint_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(mint_data)?;
+
+    if let Ok(pausable_config) = mint.get_extension::<PausableConfig>() {
+        pausable_config.check_not_paused()?;
+    }
+
+    match mint.base.mint_authority {
+        COption::Some(mint_authority) => Processor::validate_owner(
+            program_id,
+            &mint_authority,
+            authority_info,
+            authority_info_data_len,
+            account_info_iter.as_slice(),
+        )?,
+        COption::None => return Err(TokenError::FixedSupply.into()),
+    }
+
+    let auditor_encryption_pubkey = mint
+        .get_extension::<ConfidentialTransferMint>()?
+        .auditor_encryption_pubkey;
+
+    let previous_instruction =
+        get_instruction_relative(proof_instruction_offset, instructions_sysvar_info)?;
+    let proof_data = decode_proof_instruction::<TransferData>(
+        ProofInstruction::VerifyTransfer,
+        &previous_instruction,
+    )?;
+
+    if proof_data.transfer_pubkeys.auditor_pubkey != auditor_encryption_pubkey {
+        return Err(TokenError::ConfidentialTransferElGamalPubkeyMismatch.into());
+    }
+
+    let destination_ciphertext_lo = EncryptedBalance::from((
+        proof_data.ciphertext_lo.commitment,
+        proof_data.ciphertext_lo.destination_handle,
+    ));
+    let destination_ciphertext_hi = EncryptedBalance::from((
+        proof_data.ciphertext_hi.commitment,
+        proof_data.ciphertext_hi.destination_handle,
+    ));
+
+    process_destination_for_transfer(
+        destination_token_account_info,
+        mint_info,
+        &proof_data.transfer_pubkeys.destination_pubkey,
+        &destination_ciphertext_lo,
+        &destination_ciphertext_hi,
+        None,
+    )?;
+
+    let auditor_ciphertext_lo = EncryptedBalance::from((
+        proof_data.ciphertext_lo.commitment,
+        proof_data.ciphertext_lo.auditor_handle,
+    ));
+    let auditor_ciphertext_hi = EncryptedBalance::from((
+        proof_data.ciphertext_hi.commitment,
+        proof_data.ciphertext_hi.auditor_handle,
+    ));
+
+    let confidential_mint_burn = mint.get_extension_mut::<ConfidentialMintBurn>()?;
+    confidential_mint_burn.confidential_supply = ops::add_with_lo_hi(
+        &confidential_mint_burn.confidential_supply,
+        &auditor_ciphertext_lo,
+        &auditor_ciphertext_hi,
+    )
+    .ok_or(ProgramError::InvalidInstructionData)?;
+
+    Ok(())
+}
+
+/// Processes a [ConfidentialBurn] instruction.
+#[cfg(feature = "zk-ops")]
+fn process_confidential_burn(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_source_decryptable_available_balance: DecryptableBalance,
+    proof_instruction_offset: i64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let instructions_sysvar_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    check_program_account(mint_info.owner)?;
+    let mint_data = &mut mint_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(mint_data)?;
+
+    if let Ok(pausable_config) = mint.get_extension::<PausableConfig>() {
+        pausable_config.check_not_paused()?;
+    }
+
+    let auditor_encryption_pubkey = mint
+        .get_extension::<ConfidentialTransferMint>()?
+        .auditor_encryption_pubkey;
+
+    let previous_instruction =
+        get_instruction_relative(proof_instruction_offset, instructions_sysvar_info)?;
+    let proof_data = decode_proof_instruction::<TransferData>(
+        ProofInstruction::VerifyTransfer,
+        &previous_instruction,
+    )?;
+
+    if proof_data.transfer_pubkeys.auditor_pubkey != auditor_encryption_pubkey {
+        return Err(TokenError::ConfidentialTransferElGamalPubkeyMismatch.into());
+    }
+
+    let source_ciphertext_lo = EncryptedBalance::from((
+        proof_data.ciphertext_lo.commitment,
+        proof_data.ciphertext_lo.source_handle,
+    ));
+    let source_ciphertext_hi = EncryptedBalance::from((
+        proof_data.ciphertext_hi.commitment,
+        proof_data.ciphertext_hi.source_handle,
+    ));
+
+    process_source_for_transfer(
+        program_id,
+        token_account_info,
+        mint_info,
+        authority_info,
+        account_info_iter.as_slice(),
+        &proof_data.transfer_pubkeys.source_pubkey,
+        &source_ciphertext_lo,
+        &source_ciphertext_hi,
+        new_source_decryptable_available_balance,
+    )?;
+
+    let auditor_ciphertext_lo = EncryptedBalance::from((
+        proof_data.ciphertext_lo.commitment,
+        proof_data.ciphertext_lo.auditor_handle,
+    ));
+    let auditor_ciphertext_hi = EncryptedBalance::from((
+        proof_data.ciphertext_hi.commitment,
+        proof_data.ciphertext_hi.auditor_handle,
+    ));
+
+    let confidential_mint_burn = mint.get_extension_mut::<ConfidentialMintBurn>()?;
+    confidential_mint_burn.confidential_supply = ops::subtract_with_lo_hi(
+        &confidential_mint_burn.confidential_supply,
+        &auditor_ciphertext_lo,
+        &auditor_ciphertext_hi,
+    )
+    .ok_or(ProgramError::InvalidInstructionData)?;
+
+    Ok(())
+}
+
 #[allow(dead_code)]
 pub(crate) fn process_instruction(
     program_id: &Pubkey,
@@ -1285,5 +1495,38 @@ pub(crate) fn process_instruction(
                 Err(ProgramError::InvalidInstructionData)
             }
         }
+        ConfidentialTransferInstruction::InitializeConfidentialMintBurn => {
+            msg!("ConfidentialTransferInstruction::InitializeConfidentialMintBurn");
+            process_initialize_confidential_mint_burn(accounts)
+        }
+        ConfidentialTransferInstruction::ConfidentialMint => {
+            msg!("ConfidentialTransferInstruction::ConfidentialMint");
+            #[cfg(feature = "zk-ops")]
+            {
+                let data = decode_instruction_data::<ConfidentialMintInstructionData>(input)?;
+                process_confidential_mint(
+                    program_id,
+                    accounts,
+                    data.proof_instruction_offset as i64,
+                )
+            }
+            #[cfg(not(feature = "zk-ops"))]
+            Err(ProgramError::InvalidInstructionData)
+        }
+        ConfidentialTransferInstruction::ConfidentialBurn => {
+            msg!("ConfidentialTransferInstruction::ConfidentialBurn");
+            #[cfg(feature = "zk-ops")]
+            {
+                let data = decode_instruction_data::<ConfidentialBurnInstructionData>(input)?;
+                process_confidential_burn(
+                    program_id,
+                    accounts,
+                    data.new_source_decryptable_available_balance,
+                    data.proof_instruction_offset as i64,
+                )
+            }
+            #[cfg(not(feature = "zk-ops"))]
+            Err(ProgramError::InvalidInstructionData)
+        }
     }
 }
@@ -7,6 +7,7 @@ use {
                 instruction::{InitializeInstructionData, InterestBearingMintInstruction},
                 BasisPoints, InterestBearingConfig,
             },
+            scaled_ui_amount::ScaledUiAmountConfig,
             StateWithExtensionsMut,
         },
         instruction::{decode_instruction_data, decode_instruction_type},
@@ -35,6 +36,10 @@ fn process_initialize(
     let mut mint_data = mint_account_info.data.borrow_mut();
     let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data)?;
 
+    if mint.get_extension::<ScaledUiAmountConfig>().is_ok() {
+        return Err(TokenError::InvalidExtensionCombination.into());
+    }
+
     let clock = Clock::get()?;
     let extension = mint.init_extension::<InterestBearingConfig>(true)?;
     extension.rate_authority = *rate_authority;
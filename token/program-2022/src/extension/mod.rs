@@ -4,14 +4,22 @@ use {
     crate::{
         error::TokenError,
         extension::{
-            confidential_transfer::{ConfidentialTransferAccount, ConfidentialTransferMint},
+            confidential_transfer::{
+                ConfidentialMintBurn, ConfidentialTransferAccount, ConfidentialTransferMint,
+            },
+            cpi_guard::CpiGuard,
             default_account_state::DefaultAccountState,
             immutable_owner::ImmutableOwner,
             interest_bearing_mint::InterestBearingConfig,
             memo_transfer::MemoTransfer,
             mint_close_authority::MintCloseAuthority,
             non_transferable::NonTransferable,
+            pausable::{PausableAccount, PausableConfig},
+            permanent_delegate::PermanentDelegate,
+            scaled_ui_amount::ScaledUiAmountConfig,
+            token_group::{TokenGroup, TokenGroupMember},
             transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+            transfer_hook::{TransferHook, TransferHookAccount},
         },
         pod::*,
         state::{Account, Mint, Multisig},
@@ -19,6 +27,7 @@ use {
     bytemuck::{Pod, Zeroable},
     num_enum::{IntoPrimitive, TryFromPrimitive},
     solana_program::{
+        account_info::AccountInfo,
         program_error::ProgramError,
         program_pack::{IsInitialized, Pack},
     },
@@ -33,6 +42,8 @@ use serde::{Deserialize, Serialize};
 
 /// Confidential Transfer extension
 pub mod confidential_transfer;
+/// CPI Guard extension
+pub mod cpi_guard;
 /// Default Account State extension
 pub mod default_account_state;
 /// Immutable Owner extension
@@ -45,10 +56,22 @@ pub mod memo_transfer;
 pub mod mint_close_authority;
 /// Non Transferable extension
 pub mod non_transferable;
+/// Pausable extension
+pub mod pausable;
+/// Permanent Delegate extension
+pub mod permanent_delegate;
 /// Utility to reallocate token accounts
 pub mod reallocate;
+/// Scaled UI Amount extension
+pub mod scaled_ui_amount;
+/// Token Group extension
+pub mod token_group;
+/// Token Metadata extension
+pub mod token_metadata;
 /// Transfer Fee extension
 pub mod transfer_fee;
+/// Transfer Hook extension
+pub mod transfer_hook;
 
 /// Length in TLV structure
 #[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
@@ -87,12 +110,13 @@ struct TlvIndices {
     pub length_start: usize,
     pub value_start: usize,
 }
-fn get_extension_indices<V: Extension>(
+fn get_extension_indices(
     tlv_data: &[u8],
+    v_extension_type: ExtensionType,
     init: bool,
 ) -> Result<TlvIndices, ProgramError> {
     let mut start_index = 0;
-    let v_account_type = V::TYPE.get_account_type();
+    let v_account_type = v_extension_type.get_account_type();
     while start_index < tlv_data.len() {
         let tlv_indices = get_tlv_indices(start_index);
         if tlv_data.len() < tlv_indices.value_start {
@@ -108,7 +132,7 @@ fn get_extension_indices<V: Extension>(
             } else {
                 start_index = tlv_indices.length_start;
             }
-        } else if extension_type == V::TYPE {
+        } else if extension_type == v_extension_type {
             // found an instance of the extension that we're initializing, return!
             return Ok(tlv_indices);
         } else if v_account_type != account_type {
@@ -149,6 +173,33 @@ fn get_extension_types(tlv_data: &[u8]) -> Result<Vec<ExtensionType>, ProgramErr
     Ok(extension_types)
 }
 
+/// Get the number of bytes used by TLV entries, ignoring any uninitialized
+/// space at the end of the buffer
+fn get_tlv_data_used_len(tlv_data: &[u8]) -> Result<usize, ProgramError> {
+    let mut start_index = 0;
+    while start_index < tlv_data.len() {
+        let tlv_indices = get_tlv_indices(start_index);
+        if tlv_data.len() < tlv_indices.value_start {
+            return Ok(start_index);
+        }
+        let extension_type =
+            ExtensionType::try_from(&tlv_data[tlv_indices.type_start..tlv_indices.length_start])?;
+        if extension_type == ExtensionType::Uninitialized {
+            return Ok(start_index);
+        } else {
+            let length = pod_from_bytes::<Length>(
+                &tlv_data[tlv_indices.length_start..tlv_indices.value_start],
+            )?;
+            let value_end_index = tlv_indices.value_start.saturating_add(usize::from(*length));
+            if value_end_index > tlv_data.len() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            start_index = value_end_index;
+        }
+    }
+    Ok(start_index)
+}
+
 fn get_first_extension_type(tlv_data: &[u8]) -> Result<Option<ExtensionType>, ProgramError> {
     if tlv_data.is_empty() {
         Ok(None)
@@ -240,13 +291,39 @@ fn get_extension<S: BaseState, V: Extension>(tlv_data: &[u8]) -> Result<&V, Prog
         type_start: _,
         length_start,
         value_start,
-    } = get_extension_indices::<V>(tlv_data, false)?;
+    } = get_extension_indices(tlv_data, V::TYPE, false)?;
     // get_extension_indices has checked that tlv_data is long enough to include these indices
     let length = pod_from_bytes::<Length>(&tlv_data[length_start..value_start])?;
     let value_end = value_start.saturating_add(usize::from(*length));
     pod_from_bytes::<V>(&tlv_data[value_start..value_end])
 }
 
+fn get_extension_bytes<S: BaseState>(
+    tlv_data: &[u8],
+    extension_type: ExtensionType,
+) -> Result<&[u8], ProgramError> {
+    if extension_type.get_account_type() != S::ACCOUNT_TYPE {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let TlvIndices {
+        type_start: _,
+        length_start,
+        value_start,
+    } = get_extension_indices(tlv_data, extension_type, false)?;
+    // get_extension_indices has checked that tlv_data is long enough to include these indices
+    let length = pod_from_bytes::<Length>(&tlv_data[length_start..value_start])?;
+    let value_end = value_start.saturating_add(usize::from(*length));
+    tlv_data
+        .get(value_start..value_end)
+        .ok_or(ProgramError::InvalidAccountData)
+}
+
+fn get_variable_len_extension<S: BaseState, V: VariableLenPack>(
+    tlv_data: &[u8],
+) -> Result<V, ProgramError> {
+    get_extension_bytes::<S>(tlv_data, V::TYPE).and_then(V::unpack_from_slice)
+}
+
 /// Encapsulates owned immutable base state data (mint or account) with possible extensions
 #[derive(Debug, PartialEq)]
 pub struct StateWithExtensionsOwned<S: BaseState> {
@@ -283,6 +360,11 @@ impl<S: BaseState> StateWithExtensionsOwned<S> {
         get_extension::<S, V>(&self.tlv_data)
     }
 
+    /// Unpack a variable-length portion of the TLV data as the desired type
+    pub fn get_variable_len_extension<V: VariableLenPack>(&self) -> Result<V, ProgramError> {
+        get_variable_len_extension::<S, V>(&self.tlv_data)
+    }
+
     /// Iterates through the TLV entries, returning only the types
     pub fn get_extension_types(&self) -> Result<Vec<ExtensionType>, ProgramError> {
         get_extension_types(&self.tlv_data)
@@ -327,6 +409,11 @@ impl<'data, S: BaseState> StateWithExtensions<'data, S> {
         get_extension::<S, V>(self.tlv_data)
     }
 
+    /// Unpack a variable-length portion of the TLV data as the desired type
+    pub fn get_variable_len_extension<V: VariableLenPack>(&self) -> Result<V, ProgramError> {
+        get_variable_len_extension::<S, V>(self.tlv_data)
+    }
+
     /// Iterates through the TLV entries, returning only the types
     pub fn get_extension_types(&self) -> Result<Vec<ExtensionType>, ProgramError> {
         get_extension_types(self.tlv_data)
@@ -425,7 +512,7 @@ impl<'data, S: BaseState> StateWithExtensionsMut<'data, S> {
             type_start,
             length_start,
             value_start,
-        } = get_extension_indices::<V>(self.tlv_data, false)?;
+        } = get_extension_indices(self.tlv_data, V::TYPE, false)?;
 
         if self.tlv_data[type_start..].len() < V::TYPE.get_tlv_len() {
             return Err(ProgramError::InvalidAccountData);
@@ -444,7 +531,7 @@ impl<'data, S: BaseState> StateWithExtensionsMut<'data, S> {
             type_start,
             length_start,
             value_start,
-        } = get_extension_indices::<V>(self.tlv_data, false)?;
+        } = get_extension_indices(self.tlv_data, V::TYPE, false)?;
 
         if self.tlv_data[type_start..].len() < V::TYPE.get_tlv_len() {
             return Err(ProgramError::InvalidAccountData);
@@ -474,7 +561,7 @@ impl<'data, S: BaseState> StateWithExtensionsMut<'data, S> {
             type_start,
             length_start,
             value_start,
-        } = get_extension_indices::<V>(self.tlv_data, true)?;
+        } = get_extension_indices(self.tlv_data, V::TYPE, true)?;
 
         if self.tlv_data[type_start..].len() < V::TYPE.get_tlv_len() {
             return Err(ProgramError::InvalidAccountData);
@@ -522,6 +609,9 @@ impl<'data, S: BaseState> StateWithExtensionsMut<'data, S> {
             // ConfidentialTransfers are currently opt-in only, so this is a no-op for extra safety
             // on InitializeAccount
             ExtensionType::ConfidentialTransferAccount => Ok(()),
+            ExtensionType::TransferHookAccount => {
+                self.init_extension::<TransferHookAccount>(true).map(|_| ())
+            }
             #[cfg(test)]
             ExtensionType::AccountPaddingTest => {
                 self.init_extension::<AccountPaddingTest>(true).map(|_| ())
@@ -557,6 +647,193 @@ impl<'data, S: BaseState> StateWithExtensionsMut<'data, S> {
     }
 }
 
+/// Position of a variable-length extension in the TLV data, before and after
+/// it gets resized
+struct VariableLenLayout {
+    /// Required account length once the extension has its new size
+    new_account_len: usize,
+    /// Start of the extension's TLV entry, relative to the start of the TLV data
+    entry_start: usize,
+    /// Length of the extension's current TLV entry, 0 if not present
+    old_entry_len: usize,
+    /// Number of bytes used by all TLV entries currently in the account
+    used_tlv_len: usize,
+}
+
+fn get_variable_len_layout<S: BaseState>(
+    input: &[u8],
+    extension_type: ExtensionType,
+    new_value_len: usize,
+    overwrite: bool,
+) -> Result<VariableLenLayout, ProgramError> {
+    if extension_type.get_account_type() != S::ACCOUNT_TYPE {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let state = StateWithExtensions::<S>::unpack(input)?;
+    let used_tlv_len = get_tlv_data_used_len(state.tlv_data)?;
+    let (entry_start, old_entry_len) =
+        if get_extension_bytes::<S>(state.tlv_data, extension_type).is_ok() {
+            if !overwrite {
+                return Err(TokenError::ExtensionAlreadyInitialized.into());
+            }
+            let TlvIndices {
+                type_start,
+                length_start,
+                value_start,
+            } = get_extension_indices(state.tlv_data, extension_type, false)?;
+            let length = pod_from_bytes::<Length>(&state.tlv_data[length_start..value_start])?;
+            (
+                type_start,
+                value_start
+                    .saturating_add(usize::from(*length))
+                    .saturating_sub(type_start),
+            )
+        } else {
+            (used_tlv_len, 0)
+        };
+    let new_entry_len = get_tlv_indices(0).value_start.saturating_add(new_value_len);
+    let new_tlv_len = used_tlv_len
+        .saturating_sub(old_entry_len)
+        .saturating_add(new_entry_len);
+    let mut new_account_len = BASE_ACCOUNT_LENGTH
+        .saturating_add(size_of::<AccountType>())
+        .saturating_add(new_tlv_len);
+    if new_account_len == Multisig::LEN {
+        new_account_len = new_account_len.saturating_add(size_of::<ExtensionType>());
+    }
+    Ok(VariableLenLayout {
+        new_account_len,
+        entry_start,
+        old_entry_len,
+        used_tlv_len,
+    })
+}
+
+/// Get the required account data length if a variable-length extension were
+/// written into the given account data, replacing any existing instance
+pub fn get_account_len_with_variable_len_extension<S: BaseState, V: VariableLenPack>(
+    input: &[u8],
+    new_extension: &V,
+) -> Result<usize, ProgramError> {
+    let new_value_len = new_extension.get_packed_len()?;
+    get_variable_len_layout::<S>(input, V::TYPE, new_value_len, true)
+        .map(|layout| layout.new_account_len)
+}
+
+/// Get the required account data length if a fixed-length extension were
+/// added to the given, already initialized, account data
+pub fn get_account_len_with_fixed_len_extension<S: BaseState, V: Extension>(
+    input: &[u8],
+) -> Result<usize, ProgramError> {
+    get_variable_len_layout::<S>(input, V::TYPE, pod_get_packed_len::<V>(), true)
+        .map(|layout| layout.new_account_len)
+}
+
+/// Packs a variable-length extension into the account, reallocating the
+/// account to exactly fit the new data, and moving any later TLV entries.
+///
+/// The account must already hold enough lamports to remain rent-exempt at its
+/// new size. If the extension is already present, it is only overwritten if
+/// `overwrite` is set.
+pub fn alloc_and_serialize_variable_len_extension<S: BaseState, V: VariableLenPack>(
+    account_info: &AccountInfo,
+    new_extension: &V,
+    overwrite: bool,
+) -> Result<(), ProgramError> {
+    let new_value_len = new_extension.get_packed_len()?;
+    alloc_and_write_extension::<S, _>(account_info, V::TYPE, new_value_len, overwrite, |dst| {
+        new_extension.pack_into_slice(dst)
+    })
+}
+
+/// Packs a fixed-length extension into an already initialized account,
+/// reallocating the account to fit it.
+///
+/// The account must already hold enough lamports to remain rent-exempt at its
+/// new size. If the extension is already present, it is only overwritten if
+/// `overwrite` is set.
+pub fn alloc_and_serialize_fixed_len_extension<S: BaseState, V: Extension>(
+    account_info: &AccountInfo,
+    new_extension: &V,
+    overwrite: bool,
+) -> Result<(), ProgramError> {
+    alloc_and_write_extension::<S, _>(
+        account_info,
+        V::TYPE,
+        pod_get_packed_len::<V>(),
+        overwrite,
+        |dst| {
+            dst.copy_from_slice(bytemuck::bytes_of(new_extension));
+            Ok(())
+        },
+    )
+}
+
+/// Writes an extension entry of `new_value_len` bytes into the account using
+/// `write`, reallocating the account to exactly fit the new data, and moving
+/// any later TLV entries
+fn alloc_and_write_extension<S: BaseState, F: FnOnce(&mut [u8]) -> Result<(), ProgramError>>(
+    account_info: &AccountInfo,
+    extension_type: ExtensionType,
+    new_value_len: usize,
+    overwrite: bool,
+    write: F,
+) -> Result<(), ProgramError> {
+    let previous_account_len = account_info.data_len();
+    let VariableLenLayout {
+        new_account_len,
+        entry_start,
+        old_entry_len,
+        used_tlv_len,
+    } = get_variable_len_layout::<S>(
+        &account_info.try_borrow_data()?,
+        extension_type,
+        new_value_len,
+        overwrite,
+    )?;
+
+    if new_account_len > previous_account_len {
+        account_info.realloc(new_account_len, true)?;
+    }
+    {
+        let mut data = account_info.try_borrow_mut_data()?;
+        let tlv_start = BASE_ACCOUNT_LENGTH.saturating_add(size_of::<AccountType>());
+        let tlv_data = &mut data[tlv_start..];
+
+        // move everything after the extension to its new position
+        let old_entry_end = entry_start.saturating_add(old_entry_len);
+        let TlvIndices {
+            type_start: _,
+            length_start,
+            value_start,
+        } = get_tlv_indices(entry_start);
+        let new_entry_end = value_start.saturating_add(new_value_len);
+        tlv_data.copy_within(old_entry_end..used_tlv_len, new_entry_end);
+
+        // write the extension
+        let extension_type_array: [u8; 2] = extension_type.into();
+        tlv_data[entry_start..length_start].copy_from_slice(&extension_type_array);
+        *pod_from_bytes_mut::<Length>(&mut tlv_data[length_start..value_start])? =
+            Length::try_from(new_value_len)?;
+        write(&mut tlv_data[value_start..new_entry_end])?;
+
+        // clear out any stale bytes left behind by a shrinking extension
+        let new_used_tlv_len = used_tlv_len
+            .saturating_sub(old_entry_len)
+            .saturating_add(new_entry_end.saturating_sub(entry_start));
+        for byte in tlv_data[new_used_tlv_len..].iter_mut() {
+            *byte = 0;
+        }
+    }
+    if new_account_len < previous_account_len {
+        account_info.realloc(new_account_len, false)?;
+    }
+
+    // a base-only account needs its account type set to hold extensions
+    let mut data = account_info.try_borrow_mut_data()?;
+    set_account_type::<S>(&mut data)
+}
+
 /// If AccountType is uninitialized, set it to the BaseState's ACCOUNT_TYPE;
 /// if AccountType is already set, check is set correctly for BaseState
 /// This method assumes that the `base_data` has already been packed with data of the desired type.
@@ -629,6 +906,28 @@ pub enum ExtensionType {
     NonTransferable,
     /// Tokens accrue interest over time,
     InterestBearingConfig,
+    /// Includes an optional permanent delegate
+    PermanentDelegate,
+    /// Mint requires a CPI to a program implementing the "transfer hook" interface
+    TransferHook,
+    /// Indicates that the tokens in this account belong to a mint with a transfer hook
+    TransferHookAccount,
+    /// Variable-length token metadata stored in the mint
+    TokenMetadata,
+    /// Indicates that the account is locked against certain operations via CPI
+    CpiGuard,
+    /// Mint supply is encrypted, and tokens are minted and burned confidentially
+    ConfidentialMintBurn,
+    /// Tokens have a UI amount scaled by an issuer-set multiplier
+    ScaledUiAmount,
+    /// Tokens from this mint can be paused, blocking minting, burning, and transferring
+    Pausable,
+    /// Indicates that the tokens in this account belong to a pausable mint
+    PausableAccount,
+    /// Group of mints, with a size and max size
+    TokenGroup,
+    /// Member of a group of mints
+    TokenGroupMember,
     /// Padding extension used to make an account exactly Multisig::LEN, used for testing
     #[cfg(test)]
     AccountPaddingTest = u16::MAX - 1,
@@ -669,6 +968,18 @@ impl ExtensionType {
             ExtensionType::MemoTransfer => pod_get_packed_len::<MemoTransfer>(),
             ExtensionType::NonTransferable => pod_get_packed_len::<NonTransferable>(),
             ExtensionType::InterestBearingConfig => pod_get_packed_len::<InterestBearingConfig>(),
+            ExtensionType::PermanentDelegate => pod_get_packed_len::<PermanentDelegate>(),
+            ExtensionType::TransferHook => pod_get_packed_len::<TransferHook>(),
+            ExtensionType::TransferHookAccount => pod_get_packed_len::<TransferHookAccount>(),
+            // variable-length, space is allocated when the extension is written
+            ExtensionType::TokenMetadata => 0,
+            ExtensionType::CpiGuard => pod_get_packed_len::<CpiGuard>(),
+            ExtensionType::ConfidentialMintBurn => pod_get_packed_len::<ConfidentialMintBurn>(),
+            ExtensionType::ScaledUiAmount => pod_get_packed_len::<ScaledUiAmountConfig>(),
+            ExtensionType::Pausable => pod_get_packed_len::<PausableConfig>(),
+            ExtensionType::PausableAccount => pod_get_packed_len::<PausableAccount>(),
+            ExtensionType::TokenGroup => pod_get_packed_len::<TokenGroup>(),
+            ExtensionType::TokenGroupMember => pod_get_packed_len::<TokenGroupMember>(),
             #[cfg(test)]
             ExtensionType::AccountPaddingTest => pod_get_packed_len::<AccountPaddingTest>(),
             #[cfg(test)]
@@ -725,11 +1036,22 @@ impl ExtensionType {
             | ExtensionType::ConfidentialTransferMint
             | ExtensionType::DefaultAccountState
             | ExtensionType::NonTransferable
-            | ExtensionType::InterestBearingConfig => AccountType::Mint,
+            | ExtensionType::InterestBearingConfig
+            | ExtensionType::PermanentDelegate
+            | ExtensionType::TransferHook
+            | ExtensionType::TokenMetadata
+            | ExtensionType::ConfidentialMintBurn
+            | ExtensionType::ScaledUiAmount
+            | ExtensionType::Pausable
+            | ExtensionType::TokenGroup
+            | ExtensionType::TokenGroupMember => AccountType::Mint,
             ExtensionType::ImmutableOwner
             | ExtensionType::TransferFeeAmount
             | ExtensionType::ConfidentialTransferAccount
-            | ExtensionType::MemoTransfer => AccountType::Account,
+            | ExtensionType::MemoTransfer
+            | ExtensionType::TransferHookAccount
+            | ExtensionType::CpiGuard
+            | ExtensionType::PausableAccount => AccountType::Account,
             #[cfg(test)]
             ExtensionType::AccountPaddingTest => AccountType::Account,
             #[cfg(test)]
@@ -742,11 +1064,16 @@ impl ExtensionType {
     pub fn get_required_init_account_extensions(mint_extension_types: &[Self]) -> Vec<Self> {
         let mut account_extension_types = vec![];
         for extension_type in mint_extension_types {
-            #[allow(clippy::single_match)]
             match extension_type {
                 ExtensionType::TransferFeeConfig => {
                     account_extension_types.push(ExtensionType::TransferFeeAmount);
                 }
+                ExtensionType::TransferHook => {
+                    account_extension_types.push(ExtensionType::TransferHookAccount);
+                }
+                ExtensionType::Pausable => {
+                    account_extension_types.push(ExtensionType::PausableAccount);
+                }
                 #[cfg(test)]
                 ExtensionType::MintPaddingTest => {
                     account_extension_types.push(ExtensionType::AccountPaddingTest);
@@ -777,6 +1104,19 @@ pub trait Extension: Pod + Default {
     const TYPE: ExtensionType;
 }
 
+/// Trait to be implemented by extension states with a variable length, which
+/// are packed and unpacked explicitly rather than used in place
+pub trait VariableLenPack: Sized {
+    /// Associated extension type enum, checked at the start of TLV entries
+    const TYPE: ExtensionType;
+    /// Packs the extension into a buffer of exactly `get_packed_len()` bytes
+    fn pack_into_slice(&self, dst: &mut [u8]) -> Result<(), ProgramError>;
+    /// Unpacks the extension from its full TLV value
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError>;
+    /// Length of the packed extension
+    fn get_packed_len(&self) -> Result<usize, ProgramError>;
+}
+
 /// Padding a mint account to be exactly Multisig::LEN.
 /// We need to pad 185 bytes, since Multisig::LEN = 355, Account::LEN = 165,
 /// size_of AccountType = 1, size_of ExtensionType = 2, size_of Length = 2.
@@ -1483,6 +1823,21 @@ mod test {
             vec![ExtensionType::TransferFeeAmount]
         );
 
+        // Transfer hook mints require the account extension for the flag
+        let mint_extensions = vec![ExtensionType::TransferHook];
+        assert_eq!(
+            ExtensionType::get_required_init_account_extensions(&mint_extensions),
+            vec![ExtensionType::TransferHookAccount]
+        );
+
+        // Pausable mints require the account extension so that transfers must
+        // include the mint
+        let mint_extensions = vec![ExtensionType::Pausable];
+        assert_eq!(
+            ExtensionType::get_required_init_account_extensions(&mint_extensions),
+            vec![ExtensionType::PausableAccount]
+        );
+
         // Some mint extensions both with required account extensions
         let mint_extensions = vec![
             ExtensionType::TransferFeeConfig,
@@ -1576,6 +1931,47 @@ mod test {
         assert_eq!(state.get_extension_types().unwrap(), vec![]);
     }
 
+    #[test]
+    fn variable_len_extension_account_len() {
+        let metadata = token_metadata::TokenMetadata {
+            name: "Token".to_string(),
+            ..Default::default()
+        };
+        let metadata_tlv_len = get_tlv_indices(0)
+            .value_start
+            .saturating_add(metadata.get_packed_len().unwrap());
+
+        // base mint without extensions
+        let mut buffer = vec![0; Mint::LEN];
+        Mint::pack(TEST_MINT, &mut buffer).unwrap();
+        assert_eq!(
+            get_account_len_with_variable_len_extension::<Mint, _>(&buffer, &metadata).unwrap(),
+            BASE_ACCOUNT_LENGTH + size_of::<AccountType>() + metadata_tlv_len
+        );
+
+        // mint with a fixed-size extension already
+        let mint_size =
+            ExtensionType::get_account_len::<Mint>(&[ExtensionType::MintCloseAuthority]);
+        let mut buffer = vec![0; mint_size];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut buffer).unwrap();
+        state.base = TEST_MINT;
+        state.pack_base();
+        state.init_account_type().unwrap();
+        state.init_extension::<MintCloseAuthority>(true).unwrap();
+        assert_eq!(
+            get_account_len_with_variable_len_extension::<Mint, _>(&buffer, &metadata).unwrap(),
+            mint_size + metadata_tlv_len
+        );
+
+        // only mint extensions are allowed
+        let mut buffer = vec![0; Account::LEN];
+        Account::pack(TEST_ACCOUNT, &mut buffer).unwrap();
+        assert_eq!(
+            get_account_len_with_variable_len_extension::<Account, _>(&buffer, &metadata),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
     #[test]
     fn test_extension_with_no_data() {
         let account_size =
@@ -0,0 +1,46 @@
+use {
+    crate::{
+        error::TokenError,
+        extension::{Extension, ExtensionType},
+        pod::{OptionalNonZeroPubkey, PodBool},
+    },
+    bytemuck::{Pod, Zeroable},
+    solana_program::entrypoint::ProgramResult,
+};
+
+/// Pausable extension instructions
+pub mod instruction;
+
+/// Pausable extension processor
+pub mod processor;
+
+/// Pausable extension data for mints
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct PausableConfig {
+    /// Authority that can pause or resume activity on the mint
+    pub authority: OptionalNonZeroPubkey,
+    /// Whether minting, burning, and transferring are paused
+    pub paused: PodBool,
+}
+impl PausableConfig {
+    /// Check that the mint is not paused
+    pub fn check_not_paused(&self) -> ProgramResult {
+        if bool::from(&self.paused) {
+            Err(TokenError::MintPaused.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+impl Extension for PausableConfig {
+    const TYPE: ExtensionType = ExtensionType::Pausable;
+}
+
+/// Indicates that the tokens from this account belong to a pausable mint
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct PausableAccount;
+impl Extension for PausableAccount {
+    const TYPE: ExtensionType = ExtensionType::PausableAccount;
+}
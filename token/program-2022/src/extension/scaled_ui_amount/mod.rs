@@ -0,0 +1,175 @@
+use {
+    crate::{
+        error::TokenError,
+        extension::{Extension, ExtensionType},
+        pod::{OptionalNonZeroPubkey, PodF64, PodI64},
+    },
+    bytemuck::{Pod, Zeroable},
+    solana_program::program_error::ProgramError,
+};
+
+/// Scaled UI amount extension instructions
+pub mod instruction;
+
+/// Scaled UI amount extension processor
+pub mod processor;
+
+/// UnixTimestamp expressed with an alignment-independent type
+pub type UnixTimestamp = PodI64;
+
+/// Scaled UI amount extension data for mints
+///
+/// The UI amount of a token is its raw amount multiplied by `multiplier`, which
+/// allows the issuer to rebase or split a token without touching any account.
+///
+/// To support scheduling a change, the config also holds a `new_multiplier`
+/// that replaces `multiplier` once `new_multiplier_effective_timestamp` has
+/// passed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ScaledUiAmountConfig {
+    /// Authority that can set the multiplier and authority
+    pub authority: OptionalNonZeroPubkey,
+    /// Multiplier in effect until `new_multiplier_effective_timestamp`
+    pub multiplier: PodF64,
+    /// Timestamp at which `new_multiplier` takes effect
+    pub new_multiplier_effective_timestamp: UnixTimestamp,
+    /// Multiplier in effect from `new_multiplier_effective_timestamp`
+    pub new_multiplier: PodF64,
+}
+impl ScaledUiAmountConfig {
+    /// Get the multiplier in effect at the given timestamp
+    pub fn effective_multiplier(&self, unix_timestamp: i64) -> f64 {
+        if unix_timestamp >= i64::from(self.new_multiplier_effective_timestamp) {
+            self.new_multiplier.into()
+        } else {
+            self.multiplier.into()
+        }
+    }
+
+    fn total_scale(&self, decimals: u8, unix_timestamp: i64) -> f64 {
+        self.effective_multiplier(unix_timestamp) / 10_f64.powi(decimals as i32)
+    }
+
+    /// Convert a raw amount to its UI representation using the given decimals field
+    /// Excess zeroes or unneeded decimal point are trimmed.
+    pub fn amount_to_ui_amount(
+        &self,
+        amount: u64,
+        decimals: u8,
+        unix_timestamp: i64,
+    ) -> Option<String> {
+        let scaled_amount = (amount as f64) * self.total_scale(decimals, unix_timestamp);
+        if scaled_amount.is_finite() {
+            Some(scaled_amount.to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Try to convert a UI represenation of a token amount to its raw amount using the given decimals
+    /// field
+    pub fn try_ui_amount_into_amount(
+        &self,
+        ui_amount: &str,
+        decimals: u8,
+        unix_timestamp: i64,
+    ) -> Result<u64, ProgramError> {
+        let scaled_amount = ui_amount
+            .parse::<f64>()
+            .map_err(|_| ProgramError::InvalidArgument)?;
+        let amount = scaled_amount / self.total_scale(decimals, unix_timestamp);
+        if amount > (u64::MAX as f64) || amount < (u64::MIN as f64) || amount.is_nan() {
+            Err(ProgramError::InvalidArgument)
+        } else {
+            Ok(amount.round() as u64)
+        }
+    }
+}
+impl Extension for ScaledUiAmountConfig {
+    const TYPE: ExtensionType = ExtensionType::ScaledUiAmount;
+}
+
+/// Check that a multiplier can be used to scale amounts
+pub fn check_multiplier(multiplier: f64) -> Result<(), ProgramError> {
+    if multiplier.is_finite() && multiplier > 0. {
+        Ok(())
+    } else {
+        Err(TokenError::InvalidUiAmountMultiplier.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(multiplier: f64, new_multiplier: f64, timestamp: i64) -> ScaledUiAmountConfig {
+        ScaledUiAmountConfig {
+            authority: OptionalNonZeroPubkey::default(),
+            multiplier: multiplier.into(),
+            new_multiplier_effective_timestamp: timestamp.into(),
+            new_multiplier: new_multiplier.into(),
+        }
+    }
+
+    #[test]
+    fn specific_amount_to_ui_amount() {
+        let config = config(2., 2., 0);
+        assert_eq!(config.amount_to_ui_amount(1, 0, 0).unwrap(), "2");
+        assert_eq!(config.amount_to_ui_amount(1, 1, 0).unwrap(), "0.2");
+        assert_eq!(
+            config.amount_to_ui_amount(u64::MAX, 0, 0).unwrap(),
+            "36893488147419103000"
+        );
+
+        let config = ScaledUiAmountConfig {
+            multiplier: 0.5.into(),
+            ..config
+        };
+        // the new multiplier is already in effect
+        assert_eq!(config.amount_to_ui_amount(5, 1, 0).unwrap(), "1");
+    }
+
+    #[test]
+    fn scheduled_multiplier() {
+        let config = config(1., 3., 100);
+        assert_eq!(config.effective_multiplier(99), 1.);
+        assert_eq!(config.effective_multiplier(100), 3.);
+        assert_eq!(config.amount_to_ui_amount(10, 0, 0).unwrap(), "10");
+        assert_eq!(config.amount_to_ui_amount(10, 0, 100).unwrap(), "30");
+        assert_eq!(config.try_ui_amount_into_amount("30", 0, 100).unwrap(), 10);
+        assert_eq!(config.try_ui_amount_into_amount("30", 0, 99).unwrap(), 30);
+    }
+
+    #[test]
+    fn specific_ui_amount_to_amount() {
+        let config = config(1.5, 1.5, 0);
+        assert_eq!(config.try_ui_amount_into_amount("3", 0, 0).unwrap(), 2);
+        assert_eq!(config.try_ui_amount_into_amount("0.3", 1, 0).unwrap(), 2);
+        assert_eq!(config.try_ui_amount_into_amount("1", 0, 0).unwrap(), 1);
+        assert_eq!(
+            config.try_ui_amount_into_amount("-1", 0, 0).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+        assert_eq!(
+            config.try_ui_amount_into_amount("bad", 0, 0).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+        assert_eq!(
+            config.try_ui_amount_into_amount("1e30", 0, 0).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn multiplier_validation() {
+        assert!(check_multiplier(1.).is_ok());
+        assert!(check_multiplier(0.001).is_ok());
+        for multiplier in [0., -1., f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                check_multiplier(multiplier).unwrap_err(),
+                TokenError::InvalidUiAmountMultiplier.into()
+            );
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use {
+    crate::{
+        check_program_account,
+        error::TokenError,
+        extension::{
+            interest_bearing_mint::InterestBearingConfig,
+            scaled_ui_amount::{
+                check_multiplier,
+                instruction::{
+                    InitializeInstructionData, ScaledUiAmountMintInstruction,
+                    UpdateMultiplierInstructionData,
+                },
+                ScaledUiAmountConfig,
+            },
+            StateWithExtensionsMut,
+        },
+        instruction::{decode_instruction_data, decode_instruction_type},
+        pod::{OptionalNonZeroPubkey, PodF64},
+        processor::Processor,
+        state::Mint,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+fn process_initialize(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    authority: &OptionalNonZeroPubkey,
+    multiplier: &PodF64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data)?;
+
+    if mint.get_extension::<InterestBearingConfig>().is_ok() {
+        return Err(TokenError::InvalidExtensionCombination.into());
+    }
+    check_multiplier((*multiplier).into())?;
+
+    let extension = mint.init_extension::<ScaledUiAmountConfig>(true)?;
+    extension.authority = *authority;
+    extension.multiplier = *multiplier;
+    extension.new_multiplier_effective_timestamp = 0.into();
+    extension.new_multiplier = *multiplier;
+    Ok(())
+}
+
+fn process_update_multiplier(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_multiplier: &PodF64,
+    effective_timestamp: i64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_account_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let owner_info_data_len = owner_info.data_len();
+
+    let mut mint_data = mint_account_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(&mut mint_data)?;
+    let extension = mint.get_extension_mut::<ScaledUiAmountConfig>()?;
+    let authority =
+        Option::<Pubkey>::from(extension.authority).ok_or(TokenError::NoAuthorityExists)?;
+
+    Processor::validate_owner(
+        program_id,
+        &authority,
+        owner_info,
+        owner_info_data_len,
+        account_info_iter.as_slice(),
+    )?;
+
+    check_multiplier((*new_multiplier).into())?;
+
+    let clock = Clock::get()?;
+    if effective_timestamp <= clock.unix_timestamp {
+        extension.multiplier = *new_multiplier;
+    } else if clock.unix_timestamp >= i64::from(extension.new_multiplier_effective_timestamp) {
+        // the previously scheduled multiplier is in effect, so it becomes the
+        // current one until the new multiplier takes over
+        extension.multiplier = extension.new_multiplier;
+    }
+    extension.new_multiplier_effective_timestamp = effective_timestamp.into();
+    extension.new_multiplier = *new_multiplier;
+    Ok(())
+}
+
+pub(crate) fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    check_program_account(program_id)?;
+    match decode_instruction_type(input)? {
+        ScaledUiAmountMintInstruction::Initialize => {
+            msg!("ScaledUiAmountMintInstruction::Initialize");
+            let InitializeInstructionData {
+                authority,
+                multiplier,
+            } = decode_instruction_data(input)?;
+            process_initialize(program_id, accounts, authority, multiplier)
+        }
+        ScaledUiAmountMintInstruction::UpdateMultiplier => {
+            msg!("ScaledUiAmountMintInstruction::UpdateMultiplier");
+            let UpdateMultiplierInstructionData {
+                multiplier,
+                effective_timestamp,
+            } = decode_instruction_data(input)?;
+            process_update_multiplier(
+                program_id,
+                accounts,
+                multiplier,
+                i64::from(*effective_timestamp),
+            )
+        }
+    }
+}
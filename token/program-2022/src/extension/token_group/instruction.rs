@@ -0,0 +1,238 @@
+use {
+    crate::{
+        check_program_account,
+        instruction::{encode_instruction, TokenInstruction},
+        pod::{OptionalNonZeroPubkey, PodU64},
+    },
+    bytemuck::{Pod, Zeroable},
+    num_enum::{IntoPrimitive, TryFromPrimitive},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    std::convert::TryInto,
+};
+
+/// Token group extension instructions
+#[derive(Clone, Copy, Debug, PartialEq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum TokenGroupInstruction {
+    /// Initialize the group data stored in an initialized mint.
+    ///
+    /// The mint is reallocated to fit the group, so it must hold enough
+    /// lamports to remain rent-exempt at its new size.
+    ///
+    /// Fails if the group has already been initialized.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The group mint.
+    ///   1. `[signer]` The mint authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The group mint.
+    ///   1. `[]` The mint's multisignature mint authority.
+    ///   2. ..2+M `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::token_group::instruction::InitializeGroupInstructionData`
+    ///
+    InitializeGroup,
+    /// Update the max size of the group. Fails if the group already has more
+    /// members than the new max size.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The group mint.
+    ///   1. `[signer]` The group update authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The group mint.
+    ///   1. `[]` The group's multisignature update authority.
+    ///   2. ..2+M `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::token_group::instruction::UpdateGroupMaxSizeInstructionData`
+    ///
+    UpdateGroupMaxSize,
+    /// Update the group update authority. Setting it to `None` permanently
+    /// freezes the group, so that no more members can be added.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The group mint.
+    ///   1. `[signer]` The current group update authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The group mint.
+    ///   1. `[]` The group's multisignature update authority.
+    ///   2. ..2+M `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::token_group::instruction::UpdateGroupAuthorityInstructionData`
+    ///
+    UpdateGroupAuthority,
+    /// Initialize the member data stored in an initialized mint, adding it to
+    /// the group and incrementing the group size.
+    ///
+    /// The member mint is reallocated to fit the member data, so it must hold
+    /// enough lamports to remain rent-exempt at its new size.
+    ///
+    /// Fails if the member has already been initialized, if the group is full,
+    /// or if the member and group mints are the same.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authorities
+    ///   0. `[writable]` The member mint.
+    ///   1. `[signer]` The member mint's mint authority.
+    ///   2. `[writable]` The group mint.
+    ///   3. `[signer]` The group update authority.
+    ///
+    ///   * Multisignature authorities
+    ///   0. `[writable]` The member mint.
+    ///   1. `[]` The member mint's mint authority.
+    ///   2. `[writable]` The group mint.
+    ///   3. `[]` The group update authority.
+    ///   4. ..4+M `[signer]` M signer accounts, covering the signers of
+    ///      either multisignature authority and any single authority.
+    ///
+    InitializeMember,
+}
+
+/// Data expected by `TokenGroupInstruction::InitializeGroup`
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct InitializeGroupInstructionData {
+    /// The public key for the account that can update the group and add members
+    pub update_authority: OptionalNonZeroPubkey,
+    /// The maximum number of group members
+    pub max_size: PodU64,
+}
+
+/// Data expected by `TokenGroupInstruction::UpdateGroupMaxSize`
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct UpdateGroupMaxSizeInstructionData {
+    /// The new maximum number of group members
+    pub max_size: PodU64,
+}
+
+/// Data expected by `TokenGroupInstruction::UpdateGroupAuthority`
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct UpdateGroupAuthorityInstructionData {
+    /// The new group update authority
+    pub new_authority: OptionalNonZeroPubkey,
+}
+
+fn push_authority_accounts(
+    accounts: &mut Vec<AccountMeta>,
+    authority: &Pubkey,
+    signers: &[&Pubkey],
+) {
+    accounts.push(AccountMeta::new_readonly(*authority, signers.is_empty()));
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+}
+
+/// Create an `InitializeGroup` instruction
+pub fn initialize_group(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    signers: &[&Pubkey],
+    update_authority: Option<Pubkey>,
+    max_size: u64,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![AccountMeta::new(*mint, false)];
+    push_authority_accounts(&mut accounts, mint_authority, signers);
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::TokenGroupExtension,
+        TokenGroupInstruction::InitializeGroup,
+        &InitializeGroupInstructionData {
+            update_authority: update_authority.try_into()?,
+            max_size: max_size.into(),
+        },
+    ))
+}
+
+/// Create an `UpdateGroupMaxSize` instruction
+pub fn update_group_max_size(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    max_size: u64,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![AccountMeta::new(*mint, false)];
+    push_authority_accounts(&mut accounts, update_authority, signers);
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::TokenGroupExtension,
+        TokenGroupInstruction::UpdateGroupMaxSize,
+        &UpdateGroupMaxSizeInstructionData {
+            max_size: max_size.into(),
+        },
+    ))
+}
+
+/// Create an `UpdateGroupAuthority` instruction
+pub fn update_group_authority(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    current_authority: &Pubkey,
+    signers: &[&Pubkey],
+    new_authority: Option<Pubkey>,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![AccountMeta::new(*mint, false)];
+    push_authority_accounts(&mut accounts, current_authority, signers);
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::TokenGroupExtension,
+        TokenGroupInstruction::UpdateGroupAuthority,
+        &UpdateGroupAuthorityInstructionData {
+            new_authority: new_authority.try_into()?,
+        },
+    ))
+}
+
+/// Create an `InitializeMember` instruction
+pub fn initialize_member(
+    token_program_id: &Pubkey,
+    member_mint: &Pubkey,
+    member_mint_authority: &Pubkey,
+    group_mint: &Pubkey,
+    group_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*member_mint, false),
+        AccountMeta::new_readonly(*member_mint_authority, signers.is_empty()),
+        AccountMeta::new(*group_mint, false),
+        AccountMeta::new_readonly(*group_update_authority, signers.is_empty()),
+    ];
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenInstruction::TokenGroupExtension,
+        TokenGroupInstruction::InitializeMember,
+        &(),
+    ))
+}
@@ -0,0 +1,98 @@
+use {
+    crate::{
+        error::TokenError,
+        extension::{Extension, ExtensionType},
+        pod::{OptionalNonZeroPubkey, PodU64},
+    },
+    bytemuck::{Pod, Zeroable},
+    solana_program::{program_error::ProgramError, pubkey::Pubkey},
+};
+
+/// Token group extension instructions
+pub mod instruction;
+
+/// Token group extension processor
+pub mod processor;
+
+/// Data for a group of mints, stored in the group mint
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TokenGroup {
+    /// The authority that can sign to update the group and add members
+    pub update_authority: OptionalNonZeroPubkey,
+    /// The associated mint, used to counter spoofing to be sure that the group
+    /// belongs to a particular mint
+    pub mint: Pubkey,
+    /// The current number of group members
+    pub size: PodU64,
+    /// The maximum number of group members
+    pub max_size: PodU64,
+}
+impl TokenGroup {
+    /// Update the max size, which cannot go below the current size
+    pub fn update_max_size(&mut self, new_max_size: u64) -> Result<(), ProgramError> {
+        if new_max_size < u64::from(self.size) {
+            return Err(TokenError::SizeExceedsNewMaxSize.into());
+        }
+        self.max_size = new_max_size.into();
+        Ok(())
+    }
+
+    /// Add a member to the group, returning its member number
+    pub fn increment_size(&mut self) -> Result<u64, ProgramError> {
+        let new_size = u64::from(self.size)
+            .checked_add(1)
+            .ok_or(TokenError::Overflow)?;
+        if new_size > u64::from(self.max_size) {
+            return Err(TokenError::SizeExceedsMaxSize.into());
+        }
+        self.size = new_size.into();
+        Ok(new_size)
+    }
+}
+impl Extension for TokenGroup {
+    const TYPE: ExtensionType = ExtensionType::TokenGroup;
+}
+
+/// Data for a member of a group of mints, stored in the member mint
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TokenGroupMember {
+    /// The associated mint, used to counter spoofing to be sure that the member
+    /// belongs to a particular mint
+    pub mint: Pubkey,
+    /// The mint of the group this member belongs to
+    pub group: Pubkey,
+    /// The member number, starting at 1
+    pub member_number: PodU64,
+}
+impl Extension for TokenGroupMember {
+    const TYPE: ExtensionType = ExtensionType::TokenGroupMember;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_size() {
+        let mut group = TokenGroup {
+            max_size: 2.into(),
+            ..TokenGroup::default()
+        };
+        assert_eq!(group.increment_size().unwrap(), 1);
+        assert_eq!(group.increment_size().unwrap(), 2);
+        assert_eq!(
+            group.increment_size().unwrap_err(),
+            TokenError::SizeExceedsMaxSize.into()
+        );
+
+        assert_eq!(
+            group.update_max_size(1).unwrap_err(),
+            TokenError::SizeExceedsNewMaxSize.into()
+        );
+        group.update_max_size(3).unwrap();
+        assert_eq!(group.increment_size().unwrap(), 3);
+        assert_eq!(u64::from(group.size), 3);
+    }
+}
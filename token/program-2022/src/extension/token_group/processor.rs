@@ -0,0 +1,213 @@
+use {
+    crate::{
+        check_program_account,
+        error::TokenError,
+        extension::{
+            alloc_and_serialize_fixed_len_extension,
+            token_group::{
+                instruction::{
+                    InitializeGroupInstructionData, TokenGroupInstruction,
+                    UpdateGroupAuthorityInstructionData, UpdateGroupMaxSizeInstructionData,
+                },
+                TokenGroup, TokenGroupMember,
+            },
+            StateWithExtensions, StateWithExtensionsMut,
+        },
+        instruction::{decode_instruction_data, decode_instruction_type},
+        pod::OptionalNonZeroPubkey,
+        processor::Processor,
+        state::Mint,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        program_option::COption,
+        pubkey::Pubkey,
+    },
+};
+
+/// Checks that the mint authority of the given mint signed the instruction
+fn check_mint_authority(
+    program_id: &Pubkey,
+    mint_info: &AccountInfo,
+    mint_authority_info: &AccountInfo,
+    signers: &[AccountInfo],
+) -> ProgramResult {
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    match mint.base.mint_authority {
+        COption::Some(mint_authority) => Processor::validate_owner(
+            program_id,
+            &mint_authority,
+            mint_authority_info,
+            mint_authority_info.data_len(),
+            signers,
+        ),
+        COption::None => Err(TokenError::NoAuthorityExists.into()),
+    }
+}
+
+/// Checks that the update authority of the group signed the instruction
+fn check_update_authority(
+    program_id: &Pubkey,
+    group: &TokenGroup,
+    authority_info: &AccountInfo,
+    signers: &[AccountInfo],
+) -> ProgramResult {
+    let update_authority =
+        Option::<Pubkey>::from(group.update_authority).ok_or(TokenError::NoAuthorityExists)?;
+    Processor::validate_owner(
+        program_id,
+        &update_authority,
+        authority_info,
+        authority_info.data_len(),
+        signers,
+    )
+}
+
+fn process_initialize_group(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    update_authority: &OptionalNonZeroPubkey,
+    max_size: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let mint_authority_info = next_account_info(account_info_iter)?;
+
+    check_program_account(mint_info.owner)?;
+    check_mint_authority(
+        program_id,
+        mint_info,
+        mint_authority_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    let group = TokenGroup {
+        update_authority: *update_authority,
+        mint: *mint_info.key,
+        size: 0.into(),
+        max_size: max_size.into(),
+    };
+    alloc_and_serialize_fixed_len_extension::<Mint, _>(mint_info, &group, false)
+}
+
+fn process_update_group_max_size(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    max_size: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    check_program_account(mint_info.owner)?;
+    let mut mint_data = mint_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(&mut mint_data)?;
+    let group = mint.get_extension_mut::<TokenGroup>()?;
+
+    check_update_authority(
+        program_id,
+        group,
+        authority_info,
+        account_info_iter.as_slice(),
+    )?;
+    group.update_max_size(max_size)
+}
+
+fn process_update_group_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_authority: &OptionalNonZeroPubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    check_program_account(mint_info.owner)?;
+    let mut mint_data = mint_info.data.borrow_mut();
+    let mut mint = StateWithExtensionsMut::<Mint>::unpack(&mut mint_data)?;
+    let group = mint.get_extension_mut::<TokenGroup>()?;
+
+    check_update_authority(
+        program_id,
+        group,
+        authority_info,
+        account_info_iter.as_slice(),
+    )?;
+    group.update_authority = *new_authority;
+    Ok(())
+}
+
+fn process_initialize_member(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let member_mint_info = next_account_info(account_info_iter)?;
+    let member_mint_authority_info = next_account_info(account_info_iter)?;
+    let group_mint_info = next_account_info(account_info_iter)?;
+    let group_update_authority_info = next_account_info(account_info_iter)?;
+
+    if member_mint_info.key == group_mint_info.key {
+        msg!("A mint cannot be a member of its own group");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    check_program_account(member_mint_info.owner)?;
+    check_program_account(group_mint_info.owner)?;
+    let signers = account_info_iter.as_slice();
+    check_mint_authority(
+        program_id,
+        member_mint_info,
+        member_mint_authority_info,
+        signers,
+    )?;
+
+    let member_number = {
+        let mut group_mint_data = group_mint_info.data.borrow_mut();
+        let mut group_mint = StateWithExtensionsMut::<Mint>::unpack(&mut group_mint_data)?;
+        let group = group_mint.get_extension_mut::<TokenGroup>()?;
+        check_update_authority(program_id, group, group_update_authority_info, signers)?;
+        group.increment_size()?
+    };
+
+    let member = TokenGroupMember {
+        mint: *member_mint_info.key,
+        group: *group_mint_info.key,
+        member_number: member_number.into(),
+    };
+    alloc_and_serialize_fixed_len_extension::<Mint, _>(member_mint_info, &member, false)
+}
+
+pub(crate) fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    check_program_account(program_id)?;
+    match decode_instruction_type(input)? {
+        TokenGroupInstruction::InitializeGroup => {
+            msg!("TokenGroupInstruction::InitializeGroup");
+            let InitializeGroupInstructionData {
+                update_authority,
+                max_size,
+            } = decode_instruction_data(input)?;
+            process_initialize_group(program_id, accounts, update_authority, u64::from(*max_size))
+        }
+        TokenGroupInstruction::UpdateGroupMaxSize => {
+            msg!("TokenGroupInstruction::UpdateGroupMaxSize");
+            let UpdateGroupMaxSizeInstructionData { max_size } = decode_instruction_data(input)?;
+            process_update_group_max_size(program_id, accounts, u64::from(*max_size))
+        }
+        TokenGroupInstruction::UpdateGroupAuthority => {
+            msg!("TokenGroupInstruction::UpdateGroupAuthority");
+            let UpdateGroupAuthorityInstructionData { new_authority } =
+                decode_instruction_data(input)?;
+            process_update_group_authority(program_id, accounts, new_authority)
+        }
+        TokenGroupInstruction::InitializeMember => {
+            msg!("TokenGroupInstruction::InitializeMember");
+            process_initialize_member(program_id, accounts)
+        }
+    }
+}
@@ -0,0 +1,436 @@
+use {
+    crate::{
+        check_program_account,
+        extension::token_metadata::{pack_str, unpack_str, Field},
+        instruction::TokenInstruction,
+        pod::{pod_from_bytes, OptionalNonZeroPubkey},
+    },
+    num_enum::{IntoPrimitive, TryFromPrimitive},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    std::convert::TryInto,
+};
+
+/// Token metadata extension instructions
+#[derive(Clone, Copy, Debug, PartialEq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum TokenMetadataInstruction {
+    /// Initialize the token metadata stored in an initialized mint.
+    ///
+    /// The mint is reallocated to fit the metadata, so it must hold enough
+    /// lamports to remain rent-exempt at its new size.
+    ///
+    /// Fails if the metadata has already been initialized.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The update authority for the metadata.
+    ///   2. `[signer]` The mint authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The update authority for the metadata.
+    ///   2. `[]` The mint's multisignature mint authority.
+    ///   3. ..3+M `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::token_metadata::instruction::InitializeInstructionData`
+    ///
+    Initialize,
+    /// Update a field in the token metadata, adding it if it is a new key.
+    ///
+    /// The mint is reallocated to fit the new metadata, so it must hold enough
+    /// lamports to remain rent-exempt at its new size.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[signer]` The update authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The multisignature update authority.
+    ///   2. ..2+M `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::token_metadata::instruction::UpdateFieldInstructionData`
+    ///
+    UpdateField,
+    /// Remove a key from the additional metadata, shrinking the mint.
+    ///
+    /// Fails if the key is not present, unless `idempotent` is set.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[signer]` The update authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The multisignature update authority.
+    ///   2. ..2+M `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::token_metadata::instruction::RemoveKeyInstructionData`
+    ///
+    RemoveKey,
+    /// Set or clear the update authority of the token metadata. Once cleared,
+    /// the metadata can never be changed again.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[signer]` The current update authority.
+    ///
+    ///   * Multisignature authority
+    ///   0. `[writable]` The mint.
+    ///   1. `[]` The current multisignature update authority.
+    ///   2. ..2+M `[signer]` M signer accounts.
+    ///
+    /// Data expected by this instruction:
+    ///   `crate::extension::token_metadata::instruction::UpdateAuthorityInstructionData`
+    ///
+    UpdateAuthority,
+}
+
+/// Data expected by `TokenMetadataInstruction::Initialize`
+#[derive(Clone, Debug, PartialEq)]
+pub struct InitializeInstructionData {
+    /// Longer name of the token
+    pub name: String,
+    /// Shortened symbol of the token
+    pub symbol: String,
+    /// URI pointing to more metadata (image, video, etc.)
+    pub uri: String,
+}
+impl InitializeInstructionData {
+    fn pack(&self, dst: &mut Vec<u8>) {
+        pack_str(dst, &self.name);
+        pack_str(dst, &self.symbol);
+        pack_str(dst, &self.uri);
+    }
+
+    pub(crate) fn unpack(mut input: &[u8]) -> Result<Self, ProgramError> {
+        let name = unpack_instruction_str(&mut input)?;
+        let symbol = unpack_instruction_str(&mut input)?;
+        let uri = unpack_instruction_str(&mut input)?;
+        check_empty(input)?;
+        Ok(Self { name, symbol, uri })
+    }
+}
+
+/// Data expected by `TokenMetadataInstruction::UpdateField`
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateFieldInstructionData {
+    /// Field to update in the metadata
+    pub field: Field,
+    /// Value to write for the field
+    pub value: String,
+}
+impl UpdateFieldInstructionData {
+    fn pack(&self, dst: &mut Vec<u8>) {
+        self.field.pack(dst);
+        pack_str(dst, &self.value);
+    }
+
+    pub(crate) fn unpack(mut input: &[u8]) -> Result<Self, ProgramError> {
+        let field = Field::unpack(&mut input)?;
+        let value = unpack_instruction_str(&mut input)?;
+        check_empty(input)?;
+        Ok(Self { field, value })
+    }
+}
+
+/// Data expected by `TokenMetadataInstruction::RemoveKey`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RemoveKeyInstructionData {
+    /// If the idempotent flag is set to true, then the instruction will not
+    /// error if the key does not exist
+    pub idempotent: bool,
+    /// Key to remove in the additional metadata portion
+    pub key: String,
+}
+impl RemoveKeyInstructionData {
+    fn pack(&self, dst: &mut Vec<u8>) {
+        dst.push(self.idempotent.into());
+        pack_str(dst, &self.key);
+    }
+
+    pub(crate) fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&idempotent, mut rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let idempotent = match idempotent {
+            0 => false,
+            1 => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        let key = unpack_instruction_str(&mut rest)?;
+        check_empty(rest)?;
+        Ok(Self { idempotent, key })
+    }
+}
+
+/// Data expected by `TokenMetadataInstruction::UpdateAuthority`
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateAuthorityInstructionData {
+    /// New authority for the token metadata, or unset if `None`
+    pub new_authority: OptionalNonZeroPubkey,
+}
+impl UpdateAuthorityInstructionData {
+    fn pack(&self, dst: &mut Vec<u8>) {
+        dst.extend_from_slice(bytemuck::bytes_of(&self.new_authority));
+    }
+
+    pub(crate) fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let new_authority = pod_from_bytes::<OptionalNonZeroPubkey>(input)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok(Self {
+            new_authority: *new_authority,
+        })
+    }
+}
+
+fn unpack_instruction_str(input: &mut &[u8]) -> Result<String, ProgramError> {
+    unpack_str(input).map_err(|_| ProgramError::InvalidInstructionData)
+}
+
+fn check_empty(input: &[u8]) -> Result<(), ProgramError> {
+    if input.is_empty() {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidInstructionData)
+    }
+}
+
+fn encode_instruction(
+    token_program_id: &Pubkey,
+    accounts: Vec<AccountMeta>,
+    instruction_type: TokenMetadataInstruction,
+    pack_data: impl FnOnce(&mut Vec<u8>),
+) -> Instruction {
+    let mut data = TokenInstruction::TokenMetadataExtension.pack();
+    data.push(instruction_type.into());
+    pack_data(&mut data);
+    Instruction {
+        program_id: *token_program_id,
+        accounts,
+        data,
+    }
+}
+
+fn push_authority_accounts(
+    accounts: &mut Vec<AccountMeta>,
+    authority: &Pubkey,
+    signers: &[&Pubkey],
+) {
+    accounts.push(AccountMeta::new_readonly(*authority, signers.is_empty()));
+    for signer_pubkey in signers.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+}
+
+/// Create an `Initialize` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn initialize(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    update_authority: &Pubkey,
+    mint_authority: &Pubkey,
+    signers: &[&Pubkey],
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*update_authority, false),
+    ];
+    push_authority_accounts(&mut accounts, mint_authority, signers);
+    let data = InitializeInstructionData { name, symbol, uri };
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenMetadataInstruction::Initialize,
+        |dst| data.pack(dst),
+    ))
+}
+
+/// Create an `UpdateField` instruction
+pub fn update_field(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    field: Field,
+    value: String,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![AccountMeta::new(*mint, false)];
+    push_authority_accounts(&mut accounts, update_authority, signers);
+    let data = UpdateFieldInstructionData { field, value };
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenMetadataInstruction::UpdateField,
+        |dst| data.pack(dst),
+    ))
+}
+
+/// Create a `RemoveKey` instruction
+pub fn remove_key(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    key: String,
+    idempotent: bool,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![AccountMeta::new(*mint, false)];
+    push_authority_accounts(&mut accounts, update_authority, signers);
+    let data = RemoveKeyInstructionData { idempotent, key };
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenMetadataInstruction::RemoveKey,
+        |dst| data.pack(dst),
+    ))
+}
+
+/// Create an `UpdateAuthority` instruction
+pub fn update_authority(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    current_authority: &Pubkey,
+    signers: &[&Pubkey],
+    new_authority: Option<Pubkey>,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    let mut accounts = vec![AccountMeta::new(*mint, false)];
+    push_authority_accounts(&mut accounts, current_authority, signers);
+    let data = UpdateAuthorityInstructionData {
+        new_authority: new_authority.try_into()?,
+    };
+    Ok(encode_instruction(
+        token_program_id,
+        accounts,
+        TokenMetadataInstruction::UpdateAuthority,
+        |dst| data.pack(dst),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::instruction::decode_instruction_type};
+
+    fn check_prefix(
+        instruction: &Instruction,
+        instruction_type: TokenMetadataInstruction,
+    ) -> &[u8] {
+        assert_eq!(
+            TokenInstruction::unpack(&instruction.data[..1]).unwrap(),
+            TokenInstruction::TokenMetadataExtension
+        );
+        assert_eq!(
+            decode_instruction_type::<TokenMetadataInstruction>(&instruction.data[1..]).unwrap(),
+            instruction_type
+        );
+        &instruction.data[2..]
+    }
+
+    #[test]
+    fn instruction_packing() {
+        let program_id = crate::id();
+        let mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+
+        let instruction = initialize(
+            &program_id,
+            &mint,
+            &authority,
+            &authority,
+            &[],
+            "name".to_string(),
+            "symbol".to_string(),
+            "uri".to_string(),
+        )
+        .unwrap();
+        let data = check_prefix(&instruction, TokenMetadataInstruction::Initialize);
+        assert_eq!(
+            InitializeInstructionData::unpack(data).unwrap(),
+            InitializeInstructionData {
+                name: "name".to_string(),
+                symbol: "symbol".to_string(),
+                uri: "uri".to_string(),
+            }
+        );
+
+        let instruction = update_field(
+            &program_id,
+            &mint,
+            &authority,
+            &[],
+            Field::Key("key".to_string()),
+            "value".to_string(),
+        )
+        .unwrap();
+        let data = check_prefix(&instruction, TokenMetadataInstruction::UpdateField);
+        assert_eq!(
+            UpdateFieldInstructionData::unpack(data).unwrap(),
+            UpdateFieldInstructionData {
+                field: Field::Key("key".to_string()),
+                value: "value".to_string(),
+            }
+        );
+
+        let instruction =
+            remove_key(&program_id, &mint, &authority, &[], "key".to_string(), true).unwrap();
+        let data = check_prefix(&instruction, TokenMetadataInstruction::RemoveKey);
+        assert_eq!(
+            RemoveKeyInstructionData::unpack(data).unwrap(),
+            RemoveKeyInstructionData {
+                idempotent: true,
+                key: "key".to_string(),
+            }
+        );
+
+        for new_authority in [None, Some(Pubkey::new_unique())] {
+            let instruction =
+                update_authority(&program_id, &mint, &authority, &[], new_authority).unwrap();
+            let data = check_prefix(&instruction, TokenMetadataInstruction::UpdateAuthority);
+            assert_eq!(
+                UpdateAuthorityInstructionData::unpack(data).unwrap(),
+                UpdateAuthorityInstructionData {
+                    new_authority: new_authority.try_into().unwrap(),
+                }
+            );
+        }
+
+        // trailing data is rejected
+        let mut data = vec![];
+        RemoveKeyInstructionData {
+            idempotent: false,
+            key: "key".to_string(),
+        }
+        .pack(&mut data);
+        data.push(0);
+        assert_eq!(
+            RemoveKeyInstructionData::unpack(&data).unwrap_err(),
+            ProgramError::InvalidInstructionData
+        );
+        assert_eq!(
+            RemoveKeyInstructionData::unpack(&[]).unwrap_err(),
+            ProgramError::InvalidInstructionData
+        );
+    }
+}
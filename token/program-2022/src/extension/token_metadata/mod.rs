@@ -0,0 +1,286 @@
+use {
+    crate::{
+        extension::{ExtensionType, VariableLenPack},
+        pod::{pod_from_bytes, OptionalNonZeroPubkey},
+    },
+    solana_program::{program_error::ProgramError, pubkey::Pubkey},
+    std::{
+        convert::{TryFrom, TryInto},
+        mem::size_of,
+    },
+};
+
+/// Token metadata extension instructions
+pub mod instruction;
+
+/// Token metadata extension processor
+pub mod processor;
+
+/// Fields in the metadata account, used for updating
+#[derive(Clone, Debug, PartialEq)]
+pub enum Field {
+    /// The name field, corresponding to `TokenMetadata.name`
+    Name,
+    /// The symbol field, corresponding to `TokenMetadata.symbol`
+    Symbol,
+    /// The uri field, corresponding to `TokenMetadata.uri`
+    Uri,
+    /// A user field, whose key is given by the associated string
+    Key(String),
+}
+impl Field {
+    /// Packs the field, appending it to the buffer
+    pub(crate) fn pack(&self, dst: &mut Vec<u8>) {
+        match self {
+            Self::Name => dst.push(0),
+            Self::Symbol => dst.push(1),
+            Self::Uri => dst.push(2),
+            Self::Key(key) => {
+                dst.push(3);
+                pack_str(dst, key);
+            }
+        }
+    }
+
+    /// Unpacks a field from the start of the buffer, advancing it
+    pub(crate) fn unpack(src: &mut &[u8]) -> Result<Self, ProgramError> {
+        let (&tag, rest) = src
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        *src = rest;
+        Ok(match tag {
+            0 => Self::Name,
+            1 => Self::Symbol,
+            2 => Self::Uri,
+            3 => Self::Key(unpack_str(src).map_err(|_| ProgramError::InvalidInstructionData)?),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+}
+
+/// Variable-length metadata stored directly in the mint
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TokenMetadata {
+    /// The authority that can sign to update the metadata
+    pub update_authority: OptionalNonZeroPubkey,
+    /// The associated mint, used to counter spoofing to be sure that metadata
+    /// belongs to a particular mint
+    pub mint: Pubkey,
+    /// The longer name of the token
+    pub name: String,
+    /// The shortened symbol for the token
+    pub symbol: String,
+    /// The URI pointing to richer metadata
+    pub uri: String,
+    /// Any additional metadata about the token as key-value pairs
+    pub additional_metadata: Vec<(String, String)>,
+}
+impl TokenMetadata {
+    /// Update a field, adding it to `additional_metadata` if it is a new key
+    pub fn update(&mut self, field: Field, value: String) {
+        match field {
+            Field::Name => self.name = value,
+            Field::Symbol => self.symbol = value,
+            Field::Uri => self.uri = value,
+            Field::Key(key) => {
+                if let Some(pair) = self
+                    .additional_metadata
+                    .iter_mut()
+                    .find(|(existing_key, _)| *existing_key == key)
+                {
+                    pair.1 = value;
+                } else {
+                    self.additional_metadata.push((key, value));
+                }
+            }
+        }
+    }
+
+    /// Remove a key from `additional_metadata`, returning true if it was present
+    pub fn remove_key(&mut self, key: &str) -> bool {
+        let previous_len = self.additional_metadata.len();
+        self.additional_metadata
+            .retain(|(existing_key, _)| existing_key != key);
+        previous_len != self.additional_metadata.len()
+    }
+}
+impl VariableLenPack for TokenMetadata {
+    const TYPE: ExtensionType = ExtensionType::TokenMetadata;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        let mut data = Vec::with_capacity(dst.len());
+        data.extend_from_slice(bytemuck::bytes_of(&self.update_authority));
+        data.extend_from_slice(self.mint.as_ref());
+        pack_str(&mut data, &self.name);
+        pack_str(&mut data, &self.symbol);
+        pack_str(&mut data, &self.uri);
+        pack_len(&mut data, self.additional_metadata.len());
+        for (key, value) in self.additional_metadata.iter() {
+            pack_str(&mut data, key);
+            pack_str(&mut data, value);
+        }
+        if data.len() != dst.len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        dst.copy_from_slice(&data);
+        Ok(())
+    }
+
+    fn unpack_from_slice(mut src: &[u8]) -> Result<Self, ProgramError> {
+        let update_authority = *pod_from_bytes::<OptionalNonZeroPubkey>(unpack_bytes(
+            &mut src,
+            size_of::<OptionalNonZeroPubkey>(),
+        )?)?;
+        let mint = unpack_pubkey(&mut src)?;
+        let name = unpack_str(&mut src)?;
+        let symbol = unpack_str(&mut src)?;
+        let uri = unpack_str(&mut src)?;
+        let num_additional_metadata = unpack_len(&mut src)?;
+        let mut additional_metadata = Vec::new();
+        for _ in 0..num_additional_metadata {
+            let key = unpack_str(&mut src)?;
+            let value = unpack_str(&mut src)?;
+            additional_metadata.push((key, value));
+        }
+        if !src.is_empty() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Self {
+            update_authority,
+            mint,
+            name,
+            symbol,
+            uri,
+            additional_metadata,
+        })
+    }
+
+    fn get_packed_len(&self) -> Result<usize, ProgramError> {
+        let str_len = |s: &str| size_of::<u32>().saturating_add(s.len());
+        Ok(size_of::<OptionalNonZeroPubkey>()
+            .saturating_add(size_of::<Pubkey>())
+            .saturating_add(str_len(&self.name))
+            .saturating_add(str_len(&self.symbol))
+            .saturating_add(str_len(&self.uri))
+            .saturating_add(size_of::<u32>())
+            .saturating_add(
+                self.additional_metadata
+                    .iter()
+                    .map(|(key, value)| str_len(key).saturating_add(str_len(value)))
+                    .sum(),
+            ))
+    }
+}
+
+/// Strings and vectors are prefixed by their length as a little-endian u32
+fn pack_len(dst: &mut Vec<u8>, len: usize) {
+    dst.extend_from_slice(&(len as u32).to_le_bytes());
+}
+
+pub(crate) fn pack_str(dst: &mut Vec<u8>, s: &str) {
+    pack_len(dst, s.len());
+    dst.extend_from_slice(s.as_bytes());
+}
+
+fn unpack_bytes<'a>(src: &mut &'a [u8], len: usize) -> Result<&'a [u8], ProgramError> {
+    if src.len() < len {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (bytes, rest) = src.split_at(len);
+    *src = rest;
+    Ok(bytes)
+}
+
+fn unpack_len(src: &mut &[u8]) -> Result<usize, ProgramError> {
+    let bytes = unpack_bytes(src, size_of::<u32>())?;
+    let len = u32::from_le_bytes(
+        bytes
+            .try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)?,
+    );
+    usize::try_from(len).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+pub(crate) fn unpack_str(src: &mut &[u8]) -> Result<String, ProgramError> {
+    let len = unpack_len(src)?;
+    let bytes = unpack_bytes(src, len)?;
+    String::from_utf8(bytes.to_vec()).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+fn unpack_pubkey(src: &mut &[u8]) -> Result<Pubkey, ProgramError> {
+    let bytes = unpack_bytes(src, size_of::<Pubkey>())?;
+    let bytes = <[u8; 32]>::try_from(bytes).map_err(|_| ProgramError::InvalidAccountData)?;
+    Ok(Pubkey::new_from_array(bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pack_unpack() {
+        let mut metadata = TokenMetadata {
+            update_authority: Some(Pubkey::new_unique()).try_into().unwrap(),
+            mint: Pubkey::new_unique(),
+            name: "Token".to_string(),
+            symbol: "TKN".to_string(),
+            uri: "https://example.com/token.json".to_string(),
+            additional_metadata: vec![],
+        };
+        metadata.update(Field::Key("color".to_string()), "blue".to_string());
+        metadata.update(Field::Key("size".to_string()), "big".to_string());
+        metadata.update(Field::Key("color".to_string()), "red".to_string());
+        metadata.update(Field::Name, "Better Token".to_string());
+        assert_eq!(
+            metadata.additional_metadata,
+            vec![
+                ("color".to_string(), "red".to_string()),
+                ("size".to_string(), "big".to_string()),
+            ]
+        );
+        assert_eq!(metadata.name, "Better Token");
+
+        let mut data = vec![0; metadata.get_packed_len().unwrap()];
+        metadata.pack_into_slice(&mut data).unwrap();
+        assert_eq!(TokenMetadata::unpack_from_slice(&data).unwrap(), metadata);
+
+        // wrong sizes fail
+        let mut too_big = vec![0; data.len() + 1];
+        assert_eq!(
+            metadata.pack_into_slice(&mut too_big).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        assert_eq!(
+            TokenMetadata::unpack_from_slice(&data[..data.len() - 1]).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+
+        assert!(metadata.remove_key("size"));
+        assert!(!metadata.remove_key("size"));
+        assert_eq!(
+            metadata.additional_metadata,
+            vec![("color".to_string(), "red".to_string())]
+        );
+    }
+
+    #[test]
+    fn field_pack_unpack() {
+        for field in [
+            Field::Name,
+            Field::Symbol,
+            Field::Uri,
+            Field::Key("key".to_string()),
+        ] {
+            let mut data = vec![];
+            field.pack(&mut data);
+            let mut src = data.as_slice();
+            assert_eq!(Field::unpack(&mut src).unwrap(), field);
+            assert!(src.is_empty());
+        }
+        let mut src: &[u8] = &[4];
+        assert_eq!(
+            Field::unpack(&mut src).unwrap_err(),
+            ProgramError::InvalidInstructionData
+        );
+    }
+}
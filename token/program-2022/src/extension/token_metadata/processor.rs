@@ -0,0 +1,194 @@
+use {
+    crate::{
+        check_program_account,
+        error::TokenError,
+        extension::{
+            alloc_and_serialize_variable_len_extension,
+            token_metadata::{
+                instruction::{
+                    InitializeInstructionData, RemoveKeyInstructionData, TokenMetadataInstruction,
+                    UpdateAuthorityInstructionData, UpdateFieldInstructionData,
+                },
+                TokenMetadata,
+            },
+            StateWithExtensions,
+        },
+        instruction::decode_instruction_type,
+        processor::Processor,
+        state::Mint,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        program_option::COption,
+        pubkey::Pubkey,
+    },
+    std::convert::TryInto,
+};
+
+/// Loads the metadata from the mint, and checks the update authority
+fn check_update_authority(
+    program_id: &Pubkey,
+    mint_info: &AccountInfo,
+    authority_info: &AccountInfo,
+    signers: &[AccountInfo],
+) -> Result<TokenMetadata, ProgramError> {
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let token_metadata = mint.get_variable_len_extension::<TokenMetadata>()?;
+    let update_authority = Option::<Pubkey>::from(token_metadata.update_authority)
+        .ok_or(TokenError::NoAuthorityExists)?;
+    Processor::validate_owner(
+        program_id,
+        &update_authority,
+        authority_info,
+        authority_info.data_len(),
+        signers,
+    )?;
+    Ok(token_metadata)
+}
+
+fn process_initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: InitializeInstructionData,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let mint_authority_info = next_account_info(account_info_iter)?;
+    let mint_authority_info_data_len = mint_authority_info.data_len();
+
+    check_program_account(mint_info.owner)?;
+    {
+        let mint_data = mint_info.try_borrow_data()?;
+        let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+        match mint.base.mint_authority {
+            COption::Some(mint_authority) => Processor::validate_owner(
+                program_id,
+                &mint_authority,
+                mint_authority_info,
+                mint_authority_info_data_len,
+                account_info_iter.as_slice(),
+            )?,
+            COption::None => return Err(TokenError::NoAuthorityExists.into()),
+        }
+    }
+
+    let InitializeInstructionData { name, symbol, uri } = data;
+    let token_metadata = TokenMetadata {
+        update_authority: Some(*update_authority_info.key).try_into()?,
+        mint: *mint_info.key,
+        name,
+        symbol,
+        uri,
+        additional_metadata: vec![],
+    };
+    alloc_and_serialize_variable_len_extension::<Mint, _>(mint_info, &token_metadata, false)
+}
+
+fn process_update_field(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: UpdateFieldInstructionData,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    let mut token_metadata = check_update_authority(
+        program_id,
+        mint_info,
+        authority_info,
+        account_info_iter.as_slice(),
+    )?;
+    token_metadata.update(data.field, data.value);
+    alloc_and_serialize_variable_len_extension::<Mint, _>(mint_info, &token_metadata, true)
+}
+
+fn process_remove_key(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: RemoveKeyInstructionData,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    let mut token_metadata = check_update_authority(
+        program_id,
+        mint_info,
+        authority_info,
+        account_info_iter.as_slice(),
+    )?;
+    if !token_metadata.remove_key(&data.key) && !data.idempotent {
+        return Err(TokenError::TokenMetadataKeyNotFound.into());
+    }
+    alloc_and_serialize_variable_len_extension::<Mint, _>(mint_info, &token_metadata, true)
+}
+
+fn process_update_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: UpdateAuthorityInstructionData,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    let mut token_metadata = check_update_authority(
+        program_id,
+        mint_info,
+        authority_info,
+        account_info_iter.as_slice(),
+    )?;
+    token_metadata.update_authority = data.new_authority;
+    // the size is unchanged, so this only rewrites the extension in place
+    alloc_and_serialize_variable_len_extension::<Mint, _>(mint_info, &token_metadata, true)
+}
+
+pub(crate) fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    check_program_account(program_id)?;
+    let instruction_type = decode_instruction_type(input)?;
+    let data = &input[1..];
+    match instruction_type {
+        TokenMetadataInstruction::Initialize => {
+            msg!("TokenMetadataInstruction::Initialize");
+            process_initialize(
+                program_id,
+                accounts,
+                InitializeInstructionData::unpack(data)?,
+            )
+        }
+        TokenMetadataInstruction::UpdateField => {
+            msg!("TokenMetadataInstruction::UpdateField");
+            process_update_field(
+                program_id,
+                accounts,
+                UpdateFieldInstructionData::unpack(data)?,
+            )
+        }
+        TokenMetadataInstruction::RemoveKey => {
+            msg!("TokenMetadataInstruction::RemoveKey");
+            process_remove_key(
+                program_id,
+                accounts,
+                RemoveKeyInstructionData::unpack(data)?,
+            )
+        }
+        TokenMetadataInstruction::UpdateAuthority => {
+            msg!("TokenMetadataInstruction::UpdateAuthority");
+            process_update_authority(
+                program_id,
+                accounts,
+                UpdateAuthorityInstructionData::unpack(data)?,
+            )
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! A transfer hook program is invoked by the token program at the end of every
+//! checked transfer of a mint that names it in its `TransferHook` extension.
+//! The program must implement the `Execute` instruction described here, and
+//! may require additional accounts, listed in a PDA owned by the hook program
+//! at `get_extra_account_metas_address`.
+
+use {
+    crate::pod::{pod_from_bytes, pod_from_bytes_mut, PodBool, PodU16},
+    bytemuck::{Pod, Zeroable},
+    solana_program::{
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction},
+        program::invoke,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    std::{convert::TryFrom, mem::size_of},
+};
+
+/// Seed for the PDA listing the extra accounts required by `Execute`
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+/// Prefix of the `Execute` instruction data, the first 8 bytes of
+/// `sha256("spl-transfer-hook-interface:execute")`
+pub const EXECUTE_DISCRIMINATOR: [u8; 8] = [105, 37, 101, 197, 75, 251, 102, 26];
+
+/// Length of the `Execute` instruction data
+const EXECUTE_DATA_LEN: usize = EXECUTE_DISCRIMINATOR.len() + size_of::<u64>();
+
+/// Get the address of the PDA listing the extra accounts required by `Execute`
+pub fn get_extra_account_metas_address(mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    get_extra_account_metas_address_and_bump_seed(mint, program_id).0
+}
+
+/// Get the address and bump seed of the PDA listing the extra accounts required
+/// by `Execute`
+pub fn get_extra_account_metas_address_and_bump_seed(
+    mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EXTRA_ACCOUNT_METAS_SEED, mint.as_ref()], program_id)
+}
+
+/// `AccountMeta` that can be stored in account data
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct PodAccountMeta {
+    /// The pubkey of the account
+    pub pubkey: Pubkey,
+    /// Whether the account should sign
+    pub is_signer: PodBool,
+    /// Whether the account should be writable
+    pub is_writable: PodBool,
+}
+impl From<&AccountMeta> for PodAccountMeta {
+    fn from(meta: &AccountMeta) -> Self {
+        Self {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer.into(),
+            is_writable: meta.is_writable.into(),
+        }
+    }
+}
+impl From<&PodAccountMeta> for AccountMeta {
+    fn from(meta: &PodAccountMeta) -> Self {
+        Self {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer.into(),
+            is_writable: meta.is_writable.into(),
+        }
+    }
+}
+
+/// Size of the extra account metas PDA data for the given number of accounts.
+///
+/// The data is laid out as a `PodU16` count, followed by that many
+/// `PodAccountMeta`s.
+pub fn get_extra_account_metas_len(num_metas: usize) -> usize {
+    size_of::<PodU16>().saturating_add(num_metas.saturating_mul(size_of::<PodAccountMeta>()))
+}
+
+/// Write the list of extra accounts into the data of the extra account metas PDA
+pub fn init_extra_account_metas(data: &mut [u8], metas: &[AccountMeta]) -> ProgramResult {
+    let num_metas = u16::try_from(metas.len()).map_err(|_| ProgramError::InvalidArgument)?;
+    if data.len() < get_extra_account_metas_len(metas.len()) {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    let (length_data, rest) = data.split_at_mut(size_of::<PodU16>());
+    *pod_from_bytes_mut::<PodU16>(length_data)? = num_metas.into();
+    let metas_len = metas.len().saturating_mul(size_of::<PodAccountMeta>());
+    let pod_metas = bytemuck::try_cast_slice_mut::<u8, PodAccountMeta>(&mut rest[..metas_len])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    for (pod_meta, meta) in pod_metas.iter_mut().zip(metas.iter()) {
+        *pod_meta = PodAccountMeta::from(meta);
+    }
+    Ok(())
+}
+
+/// Read the list of extra accounts from the data of the extra account metas PDA
+pub fn unpack_extra_account_metas(data: &[u8]) -> Result<&[PodAccountMeta], ProgramError> {
+    if data.len() < size_of::<PodU16>() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (length_data, rest) = data.split_at(size_of::<PodU16>());
+    let num_metas = usize::from(u16::from(*pod_from_bytes::<PodU16>(length_data)?));
+    let metas_len = num_metas.saturating_mul(size_of::<PodAccountMeta>());
+    let metas_data = rest
+        .get(..metas_len)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    bytemuck::try_cast_slice::<u8, PodAccountMeta>(metas_data)
+        .map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Creates an `Execute` instruction, without any of the extra accounts
+#[allow(clippy::too_many_arguments)]
+pub fn execute(
+    program_id: &Pubkey,
+    source_pubkey: &Pubkey,
+    mint_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    validate_state_pubkey: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut data = Vec::with_capacity(EXECUTE_DATA_LEN);
+    data.extend_from_slice(&EXECUTE_DISCRIMINATOR);
+    data.extend_from_slice(&amount.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*source_pubkey, false),
+            AccountMeta::new_readonly(*mint_pubkey, false),
+            AccountMeta::new_readonly(*destination_pubkey, false),
+            AccountMeta::new_readonly(*authority_pubkey, false),
+            AccountMeta::new_readonly(*validate_state_pubkey, false),
+        ],
+        data,
+    }
+}
+
+/// Unpacks the amount from `Execute` instruction data, for use by transfer
+/// hook programs
+pub fn unpack_execute(input: &[u8]) -> Result<u64, ProgramError> {
+    if input.len() != EXECUTE_DATA_LEN
+        || input[..EXECUTE_DISCRIMINATOR.len()] != EXECUTE_DISCRIMINATOR
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let mut amount = [0u8; 8];
+    amount.copy_from_slice(&input[EXECUTE_DISCRIMINATOR.len()..]);
+    Ok(u64::from_le_bytes(amount))
+}
+
+/// Invokes the `Execute` instruction on the transfer hook program, finding the
+/// extra account metas PDA and all of the extra accounts it lists among
+/// `additional_accounts`.
+///
+/// Extra accounts are never passed as signers, and are only writable if both
+/// the extra account metas and the transfer instruction mark them writable.
+pub fn invoke_execute<'a>(
+    program_id: &Pubkey,
+    source_info: AccountInfo<'a>,
+    mint_info: AccountInfo<'a>,
+    destination_info: AccountInfo<'a>,
+    authority_info: AccountInfo<'a>,
+    additional_accounts: &[AccountInfo<'a>],
+    amount: u64,
+) -> ProgramResult {
+    let program_info = additional_accounts
+        .iter()
+        .find(|info| info.key == program_id)
+        .ok_or(ProgramError::IncorrectProgramId)?;
+    let validate_state_pubkey = get_extra_account_metas_address(mint_info.key, program_id);
+    let validate_state_info = additional_accounts
+        .iter()
+        .find(|info| info.key == &validate_state_pubkey)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if validate_state_info.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut cpi_instruction = execute(
+        program_id,
+        source_info.key,
+        mint_info.key,
+        destination_info.key,
+        authority_info.key,
+        &validate_state_pubkey,
+        amount,
+    );
+    let mut cpi_account_infos = vec![
+        source_info,
+        mint_info,
+        destination_info,
+        authority_info,
+        validate_state_info.clone(),
+    ];
+
+    let validate_state_data = validate_state_info.try_borrow_data()?;
+    for extra_meta in unpack_extra_account_metas(&validate_state_data)? {
+        let extra_info = additional_accounts
+            .iter()
+            .find(|info| info.key == &extra_meta.pubkey)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        // never let the hook program act on behalf of a signer of the transfer
+        cpi_instruction.accounts.push(AccountMeta {
+            pubkey: extra_meta.pubkey,
+            is_signer: false,
+            is_writable: bool::from(extra_meta.is_writable) && extra_info.is_writable,
+        });
+        cpi_account_infos.push(extra_info.clone());
+    }
+    drop(validate_state_data);
+    cpi_account_infos.push(program_info.clone());
+
+    invoke(&cpi_instruction, &cpi_account_infos)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extra_account_metas_pack_unpack() {
+        let metas = [
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), true),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+        ];
+        let mut data = vec![0; get_extra_account_metas_len(metas.len())];
+        init_extra_account_metas(&mut data, &metas).unwrap();
+        let unpacked = unpack_extra_account_metas(&data).unwrap();
+        assert_eq!(
+            unpacked.iter().map(AccountMeta::from).collect::<Vec<_>>(),
+            metas.to_vec()
+        );
+
+        let mut too_small = vec![0; get_extra_account_metas_len(metas.len()) - 1];
+        assert_eq!(
+            init_extra_account_metas(&mut too_small, &metas).unwrap_err(),
+            ProgramError::AccountDataTooSmall
+        );
+        assert_eq!(
+            unpack_extra_account_metas(&data[..data.len() - 1]).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        assert_eq!(unpack_extra_account_metas(&[0, 0]).unwrap(), &[]);
+    }
+
+    #[test]
+    fn execute_pack_unpack() {
+        let program_id = Pubkey::new_unique();
+        let instruction = execute(
+            &program_id,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            42,
+        );
+        assert_eq!(instruction.data[..8], EXECUTE_DISCRIMINATOR);
+        assert_eq!(unpack_execute(&instruction.data).unwrap(), 42);
+        assert_eq!(
+            unpack_execute(&instruction.data[..8]).unwrap_err(),
+            ProgramError::InvalidInstructionData
+        );
+        let mut bad_data = instruction.data;
+        bad_data[0] = 0;
+        assert_eq!(
+            unpack_execute(&bad_data).unwrap_err(),
+            ProgramError::InvalidInstructionData
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use {
+    crate::{
+        extension::{Extension, ExtensionType, StateWithExtensionsMut},
+        pod::{OptionalNonZeroPubkey, PodBool},
+        state::Account,
+    },
+    bytemuck::{Pod, Zeroable},
+    solana_program::{account_info::AccountInfo, entrypoint::ProgramResult},
+};
+
+/// Transfer hook extension instructions
+pub mod instruction;
+
+/// Interface implemented by transfer hook programs
+pub mod interface;
+
+/// Transfer hook extension processor
+pub mod processor;
+
+/// Transfer hook extension data for mints.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TransferHook {
+    /// Authority that can set the transfer hook program id
+    pub authority: OptionalNonZeroPubkey,
+    /// Program that authorizes the transfer
+    pub program_id: OptionalNonZeroPubkey,
+}
+impl Extension for TransferHook {
+    const TYPE: ExtensionType = ExtensionType::TransferHook;
+}
+
+/// Indicates that the tokens from this account belong to a mint with a transfer hook
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct TransferHookAccount {
+    /// Flag to indicate that the account is in the middle of a transfer, so that
+    /// the transfer hook program can check that it is called by the token program
+    pub transferring: PodBool,
+}
+impl Extension for TransferHookAccount {
+    const TYPE: ExtensionType = ExtensionType::TransferHookAccount;
+}
+
+/// Set the `transferring` flag on an account before invoking the transfer hook
+pub fn set_transferring(account: &mut StateWithExtensionsMut<Account>) -> ProgramResult {
+    let account_extension = account.get_extension_mut::<TransferHookAccount>()?;
+    account_extension.transferring = true.into();
+    Ok(())
+}
+
+/// Unset the `transferring` flag on an account after the transfer hook returns
+pub fn unset_transferring(account_info: &AccountInfo) -> ProgramResult {
+    let mut account_data = account_info.data.borrow_mut();
+    let mut account = StateWithExtensionsMut::<Account>::unpack(&mut account_data)?;
+    let account_extension = account.get_extension_mut::<TransferHookAccount>()?;
+    account_extension.transferring = false.into();
+    Ok(())
+}
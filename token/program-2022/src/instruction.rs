@@ -303,6 +303,11 @@ pub enum TokenInstruction<'a> {
     /// If either account contains an `TransferFeeAmount` extension, the fee is
     /// withheld in the destination account.
     ///
+    /// If the mint contains a `TransferHook` extension with a program id, the
+    /// transfer hook program is invoked after the transfer, and all accounts
+    /// it requires must be provided at the end of the list: the transfer hook
+    /// program, its extra account metas PDA, and every account listed there.
+    ///
     /// Accounts expected by this instruction:
     ///
     ///   * Single owner/delegate
@@ -317,6 +322,11 @@ pub enum TokenInstruction<'a> {
     ///   2. `[writable]` The destination account.
     ///   3. `[]` The source account's multisignature owner/delegate.
     ///   4. ..4+M `[signer]` M signer accounts.
+    ///
+    ///   * Transfer hook, if required, after either of the above
+    ///   0. `[]` The transfer hook program.
+    ///   1. `[]` The transfer hook program's extra account metas PDA.
+    ///   2. ..2+N `[]` N extra accounts required by the transfer hook program.
     TransferChecked {
         /// The amount of tokens to transfer.
         amount: u64,
@@ -605,6 +615,57 @@ pub enum TokenInstruction<'a> {
     /// See `extension::interest_bearing_mint::instruction::InterestBearingMintInstruction` for
     /// further details about the extended instructions that share this instruction prefix
     InterestBearingMintExtension,
+    /// Initialize the permanent delegate on a new mint.
+    ///
+    /// Fails if the mint has already been initialized, so must be called before
+    /// `InitializeMint`.
+    ///
+    /// The mint must have exactly enough space allocated for the base mint (82
+    /// bytes), plus 83 bytes of padding, 1 byte reserved for the account type,
+    /// then space required for this extension, plus any others.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The mint to initialize.
+    ///
+    /// Data expected by this instruction:
+    ///   Pubkey for the permanent delegate
+    ///
+    InitializePermanentDelegate {
+        /// Authority that may sign for `Transfer`s and `Burn`s on any account
+        #[cfg_attr(feature = "serde-traits", serde(with = "As::<DisplayFromStr>"))]
+        delegate: Pubkey,
+    },
+    /// The common instruction prefix for transfer hook extension instructions.
+    ///
+    /// See `extension::transfer_hook::instruction::TransferHookInstruction` for
+    /// further details about the extended instructions that share this instruction prefix
+    TransferHookExtension,
+    /// The common instruction prefix for token metadata extension instructions.
+    ///
+    /// See `extension::token_metadata::instruction::TokenMetadataInstruction` for
+    /// further details about the extended instructions that share this instruction prefix
+    TokenMetadataExtension,
+    /// The common instruction prefix for CPI Guard account extension instructions.
+    ///
+    /// See `extension::cpi_guard::instruction::CpiGuardInstruction` for
+    /// further details about the extended instructions that share this instruction prefix
+    CpiGuardExtension,
+    /// The common instruction prefix for scaled UI amount extension instructions.
+    ///
+    /// See `extension::scaled_ui_amount::instruction::ScaledUiAmountMintInstruction` for
+    /// further details about the extended instructions that share this instruction prefix
+    ScaledUiAmountExtension,
+    /// The common instruction prefix for pausable extension instructions.
+    ///
+    /// See `extension::pausable::instruction::PausableInstruction` for
+    /// further details about the extended instructions that share this instruction prefix
+    PausableExtension,
+    /// The common instruction prefix for token group extension instructions.
+    ///
+    /// See `extension::token_group::instruction::TokenGroupInstruction` for
+    /// further details about the extended instructions that share this instruction prefix
+    TokenGroupExtension,
 }
 impl<'a> TokenInstruction<'a> {
     /// Unpacks a byte buffer into a [TokenInstruction](enum.TokenInstruction.html).
@@ -735,6 +796,16 @@ impl<'a> TokenInstruction<'a> {
             31 => Self::CreateNativeMint,
             32 => Self::InitializeNonTransferableMint,
             33 => Self::InterestBearingMintExtension,
+            34 => {
+                let (delegate, _rest) = Self::unpack_pubkey(rest)?;
+                Self::InitializePermanentDelegate { delegate }
+            }
+            35 => Self::TransferHookExtension,
+            36 => Self::TokenMetadataExtension,
+            37 => Self::CpiGuardExtension,
+            38 => Self::ScaledUiAmountExtension,
+            39 => Self::PausableExtension,
+            40 => Self::TokenGroupExtension,
             _ => return Err(TokenError::InvalidInstruction.into()),
         })
     }
@@ -887,6 +958,28 @@ impl<'a> TokenInstruction<'a> {
             &Self::InterestBearingMintExtension => {
                 buf.push(33);
             }
+            &Self::InitializePermanentDelegate { ref delegate } => {
+                buf.push(34);
+                buf.extend_from_slice(delegate.as_ref());
+            }
+            &Self::TransferHookExtension => {
+                buf.push(35);
+            }
+            &Self::TokenMetadataExtension => {
+                buf.push(36);
+            }
+            &Self::CpiGuardExtension => {
+                buf.push(37);
+            }
+            &Self::ScaledUiAmountExtension => {
+                buf.push(38);
+            }
+            &Self::PausableExtension => {
+                buf.push(39);
+            }
+            &Self::TokenGroupExtension => {
+                buf.push(40);
+            }
         };
         buf
     }
@@ -968,6 +1061,14 @@ pub enum AuthorityType {
     CloseMint,
     /// Authority to set the interest rate
     InterestRate,
+    /// Authority to transfer or burn any tokens for a mint
+    PermanentDelegate,
+    /// Authority to update the transfer hook program id
+    TransferHookProgramId,
+    /// Authority to update the scaled UI amount multiplier
+    ScaledUiAmount,
+    /// Authority to pause or resume minting, burning, and transferring
+    Pause,
 }
 
 impl AuthorityType {
@@ -981,6 +1082,10 @@ impl AuthorityType {
             AuthorityType::WithheldWithdraw => 5,
             AuthorityType::CloseMint => 6,
             AuthorityType::InterestRate => 7,
+            AuthorityType::PermanentDelegate => 8,
+            AuthorityType::TransferHookProgramId => 9,
+            AuthorityType::ScaledUiAmount => 10,
+            AuthorityType::Pause => 11,
         }
     }
 
@@ -994,6 +1099,10 @@ impl AuthorityType {
             5 => Ok(AuthorityType::WithheldWithdraw),
             6 => Ok(AuthorityType::CloseMint),
             7 => Ok(AuthorityType::InterestRate),
+            8 => Ok(AuthorityType::PermanentDelegate),
+            9 => Ok(AuthorityType::TransferHookProgramId),
+            10 => Ok(AuthorityType::ScaledUiAmount),
+            11 => Ok(AuthorityType::Pause),
             _ => Err(TokenError::InvalidInstruction.into()),
         }
     }
@@ -1744,6 +1853,23 @@ pub fn initialize_non_transferable_mint(
     })
 }
 
+/// Creates an `InitializePermanentDelegate` instruction
+pub fn initialize_permanent_delegate(
+    token_program_id: &Pubkey,
+    mint_pubkey: &Pubkey,
+    delegate: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    check_program_account(token_program_id)?;
+    Ok(Instruction {
+        program_id: *token_program_id,
+        accounts: vec![AccountMeta::new(*mint_pubkey, false)],
+        data: TokenInstruction::InitializePermanentDelegate {
+            delegate: *delegate,
+        }
+        .pack(),
+    })
+}
+
 /// Utility function that checks index is between MIN_SIGNERS and MAX_SIGNERS
 pub fn is_valid_signer_index(index: usize) -> bool {
     (MIN_SIGNERS..=MAX_SIGNERS).contains(&index)
@@ -2053,6 +2179,16 @@ mod test {
         assert_eq!(packed, expect);
         let unpacked = TokenInstruction::unpack(&expect).unwrap();
         assert_eq!(unpacked, check);
+
+        let check = TokenInstruction::InitializePermanentDelegate {
+            delegate: Pubkey::new(&[11u8; 32]),
+        };
+        let packed = check.pack();
+        let mut expect = vec![34u8];
+        expect.extend_from_slice(&[11u8; 32]);
+        assert_eq!(packed, expect);
+        let unpacked = TokenInstruction::unpack(&expect).unwrap();
+        assert_eq!(unpacked, check);
     }
 
     macro_rules! test_instruction {
@@ -123,6 +123,12 @@ impl_int_conversion!(PodU64, u64);
 pub struct PodI64([u8; 8]);
 impl_int_conversion!(PodI64, i64);
 
+/// `f64` type that can be used in `Pod`s
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct PodF64([u8; 8]);
+impl_int_conversion!(PodF64, f64);
+
 /// On-chain size of a `Pod` type
 pub fn pod_get_packed_len<T: Pod>() -> usize {
     std::mem::size_of::<T>()
@@ -5,15 +5,24 @@ use {
         check_program_account, cmp_pubkeys,
         error::TokenError,
         extension::{
-            confidential_transfer::{self, ConfidentialTransferAccount},
+            confidential_transfer::{self, ConfidentialMintBurn, ConfidentialTransferAccount},
+            cpi_guard::{self, cpi_guard_enabled, in_cpi, CpiGuard},
             default_account_state::{self, DefaultAccountState},
             immutable_owner::ImmutableOwner,
             interest_bearing_mint::{self, InterestBearingConfig},
             memo_transfer::{self, check_previous_sibling_instruction_is_memo, memo_required},
             mint_close_authority::MintCloseAuthority,
             non_transferable::NonTransferable,
+            pausable::{self, PausableAccount, PausableConfig},
+            permanent_delegate::PermanentDelegate,
             reallocate,
+            scaled_ui_amount::{self, ScaledUiAmountConfig},
+            token_group, token_metadata,
             transfer_fee::{self, TransferFeeAmount, TransferFeeConfig},
+            transfer_hook::{
+                self, interface::invoke_execute, set_transferring, unset_transferring,
+                TransferHook, TransferHookAccount,
+            },
             ExtensionType, StateWithExtensions, StateWithExtensionsMut,
         },
         instruction::{is_valid_signer_index, AuthorityType, TokenInstruction, MAX_SIGNERS},
@@ -282,41 +291,72 @@ impl Processor {
         if source_account.base.amount < amount {
             return Err(TokenError::InsufficientFunds.into());
         }
-        let fee = if let Some((mint_info, expected_decimals)) = expected_mint_info {
-            if !cmp_pubkeys(&source_account.base.mint, mint_info.key) {
-                return Err(TokenError::MintMismatch.into());
-            }
+        let (fee, maybe_permanent_delegate, maybe_transfer_hook_program_id) =
+            if let Some((mint_info, expected_decimals)) = expected_mint_info {
+                if !cmp_pubkeys(&source_account.base.mint, mint_info.key) {
+                    return Err(TokenError::MintMismatch.into());
+                }
 
-            let mint_data = mint_info.try_borrow_data()?;
-            let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+                let mint_data = mint_info.try_borrow_data()?;
+                let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
 
-            if mint.get_extension::<NonTransferable>().is_ok() {
-                return Err(TokenError::NonTransferable.into());
-            }
+                if mint.get_extension::<NonTransferable>().is_ok() {
+                    return Err(TokenError::NonTransferable.into());
+                }
 
-            if expected_decimals != mint.base.decimals {
-                return Err(TokenError::MintDecimalsMismatch.into());
-            }
+                if let Ok(pausable_config) = mint.get_extension::<PausableConfig>() {
+                    pausable_config.check_not_paused()?;
+                }
 
-            if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
-                transfer_fee_config
-                    .calculate_epoch_fee(Clock::get()?.epoch, amount)
-                    .ok_or(TokenError::Overflow)?
-            } else {
-                0
-            }
-        } else {
-            // Transfer fee amount extension exists on the account, but no mint
-            // was provided to calculate the fee, abort
-            if source_account
-                .get_extension_mut::<TransferFeeAmount>()
-                .is_ok()
-            {
-                return Err(TokenError::MintRequiredForTransfer.into());
+                if expected_decimals != mint.base.decimals {
+                    return Err(TokenError::MintDecimalsMismatch.into());
+                }
+
+                let fee = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>()
+                {
+                    transfer_fee_config
+                        .calculate_epoch_fee(Clock::get()?.epoch, amount)
+                        .ok_or(TokenError::Overflow)?
+                } else {
+                    0
+                };
+                let maybe_permanent_delegate = mint
+                    .get_extension::<PermanentDelegate>()
+                    .ok()
+                    .and_then(|extension| Option::<Pubkey>::from(extension.delegate));
+                let maybe_transfer_hook_program_id = mint
+                    .get_extension::<TransferHook>()
+                    .ok()
+                    .and_then(|extension| Option::<Pubkey>::from(extension.program_id));
+                (
+                    fee,
+                    maybe_permanent_delegate,
+                    maybe_transfer_hook_program_id,
+                )
             } else {
-                0
-            }
-        };
+                // Transfer fee amount extension exists on the account, but no mint
+                // was provided to calculate the fee, abort
+                if source_account
+                    .get_extension_mut::<TransferFeeAmount>()
+                    .is_ok()
+                {
+                    return Err(TokenError::MintRequiredForTransfer.into());
+                }
+                // Transfer hook extension exists on the account, but no mint
+                // was provided to figure out the program to invoke, abort
+                if source_account
+                    .get_extension_mut::<TransferHookAccount>()
+                    .is_ok()
+                {
+                    return Err(TokenError::MintRequiredForTransfer.into());
+                }
+                // Pausable extension exists on the account, but no mint
+                // was provided to see if it's paused, abort
+                if source_account.get_extension::<PausableAccount>().is_ok() {
+                    return Err(TokenError::MintRequiredForTransfer.into());
+                }
+                (0, None, None)
+            };
         if let Some(expected_fee) = expected_fee {
             if expected_fee != fee {
                 msg!("Calculated fee {}, received {}", fee, expected_fee);
@@ -325,8 +365,17 @@ impl Processor {
         }
 
         let self_transfer = cmp_pubkeys(source_account_info.key, destination_account_info.key);
-        match source_account.base.delegate {
-            COption::Some(ref delegate) if cmp_pubkeys(authority_info.key, delegate) => {
+        match (source_account.base.delegate, maybe_permanent_delegate) {
+            (_, Some(ref delegate)) if cmp_pubkeys(authority_info.key, delegate) => {
+                Self::validate_owner(
+                    program_id,
+                    delegate,
+                    authority_info,
+                    authority_info_data_len,
+                    account_info_iter.as_slice(),
+                )?
+            }
+            (COption::Some(ref delegate), _) if cmp_pubkeys(authority_info.key, delegate) => {
                 Self::validate_owner(
                     program_id,
                     delegate,
@@ -348,13 +397,19 @@ impl Processor {
                     }
                 }
             }
-            _ => Self::validate_owner(
-                program_id,
-                &source_account.base.owner,
-                authority_info,
-                authority_info_data_len,
-                account_info_iter.as_slice(),
-            )?,
+            _ => {
+                Self::validate_owner(
+                    program_id,
+                    &source_account.base.owner,
+                    authority_info,
+                    authority_info_data_len,
+                    account_info_iter.as_slice(),
+                )?;
+
+                if cpi_guard_enabled(&source_account) && in_cpi() {
+                    return Err(TokenError::CpiGuardTransferBlocked.into());
+                }
+            }
         };
 
         // Revisit this later to see if it's worth adding a check to reduce
@@ -425,6 +480,30 @@ impl Processor {
         source_account.pack_base();
         destination_account.pack_base();
 
+        if let (Some(transfer_hook_program_id), Some((mint_info, _))) =
+            (maybe_transfer_hook_program_id, expected_mint_info)
+        {
+            set_transferring(&mut source_account)?;
+            set_transferring(&mut destination_account)?;
+            drop(source_account_data);
+            drop(destination_account_data);
+
+            // Everything after the authority is passed through, the interface
+            // picks out the accounts required by the transfer hook program
+            invoke_execute(
+                &transfer_hook_program_id,
+                source_account_info.clone(),
+                mint_info.clone(),
+                destination_account_info.clone(),
+                authority_info.clone(),
+                account_info_iter.as_slice(),
+                amount,
+            )?;
+
+            unset_transferring(source_account_info)?;
+            unset_transferring(destination_account_info)?;
+        }
+
         Ok(())
     }
 
@@ -476,6 +555,10 @@ impl Processor {
             account_info_iter.as_slice(),
         )?;
 
+        if cpi_guard_enabled(&source_account) && in_cpi() {
+            return Err(TokenError::CpiGuardApproveBlocked.into());
+        }
+
         source_account.base.delegate = COption::Some(*delegate_info.key);
         source_account.base.delegated_amount = amount;
         source_account.pack_base();
@@ -549,6 +632,10 @@ impl Processor {
                         return Err(TokenError::ImmutableOwner.into());
                     }
 
+                    if cpi_guard_enabled(&account) && in_cpi() {
+                        return Err(TokenError::CpiGuardSetAuthorityBlocked.into());
+                    }
+
                     if let COption::Some(authority) = new_authority {
                         account.base.owner = authority;
                     } else {
@@ -571,6 +658,12 @@ impl Processor {
                         authority_info_data_len,
                         account_info_iter.as_slice(),
                     )?;
+
+                    // a close authority may only be removed, never added or changed, via CPI
+                    if cpi_guard_enabled(&account) && new_authority.is_some() && in_cpi() {
+                        return Err(TokenError::CpiGuardSetAuthorityBlocked.into());
+                    }
+
                     account.base.close_authority = new_authority;
                 }
                 _ => {
@@ -672,6 +765,58 @@ impl Processor {
                     )?;
                     extension.rate_authority = new_authority.try_into()?;
                 }
+                AuthorityType::PermanentDelegate => {
+                    let extension = mint.get_extension_mut::<PermanentDelegate>()?;
+                    let maybe_delegate: Option<Pubkey> = extension.delegate.into();
+                    let delegate = maybe_delegate.ok_or(TokenError::AuthorityTypeNotSupported)?;
+                    Self::validate_owner(
+                        program_id,
+                        &delegate,
+                        authority_info,
+                        authority_info_data_len,
+                        account_info_iter.as_slice(),
+                    )?;
+                    extension.delegate = new_authority.try_into()?;
+                }
+                AuthorityType::TransferHookProgramId => {
+                    let extension = mint.get_extension_mut::<TransferHook>()?;
+                    let maybe_authority: Option<Pubkey> = extension.authority.into();
+                    let authority = maybe_authority.ok_or(TokenError::AuthorityTypeNotSupported)?;
+                    Self::validate_owner(
+                        program_id,
+                        &authority,
+                        authority_info,
+                        authority_info_data_len,
+                        account_info_iter.as_slice(),
+                    )?;
+                    extension.authority = new_authority.try_into()?;
+                }
+                AuthorityType::ScaledUiAmount => {
+                    let extension = mint.get_extension_mut::<ScaledUiAmountConfig>()?;
+                    let maybe_authority: Option<Pubkey> = extension.authority.into();
+                    let authority = maybe_authority.ok_or(TokenError::AuthorityTypeNotSupported)?;
+                    Self::validate_owner(
+                        program_id,
+                        &authority,
+                        authority_info,
+                        authority_info_data_len,
+                        account_info_iter.as_slice(),
+                    )?;
+                    extension.authority = new_authority.try_into()?;
+                }
+                AuthorityType::Pause => {
+                    let extension = mint.get_extension_mut::<PausableConfig>()?;
+                    let maybe_authority: Option<Pubkey> = extension.authority.into();
+                    let authority = maybe_authority.ok_or(TokenError::AuthorityTypeNotSupported)?;
+                    Self::validate_owner(
+                        program_id,
+                        &authority,
+                        authority_info,
+                        authority_info_data_len,
+                        account_info_iter.as_slice(),
+                    )?;
+                    extension.authority = new_authority.try_into()?;
+                }
                 _ => {
                     return Err(TokenError::AuthorityTypeNotSupported.into());
                 }
@@ -729,6 +874,14 @@ impl Processor {
             }
         }
 
+        if mint.get_extension::<ConfidentialMintBurn>().is_ok() {
+            return Err(TokenError::PublicMintBurnDisabled.into());
+        }
+
+        if let Ok(pausable_config) = mint.get_extension::<PausableConfig>() {
+            pausable_config.check_not_paused()?;
+        }
+
         match mint.base.mint_authority {
             COption::Some(mint_authority) => Self::validate_owner(
                 program_id,
@@ -803,12 +956,33 @@ impl Processor {
             }
         }
 
+        if mint.get_extension::<ConfidentialMintBurn>().is_ok() {
+            return Err(TokenError::PublicMintBurnDisabled.into());
+        }
+
+        if let Ok(pausable_config) = mint.get_extension::<PausableConfig>() {
+            pausable_config.check_not_paused()?;
+        }
+
+        let maybe_permanent_delegate = mint
+            .get_extension::<PermanentDelegate>()
+            .ok()
+            .and_then(|extension| Option::<Pubkey>::from(extension.delegate));
         if !source_account
             .base
             .is_owned_by_system_program_or_incinerator()
         {
-            match source_account.base.delegate {
-                COption::Some(ref delegate) if cmp_pubkeys(authority_info.key, delegate) => {
+            match (source_account.base.delegate, maybe_permanent_delegate) {
+                (_, Some(ref delegate)) if cmp_pubkeys(authority_info.key, delegate) => {
+                    Self::validate_owner(
+                        program_id,
+                        delegate,
+                        authority_info,
+                        authority_info_data_len,
+                        account_info_iter.as_slice(),
+                    )?
+                }
+                (COption::Some(ref delegate), _) if cmp_pubkeys(authority_info.key, delegate) => {
                     Self::validate_owner(
                         program_id,
                         delegate,
@@ -900,6 +1074,15 @@ impl Processor {
                 return Err(ProgramError::InvalidAccountData);
             }
 
+            if let Ok(cpi_guard) = source_account.get_extension::<CpiGuard>() {
+                if cpi_guard.lock_cpi.into()
+                    && in_cpi()
+                    && !cmp_pubkeys(destination_account_info.key, &source_account.base.owner)
+                {
+                    return Err(TokenError::CpiGuardCloseAccountBlocked.into());
+                }
+            }
+
             if let Ok(confidential_transfer_state) =
                 source_account.get_extension::<ConfidentialTransferAccount>()
             {
@@ -1079,6 +1262,11 @@ impl Processor {
             extension
                 .amount_to_ui_amount(amount, mint.base.decimals, unix_timestamp)
                 .ok_or(ProgramError::InvalidArgument)?
+        } else if let Ok(extension) = mint.get_extension::<ScaledUiAmountConfig>() {
+            let unix_timestamp = Clock::get()?.unix_timestamp;
+            extension
+                .amount_to_ui_amount(amount, mint.base.decimals, unix_timestamp)
+                .ok_or(ProgramError::InvalidArgument)?
         } else {
             crate::amount_to_ui_amount_string_trimmed(amount, mint.base.decimals)
         };
@@ -1099,6 +1287,9 @@ impl Processor {
         let amount = if let Ok(extension) = mint.get_extension::<InterestBearingConfig>() {
             let unix_timestamp = Clock::get()?.unix_timestamp;
             extension.try_ui_amount_into_amount(ui_amount, mint.base.decimals, unix_timestamp)?
+        } else if let Ok(extension) = mint.get_extension::<ScaledUiAmountConfig>() {
+            let unix_timestamp = Clock::get()?.unix_timestamp;
+            extension.try_ui_amount_into_amount(ui_amount, mint.base.decimals, unix_timestamp)?
         } else {
             crate::try_ui_amount_into_amount(ui_amount.to_string(), mint.base.decimals)?
         };
@@ -1164,6 +1355,22 @@ impl Processor {
         Ok(())
     }
 
+    /// Processes an [InitializePermanentDelegate](enum.TokenInstruction.html) instruction
+    pub fn process_initialize_permanent_delegate(
+        accounts: &[AccountInfo],
+        delegate: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let mint_account_info = next_account_info(account_info_iter)?;
+
+        let mut mint_data = mint_account_info.data.borrow_mut();
+        let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data)?;
+        let extension = mint.init_extension::<PermanentDelegate>(true)?;
+        extension.delegate = Some(*delegate).try_into()?;
+
+        Ok(())
+    }
+
     /// Processes an [Instruction](enum.Instruction.html).
     pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
         let instruction = TokenInstruction::unpack(input)?;
@@ -1324,6 +1531,28 @@ impl Processor {
                     &input[1..],
                 )
             }
+            TokenInstruction::InitializePermanentDelegate { delegate } => {
+                msg!("Instruction: InitializePermanentDelegate");
+                Self::process_initialize_permanent_delegate(accounts, &delegate)
+            }
+            TokenInstruction::TransferHookExtension => {
+                transfer_hook::processor::process_instruction(program_id, accounts, &input[1..])
+            }
+            TokenInstruction::TokenMetadataExtension => {
+                token_metadata::processor::process_instruction(program_id, accounts, &input[1..])
+            }
+            TokenInstruction::CpiGuardExtension => {
+                cpi_guard::processor::process_instruction(program_id, accounts, &input[1..])
+            }
+            TokenInstruction::ScaledUiAmountExtension => {
+                scaled_ui_amount::processor::process_instruction(program_id, accounts, &input[1..])
+            }
+            TokenInstruction::PausableExtension => {
+                pausable::processor::process_instruction(program_id, accounts, &input[1..])
+            }
+            TokenInstruction::TokenGroupExtension => {
+                token_group::processor::process_instruction(program_id, accounts, &input[1..])
+            }
         }
     }
 